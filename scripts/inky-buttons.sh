@@ -0,0 +1,64 @@
+#!/bin/sh
+
+# Reports the buttons of the Inky Impression to the web server and shows the image it answers with
+# Run it on the Pi as a service next to inky-refresh.sh. What each button does is configured in the
+# [web_server.buttons] section of the server's config, see WEB_SERVER.md
+# The buttons are A to D from the top on GPIO 5, 6, 16 and 24, the 13.3" Impression has C on GPIO 25:
+#   BUTTON_PINS="5 6 25 24" ./inky-buttons.sh
+
+SERVER_URL=${1:-"http://localhost:8080"}
+SATURATION=${2:-1.0}
+
+PYTHON_PATH="/home/dietpi/env/bin/python3"
+INKY_SCRIPT="/home/dietpi/Pimoroni/inky/examples/7color/image.py"
+
+export SERVER_URL SATURATION PYTHON_PATH INKY_SCRIPT
+export BUTTON_PINS=${BUTTON_PINS:-"5 6 16 24"}
+
+sudo --preserve-env=SERVER_URL,SATURATION,PYTHON_PATH,INKY_SCRIPT,BUTTON_PINS "${PYTHON_PATH}" - << 'PYTHON'
+import os
+import subprocess
+import urllib.error
+import urllib.request
+
+import gpiod
+import gpiodevice
+from gpiod.line import Bias, Direction, Edge
+
+IMAGE = "/tmp/dashboard-button.png"
+LABELS = ["a", "b", "c", "d"]
+
+pins = [int(pin) for pin in os.environ["BUTTON_PINS"].split()]
+chip = gpiodevice.find_chip_by_platform()
+offsets = [chip.line_offset_from_id(pin) for pin in pins]
+settings = gpiod.LineSettings(
+    direction=Direction.INPUT, bias=Bias.PULL_UP, edge_detection=Edge.FALLING
+)
+request = chip.request_lines(consumer="inky-buttons", config=dict.fromkeys(offsets, settings))
+
+while True:
+    for event in request.read_edge_events():
+        button = LABELS[offsets.index(event.line_offset)]
+        url = f"{os.environ['SERVER_URL']}/api/buttons/{button}"
+        try:
+            with urllib.request.urlopen(urllib.request.Request(url, method="POST")) as response:
+                # 204 No Content: the button does nothing or it's quiet hours in skip mode
+                if response.status != 200:
+                    continue
+                with open(IMAGE, "wb") as image:
+                    image.write(response.read())
+        except urllib.error.URLError as error:
+            print(f"Button {button}: {error}")
+            continue
+        subprocess.run(
+            [
+                os.environ["PYTHON_PATH"],
+                os.environ["INKY_SCRIPT"],
+                "--file",
+                IMAGE,
+                "--saturation",
+                os.environ["SATURATION"],
+            ],
+            check=False,
+        )
+PYTHON
@@ -0,0 +1,36 @@
+#!/bin/sh
+
+# Fetches the dashboard from the web server and shows it on the Inky display
+# /display.png is the layout selected with the display buttons, see inky-buttons.sh
+# Run it on the Pi, e.g. from cron. When the server asks for a full clear (X-Full-Clear: true)
+# the panel is flashed white first, see full_clear_interval in the [web_server] config
+# During quiet hours in skip mode the display is left untouched
+
+SERVER_URL=${1:-"http://localhost:8080"}
+SATURATION=${2:-1.0}
+
+PYTHON_PATH="/home/dietpi/env/bin/python3"
+INKY_SCRIPT="/home/dietpi/Pimoroni/inky/examples/7color/image.py"
+
+IMAGE="/tmp/dashboard.png"
+HEADERS="/tmp/dashboard.headers"
+
+STATUS=$(curl --fail --silent --show-error --dump-header "${HEADERS}" --output "${IMAGE}" --write-out '%{http_code}' "${SERVER_URL}/display.png") || exit 1
+
+# 204 No Content: quiet hours in skip mode, keep the current image
+if [ "${STATUS}" = "204" ]; then
+    exit 0
+fi
+
+if grep -qi '^x-full-clear: true' "${HEADERS}"; then
+    sudo "${PYTHON_PATH}" - << 'PYTHON'
+from inky.auto import auto
+from PIL import Image
+
+display = auto()
+display.set_image(Image.new("RGB", display.resolution, (255, 255, 255)))
+display.show()
+PYTHON
+fi
+
+sudo "${PYTHON_PATH}" "${INKY_SCRIPT}" --file "${IMAGE}" --saturation "${SATURATION}"
@@ -0,0 +1,52 @@
+#!/bin/sh
+
+# Fetches the dashboard from the web server and shows it on an IT8951 based panel (9.7"/10.3"
+# Waveshare e-paper HATs) over SPI, using the IT8951 Python package:
+#   pip install git+https://github.com/GregDMeyer/IT8951
+# Run it on the Pi, e.g. from cron. The server converts the image to the panel's 16 grey levels
+# (driver=it8951). When it asks for a full clear (X-Full-Clear: true) the panel is cleared first.
+# During quiet hours in skip mode the display is left untouched
+#
+# VCOM is printed on the panel's flex cable, e.g. -1.48
+
+SERVER_URL=${1:-"http://localhost:8080"}
+VCOM=${2:-"-2.06"}
+
+PYTHON_PATH="/home/dietpi/env/bin/python3"
+
+IMAGE="/tmp/dashboard.png"
+HEADERS="/tmp/dashboard.headers"
+
+STATUS=$(curl --fail --silent --show-error --dump-header "${HEADERS}" --output "${IMAGE}" --write-out '%{http_code}' "${SERVER_URL}/dashboard.png?driver=it8951") || exit 1
+
+# 204 No Content: quiet hours in skip mode, keep the current image
+if [ "${STATUS}" = "204" ]; then
+    exit 0
+fi
+
+FULL_CLEAR=false
+if grep -qi '^x-full-clear: true' "${HEADERS}"; then
+    FULL_CLEAR=true
+fi
+
+sudo "${PYTHON_PATH}" - "${IMAGE}" "${VCOM}" "${FULL_CLEAR}" << 'PYTHON'
+import sys
+
+from IT8951 import constants
+from IT8951.display import AutoEPDDisplay
+from PIL import Image
+
+image_path, vcom, full_clear = sys.argv[1], float(sys.argv[2]), sys.argv[3] == "true"
+
+display = AutoEPDDisplay(vcom=vcom)
+if full_clear:
+    display.clear()
+
+image = Image.open(image_path).convert("L")
+image.thumbnail((display.width, display.height))
+display.frame_buf.paste(0xFF, box=(0, 0, display.width, display.height))
+display.frame_buf.paste(
+    image, ((display.width - image.width) // 2, (display.height - image.height) // 2)
+)
+display.draw_full(constants.DisplayModes.GC16)
+PYTHON
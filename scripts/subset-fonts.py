@@ -0,0 +1,250 @@
+#!/usr/bin/env python3
+"""Writes the subsets of the Roboto fonts embedded in the binary to static/fonts/embedded.
+
+The embedded fonts are only drawn when static/fonts is missing, so they keep the Latin
+scripts, the punctuation and the symbols the templates use. Other characters fall back to
+the system fonts. Glyph ids are kept, so the layout tables and the variations stay valid,
+the outlines of the glyphs left out are emptied.
+
+Run it from the repository root after updating the fonts in static/fonts:
+
+    python3 scripts/subset-fonts.py
+"""
+
+import os
+import struct
+
+FONTS = [
+    "Roboto-VariableFont_wdth,wght.ttf",
+    "Roboto-Italic-VariableFont_wdth,wght.ttf",
+    "Roboto-Regular-Dashed.ttf",
+]
+
+# Basic Latin, Latin-1, Latin Extended-A and B, the spacing and combining marks,
+# punctuation, super- and subscripts, currency, letterlike symbols, arrows, maths,
+# box drawing, geometric shapes and the ligatures the layout tables substitute
+KEPT_RANGES = [
+    (0x0020, 0x024F),
+    (0x02B0, 0x036F),
+    (0x2000, 0x22FF),
+    (0x2500, 0x25FF),
+    (0xFB00, 0xFB06),
+]
+
+ARG_1_AND_2_ARE_WORDS = 0x0001
+WE_HAVE_A_SCALE = 0x0008
+MORE_COMPONENTS = 0x0020
+WE_HAVE_AN_X_AND_Y_SCALE = 0x0040
+WE_HAVE_A_TWO_BY_TWO = 0x0080
+
+
+def read_tables(data):
+    count = struct.unpack(">H", data[4:6])[0]
+    tables = {}
+    for i in range(count):
+        tag, _, offset, length = struct.unpack(">4sIII", data[12 + 16 * i : 28 + 16 * i])
+        tables[tag.decode("latin-1")] = data[offset : offset + length]
+    return data[0:4], tables
+
+
+def cmap_glyphs(cmap):
+    """Maps the code points of the format 4 and 12 subtables to their glyph ids."""
+    mapping = {}
+    count = struct.unpack(">H", cmap[2:4])[0]
+    for i in range(count):
+        offset = struct.unpack(">I", cmap[8 + 8 * i : 12 + 8 * i])[0]
+        table = cmap[offset:]
+        fmt = struct.unpack(">H", table[0:2])[0]
+        if fmt == 4:
+            segments = struct.unpack(">H", table[6:8])[0] // 2
+            ends = struct.unpack(f">{segments}H", table[14 : 14 + 2 * segments])
+            start_at = 16 + 2 * segments
+            starts = struct.unpack(f">{segments}H", table[start_at : start_at + 2 * segments])
+            delta_at = start_at + 2 * segments
+            deltas = struct.unpack(f">{segments}h", table[delta_at : delta_at + 2 * segments])
+            range_at = delta_at + 2 * segments
+            for s in range(segments):
+                range_offset = struct.unpack(">H", table[range_at + 2 * s : range_at + 2 * s + 2])[0]
+                for code in range(starts[s], ends[s] + 1):
+                    if code == 0xFFFF:
+                        continue
+                    if range_offset == 0:
+                        glyph = (code + deltas[s]) & 0xFFFF
+                    else:
+                        at = range_at + 2 * s + range_offset + 2 * (code - starts[s])
+                        glyph = struct.unpack(">H", table[at : at + 2])[0]
+                        if glyph:
+                            glyph = (glyph + deltas[s]) & 0xFFFF
+                    if glyph:
+                        mapping[code] = glyph
+        elif fmt == 12:
+            groups = struct.unpack(">I", table[12:16])[0]
+            for g in range(groups):
+                start, end, glyph = struct.unpack(">III", table[16 + 12 * g : 28 + 12 * g])
+                for code in range(start, end + 1):
+                    mapping[code] = glyph + code - start
+    return mapping
+
+
+def glyph_offsets(loca, long_offsets, glyph_count):
+    if long_offsets:
+        return list(struct.unpack(f">{glyph_count + 1}I", loca[: 4 * (glyph_count + 1)]))
+    return [o * 2 for o in struct.unpack(f">{glyph_count + 1}H", loca[: 2 * (glyph_count + 1)])]
+
+
+def components(glyph):
+    """Glyph ids of the components of a composite glyph."""
+    if len(glyph) < 10 or struct.unpack(">h", glyph[0:2])[0] >= 0:
+        return []
+    found = []
+    at = 10
+    while True:
+        flags, index = struct.unpack(">HH", glyph[at : at + 4])
+        found.append(index)
+        at += 4 + (4 if flags & ARG_1_AND_2_ARE_WORDS else 2)
+        if flags & WE_HAVE_A_SCALE:
+            at += 2
+        elif flags & WE_HAVE_AN_X_AND_Y_SCALE:
+            at += 4
+        elif flags & WE_HAVE_A_TWO_BY_TWO:
+            at += 8
+        if not flags & MORE_COMPONENTS:
+            return found
+
+
+def kept_glyphs(tables, glyph_count, long_offsets):
+    mapping = cmap_glyphs(tables["cmap"])
+    encoded = set(mapping.values())
+    # Glyphs without a character are alternates and ligatures reached through the
+    # layout tables, they stay
+    kept = {g for g in range(glyph_count) if g not in encoded}
+    kept |= {
+        glyph
+        for code, glyph in mapping.items()
+        if any(start <= code <= end for start, end in KEPT_RANGES)
+    }
+    offsets = glyph_offsets(tables["loca"], long_offsets, glyph_count)
+    pending = list(kept)
+    while pending:
+        glyph = pending.pop()
+        for component in components(tables["glyf"][offsets[glyph] : offsets[glyph + 1]]):
+            if component not in kept:
+                kept.add(component)
+                pending.append(component)
+    return kept
+
+
+def subset_glyf(tables, kept, glyph_count, long_offsets):
+    offsets = glyph_offsets(tables["loca"], long_offsets, glyph_count)
+    glyf = bytearray()
+    loca = [0]
+    for glyph in range(glyph_count):
+        if glyph in kept:
+            glyf += tables["glyf"][offsets[glyph] : offsets[glyph + 1]]
+        loca.append(len(glyf))
+    tables["glyf"] = bytes(glyf)
+    if long_offsets:
+        tables["loca"] = struct.pack(f">{len(loca)}I", *loca)
+    else:
+        tables["loca"] = struct.pack(f">{len(loca)}H", *(o // 2 for o in loca))
+
+
+def subset_gvar(tables, kept):
+    gvar = tables["gvar"]
+    (major, minor, axes, shared_count, shared_at, glyph_count, flags, data_at) = struct.unpack(
+        ">HHHHIHHI", gvar[0:20]
+    )
+    long_offsets = flags & 1
+    if long_offsets:
+        offsets = struct.unpack(f">{glyph_count + 1}I", gvar[20 : 20 + 4 * (glyph_count + 1)])
+    else:
+        offsets = [
+            o * 2 for o in struct.unpack(f">{glyph_count + 1}H", gvar[20 : 20 + 2 * (glyph_count + 1)])
+        ]
+    shared = gvar[shared_at : shared_at + 2 * axes * shared_count]
+    data = bytearray()
+    new_offsets = [0]
+    for glyph in range(glyph_count):
+        if glyph in kept:
+            data += gvar[data_at + offsets[glyph] : data_at + offsets[glyph + 1]]
+        new_offsets.append(len(data))
+    if long_offsets:
+        offset_array = struct.pack(f">{len(new_offsets)}I", *new_offsets)
+    else:
+        offset_array = struct.pack(f">{len(new_offsets)}H", *(o // 2 for o in new_offsets))
+    new_shared_at = 20 + len(offset_array)
+    new_data_at = new_shared_at + len(shared)
+    header = struct.pack(
+        ">HHHHIHHI",
+        major,
+        minor,
+        axes,
+        shared_count,
+        new_shared_at,
+        glyph_count,
+        flags,
+        new_data_at,
+    )
+    tables["gvar"] = header + offset_array + shared + bytes(data)
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def write_font(version, tables):
+    tags = sorted(tables)
+    count = len(tags)
+    power = 1
+    while power * 2 <= count:
+        power *= 2
+    search_range = power * 16
+    header = version + struct.pack(
+        ">HHHH", count, search_range, power.bit_length() - 1, count * 16 - search_range
+    )
+    head = bytearray(tables["head"])
+    head[8:12] = b"\0\0\0\0"
+    tables["head"] = bytes(head)
+
+    directory = bytearray()
+    body = bytearray()
+    offset = 12 + 16 * count
+    for tag in tags:
+        table = tables[tag]
+        directory += struct.pack(
+            ">4sIII", tag.encode("latin-1"), checksum(table), offset + len(body), len(table)
+        )
+        body += table + b"\0" * (-len(table) % 4)
+    font = bytearray(header + directory + body)
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    head_at = 12 + 16 * count + sum(
+        len(tables[tag]) + (-len(tables[tag]) % 4) for tag in tags[: tags.index("head")]
+    )
+    font[head_at + 8 : head_at + 12] = struct.pack(">I", adjustment)
+    return bytes(font)
+
+
+def subset(path):
+    with open(path, "rb") as file:
+        version, tables = read_tables(file.read())
+    glyph_count = struct.unpack(">H", tables["maxp"][4:6])[0]
+    long_offsets = struct.unpack(">h", tables["head"][50:52])[0] == 1
+    kept = kept_glyphs(tables, glyph_count, long_offsets)
+    subset_glyf(tables, kept, glyph_count, long_offsets)
+    if "gvar" in tables:
+        subset_gvar(tables, kept)
+    return write_font(version, tables), len(kept), glyph_count
+
+
+def main():
+    os.makedirs("static/fonts/embedded", exist_ok=True)
+    for name in FONTS:
+        font, kept, glyph_count = subset(os.path.join("static/fonts", name))
+        with open(os.path.join("static/fonts/embedded", name), "wb") as file:
+            file.write(font)
+        print(f"{name}: kept {kept} of {glyph_count} glyphs, {len(font)} bytes")
+
+
+if __name__ == "__main__":
+    main()
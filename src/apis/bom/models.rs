@@ -11,7 +11,7 @@ use serde::Deserialize;
 use crate::{configs::settings::TemperatureUnit, CONFIG};
 
 #[derive(Deserialize, Debug, Copy, PartialOrd, PartialEq, Default, Clone)]
-pub struct RelativeHumidity(pub u16);
+pub struct RelativeHumidity(pub i16);
 
 #[derive(Deserialize, Debug, Copy, PartialOrd, PartialEq, Default, Clone)]
 pub struct HourlyUV(pub u16);
@@ -75,7 +75,7 @@ pub struct Wind {
 
 impl Wind {
     pub fn get_speed(&self) -> u16 {
-        if CONFIG.render_options.use_gust_instead_of_wind {
+        if CONFIG.render_options.wind_display.uses_gust() {
             self.gust_speed_kilometre
         } else {
             self.speed_kilometre
@@ -105,9 +105,21 @@ pub struct Rain {
     pub amount: RainAmount,
     pub chance: Option<u16>,
     // pub chance_of_no_rain_category: Option<String>,
-    // pub precipitation_amount_25_percent_chance: Option<u16>,
-    // pub precipitation_amount_50_percent_chance: Option<u16>,
-    // pub precipitation_amount_75_percent_chance: Option<u16>,
+    pub precipitation_amount_25_percent_chance: Option<u16>,
+    pub precipitation_amount_50_percent_chance: Option<u16>,
+    pub precipitation_amount_75_percent_chance: Option<u16>,
+}
+
+impl Rain {
+    /// BOM gives the amount with an N% chance of being exceeded,
+    /// so a 25% chance of exceedance is the 75th percentile and vice versa.
+    pub fn amount_percentiles(&self) -> crate::domain::models::AmountPercentiles {
+        crate::domain::models::AmountPercentiles {
+            p25: self.precipitation_amount_75_percent_chance,
+            p50: self.precipitation_amount_50_percent_chance,
+            p75: self.precipitation_amount_25_percent_chance,
+        }
+    }
 }
 
 // #[derive(Deserialize, Debug)]
@@ -152,7 +164,7 @@ pub struct DailyEntry {
     #[serde(deserialize_with = "de_temp_celsius_opt")]
     pub temp_min: Option<Temperature>,
     // pub extended_text: Option<String>,
-    // pub icon_descriptor: Option<String>,
+    pub icon_descriptor: Option<String>,
     // pub short_text: Option<String>,
     // pub surf_danger: Option<String>,
     // pub fire_danger: Option<String>,
@@ -181,7 +193,7 @@ pub struct HourlyForecast {
     pub wind: Wind,
     pub relative_humidity: RelativeHumidity,
     pub uv: Option<HourlyUV>,
-    // pub icon_descriptor: String,
+    pub icon_descriptor: Option<String>,
     // pub next_three_hourly_forecast_period: DateTime<Utc>,
     pub time: DateTime<Utc>,
     pub is_night: bool,
@@ -212,3 +224,185 @@ pub struct ErrorDetail {
     // pub status: String,
     pub detail: String,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct ObservationWind {
+    pub speed_kilometre: Option<u16>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ObservationStation {
+    pub name: String,
+}
+
+/// Latest observation of the weather station closest to the location
+#[derive(Deserialize, Debug)]
+pub struct Observation {
+    /// Observed temperatures have a decimal place, the forecasts are whole degrees
+    pub temp: Option<f32>,
+    pub temp_feels_like: Option<f32>,
+    pub wind: Option<ObservationWind>,
+    pub gust: Option<ObservationWind>,
+    pub humidity: Option<i16>,
+    /// Rain in mm since 9am local time, the station's gauge is emptied then
+    pub rain_since_9am: Option<f32>,
+    pub station: Option<ObservationStation>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ObservationsMetadata {
+    pub observation_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ObservationsResponse {
+    pub metadata: ObservationsMetadata,
+    pub data: Observation,
+}
+
+/// Details of the forecast location, only its time zone is used
+#[derive(Deserialize, Debug)]
+pub struct LocationResponse {
+    pub data: LocationData,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LocationData {
+    /// IANA time zone of the location, e.g. "Australia/Melbourne"
+    pub timezone: String,
+}
+
+impl LocationResponse {
+    /// Time zone of the location, `None` when BOM returns one chrono-tz doesn't know
+    pub fn time_zone(&self) -> Option<chrono_tz::Tz> {
+        self.data.timezone.parse().ok()
+    }
+}
+
+impl ObservationsResponse {
+    /// The station's rain gauge reading, `None` when the station has no gauge. The gauge is
+    /// emptied at 9am in the station's `time_zone`, which needn't be the one the device runs in.
+    pub fn rain_gauge_reading(
+        &self,
+        time_zone: chrono_tz::Tz,
+    ) -> Option<crate::domain::models::RainGaugeReading> {
+        use chrono::{NaiveTime, TimeZone};
+
+        let time = self.metadata.observation_time?;
+        let rain_mm = self.data.rain_since_9am?;
+        let local = time.with_timezone(&time_zone);
+        let nine_am = NaiveTime::from_hms_opt(9, 0, 0)?;
+        let emptied_on = if local.time() >= nine_am {
+            local.date_naive()
+        } else {
+            local.date_naive().pred_opt()?
+        };
+        let since = time_zone
+            .from_local_datetime(&emptied_on.and_time(nine_am))
+            .earliest()?
+            .with_timezone(&Utc);
+        Some(crate::domain::models::RainGaugeReading {
+            time,
+            since,
+            rain_mm,
+        })
+    }
+
+    /// Replaces the forecast temperatures, wind and humidity of the hour the observation was
+    /// made in by the observed values. Observations outside the forecast hours are ignored, so a
+    /// cached observation never overwrites a later hour.
+    pub fn merge_into(&self, hourly: &mut [crate::domain::models::HourlyForecast]) {
+        use crate::domain::models::Temperature as DomainTemperature;
+
+        let Some(observed_at) = self.metadata.observation_time else {
+            return;
+        };
+        let Some(hour) = hourly.iter_mut().find(|hour| {
+            hour.time <= observed_at && observed_at < hour.time + chrono::Duration::hours(1)
+        }) else {
+            return;
+        };
+
+        // Keep the unit the forecast was converted to
+        let in_unit = |celsius: f32, unit: TemperatureUnit| match unit {
+            TemperatureUnit::C => DomainTemperature::celsius(celsius),
+            TemperatureUnit::F => DomainTemperature::celsius(celsius).to_fahrenheit(),
+        };
+        let observation = &self.data;
+        if let Some(temp) = observation.temp {
+            hour.temperature = in_unit(temp, hour.temperature.unit);
+        }
+        if let Some(feels_like) = observation.temp_feels_like {
+            hour.apparent_temperature = in_unit(feels_like, hour.apparent_temperature.unit);
+        }
+        if let Some(speed) = observation
+            .wind
+            .as_ref()
+            .and_then(|wind| wind.speed_kilometre)
+        {
+            hour.wind.speed_kmh = speed;
+        }
+        if let Some(gust) = observation
+            .gust
+            .as_ref()
+            .and_then(|gust| gust.speed_kilometre)
+        {
+            hour.wind.gust_speed_kmh = gust;
+        }
+        if let Some(humidity) = observation.humidity {
+            hour.relative_humidity = humidity;
+        }
+    }
+}
+
+/// Type of the marine wind warnings, e.g. a strong wind or gale warning for a coastal water
+pub const MARINE_WIND_WARNING_TYPE: &str = "marine_wind_warning";
+
+/// A warning issued for the location, e.g. a frost or sheep graziers warning
+#[derive(Deserialize, Debug)]
+pub struct BomWarning {
+    /// e.g. "frost_warning" or [`MARINE_WIND_WARNING_TYPE`]
+    #[serde(rename = "type")]
+    pub warning_type: Option<String>,
+    pub title: String,
+    pub phase: Option<String>,
+    pub expiry_time: Option<DateTime<Utc>>,
+}
+
+impl BomWarning {
+    /// Whether the warning is in force at `now`, cancelled and expired warnings aren't
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.phase.as_deref() != Some("cancelled")
+            && self.expiry_time.is_none_or(|expiry| expiry > now)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WarningsResponse {
+    pub data: Vec<BomWarning>,
+}
+
+impl WarningsResponse {
+    /// Warnings in force at `now`, as diagnostics. Cancelled and expired warnings are left out,
+    /// so warnings from a stale cache disappear once they run out.
+    pub fn active_warnings(&self, now: DateTime<Utc>) -> Vec<crate::errors::DashboardError> {
+        self.data
+            .iter()
+            .filter(|warning| warning.is_active(now))
+            .map(|warning| crate::errors::DashboardError::WeatherWarning {
+                details: warning.title.clone(),
+            })
+            .collect()
+    }
+
+    /// Titles of the marine wind warnings in force at `now`, e.g. "Gale Warning for Port
+    /// Phillip", for the sea wind warning of the marine layout
+    pub fn marine_wind_warnings(&self, now: DateTime<Utc>) -> Vec<String> {
+        self.data
+            .iter()
+            .filter(|warning| warning.warning_type.as_deref() == Some(MARINE_WIND_WARNING_TYPE))
+            .filter(|warning| warning.is_active(now))
+            .map(|warning| warning.title.clone())
+            .collect()
+    }
+}
@@ -99,9 +99,21 @@ pub struct Hourly {
     #[serde(rename = "wind_gusts_10m")]
     pub wind_gusts_10m: Vec<f32>,
     #[serde(rename = "relative_humidity_2m")]
-    pub relative_humidity_2m: Vec<u16>,
+    /// Signed, so an impossible negative value is dropped with its hour rather than failing the
+    /// whole response
+    pub relative_humidity_2m: Vec<i16>,
     #[serde(rename = "cloud_cover")]
     pub cloud_cover: Vec<Option<u16>>,
+    /// Convective available potential energy (J/kg)
+    /// Missing from older cached responses and null for models that don't compute it
+    #[serde(default)]
+    pub cape: Vec<Option<f32>>,
+    /// Mean sea level pressure (hPa), missing from older cached responses
+    #[serde(default, rename = "pressure_msl")]
+    pub pressure_msl: Vec<Option<f32>>,
+    /// WMO weather code, missing from older cached responses
+    #[serde(default, rename = "weather_code")]
+    pub weather_code: Vec<Option<u8>>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -193,6 +205,14 @@ impl From<OpenMeteoHourlyResponse> for Vec<crate::domain::models::HourlyForecast
                 let time = hourly_data.time[i];
                 let is_night = response.current.is_day == 0;
                 let cloud_cover = hourly_data.cloud_cover[i];
+                let cape = hourly_data.cape.get(i).copied().flatten();
+                let pressure = hourly_data.pressure_msl.get(i).copied().flatten();
+                let reported_condition = hourly_data
+                    .weather_code
+                    .get(i)
+                    .copied()
+                    .flatten()
+                    .and_then(crate::domain::conditions::Condition::from_wmo_code);
 
                 crate::domain::models::HourlyForecast {
                     time,
@@ -204,12 +224,187 @@ impl From<OpenMeteoHourlyResponse> for Vec<crate::domain::models::HourlyForecast
                     relative_humidity,
                     is_night,
                     cloud_cover,
+                    cape,
+                    pressure,
+                    // Filled in from the separate air quality endpoint when needed
+                    aqi: None,
+                    pm2_5: None,
+                    // Open-Meteo has no smoke/haze codes, smoke comes from the PM2.5 override
+                    obscuration: None,
+                    // Filled in from the ensemble endpoint when the spread band is enabled
+                    temperature_spread: None,
+                    reported_condition,
                 }
             })
             .collect()
     }
 }
 
+/// Response from the Open-Meteo air quality API (separate host from the forecast API)
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenMeteoAirQualityResponse {
+    pub hourly: AirQualityHourly,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct AirQualityHourly {
+    #[serde(deserialize_with = "deserialize_vec_short_datetime")]
+    pub time: Vec<DateTime<Utc>>,
+    #[serde(default)]
+    pub us_aqi: Vec<Option<f32>>,
+    #[serde(default)]
+    pub pm2_5: Vec<Option<f32>>,
+}
+
+impl OpenMeteoAirQualityResponse {
+    /// Copies the hourly AQI and PM2.5 values onto the matching hourly forecasts.
+    pub fn merge_into(&self, hourly_forecasts: &mut [crate::domain::models::HourlyForecast]) {
+        let index_by_time: std::collections::HashMap<DateTime<Utc>, usize> = self
+            .hourly
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| (*time, i))
+            .collect();
+
+        for forecast in hourly_forecasts.iter_mut() {
+            let Some(&i) = index_by_time.get(&forecast.time) else {
+                forecast.aqi = None;
+                forecast.pm2_5 = None;
+                continue;
+            };
+            forecast.aqi = self
+                .hourly
+                .us_aqi
+                .get(i)
+                .copied()
+                .flatten()
+                .map(|aqi| aqi.round() as u16);
+            forecast.pm2_5 = self.hourly.pm2_5.get(i).copied().flatten();
+        }
+    }
+}
+
+/// Hourly snow depth and snowfall from the Open-Meteo forecast API, for the snow report
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenMeteoSnowResponse {
+    pub hourly: SnowHourly,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct SnowHourly {
+    #[serde(deserialize_with = "deserialize_vec_short_datetime")]
+    pub time: Vec<DateTime<Utc>>,
+    /// Snow depth on the ground in metres
+    pub snow_depth: Vec<Option<f32>>,
+    /// Snowfall of the preceding hour in centimetres
+    pub snowfall: Vec<Option<f32>>,
+}
+
+/// Hourly waves and swell from the Open-Meteo Marine API, for the marine layout
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenMeteoMarineResponse {
+    pub hourly: MarineHourly,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct MarineHourly {
+    #[serde(deserialize_with = "deserialize_vec_short_datetime")]
+    pub time: Vec<DateTime<Utc>>,
+    /// Significant height of wind waves and swell combined in metres
+    pub wave_height: Vec<Option<f32>>,
+    /// Significant swell height in metres
+    pub swell_wave_height: Vec<Option<f32>>,
+    /// Swell period in seconds
+    pub swell_wave_period: Vec<Option<f32>>,
+    /// Direction the swell comes from in degrees
+    pub swell_wave_direction: Vec<Option<f32>>,
+}
+
+/// Hourly soil moisture, reference evapotranspiration and rain from the Open-Meteo forecast API,
+/// for the garden indicator
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenMeteoGardenResponse {
+    pub hourly: GardenHourly,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct GardenHourly {
+    #[serde(deserialize_with = "deserialize_vec_short_datetime")]
+    pub time: Vec<DateTime<Utc>>,
+    /// Volumetric soil moisture at 3-9 cm in m³/m³
+    pub soil_moisture_3_to_9cm: Vec<Option<f32>>,
+    /// FAO reference evapotranspiration of the preceding hour in mm
+    pub et0_fao_evapotranspiration: Vec<Option<f32>>,
+    /// Rain of the preceding hour in mm
+    pub precipitation: Vec<Option<f32>>,
+}
+
+/// Response from the Open-Meteo ensemble API
+///
+/// Every ensemble member is returned as its own hourly series
+/// (`temperature_2m`, `temperature_2m_member01`, ...).
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenMeteoEnsembleResponse {
+    pub hourly: EnsembleHourly,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct EnsembleHourly {
+    #[serde(deserialize_with = "deserialize_vec_short_datetime")]
+    pub time: Vec<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub members: std::collections::HashMap<String, Vec<Option<f32>>>,
+}
+
+impl OpenMeteoEnsembleResponse {
+    /// Lowest and highest member temperature (°C) for the given hour index,
+    /// `None` when no member has a value.
+    pub fn spread_at(&self, index: usize) -> Option<(f32, f32)> {
+        self.hourly
+            .members
+            .iter()
+            .filter(|(name, _)| name.starts_with("temperature_2m"))
+            .filter_map(|(_, values)| values.get(index).copied().flatten())
+            .fold(None, |spread, value| match spread {
+                None => Some((value, value)),
+                Some((min, max)) => Some((value.min(min), value.max(max))),
+            })
+    }
+
+    /// Copies the ensemble spread onto the matching hourly forecasts, converted to `unit`.
+    pub fn merge_into(
+        &self,
+        hourly_forecasts: &mut [crate::domain::models::HourlyForecast],
+        unit: crate::configs::settings::TemperatureUnit,
+    ) {
+        use crate::configs::settings::TemperatureUnit;
+        use crate::domain::models::Temperature as DomainTemp;
+
+        let convert = |value: f32| {
+            let temp = DomainTemp::celsius(value);
+            match unit {
+                TemperatureUnit::C => temp,
+                TemperatureUnit::F => temp.to_fahrenheit(),
+            }
+        };
+        let index_by_time: std::collections::HashMap<DateTime<Utc>, usize> = self
+            .hourly
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| (*time, i))
+            .collect();
+
+        for forecast in hourly_forecasts.iter_mut() {
+            forecast.temperature_spread = index_by_time
+                .get(&forecast.time)
+                .and_then(|&i| self.spread_at(i))
+                .map(|(min, max)| (convert(min), convert(max)));
+        }
+    }
+}
+
 impl From<OpenMeteoDailyResponse> for Vec<crate::domain::models::DailyForecast> {
     fn from(response: OpenMeteoDailyResponse) -> Self {
         use crate::domain::models::{Astronomical, Precipitation, Temperature as DomainTemp};
@@ -287,6 +482,7 @@ impl From<OpenMeteoDailyResponse> for Vec<crate::domain::models::DailyForecast>
                     precipitation,
                     astronomical,
                     cloud_cover,
+                    obscuration: None,
                 }
             })
             .collect()
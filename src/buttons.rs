@@ -0,0 +1,42 @@
+//! Layouts cycled by the display buttons
+//!
+//! The layouts are the base configuration followed by each profile, sorted by name. The
+//! selection is kept in memory only, a restarted server shows the base configuration again.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::logger;
+use crate::CONFIG;
+
+/// Index into [`layouts`] of the layout on the display
+static SELECTED_LAYOUT: AtomicUsize = AtomicUsize::new(0);
+
+/// The base configuration as `None`, then the profiles
+pub fn layouts() -> Vec<Option<&'static str>> {
+    std::iter::once(None)
+        .chain(CONFIG.profile_names().into_iter().map(Some))
+        .collect()
+}
+
+/// Profile of the layout on the display, `None` for the base configuration
+pub fn selected_layout() -> Option<&'static str> {
+    let layouts = layouts();
+    layouts[SELECTED_LAYOUT.load(Ordering::Relaxed) % layouts.len()]
+}
+
+/// Switches the display to the layout after the selected one, back to the base configuration
+/// after the last profile, and returns it
+pub fn next_layout() -> Option<&'static str> {
+    let layouts = layouts();
+    let previous = SELECTED_LAYOUT
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |index| {
+            Some((index + 1) % layouts.len())
+        })
+        .unwrap_or_default();
+    let layout = layouts[(previous + 1) % layouts.len()];
+    logger::info(format!(
+        "Display switched to the {} layout",
+        layout.unwrap_or("default")
+    ));
+    layout
+}
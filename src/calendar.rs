@@ -0,0 +1,114 @@
+//! Offline calendar helpers for special dates (holidays, birthdays, ...)
+//!
+//! Special dates are configured as simple rules, no network access or ICS feed is required:
+//!
+//! * `"MM-DD"` - recurring every year (e.g. `"12-25"`)
+//! * `"YYYY-MM-DD"` - a single date (e.g. `"2026-03-14"`)
+//! * `"easter"`, `"easter+N"`, `"easter-N"` - relative to Western Easter Sunday
+//!   (e.g. `"easter-2"` for Good Friday, `"easter+1"` for Easter Monday)
+//!
+//! It also places dates in week rows starting on a configurable weekday, for templates that
+//! render a weekly grid, and numbers the calendar weeks.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+/// A parsed special date rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateRule {
+    /// Same month and day every year
+    Yearly { month: u32, day: u32 },
+    /// One specific calendar date
+    Once(NaiveDate),
+    /// Offset in days from Easter Sunday
+    Easter { offset_days: i64 },
+}
+
+impl DateRule {
+    /// Parses a rule string, returns `None` if the format is not recognised.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim().to_ascii_lowercase();
+
+        if let Some(offset) = rule.strip_prefix("easter") {
+            let offset_days = if offset.is_empty() {
+                0
+            } else {
+                offset.parse::<i64>().ok()?
+            };
+            return Some(DateRule::Easter { offset_days });
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(&rule, "%Y-%m-%d") {
+            return Some(DateRule::Once(date));
+        }
+
+        let (month, day) = rule.split_once('-')?;
+        let month = month.parse::<u32>().ok()?;
+        let day = day.parse::<u32>().ok()?;
+        // 2024 is a leap year, so Feb 29 is accepted
+        NaiveDate::from_ymd_opt(2024, month, day)?;
+        Some(DateRule::Yearly { month, day })
+    }
+
+    /// Returns true if the rule falls on the given date.
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match *self {
+            DateRule::Yearly { month, day } => date.month() == month && date.day() == day,
+            DateRule::Once(once) => date == once,
+            DateRule::Easter { offset_days } => {
+                let Some(easter) = easter_sunday(date.year()) else {
+                    return false;
+                };
+                let shifted = if offset_days >= 0 {
+                    easter.checked_add_days(Days::new(offset_days as u64))
+                } else {
+                    easter.checked_sub_days(Days::new(offset_days.unsigned_abs()))
+                };
+                shifted == Some(date)
+            }
+        }
+    }
+}
+
+/// Computes the date of Western (Gregorian) Easter Sunday for a given year
+/// using the anonymous Gregorian algorithm (Meeus/Jones/Butcher).
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Column of `date` in a week row starting on `first_weekday`, 0 for the first day
+pub fn week_column(date: NaiveDate, first_weekday: Weekday) -> u32 {
+    (date.weekday().num_days_from_monday() + 7 - first_weekday.num_days_from_monday()) % 7
+}
+
+/// First day of the week row that `date` is in
+pub fn week_start(date: NaiveDate, first_weekday: Weekday) -> NaiveDate {
+    date - Days::new(u64::from(week_column(date, first_weekday)))
+}
+
+/// Weekdays in the order of a week row starting on `first_weekday`
+pub fn week_days(first_weekday: Weekday) -> [Weekday; 7] {
+    std::array::from_fn(|offset| (0..offset).fold(first_weekday, |day, _| day.succ()))
+}
+
+/// Week number where week 1 is the one with 1 January and weeks start on `first_weekday`,
+/// the US convention with Sunday
+pub fn week_number_from_jan1(date: NaiveDate, first_weekday: Weekday) -> u32 {
+    let Some(jan1) = NaiveDate::from_ymd_opt(date.year(), 1, 1) else {
+        return 1;
+    };
+    (date.ordinal0() + week_column(jan1, first_weekday)) / 7 + 1
+}
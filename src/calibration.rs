@@ -0,0 +1,116 @@
+//! Palette test card for tuning the `[palette]` config to a panel
+//!
+//! The card shows every ink as a flat swatch labelled with the colour it is configured as,
+//! gradients from white through each ink to black, and text from 8 to 24 px on white and on
+//! black. Photographed next to the labels, the swatches tell how far the configured colours are
+//! from what the panel prints. Where the gradients switch inks shows how rendered colours are
+//! mapped, and the text shows the smallest size that stays readable.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::configs::contrast::INK_NAMES;
+use crate::configs::settings::InkPalette;
+use crate::errors::WeatherEpdError;
+use crate::logger;
+use crate::utils::{convert_png_to_raw, convert_svg_to_png};
+use crate::CONFIG;
+
+/// File name of the card outputs, without the extension
+pub const CALIBRATION_FILE_STEM: &str = "calibration";
+
+/// Inks the dashboard uses, purple isn't one of them
+const INK_COUNT: usize = 7;
+
+/// Sizes of the text samples in pixels
+const TEXT_SIZES: [u32; 6] = [8, 10, 12, 14, 18, 24];
+
+const CARD_WIDTH: u32 = 800;
+const CARD_HEIGHT: u32 = 480;
+
+fn hex([red, green, blue]: [u8; 3]) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+/// Renders the test card for the ink colours of `palette`
+pub fn calibration_card_svg(palette: &InkPalette) -> String {
+    let saturation = palette.saturation;
+    let palette = palette.colours();
+    let mut svg = format!(
+        r#"<svg width="{CARD_WIDTH}" height="{CARD_HEIGHT}" font-family="Roboto, sans-serif" xmlns="http://www.w3.org/2000/svg"><rect width="{CARD_WIDTH}" height="{CARD_HEIGHT}" fill="white"/><text x="15" y="28" font-size="18" font-weight="bold" fill="black">Palette test card, saturation {saturation}</text>"#
+    );
+
+    // Flat swatches of every ink, labelled with the configured colour
+    for (index, (name, &colour)) in INK_NAMES.iter().zip(&palette).take(INK_COUNT).enumerate() {
+        let x = 15 + index * 110;
+        let [red, green, blue] = colour;
+        let _ = write!(
+            svg,
+            r#"<rect x="{x}" y="40" width="100" height="90" fill="{}" stroke="black"/><text x="{x}" y="148" font-size="14" fill="black">{name}</text><text x="{x}" y="164" font-size="12" fill="black">{red}, {green}, {blue}</text>"#,
+            hex(colour)
+        );
+    }
+
+    // White to each ink to black, grey first
+    let gradients = std::iter::once(("grey", [128, 128, 128])).chain(
+        INK_NAMES
+            .into_iter()
+            .zip(palette)
+            .skip(2)
+            .take(INK_COUNT - 2),
+    );
+    for (index, (name, colour)) in gradients.enumerate() {
+        let y = 180 + index * 28;
+        let _ = write!(
+            svg,
+            r#"<defs><linearGradient id="gradient-{name}"><stop offset="0" stop-color="white"/><stop offset="0.5" stop-color="{}"/><stop offset="1" stop-color="black"/></linearGradient></defs><text x="15" y="{}" font-size="14" fill="black">{name}</text><rect x="100" y="{y}" width="685" height="20" fill="url(#gradient-{name})"/>"#,
+            hex(colour),
+            y + 15
+        );
+    }
+
+    // Text sizes, black on white on the left and white on black on the right
+    let _ = write!(
+        svg,
+        r#"<rect x="405" y="350" width="380" height="120" fill="black"/>"#
+    );
+    let mut y = 352;
+    for size in TEXT_SIZES {
+        y += size + 4;
+        for (x, fill) in [(15, "black"), (415, "white")] {
+            let _ = write!(
+                svg,
+                r#"<text x="{x}" y="{y}" font-size="{size}" fill="{fill}">{size}px Weather 23°C</text>"#
+            );
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Writes the test card to `calibration.svg`, `calibration.png` and `calibration.raw` in
+/// `output_dir`, at the configured PNG scale and in the configured display driver's RAW format
+pub fn write_calibration_card(output_dir: &Path) -> Result<(), WeatherEpdError> {
+    fs::create_dir_all(output_dir).map_err(WeatherEpdError::output)?;
+    let output = |extension: &str| -> PathBuf {
+        output_dir.join(format!("{CALIBRATION_FILE_STEM}.{extension}"))
+    };
+    let (svg, png, raw) = (output("svg"), output("png"), output("raw"));
+
+    fs::write(&svg, calibration_card_svg(&CONFIG.palette)).map_err(WeatherEpdError::output)?;
+    convert_svg_to_png(
+        &svg,
+        &png,
+        CONFIG.misc.png_scale_factor,
+        CONFIG.render_options.anti_aliasing,
+    )
+    .map_err(WeatherEpdError::convert)?;
+    convert_png_to_raw(&png, &raw, CONFIG.misc.display_driver).map_err(WeatherEpdError::convert)?;
+
+    for path in [&svg, &png, &raw] {
+        logger::success(format!("Test card saved: {}", path.display()));
+    }
+    Ok(())
+}
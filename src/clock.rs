@@ -3,7 +3,8 @@
 //! This module provides a trait-based abstraction for accessing the current time,
 //! which allows for dependency injection and testing of time-dependent logic.
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
+use std::time::Instant;
 
 /// Trait for accessing the current time
 ///
@@ -25,7 +26,7 @@ use chrono::{DateTime, Local, Utc};
 /// let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 10, 9, 22, 0, 0).unwrap());
 /// let now = clock.now_local();  // Always returns 10 PM Melbourne time
 /// ```
-pub trait Clock {
+pub trait Clock: Send + Sync {
     /// Returns the current local time
     fn now_local(&self) -> DateTime<Local>;
 
@@ -110,6 +111,139 @@ impl Clock for FixedClock {
     }
 }
 
+/// Clock shifted by a fixed offset from another clock
+///
+/// Shows what a render will look like later or looked like earlier, e.g. tonight's render
+/// with an offset of a few hours.
+///
+/// # Examples
+///
+/// ```ignore
+/// let clock = OffsetClock::new(SystemClock, Duration::hours(6));
+/// let tonight = clock.now_local();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetClock<C = SystemClock> {
+    inner: C,
+    offset: Duration,
+}
+
+impl<C: Clock> OffsetClock<C> {
+    /// Create a clock `offset` ahead of `inner`, a negative offset goes back in time
+    pub fn new(inner: C, offset: Duration) -> Self {
+        Self { inner, offset }
+    }
+}
+
+impl<C: Clock> Clock for OffsetClock<C> {
+    fn now_local(&self) -> DateTime<Local> {
+        self.inner.now_local() + self.offset
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.inner.now_utc() + self.offset
+    }
+}
+
+/// Clock running faster than real time from a start time, for demo loops
+///
+/// With a factor of 360 every real second advances the clock by 6 minutes, so a day of
+/// dashboards passes in 4 minutes.
+#[derive(Debug, Clone, Copy)]
+pub struct AcceleratedClock {
+    start: DateTime<Utc>,
+    started_at: Instant,
+    factor: f64,
+}
+
+impl AcceleratedClock {
+    /// Create a clock starting at `start` now, running `factor` times faster than real time
+    pub fn new(start: DateTime<Utc>, factor: f64) -> Self {
+        Self {
+            start,
+            started_at: Instant::now(),
+            factor,
+        }
+    }
+}
+
+impl Clock for AcceleratedClock {
+    fn now_local(&self) -> DateTime<Local> {
+        self.now_utc().with_timezone(&Local)
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        let elapsed = self.started_at.elapsed().as_secs_f64() * self.factor;
+        // A huge factor runs out of dates, the clock stops at the last one
+        Duration::try_milliseconds((elapsed * 1000.0) as i64)
+            .and_then(|elapsed| self.start.checked_add_signed(elapsed))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+/// Longest time offset [`parse_time_offset`] accepts, either way
+const MAX_TIME_OFFSET_DAYS: i64 = 3650;
+
+/// Parses a time offset like `-3h`, `+90m`, `1d` or `2h30m`
+///
+/// Units are `d`, `h`, `m` and `s`, a leading `-` goes back in time. Offsets of more than
+/// 10 years are refused, the clock would leave the range of dates.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(parse_time_offset("-3h"), Ok(Duration::hours(-3)));
+/// ```
+pub fn parse_time_offset(offset: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid time offset '{offset}', expected e.g. -3h, +90m or 1d12h");
+    let (sign, rest) = match offset.trim().strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, offset.trim().trim_start_matches('+')),
+    };
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().map_err(|_| invalid())?;
+        number.clear();
+        let part = match c {
+            'd' => Duration::try_days(value),
+            'h' => Duration::try_hours(value),
+            'm' => Duration::try_minutes(value),
+            's' => Duration::try_seconds(value),
+            _ => return Err(invalid()),
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .filter(|total| total.num_days() <= MAX_TIME_OFFSET_DAYS)
+            .ok_or_else(|| {
+                format!("Time offset '{offset}' is more than {MAX_TIME_OFFSET_DAYS} days")
+            })?;
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(total * sign)
+}
+
+/// Parses the speed-up factor of an [`AcceleratedClock`], a finite number above 0
+pub fn parse_accelerate_factor(factor: &str) -> Result<f64, String> {
+    factor
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|factor| factor.is_finite() && *factor > 0.0)
+        .ok_or_else(|| format!("Invalid factor '{factor}', expected a number above 0, e.g. 120"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,257 @@
+//! Configured colours checked against the display palette
+//!
+//! The display prints every pixel in the closest of its inks, so two colours that differ in the
+//! SVG can still print the same, e.g. "darkred" text on a "maroon" background is invisible on the
+//! display. The check runs at load for the base configuration, every profile and every theme rule.
+//! With `snap_to_palette` the configured colours are replaced by the exact colour of their ink
+//! at load, so large flat areas are not dithered. The inks are the ones calibrated in the
+//! `[palette]` of the same configuration, as the RAW output uses them.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use super::settings::{Colour, ColourContrastCheck, ColourOverrides, Colours, DashboardSettings};
+use crate::logger;
+use crate::utils::closest_palette_index;
+
+/// Names of the inks, in the order of the display palette
+pub const INK_NAMES: [&str; 8] = [
+    "black", "white", "green", "blue", "red", "yellow", "orange", "purple",
+];
+
+/// Ink of the display that a colour prints as, the closest one in a palette such as
+/// [`InkPalette::colours`](super::settings::InkPalette::colours)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClosestInk {
+    index: u8,
+    rgb: [u8; 3],
+}
+
+impl ClosestInk {
+    pub fn from_rgb(rgb: [u8; 3], palette: &[[u8; 3]; 8]) -> Self {
+        let index = closest_palette_index(palette, rgb);
+        Self {
+            index,
+            rgb: palette[index as usize],
+        }
+    }
+
+    /// Ink closest to a "#rgb" or "#rrggbb" colour, `None` for anything else
+    pub fn from_hex(hex: &str, palette: &[[u8; 3]; 8]) -> Option<Self> {
+        let hex = hex.trim();
+        if !hex.starts_with('#') {
+            return None;
+        }
+        Self::from_colour(hex, palette)
+    }
+
+    /// Ink closest to any SVG colour, `None` for colours without one, e.g. "transparent"
+    pub fn from_colour(colour: &str, palette: &[[u8; 3]; 8]) -> Option<Self> {
+        let colour = svgtypes::Color::from_str(colour.trim()).ok()?;
+        (colour.alpha != 0)
+            .then(|| Self::from_rgb([colour.red, colour.green, colour.blue], palette))
+    }
+
+    pub fn name(self) -> &'static str {
+        INK_NAMES[self.index as usize]
+    }
+
+    /// Exact colour of the ink in the palette
+    pub fn rgb(self) -> [u8; 3] {
+        self.rgb
+    }
+
+    /// Exact colour of the ink as "#rrggbb"
+    pub fn hex(self) -> String {
+        let [red, green, blue] = self.rgb();
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    }
+}
+
+/// Name of the ink of `palette` a configured colour prints as, `None` for colours without one
+pub fn ink(colour: &str, palette: &[[u8; 3]; 8]) -> Option<&'static str> {
+    ClosestInk::from_colour(colour, palette).map(ClosestInk::name)
+}
+
+/// `colour` replaced by the exact colour of its ink in `palette`, colours without one are kept
+pub fn snapped(colour: &Colour, palette: &[[u8; 3]; 8]) -> Colour {
+    ClosestInk::from_colour(&colour.to_string(), palette)
+        .and_then(|ink| Colour::try_new(ink.hex()).ok())
+        .unwrap_or_else(|| colour.clone())
+}
+
+fn snap_overrides(overrides: &mut ColourOverrides, palette: &[[u8; 3]; 8]) {
+    for colour in [
+        &mut overrides.background_colour,
+        &mut overrides.text_colour,
+        &mut overrides.x_axis_colour,
+        &mut overrides.y_left_axis_colour,
+        &mut overrides.y_right_axis_colour,
+        &mut overrides.actual_temp_colour,
+        &mut overrides.feels_like_colour,
+        &mut overrides.rain_colour,
+        &mut overrides.weekend_background_colour,
+        &mut overrides.weekend_text_colour,
+        &mut overrides.humidity_colour,
+        &mut overrides.cold_temp_colour,
+        &mut overrides.hot_temp_colour,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *colour = snapped(colour, palette);
+    }
+}
+
+/// Snaps the colours of `settings` and of its theme rules to its calibrated palette. The humidity
+/// bands already take palette colours only.
+pub fn snap_to_palette(settings: &mut DashboardSettings) {
+    let palette = settings.palette.colours();
+    let colours = &mut settings.colours;
+    for colour in [
+        &mut colours.background_colour,
+        &mut colours.text_colour,
+        &mut colours.x_axis_colour,
+        &mut colours.y_left_axis_colour,
+        &mut colours.y_right_axis_colour,
+        &mut colours.actual_temp_colour,
+        &mut colours.feels_like_colour,
+        &mut colours.rain_colour,
+        &mut colours.weekend_background_colour,
+        &mut colours.weekend_text_colour,
+        &mut colours.humidity_colour,
+        &mut colours.cold_temp_colour,
+        &mut colours.hot_temp_colour,
+    ] {
+        *colour = snapped(colour, &palette);
+    }
+    for rule in &mut settings.theme_rules {
+        snap_overrides(&mut rule.colours, &palette);
+    }
+}
+
+/// Two configured colours that print as the same ink
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InkClash {
+    pub first: &'static str,
+    pub second: &'static str,
+    pub ink: &'static str,
+}
+
+impl Display for InkClash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {} both print as {}",
+            self.first, self.second, self.ink
+        )
+    }
+}
+
+/// Clashes between colours that must print differently: everything drawn on the background,
+/// the weekend tiles, the curves among themselves and the humidity bands. The axes may share a
+/// curve's colour on purpose, so they are only checked against the background. The inks are the
+/// ones of `palette`.
+pub fn ink_clashes(colours: &Colours, palette: &[[u8; 3]; 8]) -> Vec<InkClash> {
+    let background = ("background_colour", colours.background_colour.to_string());
+    let text = ("text_colour", colours.text_colour.to_string());
+    let x_axis = ("x_axis_colour", colours.x_axis_colour.to_string());
+    let y_left_axis = ("y_left_axis_colour", colours.y_left_axis_colour.to_string());
+    let y_right_axis = (
+        "y_right_axis_colour",
+        colours.y_right_axis_colour.to_string(),
+    );
+    let actual_temp = ("actual_temp_colour", colours.actual_temp_colour.to_string());
+    let feels_like = ("feels_like_colour", colours.feels_like_colour.to_string());
+    let rain = ("rain_colour", colours.rain_colour.to_string());
+    let humidity = ("humidity_colour", colours.humidity_colour.to_string());
+    let cold_temp = ("cold_temp_colour", colours.cold_temp_colour.to_string());
+    let hot_temp = ("hot_temp_colour", colours.hot_temp_colour.to_string());
+    let weekend_background = (
+        "weekend_background_colour",
+        colours.weekend_background_colour.to_string(),
+    );
+    let weekend_text = (
+        "weekend_text_colour",
+        colours.weekend_text_colour.to_string(),
+    );
+    let humidity_dry = (
+        "humidity_dry_colour",
+        colours.humidity_dry_colour.to_string(),
+    );
+    let humidity_humid = (
+        "humidity_humid_colour",
+        colours.humidity_humid_colour.to_string(),
+    );
+
+    let mut pairs = vec![
+        (&weekend_text, &weekend_background),
+        (&actual_temp, &feels_like),
+        (&actual_temp, &rain),
+        (&feels_like, &rain),
+        (&humidity_dry, &humidity_humid),
+    ];
+    for drawn in [
+        &text,
+        &x_axis,
+        &y_left_axis,
+        &y_right_axis,
+        &actual_temp,
+        &feels_like,
+        &rain,
+        &humidity,
+        &cold_temp,
+        &hot_temp,
+    ] {
+        pairs.push((drawn, &background));
+    }
+
+    pairs
+        .into_iter()
+        .filter_map(|((first, first_colour), (second, second_colour))| {
+            let first_ink = ink(first_colour, palette)?;
+            (Some(first_ink) == ink(second_colour, palette)).then_some(InkClash {
+                first,
+                second,
+                ink: first_ink,
+            })
+        })
+        .collect()
+}
+
+/// Checks the colours of `settings` and of its theme rules as `check` asks, `name` tells the
+/// configuration apart in the messages
+pub fn check_colour_contrast(settings: &DashboardSettings, name: &str) -> Result<(), String> {
+    let check = settings.misc.colour_contrast_check;
+    if check == ColourContrastCheck::Off {
+        return Ok(());
+    }
+
+    let palette = settings.palette.colours();
+    let base = ink_clashes(&settings.colours, &palette);
+    let mut clashes: Vec<String> = base.iter().map(ToString::to_string).collect();
+    for (index, rule) in settings.theme_rules.iter().enumerate() {
+        let themed = settings.colours.with_overrides(&rule.colours);
+        clashes.extend(
+            ink_clashes(&themed, &palette)
+                .iter()
+                .filter(|clash| !base.contains(clash))
+                .map(|clash| format!("{clash} with theme rule {}", index + 1)),
+        );
+    }
+    if clashes.is_empty() {
+        return Ok(());
+    }
+
+    let message = format!(
+        "{name} colours print the same on the display: {}",
+        clashes.join("; ")
+    );
+    match check {
+        ColourContrastCheck::Error => Err(message),
+        _ => {
+            logger::warning(message);
+            Ok(())
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod contrast;
+pub mod profiles;
 pub mod settings;
 pub mod validation;
@@ -0,0 +1,190 @@
+//! Named configuration profiles for the web server
+//!
+//! A profile is defined under `[profiles.<name>]` and overrides parts of the base configuration,
+//! e.g. the location and colours of a display in another room:
+//!
+//! ```toml
+//! [profiles.kitchen.api]
+//! latitude = -33.8727
+//! longitude = 151.2057
+//!
+//! [profiles.kitchen.misc]
+//! template_path = "dashboard-template-5.65f.svg"
+//! ```
+//!
+//! While a profile request is handled, [`crate::CONFIG`] resolves to that profile's settings.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use config::ConfigError;
+use serde_json::Value;
+
+use super::settings::DashboardSettings;
+
+/// Sections a profile may override as a whole
+const PROFILE_SECTIONS: [&str; 5] = [
+    "api",
+    "colours",
+    "render_options",
+    "snow_report",
+    "special_dates",
+];
+/// Keys of the `misc` section a profile may override, the rest are shared by all profiles
+const PROFILE_MISC_KEYS: [&str; 1] = ["template_path"];
+
+#[derive(Debug)]
+pub struct Profile {
+    pub name: String,
+    pub settings: DashboardSettings,
+}
+
+tokio::task_local! {
+    static ACTIVE_PROFILE: &'static Profile;
+}
+
+/// Returns the profile of the request being handled, if any
+pub fn active_profile() -> Option<&'static Profile> {
+    ACTIVE_PROFILE.try_with(|profile| *profile).ok()
+}
+
+/// Runs `future` with `profile` as the active configuration
+pub async fn with_profile<F: Future>(profile: &'static Profile, future: F) -> F::Output {
+    ACTIVE_PROFILE.scope(profile, future).await
+}
+
+/// Builds every profile defined under `profiles` in the merged configuration `root`.
+///
+/// Each profile is the base configuration with the profile's values merged on top.
+pub(crate) fn build_profiles(root: &Value) -> Result<HashMap<String, Profile>, ConfigError> {
+    let Some(definitions) = root.get("profiles").and_then(Value::as_object) else {
+        return Ok(HashMap::new());
+    };
+
+    let mut base = root.clone();
+    if let Some(base) = base.as_object_mut() {
+        base.remove("profiles");
+    }
+
+    definitions
+        .iter()
+        .map(|(name, overrides)| {
+            check_overrides(name, overrides)?;
+
+            let mut merged = base.clone();
+            merge(&mut merged, overrides);
+            let settings: DashboardSettings = serde_json::from_value(merged).map_err(|e| {
+                ConfigError::Message(format!("Profile {name} validation failed: {e}"))
+            })?;
+
+            Ok((
+                name.clone(),
+                Profile {
+                    name: name.clone(),
+                    settings,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn check_overrides(name: &str, overrides: &Value) -> Result<(), ConfigError> {
+    let Some(sections) = overrides.as_object() else {
+        return Err(ConfigError::Message(format!(
+            "Profile {name} must be a table"
+        )));
+    };
+
+    for (section, values) in sections {
+        let allowed = if section == "misc" {
+            values.as_object().is_some_and(|misc| {
+                misc.keys()
+                    .all(|key| PROFILE_MISC_KEYS.contains(&key.as_str()))
+            })
+        } else {
+            PROFILE_SECTIONS.contains(&section.as_str())
+        };
+
+        if !allowed {
+            return Err(ConfigError::Message(format!(
+                "Profile {name} can't override [{section}], profiles may set {}, and misc.{}",
+                PROFILE_SECTIONS.join(", "),
+                PROFILE_MISC_KEYS.join(", misc.")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Deep merges `overrides` into `target`, tables are merged key by key, other values replaced
+fn merge(target: &mut Value, overrides: &Value) {
+    match (target, overrides) {
+        (Value::Object(target), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match target.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, value) => *target = value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn default_config() -> Value {
+        config::Config::builder()
+            .add_source(config::File::with_name("config/default"))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_profile_overrides_are_merged_into_base() {
+        let mut root = default_config();
+        root["profiles"] = json!({
+            "kitchen": {
+                "api": { "latitude": -33.8727, "longitude": 151.2057 },
+                "misc": { "template_path": "dashboard-template-5.65f.svg" }
+            }
+        });
+
+        let profiles = build_profiles(&root).unwrap();
+        let kitchen = &profiles["kitchen"].settings;
+
+        assert_eq!(kitchen.api.latitude.into_inner(), -33.8727);
+        assert_eq!(
+            kitchen.misc.template_path.to_str(),
+            Some("dashboard-template-5.65f.svg")
+        );
+        // Everything else comes from the base configuration
+        assert_eq!(
+            kitchen.misc.generated_svg_name.to_str(),
+            root["misc"]["generated_svg_name"].as_str()
+        );
+    }
+
+    #[test]
+    fn test_profile_cannot_override_shared_settings() {
+        let mut root = default_config();
+        root["profiles"] = json!({ "bad": { "misc": { "weather_data_cache_path": "/tmp" } } });
+
+        assert!(build_profiles(&root).is_err());
+    }
+
+    #[test]
+    fn test_invalid_profile_values_are_rejected() {
+        let mut root = default_config();
+        root["profiles"] = json!({ "bad": { "api": { "latitude": 200.0 } } });
+
+        assert!(build_profiles(&root).is_err());
+    }
+}
@@ -1,7 +1,19 @@
+use super::contrast;
+use super::profiles::{build_profiles, Profile};
 use super::validation::*;
+use crate::calendar;
+use crate::display_options::{DisplayDriver, Dither, Palette};
+use crate::domain::conditions::Condition;
+use crate::errors::{DiagnosticKind, DiagnosticPriority};
+use crate::output_names;
+use crate::platform;
+use crate::utils::{PALETTE_7COLOR, SATURATED_PALETTE};
+use crate::weather::icons::load_icon_overrides;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, Timelike, Weekday};
+use contrast::INK_NAMES;
 use nutype::nutype;
-use serde::Deserialize;
-use std::{env, fmt, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, env, fmt, path::PathBuf};
 use strum_macros::Display;
 use url::Url;
 
@@ -9,14 +21,29 @@ use config::{Config, ConfigError, Environment, File};
 const CONFIG_DIR: &str = "./config";
 const DEFAULT_CONFIG_NAME: &str = "default";
 
-#[derive(Debug, Deserialize, PartialOrd, PartialEq, Clone, Copy, Display)]
+/// Grid cell Open-Meteo takes the forecast from
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CellSelection {
+    /// A nearby cell on land with an elevation close to the location's
+    #[default]
+    Land,
+    /// A nearby cell over water, for coastal and marine locations
+    Sea,
+    /// The closest cell, whatever its surface
+    Nearest,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialOrd, PartialEq, Clone, Copy, Display)]
 #[serde(rename_all = "snake_case")]
 pub enum Providers {
     Bom,
     OpenMeteo,
 }
 
-#[derive(Debug, Deserialize, PartialOrd, PartialEq, Clone, Copy, Display)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize, Serialize, PartialOrd, PartialEq, Clone, Copy, Display)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum TemperatureUnit {
     #[strum(serialize = "C")]
@@ -38,6 +65,143 @@ pub enum WindSpeedUnit {
     Knots,
 }
 
+/// Unit of the swell and wave heights of the marine layout
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Display)]
+pub enum WaveHeightUnit {
+    #[serde(rename = "m")]
+    #[strum(serialize = "m")]
+    Metres,
+    #[serde(rename = "ft")]
+    #[strum(serialize = "ft")]
+    Feet,
+}
+
+impl WaveHeightUnit {
+    /// `metres` in this unit
+    pub fn convert(self, metres: f32) -> f32 {
+        match self {
+            Self::Metres => metres,
+            Self::Feet => metres / 0.3048,
+        }
+    }
+}
+
+/// Which wind speeds the current conditions and the Max24h table show
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum WindDisplay {
+    /// Sustained (mean) wind speed
+    #[strum(serialize = "sustained")]
+    Sustained,
+    /// Gust speed
+    #[strum(serialize = "gust")]
+    Gust,
+    /// Sustained and gust speed, e.g. "18 / 35"
+    #[default]
+    #[strum(serialize = "both")]
+    Both,
+}
+
+impl WindDisplay {
+    /// Whether single speed uses, e.g. the wind icon, follow the gust speed
+    pub fn uses_gust(self) -> bool {
+        self == WindDisplay::Gust
+    }
+
+    /// The wind display with the replaced `use_gust_instead_of_wind` option, `true` still shows
+    /// only the gust speed
+    pub fn with_use_gust_instead_of_wind(self, use_gust_instead_of_wind: Option<bool>) -> Self {
+        match use_gust_instead_of_wind {
+            Some(true) => WindDisplay::Gust,
+            _ => self,
+        }
+    }
+}
+
+/// How the calendar weeks are numbered
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum WeekNumbering {
+    /// ISO 8601, weeks start on Monday and week 1 has the first Thursday of the year
+    #[default]
+    #[strum(serialize = "iso")]
+    Iso,
+    /// Week 1 has 1 January and weeks start on `first_weekday`, e.g. in the US
+    #[strum(serialize = "jan1")]
+    Jan1,
+}
+
+impl WeekNumbering {
+    /// Calendar week of `date`
+    pub fn week_number(self, date: NaiveDate, first_weekday: Weekday) -> u32 {
+        match self {
+            WeekNumbering::Iso => date.iso_week().week(),
+            WeekNumbering::Jan1 => calendar::week_number_from_jan1(date, first_weekday),
+        }
+    }
+}
+
+/// What happens when two configured colours print as the same ink on the display
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum ColourContrastCheck {
+    #[strum(serialize = "off")]
+    Off,
+    /// Log a warning and render anyway
+    #[default]
+    #[strum(serialize = "warn")]
+    Warn,
+    /// Refuse to load the configuration
+    #[strum(serialize = "error")]
+    Error,
+}
+
+/// Metrics that can be shown as rows of the Now/Max24h table
+#[derive(Debug, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum MaxTableMetric {
+    #[strum(serialize = "UV")]
+    Uv,
+    #[strum(serialize = "Wind")]
+    Wind,
+    #[strum(serialize = "Humidity")]
+    Humidity,
+    #[strum(serialize = "Pressure")]
+    Pressure,
+    #[strum(serialize = "Rain Rate")]
+    RainRate,
+    #[strum(serialize = "AQI")]
+    Aqi,
+    /// Rain fallen so far today in the Now column and still expected today in the Max24h column
+    #[strum(serialize = "Rain Today")]
+    RainToday,
+}
+
+/// How a Max24h value is marked when the maximum falls on tomorrow's part of the window
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum TomorrowMaxMarker {
+    /// Italic value (the original convention)
+    #[default]
+    #[strum(serialize = "italic")]
+    Italic,
+    /// Small "(tmrw)" label under the value
+    #[strum(serialize = "suffix")]
+    Suffix,
+    /// Superscript "*" next to the value
+    #[strum(serialize = "superscript")]
+    Superscript,
+}
+
+/// The template has room for three table rows
+pub const MAX_TABLE_ROW_COUNT: usize = 3;
+
+#[nutype(
+    validate(predicate = |rows| !rows.is_empty() && rows.len() <= MAX_TABLE_ROW_COUNT),
+    derive(Debug, Deserialize, PartialEq, Clone, AsRef)
+)]
+pub struct MaxTableRows(Vec<MaxTableMetric>);
+
 #[nutype(
     sanitize(trim),
     validate(with = is_valid_colour, error = ValidationError),
@@ -51,6 +215,34 @@ impl fmt::Display for Colour {
     }
 }
 
+/// Colour of the display palette, see [`is_palette_colour`]
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(with = is_palette_colour, error = ValidationError),
+    derive(Debug, Deserialize, PartialEq, Clone)
+)]
+pub struct PaletteColour(String);
+
+impl fmt::Display for PaletteColour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.clone().into_inner())
+    }
+}
+
+/// Relative humidity (%) below which the air counts as dry and above which as humid
+#[nutype(
+    validate(predicate = |range: &(u16, u16)| range.0 < range.1 && range.1 <= 100),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy)
+)]
+pub struct HumidityComfortRange((u16, u16));
+
+/// Max temperature (°C) below which a day counts as cold and above which as hot
+#[nutype(
+    validate(predicate = |range: &(f32, f32)| range.0 < range.1),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy)
+)]
+pub struct TempColourRange((f32, f32));
+
 #[nutype(
     sanitize(trim, lowercase),
     validate(len_char_min = 6, len_char_max = 6),
@@ -64,6 +256,32 @@ impl fmt::Display for GeoHash {
     }
 }
 
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(with = is_valid_open_meteo_model, error = ValidationError),
+    derive(Debug, Deserialize, PartialEq, Clone, AsRef)
+)]
+pub struct OpenMeteoModel(String);
+
+impl fmt::Display for OpenMeteoModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+/// Days before today in the Open-Meteo forecasts, the API serves up to 92
+#[nutype(
+    validate(less_or_equal = 92),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy, AsRef)
+)]
+pub struct PastDays(u8);
+
+impl fmt::Display for PastDays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
 #[nutype(
     sanitize(),
     validate(greater_or_equal = 0),
@@ -103,6 +321,125 @@ impl fmt::Display for Latitude {
     }
 }
 
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(with = is_valid_special_date_rule, error = ValidationError),
+    derive(Debug, Deserialize, PartialEq, Clone, AsRef)
+)]
+pub struct SpecialDateRule(String);
+
+impl SpecialDateRule {
+    /// Parsed form of the rule, validation guarantees this always succeeds.
+    pub fn rule(&self) -> crate::calendar::DateRule {
+        crate::calendar::DateRule::parse(self.as_ref())
+            .expect("special date rule is validated on load")
+    }
+}
+
+impl fmt::Display for SpecialDateRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.clone().into_inner())
+    }
+}
+
+/// A time of day written as `"HH:MM"`
+#[nutype(
+    sanitize(trim),
+    validate(with = is_valid_time_of_day, error = ValidationError),
+    derive(Debug, Deserialize, PartialEq, Clone, AsRef)
+)]
+pub struct TimeOfDay(String);
+
+impl TimeOfDay {
+    /// Parsed form of the time, validation guarantees this always succeeds.
+    pub fn time(&self) -> NaiveTime {
+        NaiveTime::parse_from_str(self.as_ref(), "%H:%M").expect("time of day is validated on load")
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.clone().into_inner())
+    }
+}
+
+/// Daily time window highlighted on the hourly graph, e.g. the ride to work
+#[derive(Debug, Deserialize, Clone)]
+pub struct CommuteWindow {
+    pub start: TimeOfDay,
+    /// End of the window, after `start` on the same day
+    pub end: TimeOfDay,
+}
+
+/// Most commute windows the graph has room for
+pub const MAX_COMMUTE_WINDOWS: usize = 2;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpecialDate {
+    pub date: SpecialDateRule,
+    pub label: String,
+}
+
+#[nutype(
+    validate(greater_or_equal = 100.0, less_or_equal = 4000.0),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy, AsRef)
+)]
+pub struct GraphDimension(f32);
+
+impl fmt::Display for GraphDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+/// Hours shown by the hourly graph, 24 or 48. The graph has 24 points, so a 48 hour window is
+/// drawn at 2-hour resolution.
+#[nutype(
+    validate(predicate = |hours| *hours == 24 || *hours == 48),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy, AsRef)
+)]
+pub struct GraphWindowHours(u16);
+
+impl GraphWindowHours {
+    /// Forecast hours merged into each point of the graph
+    pub fn hours_per_point(self) -> u16 {
+        self.into_inner() / 24
+    }
+}
+
+impl fmt::Display for GraphWindowHours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+/// Locale of the numbers on the dashboard, e.g. `en-AU` or `de`, picking the decimal separator
+/// and the unit placement
+#[nutype(
+    sanitize(trim),
+    validate(with = is_valid_locale, error = ValidationError),
+    derive(Debug, Deserialize, PartialEq, Clone, AsRef)
+)]
+pub struct Locale(String);
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+#[nutype(
+    validate(less_or_equal = 23),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy, AsRef)
+)]
+pub struct HourOfDay(u8);
+
+impl fmt::Display for HourOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:00", self.into_inner())
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Release {
     pub release_info_url: Url,
@@ -115,6 +452,19 @@ pub struct Api {
     pub provider: Providers,
     pub longitude: Longitude,
     pub latitude: Latitude,
+    /// Requests per provider per day, beyond which cached data is used until the next day
+    #[serde(default)]
+    pub daily_request_budget: Option<u32>,
+    /// BOM only: replace the current hour's forecast by the nearest station's observation
+    pub bom_observations: bool,
+    /// BOM only: show the warnings in force for the location with the diagnostics
+    pub bom_warnings: bool,
+    /// Open-Meteo only: weather model of the forecasts, `best_match` lets Open-Meteo choose
+    pub open_meteo_model: OpenMeteoModel,
+    /// Open-Meteo only: grid cell the forecasts are taken from
+    pub open_meteo_cell_selection: CellSelection,
+    /// Open-Meteo only: days before today included in the forecasts
+    pub open_meteo_past_days: PastDays,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -127,6 +477,139 @@ pub struct Colours {
     pub actual_temp_colour: Colour,
     pub feels_like_colour: Colour,
     pub rain_colour: Colour,
+    pub weekend_background_colour: Colour,
+    pub weekend_text_colour: Colour,
+    pub humidity_colour: Colour,
+    pub humidity_dry_colour: PaletteColour,
+    pub humidity_humid_colour: PaletteColour,
+    /// Max temperature of the daily tiles below `temp_colour_range`
+    pub cold_temp_colour: Colour,
+    /// Max temperature of the daily tiles above `temp_colour_range`
+    pub hot_temp_colour: Colour,
+    /// Replace every colour by the exact colour of the ink it prints as, see [`contrast::ClosestInk`]
+    #[serde(default)]
+    pub snap_to_palette: bool,
+}
+
+/// Colours a theme rule changes, unset colours keep the `[colours]` value
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ColourOverrides {
+    pub background_colour: Option<Colour>,
+    pub text_colour: Option<Colour>,
+    pub x_axis_colour: Option<Colour>,
+    pub y_left_axis_colour: Option<Colour>,
+    pub y_right_axis_colour: Option<Colour>,
+    pub actual_temp_colour: Option<Colour>,
+    pub feels_like_colour: Option<Colour>,
+    pub rain_colour: Option<Colour>,
+    pub weekend_background_colour: Option<Colour>,
+    pub weekend_text_colour: Option<Colour>,
+    pub humidity_colour: Option<Colour>,
+    pub humidity_dry_colour: Option<PaletteColour>,
+    pub humidity_humid_colour: Option<PaletteColour>,
+    pub cold_temp_colour: Option<Colour>,
+    pub hot_temp_colour: Option<Colour>,
+}
+
+impl Colours {
+    /// These colours with the colours set in `overrides` replaced
+    pub fn with_overrides(&self, overrides: &ColourOverrides) -> Colours {
+        fn pick<T: Clone>(value: &Option<T>, base: &T) -> T {
+            value.clone().unwrap_or(base.clone())
+        }
+        Colours {
+            background_colour: pick(&overrides.background_colour, &self.background_colour),
+            text_colour: pick(&overrides.text_colour, &self.text_colour),
+            x_axis_colour: pick(&overrides.x_axis_colour, &self.x_axis_colour),
+            y_left_axis_colour: pick(&overrides.y_left_axis_colour, &self.y_left_axis_colour),
+            y_right_axis_colour: pick(&overrides.y_right_axis_colour, &self.y_right_axis_colour),
+            actual_temp_colour: pick(&overrides.actual_temp_colour, &self.actual_temp_colour),
+            feels_like_colour: pick(&overrides.feels_like_colour, &self.feels_like_colour),
+            rain_colour: pick(&overrides.rain_colour, &self.rain_colour),
+            weekend_background_colour: pick(
+                &overrides.weekend_background_colour,
+                &self.weekend_background_colour,
+            ),
+            weekend_text_colour: pick(&overrides.weekend_text_colour, &self.weekend_text_colour),
+            humidity_colour: pick(&overrides.humidity_colour, &self.humidity_colour),
+            humidity_dry_colour: pick(&overrides.humidity_dry_colour, &self.humidity_dry_colour),
+            humidity_humid_colour: pick(
+                &overrides.humidity_humid_colour,
+                &self.humidity_humid_colour,
+            ),
+            cold_temp_colour: pick(&overrides.cold_temp_colour, &self.cold_temp_colour),
+            hot_temp_colour: pick(&overrides.hot_temp_colour, &self.hot_temp_colour),
+            snap_to_palette: self.snap_to_palette,
+        }
+    }
+}
+
+/// How far the ink colours are blended towards the pure colours the inks aim for, from 0 to 1
+#[nutype(
+    validate(greater_or_equal = 0.0, less_or_equal = 1.0),
+    derive(Debug, Deserialize, PartialEq, Clone, Copy, AsRef)
+)]
+pub struct Saturation(f32);
+
+impl fmt::Display for Saturation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+/// Colours the display's inks print as, rendered colours are mapped to the closest ink in the
+/// RAW output. Tune them with the `calibrate` test card.
+#[derive(Debug, Deserialize, Clone)]
+pub struct InkPalette {
+    pub black: [u8; 3],
+    pub white: [u8; 3],
+    pub green: [u8; 3],
+    pub blue: [u8; 3],
+    pub red: [u8; 3],
+    pub yellow: [u8; 3],
+    pub orange: [u8; 3],
+    /// 0 keeps the colours above, 1 uses the pure colours of the inks
+    pub saturation: Saturation,
+}
+
+impl InkPalette {
+    /// The ink colours after saturation, in the order of the display palette
+    pub fn colours(&self) -> [[u8; 3]; 8] {
+        let measured = [
+            self.black,
+            self.white,
+            self.green,
+            self.blue,
+            self.red,
+            self.yellow,
+            self.orange,
+            PALETTE_7COLOR[7],
+        ];
+        let saturation = self.saturation.into_inner();
+        std::array::from_fn(|index| {
+            std::array::from_fn(|channel| {
+                let measured = f32::from(measured[index][channel]);
+                let saturated = f32::from(SATURATED_PALETTE[index][channel]);
+                (measured + (saturated - measured) * saturation).round() as u8
+            })
+        })
+    }
+}
+
+/// Colours that apply when the weather matches, the first matching rule wins
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThemeRule {
+    /// Current conditions the rule applies to, none matches any condition
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    /// Lowest forecast high of today the rule applies to, in the configured temperature unit
+    #[serde(default)]
+    pub min_temperature: Option<f32>,
+    /// Forecast high of today from which the rule no longer applies
+    #[serde(default)]
+    pub max_temperature: Option<f32>,
+    pub colours: ColourOverrides,
 }
 
 // TODO: rename the fields to indicate if it's a path or a name
@@ -137,19 +620,263 @@ pub struct Misc {
     pub generated_svg_name: PathBuf,
     pub generated_png_name: PathBuf,
     pub generated_raw_name: PathBuf,
+    /// Outputs named with placeholders older than this are removed after a run, 0 keeps them
+    pub output_retention_days: u32,
     pub svg_icons_directory: PathBuf,
     pub png_scale_factor: f32,
+    #[serde(default)]
+    pub icon_overrides_path: Option<PathBuf>,
+    /// Icons replaced by the `icon_overrides_path` file, by icon name without `.svg`, read when
+    /// the configuration loads
+    #[serde(skip)]
+    pub icon_overrides: HashMap<String, PathBuf>,
+    /// Dashboard variant
+    #[serde(default)]
+    pub layout: Layout,
+    /// Outputs aren't written with less free disk space than this, 0 disables the check
+    pub min_free_disk_space_mb: u64,
+    /// Width and height of the display in pixels, the RAW output must have exactly this size
+    #[serde(default)]
+    pub display_resolution: Option<(u32, u32)>,
+    /// Panel family the RAW output is written for
+    pub display_driver: DisplayDriver,
+    /// How far the pixels that changed since the previous frame are pushed past their new colour
+    /// against ghosting, from 0 (off) to 1, see [`crate::ghosting`]
+    pub ghosting_compensation: f32,
+    /// Check for configured colours that print as the same ink on the display
+    pub colour_contrast_check: ColourContrastCheck,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct RenderOptions {
     pub temp_unit: TemperatureUnit,
     pub wind_speed_unit: WindSpeedUnit,
+    pub wave_height_unit: WaveHeightUnit,
     pub date_format: String,
     pub time_format: String,
+    pub locale: Locale,
     pub use_moon_phase_instead_of_clear_night: bool,
     pub x_axis_always_at_min: bool,
-    pub use_gust_instead_of_wind: bool,
+    pub wind_display: WindDisplay,
+    /// Replaced by `wind_display`, see [`WindDisplay::with_use_gust_instead_of_wind`]
+    #[serde(default)]
+    pub use_gust_instead_of_wind: Option<bool>,
+    pub lightning_cape_threshold: f32,
+    pub highlight_weekend: bool,
+    pub weekend_days: Vec<Weekday>,
+    /// First day of a week row in weekly-grid templates
+    pub first_weekday: Weekday,
+    pub week_numbering: WeekNumbering,
+    pub show_sun_table: bool,
+    pub show_rain_uncertainty_band: bool,
+    pub show_temperature_spread: bool,
+    pub show_humidity_curve: bool,
+    /// Hatched dry and humid zones behind the humidity curve
+    pub show_humidity_comfort_bands: bool,
+    /// Hourly temperature shape of each day under its daily tile
+    pub show_daily_sparklines: bool,
+    pub humidity_comfort_range: HumidityComfortRange,
+    /// Daily tiles colour their max temperature by these bands
+    pub temp_colour_range: TempColourRange,
+    pub ensemble_model: String,
+    pub rain_countdown_chance_threshold: u16,
+    pub rain_countdown_horizon_minutes: i64,
+    pub max_table_rows: MaxTableRows,
+    pub graph_width: GraphDimension,
+    pub graph_height: GraphDimension,
+    /// Hours shown by the hourly graph, the Now/Max24h table and the rain total keep 24 hours
+    pub graph_window_hours: GraphWindowHours,
+    /// Anti-aliased edges when rasterizing, off gives crisp edges in pure palette colours
+    pub anti_aliasing: bool,
+    /// Rasterize the icons once per size and reuse them across renders
+    pub icon_cache: bool,
+    /// Outline in the background colour behind the graph labels
+    pub text_halo: bool,
+    pub text_halo_width: f32,
+    #[serde(default)]
+    pub smoke_pm2_5_threshold: Option<f32>,
+    #[serde(default)]
+    pub tomorrow_max_markers: HashMap<MaxTableMetric, TomorrowMaxMarker>,
+}
+
+impl RenderOptions {
+    /// Marker for a metric's Max24h value when it comes from tomorrow, italic unless configured
+    pub fn tomorrow_max_marker(&self, metric: MaxTableMetric) -> TomorrowMaxMarker {
+        self.tomorrow_max_markers
+            .get(&metric)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// External image, e.g. a webcam, embedded in a template region on every render
+#[derive(Debug, Deserialize)]
+pub struct ImagePanel {
+    /// No URL disables the panel
+    #[serde(default)]
+    pub url: Option<Url>,
+    pub width: u32,
+    pub height: u32,
+    pub palette: Palette,
+    pub dither: Dither,
+}
+
+/// Analog clock face showing the render time, in template user units
+#[derive(Debug, Deserialize)]
+pub struct ClockFace {
+    pub enabled: bool,
+    pub x: f32,
+    pub y: f32,
+    /// Width and height of the face
+    pub size: f32,
+}
+
+/// Diagnostic warning icons of the dashboard
+#[derive(Debug, Deserialize)]
+pub struct Diagnostics {
+    pub layout: DiagnosticsLayout,
+    /// Priorities overriding the default of a diagnostic kind, they decide the message shown and
+    /// the order of the icons
+    #[serde(default)]
+    pub priorities: HashMap<DiagnosticKind, DiagnosticPriority>,
+}
+
+/// Cascade of diagnostic icons in the top left corner, in template user units. The icon of the
+/// highest priority diagnostic is at `x`,`y` in front, each further icon is shifted by the
+/// offsets behind the one before.
+#[derive(Debug, Deserialize)]
+pub struct DiagnosticsLayout {
+    pub x: f32,
+    pub y: f32,
+    /// Width and height of each icon
+    pub icon_size: f32,
+    pub x_offset: f32,
+    pub y_offset: f32,
+}
+
+/// Indoor temperature and humidity compared with outdoors, in template user units.
+///
+/// The reading comes from a JSON feed, e.g. a Home Assistant entity, or from a JSON file a local
+/// sensor script writes. The feed is used when both are set.
+#[derive(Debug, Deserialize)]
+pub struct Indoor {
+    pub enabled: bool,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub url: Option<Url>,
+    /// Bearer token sent with the feed request, e.g. a Home Assistant long-lived access token
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// JSON pointers to the temperature, in the configured unit, and the relative humidity
+    pub temperature_pointer: String,
+    pub humidity_pointer: String,
+    pub comfort: ComfortRules,
+}
+
+/// When opening or closing the windows is suggested
+#[derive(Debug, Deserialize)]
+pub struct ComfortRules {
+    /// Comfortable indoor temperature range in the configured unit
+    pub min_temperature: f32,
+    pub max_temperature: f32,
+    /// Relative humidity (%) above which airing out is suggested
+    pub max_humidity: f32,
+    /// How much better outdoors has to be before opening the windows is suggested
+    pub temperature_delta: f32,
+    pub humidity_delta: f32,
+}
+
+/// Two locations side by side instead of the template, the configured location on the left and
+/// a profile's on the right
+#[derive(Debug, Deserialize)]
+pub struct Comparison {
+    pub enabled: bool,
+    /// Profile whose location is shown on the right, see `[profiles]`
+    pub profile: String,
+    /// Column headings, an empty `other_label` shows the profile name
+    pub home_label: String,
+    pub other_label: String,
+    /// Daily rows under each location's current conditions
+    pub days: usize,
+}
+
+/// Snow depth, recent snowfall and lift status for alpine locations, in template user units
+#[derive(Debug, Deserialize)]
+pub struct SnowReport {
+    pub enabled: bool,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// JSON feed of the ski field's lift status, no URL hides the lift line
+    #[serde(default)]
+    pub lift_status_url: Option<Url>,
+    /// JSON pointers to the number of open lifts and the number of lifts in the feed
+    pub lifts_open_pointer: String,
+    pub lifts_total_pointer: String,
+}
+
+/// "Watering needed" indicator from soil moisture and evapotranspiration, in template user units
+#[derive(Debug, Deserialize)]
+pub struct Garden {
+    pub enabled: bool,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Volumetric soil moisture (%) at 3-9 cm below which the garden needs water
+    pub soil_moisture_threshold: f32,
+    /// Evapotranspiration minus rain (mm) over the last 3 days above which the garden needs water
+    pub water_deficit_threshold: f32,
+}
+
+/// "Record heat" badge from the forecast history of the location, in template user units
+#[derive(Debug, Deserialize)]
+pub struct TemperatureRecords {
+    pub enabled: bool,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Days of history needed before records are flagged
+    pub min_history_days: usize,
+}
+
+/// Marker on the daily tiles whose outlook changed since the previous run
+#[derive(Debug, Deserialize)]
+pub struct ForecastChanges {
+    pub enabled: bool,
+    /// Change of the forecast high in °C that counts as a revision, whatever the configured unit
+    pub max_temp_change: f32,
+    /// How long a revised day keeps its marker
+    pub marker_hours: u32,
+}
+
+/// Quote or fact of the day drawn over a strip of the dashboard, in template user units
+#[derive(Debug, Deserialize)]
+pub struct Footer {
+    /// Text file with one quote per line, no file disables the footer
+    #[serde(default)]
+    pub quotes_path: Option<PathBuf>,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub font_size: f32,
+}
+
+/// Fonts for the characters the template's fonts don't have
+#[derive(Debug, Deserialize)]
+pub struct Fonts {
+    /// Families tried in order for a missing character, e.g. an emoji font, then CJK fonts
+    pub fallback_families: Vec<String>,
+    /// Font files loaded on top of the system fonts, also with `deterministic_output`
+    pub font_files: Vec<PathBuf>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -159,6 +886,36 @@ pub struct Debugging {
     pub disable_raw_7color_output: bool,
     pub allow_pre_release_version: bool,
     pub enable_debug_logs: bool,
+    /// Byte-identical outputs for golden tests, see [`crate::utils::normalize_svg_numbers`]
+    pub deterministic_output: bool,
+}
+
+/// Image format of the dashboard, picked from the Accept header on `/dashboard`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardFormat {
+    Svg,
+    #[default]
+    Png,
+    Bmp,
+    /// Packed display data, as `/dashboard.raw` serves it
+    Raw,
+}
+
+impl DashboardFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            DashboardFormat::Svg => "image/svg+xml",
+            DashboardFormat::Png => "image/png",
+            DashboardFormat::Bmp => "image/bmp",
+            DashboardFormat::Raw => "application/octet-stream",
+        }
+    }
+
+    /// Whether the format is an image a browser shows, raw data isn't
+    pub fn is_image(self) -> bool {
+        self != DashboardFormat::Raw
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -166,6 +923,192 @@ pub struct WebServer {
     pub active_hours_start: u8,
     pub active_hours_end: u8,
     pub active_hours_interval_seconds: u32,
+    pub render_timeout_seconds: u64,
+    /// Resident memory (MB) above which a warning is logged after a render, 0 disables it
+    pub memory_limit_mb: u32,
+    pub swagger_ui: bool,
+    /// Serves the health of the displays that report their boots at `/status`
+    pub status_page: bool,
+    pub cors_allowed_origins: Vec<String>,
+    pub cors_allowed_methods: Vec<String>,
+    pub full_clear_interval: u32,
+    pub low_battery_threshold: u8,
+    pub low_battery_template_path: PathBuf,
+    pub low_battery_interval_seconds: u32,
+    /// Format of `/dashboard` for clients that accept any, e.g. `Accept: */*`
+    pub default_dashboard_format: DashboardFormat,
+    pub buttons: Buttons,
+    pub refresh_hint: RefreshHint,
+}
+
+/// Recommended refresh intervals served to the displays, see [`crate::refresh_schedule`]
+#[derive(Debug, Deserialize, Clone)]
+pub struct RefreshHint {
+    /// Around a front or rain starting or stopping
+    pub fast_interval_seconds: u32,
+    /// Hours the forecast marks as night
+    pub night_interval_seconds: u32,
+    /// Pressure change within 3 hours (hPa) that marks a front
+    pub pressure_change_hpa: f32,
+    /// Rain change from one hour to the next (mm) that marks rain starting or stopping
+    pub rain_change_mm: f32,
+}
+
+/// Button on the display, A to D from the top on the Inky Impression
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Button {
+    A,
+    B,
+    C,
+    D,
+}
+
+/// What pressing a display button does
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ButtonAction {
+    /// The button is ignored
+    #[default]
+    None,
+    /// Renders the layout on the display again right away
+    Refresh,
+    /// Switches the display to the next layout, the base configuration and then each profile
+    NextLayout,
+    /// Shows the self-test screen with the version, the configuration and the network status
+    Diagnostics,
+}
+
+/// Actions of the display buttons, pressed buttons are reported to the web server
+#[derive(Debug, Deserialize)]
+pub struct Buttons {
+    pub a: ButtonAction,
+    pub b: ButtonAction,
+    pub c: ButtonAction,
+    pub d: ButtonAction,
+}
+
+impl Buttons {
+    pub fn action(&self, button: Button) -> ButtonAction {
+        match button {
+            Button::A => self.a,
+            Button::B => self.b,
+            Button::C => self.c,
+            Button::D => self.d,
+        }
+    }
+}
+
+/// Dashboard variant, selecting the optional data fetched for it
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Layout {
+    #[default]
+    Standard,
+    /// Swell and waves from the Open-Meteo Marine API and the BOM marine wind warnings, in the
+    /// template's `{marine_svg}` region
+    Marine,
+}
+
+/// Time-series database the render metrics are pushed to
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MetricsBackend {
+    /// Prometheus Pushgateway, the metrics replace the previous push of the job
+    #[default]
+    Pushgateway,
+    /// InfluxDB line protocol, posted to the write URL
+    Influxdb,
+}
+
+/// Render metrics pushed after each run, for time series without the web server
+#[derive(Debug, Deserialize)]
+pub struct MetricsExport {
+    /// Pushgateway base URL or InfluxDB write URL, no URL disables the export
+    #[serde(default)]
+    pub url: Option<Url>,
+    pub backend: MetricsBackend,
+    /// Pushgateway job, or InfluxDB measurement
+    pub job: String,
+    /// InfluxDB API token, sent as "Authorization: Token <token>"
+    #[serde(default)]
+    pub influxdb_token: Option<String>,
+}
+
+/// Anonymous usage ping after each run, nothing is sent unless it's enabled
+#[derive(Debug, Deserialize)]
+pub struct Telemetry {
+    pub enabled: bool,
+    /// Where the ping is posted, needed when telemetry is enabled
+    #[serde(default)]
+    pub url: Option<Url>,
+    /// Log the ping instead of sending it, enabled or not
+    pub dry_run: bool,
+}
+
+/// What the dashboard shows during the quiet hours
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy, Default, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum QuietMode {
+    /// A static night layout, the refreshes after the first one then show the same image
+    #[default]
+    Night,
+    /// No refresh at all, the display keeps the last dashboard
+    Skip,
+}
+
+/// Overnight period with fewer refreshes, e.g. for a display in a bedroom
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct QuietHours {
+    pub start: HourOfDay,
+    /// First hour after the quiet hours, before `start` when they span midnight
+    pub end: HourOfDay,
+    #[serde(default)]
+    pub mode: QuietMode,
+}
+
+impl QuietHours {
+    /// Whether `now` falls in the quiet hours
+    pub fn contains(&self, now: DateTime<Local>) -> bool {
+        let start = u32::from(self.start.into_inner());
+        let end = u32::from(self.end.into_inner());
+        let hour = now.hour();
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+
+    /// Seconds from `now` until the quiet hours end
+    pub fn seconds_until_end(&self, now: DateTime<Local>) -> u32 {
+        let elapsed = now.num_seconds_from_midnight();
+        let end = u32::from(self.end.into_inner()) * 3600;
+        if end > elapsed {
+            end - elapsed
+        } else {
+            24 * 3600 - elapsed + end
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct Schedule {
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Schedule {
+    /// The quiet hours if `now` falls in them
+    pub fn quiet_hours_at(&self, now: DateTime<Local>) -> Option<QuietHours> {
+        self.quiet_hours
+            .filter(|quiet_hours| quiet_hours.contains(now))
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -173,10 +1116,52 @@ pub struct DashboardSettings {
     pub release: Release,
     pub api: Api,
     pub colours: Colours,
+    pub palette: InkPalette,
+    pub fonts: Fonts,
     pub misc: Misc,
     pub render_options: RenderOptions,
     pub debugging: Debugging,
     pub web_server: WebServer,
+    pub image_panel: ImagePanel,
+    pub footer: Footer,
+    pub clock_face: ClockFace,
+    pub diagnostics: Diagnostics,
+    pub snow_report: SnowReport,
+    pub garden: Garden,
+    pub indoor: Indoor,
+    pub comparison: Comparison,
+    pub temperature_records: TemperatureRecords,
+    pub forecast_changes: ForecastChanges,
+    pub metrics_export: MetricsExport,
+    pub telemetry: Telemetry,
+    #[serde(default)]
+    pub schedule: Schedule,
+    #[serde(default)]
+    pub special_dates: Vec<SpecialDate>,
+    #[serde(default)]
+    pub theme_rules: Vec<ThemeRule>,
+    #[serde(default)]
+    pub commute_windows: Vec<CommuteWindow>,
+}
+
+/// Checks the commute windows fit the graph and each ends after it starts
+fn check_commute_windows(windows: &[CommuteWindow]) -> Result<(), String> {
+    if windows.len() > MAX_COMMUTE_WINDOWS {
+        return Err(format!(
+            "At most {MAX_COMMUTE_WINDOWS} commute windows can be highlighted, {} are configured",
+            windows.len()
+        ));
+    }
+    match windows
+        .iter()
+        .find(|window| window.end.time() <= window.start.time())
+    {
+        Some(window) => Err(format!(
+            "Commute window {} to {} must end after it starts",
+            window.start, window.end
+        )),
+        None => Ok(()),
+    }
 }
 
 /// Dashboard settings.
@@ -186,9 +1171,27 @@ pub struct DashboardSettings {
 /// * `release` - Release settings.
 /// * `api` - API settings.
 /// * `colours` - Colour settings.
+/// * `palette` - Colours the display's inks print as.
+/// * `fonts` - Fallback fonts for emoji and non-Latin characters.
 /// * `misc` - Miscellaneous settings.
 /// * `render_options` - Render options.
 /// * `debugging` - Debugging settings.
+/// * `image_panel` - External image embedded in the dashboard.
+/// * `footer` - Quote of the day along the bottom of the dashboard.
+/// * `clock_face` - Analog clock showing the render time.
+/// * `diagnostics` - Placement of the diagnostic warning icons.
+/// * `snow_report` - Snow depth, recent snowfall and lift status.
+/// * `garden` - Watering indicator from soil moisture and evapotranspiration.
+/// * `indoor` - Indoor temperature and humidity against outdoors, with window hints.
+/// * `comparison` - Two locations side by side instead of the template.
+/// * `temperature_records` - Badge when today's forecast is a record for the location.
+/// * `forecast_changes` - Marker on the daily tiles whose outlook was revised.
+/// * `metrics_export` - Render metrics pushed to a Pushgateway or InfluxDB after each run.
+/// * `telemetry` - Opt-in anonymous ping with the version, provider and display type.
+/// * `schedule` - Quiet hours with fewer refreshes.
+/// * `special_dates` - Holidays, birthdays and other dates marked on the daily tiles.
+/// * `theme_rules` - Colours changing with the current conditions and today's forecast high.
+/// * `commute_windows` - Daily time windows highlighted on the hourly graph.
 ///
 /// # Errors
 ///
@@ -198,7 +1201,8 @@ pub struct DashboardSettings {
 ///
 /// Panics if the configuration file is not found.
 impl DashboardSettings {
-    pub(crate) fn new() -> Result<Self, ConfigError> {
+    /// Loads the base settings and the web server profiles defined under `[profiles]`
+    pub(crate) fn load() -> Result<(Self, HashMap<String, Profile>), ConfigError> {
         let run_mode = env::var("RUN_MODE").unwrap_or_else(|_| "development".into());
         let is_test_mode = run_mode == "test";
 
@@ -209,17 +1213,16 @@ impl DashboardSettings {
         let local_config_path = root.join(CONFIG_DIR).join("local");
         let test_config_path = root.join(CONFIG_DIR).join("test");
 
-        // user config path is located at ~/.config/pi-inky-weather-epd.toml
-        let home_dir = env::var("HOME").unwrap();
-        let user_config_path = std::path::PathBuf::from(&home_dir)
-            .join(".config")
-            .join(env!("CARGO_PKG_NAME"));
+        // Start off by merging in the "default" configuration file
+        let mut config_builder =
+            Config::builder().add_source(File::with_name(default_config_path.to_str().unwrap()));
 
-        let mut config_builder = Config::builder()
-            // Start off by merging in the "default" configuration file
-            .add_source(File::with_name(default_config_path.to_str().unwrap()))
-            // Add in user configuration file
-            .add_source(File::with_name(user_config_path.to_str().unwrap()).required(false));
+        // Add in the user configuration files, ~/.config/pi-inky-weather-epd.toml and the one in
+        // the platform's config directory
+        for user_config_path in platform::user_config_files() {
+            config_builder = config_builder
+                .add_source(File::with_name(&user_config_path.to_string_lossy()).required(false));
+        }
 
         // If running tests (RUN_MODE=test), load test.toml and skip development/local
         // Otherwise, load development.toml and local.toml
@@ -247,6 +1250,8 @@ impl DashboardSettings {
                     .try_parsing(true), // Parse values to correct types
             )
             .build()?;
+        let mut profiles =
+            build_profiles(&settings.clone().try_deserialize::<serde_json::Value>()?)?;
         let final_settings: Result<DashboardSettings, ConfigError> = settings.try_deserialize();
 
         // Validate the settings after deserializing
@@ -256,7 +1261,83 @@ impl DashboardSettings {
             )));
         }
 
-        final_settings
+        let mut final_settings = final_settings?;
+        if final_settings.colours.snap_to_palette {
+            contrast::snap_to_palette(&mut final_settings);
+        }
+        for profile in profiles.values_mut() {
+            if profile.settings.colours.snap_to_palette {
+                contrast::snap_to_palette(&mut profile.settings);
+            }
+        }
+        contrast::check_colour_contrast(&final_settings, "Configuration")
+            .map_err(ConfigError::Message)?;
+        let mut profile_names: Vec<&String> = profiles.keys().collect();
+        profile_names.sort_unstable();
+        for name in profile_names {
+            contrast::check_colour_contrast(&profiles[name].settings, &format!("Profile {name}"))
+                .map_err(ConfigError::Message)?;
+        }
+        check_commute_windows(&final_settings.commute_windows).map_err(ConfigError::Message)?;
+        if final_settings
+            .render_options
+            .use_gust_instead_of_wind
+            .is_some()
+        {
+            crate::logger::warning(
+                "render_options.use_gust_instead_of_wind is replaced by wind_display = \"gust\", \"sustained\" or \"both\"",
+            );
+        }
+        for render_options in std::iter::once(&mut final_settings.render_options).chain(
+            profiles
+                .values_mut()
+                .map(|profile| &mut profile.settings.render_options),
+        ) {
+            render_options.wind_display = render_options
+                .wind_display
+                .with_use_gust_instead_of_wind(render_options.use_gust_instead_of_wind);
+        }
+        for misc in std::iter::once(&mut final_settings.misc).chain(
+            profiles
+                .values_mut()
+                .map(|profile| &mut profile.settings.misc),
+        ) {
+            if let Some(path) = &misc.icon_overrides_path {
+                misc.icon_overrides = load_icon_overrides(path, &misc.svg_icons_directory)
+                    .map_err(|e| ConfigError::Message(format!("{e:#}")))?;
+            }
+        }
+        for misc in std::iter::once(&final_settings.misc)
+            .chain(profiles.values().map(|profile| &profile.settings.misc))
+        {
+            for output in [
+                &misc.generated_svg_name,
+                &misc.generated_png_name,
+                &misc.generated_raw_name,
+            ] {
+                output_names::check_output_name(output).map_err(ConfigError::Message)?;
+            }
+        }
+        if final_settings.telemetry.enabled && final_settings.telemetry.url.is_none() {
+            return Err(ConfigError::Message(
+                "Telemetry is enabled without a telemetry.url to send it to".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&final_settings.misc.ghosting_compensation) {
+            return Err(ConfigError::Message(format!(
+                "misc.ghosting_compensation must be from 0 to 1, got {}",
+                final_settings.misc.ghosting_compensation
+            )));
+        }
+        let comparison = &final_settings.comparison;
+        if comparison.enabled && !profiles.contains_key(&comparison.profile) {
+            return Err(ConfigError::Message(format!(
+                "Comparison profile \"{}\" isn't defined under [profiles]",
+                comparison.profile
+            )));
+        }
+
+        Ok((final_settings, profiles))
     }
 
     /// Print configuration settings in a structured, hierarchical format
@@ -276,6 +1357,24 @@ impl DashboardSettings {
                 self.api.longitude.into_inner()
             ),
         );
+        logger::kvp(
+            "Daily Request Budget",
+            match self.api.daily_request_budget {
+                Some(budget) => budget.to_string(),
+                None => "unlimited".to_string(),
+            },
+        );
+        match self.api.provider {
+            Providers::Bom => {
+                logger::kvp("BOM Observations", self.api.bom_observations);
+                logger::kvp("BOM Warnings", self.api.bom_warnings);
+            }
+            Providers::OpenMeteo => {
+                logger::kvp("Open-Meteo Model", &self.api.open_meteo_model);
+                logger::kvp("Cell Selection", self.api.open_meteo_cell_selection);
+                logger::kvp("Past Days", self.api.open_meteo_past_days);
+            }
+        }
 
         // Render Options
         logger::config_group("Render Options");
@@ -287,7 +1386,13 @@ impl DashboardSettings {
             "Wind Speed Unit",
             format!("{}", self.render_options.wind_speed_unit),
         );
+        logger::kvp(
+            "Wave Height Unit",
+            format!("{}", self.render_options.wave_height_unit),
+        );
         logger::kvp("Date Format", &self.render_options.date_format);
+        logger::kvp("Locale", &self.render_options.locale);
+
         logger::kvp("Time Format", &self.render_options.time_format);
         logger::kvp(
             "Use Moon Phase",
@@ -297,10 +1402,98 @@ impl DashboardSettings {
             "X-Axis Always at Min",
             self.render_options.x_axis_always_at_min,
         );
+        logger::kvp("Wind Display", self.render_options.wind_display);
+        logger::kvp(
+            "Lightning CAPE Threshold",
+            self.render_options.lightning_cape_threshold,
+        );
+        logger::kvp("Highlight Weekend", self.render_options.highlight_weekend);
         logger::kvp(
-            "Use Gust Instead of Wind",
-            self.render_options.use_gust_instead_of_wind,
+            "Weekend Days",
+            format!("{:?}", self.render_options.weekend_days),
         );
+        logger::kvp("First Weekday", self.render_options.first_weekday);
+        logger::kvp("Week Numbering", self.render_options.week_numbering);
+
+        logger::kvp("Show Sun Table", self.render_options.show_sun_table);
+        logger::kvp(
+            "Show Rain Uncertainty Band",
+            self.render_options.show_rain_uncertainty_band,
+        );
+        logger::kvp(
+            "Show Temperature Spread",
+            self.render_options.show_temperature_spread,
+        );
+        if self.render_options.show_temperature_spread {
+            logger::kvp("Ensemble Model", &self.render_options.ensemble_model);
+        }
+        logger::kvp(
+            "Show Humidity Curve",
+            self.render_options.show_humidity_curve,
+        );
+        if self.render_options.show_humidity_curve
+            && self.render_options.show_humidity_comfort_bands
+        {
+            let (dry_below, humid_above) = self.render_options.humidity_comfort_range.into_inner();
+            logger::kvp(
+                "Humidity Comfort Bands",
+                format!("dry below {dry_below}%, humid above {humid_above}%"),
+            );
+        }
+        let (cold_below, hot_above) = self.render_options.temp_colour_range.into_inner();
+        logger::kvp(
+            "Temperature Colour Bands",
+            format!("cold below {cold_below}°C, hot above {hot_above}°C"),
+        );
+        logger::kvp(
+            "Rain Countdown Chance Threshold",
+            format!("{}%", self.render_options.rain_countdown_chance_threshold),
+        );
+        logger::kvp(
+            "Rain Countdown Horizon (min)",
+            self.render_options.rain_countdown_horizon_minutes,
+        );
+
+        logger::kvp(
+            "Max Table Rows",
+            self.render_options
+                .max_table_rows
+                .as_ref()
+                .iter()
+                .map(|metric| metric.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
+        logger::kvp(
+            "Graph Size",
+            format!(
+                "{}x{}",
+                self.render_options.graph_width, self.render_options.graph_height
+            ),
+        );
+        logger::kvp(
+            "Graph Window",
+            format!("{} hours", self.render_options.graph_window_hours),
+        );
+        logger::kvp("Anti-Aliasing", self.render_options.anti_aliasing);
+        logger::kvp("Icon Cache", self.render_options.icon_cache);
+        if self.render_options.text_halo {
+            logger::kvp("Text Halo Width", self.render_options.text_halo_width);
+        }
+        if let Some(threshold) = self.render_options.smoke_pm2_5_threshold {
+            logger::kvp("Smoke PM2.5 Threshold (µg/m³)", threshold);
+        }
+        logger::kvp(
+            "Tomorrow Max Markers",
+            format!("{:?}", self.render_options.tomorrow_max_markers),
+        );
+
+        logger::config_group("Fonts");
+        logger::kvp("Fallback Families", self.fonts.fallback_families.join(", "));
+        for file in &self.fonts.font_files {
+            logger::kvp("Font File", file.display());
+        }
 
         // Colours
         logger::config_group("Display Colours");
@@ -312,6 +1505,203 @@ impl DashboardSettings {
         logger::kvp("Actual Temp", &self.colours.actual_temp_colour);
         logger::kvp("Feels Like", &self.colours.feels_like_colour);
         logger::kvp("Rain", &self.colours.rain_colour);
+        logger::kvp(
+            "Weekend Background",
+            &self.colours.weekend_background_colour,
+        );
+        logger::kvp("Weekend Text", &self.colours.weekend_text_colour);
+        logger::kvp("Humidity", &self.colours.humidity_colour);
+        logger::kvp("Humidity Dry Band", &self.colours.humidity_dry_colour);
+        logger::kvp("Humidity Humid Band", &self.colours.humidity_humid_colour);
+        logger::kvp("Cold Temp", &self.colours.cold_temp_colour);
+        logger::kvp("Hot Temp", &self.colours.hot_temp_colour);
+        logger::kvp("Snap To Palette", self.colours.snap_to_palette);
+
+        logger::config_group("Ink Palette");
+        // Purple isn't an ink the dashboard uses, it can't be tuned
+        for (name, [red, green, blue]) in INK_NAMES.iter().zip(self.palette.colours()).take(7) {
+            logger::kvp(name, format!("rgb({red}, {green}, {blue})"));
+        }
+        logger::kvp("Saturation", self.palette.saturation);
+
+        // Special Dates
+        logger::config_group("Special Dates");
+        if self.special_dates.is_empty() {
+            logger::kvp("Special Dates", "none");
+        }
+        for special_date in &self.special_dates {
+            logger::kvp(&special_date.label, &special_date.date);
+        }
+
+        if !self.commute_windows.is_empty() {
+            logger::config_group("Commute Windows");
+            for window in &self.commute_windows {
+                logger::kvp("Window", format!("{} to {}", window.start, window.end));
+            }
+        }
+
+        if !self.diagnostics.priorities.is_empty() {
+            logger::config_group("Diagnostic Priorities");
+            let mut priorities: Vec<_> = self.diagnostics.priorities.iter().collect();
+            priorities.sort_by_key(|(kind, _)| kind.to_string());
+            for (kind, priority) in priorities {
+                logger::kvp(kind, format!("{priority:?}"));
+            }
+        }
+
+        if !self.theme_rules.is_empty() {
+            logger::config_group("Theme Rules");
+            for (index, rule) in self.theme_rules.iter().enumerate() {
+                let conditions = if rule.conditions.is_empty() {
+                    "any condition".to_string()
+                } else {
+                    rule.conditions
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let temperatures = match (rule.min_temperature, rule.max_temperature) {
+                    (Some(min), Some(max)) => format!(", high {min}° to {max}°"),
+                    (Some(min), None) => format!(", high from {min}°"),
+                    (None, Some(max)) => format!(", high below {max}°"),
+                    (None, None) => String::new(),
+                };
+                logger::kvp(
+                    format!("Rule {}", index + 1),
+                    format!("{conditions}{temperatures}"),
+                );
+            }
+        }
+
+        if let Some(url) = &self.image_panel.url {
+            logger::config_group("Image Panel");
+            logger::kvp("URL", url);
+            logger::kvp(
+                "Size",
+                format!("{}x{}", self.image_panel.width, self.image_panel.height),
+            );
+            logger::kvp("Dither", format!("{:?}", self.image_panel.dither));
+        }
+
+        if let Some(quotes_path) = &self.footer.quotes_path {
+            logger::config_group("Footer");
+            logger::kvp("Quotes", quotes_path.display());
+        }
+
+        if self.clock_face.enabled {
+            logger::config_group("Clock Face");
+            logger::kvp(
+                "Position",
+                format!(
+                    "{},{} size {}",
+                    self.clock_face.x, self.clock_face.y, self.clock_face.size
+                ),
+            );
+        }
+
+        if self.snow_report.enabled {
+            logger::config_group("Snow Report");
+            logger::kvp(
+                "Position",
+                format!(
+                    "{},{} size {}x{}",
+                    self.snow_report.x,
+                    self.snow_report.y,
+                    self.snow_report.width,
+                    self.snow_report.height
+                ),
+            );
+            if let Some(url) = &self.snow_report.lift_status_url {
+                logger::kvp("Lift Status", url);
+            }
+        }
+
+        if self.indoor.enabled {
+            logger::config_group("Indoor");
+            match (&self.indoor.url, &self.indoor.path) {
+                (Some(url), _) => logger::kvp("Feed", url),
+                (None, Some(path)) => logger::kvp("File", path.display()),
+                (None, None) => logger::kvp("Source", "none, set url or path"),
+            }
+            logger::kvp(
+                "Comfort",
+                format!(
+                    "{}-{}° up to {}%",
+                    self.indoor.comfort.min_temperature,
+                    self.indoor.comfort.max_temperature,
+                    self.indoor.comfort.max_humidity
+                ),
+            );
+        }
+
+        if self.comparison.enabled {
+            logger::config_group("Comparison");
+            logger::kvp("Profile", &self.comparison.profile);
+            logger::kvp("Days", self.comparison.days);
+        }
+
+        if self.garden.enabled {
+            logger::config_group("Garden");
+            logger::kvp(
+                "Soil Moisture Threshold",
+                format!("{}%", self.garden.soil_moisture_threshold),
+            );
+            logger::kvp(
+                "Water Deficit Threshold",
+                format!("{} mm", self.garden.water_deficit_threshold),
+            );
+        }
+
+        if self.temperature_records.enabled {
+            logger::config_group("Temperature Records");
+            logger::kvp(
+                "Min History",
+                format!("{} days", self.temperature_records.min_history_days),
+            );
+        }
+
+        if self.forecast_changes.enabled {
+            logger::config_group("Forecast Changes");
+            logger::kvp(
+                "Max Temp Change",
+                format!("{}°C", self.forecast_changes.max_temp_change),
+            );
+            logger::kvp(
+                "Marker Shown",
+                format!("{} hours", self.forecast_changes.marker_hours),
+            );
+        }
+
+        if let Some(url) = &self.metrics_export.url {
+            logger::config_group("Metrics Export");
+            logger::kvp("Backend", self.metrics_export.backend);
+            logger::kvp("URL", url);
+            logger::kvp("Job", &self.metrics_export.job);
+        }
+
+        // Always shown, whether anything leaves the device must be visible
+        logger::config_group("Telemetry");
+        logger::kvp("Enabled", self.telemetry.enabled);
+        if let Some(url) = self
+            .telemetry
+            .url
+            .as_ref()
+            .filter(|_| self.telemetry.enabled)
+        {
+            logger::kvp("URL", url);
+        }
+
+        if let Some(quiet_hours) = &self.schedule.quiet_hours {
+            logger::config_group("Schedule");
+            logger::kvp(
+                "Quiet Hours",
+                format!(
+                    "{} - {} ({})",
+                    quiet_hours.start, quiet_hours.end, quiet_hours.mode
+                ),
+            );
+        }
 
         // File Paths
         logger::config_group("File Paths");
@@ -321,7 +1711,33 @@ impl DashboardSettings {
         logger::kvp("Output SVG", self.misc.generated_svg_name.display());
         logger::kvp("Output PNG", self.misc.generated_png_name.display());
         logger::kvp("Output RAW", self.misc.generated_raw_name.display());
+        if self.misc.output_retention_days > 0 {
+            logger::kvp(
+                "Output Retention",
+                format!("{} days", self.misc.output_retention_days),
+            );
+        }
         logger::kvp("Icons Directory", self.misc.svg_icons_directory.display());
+        if let Some(path) = &self.misc.icon_overrides_path {
+            logger::kvp(
+                "Icon Overrides",
+                format!(
+                    "{} ({} icons)",
+                    path.display(),
+                    self.misc.icon_overrides.len()
+                ),
+            );
+        }
+        logger::kvp("Layout", self.misc.layout);
+        logger::kvp("Min Free Disk Space (MB)", self.misc.min_free_disk_space_mb);
+        logger::kvp("Colour Contrast Check", self.misc.colour_contrast_check);
+        if let Some((width, height)) = self.misc.display_resolution {
+            logger::kvp("Display Resolution", format!("{width}x{height}"));
+        }
+        logger::kvp("Display Driver", self.misc.display_driver);
+        if self.misc.ghosting_compensation > 0.0 {
+            logger::kvp("Ghosting Compensation", self.misc.ghosting_compensation);
+        }
 
         // Release/Update Settings
         logger::config_group("Update Settings");
@@ -343,5 +1759,6 @@ impl DashboardSettings {
             self.debugging.disable_raw_7color_output,
         );
         logger::kvp("Enable Debug Logs", self.debugging.enable_debug_logs);
+        logger::kvp("Deterministic Output", self.debugging.deterministic_output);
     }
 }
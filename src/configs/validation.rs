@@ -287,6 +287,21 @@ fn is_hsla_colour(colour: &str) -> bool {
     }
 }
 
+/// Colours the 7-colour display shows without mixing, by name
+const PALETTE_COLOURS: [&str; 7] = ["black", "white", "green", "blue", "red", "yellow", "orange"];
+
+/// Accepts only colours the display can show as they are, e.g. for hatched areas that would turn
+/// into noise when dithered
+pub fn is_palette_colour(colour: &str) -> Result<(), ValidationError> {
+    if PALETTE_COLOURS.contains(&colour.trim().to_ascii_lowercase().as_str()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "Colour must be one of black, white, green, blue, red, yellow or orange",
+        ))
+    }
+}
+
 fn is_special_colour(colour: &str) -> bool {
     SPECIAL_COLOURS.contains(&colour)
 }
@@ -385,3 +400,58 @@ pub fn is_valid_date_format(format: &str) -> Result<(), ValidationError> {
 
     Ok(())
 }
+
+/// Accepts a single Open-Meteo model name, e.g. `best_match`, `icon_seamless` or `gfs_seamless`.
+/// Several models would suffix every variable in the response with the model name.
+pub fn is_valid_open_meteo_model(model: &str) -> Result<(), ValidationError> {
+    if !model.is_empty()
+        && model
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "Open-Meteo model must be a single model name, e.g. best_match, icon_seamless or gfs_seamless",
+        ))
+    }
+}
+
+/// Accepts a locale made of a language code and an optional region, e.g. `en`, `en-AU` or
+/// `de_DE`
+pub fn is_valid_locale(locale: &str) -> Result<(), ValidationError> {
+    let mut parts = locale.split(['-', '_']);
+    let language_ok = parts.next().is_some_and(|language| {
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    let region_ok =
+        parts.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if language_ok && region_ok {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "Locale must be a language code with an optional region, e.g. en, en-AU or de_DE",
+        ))
+    }
+}
+
+/// Validates a time of day written as `"HH:MM"`
+pub fn is_valid_time_of_day(time: &str) -> Result<(), ValidationError> {
+    match chrono::NaiveTime::parse_from_str(time, "%H:%M") {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ValidationError::new(
+            "Time of day must be \"HH:MM\", e.g. \"08:30\"",
+        )),
+    }
+}
+
+/// Validates a special date rule (`"MM-DD"`, `"YYYY-MM-DD"` or `"easter[+/-N]"`).
+pub fn is_valid_special_date_rule(rule: &str) -> Result<(), ValidationError> {
+    if crate::calendar::DateRule::parse(rule).is_some() {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            "Special date must be \"MM-DD\", \"YYYY-MM-DD\" or \"easter[+/-N]\"",
+        ))
+    }
+}
@@ -5,14 +5,29 @@ use url::Url;
 
 pub const BOM_API_TEMP_UNIT: TemperatureUnit = TemperatureUnit::C;
 pub const DEFAULT_AXIS_LABEL_FONT_SIZE: u16 = 19;
+/// Font size of the rain band amounts under the right axis percentages
+pub const RAIN_BAND_AXIS_LABEL_FONT_SIZE: u16 = 14;
+/// UV index at which sun protection is recommended (WHO "moderate")
+pub const UV_PROTECTION_THRESHOLD: u16 = 3;
+/// Hourly rain amount (mm) drawn at the top of the right axis by the rain uncertainty band
+pub const RAIN_BAND_FULL_SCALE_MM: f32 = 10.0;
 
 pub const HOURLY_CACHE_SUFFIX: &str = "hourly_forecast.json";
 pub const DAILY_CACHE_SUFFIX: &str = "daily_forecast.json";
 pub const CACHE_SUFFIX: &str = "forecast.json";
+pub const AIR_QUALITY_CACHE_SUFFIX: &str = "air_quality.json";
+pub const ENSEMBLE_CACHE_SUFFIX: &str = "ensemble.json";
+pub const SNOW_CACHE_SUFFIX: &str = "snow.json";
+pub const MARINE_CACHE_SUFFIX: &str = "marine.json";
+pub const GARDEN_CACHE_SUFFIX: &str = "garden.json";
+pub const OBSERVATIONS_CACHE_SUFFIX: &str = "observations.json";
+pub const WARNINGS_CACHE_SUFFIX: &str = "warnings.json";
+pub const LOCATION_CACHE_SUFFIX: &str = "location.json";
 
 const NOT_AVAILABLE_ICON_NAME: &str = "not-available.svg";
 
-fn build_forecast_url(frequency: &str) -> Url {
+/// `{base_url}/{geohash}`, the BOM endpoints of the configured location are below it
+fn bom_location_url() -> Url {
     // Allow test override via environment variable (for wiremock/fixtures)
     let base_url = std::env::var("BOM_BASE_URL")
         .unwrap_or_else(|_| "https://api.weather.bom.gov.au/v1/locations".to_string());
@@ -26,22 +41,66 @@ fn build_forecast_url(frequency: &str) -> Url {
     )
     .expect("Failed to encode latitude and longitude to geohash");
 
+    u.path_segments_mut().unwrap().push(&geohash);
+    u
+}
+
+fn build_forecast_url(frequency: &str) -> Url {
+    let mut u = bom_location_url();
     u.path_segments_mut()
         .unwrap()
-        .push(&geohash)
         .push("forecasts")
         .push(frequency);
     u
 }
 
-pub static DAILY_FORECAST_ENDPOINT: Lazy<Url> = Lazy::new(|| build_forecast_url("daily"));
-pub static HOURLY_FORECAST_ENDPOINT: Lazy<Url> = Lazy::new(|| build_forecast_url("hourly"));
+/// BOM details of the location, with its time zone
+pub fn bom_location_endpoint() -> Url {
+    bom_location_url()
+}
+
+/// BOM observations of the weather station closest to the location
+pub fn bom_observations_endpoint() -> Url {
+    let mut u = bom_location_url();
+    u.path_segments_mut().unwrap().push("observations");
+    u
+}
+
+/// BOM warnings in force for the location
+pub fn bom_warnings_endpoint() -> Url {
+    let mut u = bom_location_url();
+    u.path_segments_mut().unwrap().push("warnings");
+    u
+}
+
+pub fn daily_forecast_endpoint() -> Url {
+    build_forecast_url("daily")
+}
+
+pub fn hourly_forecast_endpoint() -> Url {
+    build_forecast_url("hourly")
+}
+
+/// Model, cell selection and past days of the Open-Meteo forecast queries, at least
+/// `min_past_days` days before today
+fn open_meteo_forecast_options(min_past_days: u8) -> String {
+    format!(
+        "models={}&cell_selection={}&past_days={}",
+        CONFIG.api.open_meteo_model,
+        CONFIG.api.open_meteo_cell_selection,
+        CONFIG
+            .api
+            .open_meteo_past_days
+            .into_inner()
+            .max(min_past_days)
+    )
+}
 
 /// Open-Meteo endpoint for HOURLY forecasts (uses UTC timezone)
 ///
 /// Hourly data is requested in UTC and later converted to local time during processing.
 /// This ensures consistent timestamp handling across all timezones.
-pub static OPEN_METEO_HOURLY_ENDPOINT: Lazy<Url> = Lazy::new(|| {
+pub fn open_meteo_hourly_endpoint() -> Url {
     let base_url = std::env::var("OPEN_METEO_BASE_URL")
         .unwrap_or_else(|_| "https://api.open-meteo.com".to_string());
 
@@ -49,16 +108,110 @@ pub static OPEN_METEO_HOURLY_ENDPOINT: Lazy<Url> = Lazy::new(|| {
         "{}/v1/forecast?\
         latitude={}&\
         longitude={}&\
-        hourly=temperature_2m,apparent_temperature,precipitation_probability,precipitation,uv_index,wind_speed_10m,wind_gusts_10m,relative_humidity_2m,cloud_cover&\
+        hourly=temperature_2m,apparent_temperature,precipitation_probability,precipitation,uv_index,wind_speed_10m,wind_gusts_10m,relative_humidity_2m,cloud_cover,cape,pressure_msl,weather_code&\
         current=is_day&\
         forecast_days=14&\
+        {}&\
         timezone=UTC",
         base_url,
         CONFIG.api.latitude,
-        CONFIG.api.longitude
+        CONFIG.api.longitude,
+        open_meteo_forecast_options(0)
     );
     Url::parse(&url).expect("Failed to construct Open Meteo hourly endpoint URL")
-});
+}
+
+/// Open-Meteo air quality endpoint (hourly US AQI and PM2.5, UTC timestamps)
+///
+/// Served from a different host than the forecast API, so it has its own base URL override.
+pub fn open_meteo_air_quality_endpoint() -> Url {
+    let base_url = std::env::var("OPEN_METEO_AIR_QUALITY_BASE_URL")
+        .unwrap_or_else(|_| "https://air-quality-api.open-meteo.com".to_string());
+
+    let url = format!(
+        "{}/v1/air-quality?\
+        latitude={}&\
+        longitude={}&\
+        hourly=us_aqi,pm2_5&\
+        forecast_days=3&\
+        timezone=UTC",
+        base_url, CONFIG.api.latitude, CONFIG.api.longitude
+    );
+    Url::parse(&url).expect("Failed to construct Open Meteo air quality endpoint URL")
+}
+
+/// Open-Meteo ensemble endpoint (hourly temperature of every member of the configured model, UTC timestamps)
+///
+/// Served from a different host than the forecast API, so it has its own base URL override.
+pub fn open_meteo_ensemble_endpoint() -> Url {
+    let base_url = std::env::var("OPEN_METEO_ENSEMBLE_BASE_URL")
+        .unwrap_or_else(|_| "https://ensemble-api.open-meteo.com".to_string());
+
+    let url = format!(
+        "{}/v1/ensemble?\
+        latitude={}&\
+        longitude={}&\
+        hourly=temperature_2m&\
+        models={}&\
+        forecast_days=3&\
+        timezone=UTC",
+        base_url, CONFIG.api.latitude, CONFIG.api.longitude, CONFIG.render_options.ensemble_model
+    );
+    Url::parse(&url).expect("Failed to construct Open Meteo ensemble endpoint URL")
+}
+
+/// Open-Meteo endpoint for the snow report (hourly snow depth and snowfall of the last 3 days, UTC timestamps)
+pub fn open_meteo_snow_endpoint() -> Url {
+    let base_url = std::env::var("OPEN_METEO_BASE_URL")
+        .unwrap_or_else(|_| "https://api.open-meteo.com".to_string());
+
+    let url = format!(
+        "{}/v1/forecast?\
+        latitude={}&\
+        longitude={}&\
+        hourly=snow_depth,snowfall&\
+        past_days=3&\
+        forecast_days=1&\
+        timezone=UTC",
+        base_url, CONFIG.api.latitude, CONFIG.api.longitude
+    );
+    Url::parse(&url).expect("Failed to construct Open Meteo snow endpoint URL")
+}
+
+/// Open-Meteo Marine API endpoint for the marine layout (hourly waves and swell of today and tomorrow, UTC timestamps)
+pub fn open_meteo_marine_endpoint() -> Url {
+    let base_url = std::env::var("OPEN_METEO_MARINE_BASE_URL")
+        .unwrap_or_else(|_| "https://marine-api.open-meteo.com".to_string());
+
+    let url = format!(
+        "{}/v1/marine?\
+        latitude={}&\
+        longitude={}&\
+        hourly=wave_height,swell_wave_height,swell_wave_period,swell_wave_direction&\
+        forecast_days=2&\
+        timezone=UTC",
+        base_url, CONFIG.api.latitude, CONFIG.api.longitude
+    );
+    Url::parse(&url).expect("Failed to construct Open Meteo marine endpoint URL")
+}
+
+/// Open-Meteo endpoint for the garden indicator (hourly soil moisture, ET0 and rain of the last 3 days, UTC timestamps)
+pub fn open_meteo_garden_endpoint() -> Url {
+    let base_url = std::env::var("OPEN_METEO_BASE_URL")
+        .unwrap_or_else(|_| "https://api.open-meteo.com".to_string());
+
+    let url = format!(
+        "{}/v1/forecast?\
+        latitude={}&\
+        longitude={}&\
+        hourly=soil_moisture_3_to_9cm,et0_fao_evapotranspiration,precipitation&\
+        past_days=3&\
+        forecast_days=1&\
+        timezone=UTC",
+        base_url, CONFIG.api.latitude, CONFIG.api.longitude
+    );
+    Url::parse(&url).expect("Failed to construct Open Meteo garden endpoint URL")
+}
 
 /// Open-Meteo endpoint for DAILY forecasts (uses auto timezone for correct aggregation)
 ///
@@ -66,12 +219,12 @@ pub static OPEN_METEO_HOURLY_ENDPOINT: Lazy<Url> = Lazy::new(|| {
 /// local 24-hour window (midnight-to-midnight in the coordinates' timezone), not UTC's 24-hour window.
 /// This ensures "today's high" reflects the actual hottest hour in the user's local day.
 ///
-/// Uses `past_days=1` to include yesterday's data, ensuring users in timezones behind UTC
+/// Uses at least `past_days=1` to include yesterday's data, ensuring users in timezones behind UTC
 /// still have access to "today's" forecast even after UTC midnight crosses into the next
 /// calendar day.
 ///
 /// The `timezone=auto` parameter automatically determines the timezone from the lat/lon coordinates.
-pub static OPEN_METEO_DAILY_ENDPOINT: Lazy<Url> = Lazy::new(|| {
+pub fn open_meteo_daily_endpoint() -> Url {
     let base_url = std::env::var("OPEN_METEO_BASE_URL")
         .unwrap_or_else(|_| "https://api.open-meteo.com".to_string());
 
@@ -81,14 +234,15 @@ pub static OPEN_METEO_DAILY_ENDPOINT: Lazy<Url> = Lazy::new(|| {
         longitude={}&\
         daily=sunrise,sunset,temperature_2m_max,temperature_2m_min,precipitation_sum,precipitation_probability_max,cloud_cover_mean&\
         forecast_days=14&\
-        past_days=1&\
+        {}&\
         timezone=auto",
         base_url,
         CONFIG.api.latitude,
-        CONFIG.api.longitude
+        CONFIG.api.longitude,
+        open_meteo_forecast_options(1)
     );
     Url::parse(&url).expect("Failed to construct Open Meteo daily endpoint URL")
-});
+}
 
 pub static NOT_AVAILABLE_ICON_PATH: Lazy<PathBuf> = Lazy::new(|| {
     CONFIG
@@ -1,5 +1,38 @@
+//! SVG chart rendering for the 24h hourly forecast graph.
+//!
+//! The chart does not depend on the dashboard config, so it can be used on its own through
+//! [`HourlyForecastGraph::builder`]:
+//!
+//! ```
+//! use pi_inky_weather_epd::dashboard::chart::{GraphDataPath, HourlyForecastGraph};
+//!
+//! let temperatures: Vec<f32> = (0..24).map(|hour| 12.0 + (hour as f32 / 3.0)).collect();
+//! let mut graph = HourlyForecastGraph::builder()
+//!     .size(400.0, 200.0)
+//!     .text_colour("black")
+//!     .actual_temperature(&temperatures)
+//!     .feels_like(&temperatures)
+//!     .rain_chance(&[0.0; 24])
+//!     .build();
+//!
+//! let paths = graph.draw_graph().unwrap();
+//! assert!(matches!(paths[0], GraphDataPath::Temp(_)));
+//! ```
+//!
+//! Each series is indexed by point offset from the start of the chart, a point is one hour unless
+//! [`HourlyForecastGraphBuilder::hours_per_point`] says otherwise. Temperatures share the left
+//! axis, rain chance (0-100%) uses the right axis. The rain band's amounts (0 to
+//! `rain_band_full_scale` mm) are labelled on the right axis too, under the percentages.
+
 use crate::{
-    clock::Clock, constants::DEFAULT_AXIS_LABEL_FONT_SIZE, logger, weather::icons::UVIndexIcon,
+    clock::Clock,
+    constants::{
+        DEFAULT_AXIS_LABEL_FONT_SIZE, RAIN_BAND_AXIS_LABEL_FONT_SIZE, RAIN_BAND_FULL_SCALE_MM,
+    },
+    dashboard::number_format::NumberFormat,
+    logger,
+    utils::escape_xml,
+    weather::icons::UVIndexIcon,
 };
 use anyhow::Error;
 use strum_macros::Display;
@@ -52,6 +85,12 @@ impl CurveType {
         }
     }
 
+    fn data_mut(&mut self) -> &mut GraphData {
+        match self {
+            Self::ActualTemp(data) | Self::TempFeelLike(data) | Self::RainChance(data) => data,
+        }
+    }
+
     pub fn get_points(&self) -> &Vec<Point> {
         &self.data().points
     }
@@ -69,6 +108,19 @@ impl GraphData {
 pub struct HourlyForecastGraph {
     pub curves: Vec<CurveType>,
     pub uv_data: [u16; 24],
+    pub lightning_risk: [bool; 24],
+    /// Lower/upper rain amount (mm) per hour, `None` where the provider gives no range
+    pub rain_band: [Option<(f32, f32)>; 24],
+    /// Rain amount (mm) mapped to the top of the right axis
+    pub rain_band_full_scale: f32,
+    /// Lowest/highest ensemble member temperature per hour, `None` where there is no ensemble data
+    pub temperature_band: [Option<(f32, f32)>; 24],
+    /// Relative humidity (%) per hour, `None` when the humidity curve is off
+    pub humidity: [Option<f32>; 24],
+    /// Humidity (%) below which it's dry and above which it's humid, `None` draws no bands
+    pub humidity_comfort_range: Option<(f32, f32)>,
+    /// Forecast hours each point stands for, 2 draws 48 hours with 24 points
+    pub hours_per_point: u16,
     pub height: f32,
     pub width: f32,
     pub starting_x: f32,
@@ -80,9 +132,12 @@ pub struct HourlyForecastGraph {
     pub y_right_ticks: u16,
     pub x_axis_always_at_min: bool,
     pub text_colour: String,
+    /// Colour and width of the outline behind the labels, `None` draws plain labels
+    pub text_halo: Option<(String, f32)>,
+    /// Decimal separator and unit placement of the axis labels
+    pub number_format: NumberFormat,
 }
 
-// TODO: use the builder pattern to create the graph
 impl Default for HourlyForecastGraph {
     fn default() -> Self {
         Self {
@@ -101,6 +156,13 @@ impl Default for HourlyForecastGraph {
                 }),
             ],
             uv_data: [0; 24],
+            lightning_risk: [false; 24],
+            rain_band: [None; 24],
+            rain_band_full_scale: RAIN_BAND_FULL_SCALE_MM,
+            temperature_band: [None; 24],
+            humidity: [None; 24],
+            humidity_comfort_range: None,
+            hours_per_point: 1,
             height: 300.0,
             width: 600.0,
             starting_x: 0.0,
@@ -113,10 +175,161 @@ impl Default for HourlyForecastGraph {
             y_right_ticks: 5,
             x_axis_always_at_min: false,
             text_colour: "black".to_string(),
+            text_halo: None,
+            number_format: NumberFormat::default(),
         }
     }
 }
 
+/// Builder for [`HourlyForecastGraph`].
+///
+/// Starts from the dashboard defaults (600x300, black text, three empty series).
+#[derive(Default)]
+pub struct HourlyForecastGraphBuilder {
+    graph: HourlyForecastGraph,
+}
+
+impl HourlyForecastGraphBuilder {
+    /// Chart size in SVG user units.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.graph.width = width;
+        self.graph.height = height;
+        self
+    }
+
+    /// Colour used for axis labels and the "tomorrow" marker line.
+    pub fn text_colour(mut self, colour: impl Into<String>) -> Self {
+        self.graph.text_colour = colour.into();
+        self
+    }
+
+    /// Outline of `colour` behind the labels, usually the background colour, so labels stay
+    /// readable where they overlap the curves or the UV gradient.
+    pub fn text_halo(mut self, colour: impl Into<String>, width: f32) -> Self {
+        self.graph.text_halo = Some((colour.into(), width));
+        self
+    }
+
+    /// Decimal separator and unit placement of the axis labels, see [`NumberFormat::for_locale`].
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.graph.number_format = format;
+        self
+    }
+
+    /// Forecast hours each point of the series stands for, used for the axis labels and the
+    /// day lines. The series are expected to be downsampled already, see [`downsample`].
+    pub fn hours_per_point(mut self, hours: u16) -> Self {
+        self.graph.hours_per_point = hours.max(1);
+        self
+    }
+
+    /// Always draw the x-axis at the lowest temperature instead of at 0°.
+    pub fn x_axis_always_at_min(mut self, always_at_min: bool) -> Self {
+        self.graph.x_axis_always_at_min = always_at_min;
+        self
+    }
+
+    /// Number of ticks on the x, left y and right y axes.
+    pub fn ticks(mut self, x: u16, y_left: u16, y_right: u16) -> Self {
+        self.graph.x_ticks = x;
+        self.graph.y_left_ticks = y_left;
+        self.graph.y_right_ticks = y_right;
+        self
+    }
+
+    /// Hourly temperature series (left axis, smoothed).
+    pub fn actual_temperature(self, values: &[f32]) -> Self {
+        self.series(|curve| matches!(curve, CurveType::ActualTemp(_)), values)
+    }
+
+    /// Hourly apparent temperature series (left axis, smoothed).
+    pub fn feels_like(self, values: &[f32]) -> Self {
+        self.series(|curve| matches!(curve, CurveType::TempFeelLike(_)), values)
+    }
+
+    /// Hourly chance of rain series in percent (right axis).
+    pub fn rain_chance(self, values: &[f32]) -> Self {
+        self.series(|curve| matches!(curve, CurveType::RainChance(_)), values)
+    }
+
+    /// Hourly UV index, used for the gradient under the chart. Only the first 24 values are used.
+    pub fn uv_index(mut self, values: &[u16]) -> Self {
+        for (slot, value) in self.graph.uv_data.iter_mut().zip(values) {
+            *slot = *value;
+        }
+        self
+    }
+
+    /// Hours that get a lightning marker above the chart. Only the first 24 values are used.
+    pub fn lightning_risk(mut self, values: &[bool]) -> Self {
+        for (slot, value) in self.graph.lightning_risk.iter_mut().zip(values) {
+            *slot = *value;
+        }
+        self
+    }
+
+    /// Hourly lower/upper rain amount in mm, drawn as a band on the right axis where
+    /// `full_scale_mm` reaches the top. Only the first 24 values are used.
+    pub fn rain_band(mut self, values: &[Option<(f32, f32)>], full_scale_mm: f32) -> Self {
+        for (slot, value) in self.graph.rain_band.iter_mut().zip(values) {
+            *slot = *value;
+        }
+        self.graph.rain_band_full_scale = full_scale_mm;
+        self
+    }
+
+    /// Hourly lowest/highest ensemble temperature, drawn as a band behind the temperature
+    /// curves (left axis). Only the first 24 values are used.
+    pub fn temperature_band(mut self, values: &[Option<(f32, f32)>]) -> Self {
+        for (slot, value) in self.graph.temperature_band.iter_mut().zip(values) {
+            *slot = *value;
+        }
+        self
+    }
+
+    /// Hourly relative humidity in percent, drawn as a line on the right axis.
+    /// Only the first 24 values are used.
+    pub fn humidity(mut self, values: &[f32]) -> Self {
+        for (slot, value) in self.graph.humidity.iter_mut().zip(values) {
+            *slot = Some(*value);
+        }
+        self
+    }
+
+    /// Humidity in percent below which the air is dry and above which it is humid, drawn as
+    /// bands behind the humidity line.
+    pub fn humidity_comfort_range(mut self, dry_below: f32, humid_above: f32) -> Self {
+        self.graph.humidity_comfort_range = Some((dry_below, humid_above));
+        self
+    }
+
+    pub fn build(self) -> HourlyForecastGraph {
+        self.graph
+    }
+
+    fn series(mut self, is_target: impl Fn(&CurveType) -> bool, values: &[f32]) -> Self {
+        if let Some(curve) = self.graph.curves.iter_mut().find(|curve| is_target(curve)) {
+            let data = curve.data_mut();
+            data.points = values
+                .iter()
+                .enumerate()
+                .map(|(x, &y)| Point { x: x as f32, y })
+                .collect();
+        }
+        self
+    }
+}
+
+/// Merges every `hours_per_point` consecutive hourly values into one point with `combine`, the
+/// last point may merge fewer hours
+pub fn downsample<T, U>(
+    values: &[T],
+    hours_per_point: usize,
+    combine: impl Fn(&[T]) -> U,
+) -> Vec<U> {
+    values.chunks(hours_per_point.max(1)).map(combine).collect()
+}
+
 pub enum GraphDataPath {
     Temp(String),
     TempFeelLike(String),
@@ -165,6 +378,27 @@ impl UVIndexCategory {
     }
 }
 
+/// Builds a closed SVG path for a band from `(x, lower, upper)` points:
+/// along the upper edge left to right, then back along the lower edge.
+fn closed_band_path(points: &[(f32, f32, f32)]) -> String {
+    if points.is_empty() {
+        return String::new();
+    }
+
+    let upper = points.iter().map(|&(x, _, upper)| (x, upper));
+    let lower = points.iter().rev().map(|&(x, lower, _)| (x, lower));
+    let path = upper
+        .chain(lower)
+        .enumerate()
+        .map(|(i, (x, y))| {
+            let command = if i == 0 { "M" } else { "L" };
+            format!("{command} {x:.4} {y:.4}")
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    format!("{path} Z")
+}
+
 /// Convert a list of points to a list of Bézier curves
 /// using the Catmull-Rom to Bézier conversion
 ///
@@ -222,6 +456,67 @@ pub fn catmull_rom_to_bezier(points: Vec<Point>) -> Vec<Curve> {
     curves
 }
 
+/// Width and height of the box sparkline paths are drawn in, templates scale it with a `viewBox`
+pub const SPARKLINE_VIEWBOX: (f32, f32) = (100.0, 20.0);
+
+/// Path data of a sparkline through `points`, `(hour of the day, value)` pairs in time order.
+///
+/// The day runs from left to right across [`SPARKLINE_VIEWBOX`], the lowest value sits at
+/// the bottom and the highest at the top. A flat series is drawn through the middle.
+///
+/// # Returns
+///
+/// * `None` for fewer than two points, a single hour has no shape.
+pub fn sparkline_path(points: &[(f32, f32)]) -> Option<String> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let (width, height) = SPARKLINE_VIEWBOX;
+    let min = points
+        .iter()
+        .map(|&(_, value)| value)
+        .fold(f32::INFINITY, f32::min);
+    let max = points
+        .iter()
+        .map(|&(_, value)| value)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let scale_y = |value: f32| {
+        if max > min {
+            height - (value - min) / (max - min) * height
+        } else {
+            height / 2.0
+        }
+    };
+
+    let path = points
+        .iter()
+        .map(|&(hour, value)| Point {
+            x: hour / 23.0 * width,
+            y: scale_y(value),
+        })
+        .enumerate()
+        .map(|(i, point)| {
+            if i == 0 {
+                format!("M {:.2} {:.2}", point.x, point.y)
+            } else {
+                format!(" L {:.2} {:.2}", point.x, point.y)
+            }
+        })
+        .collect();
+    Some(path)
+}
+
+/// A span of the graph's hours shaded as a band, with a callout above it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+    /// Hours from the graph's first hour to the start of the window
+    pub from_hours: f32,
+    /// Hours from the graph's first hour to the end of the window
+    pub to_hours: f32,
+    pub callout: String,
+}
+
 /// Collect all axis paths and labels into one struct
 pub struct AxisPaths {
     pub x_axis_path: String,
@@ -235,6 +530,11 @@ pub struct AxisPaths {
 
 /// Create the axis paths and labels for the graph
 impl HourlyForecastGraph {
+    /// Starts building a chart, see [`HourlyForecastGraphBuilder`].
+    pub fn builder() -> HourlyForecastGraphBuilder {
+        HourlyForecastGraphBuilder::default()
+    }
+
     pub fn create_axis_with_labels(&self, current_hour: f32, clock: &dyn Clock) -> AxisPaths {
         let range_x = self.ending_x - self.starting_x + 1.0; // +1 because last hour is 23
         let range_y_left = self.max_y - self.min_y;
@@ -333,6 +633,7 @@ impl HourlyForecastGraph {
         y_right_step: f32,
     ) -> String {
         let mut y_right_labels = String::new();
+        let has_rain_band = self.rain_band.iter().any(Option::is_some);
         for k in 0..=self.y_right_ticks {
             let y_val = k as f32 * y_right_step; // percentage step
             if y_val > 100.0 {
@@ -350,14 +651,28 @@ impl HourlyForecastGraph {
 
             // Label (align to the start since it's on the right side)
             let label_x = y_right_axis_x + 10.0;
-            let label_str = format!("{y_val:.0}%");
+            let format = &self.number_format;
+            let label_str = format.with_unit(&format.fixed(y_val, 0), "%");
             y_right_labels.push_str(&format!(
-                r#"<text x="{x}" y="{y}" fill="{colour}"  font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" text-anchor="start" dy="4">{text}</text>"#,
+                r#"<text x="{x}" y="{y}" fill="{colour}"{halo}  font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" text-anchor="start" dy="4">{text}</text>"#,
                 x = label_x,
                 y = ys,
                 colour = self.text_colour,
+                halo = self.halo_attributes(),
                 text = label_str,
             ));
+            // The rain band is in mm, its scale goes under the percentage
+            if has_rain_band {
+                let mm = y_val / 100.0 * self.rain_band_full_scale;
+                y_right_labels.push_str(&format!(
+                    r#"<text x="{x}" y="{y}" fill="{colour}"{halo}  font-size="{RAIN_BAND_AXIS_LABEL_FONT_SIZE}" text-anchor="start" dy="18">{text}</text>"#,
+                    x = label_x,
+                    y = ys,
+                    colour = self.text_colour,
+                    halo = self.halo_attributes(),
+                    text = format.with_unit(&format.number((mm * 10.0).round() / 10.0), "mm"),
+                ));
+            }
         }
         y_right_labels
     }
@@ -390,19 +705,21 @@ impl HourlyForecastGraph {
 
             // Label: placed to the left of the y-axis
             let label_x = y_axis_x - 10.0;
-            let mut label_str = format!("{y_val:.1}°");
+            let format = &self.number_format;
+            let mut label_str = format.with_unit(&format.fixed(y_val, 1), "°");
             let mut font_size = DEFAULT_AXIS_LABEL_FONT_SIZE;
             if j == 0 || j == self.y_left_ticks {
                 // Normalize negative zero when rounding to integer (e.g., -0.1 → 0, not -0)
                 let display_val = if y_val.abs() < 0.5 { 0.0 } else { y_val };
-                label_str = format!("{display_val:.0}°");
+                label_str = format.with_unit(&format.fixed(display_val, 0), "°");
                 font_size = 35;
             }
             y_left_labels.push_str(&format!(
-                r#"<text x="{x}" y="{y}"  fill="{colour}" font-size="{font_size}" text-anchor="end" dx="8" dy="4">{text}</text>"#,
+                r#"<text x="{x}" y="{y}"  fill="{colour}"{halo} font-size="{font_size}" text-anchor="end" dx="8" dy="4">{text}</text>"#,
                 x = label_x,
                 y = ys,
                 colour = self.text_colour,
+                halo = self.halo_attributes(),
                 font_size = font_size,
                 text = label_str
             ));
@@ -421,6 +738,14 @@ impl HourlyForecastGraph {
         x_step: f32,
         clock: &dyn Clock,
     ) -> String {
+        let hours_per_point = self.hours_per_point as f32;
+        // Points where the following days start, midnight after the current hour
+        let day_lines: Vec<(u64, f32)> = (1..)
+            .map(|day| (day, (24.0 * day as f32 - current_hour) / hours_per_point))
+            .take_while(|&(_, x)| x <= self.ending_x)
+            .filter(|&(_, x)| x > 0.0)
+            .collect();
+
         let mut x_val: f32 = 0.0;
         let mut x_labels = String::new();
         for i in 0..=self.x_ticks {
@@ -440,8 +765,9 @@ impl HourlyForecastGraph {
             ));
 
             let x_guideline_len = self.height;
-            // do not draw guideline if it overlaps with tomorrow's line
-            if x_val != (24.0 - current_hour) {
+            let hour = (current_hour + x_val * hours_per_point) % 24.0;
+            // do not draw guideline where a day starts, it would overlap with the day line
+            if x_val == 0.0 || hour != 0.0 {
                 x_axis_guideline_path.push_str(&format!(
                     r#" M {xs} {x_guideline_len} v -{x_guideline_len} m 0 2 v -2"#
                 ));
@@ -449,7 +775,7 @@ impl HourlyForecastGraph {
             // Label: placed below the x-axis line
             let label_x = xs;
             let label_y = self.height + 20.0;
-            let hour = (current_hour + x_val) % 24.0;
+
             let period = if hour < 12.0 { "am" } else { "pm" };
             let display_hour = if hour == 0.0 && period == "am" {
                 12.0
@@ -461,34 +787,33 @@ impl HourlyForecastGraph {
             let label_str = format!("{display_hour:.0}{period}");
 
             x_labels.push_str(&format!(
-                r#"<text x="{x}" y="{y}" fill="{colour}" font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" text-anchor="middle">{text}</text>"#,
+                r#"<text x="{x}" y="{y}" fill="{colour}"{halo} font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" text-anchor="middle">{text}</text>"#,
                 x = label_x,
                 y = label_y,
                 colour = self.text_colour,
+                halo = self.halo_attributes(),
                 text = label_str
             ));
         }
 
-        // Add tomorrow day name vertically in the graph just like the guidelines
-        if current_hour != 0.0 {
-            x_labels.push_str(
-                self.draw_tomorrow_line(map_x(24.0 - current_hour), clock)
-                    .as_str(),
-            );
+        // Add the day names vertically in the graph just like the guidelines
+        for (day, day_x) in day_lines {
+            x_labels.push_str(self.draw_day_line(map_x(day_x), day, clock).as_str());
         }
         x_labels
     }
 
-    fn draw_tomorrow_line(&self, x_coor: f32, clock: &dyn Clock) -> String {
+    /// Dashed line where the day `days_ahead` of today starts, with its name
+    fn draw_day_line(&self, x_coor: f32, days_ahead: u64, clock: &dyn Clock) -> String {
         let tomorrow_day_name = clock
             .now_local()
-            .checked_add_days(chrono::Days::new(1))
+            .checked_add_days(chrono::Days::new(days_ahead))
             .map(|d| d.format("%A").to_string())
             .unwrap_or_else(|| "Tomorrow".to_string());
 
         format!(
             r#"<line x1="{x}" y1="0" x2="{x}" y2="{chart_height}" stroke="{colour}" stroke-width="2" stroke-dasharray="3,3" />
-                   <text x="{x_text}" y="{y_text}" fill="{colour}" font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" font-style="{font_style}"  transform="rotate(-90, {rotate_x_text}, {rotate_y_text})" text-anchor="start">{tomorrow_day_name}</text>"#,
+                   <text x="{x_text}" y="{y_text}" fill="{colour}"{halo} font-size="{DEFAULT_AXIS_LABEL_FONT_SIZE}" font-style="{font_style}"  transform="rotate(-90, {rotate_x_text}, {rotate_y_text})" text-anchor="start">{tomorrow_day_name}</text>"#,
             x = x_coor,
             chart_height = self.height,
             x_text = x_coor + 10.0,
@@ -497,10 +822,21 @@ impl HourlyForecastGraph {
             rotate_x_text = x_coor + 10.0 - 30.0,
             rotate_y_text = (self.height / 2.0) - 15.0,
             colour = self.text_colour,
+            halo = self.halo_attributes(),
             tomorrow_day_name = tomorrow_day_name
         )
     }
 
+    /// Stroke attributes drawing the halo under the label fill, empty without a halo
+    fn halo_attributes(&self) -> String {
+        match &self.text_halo {
+            Some((colour, width)) => format!(
+                r#" stroke="{colour}" stroke-width="{width}" stroke-linejoin="round" paint-order="stroke""#
+            ),
+            None => String::new(),
+        }
+    }
+
     fn initialize_x_y_bounds(&mut self) {
         for curve in &self.curves {
             let min_y_data = curve
@@ -528,6 +864,12 @@ impl HourlyForecastGraph {
             self.ending_x = ending_x_data;
         }
 
+        // Keep the ensemble spread inside the graph
+        for &(lower, upper) in self.temperature_band.iter().flatten() {
+            self.min_y = self.min_y.min(lower);
+            self.max_y = self.max_y.max(upper);
+        }
+
         // println!(
         //     "starting x: {}, ending x: {}",
         //     self.starting_x, self.ending_x
@@ -553,6 +895,199 @@ impl HourlyForecastGraph {
         gradient
     }
 
+    /// Draws a small lightning icon above the graph for every hour flagged in `lightning_risk`.
+    /// Uses the same x scaling as the curves, so it must be called after `draw_graph`.
+    pub fn draw_lightning_risk_icons(&self, icon_path: &str) -> String {
+        if self.ending_x <= 0.0 {
+            return String::new();
+        }
+
+        let icon_size = 24.0;
+        let xfactor = self.width / self.ending_x;
+
+        self.lightning_risk
+            .iter()
+            .enumerate()
+            .filter(|(_, &at_risk)| at_risk)
+            .map(|(hour, _)| {
+                let x_pos = hour as f32 * xfactor - icon_size / 2.0;
+                format!(
+                    r#"<image x="{x_pos:.2}" y="-42" width="{icon_size}" height="{icon_size}" href="{icon_path}"/>"#
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n        ")
+    }
+
+    /// Draws the rain amount uncertainty band as a closed path in the same (flipped)
+    /// coordinate space as the rain curve, `rain_band_full_scale` mm at the top. The right axis
+    /// labels its amounts under the percentages. Returns an empty string when no hour has a range.
+    /// Uses the same x scaling as the curves, so it must be called after `draw_graph`.
+    pub fn draw_rain_band(&self) -> String {
+        if self.ending_x <= 0.0 || self.rain_band.iter().all(Option::is_none) {
+            return String::new();
+        }
+
+        let xfactor = self.width / self.ending_x;
+        let yfactor = self.height / self.rain_band_full_scale;
+        let scale = |mm: f32| (mm * yfactor).clamp(0.0, self.height);
+        let hours = (self.ending_x as usize + 1).min(self.rain_band.len());
+        let band = &self.rain_band[..hours];
+
+        let points: Vec<(f32, f32, f32)> = band
+            .iter()
+            .enumerate()
+            .map(|(hour, range)| {
+                let (lower, upper) =
+                    range.map_or((0.0, 0.0), |(lower, upper)| (scale(lower), scale(upper)));
+                (hour as f32 * xfactor, lower, upper)
+            })
+            .collect();
+
+        closed_band_path(&points)
+    }
+
+    /// Draws the ensemble temperature spread as one closed path per run of consecutive hours
+    /// with data, in the same (flipped) coordinate space as the temperature curves.
+    /// Must be called after `draw_graph`, which sets the bounds the band is scaled with.
+    pub fn draw_temperature_band(&self) -> String {
+        if self.ending_x <= 0.0 {
+            return String::new();
+        }
+
+        let xfactor = self.width / self.ending_x;
+        let hours = (self.ending_x as usize + 1).min(self.temperature_band.len());
+
+        self.temperature_band[..hours]
+            .iter()
+            .enumerate()
+            .map(|(hour, range)| {
+                range.map(|(lower, upper)| {
+                    (
+                        hour as f32 * xfactor,
+                        self.scale_temperature(lower),
+                        self.scale_temperature(upper),
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .split(Option::is_none)
+            .filter(|run| !run.is_empty())
+            .map(|run| closed_band_path(&run.iter().flatten().copied().collect::<Vec<_>>()))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Draws the humidity as a line in the same (flipped) coordinate space as the rain curve,
+    /// on the right axis. Returns an empty string without humidity data.
+    /// Uses the same x scaling as the curves, so it must be called after `draw_graph`.
+    pub fn draw_humidity_curve(&self) -> String {
+        if self.ending_x <= 0.0 {
+            return String::new();
+        }
+
+        let xfactor = self.width / self.ending_x;
+        let yfactor = self.height / 100.0;
+        let hours = (self.ending_x as usize + 1).min(self.humidity.len());
+
+        self.humidity[..hours]
+            .iter()
+            .enumerate()
+            .filter_map(|(hour, humidity)| {
+                humidity
+                    .map(|humidity| (hour as f32 * xfactor, humidity.clamp(0.0, 100.0) * yfactor))
+            })
+            .enumerate()
+            .map(|(i, (x, y))| {
+                let command = if i == 0 { "M" } else { "L" };
+                format!("{command} {x:.4} {y:.4}")
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Horizontal position of the point `hours` after the graph's first hour, clamped to the graph.
+    /// Uses the same x scaling as the curves, so it must be called after `draw_graph`.
+    pub fn x_at_hours(&self, hours: f32) -> f32 {
+        if self.ending_x <= 0.0 {
+            return 0.0;
+        }
+        let point = hours / self.hours_per_point as f32;
+        (point * self.width / self.ending_x).clamp(0.0, self.width)
+    }
+
+    /// Draws `windows` as bands spanning the graph's height and their callouts at the top of
+    /// the graph. Both are empty without windows. Must be called after `draw_graph`.
+    pub fn draw_time_windows(&self, windows: &[TimeWindow]) -> (String, String) {
+        let mut bands = Vec::new();
+        let mut callouts = Vec::new();
+        for window in windows {
+            let from = self.x_at_hours(window.from_hours);
+            let to = self.x_at_hours(window.to_hours);
+            if to <= from {
+                continue;
+            }
+            bands.push(format!(
+                "M {from:.2} 0 H {to:.2} V {:.2} H {from:.2} Z",
+                self.height
+            ));
+            callouts.push(format!(
+                r#"<text x="{:.2}" y="16" fill="{colour}"{halo} font-size="14" text-anchor="middle">{text}</text>"#,
+                (from + to) / 2.0,
+                colour = self.text_colour,
+                halo = self.halo_attributes(),
+                text = escape_xml(&window.callout)
+            ));
+        }
+        (bands.join(" "), callouts.join("\n        "))
+    }
+
+    /// Draws the dry and humid comfort bands across the graph, below and above the comfort
+    /// range of the right axis. Both are empty without humidity data or a comfort range.
+    pub fn draw_humidity_comfort_bands(&self) -> (String, String) {
+        let Some((dry_below, humid_above)) = self.humidity_comfort_range else {
+            return (String::new(), String::new());
+        };
+        if self.humidity.iter().all(Option::is_none) {
+            return (String::new(), String::new());
+        }
+
+        let yfactor = self.height / 100.0;
+        let band = |lower: f32, upper: f32| {
+            format!(
+                "M 0 {:.4} H {:.4} V {:.4} H 0 Z",
+                lower * yfactor,
+                self.width,
+                upper * yfactor
+            )
+        };
+        (band(0.0, dry_below), band(humid_above, 100.0))
+    }
+
+    /// Vertical scaling factor for the temperature curves (left axis)
+    fn temperature_yfactor(&self) -> f32 {
+        if self.max_y >= 0.0 && self.min_y < 0.0 {
+            self.height / (self.max_y + self.min_y.abs())
+        } else if self.min_y < 0.0 && self.max_y < 0.0 {
+            // both are negative - use the absolute difference
+            self.height / (self.min_y.abs() - self.max_y.abs())
+        } else {
+            // when both are positive
+            self.height / (self.max_y - self.min_y)
+        }
+    }
+
+    /// Maps a temperature onto the graph height
+    fn scale_temperature(&self, value: f32) -> f32 {
+        // If the minimum y value is negative, we need to adjust the y value
+        // to ensure it's correctly placed on the graph
+        if self.min_y < 0.0 {
+            (value + self.min_y.abs()) * self.temperature_yfactor()
+        } else {
+            (value - self.min_y) * self.temperature_yfactor()
+        }
+    }
+
     pub fn draw_graph(&mut self) -> Result<Vec<GraphDataPath>, Error> {
         // Calculate the minimum and maximum x values from the points
         let mut data_path = vec![];
@@ -564,17 +1099,7 @@ impl HourlyForecastGraph {
             let xfactor = self.width / self.ending_x;
             let yfactor = match curve {
                 CurveType::RainChance(_) => self.height / 100.0, // Rain data is in percentage
-                CurveType::ActualTemp(_) | CurveType::TempFeelLike(_) => {
-                    if self.max_y >= 0.0 && self.min_y < 0.0 {
-                        self.height / (self.max_y + self.min_y.abs())
-                    } else if self.min_y < 0.0 && self.max_y < 0.0 {
-                        // both are negative - use the absolute difference
-                        self.height / (self.min_y.abs() - self.max_y.abs())
-                    } else {
-                        // when both are positive
-                        self.height / (self.max_y - self.min_y)
-                    }
-                }
+                CurveType::ActualTemp(_) | CurveType::TempFeelLike(_) => self.temperature_yfactor(),
             };
 
             // println!("X factor: {}, Y factor: {}", xfactor, yfactor);
@@ -588,13 +1113,7 @@ impl HourlyForecastGraph {
                     y: match curve {
                         CurveType::RainChance(_) => val.y * yfactor,
                         CurveType::ActualTemp(_) | CurveType::TempFeelLike(_) => {
-                            // If the minimum y value is negative, we need to adjust the y value
-                            // to ensure it's correctly placed on the graph
-                            if self.min_y < 0.0 {
-                                (val.y + self.min_y.abs()) * yfactor
-                            } else {
-                                (val.y - self.min_y) * yfactor
-                            }
+                            self.scale_temperature(val.y)
                         }
                     },
                 })
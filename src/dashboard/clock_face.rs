@@ -0,0 +1,58 @@
+//! Analog clock face showing the render time, for templates that want a decorative time display
+//!
+//! The face is drawn in a 100x100 box centred on the origin and scaled to the configured size.
+
+use chrono::{NaiveTime, Timelike};
+
+use crate::configs::settings::{ClockFace, Colours};
+use crate::dashboard::panel::Panel;
+
+const FACE_RADIUS: f32 = 47.0;
+const HOUR_HAND_LENGTH: f32 = 25.0;
+const MINUTE_HAND_LENGTH: f32 = 38.0;
+
+/// Builds the clock face panel for `time` in the text colour of `colours`, `None` when the clock
+/// face is disabled
+pub fn clock_face(settings: &ClockFace, time: NaiveTime, colours: &Colours) -> Option<Panel> {
+    if !settings.enabled {
+        return None;
+    }
+
+    let colour = &colours.text_colour;
+    let (hour_angle, minute_angle) = hand_angles(time);
+
+    let ticks: String = (0..12)
+        .map(|tick| {
+            // Longer ticks at 12, 3, 6 and 9 o'clock
+            let inner = if tick % 3 == 0 { 37.0 } else { 41.0 };
+            format!(
+                r#"<line y1="-{inner}" y2="-{FACE_RADIUS}" stroke-width="{}" transform="rotate({})" />"#,
+                if tick % 3 == 0 { 4 } else { 2 },
+                tick * 30
+            )
+        })
+        .collect();
+
+    Some(Panel::svg(
+        settings.x,
+        settings.y,
+        settings.size,
+        settings.size,
+        format!(
+            r#"<svg viewBox="-50 -50 100 100" width="100%" height="100%" stroke="{colour}" stroke-linecap="round">
+<circle r="{FACE_RADIUS}" fill="none" stroke-width="3" />
+{ticks}
+<line y2="-{HOUR_HAND_LENGTH}" stroke-width="6" transform="rotate({hour_angle})" />
+<line y2="-{MINUTE_HAND_LENGTH}" stroke-width="4" transform="rotate({minute_angle})" />
+<circle r="4" fill="{colour}" stroke="none" />
+</svg>"#
+        ),
+    ))
+}
+
+/// Clockwise angles in degrees from 12 o'clock of the hour and minute hands
+pub fn hand_angles(time: NaiveTime) -> (f32, f32) {
+    let minutes = time.minute() as f32 + time.second() as f32 / 60.0;
+    let hours = (time.hour() % 12) as f32 + minutes / 60.0;
+    (hours * 30.0, minutes * 6.0)
+}
@@ -0,0 +1,131 @@
+//! Commute windows on the hourly graph
+//!
+//! The `[[commute_windows]]`, e.g. 08:00–09:00 and 17:00–18:00, are shaded on the hourly graph
+//! with a callout of what the way there and back will be like: the temperature range, the
+//! highest rain chance and the rain expected in the window. A window is shown every time it falls
+//! in the hours of the graph, one already under way from the graph's first hour.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+
+use crate::configs::settings::CommuteWindow;
+use crate::dashboard::chart::TimeWindow;
+use crate::dashboard::number_format::NumberFormat;
+use crate::domain::models::HourlyForecast;
+
+/// The forecast of one commute window on one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommuteSummary {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    /// Lowest and highest temperature of the hours overlapping the window
+    pub temperature: Option<(f32, f32)>,
+    /// Highest rain chance of those hours
+    pub rain_chance: Option<u16>,
+    /// Rain expected in mm, the upper amounts of those hours added up
+    pub rain_amount: u16,
+}
+
+impl CommuteSummary {
+    /// Summarises the hours of `hourly` overlapping `start` to `end`
+    pub fn of(hourly: &[HourlyForecast], start: DateTime<Local>, end: DateTime<Local>) -> Self {
+        let hours: Vec<&HourlyForecast> = hourly
+            .iter()
+            .filter(|hour| hour.time < end && hour.time + Duration::hours(1) > start)
+            .collect();
+        let temperature = hours.iter().map(|hour| hour.temperature.value).fold(
+            None,
+            |range: Option<(f32, f32)>, value| match range {
+                Some((low, high)) => Some((low.min(value), high.max(value))),
+                None => Some((value, value)),
+            },
+        );
+        Self {
+            start,
+            end,
+            temperature,
+            rain_chance: hours
+                .iter()
+                .filter_map(|hour| hour.precipitation.chance)
+                .max(),
+            rain_amount: hours
+                .iter()
+                .filter_map(|hour| hour.precipitation.amount_max)
+                .sum(),
+        }
+    }
+
+    /// The callout text, e.g. "12–14° · 40% · 1mm", the rain amount only when some is expected
+    pub fn callout(&self, format: &NumberFormat) -> String {
+        let mut parts = Vec::new();
+        if let Some((low, high)) = self.temperature {
+            let (low, high) = (low.round(), high.round());
+            parts.push(if low == high {
+                format!("{}°", format.number(low))
+            } else {
+                format!("{}–{}°", format.number(low), format.number(high))
+            });
+        }
+        if let Some(chance) = self.rain_chance {
+            parts.push(format.with_unit(&chance.to_string(), "%"));
+        }
+        if self.rain_amount > 0 {
+            parts.push(format.with_unit(&self.rain_amount.to_string(), "mm"));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Local time `time` on `date`, the earlier one when the clocks go back
+fn at(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+/// Summaries of every occurrence of `windows` between `graph_start` and `graph_end`, in order
+pub fn commute_summaries(
+    windows: &[CommuteWindow],
+    hourly: &[HourlyForecast],
+    graph_start: DateTime<Local>,
+    graph_end: DateTime<Local>,
+) -> Vec<CommuteSummary> {
+    let mut summaries = Vec::new();
+    for date in graph_start
+        .date_naive()
+        .iter_days()
+        .take_while(|date| *date <= graph_end.date_naive())
+    {
+        for window in windows {
+            let (Some(start), Some(end)) =
+                (at(date, window.start.time()), at(date, window.end.time()))
+            else {
+                continue;
+            };
+            if end <= graph_start || start >= graph_end {
+                continue;
+            }
+            summaries.push(CommuteSummary::of(
+                hourly,
+                start.max(graph_start),
+                end.min(graph_end),
+            ));
+        }
+    }
+    summaries.sort_by_key(|summary| summary.start);
+    summaries
+}
+
+/// The summaries as graph windows, hours counted from `graph_start`
+pub fn time_windows(
+    summaries: &[CommuteSummary],
+    graph_start: DateTime<Local>,
+    format: &NumberFormat,
+) -> Vec<TimeWindow> {
+    let hours_from_start = |time: DateTime<Local>| (time - graph_start).num_minutes() as f32 / 60.0;
+    summaries
+        .iter()
+        .map(|summary| TimeWindow {
+            from_hours: hours_from_start(summary.start),
+            to_hours: hours_from_start(summary.end),
+            callout: summary.callout(format),
+        })
+        .collect()
+}
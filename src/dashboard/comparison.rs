@@ -0,0 +1,178 @@
+//! Two locations side by side
+//!
+//! With `[comparison]` enabled the dashboard shows the configured location next to a profile's,
+//! e.g. home and the parents' city, instead of the template: each column has the current
+//! conditions and a compact row per day. Both forecasts are fetched in the same run, the
+//! profile's with its own provider settings and cache, and rendered into one frame.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::clock::Clock;
+use crate::configs::settings::{Colours, Comparison};
+use crate::domain::models::{DailyForecast, HourlyForecast, Temperature};
+use crate::errors::{DashboardError, Description};
+use crate::utils::escape_xml;
+use crate::weather::icons::Icon;
+use crate::CONFIG;
+
+/// Top of the daily rows, under the current conditions
+const DAILY_TOP: f32 = 190.0;
+/// Room under the daily rows for the footer
+const FOOTER_HEIGHT: f32 = 35.0;
+/// More rows than this don't fit at a readable size
+const MAX_DAYS: usize = 7;
+
+/// What a column shows of one location
+#[derive(Debug, Clone)]
+pub struct LocationSummary {
+    pub label: String,
+    /// The latest hour that has started, the first hour when none has
+    pub current: Option<HourlyForecast>,
+    /// Today and the following days
+    pub days: Vec<DailyForecast>,
+}
+
+impl LocationSummary {
+    /// Summarises the forecasts of a location at `now`, with `days` daily rows from `today`
+    pub fn at(
+        label: &str,
+        hourly: &[HourlyForecast],
+        daily: &[DailyForecast],
+        now: DateTime<Utc>,
+        today: NaiveDate,
+        days: usize,
+    ) -> Self {
+        let current = hourly
+            .iter()
+            .filter(|hour| hour.time <= now)
+            .max_by_key(|hour| hour.time)
+            .or_else(|| hourly.first())
+            .cloned();
+        let days = daily
+            .iter()
+            .filter(|day| day.date.is_some_and(|date| date >= today))
+            .take(days.min(MAX_DAYS))
+            .cloned()
+            .collect();
+        Self {
+            label: label.to_string(),
+            current,
+            days,
+        }
+    }
+}
+
+/// Heading of the right column, the profile name unless `other_label` is set
+pub fn other_label(settings: &Comparison) -> &str {
+    if settings.other_label.is_empty() {
+        &settings.profile
+    } else {
+        &settings.other_label
+    }
+}
+
+/// Renders the comparison frame of `size`, e.g. the dashboard's [`frame_size`], in `colours`. The
+/// most important of `warnings` is named in the footer.
+///
+/// [`frame_size`]: crate::dashboard::panel::frame_size
+pub fn render_comparison_svg(
+    home: &LocationSummary,
+    other: &LocationSummary,
+    warnings: &[DashboardError],
+    size: (f32, f32),
+    colours: &Colours,
+    clock: &dyn Clock,
+) -> String {
+    let (width, height) = size;
+    let column_width = width / 2.0;
+    let daily_bottom = height - FOOTER_HEIGHT;
+    let now = clock.now_local();
+    let mut footer = format!("Updated {}", now.format(&CONFIG.render_options.time_format));
+    if let Some(warning) = warnings.iter().max_by_key(|warning| warning.priority()) {
+        footer.push_str(" · ");
+        footer.push_str(warning.short_description());
+    }
+
+    format!(
+        r#"<svg width="{width}" height="{height}" font-family="Roboto, sans-serif" xmlns="http://www.w3.org/2000/svg">
+    <rect width="{width}" height="{height}" fill="{}" />
+    <line x1="{column_width}" y1="20" x2="{column_width}" y2="{daily_bottom}" stroke="{}" stroke-width="2" />
+    {}
+    {}
+    <text x="{}" y="{}" font-size="14" text-anchor="middle" fill="{}">{}</text>
+</svg>"#,
+        colours.background_colour,
+        colours.text_colour,
+        location_column(home, 0.0, column_width, daily_bottom, colours),
+        location_column(other, column_width, column_width, daily_bottom, colours),
+        width / 2.0,
+        height - 14.0,
+        colours.text_colour,
+        escape_xml(&footer)
+    )
+}
+
+/// A location's column starting at `x`, `width` wide with the daily rows down to `daily_bottom`
+fn location_column(
+    location: &LocationSummary,
+    x: f32,
+    width: f32,
+    daily_bottom: f32,
+    colours: &Colours,
+) -> String {
+    let text_colour = &colours.text_colour;
+    let mut svg = format!(
+        r#"<text x="{:.1}" y="44" font-size="28" font-weight="bold" fill="{text_colour}">{}</text>"#,
+        x + 20.0,
+        escape_xml(&location.label)
+    );
+
+    match &location.current {
+        Some(current) => svg.push_str(&format!(
+            r#"
+    <image x="{:.1}" y="60" width="96" height="96" href="{}" />
+    <text x="{:.1}" y="118" font-size="56" font-weight="bold" fill="{text_colour}">{}°</text>
+    <text x="{:.1}" y="152" font-size="18" fill="{text_colour}">Feels {}° · {}%</text>"#,
+            x + 16.0,
+            current.get_icon_path(),
+            x + 124.0,
+            current.temperature,
+            x + 126.0,
+            current.apparent_temperature,
+            current.relative_humidity
+        )),
+        None => svg.push_str(&format!(
+            r#"
+    <text x="{:.1}" y="118" font-size="24" fill="{text_colour}">No forecast</text>"#,
+            x + 20.0
+        )),
+    }
+
+    let row_height = (daily_bottom - DAILY_TOP) / location.days.len().max(5) as f32;
+    for (index, day) in location.days.iter().enumerate() {
+        let top = DAILY_TOP + index as f32 * row_height;
+        let middle = top + row_height / 2.0;
+        let icon_size = row_height * 0.9;
+        let name = day
+            .date
+            .map(|date| date.format("%a").to_string())
+            .unwrap_or_default();
+        let temperature = |temperature: Option<Temperature>| {
+            temperature.map_or_else(|| "NA".to_string(), |t| format!("{t}°"))
+        };
+        svg.push_str(&format!(
+            r#"
+    <text x="{:.1}" y="{middle:.1}" dominant-baseline="middle" font-size="20" fill="{text_colour}">{name}</text>
+    <image x="{:.1}" y="{:.1}" width="{icon_size:.1}" height="{icon_size:.1}" href="{}" />
+    <text x="{:.1}" y="{middle:.1}" dominant-baseline="middle" font-size="20" text-anchor="end" fill="{text_colour}">{} / {}</text>"#,
+            x + 20.0,
+            x + 100.0,
+            middle - icon_size / 2.0,
+            day.get_icon_path(),
+            x + width - 24.0,
+            temperature(day.temp_max),
+            temperature(day.temp_min)
+        ));
+    }
+    svg
+}
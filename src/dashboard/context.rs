@@ -1,20 +1,149 @@
 use crate::{
+    calendar,
     clock::Clock,
-    constants::NOT_AVAILABLE_ICON_PATH,
-    dashboard::chart::{GraphDataPath, HourlyForecastGraph},
-    domain::models::{DailyForecast, HourlyForecast},
+    configs::settings::{
+        Colours, DashboardSettings, DiagnosticsLayout, MaxTableMetric, RenderOptions,
+        TempColourRange, TomorrowMaxMarker, WindDisplay, WindSpeedUnit,
+    },
+    constants::{NOT_AVAILABLE_ICON_PATH, UV_PROTECTION_THRESHOLD},
+    dashboard::chart::{sparkline_path, GraphDataPath, HourlyForecastGraph},
+    dashboard::commute::{commute_summaries, time_windows},
+    dashboard::number_format::NumberFormat,
+    dashboard::panel::Panel,
+    dashboard::rain_today::RainLog,
+    dashboard::summary::hourly_summary,
+    domain::icons::{AirQuality, Pressure, RelativeHumidity, UVIndex, WindSpeed},
+    domain::models::{minutes_until_rain, DailyForecast, HourlyForecast, Temperature, Wind},
     errors::{DashboardError, Description},
-    logger,
-    utils::{find_max_item_between_dates, get_total_between_dates},
-    weather::icons::{Icon, SunPositionIconName},
+    logger, platform,
+    utils::{find_max_item_between_dates, format_rain_countdown, get_total_between_dates},
+    weather::icons::{Icon, LightningIconName, SpecialDateIconName, SunPositionIconName},
+    weather::solar::{format_day_length, solar_noon},
     CONFIG,
 };
-use chrono::{DateTime, Local, NaiveDate, Timelike, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::chart::{CurveType, ElementVisibility, FontStyle};
 
+/// Space around the graph for the axis labels, in graph units
+pub const GRAPH_VIEWBOX_LEFT_MARGIN: f32 = 50.0;
+pub const GRAPH_VIEWBOX_RIGHT_MARGIN: f32 = 40.0;
+
+/// `viewBox` of the graph's `<svg>` element: the graph plus room for the axis labels
+pub fn graph_viewbox(width: f32, height: f32) -> String {
+    format!(
+        "-{GRAPH_VIEWBOX_LEFT_MARGIN} 0 {} {height}",
+        width + GRAPH_VIEWBOX_LEFT_MARGIN + GRAPH_VIEWBOX_RIGHT_MARGIN
+    )
+}
+
+/// `<image>` elements of the diagnostic icons stacked as `layout` says, highest priority in front
+pub fn cascading_icons_svg(diagnostics: &[DashboardError], layout: &DiagnosticsLayout) -> String {
+    let mut sorted_diagnostics = diagnostics.to_vec();
+    sorted_diagnostics.sort_by_key(|e| std::cmp::Reverse(e.priority())); // High to low
+    let icon_size = layout.icon_size;
+
+    // Reverse order so lowest priority renders first (appears in back)
+    sorted_diagnostics
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, error)| {
+            let x_pos = layout.x + index as f32 * layout.x_offset;
+            let y_pos = layout.y + index as f32 * layout.y_offset;
+            format!(
+                r#"<image x="{x_pos}" y="{y_pos}" width="{icon_size}" height="{icon_size}" href="{}"/>"#,
+                error.get_icon_path()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n        ")
+}
+
+/// Font size of the Now/Max24h values
+const TABLE_VALUE_FONT_SIZE: &str = "30";
+/// Smaller font size so "18 / 35" fits the room of a single wind speed
+const WIND_BOTH_FONT_SIZE: &str = "16";
+
+/// Font size of the wind values for the configured `wind_display`
+fn wind_font_size(wind_display: WindDisplay) -> String {
+    match wind_display {
+        WindDisplay::Both => WIND_BOTH_FONT_SIZE,
+        _ => TABLE_VALUE_FONT_SIZE,
+    }
+    .to_string()
+}
+
+/// Rendered values for one row of the Now/Max24h table
+struct TableRowValues {
+    icon: String,
+    unit: String,
+    font_size: String,
+    now: String,
+    max: String,
+    max_font_style: String,
+    max_suffix: String,
+    max_superscript: String,
+}
+
+/// Value of a table metric for one hour, in display units
+fn table_metric_value(
+    metric: MaxTableMetric,
+    forecast: &HourlyForecast,
+    render_options: &RenderOptions,
+) -> Option<f32> {
+    match metric {
+        MaxTableMetric::Uv => Some(forecast.uv_index as f32),
+        MaxTableMetric::Wind => Some(Wind::convert_speed(
+            forecast
+                .wind
+                .get_speed(render_options.wind_display.uses_gust()),
+            render_options.wind_speed_unit,
+        ) as f32),
+        MaxTableMetric::Humidity => Some(forecast.relative_humidity as f32),
+        MaxTableMetric::Pressure => forecast.pressure,
+        MaxTableMetric::RainRate | MaxTableMetric::RainToday => {
+            Some(forecast.precipitation.calculate_median())
+        }
+        MaxTableMetric::Aqi => forecast.aqi.map(f32::from),
+    }
+}
+
+fn table_metric_icon(metric: MaxTableMetric, forecast: &HourlyForecast) -> String {
+    match metric {
+        MaxTableMetric::Uv => UVIndex(forecast.uv_index).get_icon_path(),
+        MaxTableMetric::Wind => forecast.wind.get_icon_path(),
+        MaxTableMetric::Humidity => RelativeHumidity(forecast.relative_humidity).get_icon_path(),
+        MaxTableMetric::Pressure => Pressure(forecast.pressure).get_icon_path(),
+        MaxTableMetric::RainRate | MaxTableMetric::RainToday => {
+            forecast.precipitation.get_icon_path()
+        }
+        MaxTableMetric::Aqi => AirQuality.get_icon_path(),
+    }
+}
+
+fn table_metric_unit(metric: MaxTableMetric, wind_speed_unit: WindSpeedUnit) -> String {
+    match metric {
+        MaxTableMetric::Uv | MaxTableMetric::Humidity => String::new(),
+        MaxTableMetric::Wind => wind_speed_unit.to_string(),
+        MaxTableMetric::Pressure => "hPa".to_string(),
+        MaxTableMetric::RainRate => "mm/h".to_string(),
+        MaxTableMetric::RainToday => "mm".to_string(),
+        MaxTableMetric::Aqi => "AQI".to_string(),
+    }
+}
+
+fn format_table_value(metric: MaxTableMetric, value: Option<f32>, format: &NumberFormat) -> String {
+    match (metric, value) {
+        (_, None) => "NA".to_string(),
+        (MaxTableMetric::RainRate, Some(value)) => format.number(value),
+        (MaxTableMetric::RainToday, Some(value)) => format.fixed(value, 1),
+        (_, Some(value)) => format.fixed(value, 0),
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Context {
     // colours
@@ -26,21 +155,84 @@ pub struct Context {
     pub actual_temp_colour: String,
     pub feels_like_colour: String,
     pub rain_colour: String,
+    pub humidity_colour: String,
+    pub humidity_dry_colour: String,
+    pub humidity_humid_colour: String,
     // any weather element that is not graph
     pub max_uv_index: String,
     pub max_uv_index_font_style: String,
+    /// Max24h wind in the configured `wind_display`, e.g. "18 / 35" for both speeds
     pub max_gust_speed: String,
     pub max_gust_speed_font_style: String,
+    pub max_wind_sustained_speed: String,
+    pub max_wind_gust_speed: String,
     pub max_relative_humidity: String,
     pub max_relative_humidity_font_style: String,
+    // configurable Now/Max24h table rows
+    pub table_row1_visibility: String,
+    pub table_row1_icon: String,
+    pub table_row1_unit: String,
+    pub table_row1_font_size: String,
+    pub table_row1_now: String,
+    pub table_row1_max: String,
+    pub table_row1_max_font_style: String,
+    pub table_row1_max_suffix: String,
+    pub table_row1_max_superscript: String,
+    pub table_row2_visibility: String,
+    pub table_row2_icon: String,
+    pub table_row2_unit: String,
+    pub table_row2_font_size: String,
+    pub table_row2_now: String,
+    pub table_row2_max: String,
+    pub table_row2_max_font_style: String,
+    pub table_row2_max_suffix: String,
+    pub table_row2_max_superscript: String,
+    pub table_row3_visibility: String,
+    pub table_row3_icon: String,
+    pub table_row3_unit: String,
+    pub table_row3_font_size: String,
+    pub table_row3_now: String,
+    pub table_row3_max: String,
+    pub table_row3_max_font_style: String,
+    pub table_row3_max_suffix: String,
+    pub table_row3_max_superscript: String,
     pub total_rain_today: String,
+    /// `total_rain_today` with its unit placed for the locale, e.g. "2.5mm" or "2,5 mm"
+    pub total_rain_today_with_unit: String,
+    /// Rain of today's hours before the current one, from the hourly forecast and the rain log
+    pub rain_fallen_today: String,
+    /// Rain of the rest of today, the current hour included
+    pub rain_expected_today: String,
+    /// `rain_expected_today` with its unit placed for the locale, e.g. "2.5mm" or "2,5 mm"
+    pub rain_expected_today_with_unit: String,
+    /// Rain since midnight, measured by the nearest station's gauge with BOM observations,
+    /// otherwise `rain_fallen_today`
+    pub rain_since_midnight: String,
+    /// `rain_since_midnight` with its unit placed for the locale, e.g. "7.0mm" or "7,0 mm"
+    pub rain_since_midnight_with_unit: String,
+    /// Where `rain_since_midnight` comes from, "observed" or "forecast", empty when unknown
+    pub rain_since_midnight_source: String,
     pub temp_unit: String,
     pub current_wind_speed_unit: String,
     pub current_hour_actual_temp: String,
+    /// Temperatures and wind speed with their unit placed for the locale, e.g. "23°C" or "23 °C"
+    pub current_hour_actual_temp_with_unit: String,
+    pub current_hour_feels_like_with_unit: String,
+    pub current_hour_wind_speed_with_unit: String,
     pub current_hour_weather_icon: String,
+    /// Condition key of the current hour, e.g. `partly_cloudy`
+    pub current_condition: String,
+    /// Description of the current hour's condition, e.g. "Partly cloudy"
+    pub current_condition_text: String,
     pub current_hour_feels_like: String,
+    /// Current wind in the configured `wind_display`, e.g. "18 / 35" for both speeds
     pub current_hour_wind_speed: String,
     pub current_hour_wind_icon: String,
+    pub current_hour_wind_sustained_speed: String,
+    pub current_hour_wind_gust_speed: String,
+    pub current_hour_wind_gust_icon: String,
+    /// Smaller when both wind speeds are shown
+    pub current_hour_wind_font_size: String,
     pub current_hour_uv_index: String,
     pub current_hour_uv_index_icon: String,
     pub current_hour_relative_humidity: String,
@@ -49,17 +241,41 @@ pub struct Context {
     pub current_day_time: String,
     pub current_hour_rain_amount: String,
     pub current_hour_rain_measure_icon: String,
+    pub rain_starts_in: String,
+    /// Text summary of the next 24 hours, e.g. "Showers easing this afternoon, windy overnight"
+    pub hourly_summary: String,
+    /// Image panel as a PNG data URI, for an `<image href="{image_panel_href}">` region
+    pub image_panel_href: String,
+    pub image_panel_visibility: String,
+    /// Swell, waves and sea wind warning of the marine layout, for an `<svg>` region of the
+    /// template: the fragment fills the region, whatever its size
+    pub marine_svg: String,
+    pub marine_visibility: String,
+    pub rain_starts_in_visibility: String,
     pub sunset_time: String,
     pub sunrise_time: String,
     pub sunset_icon: String,
     pub sunrise_icon: String,
+    pub sun_icons_visibility: String,
+    pub sun_table_visibility: String,
+    pub solar_noon_time: String,
+    pub day_length: String,
+    pub uv_protection_window: String,
+    pub thunder_icon: String,
+    pub special_date_icon: String,
     // these values might not be used
     pub graph_height: String,
     pub graph_width: String,
+    pub graph_viewbox: String,
     // graph and curves
     pub actual_temp_curve_data: String,
     pub feel_like_curve_data: String,
     pub rain_curve_data: String,
+    pub rain_band_data: String,
+    pub temperature_band_data: String,
+    pub humidity_curve_data: String,
+    pub humidity_dry_band_data: String,
+    pub humidity_humid_band_data: String,
     pub x_axis_path: String,
     pub x_axis_guideline_path: String,
     pub y_left_axis_path: String,
@@ -68,31 +284,117 @@ pub struct Context {
     pub y_right_axis_path: String,
     pub y_right_labels: String,
     pub uv_gradient: String,
+    pub lightning_risk_icons_svg: String,
+    /// Bands of the commute windows on the graph, empty without `[[commute_windows]]`
+    pub commute_band_data: String,
+    pub commute_callouts_svg: String,
     // daily forecast
     pub day2_mintemp: String,
     pub day2_maxtemp: String,
     pub day2_icon: String,
     pub day2_name: String,
+    pub day2_thunder_visibility: String,
+    pub day2_background_colour: String,
+    pub day2_text_colour: String,
+    /// Colour of the max temperature by `temp_colour_range`, the tile's text colour when mild
+    pub day2_temp_colour: String,
+    pub day2_special_date_visibility: String,
+    pub day2_special_date_label: String,
+    pub day2_forecast_changed_visibility: String,
+    pub day2_sparkline: String,
+    pub day2_sparkline_visibility: String,
     pub day3_mintemp: String,
     pub day3_maxtemp: String,
     pub day3_icon: String,
     pub day3_name: String,
+    pub day3_thunder_visibility: String,
+    pub day3_background_colour: String,
+    pub day3_text_colour: String,
+    pub day3_temp_colour: String,
+    pub day3_special_date_visibility: String,
+    pub day3_special_date_label: String,
+    pub day3_forecast_changed_visibility: String,
+    pub day3_sparkline: String,
+    pub day3_sparkline_visibility: String,
     pub day4_mintemp: String,
     pub day4_maxtemp: String,
     pub day4_icon: String,
     pub day4_name: String,
+    pub day4_thunder_visibility: String,
+    pub day4_background_colour: String,
+    pub day4_text_colour: String,
+    pub day4_temp_colour: String,
+    pub day4_special_date_visibility: String,
+    pub day4_special_date_label: String,
+    pub day4_forecast_changed_visibility: String,
+    pub day4_sparkline: String,
+    pub day4_sparkline_visibility: String,
     pub day5_mintemp: String,
     pub day5_maxtemp: String,
     pub day5_icon: String,
     pub day5_name: String,
+    pub day5_thunder_visibility: String,
+    pub day5_background_colour: String,
+    pub day5_text_colour: String,
+    pub day5_temp_colour: String,
+    pub day5_special_date_visibility: String,
+    pub day5_special_date_label: String,
+    pub day5_forecast_changed_visibility: String,
+    pub day5_sparkline: String,
+    pub day5_sparkline_visibility: String,
     pub day6_mintemp: String,
     pub day6_maxtemp: String,
     pub day6_icon: String,
     pub day6_name: String,
+    pub day6_thunder_visibility: String,
+    pub day6_background_colour: String,
+    pub day6_text_colour: String,
+    pub day6_temp_colour: String,
+    pub day6_special_date_visibility: String,
+    pub day6_special_date_label: String,
+    pub day6_forecast_changed_visibility: String,
+    pub day6_sparkline: String,
+    pub day6_sparkline_visibility: String,
     pub day7_mintemp: String,
     pub day7_maxtemp: String,
     pub day7_icon: String,
     pub day7_name: String,
+    pub day7_thunder_visibility: String,
+    pub day7_background_colour: String,
+    pub day7_text_colour: String,
+    pub day7_temp_colour: String,
+    pub day7_special_date_visibility: String,
+    pub day7_special_date_label: String,
+    pub day7_forecast_changed_visibility: String,
+    pub day7_sparkline: String,
+    pub day7_sparkline_visibility: String,
+    // weekly grid, the week rows start on `first_weekday`
+    /// Column of today in its week row, 0 for `first_weekday`
+    pub today_week_column: String,
+    pub day2_week_column: String,
+    /// Week row of the day, 0 for the week of today and 1 for the next
+    pub day2_week_row: String,
+    pub day3_week_column: String,
+    pub day3_week_row: String,
+    pub day4_week_column: String,
+    pub day4_week_row: String,
+    pub day5_week_column: String,
+    pub day5_week_row: String,
+    pub day6_week_column: String,
+    pub day6_week_row: String,
+    pub day7_week_column: String,
+    pub day7_week_row: String,
+    /// Short weekday names in the order of the week row columns
+    pub weekday_header1: String,
+    pub weekday_header2: String,
+    pub weekday_header3: String,
+    pub weekday_header4: String,
+    pub weekday_header5: String,
+    pub weekday_header6: String,
+    pub weekday_header7: String,
+    /// Calendar week of today
+    pub week_number: String,
+    pub next_week_number: String,
     // warning message
     pub diagnostic_message: String,
     pub diagnostic_visibility: String,
@@ -102,12 +404,23 @@ pub struct Context {
 
 impl Default for Context {
     fn default() -> Self {
+        Self::new(&CONFIG)
+    }
+}
+
+impl Context {
+    /// The context before any data is added, with the colours and graph size of `settings`
+    pub fn new(settings: &DashboardSettings) -> Self {
         let na = "NA".to_string();
-        let not_available_icon_path = NOT_AVAILABLE_ICON_PATH.to_string_lossy().to_string();
-        let colours = CONFIG.colours.clone();
-        let render_options = CONFIG.render_options.clone();
-        let graph_height = "300".to_string();
-        let graph_width = "600".to_string();
+        let not_available_icon_path = platform::href(&NOT_AVAILABLE_ICON_PATH);
+        let colours = settings.colours.clone();
+        let render_options = settings.render_options.clone();
+        let graph_height = render_options.graph_height.to_string();
+        let graph_width = render_options.graph_width.to_string();
+        let graph_viewbox = graph_viewbox(
+            render_options.graph_width.into_inner(),
+            render_options.graph_height.into_inner(),
+        );
         Self {
             background_colour: colours.background_colour.to_string(),
             text_colour: colours.text_colour.to_string(),
@@ -117,20 +430,68 @@ impl Default for Context {
             actual_temp_colour: colours.actual_temp_colour.to_string(),
             feels_like_colour: colours.feels_like_colour.to_string(),
             rain_colour: colours.rain_colour.to_string(),
+            humidity_colour: colours.humidity_colour.to_string(),
+            humidity_dry_colour: colours.humidity_dry_colour.to_string(),
+            humidity_humid_colour: colours.humidity_humid_colour.to_string(),
             max_uv_index: na.clone(),
             max_uv_index_font_style: FontStyle::Normal.to_string(),
             max_gust_speed: na.clone(),
             max_gust_speed_font_style: FontStyle::Normal.to_string(),
+            max_wind_sustained_speed: na.clone(),
+            max_wind_gust_speed: na.clone(),
             max_relative_humidity: na.clone(),
             max_relative_humidity_font_style: FontStyle::Normal.to_string(),
+            table_row1_visibility: ElementVisibility::Hidden.to_string(),
+            table_row1_icon: not_available_icon_path.clone(),
+            table_row1_unit: String::new(),
+            table_row1_font_size: TABLE_VALUE_FONT_SIZE.to_string(),
+            table_row1_now: na.clone(),
+            table_row1_max: na.clone(),
+            table_row1_max_font_style: FontStyle::Normal.to_string(),
+            table_row1_max_suffix: String::new(),
+            table_row1_max_superscript: String::new(),
+            table_row2_visibility: ElementVisibility::Hidden.to_string(),
+            table_row2_icon: not_available_icon_path.clone(),
+            table_row2_unit: String::new(),
+            table_row2_font_size: TABLE_VALUE_FONT_SIZE.to_string(),
+            table_row2_now: na.clone(),
+            table_row2_max: na.clone(),
+            table_row2_max_font_style: FontStyle::Normal.to_string(),
+            table_row2_max_suffix: String::new(),
+            table_row2_max_superscript: String::new(),
+            table_row3_visibility: ElementVisibility::Hidden.to_string(),
+            table_row3_icon: not_available_icon_path.clone(),
+            table_row3_unit: String::new(),
+            table_row3_font_size: TABLE_VALUE_FONT_SIZE.to_string(),
+            table_row3_now: na.clone(),
+            table_row3_max: na.clone(),
+            table_row3_max_font_style: FontStyle::Normal.to_string(),
+            table_row3_max_suffix: String::new(),
+            table_row3_max_superscript: String::new(),
             total_rain_today: na.clone(),
+            total_rain_today_with_unit: na.clone(),
+            rain_fallen_today: na.clone(),
+            rain_expected_today: na.clone(),
+            rain_expected_today_with_unit: na.clone(),
+            rain_since_midnight: na.clone(),
+            rain_since_midnight_with_unit: na.clone(),
+            rain_since_midnight_source: String::new(),
             temp_unit: render_options.temp_unit.to_string(),
             current_wind_speed_unit: render_options.wind_speed_unit.to_string(),
             current_hour_actual_temp: na.clone(),
+            current_hour_actual_temp_with_unit: na.clone(),
+            current_hour_feels_like_with_unit: na.clone(),
+            current_hour_wind_speed_with_unit: na.clone(),
             current_hour_weather_icon: not_available_icon_path.clone(),
+            current_condition: na.clone(),
+            current_condition_text: na.clone(),
             current_hour_feels_like: na.clone(),
             current_hour_wind_speed: na.clone(),
             current_hour_wind_icon: not_available_icon_path.clone(),
+            current_hour_wind_sustained_speed: na.clone(),
+            current_hour_wind_gust_speed: na.clone(),
+            current_hour_wind_gust_icon: not_available_icon_path.clone(),
+            current_hour_wind_font_size: wind_font_size(render_options.wind_display),
             current_hour_uv_index: na.clone(),
             current_hour_uv_index_icon: not_available_icon_path.clone(),
             current_hour_relative_humidity: na.clone(),
@@ -139,15 +500,43 @@ impl Default for Context {
             current_day_time: na.clone(),
             current_hour_rain_amount: na.clone(),
             current_hour_rain_measure_icon: not_available_icon_path.clone(),
+            rain_starts_in: String::new(),
+            hourly_summary: String::new(),
+            image_panel_href: String::new(),
+            image_panel_visibility: ElementVisibility::Hidden.to_string(),
+            marine_svg: String::new(),
+            marine_visibility: ElementVisibility::Hidden.to_string(),
+            rain_starts_in_visibility: ElementVisibility::Hidden.to_string(),
             sunrise_time: na.clone(),
             sunset_time: na.clone(),
             sunset_icon: SunPositionIconName::Sunset.get_icon_path(),
             sunrise_icon: SunPositionIconName::Sunrise.get_icon_path(),
+            sun_icons_visibility: if render_options.show_sun_table {
+                ElementVisibility::Hidden.to_string()
+            } else {
+                ElementVisibility::Visible.to_string()
+            },
+            sun_table_visibility: if render_options.show_sun_table {
+                ElementVisibility::Visible.to_string()
+            } else {
+                ElementVisibility::Hidden.to_string()
+            },
+            solar_noon_time: na.clone(),
+            day_length: na.clone(),
+            uv_protection_window: na.clone(),
+            thunder_icon: LightningIconName::LightningBolt.get_icon_path(),
+            special_date_icon: SpecialDateIconName::Star.get_icon_path(),
             graph_height,
             graph_width,
+            graph_viewbox,
             actual_temp_curve_data: String::new(),
             feel_like_curve_data: String::new(),
             rain_curve_data: String::new(),
+            rain_band_data: String::new(),
+            temperature_band_data: String::new(),
+            humidity_curve_data: String::new(),
+            humidity_dry_band_data: String::new(),
+            humidity_humid_band_data: String::new(),
             x_axis_path: String::new(),
             x_axis_guideline_path: String::new(),
             y_left_axis_path: String::new(),
@@ -156,30 +545,109 @@ impl Default for Context {
             y_right_axis_path: String::new(),
             y_right_labels: String::new(),
             uv_gradient: String::new(),
+            lightning_risk_icons_svg: String::new(),
+            commute_band_data: String::new(),
+            commute_callouts_svg: String::new(),
             day2_mintemp: na.clone(),
             day2_maxtemp: na.clone(),
             day2_icon: not_available_icon_path.clone(),
             day2_name: na.clone(),
+            day2_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day2_background_colour: colours.background_colour.to_string(),
+            day2_text_colour: colours.text_colour.to_string(),
+            day2_temp_colour: colours.text_colour.to_string(),
+            day2_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day2_special_date_label: String::new(),
+            day2_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day2_sparkline: String::new(),
+            day2_sparkline_visibility: ElementVisibility::Hidden.to_string(),
             day3_mintemp: na.clone(),
             day3_maxtemp: na.clone(),
             day3_icon: not_available_icon_path.clone(),
             day3_name: na.clone(),
+            day3_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day3_background_colour: colours.background_colour.to_string(),
+            day3_text_colour: colours.text_colour.to_string(),
+            day3_temp_colour: colours.text_colour.to_string(),
+            day3_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day3_special_date_label: String::new(),
+            day3_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day3_sparkline: String::new(),
+            day3_sparkline_visibility: ElementVisibility::Hidden.to_string(),
             day4_mintemp: na.clone(),
             day4_maxtemp: na.clone(),
             day4_icon: not_available_icon_path.clone(),
             day4_name: na.clone(),
+            day4_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day4_background_colour: colours.background_colour.to_string(),
+            day4_text_colour: colours.text_colour.to_string(),
+            day4_temp_colour: colours.text_colour.to_string(),
+            day4_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day4_special_date_label: String::new(),
+            day4_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day4_sparkline: String::new(),
+            day4_sparkline_visibility: ElementVisibility::Hidden.to_string(),
             day5_mintemp: na.clone(),
             day5_maxtemp: na.clone(),
             day5_icon: not_available_icon_path.clone(),
             day5_name: na.clone(),
+            day5_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day5_background_colour: colours.background_colour.to_string(),
+            day5_text_colour: colours.text_colour.to_string(),
+            day5_temp_colour: colours.text_colour.to_string(),
+            day5_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day5_special_date_label: String::new(),
+            day5_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day5_sparkline: String::new(),
+            day5_sparkline_visibility: ElementVisibility::Hidden.to_string(),
             day6_mintemp: na.clone(),
             day6_maxtemp: na.clone(),
             day6_icon: not_available_icon_path.clone(),
             day6_name: na.clone(),
+            day6_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day6_background_colour: colours.background_colour.to_string(),
+            day6_text_colour: colours.text_colour.to_string(),
+            day6_temp_colour: colours.text_colour.to_string(),
+            day6_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day6_special_date_label: String::new(),
+            day6_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day6_sparkline: String::new(),
+            day6_sparkline_visibility: ElementVisibility::Hidden.to_string(),
             day7_mintemp: na.clone(),
             day7_maxtemp: na.clone(),
             day7_icon: not_available_icon_path.clone(),
             day7_name: na.clone(),
+            day7_thunder_visibility: ElementVisibility::Hidden.to_string(),
+            day7_background_colour: colours.background_colour.to_string(),
+            day7_text_colour: colours.text_colour.to_string(),
+            day7_temp_colour: colours.text_colour.to_string(),
+            day7_special_date_visibility: ElementVisibility::Hidden.to_string(),
+            day7_special_date_label: String::new(),
+            day7_forecast_changed_visibility: ElementVisibility::Hidden.to_string(),
+            day7_sparkline: String::new(),
+            day7_sparkline_visibility: ElementVisibility::Hidden.to_string(),
+            today_week_column: String::new(),
+            day2_week_column: String::new(),
+            day2_week_row: String::new(),
+            day3_week_column: String::new(),
+            day3_week_row: String::new(),
+            day4_week_column: String::new(),
+            day4_week_row: String::new(),
+            day5_week_column: String::new(),
+            day5_week_row: String::new(),
+            day6_week_column: String::new(),
+            day6_week_row: String::new(),
+            day7_week_column: String::new(),
+            day7_week_row: String::new(),
+            weekday_header1: String::new(),
+            weekday_header2: String::new(),
+            weekday_header3: String::new(),
+            weekday_header4: String::new(),
+            weekday_header5: String::new(),
+            weekday_header6: String::new(),
+            weekday_header7: String::new(),
+            week_number: String::new(),
+            next_week_number: String::new(),
             diagnostic_message: na,
             diagnostic_visibility: ElementVisibility::Hidden.to_string(),
             diagnostic_icons_svg: String::new(),
@@ -187,25 +655,121 @@ impl Default for Context {
     }
 }
 
-pub struct ContextBuilder {
+/// Colour of a daily tile's max temperature: cold below `temp_colour_range`, hot above it and
+/// the tile's text colour in between or without a forecast
+fn temp_band_colour(
+    temp_max: Option<Temperature>,
+    colours: &Colours,
+    text_colour: &str,
+    temp_colour_range: TempColourRange,
+) -> String {
+    let (cold_below, hot_above) = temp_colour_range.into_inner();
+    match temp_max.map(|temp| temp.to_celsius().value) {
+        Some(max) if max < cold_below => colours.cold_temp_colour.to_string(),
+        Some(max) if max > hot_above => colours.hot_temp_colour.to_string(),
+        _ => text_colour.to_string(),
+    }
+}
+
+pub struct ContextBuilder<'a> {
     pub context: Context,
+    /// Settings the context is built with, [`CONFIG`] unless given to [`Self::with_settings`]
+    settings: &'a DashboardSettings,
     diagnostics: Vec<DashboardError>,
+    panels: Vec<Panel>,
+    /// Colours of the dashboard, the configured colours unless a theme rule changed them
+    colours: Colours,
+    /// Rain of the hours past, for the ones the hourly forecast no longer has
+    rain_log: RainLog,
 }
 
-impl Default for ContextBuilder {
+impl Default for ContextBuilder<'static> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl ContextBuilder {
+impl ContextBuilder<'static> {
     pub fn new() -> Self {
+        Self::with_settings(&CONFIG)
+    }
+}
+
+impl<'a> ContextBuilder<'a> {
+    /// A builder reading `settings` instead of [`CONFIG`]
+    pub fn with_settings(settings: &'a DashboardSettings) -> Self {
         Self {
-            context: Context::default(),
+            context: Context::new(settings),
+            settings,
             diagnostics: Vec::new(),
+            panels: Vec::new(),
+            colours: settings.colours.clone(),
+            rain_log: RainLog::default(),
         }
     }
 
+    /// Number format of the `locale` of the settings
+    fn number_format(&self) -> NumberFormat {
+        NumberFormat::for_locale(self.settings.render_options.locale.as_ref())
+    }
+
+    /// Replaces the configured colours, e.g. with the colours of a matching theme rule.
+    /// Call it before adding the forecast data, which colours the weekend tiles and the graph.
+    pub fn with_colours(&mut self, colours: Colours) -> &mut Self {
+        let context = &mut self.context;
+        let background = colours.background_colour.to_string();
+        let text = colours.text_colour.to_string();
+        context.background_colour.clone_from(&background);
+        context.text_colour.clone_from(&text);
+        context.x_axis_colour = colours.x_axis_colour.to_string();
+        context.y_left_axis_colour = colours.y_left_axis_colour.to_string();
+        context.y_right_axis_colour = colours.y_right_axis_colour.to_string();
+        context.actual_temp_colour = colours.actual_temp_colour.to_string();
+        context.feels_like_colour = colours.feels_like_colour.to_string();
+        context.rain_colour = colours.rain_colour.to_string();
+        context.humidity_colour = colours.humidity_colour.to_string();
+        context.humidity_dry_colour = colours.humidity_dry_colour.to_string();
+        context.humidity_humid_colour = colours.humidity_humid_colour.to_string();
+        for (day_background, day_text) in [
+            (
+                &mut context.day2_background_colour,
+                &mut context.day2_text_colour,
+            ),
+            (
+                &mut context.day3_background_colour,
+                &mut context.day3_text_colour,
+            ),
+            (
+                &mut context.day4_background_colour,
+                &mut context.day4_text_colour,
+            ),
+            (
+                &mut context.day5_background_colour,
+                &mut context.day5_text_colour,
+            ),
+            (
+                &mut context.day6_background_colour,
+                &mut context.day6_text_colour,
+            ),
+            (
+                &mut context.day7_background_colour,
+                &mut context.day7_text_colour,
+            ),
+        ] {
+            day_background.clone_from(&background);
+            day_text.clone_from(&text);
+        }
+        self.colours = colours;
+        self
+    }
+
+    /// Rain of the hours past the hourly forecast no longer has, see
+    /// [`crate::dashboard::rain_today`]. Call it before adding the hourly forecast.
+    pub fn with_rain_log(&mut self, rain_log: RainLog) -> &mut Self {
+        self.rain_log = rain_log;
+        self
+    }
+
     /// Updates the warning display fields based on the highest priority diagnostic.
     /// Called internally after adding diagnostics.
     fn update_warning_display(&mut self) {
@@ -228,30 +792,7 @@ impl ContextBuilder {
     /// Icons are stacked diagonally with offset, sorted by priority (high to low).
     /// Highest priority appears at front (lowest x, lowest y), lowest priority at back.
     fn generate_cascading_icons_svg(&self) -> String {
-        let mut sorted_diagnostics = self.diagnostics.clone();
-        sorted_diagnostics.sort_by_key(|e| std::cmp::Reverse(e.priority())); // High to low
-
-        let icon_size = 74;
-        let x_start = 63; // Starting X position for highest priority
-        let y_start = -10; // Starting Y position for highest priority
-        let x_offset = -5; // Move each subsequent icon left (creates depth)
-        let y_offset = -3; // Move each subsequent icon up (creates depth)
-
-        // Reverse order so lowest priority renders first (appears in back)
-        sorted_diagnostics
-            .iter()
-            .enumerate()
-            .rev()
-            .map(|(index, error)| {
-                let x_pos = x_start + (index as i32 * x_offset);
-                let y_pos = y_start + (index as i32 * y_offset);
-                format!(
-                    r#"<image x="{x_pos}" y="{y_pos}" width="{icon_size}" height="{icon_size}" href="{}"/>"#,
-                    error.get_icon_path()
-                )
-            })
-            .collect::<Vec<String>>()
-            .join("\n        ")
+        cascading_icons_svg(&self.diagnostics, &self.settings.diagnostics.layout)
     }
 
     /// Defines the 7-day forecast window starting from today.
@@ -282,11 +823,10 @@ impl ContextBuilder {
         let min_temp_value = forecast
             .and_then(|f| f.temp_min)
             .map_or("NA".to_string(), |temp| temp.to_string());
-        let max_temp_value = forecast
-            .and_then(|f| f.temp_max)
-            .map_or("NA".to_string(), |temp| temp.to_string());
+        let temp_max = forecast.and_then(|f| f.temp_max);
+        let max_temp_value = temp_max.map_or("NA".to_string(), |temp| temp.to_string());
         let icon_value = forecast.map_or_else(
-            || NOT_AVAILABLE_ICON_PATH.to_string_lossy().to_string(),
+            || platform::href(&NOT_AVAILABLE_ICON_PATH),
             |f| f.get_icon_path(),
         );
 
@@ -305,35 +845,83 @@ impl ContextBuilder {
                             .sunset_time
                             .map(|dt| dt.format("%H:%M").to_string())
                             .unwrap_or_else(|| "NA".to_string());
+                        if let (Some(sunrise), Some(sunset)) =
+                            (astro.sunrise_time, astro.sunset_time)
+                        {
+                            self.context.day_length = format_day_length(sunset - sunrise);
+                        }
+                    }
+                    if let Some(solar_noon) = forecast
+                        .date
+                        .and_then(|date| solar_noon(date, self.settings.api.longitude.into_inner()))
+                    {
+                        self.context.solar_noon_time =
+                            solar_noon.with_timezone(&Local).format("%H:%M").to_string();
                     }
                 }
             }
             1 => {
+                self.context.day2_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day2_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day2_mintemp = min_temp_value;
                 self.context.day2_maxtemp = max_temp_value;
                 self.context.day2_icon = icon_value;
             }
             2 => {
+                self.context.day3_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day3_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day3_mintemp = min_temp_value;
                 self.context.day3_maxtemp = max_temp_value;
                 self.context.day3_icon = icon_value;
             }
             3 => {
+                self.context.day4_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day4_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day4_mintemp = min_temp_value;
                 self.context.day4_maxtemp = max_temp_value;
                 self.context.day4_icon = icon_value;
             }
             4 => {
+                self.context.day5_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day5_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day5_mintemp = min_temp_value;
                 self.context.day5_maxtemp = max_temp_value;
                 self.context.day5_icon = icon_value;
             }
             5 => {
+                self.context.day6_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day6_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day6_mintemp = min_temp_value;
                 self.context.day6_maxtemp = max_temp_value;
                 self.context.day6_icon = icon_value;
             }
             6 => {
+                self.context.day7_temp_colour = temp_band_colour(
+                    temp_max,
+                    &self.colours,
+                    &self.context.day7_text_colour,
+                    self.settings.render_options.temp_colour_range,
+                );
                 self.context.day7_mintemp = min_temp_value;
                 self.context.day7_maxtemp = max_temp_value;
                 self.context.day7_icon = icon_value;
@@ -356,6 +944,9 @@ impl ContextBuilder {
 
         // Pre-populate day names from local calendar (tomorrow through +6 days)
         self.initialize_day_names(clock.now_local());
+        self.initialize_weekend_highlight(clock.now_local());
+        self.initialize_special_dates(today_local_date);
+        self.initialize_week_grid(today_local_date);
 
         // Define the 7-day forecast window (today through +6 days)
         let forecast_window = Self::define_daily_forecast_window(today_local_date);
@@ -436,6 +1027,172 @@ impl ContextBuilder {
             .to_string();
     }
 
+    /// Switches daily tiles that fall on a configured weekend day to the weekend colours.
+    fn initialize_weekend_highlight(&mut self, local_time: DateTime<Local>) {
+        if !self.settings.render_options.highlight_weekend {
+            return;
+        }
+
+        let background = self.colours.weekend_background_colour.to_string();
+        let text = self.colours.weekend_text_colour.to_string();
+
+        for offset in 1..7 {
+            let weekday = (local_time + chrono::Duration::days(offset)).weekday();
+            if !self.settings.render_options.weekend_days.contains(&weekday) {
+                continue;
+            }
+
+            let (day_background, day_text) = match offset {
+                1 => (
+                    &mut self.context.day2_background_colour,
+                    &mut self.context.day2_text_colour,
+                ),
+                2 => (
+                    &mut self.context.day3_background_colour,
+                    &mut self.context.day3_text_colour,
+                ),
+                3 => (
+                    &mut self.context.day4_background_colour,
+                    &mut self.context.day4_text_colour,
+                ),
+                4 => (
+                    &mut self.context.day5_background_colour,
+                    &mut self.context.day5_text_colour,
+                ),
+                5 => (
+                    &mut self.context.day6_background_colour,
+                    &mut self.context.day6_text_colour,
+                ),
+                _ => (
+                    &mut self.context.day7_background_colour,
+                    &mut self.context.day7_text_colour,
+                ),
+            };
+            day_background.clone_from(&background);
+            day_text.clone_from(&text);
+        }
+    }
+
+    /// Shows the "forecast changed" marker on the daily tiles of the `revised` days, see
+    /// [`crate::dashboard::forecast_changes`]
+    pub fn with_forecast_changes(&mut self, revised: &[NaiveDate], today: NaiveDate) -> &mut Self {
+        for date in revised {
+            let visibility = match (*date - today).num_days() {
+                1 => &mut self.context.day2_forecast_changed_visibility,
+                2 => &mut self.context.day3_forecast_changed_visibility,
+                3 => &mut self.context.day4_forecast_changed_visibility,
+                4 => &mut self.context.day5_forecast_changed_visibility,
+                5 => &mut self.context.day6_forecast_changed_visibility,
+                6 => &mut self.context.day7_forecast_changed_visibility,
+                _ => continue,
+            };
+            *visibility = ElementVisibility::Visible.to_string();
+        }
+        self
+    }
+
+    /// Shows the special date badge on daily tiles that match a configured holiday or birthday.
+    fn initialize_special_dates(&mut self, today: NaiveDate) {
+        for offset in 1..7 {
+            let date = today + chrono::Duration::days(offset);
+            let labels: Vec<&str> = self
+                .settings
+                .special_dates
+                .iter()
+                .filter(|special_date| special_date.date.rule().matches(date))
+                .map(|special_date| special_date.label.as_str())
+                .collect();
+            if labels.is_empty() {
+                continue;
+            }
+
+            logger::detail(format!("Special date on {date}: {}", labels.join(", ")));
+
+            let (visibility, label) = match offset {
+                1 => (
+                    &mut self.context.day2_special_date_visibility,
+                    &mut self.context.day2_special_date_label,
+                ),
+                2 => (
+                    &mut self.context.day3_special_date_visibility,
+                    &mut self.context.day3_special_date_label,
+                ),
+                3 => (
+                    &mut self.context.day4_special_date_visibility,
+                    &mut self.context.day4_special_date_label,
+                ),
+                4 => (
+                    &mut self.context.day5_special_date_visibility,
+                    &mut self.context.day5_special_date_label,
+                ),
+                5 => (
+                    &mut self.context.day6_special_date_visibility,
+                    &mut self.context.day6_special_date_label,
+                ),
+                _ => (
+                    &mut self.context.day7_special_date_visibility,
+                    &mut self.context.day7_special_date_label,
+                ),
+            };
+            *visibility = ElementVisibility::Visible.to_string();
+            *label = labels.join(", ");
+        }
+    }
+
+    /// Places today and the next six days in week rows starting on the configured weekday, for
+    /// templates that render a weekly grid, and numbers the weeks.
+    fn initialize_week_grid(&mut self, today: NaiveDate) {
+        let first_weekday = self.settings.render_options.first_weekday;
+        let numbering = self.settings.render_options.week_numbering;
+        let next_week = calendar::week_start(today, first_weekday) + chrono::Duration::days(7);
+
+        self.context.today_week_column = calendar::week_column(today, first_weekday).to_string();
+        self.context.week_number = numbering.week_number(today, first_weekday).to_string();
+        self.context.next_week_number = numbering.week_number(next_week, first_weekday).to_string();
+
+        let [h1, h2, h3, h4, h5, h6, h7] =
+            calendar::week_days(first_weekday).map(|weekday| weekday.to_string());
+        self.context.weekday_header1 = h1;
+        self.context.weekday_header2 = h2;
+        self.context.weekday_header3 = h3;
+        self.context.weekday_header4 = h4;
+        self.context.weekday_header5 = h5;
+        self.context.weekday_header6 = h6;
+        self.context.weekday_header7 = h7;
+
+        for offset in 1..7 {
+            let date = today + chrono::Duration::days(offset);
+            let (column, row) = match offset {
+                1 => (
+                    &mut self.context.day2_week_column,
+                    &mut self.context.day2_week_row,
+                ),
+                2 => (
+                    &mut self.context.day3_week_column,
+                    &mut self.context.day3_week_row,
+                ),
+                3 => (
+                    &mut self.context.day4_week_column,
+                    &mut self.context.day4_week_row,
+                ),
+                4 => (
+                    &mut self.context.day5_week_column,
+                    &mut self.context.day5_week_row,
+                ),
+                5 => (
+                    &mut self.context.day6_week_column,
+                    &mut self.context.day6_week_row,
+                ),
+                _ => (
+                    &mut self.context.day7_week_column,
+                    &mut self.context.day7_week_row,
+                ),
+            };
+            *column = calendar::week_column(date, first_weekday).to_string();
+            *row = if date < next_week { "0" } else { "1" }.to_string();
+        }
+    }
+
     // Extrusion Pattern: force everything through one function until it resembles spaghetti
     pub fn with_hourly_forecast_data(
         &mut self,
@@ -481,17 +1238,41 @@ impl ContextBuilder {
 
         // println!("Day end: {:?}", day_end);
 
-        let mut graph = HourlyForecastGraph {
-            x_axis_always_at_min: CONFIG.render_options.x_axis_always_at_min,
-            text_colour: CONFIG.colours.text_colour.to_string(),
-            ..Default::default()
-        };
+        let graph_window = self.settings.render_options.graph_window_hours;
+        let mut graph_builder = HourlyForecastGraph::builder()
+            .size(
+                self.settings.render_options.graph_width.into_inner(),
+                self.settings.render_options.graph_height.into_inner(),
+            )
+            .hours_per_point(graph_window.hours_per_point())
+            .number_format(self.number_format())
+            .x_axis_always_at_min(self.settings.render_options.x_axis_always_at_min)
+            .text_colour(self.colours.text_colour.to_string());
+        if self.settings.render_options.text_halo {
+            graph_builder = graph_builder.text_halo(
+                self.colours.background_colour.to_string(),
+                self.settings.render_options.text_halo_width,
+            );
+        }
+        if self.settings.render_options.show_humidity_curve
+            && self.settings.render_options.show_humidity_comfort_bands
+        {
+            let (dry_below, humid_above) = self
+                .settings
+                .render_options
+                .humidity_comfort_range
+                .into_inner();
+            graph_builder =
+                graph_builder.humidity_comfort_range(dry_below.into(), humid_above.into());
+        }
+        let mut graph = graph_builder.build();
 
+        // The graph may show more hours than the tables, which keep the 24 hour window
         Self::populate_graph_data(
             self,
             &hourly_forecast_data,
             local_forecast_window_start,
-            local_forecast_window_end,
+            local_forecast_window_start + chrono::Duration::hours(graph_window.into_inner().into()),
             &mut graph,
             clock,
         );
@@ -504,6 +1285,13 @@ impl ContextBuilder {
         self.context.actual_temp_curve_data = temp_curve_data;
         self.context.feel_like_curve_data = feel_like_curve_data;
         self.context.rain_curve_data = rain_curve_data;
+        self.context.rain_band_data = graph.draw_rain_band();
+        self.context.temperature_band_data = graph.draw_temperature_band();
+        self.context.humidity_curve_data = graph.draw_humidity_curve();
+        (
+            self.context.humidity_dry_band_data,
+            self.context.humidity_humid_band_data,
+        ) = graph.draw_humidity_comfort_bands();
 
         let axis_data_path =
             graph.create_axis_with_labels(local_forecast_window_start.hour() as f32, clock);
@@ -517,6 +1305,28 @@ impl ContextBuilder {
         self.context.x_axis_guideline_path = axis_data_path.x_axis_guideline_path;
 
         self.context.uv_gradient = graph.draw_uv_gradient_over_time();
+        self.context.lightning_risk_icons_svg =
+            graph.draw_lightning_risk_icons(&self.context.thunder_icon);
+        let commutes = commute_summaries(
+            &self.settings.commute_windows,
+            &hourly_forecast_data,
+            local_forecast_window_start,
+            local_forecast_window_start + chrono::Duration::hours(graph_window.into_inner().into()),
+        );
+        (
+            self.context.commute_band_data,
+            self.context.commute_callouts_svg,
+        ) = graph.draw_time_windows(&time_windows(
+            &commutes,
+            local_forecast_window_start,
+            &self.number_format(),
+        ));
+
+        Self::set_daily_thunder_indicators(self, &hourly_forecast_data, clock);
+        Self::set_daily_sparklines(self, &hourly_forecast_data, clock);
+        Self::set_uv_protection_window(self, &hourly_forecast_data, clock);
+        Self::set_rain_countdown(self, &hourly_forecast_data, clock);
+        Self::set_hourly_summary(self, &hourly_forecast_data, clock);
 
         Self::set_max_values_for_table(
             self,
@@ -526,14 +1336,32 @@ impl ContextBuilder {
             local_forecast_window_end,
         );
 
-        self.context.total_rain_today = (get_total_between_dates(
+        Self::set_rain_today(
+            self,
+            &hourly_forecast_data,
+            local_forecast_window_start,
+            day_end,
+        );
+
+        Self::set_table_rows(
+            self,
+            &hourly_forecast_data,
+            local_forecast_window_start,
+            day_end,
+            local_forecast_window_end,
+        );
+
+        let total_rain_today = get_total_between_dates(
             &hourly_forecast_data,
             &local_forecast_window_start,
             &local_forecast_window_end,
             |item: &HourlyForecast| item.precipitation.calculate_median(),
             |item| item.time.with_timezone(&Local),
-        ))
-        .to_string();
+        );
+        self.context.total_rain_today = self.number_format().number(total_rain_today);
+        self.context.total_rain_today_with_unit = self
+            .number_format()
+            .with_unit(&self.context.total_rain_today, "mm");
 
         self
     }
@@ -606,40 +1434,87 @@ impl ContextBuilder {
         graph: &mut HourlyForecastGraph,
         clock: &dyn Clock,
     ) {
-        let mut x = 0;
-        hourly_forecast_data
+        let hours: Vec<&HourlyForecast> = hourly_forecast_data
             .iter()
             .filter(|forecast| {
                 forecast.time >= forecast_window_start && forecast.time < forecast_window_end
             })
-            .for_each(|forecast| {
-                if x == 0 {
-                    self.with_current_hour_data(forecast, clock);
-                    self.set_now_values_for_table(forecast)
-                } else if x >= 24 {
-                    logger::warning(
-                        "More than 24 hours of hourly forecast data, this should not happen",
-                    );
-                    return;
-                }
-                // we won't push the actual hour right now
-                // we can calculate it later
-                // we push this index to make scaling graph easier
-                for curve_type in &mut graph.curves.iter_mut() {
-                    match curve_type {
-                        CurveType::ActualTemp(curve) => {
-                            curve.add_point(x as f32, *forecast.temperature)
-                        }
-                        CurveType::TempFeelLike(curve) => {
-                            curve.add_point(x as f32, *forecast.apparent_temperature)
-                        }
-                        CurveType::RainChance(curve) => curve
-                            .add_point(x as f32, forecast.precipitation.chance.unwrap_or(0) as f32),
-                    }
+            .collect();
+        if let Some(current_hour) = hours.first() {
+            self.with_current_hour_data(current_hour, clock);
+            self.set_now_values_for_table(current_hour);
+        }
+
+        // Each point merges `hours_per_point` hours: temperatures and humidity are averaged,
+        // rain, UV and lightning take the worst hour and the bands span all of them. Hours are
+        // placed by their offset from the window start, so an hour dropped as implausible leaves
+        // a gap instead of shifting the later points left
+        let hours_per_point = (graph.hours_per_point as usize).max(1);
+        let mut points: Vec<Vec<&HourlyForecast>> = vec![Vec::new(); graph.uv_data.len()];
+        for hour in hours {
+            let offset = hour.time.signed_duration_since(forecast_window_start);
+            let slot = offset.num_hours() as usize / hours_per_point;
+            match points.get_mut(slot) {
+                Some(point) => point.push(hour),
+                None => logger::warning(
+                    "More than 24 points of hourly forecast data, this should not happen",
+                ),
+            }
+        }
+        let mean = |values: &mut dyn Iterator<Item = f32>| {
+            let (sum, count) = values.fold((0.0, 0.0), |(sum, count), v| (sum + v, count + 1.0));
+            sum / count
+        };
+        let span = |bands: &mut dyn Iterator<Item = Option<(f32, f32)>>| {
+            bands
+                .flatten()
+                .reduce(|(lower, upper), (other_lower, other_upper)| {
+                    (lower.min(other_lower), upper.max(other_upper))
+                })
+        };
+
+        for (x, chunk) in points.iter().enumerate() {
+            if chunk.is_empty() {
+                continue;
+            }
+            for curve_type in &mut graph.curves.iter_mut() {
+                match curve_type {
+                    CurveType::ActualTemp(curve) => curve.add_point(
+                        x as f32,
+                        mean(&mut chunk.iter().map(|hour| *hour.temperature)),
+                    ),
+                    CurveType::TempFeelLike(curve) => curve.add_point(
+                        x as f32,
+                        mean(&mut chunk.iter().map(|hour| *hour.apparent_temperature)),
+                    ),
+                    CurveType::RainChance(curve) => curve.add_point(
+                        x as f32,
+                        chunk
+                            .iter()
+                            .map(|hour| hour.precipitation.chance.unwrap_or(0))
+                            .max()
+                            .unwrap_or(0) as f32,
+                    ),
                 }
-                graph.uv_data[x] = forecast.uv_index;
-                x += 1;
+            }
+            graph.uv_data[x] = chunk.iter().map(|hour| hour.uv_index).max().unwrap_or(0);
+            graph.temperature_band[x] = span(&mut chunk.iter().map(|hour| {
+                hour.temperature_spread
+                    .map(|(lower, upper)| (*lower, *upper))
+            }));
+            if self.settings.render_options.show_rain_uncertainty_band {
+                graph.rain_band[x] =
+                    span(&mut chunk.iter().map(|hour| hour.precipitation.amount_band()));
+            }
+            if self.settings.render_options.show_humidity_curve {
+                graph.humidity[x] = Some(mean(
+                    &mut chunk.iter().map(|hour| f32::from(hour.relative_humidity)),
+                ));
+            }
+            graph.lightning_risk[x] = chunk.iter().any(|hour| {
+                hour.has_lightning_risk(self.settings.render_options.lightning_cape_threshold)
             });
+        }
     }
 
     fn with_current_hour_data(
@@ -647,33 +1522,46 @@ impl ContextBuilder {
         current_hour: &HourlyForecast,
         clock: &dyn Clock,
     ) -> &mut Self {
+        let format = self.number_format();
+        let temp_unit = format!("°{}", self.settings.render_options.temp_unit);
         self.context.current_hour_actual_temp = current_hour.temperature.to_string();
+        self.context.current_hour_actual_temp_with_unit =
+            format.with_unit(&self.context.current_hour_actual_temp, &temp_unit);
         self.context.current_hour_weather_icon = current_hour.get_icon_path();
+        let condition = current_hour.condition();
+        self.context.current_condition = condition.to_string();
+        self.context.current_condition_text = condition.description().to_string();
         self.context.current_hour_feels_like = current_hour.apparent_temperature.to_string();
+        self.context.current_hour_feels_like_with_unit =
+            format.with_unit(&self.context.current_hour_feels_like, &temp_unit);
         self.context.current_day_date = clock
             .now_local()
-            .format(&CONFIG.render_options.date_format)
+            .format(&self.settings.render_options.date_format)
             .to_string();
         self.context.current_day_time = clock
             .now_local()
-            .format(&CONFIG.render_options.time_format)
+            .format(&self.settings.render_options.time_format)
             .to_string();
         self.context.current_hour_rain_amount =
-            current_hour.precipitation.calculate_median().to_string();
+            format.number(current_hour.precipitation.calculate_median());
         self.context.current_hour_rain_measure_icon = current_hour.precipitation.get_icon_path();
 
         self
     }
 
     fn set_now_values_for_table(&mut self, current_hour: &HourlyForecast) {
-        self.context.current_hour_wind_speed = current_hour
-            .wind
-            .get_speed_in_unit(
-                CONFIG.render_options.use_gust_instead_of_wind,
-                CONFIG.render_options.wind_speed_unit,
-            )
-            .to_string();
-        self.context.current_hour_wind_icon = current_hour.wind.get_icon_path();
+        let wind = &current_hour.wind;
+        let unit = self.settings.render_options.wind_speed_unit;
+        self.context.current_hour_wind_speed =
+            wind.format_speed(self.settings.render_options.wind_display, unit);
+        self.context.current_hour_wind_speed_with_unit = self
+            .number_format()
+            .with_unit(&self.context.current_hour_wind_speed, &unit.to_string());
+        self.context.current_hour_wind_icon = wind.get_icon_path();
+        self.context.current_hour_wind_sustained_speed =
+            wind.get_speed_in_unit(false, unit).to_string();
+        self.context.current_hour_wind_gust_speed = wind.get_speed_in_unit(true, unit).to_string();
+        self.context.current_hour_wind_gust_icon = WindSpeed(wind.gust_speed_kmh).get_icon_path();
         self.context.current_hour_uv_index = current_hour.uv_index.to_string();
         self.context.current_hour_uv_index_icon =
             crate::domain::icons::UVIndex(current_hour.uv_index).get_icon_path();
@@ -682,6 +1570,143 @@ impl ContextBuilder {
             crate::domain::icons::RelativeHumidity(current_hour.relative_humidity).get_icon_path();
     }
 
+    /// Marks daily tiles (tomorrow through +6 days) that have at least one hour
+    /// at or above the configured CAPE threshold.
+    fn set_daily_thunder_indicators(
+        &mut self,
+        hourly_forecast_data: &[HourlyForecast],
+        clock: &dyn Clock,
+    ) {
+        let today = clock.now_local().date_naive();
+        let threshold = self.settings.render_options.lightning_cape_threshold;
+
+        for day_index in 1..7 {
+            let date = today + chrono::Days::new(day_index);
+            let at_risk = hourly_forecast_data.iter().any(|forecast| {
+                forecast.time.with_timezone(&Local).date_naive() == date
+                    && forecast.has_lightning_risk(threshold)
+            });
+            if !at_risk {
+                continue;
+            }
+
+            logger::detail(format!("Thunderstorm risk on {date}"));
+            let visibility = ElementVisibility::Visible.to_string();
+            match day_index {
+                1 => self.context.day2_thunder_visibility = visibility,
+                2 => self.context.day3_thunder_visibility = visibility,
+                3 => self.context.day4_thunder_visibility = visibility,
+                4 => self.context.day5_thunder_visibility = visibility,
+                5 => self.context.day6_thunder_visibility = visibility,
+                6 => self.context.day7_thunder_visibility = visibility,
+                _ => {}
+            }
+        }
+    }
+
+    /// Draws the hourly temperature shape of each daily tile's day, for the days with hourly data,
+    /// when `show_daily_sparklines` is on.
+    fn set_daily_sparklines(&mut self, hourly_forecast_data: &[HourlyForecast], clock: &dyn Clock) {
+        if !self.settings.render_options.show_daily_sparklines {
+            return;
+        }
+        let today = clock.now_local().date_naive();
+
+        for day_index in 1..7 {
+            let date = today + chrono::Days::new(day_index);
+            let points: Vec<(f32, f32)> = hourly_forecast_data
+                .iter()
+                .map(|forecast| (forecast.time.with_timezone(&Local), forecast))
+                .filter(|(time, _)| time.date_naive() == date)
+                .map(|(time, forecast)| (time.hour() as f32, forecast.temperature.value))
+                .collect();
+            let Some(path) = sparkline_path(&points) else {
+                continue;
+            };
+
+            let (sparkline, visibility) = match day_index {
+                1 => (
+                    &mut self.context.day2_sparkline,
+                    &mut self.context.day2_sparkline_visibility,
+                ),
+                2 => (
+                    &mut self.context.day3_sparkline,
+                    &mut self.context.day3_sparkline_visibility,
+                ),
+                3 => (
+                    &mut self.context.day4_sparkline,
+                    &mut self.context.day4_sparkline_visibility,
+                ),
+                4 => (
+                    &mut self.context.day5_sparkline,
+                    &mut self.context.day5_sparkline_visibility,
+                ),
+                5 => (
+                    &mut self.context.day6_sparkline,
+                    &mut self.context.day6_sparkline_visibility,
+                ),
+                _ => (
+                    &mut self.context.day7_sparkline,
+                    &mut self.context.day7_sparkline_visibility,
+                ),
+            };
+            *sparkline = path;
+            *visibility = ElementVisibility::Visible.to_string();
+        }
+    }
+
+    /// Shows "Rain in ~40 min" when rain is likely within the configured horizon.
+    fn set_rain_countdown(&mut self, hourly_forecast_data: &[HourlyForecast], clock: &dyn Clock) {
+        let Some(minutes) = minutes_until_rain(
+            hourly_forecast_data,
+            clock.now_utc(),
+            self.settings.render_options.rain_countdown_chance_threshold,
+            self.settings.render_options.rain_countdown_horizon_minutes,
+        ) else {
+            return;
+        };
+
+        self.context.rain_starts_in = format_rain_countdown(minutes);
+        self.context.rain_starts_in_visibility = ElementVisibility::Visible.to_string();
+        logger::detail(format!("Rain countdown: {}", self.context.rain_starts_in));
+    }
+
+    /// Sets the text summary of the hourly forecast.
+    fn set_hourly_summary(&mut self, hourly_forecast_data: &[HourlyForecast], clock: &dyn Clock) {
+        self.context.hourly_summary = hourly_summary(
+            hourly_forecast_data,
+            clock.now_local(),
+            self.settings.render_options.rain_countdown_chance_threshold,
+            self.settings.render_options.wind_display.uses_gust(),
+        );
+        logger::detail(format!("Hourly summary: {}", self.context.hourly_summary));
+    }
+
+    /// Finds today's window where the UV index reaches the sun protection threshold.
+    fn set_uv_protection_window(
+        &mut self,
+        hourly_forecast_data: &[HourlyForecast],
+        clock: &dyn Clock,
+    ) {
+        let today = clock.now_local().date_naive();
+        let mut protection_hours = hourly_forecast_data
+            .iter()
+            .filter(|forecast| {
+                forecast.time.with_timezone(&Local).date_naive() == today
+                    && forecast.uv_index >= UV_PROTECTION_THRESHOLD
+            })
+            .map(|forecast| forecast.time.with_timezone(&Local));
+
+        self.context.uv_protection_window = match protection_hours.next() {
+            Some(first) => {
+                let last =
+                    protection_hours.next_back().unwrap_or(first) + chrono::Duration::hours(1);
+                format!("{}-{}", first.format("%H:%M"), last.format("%H:%M"))
+            }
+            None => "None".to_string(),
+        };
+    }
+
     fn set_max_values_for_table(
         &mut self,
         hourly_forecast_data: &[HourlyForecast],
@@ -733,16 +1758,16 @@ impl ContextBuilder {
 
         let (max_wind_today, max_wind_tomorrow) = max_in_today_and_tomorrow!(|item| item
             .wind
-            .get_speed(CONFIG.render_options.use_gust_instead_of_wind));
+            .get_speed(self.settings.render_options.wind_display.uses_gust()));
 
         // Convert wind speed to configured unit
         let max_wind_today_converted = crate::domain::models::Wind::convert_speed(
             max_wind_today,
-            CONFIG.render_options.wind_speed_unit,
+            self.settings.render_options.wind_speed_unit,
         );
         let max_wind_tomorrow_converted = crate::domain::models::Wind::convert_speed(
             max_wind_tomorrow,
-            CONFIG.render_options.wind_speed_unit,
+            self.settings.render_options.wind_speed_unit,
         );
 
         if max_wind_today > max_wind_tomorrow {
@@ -752,6 +1777,23 @@ impl ContextBuilder {
             self.context.max_gust_speed_font_style = FontStyle::Italic.to_string();
         }
 
+        // Both speeds from the day the displayed maximum comes from
+        let (max_sustained_today, max_sustained_tomorrow) =
+            max_in_today_and_tomorrow!(|item| item.wind.speed_kmh);
+        let (max_gust_today, max_gust_tomorrow) =
+            max_in_today_and_tomorrow!(|item| item.wind.gust_speed_kmh);
+        let max_wind = if max_wind_today > max_wind_tomorrow {
+            Wind::new(max_sustained_today, max_gust_today)
+        } else {
+            Wind::new(max_sustained_tomorrow, max_gust_tomorrow)
+        };
+        let unit = self.settings.render_options.wind_speed_unit;
+        self.context.max_wind_sustained_speed = max_wind.get_speed_in_unit(false, unit).to_string();
+        self.context.max_wind_gust_speed = max_wind.get_speed_in_unit(true, unit).to_string();
+        if self.settings.render_options.wind_display == WindDisplay::Both {
+            self.context.max_gust_speed = max_wind.format_speed(WindDisplay::Both, unit);
+        }
+
         let (max_uv_index_today, max_uv_index_tomorrow) =
             max_in_today_and_tomorrow!(|item| item.uv_index);
 
@@ -773,6 +1815,220 @@ impl ContextBuilder {
         }
     }
 
+    /// Fills the Now/Max24h table rows with the metrics chosen in `render_options.max_table_rows`.
+    fn set_table_rows(
+        &mut self,
+        hourly_forecast_data: &[HourlyForecast],
+        forecast_window_start: chrono::DateTime<Local>,
+        day_end: chrono::DateTime<Local>,
+        forecast_window_end: chrono::DateTime<Local>,
+    ) {
+        let current_hour = hourly_forecast_data
+            .iter()
+            .find(|forecast| forecast.time >= forecast_window_start);
+        let get_time = |item: &HourlyForecast| item.time.with_timezone(&Local);
+
+        for (slot, metric) in self
+            .settings
+            .render_options
+            .max_table_rows
+            .as_ref()
+            .iter()
+            .enumerate()
+        {
+            if *metric == MaxTableMetric::RainToday {
+                let row = TableRowValues {
+                    icon: current_hour.map_or_else(
+                        || platform::href(&NOT_AVAILABLE_ICON_PATH),
+                        |forecast| table_metric_icon(*metric, forecast),
+                    ),
+                    unit: table_metric_unit(*metric, self.settings.render_options.wind_speed_unit),
+                    font_size: TABLE_VALUE_FONT_SIZE.to_string(),
+                    now: self.context.rain_fallen_today.clone(),
+                    max: self.context.rain_expected_today.clone(),
+                    max_font_style: FontStyle::Normal.to_string(),
+                    max_suffix: "to come".to_string(),
+                    max_superscript: String::new(),
+                };
+                self.assign_table_row(slot, row);
+                continue;
+            }
+
+            let render_options = &self.settings.render_options;
+            let get_value =
+                |forecast: &HourlyForecast| table_metric_value(*metric, forecast, render_options);
+            let max_today = find_max_item_between_dates(
+                hourly_forecast_data,
+                &forecast_window_start,
+                &day_end,
+                get_value,
+                get_time,
+            );
+            let max_tomorrow = find_max_item_between_dates(
+                hourly_forecast_data,
+                &day_end,
+                &forecast_window_end,
+                get_value,
+                get_time,
+            );
+            let max_is_tomorrow = max_today <= max_tomorrow;
+            let max_value = if max_is_tomorrow {
+                max_tomorrow
+            } else {
+                max_today
+            };
+
+            let mut row = TableRowValues {
+                icon: current_hour.map_or_else(
+                    || platform::href(&NOT_AVAILABLE_ICON_PATH),
+                    |forecast| table_metric_icon(*metric, forecast),
+                ),
+                unit: table_metric_unit(*metric, self.settings.render_options.wind_speed_unit),
+                font_size: match metric {
+                    MaxTableMetric::Wind => {
+                        wind_font_size(self.settings.render_options.wind_display)
+                    }
+                    _ => TABLE_VALUE_FONT_SIZE.to_string(),
+                },
+                now: format_table_value(
+                    *metric,
+                    current_hour.and_then(get_value),
+                    &self.number_format(),
+                ),
+                max: format_table_value(*metric, max_value, &self.number_format()),
+                max_font_style: FontStyle::Normal.to_string(),
+                max_suffix: String::new(),
+                max_superscript: String::new(),
+            };
+            if *metric == MaxTableMetric::Wind
+                && self.settings.render_options.wind_display == WindDisplay::Both
+            {
+                // The value above is the sustained speed, the gust max comes from the same day
+                let (start, end) = if max_is_tomorrow {
+                    (&day_end, &forecast_window_end)
+                } else {
+                    (&forecast_window_start, &day_end)
+                };
+                let max_gust = find_max_item_between_dates(
+                    hourly_forecast_data,
+                    start,
+                    end,
+                    |forecast| forecast.wind.gust_speed_kmh,
+                    get_time,
+                );
+                let unit = self.settings.render_options.wind_speed_unit;
+                row.max = format!("{} / {}", row.max, Wind::convert_speed(max_gust, unit));
+                if let Some(forecast) = current_hour {
+                    row.now = forecast.wind.format_speed(WindDisplay::Both, unit);
+                }
+            }
+            if max_is_tomorrow {
+                match self.settings.render_options.tomorrow_max_marker(*metric) {
+                    TomorrowMaxMarker::Italic => row.max_font_style = FontStyle::Italic.to_string(),
+                    TomorrowMaxMarker::Suffix => row.max_suffix = "(tmrw)".to_string(),
+                    TomorrowMaxMarker::Superscript => row.max_superscript = "*".to_string(),
+                }
+            }
+            self.assign_table_row(slot, row);
+        }
+    }
+
+    /// Splits today's rain into the hours past, from the hourly forecast and the rain log, and the
+    /// rest of the day, and takes the rain since midnight from the gauge readings in the log
+    fn set_rain_today(
+        &mut self,
+        hourly_forecast_data: &[HourlyForecast],
+        current_hour: chrono::DateTime<Local>,
+        day_end: chrono::DateTime<Local>,
+    ) {
+        let day_start = day_end - chrono::Duration::days(1);
+        let mut log = self.rain_log.clone();
+        log.record(hourly_forecast_data, current_hour.with_timezone(&Utc));
+
+        let fallen = log.total_between(
+            day_start.with_timezone(&Utc),
+            current_hour.with_timezone(&Utc),
+        );
+        let expected = get_total_between_dates(
+            hourly_forecast_data,
+            &current_hour,
+            &day_end,
+            |item: &HourlyForecast| item.precipitation.calculate_median(),
+            |item| item.time.with_timezone(&Local),
+        );
+        self.context.rain_fallen_today =
+            format_table_value(MaxTableMetric::RainToday, fallen, &self.number_format());
+        self.context.rain_expected_today = format_table_value(
+            MaxTableMetric::RainToday,
+            Some(expected),
+            &self.number_format(),
+        );
+        self.context.rain_expected_today_with_unit = self
+            .number_format()
+            .with_unit(&self.context.rain_expected_today, "mm");
+
+        // Up to the day's end rather than the current hour, so the reading made during it counts
+        let observed =
+            log.observed_between(day_start.with_timezone(&Utc), day_end.with_timezone(&Utc));
+        let (since_midnight, source) = match (observed, fallen) {
+            (Some(observed), _) => (Some(observed), "observed"),
+            (None, Some(fallen)) => (Some(fallen), "forecast"),
+            (None, None) => (None, ""),
+        };
+        self.context.rain_since_midnight = format_table_value(
+            MaxTableMetric::RainToday,
+            since_midnight,
+            &self.number_format(),
+        );
+        self.context.rain_since_midnight_with_unit = match since_midnight {
+            Some(_) => self
+                .number_format()
+                .with_unit(&self.context.rain_since_midnight, "mm"),
+            None => self.context.rain_since_midnight.clone(),
+        };
+        self.context.rain_since_midnight_source = source.to_string();
+    }
+
+    fn assign_table_row(&mut self, slot: usize, row: TableRowValues) {
+        let visible = ElementVisibility::Visible.to_string();
+        match slot {
+            0 => {
+                self.context.table_row1_visibility = visible;
+                self.context.table_row1_icon = row.icon;
+                self.context.table_row1_unit = row.unit;
+                self.context.table_row1_font_size = row.font_size;
+                self.context.table_row1_now = row.now;
+                self.context.table_row1_max = row.max;
+                self.context.table_row1_max_font_style = row.max_font_style;
+                self.context.table_row1_max_suffix = row.max_suffix;
+                self.context.table_row1_max_superscript = row.max_superscript;
+            }
+            1 => {
+                self.context.table_row2_visibility = visible;
+                self.context.table_row2_icon = row.icon;
+                self.context.table_row2_unit = row.unit;
+                self.context.table_row2_font_size = row.font_size;
+                self.context.table_row2_now = row.now;
+                self.context.table_row2_max = row.max;
+                self.context.table_row2_max_font_style = row.max_font_style;
+                self.context.table_row2_max_suffix = row.max_suffix;
+                self.context.table_row2_max_superscript = row.max_superscript;
+            }
+            2 => {
+                self.context.table_row3_visibility = visible;
+                self.context.table_row3_icon = row.icon;
+                self.context.table_row3_unit = row.unit;
+                self.context.table_row3_font_size = row.font_size;
+                self.context.table_row3_now = row.now;
+                self.context.table_row3_max = row.max;
+                self.context.table_row3_max_font_style = row.max_font_style;
+                self.context.table_row3_max_suffix = row.max_suffix;
+                self.context.table_row3_max_superscript = row.max_superscript;
+            }
+            _ => {}
+        }
+    }
+
     /// Sets a validation error detected internally during context building.
     ///
     /// This method is used when data validation fails (e.g., incomplete forecast data).
@@ -802,4 +2058,43 @@ impl ContextBuilder {
         self.update_warning_display();
         self
     }
+
+    /// Shows the image panel, it stays hidden without an image
+    pub fn with_image_panel(&mut self, href: Option<String>) -> &mut Self {
+        if let Some(href) = href {
+            self.context.image_panel_href = href;
+            self.context.image_panel_visibility = ElementVisibility::Visible.to_string();
+        }
+        self
+    }
+
+    /// Shows the sea state of the marine layout, it stays hidden without marine data
+    pub fn with_marine(&mut self, svg: Option<String>) -> &mut Self {
+        if let Some(svg) = svg {
+            self.context.marine_svg = svg;
+            self.context.marine_visibility = ElementVisibility::Visible.to_string();
+        }
+        self
+    }
+
+    /// Adds a panel drawn on top of the rendered template, e.g. the snow report
+    pub fn with_panel(&mut self, panel: Option<Panel>) -> &mut Self {
+        self.panels.extend(panel);
+        self
+    }
+
+    /// Diagnostics added so far, from fetching and from validating the forecast data
+    pub fn diagnostics(&self) -> &[DashboardError] {
+        &self.diagnostics
+    }
+
+    /// Panels added from the forecast data, composited over the rendered template
+    pub fn panels(&self) -> &[Panel] {
+        &self.panels
+    }
+
+    /// Colours of the dashboard, for the panels drawn around the template
+    pub fn colours(&self) -> &Colours {
+        &self.colours
+    }
 }
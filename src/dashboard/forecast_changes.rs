@@ -0,0 +1,178 @@
+//! "Forecast changed" marker on the daily tiles
+//!
+//! The outlook of every forecast day is kept in `forecast_outlooks.json` in the cache directory,
+//! which is per profile. Each run compares the new daily forecast with it: a day that turned from
+//! dry to wet or back, or whose forecast high moved by `max_temp_change` °C or more, is marked as
+//! revised. The marker stays for `marker_hours`, so a revision made overnight is still shown in
+//! the morning.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::configs::settings::ForecastChanges;
+use crate::domain::conditions::Condition;
+use crate::domain::models::DailyForecast;
+use crate::logger;
+use crate::providers::factory::cache_path;
+
+/// Outlooks file in the cache directory
+pub const OUTLOOKS_FILE_NAME: &str = "forecast_outlooks.json";
+
+/// What a day's forecast looked like
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DayOutlook {
+    pub condition: Condition,
+    /// Forecast maximum temperature in °C
+    #[serde(default)]
+    pub temp_max: Option<f32>,
+}
+
+impl DayOutlook {
+    pub fn of(day: &DailyForecast) -> Self {
+        Self {
+            condition: day.condition(),
+            temp_max: day.temp_max.map(|t| t.to_celsius().value),
+        }
+    }
+
+    /// Whether `self` is a revision of `previous` worth pointing out
+    pub fn revises(&self, previous: &DayOutlook, max_temp_change: f32) -> bool {
+        let temp_moved = match (self.temp_max, previous.temp_max) {
+            (Some(max), Some(previous_max)) => (max - previous_max).abs() >= max_temp_change,
+            _ => false,
+        };
+        is_wet(self.condition) != is_wet(previous.condition) || temp_moved
+    }
+}
+
+/// Whether the condition brings precipitation, a change between cloud levels isn't a revision
+fn is_wet(condition: Condition) -> bool {
+    matches!(
+        condition,
+        Condition::Drizzle
+            | Condition::LightRain
+            | Condition::Rain
+            | Condition::HeavyRain
+            | Condition::FreezingRain
+            | Condition::LightShowers
+            | Condition::Showers
+            | Condition::HeavyShowers
+            | Condition::Snow
+            | Condition::SnowShowers
+            | Condition::Storm
+            | Condition::StormWithHail
+            | Condition::Cyclone
+    )
+}
+
+/// Latest outlook of a day and when it was last revised
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrackedDay {
+    pub outlook: DayOutlook,
+    #[serde(default)]
+    pub revised_at: Option<DateTime<Utc>>,
+}
+
+/// Outlooks of the forecast days, by date
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ForecastOutlooks {
+    pub days: BTreeMap<NaiveDate, TrackedDay>,
+}
+
+impl ForecastOutlooks {
+    /// Reads the outlooks at `path`, empty when there are none yet or they cannot be read
+    pub fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            logger::warning(format!(
+                "Ignoring unreadable forecast outlooks {}: {e}",
+                path.display()
+            ));
+            Self::default()
+        })
+    }
+
+    /// Writes the outlooks to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_string(self).expect("outlooks always serialize"),
+        )
+    }
+
+    /// Records the outlooks of `daily`, marking the days that `revises` the recorded ones as
+    /// revised at `now`, and drops the days before `today`
+    pub fn update(
+        &mut self,
+        daily: &[DailyForecast],
+        today: NaiveDate,
+        now: DateTime<Utc>,
+        max_temp_change: f32,
+    ) {
+        for day in daily {
+            let Some(date) = day.date.filter(|date| *date >= today) else {
+                continue;
+            };
+            let outlook = DayOutlook::of(day);
+            let tracked = self.days.entry(date).or_insert(TrackedDay {
+                outlook,
+                revised_at: None,
+            });
+            if outlook.revises(&tracked.outlook, max_temp_change) {
+                logger::detail(format!(
+                    "Forecast for {date} revised: {} to {}",
+                    tracked.outlook.condition.description(),
+                    outlook.condition.description()
+                ));
+                tracked.revised_at = Some(now);
+            }
+            tracked.outlook = outlook;
+        }
+        self.days.retain(|date, _| *date >= today);
+    }
+
+    /// Days revised less than `marker_hours` before `now`
+    pub fn revised_days(&self, now: DateTime<Utc>, marker_hours: u32) -> Vec<NaiveDate> {
+        let since = now - Duration::hours(marker_hours.into());
+        self.days
+            .iter()
+            .filter(|(_, day)| day.revised_at.is_some_and(|revised| revised > since))
+            .map(|(date, _)| *date)
+            .collect()
+    }
+}
+
+/// Outlooks file of the active profile
+pub fn outlooks_path() -> PathBuf {
+    cache_path().join(OUTLOOKS_FILE_NAME)
+}
+
+/// Records the outlooks of `daily` and returns the days to mark as revised, none when the marker
+/// is disabled
+pub fn forecast_changes(
+    settings: &ForecastChanges,
+    daily: &[DailyForecast],
+    today: NaiveDate,
+    now: DateTime<Utc>,
+) -> Vec<NaiveDate> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+
+    let path = outlooks_path();
+    let mut outlooks = ForecastOutlooks::load(&path);
+    outlooks.update(daily, today, now, settings.max_temp_change);
+    if let Err(e) = outlooks.save(&path) {
+        logger::warning(format!("Failed to save the forecast outlooks: {e}"));
+    }
+
+    outlooks.revised_days(now, settings.marker_hours)
+}
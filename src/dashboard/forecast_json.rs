@@ -0,0 +1,165 @@
+//! Forecast data served as JSON by the web server (`/api/forecast.json`)
+//!
+//! Holds the same normalized domain data the dashboard renders, limited to the
+//! dashboard's windows: 24 hours from the current hour and 7 days from today.
+//! Also holds the dashboard diagnostics served at `/api/diagnostics.json`.
+//!
+//! The JSON is a contract with companion apps and widgets, so it only changes with
+//! [`FORECAST_SCHEMA_VERSION`]. Adding a field keeps the version, renaming, removing or retyping
+//! one bumps it. Providers convert to the domain models, so a provider refactor never shows here.
+//!
+//! | Version | Changes                                                                 |
+//! |---------|-------------------------------------------------------------------------|
+//! | 1       | `generated_at`, `current`, `hourly`, `daily` and `diagnostics`          |
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::Serialize;
+use strum_macros::Display;
+
+use crate::clock::Clock;
+use crate::domain::models::{DailyForecast, HourlyForecast};
+use crate::errors::{DashboardError, Description, DiagnosticPriority};
+
+/// Hours of hourly forecast shown on the dashboard graph
+const HOURLY_WINDOW_HOURS: i64 = 24;
+/// Days of daily forecast shown on the dashboard, today included
+const DAILY_WINDOW_DAYS: u64 = 7;
+
+/// Version of the forecast JSON schema, sent as `schema_version`
+pub const FORECAST_SCHEMA_VERSION: u32 = 1;
+
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize)]
+pub struct ForecastJson {
+    /// [`FORECAST_SCHEMA_VERSION`] of the JSON
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    /// Forecast for the current hour, `None` when the hourly data doesn't cover it
+    pub current: Option<HourlyForecast>,
+    pub hourly: Vec<HourlyForecast>,
+    pub daily: Vec<DailyForecast>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A dashboard diagnostic, e.g. stale cached data
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub priority: DiagnosticPriority,
+    pub summary: &'static str,
+    pub details: String,
+}
+
+impl From<&DashboardError> for Diagnostic {
+    fn from(error: &DashboardError) -> Self {
+        Self {
+            priority: error.priority(),
+            summary: error.short_description(),
+            details: error.long_description(),
+        }
+    }
+}
+
+/// Sorts the diagnostics from the highest priority down, the order the dashboard shows them in
+fn by_priority(warnings: &[DashboardError]) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = warnings.iter().map(Diagnostic::from).collect();
+    diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.priority));
+    diagnostics
+}
+
+/// Overall state of a served dashboard, sent in the `X-Dashboard-Status` header
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DashboardStatus {
+    /// Fresh and complete data
+    Ok,
+    /// Rendered from stale cached data, with missing days or with another diagnostic than a
+    /// weather warning
+    Degraded,
+    /// Rendering failed, the image only reports the failure
+    Failed,
+}
+
+/// Dashboard status with the diagnostics behind it, served at `/api/diagnostics.json`
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardDiagnostics {
+    pub status: DashboardStatus,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DashboardDiagnostics {
+    /// Weather warnings are shown but don't degrade the status, a display skipping degraded
+    /// refreshes must keep updating through a warning
+    pub fn new(warnings: &[DashboardError]) -> Self {
+        let degraded = warnings
+            .iter()
+            .any(|warning| !matches!(warning, DashboardError::WeatherWarning { .. }));
+        let status = if degraded {
+            DashboardStatus::Degraded
+        } else {
+            DashboardStatus::Ok
+        };
+
+        Self {
+            status,
+            diagnostics: by_priority(warnings),
+        }
+    }
+
+    /// Diagnostics of the frame served in place of a dashboard that failed to render
+    pub fn failed() -> Self {
+        Self {
+            status: DashboardStatus::Failed,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+impl ForecastJson {
+    pub fn new(
+        daily: Vec<DailyForecast>,
+        hourly: Vec<HourlyForecast>,
+        warnings: &[DashboardError],
+        clock: &dyn Clock,
+    ) -> Self {
+        let now = clock.now_utc();
+        let current_hour = now
+            .with_minute(0)
+            .and_then(|t| t.with_second(0))
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(now);
+        let hourly_window_end = current_hour + Duration::hours(HOURLY_WINDOW_HOURS);
+
+        let hourly: Vec<HourlyForecast> = hourly
+            .into_iter()
+            .filter(|forecast| forecast.time >= current_hour && forecast.time < hourly_window_end)
+            .collect();
+        let current = hourly
+            .first()
+            .filter(|forecast| forecast.time == current_hour)
+            .cloned();
+
+        let today = clock.now_local().date_naive();
+        let daily_window_end = today + chrono::Days::new(DAILY_WINDOW_DAYS);
+        let daily = daily
+            .into_iter()
+            .filter(|forecast| {
+                forecast
+                    .date
+                    .is_some_and(|date| date >= today && date < daily_window_end)
+            })
+            .collect();
+
+        Self {
+            schema_version: FORECAST_SCHEMA_VERSION,
+            generated_at: now,
+            current,
+            hourly,
+            daily,
+            diagnostics: by_priority(warnings),
+        }
+    }
+}
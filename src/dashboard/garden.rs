@@ -0,0 +1,121 @@
+//! "Watering needed" indicator for a garden display
+//!
+//! Soil moisture, reference evapotranspiration (ET0) and rain come from Open-Meteo for the
+//! configured location, whichever provider renders the forecast. The garden needs water when the
+//! soil is drier than the threshold, or when the last 3 days evaporated more than it rained.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::apis::open_meteo::models::OpenMeteoGardenResponse;
+use crate::configs::settings::{Colours, Garden};
+use crate::dashboard::panel::Panel;
+use crate::logger;
+use crate::providers::{factory::cache_path, open_meteo::OpenMeteoProvider};
+
+/// Water drop drawn in a 24x24 box
+const DROP_PATH: &str = "M12 2C12 2 5 10 5 15a7 7 0 0 0 14 0C19 10 12 2 12 2Z";
+
+/// Soil and water balance the indicator is based on
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GardenConditions {
+    /// Latest volumetric soil moisture at 3-9 cm in percent
+    pub soil_moisture: Option<f32>,
+    /// Reference evapotranspiration minus rain over the last 3 days in mm, `None` without ET0 or
+    /// rain in that time
+    pub water_deficit: Option<f32>,
+}
+
+impl GardenConditions {
+    /// Summarises the garden data at `now`, later hours are forecasts and left out
+    pub fn at(data: &OpenMeteoGardenResponse, now: DateTime<Utc>) -> Self {
+        let hourly = &data.hourly;
+        let past = |values: &[Option<f32>], since: DateTime<Utc>| {
+            hourly
+                .time
+                .iter()
+                .zip(values)
+                .filter(move |(time, _)| **time > since && **time <= now)
+                .filter_map(|(_, value)| *value)
+                .collect::<Vec<f32>>()
+        };
+
+        let since = now - Duration::days(3);
+        let soil_moisture = past(&hourly.soil_moisture_3_to_9cm, since)
+            .last()
+            .map(|moisture| moisture * 100.0);
+        let et0 = past(&hourly.et0_fao_evapotranspiration, since);
+        let rain = past(&hourly.precipitation, since);
+        let water_deficit = (!et0.is_empty() && !rain.is_empty())
+            .then(|| et0.iter().sum::<f32>() - rain.iter().sum::<f32>());
+
+        Self {
+            soil_moisture,
+            water_deficit,
+        }
+    }
+
+    /// Whether the garden needs water with the thresholds of `settings`
+    pub fn needs_water(&self, settings: &Garden) -> bool {
+        self.soil_moisture
+            .is_some_and(|moisture| moisture < settings.soil_moisture_threshold)
+            || self
+                .water_deficit
+                .is_some_and(|deficit| deficit > settings.water_deficit_threshold)
+    }
+}
+
+/// Fetches the garden data, `None` when the indicator is disabled or the data is unavailable
+pub async fn fetch_garden(settings: &Garden) -> Option<OpenMeteoGardenResponse> {
+    if !settings.enabled {
+        return None;
+    }
+
+    logger::detail("Fetching soil moisture and evapotranspiration");
+    OpenMeteoProvider::new(cache_path()).fetch_garden().await
+}
+
+/// Watering status of the panel
+fn watering(needs_water: bool) -> &'static str {
+    if needs_water {
+        "Watering needed"
+    } else {
+        "No watering"
+    }
+}
+
+/// Builds the indicator panel in `colours`: a filled drop when the garden needs water, an outline
+/// otherwise, and "NA" when neither the soil moisture nor the water balance is known
+pub fn garden_panel(settings: &Garden, conditions: &GardenConditions, colours: &Colours) -> Panel {
+    let needs_water = conditions.needs_water(settings);
+    let colour = &colours.text_colour;
+    let drop_fill = if needs_water {
+        colours.rain_colour.to_string()
+    } else {
+        "none".to_string()
+    };
+    let (status, details) = match (conditions.soil_moisture, conditions.water_deficit) {
+        (None, None) => ("NA", "No garden data".to_string()),
+        (Some(moisture), Some(deficit)) => (
+            watering(needs_water),
+            format!("Soil {moisture:.0}% · deficit {deficit:.0} mm"),
+        ),
+        (Some(moisture), None) => (watering(needs_water), format!("Soil {moisture:.0}%")),
+        (None, Some(deficit)) => (watering(needs_water), format!("Deficit {deficit:.0} mm")),
+    };
+
+    let scale = settings.height / 24.0;
+    let line_height = settings.height / 2.0;
+    let font_size = line_height * 0.6;
+    Panel::svg(
+        settings.x,
+        settings.y,
+        settings.width,
+        settings.height,
+        format!(
+            r#"<path transform="scale({scale:.3})" d="{DROP_PATH}" fill="{drop_fill}" stroke="{colour}" stroke-width="1.5"/><text x="{x:.1}" y="{:.1}" dominant-baseline="middle" font-size="{font_size:.1}" font-weight="bold" fill="{colour}">{status}</text><text x="{x:.1}" y="{:.1}" dominant-baseline="middle" font-size="{font_size:.1}" fill="{colour}">{details}</text>"#,
+            line_height * 0.5,
+            line_height * 1.5,
+            x = settings.height,
+        ),
+    )
+}
@@ -0,0 +1,109 @@
+//! External image panel, e.g. a webcam snapshot or an image of the day
+//!
+//! The image is fetched on every render, cropped to fill the panel, reduced to the display
+//! palette and embedded in the SVG as a data URI. The prepared image is cached, so a failed
+//! fetch shows the previous image instead of an empty region.
+
+use anyhow::{anyhow, Error};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{imageops::FilterType, ImageFormat};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::configs::settings::ImagePanel;
+use crate::display_options::DisplayOptions;
+use crate::http::http_client;
+use crate::logger;
+
+const IMAGE_PANEL_CACHE_FILE: &str = "image_panel.png";
+/// The panel is decoration, a slow image server must not hold up the forecast
+const IMAGE_PANEL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Fetches and prepares the panel image, falling back to the cached one when that fails.
+///
+/// # Arguments
+///
+/// * `settings` - Image panel settings, `None` is returned when no URL is configured.
+/// * `cache_dir` - Directory the prepared image is cached in.
+/// * `offline` - Only use the cached image, e.g. when weather API requests are disabled.
+///
+/// # Returns
+///
+/// * The prepared image as a PNG data URI, `None` when there is neither a fresh nor a cached image.
+pub async fn fetch_image_panel(
+    settings: &ImagePanel,
+    cache_dir: &Path,
+    offline: bool,
+) -> Option<String> {
+    let url = settings.url.as_ref()?;
+    let cache_file = cache_dir.join(IMAGE_PANEL_CACHE_FILE);
+
+    if !offline {
+        logger::detail(format!("Fetching image panel from {url}"));
+        match fetch_prepared_image(url.as_str(), settings).await {
+            Ok(png) => {
+                let written =
+                    fs::create_dir_all(cache_dir).and_then(|()| fs::write(&cache_file, &png));
+                if let Err(e) = written {
+                    logger::warning(format!("Failed to cache the image panel: {e}"));
+                }
+                return Some(png_data_uri(&png));
+            }
+            Err(e) => logger::warning(format!("Image panel fetch failed: {e}")),
+        }
+    }
+
+    match fs::read(&cache_file) {
+        Ok(png) => {
+            logger::detail("Using the cached image panel");
+            Some(png_data_uri(&png))
+        }
+        Err(_) => {
+            logger::warning("No cached image panel available, hiding the panel");
+            None
+        }
+    }
+}
+
+async fn fetch_prepared_image(url: &str, settings: &ImagePanel) -> Result<Vec<u8>, Error> {
+    let bytes = http_client()
+        .get(url)
+        .timeout(IMAGE_PANEL_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    prepare_image(&bytes, settings)
+}
+
+/// Decodes an image, crops it to fill the panel, reduces it to the panel palette and encodes it as PNG
+pub fn prepare_image(bytes: &[u8], settings: &ImagePanel) -> Result<Vec<u8>, Error> {
+    if settings.width == 0 || settings.height == 0 {
+        return Err(anyhow!(
+            "image panel size {}x{} must not be empty",
+            settings.width,
+            settings.height
+        ));
+    }
+
+    let image = image::load_from_memory(bytes)?
+        .resize_to_fill(settings.width, settings.height, FilterType::Triangle)
+        .to_rgb8();
+    let image = DisplayOptions {
+        palette: Some(settings.palette),
+        dither: settings.dither,
+        ..DisplayOptions::default()
+    }
+    .apply(image);
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+fn png_data_uri(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(png))
+}
@@ -0,0 +1,282 @@
+//! Indoor temperature and humidity against outdoors
+//!
+//! The indoor reading comes from a JSON feed, e.g. the state of a Home Assistant entity, or from
+//! a JSON file a local sensor script keeps up to date, the values are picked with JSON pointers.
+//! It is compared with the current hour of the forecast, and the comfort rules suggest opening
+//! the windows when outdoors would bring the room back into the comfortable range.
+
+use anyhow::Error;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::configs::settings::{Colours, ComfortRules, Indoor};
+use crate::dashboard::number_format::number_format;
+use crate::dashboard::panel::Panel;
+use crate::domain::models::HourlyForecast;
+use crate::http::http_client;
+use crate::logger;
+use crate::utils::escape_xml;
+
+/// The indoor feed is decoration, a slow sensor must not hold up the forecast
+const INDOOR_FEED_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Latest indoor sensor values
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IndoorReading {
+    /// Temperature in the configured unit
+    pub temperature: Option<f32>,
+    /// Relative humidity in percent
+    pub humidity: Option<f32>,
+}
+
+/// What the comfort rules suggest doing with the windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowHint {
+    /// Outdoors is cooler when it's too warm inside, or warmer when it's too cold
+    Open,
+    /// Outdoors is drier when it's too humid inside
+    AirOut,
+    /// Outdoors is even warmer when it's too warm inside
+    KeepClosed,
+}
+
+impl WindowHint {
+    pub fn text(self) -> &'static str {
+        match self {
+            WindowHint::Open => "Open the windows",
+            WindowHint::AirOut => "Open the windows to air out",
+            WindowHint::KeepClosed => "Keep the windows closed",
+        }
+    }
+}
+
+/// Indoor reading next to the outdoor values of the current hour
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IndoorComparison {
+    pub indoor: IndoorReading,
+    pub outdoor: IndoorReading,
+}
+
+impl IndoorComparison {
+    /// Compares `indoor` with the forecast at `now`, the current hour is the latest hourly
+    /// forecast that has started
+    pub fn at(indoor: IndoorReading, hourly: &[HourlyForecast], now: DateTime<Utc>) -> Self {
+        let current_hour = hourly
+            .iter()
+            .filter(|hour| hour.time <= now)
+            .max_by_key(|hour| hour.time);
+        Self {
+            indoor,
+            outdoor: IndoorReading {
+                temperature: current_hour.map(|hour| hour.temperature.value),
+                humidity: current_hour.map(|hour| f32::from(hour.relative_humidity)),
+            },
+        }
+    }
+
+    /// Indoor minus outdoor temperature
+    pub fn temperature_delta(&self) -> Option<f32> {
+        Some(self.indoor.temperature? - self.outdoor.temperature?)
+    }
+
+    /// Indoor minus outdoor relative humidity, in percentage points
+    pub fn humidity_delta(&self) -> Option<f32> {
+        Some(self.indoor.humidity? - self.outdoor.humidity?)
+    }
+
+    /// What `rules` suggest, `None` when the room is comfortable or outdoors wouldn't help
+    pub fn window_hint(&self, rules: &ComfortRules) -> Option<WindowHint> {
+        if let (Some(inside), Some(outside)) = (self.indoor.temperature, self.outdoor.temperature) {
+            if inside > rules.max_temperature {
+                if outside <= inside - rules.temperature_delta {
+                    return Some(WindowHint::Open);
+                }
+                if outside >= inside {
+                    return Some(WindowHint::KeepClosed);
+                }
+            }
+            if inside < rules.min_temperature && outside >= inside + rules.temperature_delta {
+                return Some(WindowHint::Open);
+            }
+        }
+
+        // Airing out on a cold day only trades the humidity for a cold room
+        let mild_outside = self
+            .outdoor
+            .temperature
+            .is_none_or(|outside| outside >= rules.min_temperature);
+        let drier_outside = self
+            .humidity_delta()
+            .is_some_and(|delta| delta >= rules.humidity_delta);
+        let humid_inside = self
+            .indoor
+            .humidity
+            .is_some_and(|humidity| humidity > rules.max_humidity);
+        (humid_inside && drier_outside && mild_outside).then_some(WindowHint::AirOut)
+    }
+}
+
+/// Fetches the indoor reading, `None` when disabled, without a source or when the source can't
+/// be read.
+///
+/// # Arguments
+///
+/// * `settings` - Indoor settings, the feed is used over the file when both are set.
+/// * `offline` - Don't request the feed, e.g. when weather API requests are disabled. The file
+///   is still read.
+///
+/// The reading is not cached, an old indoor temperature would be misleading.
+pub async fn fetch_indoor(settings: &Indoor, offline: bool) -> Option<IndoorReading> {
+    if !settings.enabled {
+        return None;
+    }
+
+    let source = match (&settings.url, &settings.path) {
+        (Some(url), _) if !offline => {
+            logger::detail(format!("Fetching indoor reading from {url}"));
+            fetch_feed(url.as_str(), settings.token.as_deref()).await
+        }
+        (_, Some(path)) => {
+            logger::detail(format!("Reading indoor sensor file {}", path.display()));
+            std::fs::read_to_string(path)
+                .map_err(Error::from)
+                .and_then(|text| Ok(serde_json::from_str(&text)?))
+        }
+        // Offline without a file to fall back on
+        (Some(_), None) => return None,
+        (None, None) => {
+            logger::warning("Indoor display enabled without a url or path, hiding it");
+            return None;
+        }
+    };
+    let source = match source {
+        Ok(source) => source,
+        Err(e) => {
+            logger::warning(format!("Indoor reading unavailable: {e}"));
+            return None;
+        }
+    };
+
+    let reading = parse_indoor_reading(
+        &source,
+        &settings.temperature_pointer,
+        &settings.humidity_pointer,
+    );
+    if reading.is_none() {
+        logger::warning(format!(
+            "Indoor source has no values at {} and {}",
+            settings.temperature_pointer, settings.humidity_pointer
+        ));
+    }
+    reading
+}
+
+async fn fetch_feed(url: &str, token: Option<&str>) -> Result<Value, Error> {
+    let mut request = http_client().get(url).timeout(INDOOR_FEED_TIMEOUT);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    Ok(request.send().await?.error_for_status()?.json().await?)
+}
+
+/// Reads the temperature and humidity at the JSON pointers of `source`, `None` when neither is
+/// there.
+///
+/// A value may be a number or a numeric string, Home Assistant states are strings.
+pub fn parse_indoor_reading(
+    source: &Value,
+    temperature_pointer: &str,
+    humidity_pointer: &str,
+) -> Option<IndoorReading> {
+    let value = |pointer: &str| -> Option<f32> {
+        match source.pointer(pointer)? {
+            Value::Number(number) => number.as_f64().map(|value| value as f32),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+        .filter(|value: &f32| value.is_finite())
+    };
+
+    let reading = IndoorReading {
+        temperature: value(temperature_pointer),
+        humidity: value(humidity_pointer),
+    };
+    (reading != IndoorReading::default()).then_some(reading)
+}
+
+/// Lines of the indoor display, lines without data are left out
+pub fn indoor_lines(comparison: &IndoorComparison, rules: &ComfortRules) -> Vec<String> {
+    let format = number_format();
+    // Deltas are signed, so a colder or drier room reads as such
+    let with_unit = |value: f32, decimals: usize, unit: &str, signed: bool| {
+        let number = format.fixed(value, decimals);
+        let number = if signed && value >= 0.0 {
+            format!("+{number}")
+        } else {
+            number
+        };
+        format.with_unit(&number, unit)
+    };
+    let temperature = |value: f32, signed: bool| with_unit(value, 1, "°", signed);
+    let humidity = |value: f32, signed: bool| with_unit(value, 0, "%", signed);
+    let join = |temperature: Option<String>, humidity: Option<String>| {
+        let parts: Vec<String> = temperature.into_iter().chain(humidity).collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    };
+
+    let reading = join(
+        comparison.indoor.temperature.map(|t| temperature(t, false)),
+        comparison.indoor.humidity.map(|h| humidity(h, false)),
+    );
+    let delta = join(
+        comparison.temperature_delta().map(|t| temperature(t, true)),
+        comparison.humidity_delta().map(|h| humidity(h, true)),
+    );
+
+    let mut lines = Vec::new();
+    lines.extend(reading.map(|reading| format!("Indoor {reading}")));
+    lines.extend(delta.map(|delta| format!("vs outside {delta}")));
+    lines.extend(
+        comparison
+            .window_hint(rules)
+            .map(|hint| hint.text().to_string()),
+    );
+    lines
+}
+
+/// Builds the indoor panel in the text colour of `colours`, `None` when there is nothing to show
+pub fn indoor_panel(
+    settings: &Indoor,
+    comparison: &IndoorComparison,
+    colours: &Colours,
+) -> Option<Panel> {
+    let lines = indoor_lines(comparison, &settings.comfort);
+    if lines.is_empty() {
+        return None;
+    }
+
+    // Room for three lines, so the display doesn't jump around when there's no hint
+    let line_height = settings.height / 3.0;
+    let font_size = line_height * 0.75;
+    let text: String = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let weight = if index == 0 { "bold" } else { "normal" };
+            format!(
+                r#"<text x="0" y="{:.1}" dominant-baseline="middle" font-size="{font_size:.1}" font-weight="{weight}" fill="{}">{}</text>"#,
+                line_height * (index as f32 + 0.5),
+                colours.text_colour,
+                escape_xml(line)
+            )
+        })
+        .collect();
+
+    Some(Panel::svg(
+        settings.x,
+        settings.y,
+        settings.width,
+        settings.height,
+        text,
+    ))
+}
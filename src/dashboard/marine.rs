@@ -0,0 +1,173 @@
+//! Swell, waves and sea wind warnings of the marine layout
+//!
+//! Waves and swell come from the Open-Meteo Marine API for the configured location, whichever
+//! provider renders the forecast. The sea wind warning is the Bureau of Meteorology marine wind
+//! warning in force for the nearby coastal waters, fetched with the BOM warnings, so it needs the
+//! BOM provider with `bom_warnings` on.
+//!
+//! The sea state is an SVG fragment in its own 170 x 75 coordinates, scaled to fill the `<svg>`
+//! region the template puts `{marine_svg}` in, so the template decides where it goes and how big.
+
+use chrono::{DateTime, Utc};
+
+use crate::apis::open_meteo::models::OpenMeteoMarineResponse;
+use crate::configs::settings::{Colours, Layout, WaveHeightUnit};
+use crate::dashboard::number_format::number_format;
+use crate::logger;
+use crate::providers::{factory::cache_path, open_meteo::OpenMeteoProvider};
+use crate::CONFIG;
+
+/// Coordinates the fragment is drawn in
+const VIEW_WIDTH: f32 = 170.0;
+const VIEW_HEIGHT: f32 = 75.0;
+
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Level of a BOM marine wind warning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SeaWindWarning {
+    StrongWind,
+    Gale,
+    Storm,
+    Hurricane,
+}
+
+impl SeaWindWarning {
+    /// Level of a marine wind warning from its title, e.g. "Gale Warning for Port Phillip"
+    pub fn from_title(title: &str) -> Option<Self> {
+        let title = title.to_lowercase();
+        [
+            (Self::Hurricane, "hurricane force wind"),
+            (Self::Storm, "storm force wind"),
+            (Self::Gale, "gale"),
+            (Self::StrongWind, "strong wind"),
+        ]
+        .into_iter()
+        .find(|(_, name)| title.contains(name))
+        .map(|(warning, _)| warning)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::StrongWind => "Strong wind warning",
+            Self::Gale => "Gale warning",
+            Self::Storm => "Storm warning",
+            Self::Hurricane => "Hurricane warning",
+        }
+    }
+}
+
+/// Sea state the marine layout shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarineConditions {
+    /// Significant swell height in metres
+    pub swell_height: Option<f32>,
+    /// Swell period in seconds
+    pub swell_period: Option<f32>,
+    /// Direction the swell comes from in degrees
+    pub swell_direction: Option<f32>,
+    /// Significant height of wind waves and swell combined in metres
+    pub wave_height: Option<f32>,
+    /// Most severe marine wind warning in force
+    pub wind_warning: Option<SeaWindWarning>,
+}
+
+impl MarineConditions {
+    /// Sea state of the hour `now` falls in, with the most severe of the `wind_warnings` in force
+    pub fn at(
+        data: &OpenMeteoMarineResponse,
+        wind_warnings: &[String],
+        now: DateTime<Utc>,
+    ) -> Self {
+        let marine = &data.hourly;
+        let current = marine.time.iter().rposition(|time| *time <= now);
+        let value =
+            |values: &[Option<f32>]| current.and_then(|index| values.get(index).copied().flatten());
+
+        Self {
+            swell_height: value(&marine.swell_wave_height),
+            swell_period: value(&marine.swell_wave_period),
+            swell_direction: value(&marine.swell_wave_direction),
+            wave_height: value(&marine.wave_height),
+            wind_warning: wind_warnings
+                .iter()
+                .filter_map(|title| SeaWindWarning::from_title(title))
+                .max(),
+        }
+    }
+}
+
+/// 16-point compass name of a direction in degrees, e.g. "SSW"
+pub fn compass_point(degrees: f32) -> &'static str {
+    let index = (degrees.rem_euclid(360.0) / 22.5).round() as usize % COMPASS_POINTS.len();
+    COMPASS_POINTS[index]
+}
+
+/// Fetches the waves and swell, `None` without the marine layout or when the data is unavailable
+pub async fn fetch_marine() -> Option<OpenMeteoMarineResponse> {
+    if CONFIG.misc.layout != Layout::Marine {
+        return None;
+    }
+
+    logger::detail("Fetching waves and swell");
+    OpenMeteoProvider::new(cache_path()).fetch_marine().await
+}
+
+/// The sea state as an SVG filling its region in `colours`: swell and wave lines in `unit`, and
+/// the sea wind warning in the actual temperature's colour, red by default, when there is one
+pub fn marine_svg(
+    conditions: &MarineConditions,
+    unit: WaveHeightUnit,
+    colours: &Colours,
+) -> String {
+    let colour = colours.text_colour.to_string();
+    let format = number_format();
+    let height =
+        |metres: f32| format.with_unit(&format.fixed(unit.convert(metres), 1), &unit.to_string());
+
+    let swell = match conditions.swell_height {
+        Some(swell_height) => {
+            let mut line = format!("Swell {}", height(swell_height));
+            if let Some(period) = conditions.swell_period {
+                line.push(' ');
+                line.push_str(&format.with_unit(&format.fixed(period, 0), "s"));
+            }
+            if let Some(direction) = conditions.swell_direction {
+                line.push(' ');
+                line.push_str(compass_point(direction));
+            }
+            line
+        }
+        None => "Swell n/a".to_string(),
+    };
+    let waves = match conditions.wave_height {
+        Some(wave_height) => format!("Waves {}", height(wave_height)),
+        None => "Waves n/a".to_string(),
+    };
+
+    let line_height = VIEW_HEIGHT / 3.0;
+    let font_size = line_height * 0.6;
+    let line = |row: f32, text: &str, weight: &str, fill: &str| {
+        format!(
+            r#"<text x="0" y="{:.1}" dominant-baseline="middle" font-size="{font_size:.1}" font-weight="{weight}" fill="{fill}">{text}</text>"#,
+            line_height * (row + 0.5)
+        )
+    };
+    let mut lines = line(0.0, &swell, "bold", &colour);
+    lines.push_str(&line(1.0, &waves, "normal", &colour));
+    if let Some(warning) = conditions.wind_warning {
+        lines.push_str(&line(
+            2.0,
+            warning.label(),
+            "bold",
+            &colours.actual_temp_colour.to_string(),
+        ));
+    }
+
+    format!(
+        r#"<svg viewBox="0 0 {VIEW_WIDTH} {VIEW_HEIGHT}" width="100%" height="100%" preserveAspectRatio="xMinYMid meet">{lines}</svg>"#
+    )
+}
@@ -1,2 +1,21 @@
 pub mod chart;
+pub mod clock_face;
+pub mod commute;
+pub mod comparison;
 pub mod context;
+pub mod forecast_changes;
+pub mod forecast_json;
+pub mod garden;
+pub mod image_panel;
+pub mod indoor;
+pub mod marine;
+pub mod number_format;
+pub mod panel;
+pub mod quote_footer;
+pub mod rain_today;
+pub mod snow_report;
+pub mod summary;
+pub mod temperature_records;
+pub mod template_filters;
+pub mod text_fallback;
+pub mod theme;
@@ -0,0 +1,90 @@
+//! Locale-aware formatting of the numbers on the dashboard
+//!
+//! The `locale` setting picks the decimal separator and whether a unit is set apart from its
+//! number: `en` renders "23.4°C", `de` renders "23,4 °C". Only the language of the locale is
+//! looked at, so `de-AT` formats like `de`. Languages that write a decimal comma also put a space
+//! before the unit, languages that don't keep the unit next to the number.
+
+use std::fmt::Display;
+
+use crate::CONFIG;
+
+/// Languages writing a decimal comma, by their ISO 639-1 code
+const DECIMAL_COMMA_LANGUAGES: [&str; 31] = [
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv",
+    "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+/// How numbers and units are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    /// Whether a space goes between a number and its unit
+    pub unit_space: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            unit_space: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Format of `locale`, e.g. `en-AU`, `de_DE` or `fr`. Unknown languages format like English.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if DECIMAL_COMMA_LANGUAGES.contains(&language.as_str()) {
+            Self {
+                decimal_separator: ',',
+                unit_space: true,
+            }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// `value` as it displays, with the decimal separator of the locale
+    pub fn number(&self, value: impl Display) -> String {
+        let text = value.to_string();
+        if self.decimal_separator == '.' {
+            text
+        } else {
+            text.replace('.', &self.decimal_separator.to_string())
+        }
+    }
+
+    /// `value` with `decimals` digits after the decimal separator
+    pub fn fixed(&self, value: f32, decimals: usize) -> String {
+        self.number(format!("{value:.decimals$}"))
+    }
+
+    /// An already formatted `number` followed by `unit`, nothing added when the unit is empty
+    pub fn with_unit(&self, number: &str, unit: &str) -> String {
+        match (unit.is_empty(), self.unit_space) {
+            (true, _) => number.to_string(),
+            (false, true) => format!("{number} {unit}"),
+            (false, false) => format!("{number}{unit}"),
+        }
+    }
+
+    /// Reads back a number written by this format, e.g. "23,4" as 23.4
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        text.trim()
+            .replace(self.decimal_separator, ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+    }
+}
+
+/// Number format of the configured `locale`
+pub fn number_format() -> NumberFormat {
+    NumberFormat::for_locale(CONFIG.render_options.locale.as_ref())
+}
@@ -0,0 +1,210 @@
+//! Custom panels composited on top of the rendered dashboard
+//!
+//! A panel is an SVG fragment or a raster image placed at a position on the dashboard. Panels
+//! are registered once, e.g. by a widget crate at startup, and are drawn over the rendered
+//! template in registration order, so templates need no placeholder for them.
+//!
+//! ```no_run
+//! use pi_inky_weather_epd::dashboard::panel::{register_panel, Panel};
+//!
+//! register_panel(Panel::svg(10.0, 10.0, 100.0, 40.0, "<text y=\"30\">Hello</text>"));
+//! ```
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use resvg::usvg::roxmltree;
+use std::sync::{PoisonError, RwLock};
+
+use crate::clock::Clock;
+use crate::configs::settings::Misc;
+use crate::icon_cache::length;
+
+/// Size the frames drawn without a template are laid out for, in user units
+pub const DEFAULT_FRAME_SIZE: (f32, f32) = (800.0, 480.0);
+
+/// What a panel draws
+#[derive(Debug, Clone, PartialEq)]
+pub enum PanelContent {
+    /// SVG markup drawn in the panel's own coordinates, `(0, 0)` is the panel's top left corner.
+    /// Anything outside the panel is clipped.
+    Svg(String),
+    /// PNG image scaled to the panel size
+    Png(Vec<u8>),
+}
+
+/// Content placed at a position on the dashboard, in the template's user units
+#[derive(Debug, Clone, PartialEq)]
+pub struct Panel {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub content: PanelContent,
+}
+
+impl Panel {
+    pub fn svg(x: f32, y: f32, width: f32, height: f32, fragment: impl Into<String>) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            content: PanelContent::Svg(fragment.into()),
+        }
+    }
+
+    pub fn png(x: f32, y: f32, width: f32, height: f32, png: Vec<u8>) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            content: PanelContent::Png(png),
+        }
+    }
+
+    /// The panel as an SVG element
+    pub fn to_svg(&self) -> String {
+        let Self {
+            x,
+            y,
+            width,
+            height,
+            ..
+        } = self;
+        match &self.content {
+            PanelContent::Svg(fragment) => format!(
+                r#"<svg x="{x}" y="{y}" width="{width}" height="{height}" overflow="hidden">{fragment}</svg>"#
+            ),
+            PanelContent::Png(png) => format!(
+                r#"<image x="{x}" y="{y}" width="{width}" height="{height}" preserveAspectRatio="none" href="data:image/png;base64,{}"/>"#,
+                STANDARD.encode(png)
+            ),
+        }
+    }
+}
+
+/// Produces a panel on every render, `None` skips the panel for that render.
+///
+/// A [`Panel`] is a source that always draws the same content, closures taking the render
+/// clock can draw content that changes over time.
+pub trait PanelSource: Send + Sync {
+    fn render(&self, clock: &dyn Clock) -> Option<Panel>;
+}
+
+impl PanelSource for Panel {
+    fn render(&self, _clock: &dyn Clock) -> Option<Panel> {
+        Some(self.clone())
+    }
+}
+
+impl<F> PanelSource for F
+where
+    F: Fn(&dyn Clock) -> Option<Panel> + Send + Sync,
+{
+    fn render(&self, clock: &dyn Clock) -> Option<Panel> {
+        self(clock)
+    }
+}
+
+static PANELS: RwLock<Vec<Box<dyn PanelSource>>> = RwLock::new(Vec::new());
+
+/// Registers a panel drawn on every dashboard rendered from now on
+pub fn register_panel(source: impl PanelSource + 'static) {
+    PANELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Box::new(source));
+}
+
+/// Removes all registered panels
+pub fn clear_panels() {
+    PANELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
+
+/// Renders the registered panels for the current render
+pub fn registered_panels(clock: &dyn Clock) -> Vec<Panel> {
+    PANELS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .filter_map(|source| source.render(clock))
+        .collect()
+}
+
+/// Width and height of the root `<svg>` in user units, from its `width` and `height` or else its
+/// `viewBox`. `None` when it has neither or doesn't parse.
+pub fn svg_size(svg: &str) -> Option<(f32, f32)> {
+    let document = roxmltree::Document::parse(svg).ok()?;
+    let root = document.root_element();
+    let length = |name: &str| root.attribute(name).and_then(length);
+    length("width").zip(length("height")).or_else(|| {
+        let view_box: Vec<f32> = root
+            .attribute("viewBox")?
+            .split([' ', ','])
+            .filter_map(|value| value.parse().ok())
+            .collect();
+        match view_box[..] {
+            [_, _, width, height] if width > 0.0 && height > 0.0 => Some((width, height)),
+            _ => None,
+        }
+    })
+}
+
+/// Size of the dashboard in user units: the `display_resolution` over the `png_scale_factor`
+/// when a resolution is configured, else the size of the template at `template_path`, else
+/// [`DEFAULT_FRAME_SIZE`]
+pub fn frame_size(misc: &Misc) -> (f32, f32) {
+    if let Some((width, height)) = misc.display_resolution {
+        if misc.png_scale_factor > 0.0 {
+            return (
+                width as f32 / misc.png_scale_factor,
+                height as f32 / misc.png_scale_factor,
+            );
+        }
+    }
+    std::fs::read_to_string(&misc.template_path)
+        .ok()
+        .and_then(|template| svg_size(&template))
+        .unwrap_or(DEFAULT_FRAME_SIZE)
+}
+
+/// A `size` frame filled with `background`, with `content` laid out for [`DEFAULT_FRAME_SIZE`]
+/// scaled to fit it and centred
+pub fn scaled_frame(size: (f32, f32), background: &str, content: &str) -> String {
+    let (width, height) = size;
+    let (layout_width, layout_height) = DEFAULT_FRAME_SIZE;
+    let scale = (width / layout_width).min(height / layout_height);
+    let x = (width - layout_width * scale) / 2.0;
+    let y = (height - layout_height * scale) / 2.0;
+    format!(
+        r#"<svg width="{width}" height="{height}" font-family="Roboto, sans-serif" xmlns="http://www.w3.org/2000/svg">
+    <rect width="{width}" height="{height}" fill="{background}" />
+    <g transform="translate({x} {y}) scale({scale})">
+{content}    </g>
+</svg>"#
+    )
+}
+
+/// Inserts the panels just before the closing tag of the root `<svg>`, so they are drawn on top.
+///
+/// The SVG is returned unchanged when there are no panels or it has no closing tag.
+pub fn composite_panels(svg: String, panels: &[Panel]) -> String {
+    if panels.is_empty() {
+        return svg;
+    }
+    let Some(end) = svg.rfind("</svg>") else {
+        return svg;
+    };
+
+    let mut composited = String::with_capacity(svg.len());
+    composited.push_str(&svg[..end]);
+    for panel in panels {
+        composited.push_str(&panel.to_svg());
+        composited.push('\n');
+    }
+    composited.push_str(&svg[end..]);
+    composited
+}
@@ -0,0 +1,71 @@
+//! Quote or fact of the day along the bottom of the dashboard
+//!
+//! Quotes come from a local text file, one per line. The quote is picked by date, so every
+//! refresh on the same day shows the same one and the file is worked through day by day.
+
+use chrono::{Datelike, NaiveDate};
+use std::fs;
+
+use crate::configs::settings::{Colours, Footer};
+use crate::dashboard::panel::Panel;
+use crate::logger;
+use crate::utils::escape_xml;
+
+/// Average glyph width relative to the font size, used to keep a quote within the footer width
+const AVERAGE_CHAR_WIDTH_EM: f32 = 0.5;
+
+/// Reads the quotes file, skipping blank lines and `#` comments
+pub fn parse_quotes(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Picks the quote for `date`, the next day gets the next quote in the file
+pub fn quote_of_the_day<'a>(quotes: &[&'a str], date: NaiveDate) -> Option<&'a str> {
+    if quotes.is_empty() {
+        return None;
+    }
+    let day = usize::try_from(date.num_days_from_ce()).unwrap_or_default();
+    Some(quotes[day % quotes.len()])
+}
+
+/// Builds the footer panel for `date` in the text colour of `colours`, `None` when no quotes file
+/// is configured or it has no quotes
+pub fn quote_footer(settings: &Footer, date: NaiveDate, colours: &Colours) -> Option<Panel> {
+    let path = settings.quotes_path.as_ref()?;
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            logger::warning(format!(
+                "Failed to read quotes file {}: {e}",
+                path.display()
+            ));
+            return None;
+        }
+    };
+    let quote = quote_of_the_day(&parse_quotes(&content), date)?;
+
+    let max_chars = (settings.width / (settings.font_size * AVERAGE_CHAR_WIDTH_EM)) as usize;
+    let quote = if quote.chars().count() > max_chars {
+        let truncated: String = quote.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        quote.to_string()
+    };
+
+    Some(Panel::svg(
+        settings.x,
+        settings.y,
+        settings.width,
+        settings.height,
+        format!(
+            r#"<text x="50%" y="50%" dominant-baseline="middle" text-anchor="middle" font-size="{}" font-style="italic" fill="{}">{}</text>"#,
+            settings.font_size,
+            colours.text_colour,
+            escape_xml(&quote)
+        ),
+    ))
+}
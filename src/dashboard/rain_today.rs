@@ -0,0 +1,149 @@
+//! Rain fallen so far today and still expected
+//!
+//! Most providers start the hourly forecast at the current hour, so the hours already past are
+//! kept in `rain_log.json` in the cache directory, which is per profile. Every run records the
+//! hours of the forecast up to the current one, an hour recorded again is replaced, so the log
+//! ends up with the last forecast of each hour.
+//!
+//! With BOM observations the log also keeps the readings of the nearest station's rain gauge, so
+//! the rain since midnight is what actually fell rather than what was forecast. The gauge is
+//! emptied at 9am, the rain between two readings is their difference, or all of the later
+//! reading when the gauge was emptied in between.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain::models::{HourlyForecast, RainGaugeReading};
+use crate::logger;
+use crate::providers::factory::cache_path;
+
+/// Rain log file in the cache directory
+pub const RAIN_LOG_FILE_NAME: &str = "rain_log.json";
+
+/// Hours older than this are dropped from the log, today's hours are always kept
+pub const RAIN_LOG_RETENTION_HOURS: i64 = 48;
+
+/// Rain a gauge had collected when it was read
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GaugeTotal {
+    /// When the gauge was last emptied
+    pub since: DateTime<Utc>,
+    pub rain_mm: f32,
+}
+
+/// Rain of past hours in mm, by the start of the hour, and the rain gauge readings, by the time
+/// they were made
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RainLog {
+    pub hours: BTreeMap<DateTime<Utc>, f32>,
+    #[serde(default)]
+    pub gauge: BTreeMap<DateTime<Utc>, GaugeTotal>,
+}
+
+impl RainLog {
+    /// Reads the log at `path`, empty when there is none yet or it cannot be read
+    pub fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            logger::warning(format!(
+                "Ignoring unreadable rain log {}: {e}",
+                path.display()
+            ));
+            Self::default()
+        })
+    }
+
+    /// Writes the log to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_string(self).expect("rain log always serializes"),
+        )
+    }
+
+    /// Records the rain of the hours of `hourly` that started by `now` and drops the hours past
+    /// the retention
+    pub fn record(&mut self, hourly: &[HourlyForecast], now: DateTime<Utc>) {
+        for hour in hourly.iter().filter(|hour| hour.time <= now) {
+            self.hours
+                .insert(hour.time, hour.precipitation.calculate_median());
+        }
+        let oldest = now - Duration::hours(RAIN_LOG_RETENTION_HOURS);
+        self.hours.retain(|time, _| *time >= oldest);
+        self.gauge.retain(|time, _| *time >= oldest);
+    }
+
+    /// Records a rain gauge reading, a reading made again is replaced
+    pub fn record_gauge(&mut self, reading: RainGaugeReading) {
+        self.gauge.insert(
+            reading.time,
+            GaugeTotal {
+                since: reading.since,
+                rain_mm: reading.rain_mm,
+            },
+        );
+    }
+
+    /// Rain of the recorded hours starting in `start..end`, `None` when none is recorded
+    pub fn total_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<f32> {
+        let mut hours = self.hours.range(start..end).peekable();
+        hours.peek()?;
+        Some(hours.map(|(_, rain)| rain).sum())
+    }
+
+    /// Rain the gauge collected between the readings made in `start..end` and the reading before
+    /// each, `None` without a reading before one of them. The rain between the last reading
+    /// before `start` and the first one after it is all counted.
+    pub fn observed_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<f32> {
+        let mut previous = self
+            .gauge
+            .range(..start)
+            .next_back()
+            .map(|(_, total)| total);
+        let mut observed = None;
+        for total in self.gauge.range(start..end).map(|(_, total)| total) {
+            if let Some(previous) = previous {
+                let rain = if total.since == previous.since {
+                    (total.rain_mm - previous.rain_mm).max(0.0)
+                } else {
+                    total.rain_mm
+                };
+                *observed.get_or_insert(0.0) += rain;
+            }
+            previous = Some(total);
+        }
+        observed
+    }
+}
+
+/// Rain log file of the active profile
+pub fn rain_log_path() -> PathBuf {
+    cache_path().join(RAIN_LOG_FILE_NAME)
+}
+
+/// Records the past hours of `hourly` and the rain gauge reading in the rain log of the location
+/// and returns it
+pub fn update_rain_log(
+    hourly: &[HourlyForecast],
+    gauge_reading: Option<RainGaugeReading>,
+    now: DateTime<Utc>,
+) -> RainLog {
+    let path = rain_log_path();
+    let mut log = RainLog::load(&path);
+    log.record(hourly, now);
+    if let Some(reading) = gauge_reading {
+        log.record_gauge(reading);
+    }
+    if let Err(e) = log.save(&path) {
+        logger::warning(format!("Failed to save the rain log: {e}"));
+    }
+    log
+}
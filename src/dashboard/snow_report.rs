@@ -0,0 +1,231 @@
+//! Snow report for alpine locations
+//!
+//! Snow depth and the snowfall of the last 24 hours and 3 days come from Open-Meteo for the
+//! configured location, whichever provider renders the forecast. The lift status of a ski field
+//! can be added from a JSON feed, the open and total lift counts are picked with JSON pointers.
+
+use anyhow::Error;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+use crate::apis::open_meteo::models::OpenMeteoSnowResponse;
+use crate::configs::settings::{Colours, SnowReport};
+use crate::dashboard::panel::Panel;
+use crate::http::http_client;
+use crate::logger;
+use crate::providers::{factory::cache_path, open_meteo::OpenMeteoProvider};
+use crate::utils::escape_xml;
+
+/// The lift feed is decoration, a slow ski field server must not hold up the forecast
+const LIFT_STATUS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiftStatus {
+    pub open: u32,
+    pub total: u32,
+}
+
+/// Snow data fetched for the report, summarised at render time with [`SnowData::conditions_at`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnowData {
+    pub snow: Option<OpenMeteoSnowResponse>,
+    pub lifts: Option<LiftStatus>,
+}
+
+/// What the snow report shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnowConditions {
+    /// Latest snow depth in centimetres
+    pub depth_cm: Option<f32>,
+    /// Snowfall of the last 24 hours and 3 days in centimetres, `None` without snow data
+    pub snowfall_24h_cm: Option<f32>,
+    pub snowfall_3d_cm: Option<f32>,
+    pub lifts: Option<LiftStatus>,
+}
+
+impl SnowData {
+    /// Summarises the snow data at `now`, later hours are forecasts and left out
+    pub fn conditions_at(&self, now: DateTime<Utc>) -> SnowConditions {
+        let Some(snow) = &self.snow else {
+            return SnowConditions {
+                lifts: self.lifts,
+                ..SnowConditions::default()
+            };
+        };
+        let hourly = &snow.hourly;
+
+        let depth_cm = hourly
+            .time
+            .iter()
+            .zip(&hourly.snow_depth)
+            .filter(|(time, _)| **time <= now)
+            .filter_map(|(_, depth)| *depth)
+            .next_back()
+            .map(|depth| depth * 100.0);
+
+        // Snowfall is the sum of the hour before each timestamp
+        let snowfall_since = |since: DateTime<Utc>| -> f32 {
+            hourly
+                .time
+                .iter()
+                .zip(&hourly.snowfall)
+                .filter(|(time, _)| **time > since && **time <= now)
+                .filter_map(|(_, snowfall)| *snowfall)
+                .sum()
+        };
+
+        SnowConditions {
+            depth_cm,
+            snowfall_24h_cm: Some(snowfall_since(now - Duration::hours(24))),
+            snowfall_3d_cm: Some(snowfall_since(now - Duration::days(3))),
+            lifts: self.lifts,
+        }
+    }
+}
+
+/// Fetches the snow data and the lift status for the report.
+///
+/// # Arguments
+///
+/// * `settings` - Snow report settings, `None` is returned when the report is disabled.
+/// * `offline` - Don't fetch the lift status, e.g. when weather API requests are disabled.
+///   The snow data then comes from the cache.
+///
+/// # Returns
+///
+/// * The snow data, `None` when the report is disabled or neither source has data.
+pub async fn fetch_snow_report(settings: &SnowReport, offline: bool) -> Option<SnowData> {
+    if !settings.enabled {
+        return None;
+    }
+
+    logger::detail("Fetching snow report");
+    let provider = OpenMeteoProvider::new(cache_path());
+    let (snow, lifts) = tokio::join!(provider.fetch_snow(), fetch_lift_status(settings, offline));
+
+    if snow.is_none() && lifts.is_none() {
+        logger::warning("No snow data available, hiding the snow report");
+        return None;
+    }
+    Some(SnowData { snow, lifts })
+}
+
+/// Fetches the lift status feed, `None` without a feed URL or when it can't be read.
+///
+/// The lift status is not cached, yesterday's lift status would be misleading.
+pub async fn fetch_lift_status(settings: &SnowReport, offline: bool) -> Option<LiftStatus> {
+    let url = settings.lift_status_url.as_ref()?;
+    if offline {
+        return None;
+    }
+
+    logger::detail(format!("Fetching lift status from {url}"));
+    let feed = match fetch_feed(url.as_str()).await {
+        Ok(feed) => feed,
+        Err(e) => {
+            logger::warning(format!("Lift status fetch failed: {e}"));
+            return None;
+        }
+    };
+
+    let status = parse_lift_status(
+        &feed,
+        &settings.lifts_open_pointer,
+        &settings.lifts_total_pointer,
+    );
+    if status.is_none() {
+        logger::warning(format!(
+            "Lift status feed has no lift counts at {} and {}",
+            settings.lifts_open_pointer, settings.lifts_total_pointer
+        ));
+    }
+    status
+}
+
+async fn fetch_feed(url: &str) -> Result<Value, Error> {
+    Ok(http_client()
+        .get(url)
+        .timeout(LIFT_STATUS_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// Reads the open and total lift counts at the JSON pointers of `feed`.
+///
+/// A count may be a number, a numeric string or an array, which counts its entries.
+pub fn parse_lift_status(
+    feed: &Value,
+    open_pointer: &str,
+    total_pointer: &str,
+) -> Option<LiftStatus> {
+    let count = |pointer: &str| -> Option<u32> {
+        let count = match feed.pointer(pointer)? {
+            Value::Number(number) => number.as_u64()?,
+            Value::String(text) => text.trim().parse().ok()?,
+            Value::Array(entries) => entries.len() as u64,
+            _ => return None,
+        };
+        u32::try_from(count).ok()
+    };
+
+    Some(LiftStatus {
+        open: count(open_pointer)?,
+        total: count(total_pointer)?,
+    })
+}
+
+/// Lines of the snow report, lines without data are left out
+pub fn snow_report_lines(conditions: &SnowConditions) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(depth) = conditions.depth_cm {
+        lines.push(format!("Snow {depth:.0} cm"));
+    }
+    if let (Some(day), Some(three_days)) = (conditions.snowfall_24h_cm, conditions.snowfall_3d_cm) {
+        lines.push(format!("24h +{day:.0} cm · 3d +{three_days:.0} cm"));
+    }
+    if let Some(lifts) = conditions.lifts {
+        lines.push(format!("Lifts {}/{} open", lifts.open, lifts.total));
+    }
+    lines
+}
+
+/// Builds the snow report panel in the text colour of `colours`, `None` when there is nothing to
+/// show
+pub fn snow_report_panel(
+    settings: &SnowReport,
+    conditions: &SnowConditions,
+    colours: &Colours,
+) -> Option<Panel> {
+    let lines = snow_report_lines(conditions);
+    if lines.is_empty() {
+        return None;
+    }
+
+    // Room for three lines, so the report doesn't jump around when the lift line is missing
+    let line_height = settings.height / 3.0;
+    let font_size = line_height * 0.75;
+    let text: String = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let weight = if index == 0 { "bold" } else { "normal" };
+            format!(
+                r#"<text x="0" y="{:.1}" dominant-baseline="middle" font-size="{font_size:.1}" font-weight="{weight}" fill="{}">{}</text>"#,
+                line_height * (index as f32 + 0.5),
+                colours.text_colour,
+                escape_xml(line)
+            )
+        })
+        .collect();
+
+    Some(Panel::svg(
+        settings.x,
+        settings.y,
+        settings.width,
+        settings.height,
+        text,
+    ))
+}
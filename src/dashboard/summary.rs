@@ -0,0 +1,117 @@
+//! Short text summary of the hourly forecast, e.g. "Showers easing this afternoon, windy overnight"
+//!
+//! Built from simple rules over the next 24 hours, for templates that prefer text to the graph.
+
+use chrono::{DateTime, Duration, Local, Timelike};
+use std::collections::HashMap;
+
+use crate::domain::conditions::Condition;
+use crate::domain::models::HourlyForecast;
+use crate::weather::icons::RainAmountName;
+
+/// Hours covered by the summary, the same window as the hourly graph
+const SUMMARY_WINDOW_HOURS: i64 = 24;
+/// Wind speed (km/h) from which an hour counts as windy, the strong wind icon threshold
+const WINDY_SPEED_KMH: u16 = 41;
+
+/// Summarizes the hourly forecast from `now` on.
+///
+/// # Arguments
+///
+/// * `hourly_forecast_data` - Hourly forecasts, each covering one hour starting at `time`.
+/// * `now` - Current local time.
+/// * `chance_threshold` - Minimum chance of precipitation (percent) counted as rain.
+/// * `use_gust` - Use gust speed instead of the mean wind speed.
+///
+/// # Returns
+///
+/// * The summary, empty when there is no hourly data from `now` on.
+pub fn hourly_summary(
+    hourly_forecast_data: &[HourlyForecast],
+    now: DateTime<Local>,
+    chance_threshold: u16,
+    use_gust: bool,
+) -> String {
+    let window_end = now + Duration::hours(SUMMARY_WINDOW_HOURS);
+    let window: Vec<&HourlyForecast> = hourly_forecast_data
+        .iter()
+        .filter(|forecast| forecast.time + Duration::hours(1) > now && forecast.time < window_end)
+        .collect();
+    let Some(first) = window.first() else {
+        return String::new();
+    };
+
+    let wet: Vec<&HourlyForecast> = window
+        .iter()
+        .copied()
+        .filter(|forecast| forecast.is_rain_likely(chance_threshold))
+        .collect();
+    let rain = if wet.iter().any(|forecast| {
+        matches!(
+            forecast.precipitation.amount_to_name(true),
+            RainAmountName::Rain
+        )
+    }) {
+        "Rain"
+    } else {
+        "Showers"
+    };
+
+    let mut clauses = Vec::new();
+
+    if first.is_rain_likely(chance_threshold) {
+        match window
+            .iter()
+            .find(|forecast| !forecast.is_rain_likely(chance_threshold))
+        {
+            Some(dry) => clauses.push(format!("{rain} easing {}", period(dry, now))),
+            None => clauses.push(format!("{rain} continuing")),
+        }
+    } else if let Some(first_wet) = wet.first() {
+        clauses.push(format!("{rain} developing {}", period(first_wet, now)));
+    } else {
+        clauses.push(dominant_condition(&window).description().to_string());
+    }
+
+    match window
+        .iter()
+        .position(|forecast| forecast.wind.get_speed(use_gust) >= WINDY_SPEED_KMH)
+    {
+        Some(0) => clauses.push("windy".to_string()),
+        Some(index) => clauses.push(format!("windy {}", period(window[index], now))),
+        None => {}
+    }
+
+    clauses.join(", ")
+}
+
+/// Most frequent condition in the window, the earliest one wins a tie
+fn dominant_condition(window: &[&HourlyForecast]) -> Condition {
+    let conditions: Vec<Condition> = window.iter().map(|forecast| forecast.condition()).collect();
+    let mut counts: HashMap<Condition, usize> = HashMap::new();
+    for condition in &conditions {
+        *counts.entry(*condition).or_default() += 1;
+    }
+
+    let most = counts.values().copied().max().unwrap_or(0);
+    conditions
+        .into_iter()
+        .find(|condition| counts[condition] == most)
+        .unwrap_or(Condition::Clear)
+}
+
+/// Part of the day an hour falls in, relative to `now`
+fn period(forecast: &HourlyForecast, now: DateTime<Local>) -> &'static str {
+    let time = forecast.time.with_timezone(&Local);
+    let tomorrow = time.date_naive() > now.date_naive();
+
+    match (tomorrow, time.hour()) {
+        (_, 0..=4) | (false, 22..) => "overnight",
+        (false, 5..=11) => "this morning",
+        (false, 12..=17) => "this afternoon",
+        (false, _) => "this evening",
+        (true, 5..=11) => "tomorrow morning",
+        (true, 12..=17) => "tomorrow afternoon",
+        (true, _) => "tomorrow evening",
+    }
+}
@@ -0,0 +1,150 @@
+//! "Record heat" badge from the forecast history of the location
+//!
+//! Today's forecast maximum is compared with the maximums recorded on earlier days and the
+//! minimum with the earlier minimums. Beating every recorded day is a record, being among the
+//! highest (or lowest) 10% of them is a top decile. Heat is shown before cold.
+
+use chrono::NaiveDate;
+use std::fmt::{self, Display};
+
+use crate::configs::settings::{Colours, TemperatureRecords};
+use crate::dashboard::panel::Panel;
+use crate::domain::models::DailyForecast;
+use crate::history::{history_path, DayHistory, History};
+use crate::logger;
+
+/// How today's forecast compares with the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureRecord {
+    RecordHeat,
+    TopDecileHeat,
+    RecordCold,
+    TopDecileCold,
+}
+
+impl TemperatureRecord {
+    pub fn is_heat(self) -> bool {
+        matches!(self, Self::RecordHeat | Self::TopDecileHeat)
+    }
+}
+
+impl Display for TemperatureRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::RecordHeat => "Record heat",
+            Self::TopDecileHeat => "Top 10% heat",
+            Self::RecordCold => "Record cold",
+            Self::TopDecileCold => "Top 10% cold",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Today's forecast from the daily forecast, in °C
+pub fn today_history(daily: &[DailyForecast], today: NaiveDate) -> Option<DayHistory> {
+    daily
+        .iter()
+        .find(|day| day.date == Some(today))
+        .map(|day| DayHistory {
+            temp_max: day.temp_max.map(|t| t.to_celsius().value),
+            temp_min: day.temp_min.map(|t| t.to_celsius().value),
+        })
+}
+
+/// Whether `value` is a record and whether it is in the top decile of `recorded`, `exceeds` tells
+/// whether a value is more extreme than another
+fn rank(value: f32, recorded: &[f32], exceeds: impl Fn(f32, f32) -> bool) -> (bool, bool) {
+    let not_beaten = recorded
+        .iter()
+        .filter(|&&other| !exceeds(value, other))
+        .count();
+    (not_beaten == 0, not_beaten * 10 <= recorded.len())
+}
+
+/// Compares `today` with the days recorded before it, `None` with fewer than `min_history_days`
+/// recorded days or nothing remarkable
+pub fn temperature_record(
+    history: &History,
+    today: NaiveDate,
+    forecast: &DayHistory,
+    min_history_days: usize,
+) -> Option<TemperatureRecord> {
+    let earlier: Vec<&DayHistory> = history.days_before(today).collect();
+    if earlier.len() < min_history_days.max(1) {
+        return None;
+    }
+
+    let maxes: Vec<f32> = earlier.iter().filter_map(|day| day.temp_max).collect();
+    let mins: Vec<f32> = earlier.iter().filter_map(|day| day.temp_min).collect();
+    let heat = forecast
+        .temp_max
+        .filter(|_| !maxes.is_empty())
+        .map(|max| rank(max, &maxes, |value, other| value > other));
+    let cold = forecast
+        .temp_min
+        .filter(|_| !mins.is_empty())
+        .map(|min| rank(min, &mins, |value, other| value < other));
+
+    match (heat, cold) {
+        (Some((true, _)), _) => Some(TemperatureRecord::RecordHeat),
+        (Some((_, true)), _) => Some(TemperatureRecord::TopDecileHeat),
+        (_, Some((true, _))) => Some(TemperatureRecord::RecordCold),
+        (_, Some((_, true))) => Some(TemperatureRecord::TopDecileCold),
+        _ => None,
+    }
+}
+
+/// Builds the badge: the label in a rounded box, in the temperature colour of `colours` for heat
+/// and the rain colour for cold
+pub fn temperature_record_panel(
+    settings: &TemperatureRecords,
+    record: TemperatureRecord,
+    colours: &Colours,
+) -> Panel {
+    let fill = if record.is_heat() {
+        &colours.actual_temp_colour
+    } else {
+        &colours.rain_colour
+    };
+    let background = &colours.background_colour;
+    let font_size = settings.height * 0.55;
+    Panel::svg(
+        settings.x,
+        settings.y,
+        settings.width,
+        settings.height,
+        format!(
+            r#"<rect width="{width}" height="{height}" rx="{radius:.1}" fill="{fill}"/><text x="{:.1}" y="{:.1}" text-anchor="middle" dominant-baseline="middle" font-size="{font_size:.1}" font-weight="bold" fill="{background}">{record}</text>"#,
+            settings.width / 2.0,
+            settings.height / 2.0,
+            width = settings.width,
+            height = settings.height,
+            radius = settings.height / 2.0,
+        ),
+    )
+}
+
+/// Records today's forecast in the history of the location and builds the badge when it is
+/// remarkable, `None` when the badge is disabled. Each call records a sample, so it belongs on the
+/// render path only, not where the forecast is just served.
+pub fn temperature_records_badge(
+    settings: &TemperatureRecords,
+    daily: &[DailyForecast],
+    today: NaiveDate,
+    colours: &Colours,
+) -> Option<Panel> {
+    if !settings.enabled {
+        return None;
+    }
+
+    let forecast = today_history(daily, today)?;
+    let path = history_path();
+    let mut history = History::load(&path);
+    history.record(today, forecast);
+    if let Err(e) = history.save(&path) {
+        logger::warning(format!("Failed to save the forecast history: {e}"));
+    }
+
+    temperature_record(&history, today, &forecast, settings.min_history_days)
+        .map(|record| temperature_record_panel(settings, record, colours))
+}
@@ -0,0 +1,113 @@
+//! Formatters available to the SVG template
+//!
+//! A template applies them to a context value with a pipe, e.g. `{sunrise_time | localize_time}`
+//! or `{day2_name | upper}`, so presentation tweaks in a custom template need neither a new
+//! context field nor a recompile. Formatters take no arguments, `round1` is `round` to one decimal.
+//!
+//! | Formatter       | Output                                                                     |
+//! |-----------------|----------------------------------------------------------------------------|
+//! | `round`         | A number rounded to a whole number, in the configured locale               |
+//! | `round1`        | A number rounded to one decimal, in the configured locale                  |
+//! | `upper`         | The value in upper case                                                    |
+//! | `lower`         | The value in lower case                                                    |
+//! | `localize_time` | A time like "06:42" or an RFC 3339 timestamp in the configured time format |
+//! | `icon`          | Path of the named icon, e.g. `clear-day`, honouring icon overrides         |
+//!
+//! Values a formatter can't read, such as "NA" given to `round`, are written unchanged.
+
+use chrono::{DateTime, Local, NaiveTime};
+use serde_json::Value;
+use std::fmt::{Display, Write};
+use tinytemplate::TinyTemplate;
+
+use crate::dashboard::number_format::number_format;
+use crate::weather::icons::Icon;
+use crate::CONFIG;
+
+/// Registers all template formatters with `tt`
+pub fn register_filters(tt: &mut TinyTemplate) {
+    tt.add_formatter("round", |value, output| {
+        output.push_str(&round(value, 0));
+        Ok(())
+    });
+    tt.add_formatter("round1", |value, output| {
+        output.push_str(&round(value, 1));
+        Ok(())
+    });
+    tt.add_formatter("upper", |value, output| {
+        output.push_str(&text(value).to_uppercase());
+        Ok(())
+    });
+    tt.add_formatter("lower", |value, output| {
+        output.push_str(&text(value).to_lowercase());
+        Ok(())
+    });
+    tt.add_formatter("localize_time", |value, output| {
+        output.push_str(&localize_time(
+            &text(value),
+            &CONFIG.render_options.time_format,
+        ));
+        Ok(())
+    });
+    tt.add_formatter("icon", |value, output| {
+        output.push_str(&NamedIcon(text(value)).get_icon_path());
+        Ok(())
+    });
+}
+
+/// A string value as it is, anything else as JSON
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `value` with `decimals` digits after the decimal separator, unchanged if it isn't a number
+fn round(value: &Value, decimals: usize) -> String {
+    let format = number_format();
+    let number = match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => format.parse(text),
+        _ => None,
+    };
+    match number {
+        Some(number) => format.fixed(number as f32, decimals),
+        None => text(value),
+    }
+}
+
+/// `time` written with `time_format`, unchanged if it's neither a time of day nor a timestamp or
+/// the format doesn't apply to it, e.g. a date specifier given a time of day
+pub fn localize_time(time: &str, time_format: &str) -> String {
+    let time = time.trim();
+    let formatted = if let Ok(timestamp) = DateTime::parse_from_rfc3339(time) {
+        write_or_none(timestamp.with_timezone(&Local).format(time_format))
+    } else {
+        NaiveTime::parse_from_str(time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+            .ok()
+            .and_then(|time| write_or_none(time.format(time_format)))
+    };
+    formatted.unwrap_or_else(|| time.to_string())
+}
+
+/// `value` written out, `None` where `to_string` would panic on a formatting error
+fn write_or_none(value: impl Display) -> Option<String> {
+    let mut output = String::new();
+    write!(output, "{value}").ok().map(|_| output)
+}
+
+/// Icon named by the template, with or without the `.svg` extension
+struct NamedIcon(String);
+
+impl Icon for NamedIcon {
+    fn get_icon_name(&self) -> String {
+        let name = self.0.trim();
+        if name.ends_with(".svg") {
+            name.to_string()
+        } else {
+            format!("{name}.svg")
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Text-only dashboard for when the SVG template is missing or invalid
+//!
+//! Laid out straight from the context with plain `<text>` elements in the configured colours, no
+//! template, icons or graph, so the display still shows a legible forecast instead of the
+//! "Dashboard unavailable" frame.
+
+use crate::dashboard::chart::ElementVisibility;
+use crate::dashboard::context::Context;
+use crate::dashboard::panel::scaled_frame;
+use crate::utils::escape_xml;
+
+/// Longest line of the hourly summary, the diagnostic and the reason, longer ones run off the display
+const LINE_MAX_CHARS: usize = 70;
+
+/// Width of a daily column, six of them span the display
+const DAY_COLUMN_WIDTH: usize = 120;
+
+/// Renders the current conditions, the next six days and the active diagnostic as an SVG of text
+/// only, laid out for 800x480 and scaled to fit a `size` frame. `reason` says why the template
+/// wasn't used and goes in the footer.
+pub fn render_text_dashboard(context: &Context, reason: &str, size: (f32, f32)) -> String {
+    let line = |text: &str| escape_xml(&text.chars().take(LINE_MAX_CHARS).collect::<String>());
+    let background = escape_xml(&context.background_colour);
+    let text = escape_xml(&context.text_colour);
+
+    let heading = line(&format!(
+        "{} {}",
+        context.current_day_date, context.current_day_time
+    ));
+    let temperature = line(&context.current_hour_actual_temp_with_unit);
+    let feels_like = line(&format!(
+        "Feels like {}",
+        context.current_hour_feels_like_with_unit
+    ));
+    let conditions = line(&format!(
+        "Wind {}   Humidity {}%   Rain today {}",
+        context.current_hour_wind_speed_with_unit,
+        context.current_hour_relative_humidity,
+        context.total_rain_today_with_unit
+    ));
+    let sun = line(&format!(
+        "Sunrise {}   Sunset {}",
+        context.sunrise_time, context.sunset_time
+    ));
+    let summary = line(&context.hourly_summary);
+
+    let days = [
+        (
+            &context.day2_name,
+            &context.day2_maxtemp,
+            &context.day2_mintemp,
+            &context.day2_temp_colour,
+        ),
+        (
+            &context.day3_name,
+            &context.day3_maxtemp,
+            &context.day3_mintemp,
+            &context.day3_temp_colour,
+        ),
+        (
+            &context.day4_name,
+            &context.day4_maxtemp,
+            &context.day4_mintemp,
+            &context.day4_temp_colour,
+        ),
+        (
+            &context.day5_name,
+            &context.day5_maxtemp,
+            &context.day5_mintemp,
+            &context.day5_temp_colour,
+        ),
+        (
+            &context.day6_name,
+            &context.day6_maxtemp,
+            &context.day6_mintemp,
+            &context.day6_temp_colour,
+        ),
+        (
+            &context.day7_name,
+            &context.day7_maxtemp,
+            &context.day7_mintemp,
+            &context.day7_temp_colour,
+        ),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(column, (name, max, min, max_colour))| {
+        let x = 40 + column * DAY_COLUMN_WIDTH;
+        format!(
+            r#"    <text x="{x}" y="300" font-size="22" font-weight="bold" fill="{text}">{}</text>
+    <text x="{x}" y="335" font-size="22" fill="{}">{}°</text>
+    <text x="{x}" y="365" font-size="18" fill="{text}">{}°</text>
+"#,
+            line(name),
+            escape_xml(max_colour),
+            line(max),
+            line(min)
+        )
+    })
+    .collect::<String>();
+
+    let diagnostic = if context.diagnostic_visibility == ElementVisibility::Visible.to_string() {
+        format!(
+            r#"    <text x="40" y="415" font-size="18" fill="red">{}</text>
+"#,
+            line(&context.diagnostic_message)
+        )
+    } else {
+        String::new()
+    };
+    let footer = line(&format!("Text-only dashboard: {reason}"));
+
+    let content = format!(
+        r#"    <text x="40" y="50" font-size="28" font-weight="bold" fill="{text}">{heading}</text>
+    <text x="40" y="130" font-size="64" font-weight="bold" fill="{text}">{temperature}</text>
+    <text x="300" y="100" font-size="22" fill="{text}">{feels_like}</text>
+    <text x="300" y="130" font-size="22" fill="{text}">{sun}</text>
+    <text x="40" y="185" font-size="20" fill="{text}">{conditions}</text>
+    <text x="40" y="220" font-size="20" fill="{text}">{summary}</text>
+{days}{diagnostic}    <text x="40" y="460" font-size="14" fill="{text}">{footer}</text>
+"#
+    );
+    scaled_frame(size, &background, &content)
+}
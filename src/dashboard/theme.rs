@@ -0,0 +1,66 @@
+//! Colours changing with the weather
+//!
+//! `[[theme_rules]]` are checked in order against the current hour's condition and today's
+//! forecast high, the first matching rule replaces some of the configured colours, e.g. blue
+//! accents while it rains or red ones on a very hot day.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::configs::settings::{Colours, ThemeRule};
+use crate::domain::conditions::Condition;
+use crate::domain::models::{DailyForecast, HourlyForecast};
+
+/// Weather the theme rules are matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThemeWeather {
+    /// Condition of the current hour
+    pub condition: Option<Condition>,
+    /// Today's forecast high in the configured temperature unit
+    pub temp_max: Option<f32>,
+}
+
+impl ThemeWeather {
+    /// Weather at `now`, the current hour is the latest hourly forecast that has started
+    pub fn at(
+        daily: &[DailyForecast],
+        hourly: &[HourlyForecast],
+        now: DateTime<Utc>,
+        today: NaiveDate,
+    ) -> Self {
+        Self {
+            condition: hourly
+                .iter()
+                .filter(|hour| hour.time <= now)
+                .max_by_key(|hour| hour.time)
+                .map(HourlyForecast::condition),
+            temp_max: daily
+                .iter()
+                .find(|day| day.date == Some(today))
+                .and_then(|day| day.temp_max)
+                .map(|temp| temp.value),
+        }
+    }
+}
+
+/// Whether `rule` applies to `weather`, a rule with a temperature range needs a forecast high
+pub fn rule_matches(rule: &ThemeRule, weather: &ThemeWeather) -> bool {
+    let condition_matches = rule.conditions.is_empty()
+        || weather
+            .condition
+            .is_some_and(|condition| rule.conditions.contains(&condition));
+    let in_range = |check: Option<f32>, within: fn(f32, f32) -> bool| {
+        check.is_none_or(|limit| weather.temp_max.is_some_and(|temp| within(temp, limit)))
+    };
+
+    condition_matches
+        && in_range(rule.min_temperature, |temp, min| temp >= min)
+        && in_range(rule.max_temperature, |temp, max| temp < max)
+}
+
+/// `colours` with the overrides of the first rule matching `weather`
+pub fn themed_colours(colours: &Colours, rules: &[ThemeRule], weather: &ThemeWeather) -> Colours {
+    match rules.iter().find(|rule| rule_matches(rule, weather)) {
+        Some(rule) => colours.with_overrides(&rule.colours),
+        None => colours.clone(),
+    }
+}
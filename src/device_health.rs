@@ -0,0 +1,269 @@
+//! Boot reports of the displays
+//!
+//! After each refresh a display can POST what the boot cost it to `/api/devices/<id>/health`:
+//! the reset reason, how long joining Wi-Fi and the download took, how many times it polled the
+//! busy pin waiting for the panel, and the free heap. The last reports of each display are kept in
+//! `device_health.json` in the cache directory, so a display that keeps resetting or running low
+//! on memory shows up on `/api/devices.json`, and on the `/status` page when it's enabled. Only
+//! the displays seen last are kept, so made-up ids can't grow the file without bound.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use utoipa::ToSchema;
+
+use crate::logger;
+use crate::utils::escape_xml;
+use crate::weather_dashboard::staged_path;
+
+pub const DEVICE_HEALTH_FILE_NAME: &str = "device_health.json";
+
+/// Reports kept per display, a day of hourly refreshes
+pub const MAX_REPORTS_PER_DEVICE: usize = 24;
+
+/// Displays kept, the one seen longest ago is dropped for a new one
+pub const MAX_DEVICES: usize = 64;
+
+/// Longest display id accepted, ids are also keys of the health file
+pub const MAX_DEVICE_ID_LEN: usize = 32;
+
+/// Longest reset reason kept, longer ones are cut
+const MAX_RESET_REASON_LEN: usize = 64;
+
+/// Serializes updates of the health file, displays may report concurrently
+static HEALTH_LOCK: Mutex<()> = Mutex::new(());
+
+/// What one boot of a display cost it, every field is optional so firmware can send what it has
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BootReport {
+    /// Why the display last reset, as the firmware names it, e.g. `PWRON_RESET` or `WDT_RESET`
+    pub reset_reason: Option<String>,
+    /// Time to join the Wi-Fi network in milliseconds
+    pub wifi_join_ms: Option<u32>,
+    /// Time to download the dashboard in milliseconds
+    pub download_ms: Option<u32>,
+    /// Busy pin polls while the panel refreshed
+    pub busy_wait_iterations: Option<u64>,
+    /// Free heap after the refresh in bytes
+    pub free_heap_bytes: Option<u32>,
+}
+
+impl BootReport {
+    /// Whether the display was reset by its watchdog, i.e. it hung
+    pub fn is_watchdog_reset(&self) -> bool {
+        self.reset_reason.as_ref().is_some_and(|reason| {
+            let reason = reason.to_ascii_lowercase();
+            reason.contains("wdt") || reason.contains("watchdog")
+        })
+    }
+}
+
+/// A report with the time the server received it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceivedReport {
+    pub received_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub report: BootReport,
+}
+
+/// The kept reports of every display, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FleetHealth {
+    pub devices: BTreeMap<String, Vec<ReceivedReport>>,
+}
+
+/// Health of one display over its kept reports
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct DeviceSummary {
+    pub device: String,
+    pub last_seen: DateTime<Utc>,
+    pub reports: usize,
+    /// Reports after a watchdog reset
+    pub watchdog_resets: usize,
+    pub last_report: BootReport,
+    pub mean_wifi_join_ms: Option<u32>,
+    pub mean_download_ms: Option<u32>,
+    /// Lowest free heap reported, a falling value points to a leak
+    pub min_free_heap_bytes: Option<u32>,
+}
+
+/// Whether `device` can name a display: 1 to 32 ASCII letters, digits, `-` or `_`
+pub fn is_valid_device_id(device: &str) -> bool {
+    !device.is_empty()
+        && device.len() <= MAX_DEVICE_ID_LEN
+        && device
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn mean(values: impl Iterator<Item = u32>) -> Option<u32> {
+    let (sum, count) = values.fold((0u64, 0u64), |(sum, count), value| {
+        (sum + u64::from(value), count + 1)
+    });
+    (count > 0).then(|| (sum / count) as u32)
+}
+
+impl FleetHealth {
+    /// Reads the health file at `path`, empty when there is none yet or it cannot be read
+    pub fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            logger::warning(format!(
+                "Ignoring unreadable device health file {}: {e}",
+                path.display()
+            ));
+            Self::default()
+        })
+    }
+
+    /// Writes the health file to `path`, creating its directory. It's written to a staged file
+    /// first, so a crash never leaves a half-written one.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let staged = staged_path(path);
+        fs::write(
+            &staged,
+            serde_json::to_string(self).expect("device health always serializes"),
+        )?;
+        fs::rename(staged, path)
+    }
+
+    /// Adds `report` of `device`, dropping its oldest reports past [`MAX_REPORTS_PER_DEVICE`] and
+    /// the displays seen longest ago past [`MAX_DEVICES`]
+    pub fn record(&mut self, device: &str, mut report: BootReport, received_at: DateTime<Utc>) {
+        if let Some(reason) = &mut report.reset_reason {
+            *reason = reason.chars().take(MAX_RESET_REASON_LEN).collect();
+        }
+        let reports = self.devices.entry(device.to_string()).or_default();
+        reports.push(ReceivedReport {
+            received_at,
+            report,
+        });
+        let excess = reports.len().saturating_sub(MAX_REPORTS_PER_DEVICE);
+        reports.drain(..excess);
+
+        while self.devices.len() > MAX_DEVICES {
+            let oldest = self
+                .devices
+                .iter()
+                .filter(|(id, _)| id.as_str() != device)
+                .min_by_key(|(_, reports)| reports.last().map(|last| last.received_at))
+                .map(|(id, _)| id.clone());
+            let Some(oldest) = oldest else { break };
+            self.devices.remove(&oldest);
+        }
+    }
+
+    /// A summary of each display that reported, by id
+    pub fn summaries(&self) -> Vec<DeviceSummary> {
+        self.devices
+            .iter()
+            .filter_map(|(device, reports)| {
+                let last = reports.last()?;
+                let boots = || reports.iter().map(|received| &received.report);
+                Some(DeviceSummary {
+                    device: device.clone(),
+                    last_seen: last.received_at,
+                    reports: reports.len(),
+                    watchdog_resets: boots().filter(|boot| boot.is_watchdog_reset()).count(),
+                    last_report: last.report.clone(),
+                    mean_wifi_join_ms: mean(boots().filter_map(|boot| boot.wifi_join_ms)),
+                    mean_download_ms: mean(boots().filter_map(|boot| boot.download_ms)),
+                    min_free_heap_bytes: boots().filter_map(|boot| boot.free_heap_bytes).min(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Records `report` of `device` in the health file in `cache_dir`
+pub fn record_report(
+    cache_dir: &Path,
+    device: &str,
+    report: BootReport,
+    received_at: DateTime<Utc>,
+) -> std::io::Result<()> {
+    let _guard = HEALTH_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let path = cache_dir.join(DEVICE_HEALTH_FILE_NAME);
+    let mut health = FleetHealth::load(&path);
+    health.record(device, report, received_at);
+    health.save(&path)
+}
+
+/// The displays in the health file in `cache_dir`
+pub fn device_summaries(cache_dir: &Path) -> Vec<DeviceSummary> {
+    FleetHealth::load(&cache_dir.join(DEVICE_HEALTH_FILE_NAME)).summaries()
+}
+
+fn or_dash(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "–".to_string(), |value| value.to_string())
+}
+
+/// How long ago `time` was, e.g. "5 min ago"
+fn age(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - time).num_minutes().max(0);
+    match minutes {
+        0..60 => format!("{minutes} min ago"),
+        60..2880 => format!("{} h ago", minutes / 60),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
+
+/// The `/status` page, a table of the displays with the latest report and their averages
+pub fn status_page_html(summaries: &[DeviceSummary], now: DateTime<Utc>) -> String {
+    let rows: String = summaries
+        .iter()
+        .map(|summary| {
+            let last = &summary.last_report;
+            format!(
+                "\n        <tr{}><td>{}</td><td>{}</td><td>{}</td><td>{} / {}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                if summary.watchdog_resets > 0 { r#" class="warn""# } else { "" },
+                escape_xml(&summary.device),
+                age(summary.last_seen, now),
+                escape_xml(&or_dash(last.reset_reason.as_ref())),
+                summary.watchdog_resets,
+                summary.reports,
+                or_dash(summary.mean_wifi_join_ms.map(|ms| format!("{ms} ms"))),
+                or_dash(summary.mean_download_ms.map(|ms| format!("{ms} ms"))),
+                or_dash(last.busy_wait_iterations),
+                or_dash(last.free_heap_bytes.map(|bytes| format!("{} KiB", bytes / 1024))),
+                or_dash(summary.min_free_heap_bytes.map(|bytes| format!("{} KiB", bytes / 1024))),
+            )
+        })
+        .collect();
+    let body = if summaries.is_empty() {
+        "<p>No display has reported yet.</p>".to_string()
+    } else {
+        format!(
+            r#"<table>
+        <tr><th>Display</th><th>Last seen</th><th>Reset reason</th><th>Watchdog resets</th><th>Wi-Fi join</th><th>Download</th><th>Busy-wait</th><th>Free heap</th><th>Lowest heap</th></tr>{rows}
+    </table>"#
+        )
+    };
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <title>Display status</title>
+    <style>
+        body {{ font-family: sans-serif; }}
+        td, th {{ padding: 4px 12px; text-align: left; }}
+        .warn {{ background: #fdd; }}
+    </style>
+</head>
+<body>
+    <h1>Display status</h1>
+    {body}
+</body>
+</html>
+"#
+    )
+}
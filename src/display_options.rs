@@ -0,0 +1,234 @@
+//! Per-display image options for the PNG and raw outputs
+//!
+//! Lets displays that share one web server ask for the image the way their panel needs it:
+//! rotated, restricted to the colours the panel can show, and optionally dithered. The driver
+//! picks the panel family, which decides the grey levels and the layout of the raw output.
+
+use image::{imageops, RgbImage};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::utils::{raw_7color_size, raw_it8951_size, rgb_to_raw_7color, rgb_to_raw_it8951};
+use crate::CONFIG;
+
+/// Clockwise rotation applied after rendering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "u16")]
+pub enum Rotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    Clockwise270,
+}
+
+impl TryFrom<u16> for Rotation {
+    type Error = String;
+
+    fn try_from(degrees: u16) -> Result<Self, Self::Error> {
+        match degrees {
+            0 => Ok(Rotation::None),
+            90 => Ok(Rotation::Clockwise90),
+            180 => Ok(Rotation::Clockwise180),
+            270 => Ok(Rotation::Clockwise270),
+            _ => Err(format!(
+                "unsupported rotation {degrees}, expected 0, 90, 180 or 270"
+            )),
+        }
+    }
+}
+
+/// Colours a panel can show, all are subsets of the 7-colour palette so raw output stays compatible
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+pub enum Palette {
+    /// Black, white, green, blue, red, yellow and orange
+    #[default]
+    #[serde(rename = "7color")]
+    SevenColour,
+    /// Black, white and red
+    #[serde(rename = "bwr")]
+    BlackWhiteRed,
+    /// Black, white and yellow
+    #[serde(rename = "bwy")]
+    BlackWhiteYellow,
+    /// Black and white
+    #[serde(rename = "bw")]
+    BlackWhite,
+}
+
+impl Palette {
+    /// Indices into the 7-colour palette
+    fn indices(self) -> &'static [usize] {
+        match self {
+            Palette::SevenColour => &[0, 1, 2, 3, 4, 5, 6],
+            Palette::BlackWhiteRed => &[0, 1, 4],
+            Palette::BlackWhiteYellow => &[0, 1, 5],
+            Palette::BlackWhite => &[0, 1],
+        }
+    }
+
+    /// The colours as calibrated in the `[palette]` config
+    pub fn colours(self) -> Vec<[u8; 3]> {
+        let inks = CONFIG.palette.colours();
+        self.indices().iter().map(|&index| inks[index]).collect()
+    }
+}
+
+/// Grey levels of IT8951 panels, from black to white
+const GRAY_LEVELS: usize = 16;
+
+/// Panel family the images are prepared for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+pub enum DisplayDriver {
+    /// ACeP colour panels such as the Inky Impression, raw output holds 7-colour palette indices
+    #[default]
+    Acep,
+    /// IT8951 based greyscale panels such as the 9.7" and 10.3" e-paper HATs, raw output holds
+    /// 16 grey levels
+    It8951,
+}
+
+impl DisplayDriver {
+    /// Bytes per row of raw output for a `width` pixels wide image
+    pub fn raw_row_size(self, width: u32) -> usize {
+        match self {
+            DisplayDriver::Acep => width.div_ceil(2) as usize,
+            DisplayDriver::It8951 => width.div_ceil(4) as usize * 2,
+        }
+    }
+
+    /// Size in bytes of the raw output of a `width` x `height` image
+    pub fn raw_size(self, width: u32, height: u32) -> u64 {
+        match self {
+            DisplayDriver::Acep => raw_7color_size(width, height),
+            DisplayDriver::It8951 => raw_it8951_size(width, height),
+        }
+    }
+
+    /// Packs an image into the raw output of the panel
+    pub fn to_raw(self, image: &RgbImage) -> Vec<u8> {
+        match self {
+            DisplayDriver::Acep => rgb_to_raw_7color(image),
+            DisplayDriver::It8951 => rgb_to_raw_it8951(image),
+        }
+    }
+}
+
+impl fmt::Display for DisplayDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayDriver::Acep => write!(f, "acep"),
+            DisplayDriver::It8951 => write!(f, "it8951"),
+        }
+    }
+}
+
+/// The grey levels of IT8951 panels as RGB colours
+pub fn gray_levels() -> Vec<[u8; 3]> {
+    (0..GRAY_LEVELS)
+        .map(|level| [(level * 255 / (GRAY_LEVELS - 1)) as u8; 3])
+        .collect()
+}
+
+/// How pixels are mapped onto the palette
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+pub enum Dither {
+    /// Closest palette colour, keeps lines and text crisp
+    #[default]
+    None,
+    /// Error diffusion, smoother gradients at the cost of grain
+    FloydSteinberg,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DisplayOptions {
+    pub rotation: Rotation,
+    /// `None` keeps the rendered colours, unless dithering asks for the default palette
+    pub palette: Option<Palette>,
+    pub dither: Dither,
+    /// IT8951 panels get the image in their grey levels, the palette doesn't apply to them
+    pub driver: DisplayDriver,
+}
+
+impl DisplayOptions {
+    /// True when [`DisplayOptions::apply`] would return the image unchanged
+    pub fn is_identity(&self) -> bool {
+        *self == DisplayOptions::default()
+    }
+
+    pub fn apply(&self, image: RgbImage) -> RgbImage {
+        let image = match self.rotation {
+            Rotation::None => image,
+            Rotation::Clockwise90 => imageops::rotate90(&image),
+            Rotation::Clockwise180 => imageops::rotate180(&image),
+            Rotation::Clockwise270 => imageops::rotate270(&image),
+        };
+
+        match (self.driver, self.palette, self.dither) {
+            (DisplayDriver::It8951, _, dither) => quantize(image, &gray_levels(), dither),
+            (DisplayDriver::Acep, None, Dither::None) => image,
+            (DisplayDriver::Acep, palette, dither) => {
+                quantize(image, &palette.unwrap_or_default().colours(), dither)
+            }
+        }
+    }
+}
+
+fn closest_colour(palette: &[[u8; 3]], colour: [f32; 3]) -> [u8; 3] {
+    let distance = |candidate: &[u8; 3]| -> f32 {
+        (0..3)
+            .map(|channel| (colour[channel] - candidate[channel] as f32).powi(2))
+            .sum()
+    };
+
+    *palette
+        .iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .expect("palette is never empty")
+}
+
+/// Maps every pixel onto `palette`, diffusing the error to the neighbours for Floyd-Steinberg
+fn quantize(mut image: RgbImage, palette: &[[u8; 3]], dither: Dither) -> RgbImage {
+    let (width, height) = image.dimensions();
+
+    if dither == Dither::None {
+        for pixel in image.pixels_mut() {
+            pixel.0 = closest_colour(palette, pixel.0.map(f32::from));
+        }
+        return image;
+    }
+
+    let mut buffer: Vec<[f32; 3]> = image.pixels().map(|pixel| pixel.0.map(f32::from)).collect();
+    let index = |x: u32, y: u32| (y * width + x) as usize;
+
+    for y in 0..height {
+        for x in 0..width {
+            let old = buffer[index(x, y)];
+            let new = closest_colour(palette, old);
+            image.put_pixel(x, y, image::Rgb(new));
+
+            let error = [0, 1, 2].map(|channel| old[channel] - new[channel] as f32);
+            let mut diffuse = |dx: i64, dy: u32, weight: f32| {
+                let nx = x as i64 + dx;
+                let ny = y + dy;
+                if nx >= 0 && (nx as u32) < width && ny < height {
+                    let neighbour = &mut buffer[index(nx as u32, ny)];
+                    for channel in 0..3 {
+                        neighbour[channel] += error[channel] * weight;
+                    }
+                }
+            };
+            diffuse(1, 0, 7.0 / 16.0);
+            diffuse(-1, 1, 3.0 / 16.0);
+            diffuse(0, 1, 5.0 / 16.0);
+            diffuse(1, 1, 1.0 / 16.0);
+        }
+    }
+
+    image
+}
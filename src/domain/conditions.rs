@@ -0,0 +1,160 @@
+//! Provider condition codes mapped onto one internal [`Condition`]
+//!
+//! Each provider reports the weather with its own codes: BOM uses `icon_descriptor` strings,
+//! Open-Meteo uses WMO weather codes. The mapping lives in one table per provider, so the
+//! tables can be audited side by side and a new provider only needs a new table. The mapped
+//! condition is kept on each hour as its `reported_condition`.
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+use crate::domain::models::Obscuration;
+
+/// Weather condition, independent of any provider's codes
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Condition {
+    Clear,
+    MostlyClear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Haze,
+    Smoke,
+    Dust,
+    Frost,
+    Wind,
+    Drizzle,
+    LightRain,
+    Rain,
+    HeavyRain,
+    /// Freezing rain or freezing drizzle
+    FreezingRain,
+    LightShowers,
+    Showers,
+    HeavyShowers,
+    Snow,
+    SnowShowers,
+    Storm,
+    StormWithHail,
+    Cyclone,
+}
+
+impl Condition {
+    /// Visibility-reducing part of the condition, if any
+    pub fn obscuration(self) -> Option<Obscuration> {
+        match self {
+            Condition::Haze => Some(Obscuration::Haze),
+            Condition::Smoke => Some(Obscuration::Smoke),
+            Condition::Dust => Some(Obscuration::Dust),
+            _ => None,
+        }
+    }
+
+    /// Text for templates, e.g. "Partly cloudy"
+    pub fn description(self) -> &'static str {
+        match self {
+            Condition::Clear => "Clear",
+            Condition::MostlyClear => "Mostly clear",
+            Condition::PartlyCloudy => "Partly cloudy",
+            Condition::Cloudy => "Cloudy",
+            Condition::Fog => "Fog",
+            Condition::Haze => "Haze",
+            Condition::Smoke => "Smoke",
+            Condition::Dust => "Dust",
+            Condition::Frost => "Frost",
+            Condition::Wind => "Windy",
+            Condition::Drizzle => "Drizzle",
+            Condition::LightRain => "Light rain",
+            Condition::Rain => "Rain",
+            Condition::HeavyRain => "Heavy rain",
+            Condition::FreezingRain => "Freezing rain",
+            Condition::LightShowers => "Light showers",
+            Condition::Showers => "Showers",
+            Condition::HeavyShowers => "Heavy showers",
+            Condition::Snow => "Snow",
+            Condition::SnowShowers => "Snow showers",
+            Condition::Storm => "Thunderstorm",
+            Condition::StormWithHail => "Thunderstorm with hail",
+            Condition::Cyclone => "Cyclone",
+        }
+    }
+
+    /// Maps a BOM `icon_descriptor`, `None` for descriptors missing from [`BOM_ICON_DESCRIPTORS`]
+    pub fn from_bom_descriptor(descriptor: &str) -> Option<Self> {
+        lookup(BOM_ICON_DESCRIPTORS, &descriptor)
+    }
+
+    /// Maps a WMO weather code, `None` for codes missing from [`WMO_WEATHER_CODES`]
+    pub fn from_wmo_code(code: u8) -> Option<Self> {
+        lookup(WMO_WEATHER_CODES, &code)
+    }
+}
+
+fn lookup<K: PartialEq>(table: &[(K, Condition)], key: &K) -> Option<Condition> {
+    table
+        .iter()
+        .find(|(candidate, _)| candidate == key)
+        .map(|&(_, condition)| condition)
+}
+
+/// BOM `icon_descriptor` values, including the spelling variants seen in responses
+pub const BOM_ICON_DESCRIPTORS: &[(&str, Condition)] = &[
+    ("sunny", Condition::Clear),
+    ("clear", Condition::Clear),
+    ("mostly_sunny", Condition::MostlyClear),
+    ("partly_cloudy", Condition::PartlyCloudy),
+    ("cloudy", Condition::Cloudy),
+    ("fog", Condition::Fog),
+    ("haze", Condition::Haze),
+    ("hazy", Condition::Haze),
+    ("smoke", Condition::Smoke),
+    ("smoky", Condition::Smoke),
+    ("dust", Condition::Dust),
+    ("dusty", Condition::Dust),
+    ("frost", Condition::Frost),
+    ("wind", Condition::Wind),
+    ("windy", Condition::Wind),
+    ("light_rain", Condition::LightRain),
+    ("rain", Condition::Rain),
+    ("light_shower", Condition::LightShowers),
+    ("shower", Condition::Showers),
+    ("heavy_shower", Condition::HeavyShowers),
+    ("snow", Condition::Snow),
+    ("storm", Condition::Storm),
+    ("cyclone", Condition::Cyclone),
+];
+
+/// WMO weather codes as documented by Open-Meteo (`weather_code`)
+pub const WMO_WEATHER_CODES: &[(u8, Condition)] = &[
+    (0, Condition::Clear),
+    (1, Condition::MostlyClear),
+    (2, Condition::PartlyCloudy),
+    (3, Condition::Cloudy),
+    (45, Condition::Fog),
+    (48, Condition::Fog), // depositing rime fog
+    (51, Condition::Drizzle),
+    (53, Condition::Drizzle),
+    (55, Condition::Drizzle),
+    (56, Condition::FreezingRain), // freezing drizzle
+    (57, Condition::FreezingRain),
+    (61, Condition::LightRain),
+    (63, Condition::Rain),
+    (65, Condition::HeavyRain),
+    (66, Condition::FreezingRain),
+    (67, Condition::FreezingRain),
+    (71, Condition::Snow),
+    (73, Condition::Snow),
+    (75, Condition::Snow),
+    (77, Condition::Snow), // snow grains
+    (80, Condition::LightShowers),
+    (81, Condition::Showers),
+    (82, Condition::HeavyShowers),
+    (85, Condition::SnowShowers),
+    (86, Condition::SnowShowers),
+    (95, Condition::Storm),
+    (96, Condition::StormWithHail),
+    (99, Condition::StormWithHail),
+];
@@ -0,0 +1,78 @@
+//! Day or night of each forecast hour
+//!
+//! Providers flag night hours coarsely, Open-Meteo only says whether it's night at the time of
+//! the request. Every hour is classified again with the sunrise and sunset of its own day from
+//! the daily forecast, so a clear 9pm hour gets the night icon even when rendered at noon. An
+//! hour counts as night when its middle is before sunrise or after sunset. Hours of days without
+//! sun times keep the provider's flag.
+//!
+//! Some Open-Meteo configurations send no sunrise or sunset, which left the dashboard showing
+//! "NA". Days missing them get times computed from the location instead, see
+//! [`sun_times`](crate::weather::solar::sun_times), so only polar days and nights are left
+//! without.
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use super::models::{Astronomical, DailyForecast, HourlyForecast};
+use crate::weather::solar::sun_times;
+
+/// Whether the hour starting at `time` is at night, `None` without sun times
+pub fn is_night_hour(time: DateTime<Utc>, astronomical: &Astronomical) -> Option<bool> {
+    if astronomical.sunrise_time.is_none() && astronomical.sunset_time.is_none() {
+        return None;
+    }
+    let middle = (time + Duration::minutes(30))
+        .with_timezone(&Local)
+        .naive_local();
+    let before_sunrise = astronomical
+        .sunrise_time
+        .is_some_and(|sunrise| middle < sunrise);
+    let after_sunset = astronomical
+        .sunset_time
+        .is_some_and(|sunset| middle >= sunset);
+    Some(before_sunrise || after_sunset)
+}
+
+/// Sets `is_night` of every hour from the sun times of its day in `daily`
+pub fn apply_sun_times(hourly: &mut [HourlyForecast], daily: &[DailyForecast]) {
+    for hour in hourly {
+        let date = hour.time.with_timezone(&Local).date_naive();
+        let Some(astronomical) = daily
+            .iter()
+            .find(|day| day.date == Some(date))
+            .and_then(|day| day.astronomical.as_ref())
+        else {
+            continue;
+        };
+        if let Some(is_night) = is_night_hour(hour.time, astronomical) {
+            hour.is_night = is_night;
+        }
+    }
+}
+
+/// Fills in the sunrise and sunset the provider left out of `daily` with the times computed for
+/// the location, as local wall-clock times like the provider's. Days in the polar day or night
+/// are left without them.
+pub fn fill_missing_sun_times(daily: &mut [DailyForecast], latitude: f64, longitude: f64) {
+    for day in daily {
+        let astronomical = day.astronomical.get_or_insert_with(Astronomical::default);
+        if astronomical.sunrise_time.is_some() && astronomical.sunset_time.is_some() {
+            continue;
+        }
+        let Some((sunrise, sunset)) = day
+            .date
+            .and_then(|date| sun_times(date, latitude, longitude))
+        else {
+            if astronomical.sunrise_time.is_none() && astronomical.sunset_time.is_none() {
+                day.astronomical = None;
+            }
+            continue;
+        };
+        astronomical
+            .sunrise_time
+            .get_or_insert(sunrise.with_timezone(&Local).naive_local());
+        astronomical
+            .sunset_time
+            .get_or_insert(sunset.with_timezone(&Local).naive_local());
+    }
+}
@@ -1,8 +1,9 @@
-use super::models::{DailyForecast, HourlyForecast, Precipitation, Wind};
+use super::conditions::Condition;
+use super::models::{DailyForecast, HourlyForecast, Obscuration, Precipitation, Wind};
 use crate::logger;
 use crate::weather::icons::{
-    DayNight, HumidityIconName, Icon, RainAmountIcon, RainAmountName, RainChanceName, UVIndexIcon,
-    WindIconName,
+    AirQualityIcon, DayNight, HumidityIconName, Icon, ObscurationName, PressureIconName,
+    RainAmountIcon, RainAmountName, RainChanceName, UVIndexIcon, WindIconName,
 };
 use crate::weather::utils::get_moon_phase_icon_name;
 use crate::CONFIG;
@@ -13,13 +14,7 @@ use crate::CONFIG;
 
 impl Icon for Wind {
     fn get_icon_name(&self) -> String {
-        let speed = self.get_speed(CONFIG.render_options.use_gust_instead_of_wind);
-        match speed {
-            0..=20 => WindIconName::Wind,
-            21..=40 => WindIconName::UmbrellaWind,
-            41.. => WindIconName::UmbrellaWindAlt,
-        }
-        .to_string()
+        WindSpeed(self.get_speed(CONFIG.render_options.wind_display.uses_gust())).get_icon_name()
     }
 }
 
@@ -114,6 +109,65 @@ fn apply_precipitation_override(
     }
 }
 
+impl From<Obscuration> for ObscurationName {
+    fn from(obscuration: Obscuration) -> Self {
+        match obscuration {
+            Obscuration::Smoke => ObscurationName::Smoke,
+            Obscuration::Haze => ObscurationName::Haze,
+            Obscuration::Dust => ObscurationName::Dust,
+        }
+    }
+}
+
+/// Builds the icon name for smoke, haze or dust. Only used when there is no rain,
+/// the icon set has no combined rain + smoke icons.
+///
+/// # Arguments
+///
+/// * `obscuration` - The obscuring condition
+/// * `cloud_name` - Cloud cover level, clear skies use the plain obscuration icon
+/// * `day_night` - Day or night variant
+///
+/// # Returns
+///
+/// * Icon file name, e.g. `partly-cloudy-day-smoke.svg` or `dust-night.svg`
+fn obscuration_icon_name(
+    obscuration: Obscuration,
+    cloud_name: RainChanceName,
+    day_night: DayNight,
+) -> String {
+    let name = ObscurationName::from(obscuration);
+    match (obscuration, cloud_name) {
+        (Obscuration::Dust, _) | (Obscuration::Haze, RainChanceName::Clear) => {
+            format!("{name}{day_night}.svg")
+        }
+        (Obscuration::Smoke, RainChanceName::Clear) => format!("{name}.svg"),
+        _ => format!("{cloud_name}{day_night}-{name}.svg"),
+    }
+}
+
+/// Condition of a sky, the rain taking precedence over smoke, haze or dust like on the icons
+fn sky_condition(
+    chance_name: RainChanceName,
+    amount_name: RainAmountName,
+    obscuration: Option<Obscuration>,
+) -> Condition {
+    match (amount_name, obscuration, chance_name) {
+        (RainAmountName::Rain, _, _) => Condition::Rain,
+        (RainAmountName::Drizzle, _, _) => Condition::Drizzle,
+        (RainAmountName::None, Some(obscuration), _) => match obscuration {
+            Obscuration::Smoke => Condition::Smoke,
+            Obscuration::Haze => Condition::Haze,
+            Obscuration::Dust => Condition::Dust,
+        },
+        (RainAmountName::None, None, RainChanceName::Clear) => Condition::Clear,
+        (RainAmountName::None, None, RainChanceName::PartlyCloudy) => Condition::PartlyCloudy,
+        (RainAmountName::None, None, RainChanceName::Overcast | RainChanceName::Extreme) => {
+            Condition::Cloudy
+        }
+    }
+}
+
 impl Icon for Precipitation {
     fn get_icon_name(&self) -> String {
         RainAmountIcon::RainAmount.to_string()
@@ -136,7 +190,13 @@ impl Icon for DailyForecast {
             // Note: After override, Clear can only occur with amount_name = None
             let adjusted_chance_name = apply_precipitation_override(chance_name, amount_name);
 
+            if let (Some(obscuration), RainAmountName::None) = (self.obscuration, amount_name) {
+                return obscuration_icon_name(obscuration, adjusted_chance_name, DayNight::Day);
+            }
+
             format!("{adjusted_chance_name}{}{amount_name}.svg", DayNight::Day)
+        } else if let Some(obscuration) = self.obscuration {
+            obscuration_icon_name(obscuration, RainChanceName::Clear, DayNight::Day)
         } else {
             // Default to clear day if no precipitation data
             format!("{}{}.svg", RainChanceName::Clear, DayNight::Day)
@@ -144,8 +204,34 @@ impl Icon for DailyForecast {
     }
 }
 
-impl Icon for HourlyForecast {
-    fn get_icon_name(&self) -> String {
+impl DailyForecast {
+    /// Condition shown by the daily icon
+    pub fn condition(&self) -> Condition {
+        let Some(ref precip) = self.precipitation else {
+            return sky_condition(
+                RainChanceName::Clear,
+                RainAmountName::None,
+                self.obscuration,
+            );
+        };
+        let chance_name = if let Some(cloud_cover) = self.cloud_cover {
+            cloud_cover_to_name(cloud_cover)
+        } else {
+            precip.chance_to_name()
+        };
+        let amount_name = precip.amount_to_name(false);
+
+        sky_condition(
+            apply_precipitation_override(chance_name, amount_name),
+            amount_name,
+            self.obscuration,
+        )
+    }
+}
+
+impl HourlyForecast {
+    /// Cloud level and precipitation amount the hourly icon is chosen from
+    fn sky(&self) -> (RainChanceName, RainAmountName) {
         // Determine cloud coverage from cloud_cover data if available, otherwise fall back to precipitation chance
         let chance_name = if let Some(cloud_cover) = self.cloud_cover {
             cloud_cover_to_name(cloud_cover)
@@ -154,15 +240,50 @@ impl Icon for HourlyForecast {
         };
 
         let amount_name = self.precipitation.amount_to_name(true);
+
+        // Apply precipitation override: ensure heavy rain requires adequate cloud cover
+        // Note: After override, Clear can only occur with amount_name = None
+        (
+            apply_precipitation_override(chance_name, amount_name),
+            amount_name,
+        )
+    }
+
+    /// Condition of the hour: the one the provider reports when the icon can't show it, e.g. a
+    /// thunderstorm, fog or snow, otherwise the one shown by the hourly icon, so a textual
+    /// description agrees with it
+    pub fn condition(&self) -> Condition {
+        match self.reported_condition {
+            Some(
+                Condition::Clear
+                | Condition::MostlyClear
+                | Condition::PartlyCloudy
+                | Condition::Cloudy,
+            )
+            | None => {
+                let (chance_name, amount_name) = self.sky();
+                let obscuration =
+                    self.effective_obscuration(CONFIG.render_options.smoke_pm2_5_threshold);
+                sky_condition(chance_name, amount_name, obscuration)
+            }
+            Some(reported) => reported,
+        }
+    }
+}
+
+impl Icon for HourlyForecast {
+    fn get_icon_name(&self) -> String {
+        let (adjusted_chance_name, amount_name) = self.sky();
         let day_night = if self.is_night {
             DayNight::Night
         } else {
             DayNight::Day
         };
 
-        // Apply precipitation override: ensure heavy rain requires adequate cloud cover
-        // Note: After override, Clear can only occur with amount_name = None
-        let adjusted_chance_name = apply_precipitation_override(chance_name, amount_name);
+        let obscuration = self.effective_obscuration(CONFIG.render_options.smoke_pm2_5_threshold);
+        if let (Some(obscuration), RainAmountName::None) = (obscuration, amount_name) {
+            return obscuration_icon_name(obscuration, adjusted_chance_name, day_night);
+        }
 
         let mut icon_name = format!("{adjusted_chance_name}{day_night}{amount_name}.svg");
 
@@ -170,13 +291,29 @@ impl Icon for HourlyForecast {
             && icon_name.ends_with(&format!("{}{}.svg", RainChanceName::Clear, DayNight::Night))
         {
             logger::detail("Using moon phase icon instead of clear night");
-            icon_name = get_moon_phase_icon_name().to_string();
+            // The phase of the forecast hour's night, so rendering doesn't depend on the wall clock
+            let date = self.time.with_timezone(&chrono::Local).date_naive();
+            icon_name = get_moon_phase_icon_name(date).to_string();
         }
 
         icon_name
     }
 }
 
+/// Wind icon for a speed in km/h, used for the sustained and the gust speed
+pub struct WindSpeed(pub u16);
+
+impl Icon for WindSpeed {
+    fn get_icon_name(&self) -> String {
+        match self.0 {
+            0..=20 => WindIconName::Wind,
+            21..=40 => WindIconName::UmbrellaWind,
+            41.. => WindIconName::UmbrellaWindAlt,
+        }
+        .to_string()
+    }
+}
+
 /// Helper struct for UV index icon selection
 pub struct UVIndex(pub u16);
 
@@ -195,14 +332,37 @@ impl Icon for UVIndex {
 }
 
 /// Helper struct for relative humidity icon selection
-pub struct RelativeHumidity(pub u16);
+pub struct RelativeHumidity(pub i16);
 
 impl Icon for RelativeHumidity {
     fn get_icon_name(&self) -> String {
         match self.0 {
-            0..=40 => HumidityIconName::Humidity.to_string(),
+            ..=40 => HumidityIconName::Humidity.to_string(),
             41..=70 => HumidityIconName::HumidityPlus.to_string(),
             71.. => HumidityIconName::HumidityPlusPlus.to_string(),
         }
     }
 }
+
+/// Helper struct for pressure icon selection (hPa)
+pub struct Pressure(pub Option<f32>);
+
+impl Icon for Pressure {
+    fn get_icon_name(&self) -> String {
+        match self.0 {
+            Some(hpa) if hpa < 1009.0 => PressureIconName::Low,
+            Some(hpa) if hpa > 1022.0 => PressureIconName::High,
+            _ => PressureIconName::Normal,
+        }
+        .to_string()
+    }
+}
+
+/// Helper struct for air quality icon selection
+pub struct AirQuality;
+
+impl Icon for AirQuality {
+    fn get_icon_name(&self) -> String {
+        AirQualityIcon::AirQuality.to_string()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod conditions;
+pub mod daylight;
 pub mod icons;
 pub mod models;
+pub mod sanity;
@@ -1,13 +1,16 @@
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::Serialize;
 use std::{
     fmt::{self, Display},
     ops::Deref,
 };
 
 use crate::configs::settings::TemperatureUnit;
+use crate::domain::conditions::Condition;
 
 /// Domain-specific Temperature type, independent of any API
-#[derive(Debug, Copy, PartialOrd, PartialEq, Clone)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Copy, PartialOrd, PartialEq, Clone, Serialize)]
 pub struct Temperature {
     pub value: f32,
     pub unit: TemperatureUnit,
@@ -85,7 +88,8 @@ impl From<crate::apis::bom::models::Temperature> for Temperature {
 }
 
 /// Domain model for wind information
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
 pub struct Wind {
     pub speed_kmh: u16,
     pub gust_speed_kmh: u16,
@@ -126,14 +130,45 @@ impl Wind {
         let speed_kmh = self.get_speed(use_gust);
         Self::convert_speed(speed_kmh, unit)
     }
+
+    /// Wind speed text in the specified unit, "sustained / gust" when both are displayed
+    pub fn format_speed(
+        &self,
+        display: crate::configs::settings::WindDisplay,
+        unit: crate::configs::settings::WindSpeedUnit,
+    ) -> String {
+        use crate::configs::settings::WindDisplay;
+        match display {
+            WindDisplay::Both => format!(
+                "{} / {}",
+                self.get_speed_in_unit(false, unit),
+                self.get_speed_in_unit(true, unit)
+            ),
+            display => self
+                .get_speed_in_unit(display.uses_gust(), unit)
+                .to_string(),
+        }
+    }
 }
 
 /// Domain model for precipitation information
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
 pub struct Precipitation {
     pub chance: Option<u16>,
     pub amount_min: Option<u16>,
     pub amount_max: Option<u16>,
+    /// Amount percentiles (25th, 50th, 75th) when the provider publishes them (BOM only)
+    pub amount_percentiles: Option<AmountPercentiles>,
+}
+
+/// Precipitation amount percentiles in mm, any of them may be missing
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AmountPercentiles {
+    pub p25: Option<u16>,
+    pub p50: Option<u16>,
+    pub p75: Option<u16>,
 }
 
 impl Precipitation {
@@ -142,9 +177,26 @@ impl Precipitation {
             chance,
             amount_min,
             amount_max,
+            amount_percentiles: None,
         }
     }
 
+    pub fn with_percentiles(mut self, percentiles: AmountPercentiles) -> Self {
+        self.amount_percentiles = Some(percentiles);
+        self
+    }
+
+    /// Lower and upper amount estimate (mm) for the uncertainty band.
+    ///
+    /// Uses the 25th-75th percentile where available, falling back to min/max.
+    /// Returns `None` when the provider only gives a single amount.
+    pub fn amount_band(&self) -> Option<(f32, f32)> {
+        let percentiles = self.amount_percentiles.unwrap_or_default();
+        let lower = percentiles.p25.or(self.amount_min)?;
+        let upper = percentiles.p75.or(self.amount_max)?;
+        Some((lower as f32, upper.max(lower) as f32))
+    }
+
     pub fn calculate_median(&self) -> f32 {
         let min = self.amount_min.unwrap_or(0);
         let max = self.amount_max.unwrap_or(min);
@@ -152,18 +204,47 @@ impl Precipitation {
     }
 }
 
+/// Visibility-reducing conditions without precipitation
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Obscuration {
+    Smoke,
+    Haze,
+    Dust,
+}
+
+impl Obscuration {
+    /// Maps a BOM `icon_descriptor` (e.g. "hazy", "dusty") to an obscuration.
+    pub fn from_bom_descriptor(descriptor: &str) -> Option<Self> {
+        Condition::from_bom_descriptor(descriptor).and_then(Condition::obscuration)
+    }
+}
+
 /// Domain model for astronomical data
 /// Sunrise/sunset times are stored as NaiveDateTime (timezone-agnostic wall-clock times)
 /// since they represent the actual clock time at the location, not a UTC timestamp
-#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct Astronomical {
     pub sunrise_time: Option<NaiveDateTime>,
     pub sunset_time: Option<NaiveDateTime>,
 }
 
+/// Rain a station's gauge has collected since it was last emptied, e.g. BOM's rain since 9am
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RainGaugeReading {
+    /// When the gauge was read
+    pub time: DateTime<Utc>,
+    /// When the gauge was last emptied
+    pub since: DateTime<Utc>,
+    pub rain_mm: f32,
+}
+
 /// Domain model for hourly weather forecast
 /// This is what the application works with, independent of any API
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
 pub struct HourlyForecast {
     pub time: DateTime<Utc>,
     pub temperature: Temperature,
@@ -171,14 +252,82 @@ pub struct HourlyForecast {
     pub wind: Wind,
     pub precipitation: Precipitation,
     pub uv_index: u16,
-    pub relative_humidity: u16,
+    /// Relative humidity (%), signed so a provider's impossible negative value can be dropped by
+    /// [`sanity`](crate::domain::sanity)
+    pub relative_humidity: i16,
     pub is_night: bool,
     pub cloud_cover: Option<u16>,
+    /// Convective available potential energy (J/kg), used as a thunderstorm risk proxy
+    pub cape: Option<f32>,
+    /// Mean sea level pressure (hPa)
+    pub pressure: Option<f32>,
+    /// US air quality index, only fetched when the AQI table row is configured
+    pub aqi: Option<u16>,
+    /// PM2.5 concentration (µg/m³), fetched together with the AQI
+    pub pm2_5: Option<f32>,
+    /// Smoke, haze or dust reported by the provider
+    pub obscuration: Option<Obscuration>,
+    /// Lowest and highest ensemble member temperature, Open-Meteo ensemble only
+    pub temperature_spread: Option<(Temperature, Temperature)>,
+    /// Condition the provider reports for the hour, from BOM's `icon_descriptor` or Open-Meteo's
+    /// WMO weather code, see [`conditions`](crate::domain::conditions)
+    pub reported_condition: Option<Condition>,
+}
+
+impl HourlyForecast {
+    /// Returns true when the CAPE value reaches the given threshold.
+    /// Hours without CAPE data are never considered at risk.
+    pub fn has_lightning_risk(&self, cape_threshold: f32) -> bool {
+        self.cape.is_some_and(|cape| cape >= cape_threshold)
+    }
+
+    /// Provider reported obscuration, or smoke when PM2.5 reaches the configured threshold.
+    pub fn effective_obscuration(&self, smoke_pm2_5_threshold: Option<f32>) -> Option<Obscuration> {
+        self.obscuration.or_else(|| {
+            let threshold = smoke_pm2_5_threshold?;
+            self.pm2_5
+                .is_some_and(|pm2_5| pm2_5 >= threshold)
+                .then_some(Obscuration::Smoke)
+        })
+    }
+
+    /// Returns true when the chance of precipitation reaches the given threshold (percent).
+    pub fn is_rain_likely(&self, chance_threshold: u16) -> bool {
+        self.precipitation
+            .chance
+            .is_some_and(|chance| chance >= chance_threshold)
+    }
+}
+
+/// Minutes from `now` until the first hour where rain is likely, looking at most
+/// `horizon_minutes` ahead. Returns `Some(0)` when the current hour is already rainy.
+///
+/// # Arguments
+///
+/// * `hourly_forecast_data` - Hourly forecasts, each covering one hour starting at `time`.
+/// * `now` - Current time.
+/// * `chance_threshold` - Minimum chance of precipitation (percent) counted as rain.
+/// * `horizon_minutes` - How far ahead to look.
+pub fn minutes_until_rain(
+    hourly_forecast_data: &[HourlyForecast],
+    now: DateTime<Utc>,
+    chance_threshold: u16,
+    horizon_minutes: i64,
+) -> Option<i64> {
+    let horizon_end = now + chrono::Duration::minutes(horizon_minutes);
+    hourly_forecast_data
+        .iter()
+        .filter(|forecast| {
+            forecast.time + chrono::Duration::hours(1) > now && forecast.time <= horizon_end
+        })
+        .find(|forecast| forecast.is_rain_likely(chance_threshold))
+        .map(|forecast| (forecast.time - now).num_minutes().max(0))
 }
 
 /// Domain model for daily weather forecast
 /// This is what the application works with, independent of any API
-#[derive(Debug, Clone)]
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize)]
 pub struct DailyForecast {
     /// Calendar date (timezone-agnostic) representing the forecast day
     pub date: Option<NaiveDate>,
@@ -187,6 +336,7 @@ pub struct DailyForecast {
     pub precipitation: Option<Precipitation>,
     pub astronomical: Option<Astronomical>,
     pub cloud_cover: Option<u16>,
+    pub obscuration: Option<Obscuration>,
 }
 
 // ============================================================================
@@ -204,11 +354,25 @@ impl From<crate::apis::bom::models::HourlyForecast> for HourlyForecast {
                 bom.rain.chance,
                 bom.rain.amount.min,
                 bom.rain.amount.max,
-            ),
+            )
+            .with_percentiles(bom.rain.amount_percentiles()),
             uv_index: bom.uv.unwrap_or_default().0,
             relative_humidity: bom.relative_humidity.0,
             is_night: bom.is_night,
             cloud_cover: None, // BOM API doesn't provide cloud cover data
+            cape: None,        // BOM API doesn't provide CAPE data
+            pressure: None,    // BOM hourly forecasts don't include pressure
+            aqi: None,         // BOM doesn't provide air quality
+            pm2_5: None,
+            obscuration: bom
+                .icon_descriptor
+                .as_deref()
+                .and_then(Obscuration::from_bom_descriptor),
+            temperature_spread: None,
+            reported_condition: bom
+                .icon_descriptor
+                .as_deref()
+                .and_then(Condition::from_bom_descriptor),
         }
     }
 }
@@ -235,6 +399,10 @@ impl From<crate::apis::bom::models::DailyEntry> for DailyForecast {
                     .map(|dt| dt.with_timezone(&chrono::Local).naive_local()),
             }),
             cloud_cover: None, // BOM API doesn't provide cloud cover data
+            obscuration: bom
+                .icon_descriptor
+                .as_deref()
+                .and_then(Obscuration::from_bom_descriptor),
         }
     }
 }
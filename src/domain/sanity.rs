@@ -0,0 +1,119 @@
+//! Sanity checks of the fetched forecasts
+//!
+//! A provider glitch, e.g. a missing value sent as -999, would otherwise be plotted as a spike
+//! through the whole graph. Points that can't be right are dropped before the dashboard sees
+//! them: hourly temperatures outside −60..60 °C, humidity outside 0..100 %, and hours or days
+//! repeating or going back in time. A daily temperature out of range is cleared, the rest of
+//! the day is kept. Every dropped point is logged, and the dashboard gets an `IncompleteData`
+//! diagnostic saying how many were dropped.
+
+use chrono::{DateTime, Utc};
+
+use super::models::{DailyForecast, HourlyForecast, Temperature};
+use crate::errors::DashboardError;
+
+/// Lowest temperature taken as real, in °C, below the coldest ever measured outside Antarctica
+pub const MIN_PLAUSIBLE_CELSIUS: f32 = -60.0;
+
+/// Highest temperature taken as real, in °C, above the hottest ever measured
+pub const MAX_PLAUSIBLE_CELSIUS: f32 = 60.0;
+
+fn is_plausible(temperature: Temperature) -> bool {
+    let celsius = temperature.to_celsius().value;
+    (MIN_PLAUSIBLE_CELSIUS..=MAX_PLAUSIBLE_CELSIUS).contains(&celsius)
+}
+
+fn out_of_range(name: &str, temperature: Temperature) -> String {
+    format!(
+        "{name} {:.1} °C outside {MIN_PLAUSIBLE_CELSIUS}..{MAX_PLAUSIBLE_CELSIUS} °C",
+        temperature.to_celsius().value
+    )
+}
+
+fn hour_label(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+/// Removes the implausible hours from `hourly`, returns a description of each. The graph
+/// places the remaining hours by their time, a removed hour leaves a gap rather than shifting
+/// the later ones
+pub fn drop_hourly_anomalies(hourly: &mut Vec<HourlyForecast>) -> Vec<String> {
+    let mut anomalies = Vec::new();
+    let mut previous: Option<DateTime<Utc>> = None;
+    hourly.retain(|hour| {
+        let problem = match previous {
+            Some(previous) if hour.time == previous => Some("duplicate hour".to_string()),
+            Some(previous) if hour.time < previous => {
+                Some(format!("out of order after {}", hour_label(previous)))
+            }
+            _ if !is_plausible(hour.temperature) => {
+                Some(out_of_range("temperature", hour.temperature))
+            }
+            _ if !is_plausible(hour.apparent_temperature) => Some(out_of_range(
+                "apparent temperature",
+                hour.apparent_temperature,
+            )),
+            _ if !(0..=100).contains(&hour.relative_humidity) => Some(format!(
+                "humidity {} % outside 0..100 %",
+                hour.relative_humidity
+            )),
+            _ => None,
+        };
+        match problem {
+            Some(problem) => {
+                anomalies.push(format!("hour {}: {problem}", hour_label(hour.time)));
+                false
+            }
+            None => {
+                previous = Some(hour.time);
+                true
+            }
+        }
+    });
+    anomalies
+}
+
+/// Removes repeated and out of order days from `daily` and clears implausible temperatures,
+/// returns a description of each
+pub fn drop_daily_anomalies(daily: &mut Vec<DailyForecast>) -> Vec<String> {
+    let mut anomalies = Vec::new();
+    let mut previous = None;
+    daily.retain_mut(|day| {
+        let Some(date) = day.date else {
+            return true;
+        };
+        match previous {
+            Some(previous) if date == previous => {
+                anomalies.push(format!("day {date}: duplicate day"));
+                return false;
+            }
+            Some(previous) if date < previous => {
+                anomalies.push(format!("day {date}: out of order after {previous}"));
+                return false;
+            }
+            _ => previous = Some(date),
+        }
+        for (name, temperature) in [("max", &mut day.temp_max), ("min", &mut day.temp_min)] {
+            if let Some(value) = temperature.filter(|value| !is_plausible(*value)) {
+                anomalies.push(format!(
+                    "day {date}: {}",
+                    out_of_range(&format!("{name} temperature"), value)
+                ));
+                *temperature = None;
+            }
+        }
+        true
+    });
+    anomalies
+}
+
+/// The diagnostic shown for the dropped points, `None` when there are none
+pub fn anomaly_warning(anomalies: &[String]) -> Option<DashboardError> {
+    let first = anomalies.first()?;
+    Some(DashboardError::IncompleteData {
+        details: format!(
+            "Dropped {} implausible forecast value(s) from the provider, e.g. {first}",
+            anomalies.len()
+        ),
+    })
+}
@@ -1,11 +1,18 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use strum_macros::Display;
 use thiserror::Error;
 
 use crate::weather::icons::Icon;
+use crate::CONFIG;
 
 /// Priority levels for dashboard diagnostics (higher value = higher priority)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Configured in lowercase, e.g. `"high"`, served as in the forecast JSON, e.g. `"High"`.
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "lowercase"))]
 pub enum DiagnosticPriority {
     Low = 1,    // IncompleteData - yellow
     Medium = 2, // NoInternet - orange
@@ -22,6 +29,26 @@ pub enum DashboardError {
     IncompleteData { details: String },
     #[error("Update failed")]
     UpdateFailed { details: String },
+    #[error("Fonts missing")]
+    FontsMissing { details: String },
+    #[error("Request budget exceeded")]
+    RequestBudgetExceeded { details: String },
+    #[error("Weather warning")]
+    WeatherWarning { details: String },
+}
+
+/// Kind of a dashboard diagnostic, the keys of `[diagnostics.priorities]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DiagnosticKind {
+    NoInternet,
+    ApiError,
+    IncompleteData,
+    UpdateFailed,
+    FontsMissing,
+    RequestBudgetExceeded,
+    WeatherWarning,
 }
 
 #[derive(Debug, Display)]
@@ -34,6 +61,12 @@ pub enum DashboardErrorIconName {
     IncompleteData,
     #[strum(to_string = "code-green.svg")]
     UpdateFailed,
+    #[strum(to_string = "code-yellow.svg")]
+    FontsMissing,
+    #[strum(to_string = "code-orange.svg")]
+    RequestBudgetExceeded,
+    #[strum(to_string = "flag-storm-warning.svg")]
+    WeatherWarning,
 }
 
 pub trait Description {
@@ -48,20 +81,57 @@ impl Icon for DashboardError {
             DashboardError::ApiError { .. } => DashboardErrorIconName::ApiError,
             DashboardError::IncompleteData { .. } => DashboardErrorIconName::IncompleteData,
             DashboardError::UpdateFailed { .. } => DashboardErrorIconName::UpdateFailed,
+            DashboardError::FontsMissing { .. } => DashboardErrorIconName::FontsMissing,
+            DashboardError::RequestBudgetExceeded { .. } => {
+                DashboardErrorIconName::RequestBudgetExceeded
+            }
+            DashboardError::WeatherWarning { .. } => DashboardErrorIconName::WeatherWarning,
         }
         .to_string()
     }
 }
 
 impl DashboardError {
-    /// Returns the priority of this error for display purposes.
-    /// Higher priority errors take precedence when multiple errors occur.
+    pub fn kind(&self) -> DiagnosticKind {
+        match self {
+            DashboardError::NoInternet { .. } => DiagnosticKind::NoInternet,
+            DashboardError::ApiError { .. } => DiagnosticKind::ApiError,
+            DashboardError::IncompleteData { .. } => DiagnosticKind::IncompleteData,
+            DashboardError::UpdateFailed { .. } => DiagnosticKind::UpdateFailed,
+            DashboardError::FontsMissing { .. } => DiagnosticKind::FontsMissing,
+            DashboardError::RequestBudgetExceeded { .. } => DiagnosticKind::RequestBudgetExceeded,
+            DashboardError::WeatherWarning { .. } => DiagnosticKind::WeatherWarning,
+        }
+    }
+
+    /// Returns the priority of this error for display purposes, as configured in
+    /// `[diagnostics.priorities]`. Higher priority errors take precedence when multiple errors
+    /// occur.
     pub fn priority(&self) -> DiagnosticPriority {
+        self.priority_in(&CONFIG.diagnostics.priorities)
+    }
+
+    /// The priority `priorities` gives this error's kind, the default priority of the kind when
+    /// it isn't listed
+    pub fn priority_in(
+        &self,
+        priorities: &HashMap<DiagnosticKind, DiagnosticPriority>,
+    ) -> DiagnosticPriority {
+        priorities
+            .get(&self.kind())
+            .copied()
+            .unwrap_or_else(|| self.default_priority())
+    }
+
+    pub fn default_priority(&self) -> DiagnosticPriority {
         match self {
             DashboardError::ApiError { .. } => DiagnosticPriority::High,
             DashboardError::NoInternet { .. } => DiagnosticPriority::Medium,
             DashboardError::IncompleteData { .. } => DiagnosticPriority::Low,
             DashboardError::UpdateFailed { .. } => DiagnosticPriority::Low,
+            DashboardError::FontsMissing { .. } => DiagnosticPriority::Low,
+            DashboardError::RequestBudgetExceeded { .. } => DiagnosticPriority::Medium,
+            DashboardError::WeatherWarning { .. } => DiagnosticPriority::High,
         }
     }
 }
@@ -73,6 +143,9 @@ impl Description for DashboardError {
             DashboardError::ApiError { .. } => "API error -> Stale Data",
             DashboardError::IncompleteData { .. } => "Incomplete Data",
             DashboardError::UpdateFailed { .. } => "Update Failed",
+            DashboardError::FontsMissing { .. } => "Fonts missing -> Embedded Fonts",
+            DashboardError::RequestBudgetExceeded { .. } => "Request budget used -> Stale Data",
+            DashboardError::WeatherWarning { .. } => "Weather warning in force",
         }
     }
 
@@ -90,10 +163,70 @@ impl Description for DashboardError {
             DashboardError::UpdateFailed { details } => {
                 format!("The application failed to update. Details: {details}")
             }
+            DashboardError::FontsMissing { details } => {
+                format!(
+                    "Font files are missing, using the embedded fallback fonts. Details: {details}"
+                )
+            }
+            DashboardError::RequestBudgetExceeded { details } => {
+                format!(
+                    "The daily request budget is used up, showing cached data. Details: {details}"
+                )
+            }
+            DashboardError::WeatherWarning { details } => {
+                format!("The Bureau of Meteorology issued a warning: {details}")
+            }
         }
     }
 }
 
+/// Boxed cause of a [`WeatherEpdError`], keeps the underlying error chain
+pub type ErrorSource = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Errors returned by the public dashboard generation functions.
+///
+/// Each variant marks the stage that failed, the source holds the details.
+#[derive(Debug, Error)]
+pub enum WeatherEpdError {
+    /// Invalid or unusable configuration, e.g. a missing template file
+    #[error("configuration error: {0}")]
+    Config(#[source] ErrorSource),
+    /// Weather data could not be fetched or loaded from the cache
+    #[error("weather provider error: {0}")]
+    Provider(#[source] ErrorSource),
+    /// The SVG template could not be rendered
+    #[error("failed to render dashboard: {0}")]
+    Render(#[source] ErrorSource),
+    /// The SVG could not be converted to PNG or raw display data
+    #[error("failed to convert dashboard image: {0}")]
+    Convert(#[source] ErrorSource),
+    /// The generated files could not be written
+    #[error("failed to write dashboard output: {0}")]
+    Output(#[source] ErrorSource),
+}
+
+impl WeatherEpdError {
+    pub fn config(error: impl Into<ErrorSource>) -> Self {
+        Self::Config(error.into())
+    }
+
+    pub fn provider(error: impl Into<ErrorSource>) -> Self {
+        Self::Provider(error.into())
+    }
+
+    pub fn render(error: impl Into<ErrorSource>) -> Self {
+        Self::Render(error.into())
+    }
+
+    pub fn convert(error: impl Into<ErrorSource>) -> Self {
+        Self::Convert(error.into())
+    }
+
+    pub fn output(error: impl Into<ErrorSource>) -> Self {
+        Self::Output(error.into())
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GeohashError {
     InvalidCoordinateRange(f64, f64),
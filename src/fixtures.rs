@@ -0,0 +1,178 @@
+//! Test fixtures recorded from the live providers
+//!
+//! `fixtures generate` requests the hourly and daily forecasts of the configured provider for the
+//! configured location, and writes each response next to the domain forecasts it converts to.
+//! When a provider changes its payload, the recorded response becomes the regression test's input
+//! and the `.expected.json` file the output to compare with.
+//!
+//! Responses are sanitized before they are written: coordinates are rounded to one decimal, about
+//! 10 km, so fixtures don't give away where the display hangs, and values that change with every
+//! request are zeroed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::apis::bom::models::{DailyForecastResponse, HourlyForecastResponse};
+use crate::apis::open_meteo::models::{OpenMeteoDailyResponse, OpenMeteoHourlyResponse};
+use crate::configs::settings::Providers;
+use crate::constants::{
+    daily_forecast_endpoint, hourly_forecast_endpoint, open_meteo_daily_endpoint,
+    open_meteo_hourly_endpoint, DAILY_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX,
+};
+use crate::domain::models::{DailyForecast, HourlyForecast};
+use crate::http::http_client;
+use crate::logger;
+
+/// Keys whose values locate the display, rounded by [`sanitize`]
+const COORDINATE_KEYS: [&str; 2] = ["latitude", "longitude"];
+
+/// Keys whose values differ on every request, zeroed by [`sanitize`]
+const VOLATILE_KEYS: [&str; 1] = ["generationtime_ms"];
+
+/// Domain forecasts a recorded response converts to
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum NormalizedForecast {
+    Hourly(Vec<HourlyForecast>),
+    Daily(Vec<DailyForecast>),
+}
+
+impl NormalizedForecast {
+    /// "hourly" or "daily"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NormalizedForecast::Hourly(_) => "hourly",
+            NormalizedForecast::Daily(_) => "daily",
+        }
+    }
+}
+
+/// Rounds the coordinates in `value` to one decimal and zeroes the volatile values, at any depth
+pub fn sanitize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if COORDINATE_KEYS.contains(&key.as_str()) {
+                    if let Some(coordinate) = value.as_f64() {
+                        *value = Value::from((coordinate * 10.0).round() / 10.0);
+                    }
+                } else if VOLATILE_KEYS.contains(&key.as_str()) {
+                    *value = Value::from(0);
+                } else {
+                    sanitize(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(sanitize),
+        _ => {}
+    }
+}
+
+/// Converts a recorded response of `provider` to the domain forecasts, `cache_suffix` names the
+/// forecast the same way as the cache files, e.g. [`HOURLY_CACHE_SUFFIX`]
+pub fn normalize(
+    provider: Providers,
+    cache_suffix: &str,
+    response: &str,
+) -> Result<NormalizedForecast, Error> {
+    let hourly = cache_suffix == HOURLY_CACHE_SUFFIX;
+    Ok(match (provider, hourly) {
+        (Providers::OpenMeteo, true) => NormalizedForecast::Hourly(
+            serde_json::from_str::<OpenMeteoHourlyResponse>(response)?.into(),
+        ),
+        (Providers::OpenMeteo, false) => NormalizedForecast::Daily(
+            serde_json::from_str::<OpenMeteoDailyResponse>(response)?.into(),
+        ),
+        (Providers::Bom, true) => NormalizedForecast::Hourly(
+            serde_json::from_str::<HourlyForecastResponse>(response)?
+                .data
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        ),
+        (Providers::Bom, false) => NormalizedForecast::Daily(
+            serde_json::from_str::<DailyForecastResponse>(response)?
+                .data
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        ),
+    })
+}
+
+/// File name prefix of the fixtures of `provider`, the same as its cache files
+fn fixture_prefix(provider: Providers) -> &'static str {
+    match provider {
+        Providers::Bom => "bom_",
+        Providers::OpenMeteo => "open_meteo_",
+    }
+}
+
+/// Hourly and daily endpoints of `provider` for the configured location
+fn endpoints(provider: Providers) -> [(&'static str, Url); 2] {
+    match provider {
+        Providers::Bom => [
+            (HOURLY_CACHE_SUFFIX, hourly_forecast_endpoint()),
+            (DAILY_CACHE_SUFFIX, daily_forecast_endpoint()),
+        ],
+        Providers::OpenMeteo => [
+            (HOURLY_CACHE_SUFFIX, open_meteo_hourly_endpoint()),
+            (DAILY_CACHE_SUFFIX, open_meteo_daily_endpoint()),
+        ],
+    }
+}
+
+/// Sanitizes `response` and writes it with its domain forecasts to `output_dir`, returns the
+/// paths written. A response the models can't read is still written, to update them with, but
+/// fails without the domain forecasts.
+pub fn write_fixture(
+    output_dir: &Path,
+    provider: Providers,
+    cache_suffix: &str,
+    response: &str,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut value: Value = serde_json::from_str(response).context("Response isn't JSON")?;
+    sanitize(&mut value);
+    let sanitized = serde_json::to_string_pretty(&value)?;
+
+    fs::create_dir_all(output_dir)?;
+    let fixture_path = output_dir.join(format!("{}{cache_suffix}", fixture_prefix(provider)));
+    let expected_path = fixture_path.with_extension("expected.json");
+    fs::write(&fixture_path, format!("{sanitized}\n"))?;
+    let normalized = normalize(provider, cache_suffix, &sanitized).with_context(|| {
+        format!(
+            "{} doesn't match the models, no expected output written",
+            fixture_path.display()
+        )
+    })?;
+    fs::write(
+        &expected_path,
+        serde_json::to_string_pretty(&normalized)? + "\n",
+    )?;
+    Ok(vec![fixture_path, expected_path])
+}
+
+/// Requests the forecasts of `provider` and writes them as fixtures to `output_dir`. The
+/// requests skip the cache and the daily request budget.
+pub async fn generate_fixtures(output_dir: &Path, provider: Providers) -> Result<(), Error> {
+    let client = http_client();
+    for (cache_suffix, endpoint) in endpoints(provider) {
+        logger::detail(format!("Requesting {endpoint}"));
+        let response = client
+            .get(endpoint)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        for path in write_fixture(output_dir, provider, cache_suffix, &response)? {
+            logger::success(format!("Wrote {}", path.display()));
+        }
+    }
+    Ok(())
+}
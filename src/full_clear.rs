@@ -0,0 +1,83 @@
+//! Periodic full clear of the display
+//!
+//! ACeP panels slowly build up ghosting from earlier images when they are only ever redrawn.
+//! Clearing the panel to white every few refreshes keeps it in check. The refreshes are counted
+//! in `refresh_count.json` in the cache directory, so the cycle survives server restarts.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use crate::logger;
+
+const REFRESH_COUNT_FILE: &str = "refresh_count.json";
+
+/// Serializes updates of the count file, displays may poll concurrently
+static COUNT_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RefreshCount {
+    refreshes: u64,
+}
+
+/// Counts a refresh of the display and returns whether it should be fully cleared first.
+///
+/// Every `interval`th refresh is a full clear, an `interval` of 0 disables them and counts nothing.
+pub(crate) fn count_refresh(cache_dir: &Path, interval: u32) -> bool {
+    if interval == 0 {
+        return false;
+    }
+
+    let _guard = COUNT_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let path = cache_dir.join(REFRESH_COUNT_FILE);
+
+    let mut count: RefreshCount = fs::read_to_string(&path)
+        .ok()
+        .and_then(|count| serde_json::from_str(&count).ok())
+        .unwrap_or_default();
+    count.refreshes += 1;
+
+    let written = fs::create_dir_all(cache_dir).and_then(|()| {
+        fs::write(
+            &path,
+            serde_json::to_string(&count).expect("refresh count always serializes"),
+        )
+    });
+    if let Err(e) = written {
+        logger::warning(format!("Failed to save the refresh count: {e}"));
+    }
+
+    count.refreshes.is_multiple_of(u64::from(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_nth_refresh_is_a_full_clear() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let clears: Vec<bool> = (0..6).map(|_| count_refresh(dir.path(), 3)).collect();
+
+        assert_eq!(clears, [false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn test_count_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(REFRESH_COUNT_FILE), r#"{"refreshes":9}"#).unwrap();
+
+        assert!(count_refresh(dir.path(), 10));
+        assert!(!count_refresh(dir.path(), 10));
+    }
+
+    #[test]
+    fn test_zero_interval_counts_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(!count_refresh(dir.path(), 0));
+        assert!(!dir.path().join(REFRESH_COUNT_FILE).exists());
+    }
+}
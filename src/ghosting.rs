@@ -0,0 +1,117 @@
+//! Ghosting compensation for ACeP panels
+//!
+//! Colour e-paper panels don't fully clear the previous image on a refresh, so where the frame
+//! changed a faint copy of the old content shows through. Before quantization the pixels that
+//! changed since the previous frame are pushed further away from their old colour, in proportion
+//! to `ghosting_compensation`, which drives the particles harder where the panel has to switch.
+//! Unchanged regions are left alone. The previous frame is kept, uncompensated, in
+//! `ghosting_previous.png` in the cache directory, and the frame it was compensated against in
+//! `ghosting_reference.png`. Rendering the same content again compensates it against the same
+//! reference, so the display keeps getting the frame it already shows instead of one with the
+//! compensation undone.
+
+use image::{Rgb, RgbImage};
+use std::path::Path;
+
+use crate::logger;
+
+/// Previous frame file in the cache directory
+pub const PREVIOUS_FRAME_FILE: &str = "ghosting_previous.png";
+
+/// File in the cache directory with the frame the previous frame was compensated against
+pub const REFERENCE_FRAME_FILE: &str = "ghosting_reference.png";
+
+/// A pixel changed when one of its channels differs by more than this from the previous frame,
+/// anti-aliasing differences on unchanged edges stay below it
+pub const CHANGE_THRESHOLD: u8 = 32;
+
+/// Pushes the pixels of `image` that changed since `previous` away from their previous colour by
+/// `strength` times the change, e.g. 0.5 turns a change from 100 to 200 into 250. Frames of
+/// another size are unrelated, `image` is then left as it is.
+pub fn compensate(image: &mut RgbImage, previous: &RgbImage, strength: f32) {
+    if strength <= 0.0 || image.dimensions() != previous.dimensions() {
+        return;
+    }
+    for (pixel, old) in image.pixels_mut().zip(previous.pixels()) {
+        if !pixel_changed(pixel, old) {
+            continue;
+        }
+        for (value, old) in pixel.0.iter_mut().zip(old.0) {
+            let new = f32::from(*value);
+            let change = new - f32::from(old);
+            *value = (new + change * strength).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+fn pixel_changed(pixel: &Rgb<u8>, old: &Rgb<u8>) -> bool {
+    pixel
+        .0
+        .iter()
+        .zip(old.0)
+        .any(|(new, old)| new.abs_diff(old) > CHANGE_THRESHOLD)
+}
+
+/// Whether a pixel of `image` changed since `previous`, frames of another size always differ
+pub fn frame_changed(image: &RgbImage, previous: &RgbImage) -> bool {
+    image.dimensions() != previous.dimensions()
+        || image
+            .pixels()
+            .zip(previous.pixels())
+            .any(|(pixel, old)| pixel_changed(pixel, old))
+}
+
+/// Compensates `image` against the previous frame in `cache_dir` and keeps `image` as the next
+/// one's previous frame. The first frame has nothing to compare with and is returned unchanged.
+///
+/// The same content as the previous frame is compensated against the frame that one was
+/// compensated against, so it comes out as it did the first time, and nothing is written.
+pub fn compensate_against_previous(
+    mut image: RgbImage,
+    cache_dir: &Path,
+    strength: f32,
+) -> RgbImage {
+    if strength <= 0.0 {
+        return image;
+    }
+    let open = |file| {
+        image::open(cache_dir.join(file))
+            .ok()
+            .map(|frame| frame.to_rgb8())
+    };
+    let previous = open(PREVIOUS_FRAME_FILE);
+
+    let reference = match previous {
+        Some(previous) if !frame_changed(&image, &previous) => open(REFERENCE_FRAME_FILE),
+        previous => {
+            if let Err(e) = save_frames(&image, previous.as_ref(), cache_dir) {
+                logger::warning(format!(
+                    "Failed to keep the frame for ghosting compensation: {e}"
+                ));
+            }
+            previous
+        }
+    };
+
+    if let Some(reference) = reference {
+        compensate(&mut image, &reference, strength);
+    }
+    image
+}
+
+/// Keeps `image` as the previous frame and `reference`, the frame before it, as its reference
+fn save_frames(
+    image: &RgbImage,
+    reference: Option<&RgbImage>,
+    cache_dir: &Path,
+) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(cache_dir)?;
+    let reference_path = cache_dir.join(REFERENCE_FRAME_FILE);
+    match reference {
+        Some(reference) => reference.save(&reference_path)?,
+        None if reference_path.exists() => std::fs::remove_file(&reference_path)?,
+        None => {}
+    }
+    image.save(cache_dir.join(PREVIOUS_FRAME_FILE))?;
+    Ok(())
+}
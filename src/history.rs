@@ -0,0 +1,82 @@
+//! Forecast history kept across runs
+//!
+//! Every rendered dashboard records the day's forecast in `history.json` in the cache directory,
+//! which is per profile, so each location builds up its own history. Temperatures are stored in
+//! °C whatever the configured unit.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::logger;
+use crate::providers::factory::cache_path;
+
+/// History file in the cache directory
+pub const HISTORY_FILE_NAME: &str = "history.json";
+
+/// Days older than this are dropped from the history, about 10 years
+pub const HISTORY_RETENTION_DAYS: i64 = 3660;
+
+/// What is known about one day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayHistory {
+    /// Forecast maximum temperature in °C
+    #[serde(default)]
+    pub temp_max: Option<f32>,
+    /// Forecast minimum temperature in °C
+    #[serde(default)]
+    pub temp_min: Option<f32>,
+}
+
+/// Recorded days of one location, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub days: BTreeMap<NaiveDate, DayHistory>,
+}
+
+impl History {
+    /// Reads the history at `path`, empty when there is none yet or it cannot be read
+    pub fn load(path: &Path) -> Self {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            logger::warning(format!(
+                "Ignoring unreadable history {}: {e}",
+                path.display()
+            ));
+            Self::default()
+        })
+    }
+
+    /// Writes the history to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            serde_json::to_string(self).expect("history always serializes"),
+        )
+    }
+
+    /// Records `day` for `date`, replacing an earlier forecast of the same day, and drops the days
+    /// past the retention
+    pub fn record(&mut self, date: NaiveDate, day: DayHistory) {
+        self.days.insert(date, day);
+        let oldest = date - Duration::days(HISTORY_RETENTION_DAYS);
+        self.days.retain(|recorded, _| *recorded >= oldest);
+    }
+
+    /// Recorded days before `date`
+    pub fn days_before(&self, date: NaiveDate) -> impl Iterator<Item = &DayHistory> {
+        self.days.range(..date).map(|(_, day)| day)
+    }
+}
+
+/// History file of the active profile
+pub fn history_path() -> PathBuf {
+    cache_path().join(HISTORY_FILE_NAME)
+}
@@ -0,0 +1,24 @@
+//! HTTP client shared by every request of the dashboard
+//!
+//! Building a client loads the TLS root certificates, which took a few hundred kilobytes on every
+//! render when each fetch built its own client. One client is built on first use instead and
+//! cloned, timeouts are set per request.
+//!
+//! Idle connections aren't kept. Renders are minutes apart, so the server would have closed them
+//! before they're reused, and a connection opened on the runtime of one CLI run can't be used on
+//! the next one's.
+
+use once_cell::sync::Lazy;
+
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    let builder = reqwest::Client::builder();
+    // In WebAssembly the browser's fetch keeps the connections
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = builder.pool_max_idle_per_host(0);
+    builder.build().unwrap_or_default()
+});
+
+/// The shared client, cheap to clone
+pub fn http_client() -> reqwest::Client {
+    HTTP_CLIENT.clone()
+}
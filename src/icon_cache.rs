@@ -0,0 +1,216 @@
+//! Weather icons rasterized once and shared between renders
+//!
+//! A dashboard draws a few dozen icon SVGs, and usvg would read and parse each of them again on
+//! every render. Before a dashboard is parsed, the `<image>` elements drawing a local SVG icon at
+//! a known size are rasterized at the size they end up on the canvas, once per icon, pixel size
+//! and anti-aliasing, and every later render, e.g. each web request, reuses the PNG from the
+//! cache. Icons that are scaled by a transform or a `viewBox` above them are left to usvg, the
+//! size they're drawn at isn't known up front.
+
+use once_cell::sync::Lazy;
+use resvg::tiny_skia;
+use resvg::usvg;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use usvg::roxmltree;
+
+use crate::logger;
+
+/// Icons kept in the cache, it's emptied when it's full
+pub const MAX_CACHED_ICONS: usize = 256;
+
+/// Prefix of the hrefs pointing into the icons of a render
+const CACHED_HREF_PREFIX: &str = "icon-cache:";
+
+/// Elements an icon can sit in and still be drawn at its own size
+const PLAIN_CONTAINERS: [&str; 4] = ["svg", "g", "a", "switch"];
+
+/// One rasterization of an icon
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IconKey {
+    pub path: PathBuf,
+    /// Size on the canvas in pixels
+    pub width: u32,
+    pub height: u32,
+    pub anti_aliasing: bool,
+}
+
+static ICON_CACHE: Lazy<Mutex<HashMap<IconKey, Arc<Vec<u8>>>>> = Lazy::new(Default::default);
+
+/// Whether the rasterization at `key` is in the cache
+pub fn is_cached(key: &IconKey) -> bool {
+    ICON_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .contains_key(key)
+}
+
+/// The PNG of the icon at `key`, rasterized on first use with `options`
+fn rasterized(key: &IconKey, options: &usvg::Options) -> Option<Arc<Vec<u8>>> {
+    if let Some(png) = ICON_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(key)
+    {
+        return Some(png.clone());
+    }
+
+    let png = match rasterize(key, options) {
+        Ok(png) => Arc::new(png),
+        Err(e) => {
+            logger::warning(format!("Not caching icon {}: {e}", key.path.display()));
+            return None;
+        }
+    };
+    let mut cache = ICON_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if cache.len() >= MAX_CACHED_ICONS {
+        cache.clear();
+    }
+    cache.insert(key.clone(), png.clone());
+    Some(png)
+}
+
+/// Renders the icon at `key` centred in its size, as `<image>` does by default
+fn rasterize(key: &IconKey, options: &usvg::Options) -> Result<Vec<u8>, String> {
+    let data = fs::read(&key.path).map_err(|e| e.to_string())?;
+    let tree = usvg::Tree::from_data(&data, options).map_err(|e| e.to_string())?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(key.width, key.height).ok_or("the icon has no pixels")?;
+    let size = tree.size();
+    let scale = (key.width as f32 / size.width()).min(key.height as f32 / size.height());
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+        (key.width as f32 - size.width() * scale) / 2.0,
+        (key.height as f32 - size.height() * scale) / 2.0,
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|e| e.to_string())
+}
+
+/// A length in user units, plain numbers and pixels only
+pub(crate) fn length(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .trim_end_matches("px")
+        .parse()
+        .ok()
+        .filter(|length: &f32| length.is_finite() && *length > 0.0)
+}
+
+/// Whether `transform` only moves what it applies to
+fn is_translation(transform: &str) -> bool {
+    let transform = transform.trim();
+    transform.is_empty()
+        || transform
+            .strip_prefix("translate(")
+            .is_some_and(|rest| rest.ends_with(')') && !rest.contains('('))
+}
+
+/// Whether `image` is drawn at its own width and height, nothing above it scales it
+fn drawn_at_own_size(image: roxmltree::Node) -> bool {
+    image.attribute("transform").is_none()
+        && image
+            .attribute("preserveAspectRatio")
+            .is_none_or(|ratio| matches!(ratio.trim(), "xMidYMid" | "xMidYMid meet"))
+        && image
+            .ancestors()
+            .skip(1)
+            .filter(|node| node.is_element())
+            .all(|node| {
+                PLAIN_CONTAINERS.contains(&node.tag_name().name())
+                    && node.attribute("viewBox").is_none()
+                    && node.attribute("transform").is_none_or(is_translation)
+            })
+}
+
+/// The icon file an `<image>` href points to, local SVG files only
+fn icon_path(href: &str) -> Option<&Path> {
+    let is_svg = Path::new(href)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+    (is_svg && !href.contains(':')).then(|| Path::new(href))
+}
+
+/// A dashboard with its icons rasterized, ready to be parsed with [`Self::options`]
+pub struct CachedIcons {
+    pub svg: String,
+    /// The PNGs the rewritten hrefs point to
+    pub icons: HashMap<String, Arc<Vec<u8>>>,
+}
+
+impl CachedIcons {
+    /// Points the icons of `svg` drawn at their own size to their rasterization for
+    /// `scale_factor`, rasterizing those not in the cache yet with `options`
+    pub fn of(svg: &str, scale_factor: f32, anti_aliasing: bool, options: &usvg::Options) -> Self {
+        let mut icons = HashMap::new();
+        let Ok(document) = roxmltree::Document::parse(svg) else {
+            return Self {
+                svg: svg.to_string(),
+                icons,
+            };
+        };
+
+        let mut edits = Vec::new();
+        for image in document
+            .descendants()
+            .filter(|node| node.has_tag_name("image") && drawn_at_own_size(*node))
+        {
+            let Some(href) = image
+                .attributes()
+                .find(|attribute| attribute.name() == "href")
+            else {
+                continue;
+            };
+            let (Some(path), Some(width), Some(height)) = (
+                icon_path(href.value()),
+                image.attribute("width").and_then(length),
+                image.attribute("height").and_then(length),
+            ) else {
+                continue;
+            };
+            let key = IconKey {
+                path: path.to_path_buf(),
+                width: ((width * scale_factor).round() as u32).max(1),
+                height: ((height * scale_factor).round() as u32).max(1),
+                anti_aliasing,
+            };
+            let Some(png) = rasterized(&key, options) else {
+                continue;
+            };
+
+            let cached_href = format!("{CACHED_HREF_PREFIX}{}", icons.len());
+            icons.insert(cached_href.clone(), png);
+            edits.push((href.range_value(), cached_href));
+            if image.attribute("image-rendering").is_none()
+                && svg[image.range()].starts_with("<image")
+            {
+                // The raster is drawn 1:1, sampling it smoothly would only blur it
+                let after_name = image.range().start + "<image".len();
+                edits.push((
+                    after_name..after_name,
+                    r#" image-rendering="optimizeSpeed""#.to_string(),
+                ));
+            }
+        }
+
+        let mut svg = svg.to_string();
+        edits.sort_by_key(|(range, _)| range.start);
+        for (range, replacement) in edits.into_iter().rev() {
+            svg.replace_range(range, &replacement);
+        }
+        Self { svg, icons }
+    }
+
+    /// `options` resolving the rewritten hrefs to the rasterized icons, other hrefs as before
+    pub fn options(self, mut options: usvg::Options<'static>) -> (String, usvg::Options<'static>) {
+        let icons = self.icons;
+        let resolve_file = usvg::ImageHrefResolver::default_string_resolver();
+        options.image_href_resolver.resolve_string =
+            Box::new(move |href, options| match icons.get(href) {
+                Some(png) => Some(usvg::ImageKind::PNG(png.clone())),
+                None => resolve_file(href, options),
+            });
+        (self.svg, options)
+    }
+}
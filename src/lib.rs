@@ -1,12 +1,41 @@
 pub mod apis;
+#[cfg(feature = "web")]
+pub mod buttons;
+pub mod calendar;
+pub mod calibration;
 pub mod clock;
 pub mod configs;
 pub mod constants;
 pub mod dashboard;
+#[cfg(feature = "web")]
+pub mod device_health;
+pub mod display_options;
 pub mod domain;
 pub mod errors;
+pub mod fixtures;
+#[cfg(feature = "web")]
+mod full_clear;
+pub mod ghosting;
+pub mod history;
+pub mod http;
+pub mod icon_cache;
 mod logger;
+pub mod memory;
+pub mod metrics_export;
+pub mod output_names;
+pub mod platform;
+#[cfg(feature = "web")]
+pub mod preview;
+#[cfg(feature = "web")]
+pub mod protocol;
 mod providers;
+pub mod raw_diff;
+pub mod refresh_schedule;
+pub mod render_core;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod run_guard;
+pub mod self_test;
+pub mod telemetry;
 pub mod update;
 pub mod utils;
 pub mod weather;
@@ -15,55 +44,193 @@ pub mod weather_dashboard;
 #[cfg(feature = "web")]
 pub mod web_server;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::configs::profiles::with_profile;
+use crate::configs::profiles::{active_profile, Profile};
 use crate::configs::settings::DashboardSettings;
-use crate::weather_dashboard::generate_weather_dashboard;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::run_guard::RunGuard;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::weather_dashboard::generate_weather_dashboard_in_dir;
+use crate::weather_dashboard::{
+    generate_weather_dashboard, generate_weather_dashboard_or_unavailable,
+};
 use anyhow::Error;
 use anyhow::Result;
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
 use update::update_app;
 
 // Re-export for testing
 pub use crate::weather_dashboard::generate_weather_dashboard_injection;
-pub use clock::{Clock, FixedClock, SystemClock};
+pub use clock::{AcceleratedClock, Clock, FixedClock, OffsetClock, SystemClock};
+pub use errors::WeatherEpdError;
+pub use logger::{reset_log_sink, set_log_sink, LogLevel, LogSink};
+
+/// Base settings and the web server profiles
+type Settings = (DashboardSettings, HashMap<String, Profile>);
 
-pub static CONFIG: Lazy<DashboardSettings> = Lazy::new(|| match DashboardSettings::new() {
-    Ok(config) => {
+/// Settings loaded on first use.
+///
+/// A configuration error is kept, so [`init_config`] can return it to the caller.
+static SETTINGS: Lazy<Result<Settings, String>> = Lazy::new(|| match DashboardSettings::load() {
+    Ok((config, profiles)) => {
         config.print_config();
-        config
+        print_profiles(&profiles);
+        for warning in platform::path_case_warnings(&config) {
+            logger::warning(warning);
+        }
+        Ok((config, profiles))
     }
     Err(e) => {
         logger::error(format!("Failed to load config: {e}"));
-        std::process::exit(1);
+        Err(e.to_string())
     }
 });
 
+/// Loads the configuration, returning the error instead of panicking on first use of [`CONFIG`].
+///
+/// The entry points below call it first. Applications embedding the crate must call it before
+/// using anything that reads the configuration.
+pub fn init_config() -> Result<(), WeatherEpdError> {
+    loaded_settings().map(|_| ())
+}
+
+/// The loaded settings, or the error loading them failed with
+fn loaded_settings() -> Result<&'static Settings, WeatherEpdError> {
+    SETTINGS
+        .as_ref()
+        .map_err(|e| WeatherEpdError::config(e.clone()))
+}
+
+/// The loaded settings for [`CONFIG`], which has no way to return the error
+///
+/// # Panics
+///
+/// When the configuration failed to load, see [`CONFIG`]
+fn expect_settings() -> &'static Settings {
+    loaded_settings()
+        .unwrap_or_else(|e| panic!("{e}, call init_config() first to handle the error"))
+}
+
+/// Settings in effect: the active web server profile's while one of its requests is handled,
+/// the base configuration otherwise
+///
+/// # Panics
+///
+/// Reading it panics when the configuration fails to load. [`init_config`] returns that error
+/// instead, so it must be called before anything reads the settings.
+pub static CONFIG: ActiveConfig = ActiveConfig;
+
+pub struct ActiveConfig;
+
+impl ActiveConfig {
+    /// Looks up a web server profile by name
+    pub fn profile(&self, name: &str) -> Option<&'static Profile> {
+        expect_settings().1.get(name)
+    }
+
+    /// Names of the web server profiles, sorted
+    pub fn profile_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&str> = expect_settings().1.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl std::ops::Deref for ActiveConfig {
+    type Target = DashboardSettings;
+
+    fn deref(&self) -> &DashboardSettings {
+        match active_profile() {
+            Some(profile) => &profile.settings,
+            None => &expect_settings().0,
+        }
+    }
+}
+
+fn print_profiles(profiles: &HashMap<String, Profile>) {
+    if profiles.is_empty() {
+        return;
+    }
+
+    logger::config_group("Profiles");
+    let mut names: Vec<&String> = profiles.keys().collect();
+    names.sort();
+    for name in names {
+        let api = &profiles[name].settings.api;
+        logger::kvp(
+            name,
+            format!(
+                "lat: {}, lon: {}",
+                api.latitude.into_inner(),
+                api.longitude.into_inner()
+            ),
+        );
+    }
+}
+
 pub fn generate_weather_dashboard_wrapper() -> Result<(), Error> {
-    generate_weather_dashboard()
+    block_on(generate_weather_dashboard())
 }
 
+/// Drives an async dashboard future to completion for the non-web binaries.
+///
+/// A single-threaded runtime is enough, the fetches only wait on the network.
+pub fn block_on<T, E>(future: impl std::future::Future<Output = Result<T, E>>) -> Result<T, Error>
+where
+    Error: From<E>,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(runtime.block_on(future)?)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 pub fn run_weather_dashboard() -> Result<(), anyhow::Error> {
+    init_config()?;
     logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
 
+    let guard = RunGuard::start(&CONFIG.misc.weather_data_cache_path)?;
+    let result = generate_and_update(&guard);
+    guard.finish(&result);
+    result?;
+
+    logger::app_end();
+    Ok(())
+}
+
+/// The phases of a cron run, a signal stops the run between them
+#[cfg(not(target_arch = "wasm32"))]
+fn generate_and_update(guard: &RunGuard) -> Result<(), anyhow::Error> {
     logger::section("Generating weather dashboard");
-    generate_weather_dashboard_wrapper()?;
+    guard.run("generating", generate_weather_dashboard())?;
 
     if CONFIG.release.update_interval_days.into_inner() > 0 {
         logger::section("Checking for updates");
-        update_app()?;
+        guard.finish_phase("updating", update_app)?;
     };
-
-    logger::app_end();
     Ok(())
 }
 
 /// Run weather dashboard with a custom clock (for simulation/testing)
+#[cfg(not(target_arch = "wasm32"))]
 pub fn run_weather_dashboard_with_clock(clock: &dyn Clock) -> Result<(), anyhow::Error> {
+    init_config()?;
     logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
 
     logger::section("Generating weather dashboard (simulation mode)");
     let input_template_name = &CONFIG.misc.template_path;
     let output_svg_name = &CONFIG.misc.generated_svg_name;
-    generate_weather_dashboard_injection(clock, input_template_name, output_svg_name)?;
+    let guard = RunGuard::start(&CONFIG.misc.weather_data_cache_path)?;
+    let result = guard.run(
+        "generating",
+        generate_weather_dashboard_or_unavailable(clock, input_template_name, output_svg_name),
+    );
+    guard.finish(&result);
+    result?;
 
     // Skip auto-update in simulation mode
     logger::detail("Skipping auto-update check in simulation mode");
@@ -71,3 +238,169 @@ pub fn run_weather_dashboard_with_clock(clock: &dyn Clock) -> Result<(), anyhow:
     logger::app_end();
     Ok(())
 }
+
+/// Renders the dashboard every `frame_interval` with `clock` until the process is stopped.
+///
+/// Meant for demo loops with an [`AcceleratedClock`]: the display shows the day passing faster.
+/// A failed frame is logged and the next one is tried.
+pub fn run_weather_dashboard_demo_loop(
+    clock: &dyn Clock,
+    frame_interval: std::time::Duration,
+) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    loop {
+        logger::section(format!(
+            "Generating weather dashboard for {} (demo loop)",
+            clock.now_local().format("%Y-%m-%d %H:%M")
+        ));
+        if let Err(e) = block_on(generate_weather_dashboard_or_unavailable(
+            clock,
+            &CONFIG.misc.template_path,
+            &CONFIG.misc.generated_svg_name,
+        )) {
+            logger::error(format!("Demo frame failed: {e}"));
+        }
+        std::thread::sleep(frame_interval);
+    }
+}
+
+/// Writes the palette test card to `output_dir`, see [`calibration`]
+pub fn run_calibration(output_dir: &std::path::Path) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    logger::section("Rendering the palette test card");
+    calibration::write_calibration_card(output_dir)?;
+
+    logger::app_end();
+    Ok(())
+}
+
+/// Records the forecasts of the configured provider as test fixtures in `output_dir`, see
+/// [`fixtures`]
+pub fn run_fixture_generation(output_dir: &std::path::Path) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    logger::section("Recording test fixtures");
+    block_on(fixtures::generate_fixtures(output_dir, CONFIG.api.provider))?;
+
+    logger::app_end();
+    Ok(())
+}
+
+/// Writes the forecast the dashboard renders to `output` as JSON, in the versioned schema of
+/// `/api/forecast.json`, see [`dashboard::forecast_json`]
+pub fn run_forecast_export(
+    output: &std::path::Path,
+    clock: &dyn Clock,
+) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    logger::section("Exporting the forecast");
+    let forecast = block_on(weather_dashboard::generate_forecast_json(clock))?;
+    std::fs::write(output, serde_json::to_string_pretty(&forecast)?)?;
+    logger::success(format!("Forecast written to {}", output.display()));
+
+    logger::app_end();
+    Ok(())
+}
+
+/// Writes the self-test screen to the configured outputs instead of the dashboard, see
+/// [`self_test`]
+pub fn run_self_test(clock: &dyn Clock) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    logger::section("Rendering the self-test screen");
+    let network = block_on(async { Ok::<_, Error>(self_test::check_network().await) })?;
+    match &network {
+        self_test::NetworkStatus::Reachable { .. } => logger::success(network.to_string()),
+        self_test::NetworkStatus::Unreachable { .. } => logger::warning(network.to_string()),
+    }
+    weather_dashboard::write_self_test_dashboard(clock, &network)?;
+
+    logger::app_end();
+    Ok(())
+}
+
+/// Name of the batch output directory of the base configuration
+pub const BATCH_DEFAULT_LOCATION: &str = "default";
+
+/// Renders the dashboard of every configured location in one run.
+///
+/// The base configuration is written to `output_dir/default/` and each profile to
+/// `output_dir/<profile>/`, using the configured file names. Locations are rendered in parallel
+/// and identical API requests, e.g. two profiles for the same coordinates, are only made once.
+///
+/// A failed location gets the "dashboard unavailable" image like a single run and doesn't stop
+/// the others, the run fails afterwards naming how many locations failed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_weather_dashboard_batch(output_dir: &std::path::Path) -> Result<(), anyhow::Error> {
+    init_config()?;
+    logger::app_start("Pi Inky Weather Display", env!("CARGO_PKG_VERSION"));
+
+    let mut locations: Vec<(&str, Option<&'static Profile>)> = vec![(BATCH_DEFAULT_LOCATION, None)];
+    locations.extend(
+        CONFIG
+            .profile_names()
+            .into_iter()
+            .map(|name| (name, CONFIG.profile(name))),
+    );
+    logger::section(format!(
+        "Generating weather dashboards for {} locations",
+        locations.len()
+    ));
+
+    let _shared_responses = providers::fetcher::share_responses();
+    let guard = RunGuard::start(&CONFIG.misc.weather_data_cache_path)?;
+    let result = guard.run("generating", async {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (name, profile) in &locations {
+            let name = name.to_string();
+            let location_dir = output_dir.join(&name);
+            let profile = *profile;
+            tasks.spawn(async move {
+                let render = generate_weather_dashboard_in_dir(&SystemClock, &location_dir);
+                let result = match profile {
+                    Some(profile) => with_profile(profile, render).await,
+                    None => render.await,
+                };
+                (name, location_dir, result)
+            });
+        }
+
+        let mut failed = 0;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((name, location_dir, Ok(()))) => logger::success(format!(
+                    "Location {name} rendered to {}",
+                    location_dir.display()
+                )),
+                Ok((name, _, Err(e))) => {
+                    logger::error(format!("Location {name} failed: {e}"));
+                    failed += 1;
+                }
+                Err(e) => {
+                    logger::error(format!("Location task failed: {e}"));
+                    failed += 1;
+                }
+            }
+        }
+        match failed {
+            0 => Ok(()),
+            failed => Err(anyhow::anyhow!(
+                "{failed} of {} locations failed",
+                locations.len()
+            )),
+        }
+    });
+    guard.finish(&result);
+    result?;
+
+    logger::app_end();
+    Ok(())
+}
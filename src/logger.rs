@@ -1,11 +1,53 @@
 //! Simple, professional logging utility for the weather dashboard
 //!
 //! Provides structured logging with visual indicators and clean formatting.
+//!
+//! Output goes to stdout unless an application embedding the crate installs its own sink with
+//! [`set_log_sink`], e.g. to show the messages in a GUI or forward them to its own logger.
 
 use std::fmt::Display;
+use std::sync::RwLock;
+
+/// Receives the log messages instead of stdout.
+///
+/// Messages are plain text without colours. Section headers, configuration values and details
+/// are logged as [`LogLevel::Info`].
+pub trait LogSink: Send + Sync {
+    fn log(&self, level: LogLevel, message: &str);
+}
+
+impl<F> LogSink for F
+where
+    F: Fn(LogLevel, &str) + Send + Sync,
+{
+    fn log(&self, level: LogLevel, message: &str) {
+        self(level, message)
+    }
+}
+
+static LOG_SINK: RwLock<Option<Box<dyn LogSink>>> = RwLock::new(None);
+
+/// Sends all further log messages to `sink` instead of stdout
+pub fn set_log_sink(sink: impl LogSink + 'static) {
+    *LOG_SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(sink));
+}
+
+/// Logs to stdout again
+pub fn reset_log_sink() {
+    *LOG_SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Passes `message` to the installed sink, or prints the `styled` line without one
+fn emit(level: LogLevel, message: impl Display, styled: impl FnOnce(&str) -> String) {
+    let message = message.to_string();
+    match LOG_SINK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(sink) => sink.log(level, &message),
+        None => println!("{}", styled(&message)),
+    }
+}
 
 /// Log levels with visual indicators
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
     Success,
@@ -54,24 +96,30 @@ impl LogLevel {
 
 /// Log a message with the specified level
 fn log_message(level: LogLevel, message: impl Display) {
-    println!(
-        "{}{} {}{} {}",
-        level.colour_code(),
-        level.symbol(),
-        level.label(),
-        LogLevel::RESET,
-        message
-    );
+    emit(level, message, |message| {
+        format!(
+            "{}{} {}{} {}",
+            level.colour_code(),
+            level.symbol(),
+            level.label(),
+            LogLevel::RESET,
+            message
+        )
+    });
 }
 
 /// Log a section header (major step in the process)
 pub fn section(title: impl Display) {
-    println!("\n\x1b[34m\x1b[1m▶ {title}\x1b[0m");
+    emit(LogLevel::Info, title, |title| {
+        format!("\n\x1b[34m\x1b[1m▶ {title}\x1b[0m")
+    });
 }
 
 /// Log a subsection (minor step within a major step)
 pub fn subsection(title: impl Display) {
-    println!("  \x1b[36m→\x1b[0m {title}");
+    emit(LogLevel::Info, title, |title| {
+        format!("  \x1b[36m→\x1b[0m {title}")
+    });
 }
 
 /// Log an info message
@@ -104,33 +152,43 @@ pub fn debug(message: impl Display) {
 
 /// Log a configuration group header
 pub fn config_group(title: impl Display) {
-    println!("  \x1b[1m[{}]\x1b[0m", title);
+    emit(LogLevel::Info, title, |title| {
+        format!("  \x1b[1m[{title}]\x1b[0m")
+    });
 }
 
 /// Log a key-value pair (useful for configuration or data display)
 pub fn kvp(key: impl Display, value: impl Display) {
-    let bullet = "\x1b[90m•\x1b[0m";
-    println!("  {bullet} {key}: {value}");
+    emit(LogLevel::Info, format!("{key}: {value}"), |pair| {
+        format!("  \x1b[90m•\x1b[0m {pair}")
+    });
 }
 
 /// Log raw data detail (like API responses)
 pub fn detail(message: impl Display) {
-    println!("    \x1b[90m{}\x1b[0m", message);
+    emit(LogLevel::Info, message, |message| {
+        format!("    \x1b[90m{message}\x1b[0m")
+    });
 }
 
 /// Log a separator line
 #[allow(dead_code)]
 pub fn separator() {
-    println!("\x1b[90m{}\x1b[0m", "─".repeat(60));
+    emit(LogLevel::Info, "─".repeat(60), |line| {
+        format!("\x1b[90m{line}\x1b[0m")
+    });
 }
 
 /// Log the start of the application
 pub fn app_start(app_name: &str, version: &str) {
-    println!("\n\x1b[1m{} v{}\x1b[0m", app_name, version);
-    println!("\x1b[90m{}\x1b[0m", "=".repeat(60));
+    emit(LogLevel::Info, format!("{app_name} v{version}"), |title| {
+        format!("\n\x1b[1m{title}\x1b[0m\n\x1b[90m{}\x1b[0m", "=".repeat(60))
+    });
 }
 
 /// Log the end of the application
 pub fn app_end() {
-    println!("\n\x1b[90m{}\x1b[0m", "=".repeat(60));
+    emit(LogLevel::Info, "=".repeat(60), |line| {
+        format!("\n\x1b[90m{line}\x1b[0m")
+    });
 }
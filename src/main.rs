@@ -3,14 +3,110 @@ use anyhow::Result;
 #[cfg(not(any(feature = "cli", feature = "web")))]
 use pi_inky_weather_epd::run_weather_dashboard;
 
+/// SIGINT and SIGTERM of the cron runs. The first one stops the run after the current step, see
+/// [`pi_inky_weather_epd::run_guard`], a second one ends the process right away.
+#[cfg(not(feature = "web"))]
+mod signals {
+    use anyhow::Result;
+    use pi_inky_weather_epd::run_guard::{request_stop, Interrupted};
+
+    /// Exit code of a process ended by a signal, as a shell reports SIGINT
+    const SECOND_SIGNAL_EXIT_CODE: i32 = 130;
+
+    /// Registers the handlers before returning, so a signal right after it isn't missed
+    pub fn handle() -> Result<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let mut signals = {
+            let _runtime = runtime.enter();
+            Signals::register()?
+        };
+        std::thread::spawn(move || {
+            runtime.block_on(async {
+                let name = signals.next().await;
+                eprintln!("{name} received, stopping after the current step");
+                request_stop();
+
+                let name = signals.next().await;
+                eprintln!("{name} received again, exiting");
+                std::process::exit(SECOND_SIGNAL_EXIT_CODE);
+            })
+        });
+        Ok(())
+    }
+
+    /// Ends a run stopped by a signal with the same exit code as a second signal, so cron and
+    /// systemd tell it apart from a failed run
+    pub fn exit_code(result: Result<()>) -> Result<()> {
+        match result {
+            Err(error) if error.downcast_ref::<Interrupted>().is_some() => {
+                eprintln!("{error}");
+                std::process::exit(SECOND_SIGNAL_EXIT_CODE);
+            }
+            result => result,
+        }
+    }
+
+    /// SIGINT and SIGTERM, Ctrl-C off Unix
+    struct Signals {
+        #[cfg(unix)]
+        interrupt: tokio::signal::unix::Signal,
+        #[cfg(unix)]
+        terminate: tokio::signal::unix::Signal,
+    }
+
+    impl Signals {
+        #[cfg(unix)]
+        fn register() -> Result<Self> {
+            use tokio::signal::unix::{signal, SignalKind};
+            Ok(Self {
+                interrupt: signal(SignalKind::interrupt())?,
+                terminate: signal(SignalKind::terminate())?,
+            })
+        }
+
+        #[cfg(not(unix))]
+        fn register() -> Result<Self> {
+            Ok(Self {})
+        }
+
+        /// Waits for the next signal and names it
+        #[cfg(unix)]
+        async fn next(&mut self) -> &'static str {
+            tokio::select! {
+                _ = self.interrupt.recv() => "SIGINT",
+                _ = self.terminate.recv() => "SIGTERM",
+            }
+        }
+
+        #[cfg(not(unix))]
+        async fn next(&mut self) -> &'static str {
+            let _ = tokio::signal::ctrl_c().await;
+            "Ctrl-C"
+        }
+    }
+}
+
 // CLI features only available when 'cli' feature is enabled (for simulation/testing)
 #[cfg(feature = "cli")]
 mod cli {
     use anyhow::Result;
-    use clap::Parser;
+    use clap::{Parser, Subcommand};
     use pi_inky_weather_epd::{
-        clock::FixedClock, run_weather_dashboard, run_weather_dashboard_with_clock,
+        clock::{
+            parse_accelerate_factor, parse_time_offset, AcceleratedClock, Clock, FixedClock,
+            OffsetClock, SystemClock,
+        },
+        run_calibration, run_fixture_generation, run_forecast_export, run_self_test,
+        run_weather_dashboard, run_weather_dashboard_batch, run_weather_dashboard_demo_loop,
+        run_weather_dashboard_with_clock,
     };
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    /// Real time between the frames of a demo loop
+    const DEMO_FRAME_INTERVAL: Duration = Duration::from_secs(30);
 
     /// Pi Inky Weather Display - Generate weather dashboards for e-paper displays
     #[derive(Parser, Debug)]
@@ -22,21 +118,139 @@ mod cli {
         /// Useful for generating multiple dashboards at different times for testing.
         #[arg(long, value_name = "TIMESTAMP")]
         pub simulate_time: Option<String>,
+
+        /// Shift the time by an offset, e.g. "6h" for tonight's render or "-3h" for this morning's.
+        /// Units are d, h, m and s. Applies to --simulate-time when both are given.
+        #[arg(long, value_name = "OFFSET", allow_hyphen_values = true, value_parser = parse_time_offset)]
+        pub time_offset: Option<chrono::Duration>,
+
+        /// Demo loop: render a dashboard every 30 seconds with time running FACTOR times faster,
+        /// e.g. 120 for an hour per frame. Starts at --simulate-time or now, shifted by --time-offset.
+        #[arg(long, value_name = "FACTOR", conflicts_with = "deterministic", value_parser = parse_accelerate_factor)]
+        pub accelerate: Option<f64>,
+
+        /// Deterministic mode: byte-identical SVG/PNG outputs for golden tests.
+        /// Rounds the SVG coordinates and only uses the bundled fonts, needs --simulate-time.
+        #[arg(long, requires = "simulate_time")]
+        pub deterministic: bool,
+
+        /// Batch mode: render every configured location, the base configuration and each
+        /// profile, in parallel to DIR/default/ and DIR/<profile>/
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with_all = ["simulate_time", "time_offset", "accelerate"]
+        )]
+        pub batch: Option<PathBuf>,
+
+        /// Log the anonymous telemetry ping of this run instead of sending it, whether or not
+        /// telemetry is enabled, to see exactly what would leave the device
+        #[arg(long)]
+        pub show_telemetry: bool,
+
+        #[command(subcommand)]
+        pub command: Option<Command>,
+    }
+
+    #[derive(Subcommand, Debug)]
+    pub enum Command {
+        /// Render a palette test card with every ink, gradients and text sizes to
+        /// calibration.svg, .png and .raw. Show it on the panel and photograph it to tune the
+        /// [palette] config.
+        Calibrate {
+            /// Directory the test card is written to
+            #[arg(long, value_name = "DIR", default_value = ".")]
+            output_dir: PathBuf,
+        },
+        /// Render the dashboard to the configured outputs, the same as running without a command
+        Render {
+            /// Render a diagnostic screen instead, with the version, a configuration summary,
+            /// the network status, sample icons and a timestamp. Push it to the panel during
+            /// setup to check the SVG to RAW to panel chain without live weather data.
+            #[arg(long)]
+            self_test: bool,
+        },
+        /// Write the forecast the dashboard renders as JSON, in the versioned schema of the web
+        /// server's /api/forecast.json
+        Export {
+            /// File the forecast is written to
+            #[arg(long, value_name = "FILE", default_value = "forecast.json")]
+            output: PathBuf,
+        },
+        /// Test fixtures for the provider models
+        Fixtures {
+            #[command(subcommand)]
+            command: FixturesCommand,
+        },
+    }
+
+    #[derive(Subcommand, Debug)]
+    pub enum FixturesCommand {
+        /// Request the hourly and daily forecasts of the configured provider and location, and
+        /// write the sanitized responses with the domain forecasts they convert to, in
+        /// <provider>_<forecast>.json and <provider>_<forecast>.expected.json
+        Generate {
+            /// Directory the fixtures are written to
+            #[arg(long, value_name = "DIR", default_value = "tests/fixtures/generated")]
+            output_dir: PathBuf,
+        },
     }
 
     pub fn run() -> Result<()> {
         let args = Args::parse();
 
-        if let Some(timestamp) = args.simulate_time {
-            let fixed_clock = FixedClock::from_rfc3339(&timestamp).map_err(|e| {
-                anyhow::anyhow!(
-                    "Invalid timestamp format: {}. Expected RFC3339 format like '2025-12-26T09:00:00Z'",
-                    e
-                )
-            })?;
-            run_weather_dashboard_with_clock(&fixed_clock)?;
-        } else {
-            run_weather_dashboard()?;
+        match args.command {
+            Some(Command::Calibrate { output_dir }) => return run_calibration(&output_dir),
+            Some(Command::Render { self_test: true }) => return run_self_test(&SystemClock),
+            Some(Command::Export { output }) => return run_forecast_export(&output, &SystemClock),
+            Some(Command::Fixtures {
+                command: FixturesCommand::Generate { output_dir },
+            }) => return run_fixture_generation(&output_dir),
+            Some(Command::Render { self_test: false }) | None => {}
+        }
+
+        if args.deterministic {
+            // Read when the configuration is loaded, like any other APP_ override
+            std::env::set_var("APP_DEBUGGING__DETERMINISTIC_OUTPUT", "true");
+        }
+        if args.show_telemetry {
+            std::env::set_var("APP_TELEMETRY__DRY_RUN", "true");
+        }
+
+        if let Some(output_dir) = args.batch {
+            return run_weather_dashboard_batch(&output_dir);
+        }
+
+        let fixed_clock = args
+            .simulate_time
+            .map(|timestamp| {
+                FixedClock::from_rfc3339(&timestamp).map_err(|e| {
+                    anyhow::anyhow!(
+                        "Invalid timestamp format: {}. Expected RFC3339 format like '2025-12-26T09:00:00Z'",
+                        e
+                    )
+                })
+            })
+            .transpose()?;
+
+        if let Some(factor) = args.accelerate {
+            let start = fixed_clock.map_or_else(chrono::Utc::now, |clock| clock.now_utc())
+                + args.time_offset.unwrap_or_default();
+            return run_weather_dashboard_demo_loop(
+                &AcceleratedClock::new(start, factor),
+                DEMO_FRAME_INTERVAL,
+            );
+        }
+
+        match (fixed_clock, args.time_offset) {
+            (Some(clock), Some(offset)) => {
+                run_weather_dashboard_with_clock(&OffsetClock::new(clock, offset))?
+            }
+            (Some(clock), None) => run_weather_dashboard_with_clock(&clock)?,
+            (None, Some(offset)) => {
+                run_weather_dashboard_with_clock(&OffsetClock::new(SystemClock, offset))?
+            }
+            (None, None) => run_weather_dashboard()?,
         }
 
         Ok(())
@@ -47,10 +261,14 @@ mod cli {
 #[cfg(feature = "web")]
 mod web {
     use anyhow::Result;
-    use clap::Parser;
-    use pi_inky_weather_epd::web_server;
+    use clap::{Parser, Subcommand};
+    use pi_inky_weather_epd::preview::run_preview;
+    use pi_inky_weather_epd::web_server::{self, ListenAddress};
+    use std::path::PathBuf;
 
     /// Pi Inky Weather Display - Web Server Mode
+    ///
+    /// A socket passed by systemd socket activation takes precedence over --port and --unix-socket.
     #[derive(Parser, Debug)]
     #[command(name = "pi-inky-weather-epd")]
     #[command(version, about, long_about = None)]
@@ -58,18 +276,46 @@ mod web {
         /// Port to run the web server on
         #[arg(short, long, default_value = "8080")]
         pub port: u16,
+
+        /// Listen on this Unix domain socket instead of a TCP port
+        #[arg(long, value_name = "PATH", conflicts_with = "port")]
+        pub unix_socket: Option<PathBuf>,
+
+        #[command(subcommand)]
+        pub command: Option<Command>,
+    }
+
+    #[derive(Subcommand, Debug)]
+    pub enum Command {
+        /// Preview the dashboard in a browser at http://127.0.0.1:PORT/preview, rendered from
+        /// the cached forecast
+        Preview {
+            /// Refresh the browser when the template or the icons change, and restart when the
+            /// config changes
+            #[arg(long)]
+            watch: bool,
+        },
     }
 
     pub async fn run() -> Result<()> {
         let args = Args::parse();
-        web_server::run_server(args.port).await?;
+        if let Some(Command::Preview { watch }) = args.command {
+            return run_preview(args.port, watch).await;
+        }
+
+        let address = match args.unix_socket {
+            Some(path) => ListenAddress::UnixSocket(path),
+            None => ListenAddress::Port(args.port),
+        };
+        web_server::run_server(address).await?;
         Ok(())
     }
 }
 
 #[cfg(feature = "cli")]
 fn main() -> Result<()> {
-    cli::run()
+    signals::handle()?;
+    signals::exit_code(cli::run())
 }
 
 #[cfg(feature = "web")]
@@ -80,6 +326,6 @@ async fn main() -> Result<()> {
 
 #[cfg(not(any(feature = "cli", feature = "web")))]
 fn main() -> Result<()> {
-    run_weather_dashboard()?;
-    Ok(())
+    signals::handle()?;
+    signals::exit_code(run_weather_dashboard())
 }
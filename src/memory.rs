@@ -0,0 +1,117 @@
+//! Memory use of the long-running modes
+//!
+//! A render allocates the SVG tree, the pixmap and the PNG, tens of megabytes at the resolution
+//! of the 7.3" panel. glibc keeps memory freed by other threads in their arenas instead of
+//! returning it to the system, so the resident set of the web server crept up render after
+//! render until a 512 MB Pi Zero ran out. The web server hands the freed memory back after
+//! each render and checks the resident set against `web_server.memory_limit_mb`.
+//!
+//! The pixmap, the largest of them, is pooled: a render takes the pixmap of an earlier render of
+//! the same size instead of allocating a new one. The font database is loaded once and shared by
+//! all renders. The SVG tree and its parse options are still built for each render, the tree is
+//! the rendered dashboard itself and the options hold the render's rasterized icons.
+
+use resvg::tiny_skia;
+use serde::Serialize;
+use std::sync::{Mutex, PoisonError};
+
+use crate::logger;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Pixmaps kept for reuse, enough for a few web server renders at once
+const MAX_POOLED_PIXMAPS: usize = 2;
+
+static PIXMAPS: Mutex<Vec<tiny_skia::Pixmap>> = Mutex::new(Vec::new());
+
+/// Resident set of the process, from `/proc/self/status`
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryUsage {
+    /// Memory the process occupies now
+    pub rss_bytes: u64,
+    /// Highest resident set since the process started
+    pub peak_rss_bytes: u64,
+}
+
+impl MemoryUsage {
+    /// Memory use of this process, `None` where `/proc` isn't available
+    pub fn current() -> Option<Self> {
+        Self::from_proc_status(&std::fs::read_to_string("/proc/self/status").ok()?)
+    }
+
+    /// Reads the `VmRSS` and `VmHWM` lines of a `/proc/<pid>/status` file
+    pub fn from_proc_status(status: &str) -> Option<Self> {
+        let kilobytes = |field: &str| -> Option<u64> {
+            let line = status
+                .lines()
+                .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))?;
+            let value = line.trim().strip_suffix("kB")?.trim();
+            Some(value.parse::<u64>().ok()? * 1024)
+        };
+        Some(Self {
+            rss_bytes: kilobytes("VmRSS")?,
+            peak_rss_bytes: kilobytes("VmHWM")?,
+        })
+    }
+
+    pub fn rss_mb(&self) -> u64 {
+        self.rss_bytes / BYTES_PER_MB
+    }
+}
+
+/// A transparent `width` x `height` pixmap, the one of an earlier render of that size when one
+/// was handed back with [`return_pixmap`]. `None` when either size is 0.
+pub fn pooled_pixmap(width: u32, height: u32) -> Option<tiny_skia::Pixmap> {
+    let reused = {
+        let mut pixmaps = PIXMAPS.lock().unwrap_or_else(PoisonError::into_inner);
+        pixmaps
+            .iter()
+            .position(|pixmap| pixmap.width() == width && pixmap.height() == height)
+            .map(|index| pixmaps.swap_remove(index))
+    };
+    match reused {
+        Some(mut pixmap) => {
+            pixmap.fill(tiny_skia::Color::TRANSPARENT);
+            Some(pixmap)
+        }
+        None => tiny_skia::Pixmap::new(width, height),
+    }
+}
+
+/// Keeps `pixmap` for the next render, dropping the oldest kept one when the pool is full
+pub fn return_pixmap(pixmap: tiny_skia::Pixmap) {
+    let mut pixmaps = PIXMAPS.lock().unwrap_or_else(PoisonError::into_inner);
+    if pixmaps.len() == MAX_POOLED_PIXMAPS {
+        pixmaps.remove(0);
+    }
+    pixmaps.push(pixmap);
+}
+
+/// Returns memory freed by the allocator to the system, only glibc holds on to it
+pub fn release_freed_memory() {
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    // SAFETY: malloc_trim only releases free pages of the allocator's own heaps
+    unsafe {
+        libc::malloc_trim(0);
+    }
+}
+
+/// Releases the memory freed by a render and warns when the resident set is above `limit_mb`,
+/// 0 disables the warning
+pub fn after_render(limit_mb: u32) -> Option<MemoryUsage> {
+    release_freed_memory();
+    let usage = MemoryUsage::current()?;
+    logger::debug(format!(
+        "Resident memory {} MB, peak {} MB",
+        usage.rss_mb(),
+        usage.peak_rss_bytes / BYTES_PER_MB
+    ));
+    if limit_mb > 0 && usage.rss_bytes > u64::from(limit_mb) * BYTES_PER_MB {
+        logger::warning(format!(
+            "Resident memory {} MB is above the limit of {limit_mb} MB",
+            usage.rss_mb()
+        ));
+    }
+    Some(usage)
+}
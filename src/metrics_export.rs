@@ -0,0 +1,162 @@
+//! Render metrics pushed to a Prometheus Pushgateway or InfluxDB after each run
+//!
+//! The web server can be scraped, a cron run can't, so the CLI pushes a few numbers from the
+//! rendered dashboard instead: the current temperature, the max UV index, today's rain total,
+//! how long the render took and the resident memory afterwards. The metrics are labelled with the job and the profile only, the
+//! location is never sent.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use anyhow::Error;
+use url::Url;
+
+use crate::configs::profiles::active_profile;
+use crate::configs::settings::{MetricsBackend, MetricsExport};
+use crate::dashboard::context::Context;
+use crate::dashboard::number_format::number_format;
+use crate::http::http_client;
+use crate::logger;
+use crate::memory::MemoryUsage;
+
+/// How long a push may take before it is given up
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Label of the base configuration, profiles are labelled with their name
+const DEFAULT_INSTANCE: &str = "default";
+
+/// Numbers of one rendered dashboard, `None` when the dashboard showed no value
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderMetrics {
+    pub current_temperature: Option<f64>,
+    pub max_uv_index: Option<f64>,
+    pub rain_total: Option<f64>,
+    pub render_duration: Duration,
+    /// Resident memory after the render
+    pub rss_bytes: Option<f64>,
+}
+
+impl RenderMetrics {
+    /// Reads the metrics from the values rendered into the dashboard template
+    pub fn from_context(context: &Context, render_duration: Duration) -> Self {
+        // The values are written with the decimal separator of the configured locale
+        let format = number_format();
+        let value = |text: &str| format.parse(text);
+        Self {
+            current_temperature: value(&context.current_hour_actual_temp),
+            max_uv_index: value(&context.max_uv_index),
+            rain_total: value(&context.total_rain_today),
+            render_duration,
+            rss_bytes: None,
+        }
+    }
+
+    /// The metrics with the resident memory of `usage`
+    pub fn with_memory_usage(self, usage: Option<MemoryUsage>) -> Self {
+        Self {
+            rss_bytes: usage.map(|usage| usage.rss_bytes as f64),
+            ..self
+        }
+    }
+
+    /// Metric names without prefix and their values, the missing ones left out
+    fn values(&self) -> Vec<(&'static str, f64)> {
+        [
+            ("current_temperature", self.current_temperature),
+            ("max_uv_index", self.max_uv_index),
+            ("rain_total", self.rain_total),
+            (
+                "render_duration_seconds",
+                Some(self.render_duration.as_secs_f64()),
+            ),
+            ("rss_bytes", self.rss_bytes),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+    }
+}
+
+/// The metrics in the Prometheus text format, as gauges prefixed with `weather_epd_`
+pub fn prometheus_text(metrics: &RenderMetrics) -> String {
+    let mut text = String::new();
+    for (name, value) in metrics.values() {
+        let _ = writeln!(text, "# TYPE weather_epd_{name} gauge");
+        let _ = writeln!(text, "weather_epd_{name} {value}");
+    }
+    text
+}
+
+/// The metrics as one InfluxDB line, tagged with the instance and stamped by the server
+pub fn influx_line(metrics: &RenderMetrics, measurement: &str, instance: &str) -> String {
+    let escape = |text: &str| {
+        text.replace('\\', "\\\\")
+            .replace(',', "\\,")
+            .replace('=', "\\=")
+            .replace(' ', "\\ ")
+    };
+    let fields: Vec<String> = metrics
+        .values()
+        .into_iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    format!(
+        "{},instance={} {}\n",
+        escape(measurement),
+        escape(instance),
+        fields.join(",")
+    )
+}
+
+/// Pushgateway URL of the job's group for `instance`
+pub fn pushgateway_url(base: &Url, job: &str, instance: &str) -> Result<Url, Error> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|()| anyhow::anyhow!("{base} can't be a Pushgateway URL"))?
+        .pop_if_empty()
+        .extend(["metrics", "job", job, "instance", instance]);
+    Ok(url)
+}
+
+async fn push(settings: &MetricsExport, url: &Url, metrics: &RenderMetrics) -> Result<(), Error> {
+    let instance = active_profile().map_or(DEFAULT_INSTANCE, |profile| profile.name.as_str());
+    let client = http_client();
+
+    let request = match settings.backend {
+        // PUT replaces every metric of the group, so a metric missing now doesn't linger
+        MetricsBackend::Pushgateway => client
+            .put(pushgateway_url(url, &settings.job, instance)?)
+            .body(prometheus_text(metrics)),
+        MetricsBackend::Influxdb => {
+            let request =
+                client
+                    .post(url.clone())
+                    .body(influx_line(metrics, &settings.job, instance));
+            match &settings.influxdb_token {
+                Some(token) => {
+                    request.header(reqwest::header::AUTHORIZATION, format!("Token {token}"))
+                }
+                None => request,
+            }
+        }
+    };
+    request
+        .timeout(PUSH_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Pushes `metrics` as `settings` asks. A failed push is only logged, it never fails the run.
+pub async fn push_metrics(settings: &MetricsExport, metrics: &RenderMetrics) {
+    let Some(url) = &settings.url else {
+        return;
+    };
+
+    logger::subsection(format!("Pushing render metrics to {}", settings.backend));
+    match push(settings, url, metrics).await {
+        Ok(()) => logger::success("Render metrics pushed"),
+        Err(error) => logger::warning(format!("Failed to push the render metrics: {error}")),
+    }
+}
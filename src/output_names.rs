@@ -0,0 +1,125 @@
+//! Output file names with timestamp placeholders
+//!
+//! An output name like `out/dashboard-{date}-{hour}.png` gets the time of the render filled in,
+//! so every run keeps its own files for an archive. Placeholders are only allowed in the file
+//! name, and with `misc.output_retention_days` the archived files older than that are removed
+//! after each run. Only files matching the configured name are touched.
+
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::logger;
+
+/// The placeholders, their format and a pattern matching what they're replaced with
+const PLACEHOLDERS: [(&str, &str, &str); 4] = [
+    ("{date}", "%Y-%m-%d", r"\d{4}-\d{2}-\d{2}"),
+    ("{time}", "%H%M", r"\d{4}"),
+    ("{hour}", "%H", r"\d{2}"),
+    ("{minute}", "%M", r"\d{2}"),
+];
+
+/// Whether the file name of `path` has placeholders
+pub fn has_placeholders(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        PLACEHOLDERS
+            .iter()
+            .any(|(placeholder, ..)| name.to_string_lossy().contains(placeholder))
+    })
+}
+
+/// Checks the placeholders of an output name are known and only in its file name
+pub fn check_output_name(path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        if parent.to_string_lossy().contains('{') {
+            return Err(format!(
+                "Output {} has a placeholder in its directory, placeholders are only allowed in the file name",
+                path.display()
+            ));
+        }
+    }
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    for (placeholder, ..) in PLACEHOLDERS {
+        name = name.replace(placeholder, "");
+    }
+    if name.contains('{') || name.contains('}') {
+        return Err(format!(
+            "Output {} has an unknown placeholder, the placeholders are {}",
+            path.display(),
+            PLACEHOLDERS.map(|(placeholder, ..)| placeholder).join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// `path` with the placeholders replaced by `time`
+pub fn expand(path: &Path, time: DateTime<Local>) -> PathBuf {
+    let Some(name) = path.file_name() else {
+        return path.to_path_buf();
+    };
+    let mut name = name.to_string_lossy().into_owned();
+    for (placeholder, format, _) in PLACEHOLDERS {
+        name = name.replace(placeholder, &time.format(format).to_string());
+    }
+    path.with_file_name(name)
+}
+
+/// A pattern of the file names `path` expands to
+fn file_name_pattern(path: &Path) -> Option<regex::Regex> {
+    let name = path.file_name()?.to_string_lossy();
+    let mut pattern = regex::escape(&name);
+    for (placeholder, _, matches) in PLACEHOLDERS {
+        pattern = pattern.replace(&regex::escape(placeholder), matches);
+    }
+    regex::Regex::new(&format!("^{pattern}$")).ok()
+}
+
+/// Removes the files `path` expanded to that were last written more than `retention` before
+/// `now`, returns how many were removed. A name without placeholders is always kept.
+pub fn remove_expired(path: &Path, retention: Duration, now: SystemTime) -> usize {
+    if !has_placeholders(path) {
+        return 0;
+    }
+    let Some(pattern) = file_name_pattern(path) else {
+        return 0;
+    };
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        if !pattern.is_match(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|age| age > retention)
+            });
+        if !expired {
+            continue;
+        }
+        match fs::remove_file(entry.path()) {
+            Ok(()) => {
+                logger::detail(format!("Removed expired output {}", entry.path().display()));
+                removed += 1;
+            }
+            Err(e) => logger::warning(format!(
+                "Failed to remove expired output {}: {e}",
+                entry.path().display()
+            )),
+        }
+    }
+    removed
+}
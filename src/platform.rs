@@ -0,0 +1,116 @@
+//! Differences between the Raspberry Pi and development machines
+//!
+//! The dashboard runs on a Pi, but templates are quicker to iterate on from a laptop. macOS and
+//! Windows keep the user config and fonts in other directories, Windows separates paths with
+//! backslashes, which aren't valid in an SVG href, and both usually ignore case in file names, so
+//! a path that works there can miss its file on the Pi. The helpers here keep a render the same
+//! on all of them.
+
+use directories::BaseDirs;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+
+use crate::configs::settings::DashboardSettings;
+
+/// The user config files, without the `.toml` extension, in the order they're merged.
+///
+/// `~/.config/pi-inky-weather-epd` is read everywhere, then the platform's config directory,
+/// e.g. `~/Library/Application Support/pi-inky-weather-epd` on macOS or
+/// `%APPDATA%\pi-inky-weather-epd` on Windows. On Linux both are the same file unless
+/// `XDG_CONFIG_HOME` points elsewhere. Empty when there is no home directory.
+pub fn user_config_files() -> Vec<PathBuf> {
+    let Some(dirs) = BaseDirs::new() else {
+        return Vec::new();
+    };
+    let mut files = vec![dirs.home_dir().join(".config").join(env!("CARGO_PKG_NAME"))];
+    let platform_file = dirs.config_dir().join(env!("CARGO_PKG_NAME"));
+    if !files.contains(&platform_file) {
+        files.push(platform_file);
+    }
+    files
+}
+
+/// Directory whose fonts are loaded with the system fonts, e.g.
+/// `~/.local/share/pi-inky-weather-epd/fonts` on Linux or
+/// `~/Library/Application Support/pi-inky-weather-epd/fonts` on macOS
+pub fn user_fonts_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.data_dir().join(env!("CARGO_PKG_NAME")).join("fonts"))
+}
+
+/// `path` as an SVG href, with forward slashes on every platform
+pub fn href(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if MAIN_SEPARATOR == '/' {
+        return path.into_owned();
+    }
+    path.replace(MAIN_SEPARATOR, "/")
+}
+
+/// The spelling on disk of `path` when it only matches a file ignoring case.
+///
+/// `None` when every component matches exactly, or when part of the path doesn't exist at all.
+/// A case-insensitive file system finds the file either way, the Pi's doesn't.
+pub fn case_mismatch(path: &Path) -> Option<PathBuf> {
+    let mut on_disk = PathBuf::new();
+    let mut mismatched = false;
+    for component in path.components() {
+        let Component::Normal(name) = component else {
+            on_disk.push(component);
+            continue;
+        };
+        let dir = if on_disk.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            on_disk.as_path()
+        };
+        let entries: Vec<_> = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name())
+            .collect();
+        if entries.iter().any(|entry| entry == name) {
+            on_disk.push(name);
+            continue;
+        }
+        let wanted = name.to_string_lossy().to_lowercase();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.to_string_lossy().to_lowercase() == wanted)?;
+        on_disk.push(entry);
+        mismatched = true;
+    }
+    mismatched.then_some(on_disk)
+}
+
+/// Warnings for the configured paths that only match their file ignoring case
+pub fn path_case_warnings(settings: &DashboardSettings) -> Vec<String> {
+    let mut paths = vec![
+        ("Template", &settings.misc.template_path),
+        ("Icon directory", &settings.misc.svg_icons_directory),
+    ];
+    if let Some(path) = &settings.misc.icon_overrides_path {
+        paths.push(("Icon overrides", path));
+    }
+    paths.extend(
+        settings
+            .fonts
+            .font_files
+            .iter()
+            .map(|file| ("Font file", file)),
+    );
+
+    paths
+        .into_iter()
+        .filter_map(|(name, path)| {
+            case_mismatch(path).map(|on_disk| case_warning(name, path, &on_disk))
+        })
+        .collect()
+}
+
+/// Warning for `path` whose file is spelled `on_disk`
+pub fn case_warning(name: &str, path: &Path, on_disk: &Path) -> String {
+    format!(
+        "{name} {} is spelled {} on disk, it won't be found on a case-sensitive file system like the Pi's",
+        path.display(),
+        on_disk.display()
+    )
+}
@@ -0,0 +1,200 @@
+//! Live preview of the dashboard for template work
+//!
+//! `preview` serves the dashboard on localhost with a page that shows it in the browser. With
+//! `--watch` the template, the icons and the config files are polled for changes: a changed
+//! template or icon refreshes the page, a changed config restarts the preview so it is loaded
+//! again. The dashboard is rendered from the cached forecast, so saving a template doesn't cost
+//! an API request.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+
+use crate::configs::settings::DashboardSettings;
+use crate::logger;
+use crate::platform;
+use crate::web_server::router;
+use crate::CONFIG;
+
+/// How often the watched files are checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Changes when the preview page should load the dashboard again, the time of the last change
+/// so a restarted preview never repeats a value the page has seen
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Size, modification time and path of every watched file
+pub type Fingerprint = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+const PREVIEW_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>Dashboard preview</title>
+</head>
+<body style="margin: 0; padding: 16px; background: #808080">
+    <object id="dashboard" data="/dashboard.svg" type="image/svg+xml"></object>
+    <script>
+        let generation = null;
+        async function poll() {
+            try {
+                const response = await fetch("/preview/generation");
+                const next = await response.text();
+                if (generation !== null && next !== generation) {
+                    const dashboard = document.getElementById("dashboard");
+                    dashboard.data = "/dashboard.svg?generation=" + next;
+                }
+                generation = next;
+            } catch (error) {
+                // The preview is restarting after a config change
+            }
+            setTimeout(poll, 1000);
+        }
+        poll();
+    </script>
+</body>
+</html>
+"#;
+
+fn bump_generation() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64);
+    GENERATION.store(now, Ordering::Relaxed);
+}
+
+async fn serve_preview_page() -> Html<&'static str> {
+    Html(PREVIEW_PAGE)
+}
+
+async fn serve_generation() -> String {
+    GENERATION.load(Ordering::Relaxed).to_string()
+}
+
+/// The web server's router with the preview page at `/preview`
+pub fn preview_router() -> Router {
+    router()
+        .route("/preview", get(serve_preview_page))
+        .route("/preview/generation", get(serve_generation))
+}
+
+fn collect_files(path: &Path, fingerprint: &mut Fingerprint) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if !metadata.is_dir() {
+        fingerprint.push((path.to_path_buf(), metadata.len(), metadata.modified().ok()));
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        collect_files(&entry.path(), fingerprint);
+    }
+}
+
+/// Files under `paths`, sorted, so two fingerprints differ when a file is saved, added or
+/// removed. Missing paths are skipped.
+pub fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    let mut fingerprint = Fingerprint::new();
+    for path in paths {
+        collect_files(path, &mut fingerprint);
+    }
+    fingerprint.sort();
+    fingerprint
+}
+
+/// The template and the icons, read on every render
+fn template_paths() -> Vec<PathBuf> {
+    vec![
+        CONFIG.misc.template_path.clone(),
+        CONFIG.misc.svg_icons_directory.clone(),
+    ]
+}
+
+/// The config directory and the user config, only read at startup
+fn config_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("config")];
+    paths.extend(
+        platform::user_config_files()
+            .into_iter()
+            .map(|file| file.with_extension("toml")),
+    );
+    paths
+}
+
+/// Replaces the process with a new preview, started with the same arguments
+#[cfg(unix)]
+fn restart() -> std::io::Error {
+    use std::os::unix::process::CommandExt;
+
+    let program = match std::env::current_exe() {
+        Ok(program) => program,
+        Err(error) => return error,
+    };
+    std::process::Command::new(program)
+        .args(std::env::args_os().skip(1))
+        .exec()
+}
+
+/// Without `exec` a new preview couldn't take over the port of the running one
+#[cfg(not(unix))]
+fn restart() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "start it again to load the changed config",
+    )
+}
+
+async fn watch_for_changes() {
+    let mut templates = fingerprint(&template_paths());
+    let mut configs = fingerprint(&config_paths());
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+
+        let changed_configs = fingerprint(&config_paths());
+        if changed_configs != configs {
+            configs = changed_configs;
+            // A broken config would stop the preview, keep the running one until it's fixed
+            match DashboardSettings::load() {
+                Ok(_) => {
+                    logger::info("Config changed, restarting the preview");
+                    let error = restart();
+                    logger::error(format!("Failed to restart the preview: {error}"));
+                }
+                Err(error) => logger::warning(format!("Config changed but doesn't load: {error}")),
+            }
+            continue;
+        }
+
+        let changed_templates = fingerprint(&template_paths());
+        if changed_templates != templates {
+            templates = changed_templates;
+            logger::info("Template or icons changed, refreshing the preview");
+            bump_generation();
+        }
+    }
+}
+
+/// Serves the preview on localhost `port` until stopped, watching for changes with `watch`
+pub async fn run_preview(port: u16, watch: bool) -> Result<(), anyhow::Error> {
+    // Read when the configuration is loaded, like any other APP_ override
+    std::env::set_var("APP_DEBUGGING__DISABLE_WEATHER_API_REQUESTS", "true");
+    crate::init_config()?;
+    bump_generation();
+
+    if watch {
+        tokio::spawn(watch_for_changes());
+    }
+
+    let addr = format!("127.0.0.1:{port}");
+    logger::info(format!("Previewing the dashboard on http://{addr}/preview"));
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    axum::serve(listener, preview_router()).await?;
+    Ok(())
+}
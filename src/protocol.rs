@@ -0,0 +1,81 @@
+//! Protocol version negotiated with the display firmware
+//!
+//! Displays that fetch the raw output send the protocol version their firmware speaks in the
+//! `X-Protocol-Version` header, and the server answers with the version it served, the lower of
+//! the two. Firmware from before the negotiation sends no header and gets version 1, so a server
+//! upgrade never hands an old device a frame it can't decode.
+//!
+//! | Version | Raw output                                                               |
+//! |---------|--------------------------------------------------------------------------|
+//! | 1       | `/dashboard.raw` with ACeP palette indices only                          |
+//! | 2       | IT8951 grey levels with `driver=it8951`, and `/dashboard.diff`           |
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::dashboard::forecast_json::FORECAST_SCHEMA_VERSION;
+
+/// Newest protocol version the server speaks
+pub const PROTOCOL_VERSION: u16 = 2;
+
+/// Oldest protocol version the server still serves
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+/// Request and response header carrying the protocol version
+pub const PROTOCOL_VERSION_HEADER: &str = "X-Protocol-Version";
+
+/// Protocol version a response is served with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProtocolVersion(pub u16);
+
+impl ProtocolVersion {
+    /// Version to serve a display sending `requested`, or a message for a 400 response. Firmware
+    /// newer than the server gets the server's version and is expected to fall back to it.
+    pub fn negotiate(requested: Option<&str>) -> Result<Self, String> {
+        let Some(requested) = requested else {
+            return Ok(Self(MIN_PROTOCOL_VERSION));
+        };
+        match requested.trim().parse::<u16>() {
+            Ok(version) if version >= MIN_PROTOCOL_VERSION => {
+                Ok(Self(version.min(PROTOCOL_VERSION)))
+            }
+            _ => Err(format!(
+                "{PROTOCOL_VERSION_HEADER} must be a protocol version from {MIN_PROTOCOL_VERSION}, the server speaks up to {PROTOCOL_VERSION}"
+            )),
+        }
+    }
+
+    /// Whether the firmware can show IT8951 grey levels
+    pub fn supports_greyscale(self) -> bool {
+        self.0 >= 2
+    }
+
+    /// Whether the firmware can apply raw diffs
+    pub fn supports_diff(self) -> bool {
+        self.0 >= 2
+    }
+}
+
+/// Versions of the server, served at `/version`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct VersionInfo {
+    /// Release of the server, e.g. "1.4.0"
+    pub version: &'static str,
+    /// Newest protocol version the server speaks
+    pub protocol_version: u16,
+    /// Oldest protocol version the server still serves
+    pub min_protocol_version: u16,
+    /// Version of the `/api/forecast.json` schema
+    pub forecast_schema_version: u32,
+}
+
+impl VersionInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+            forecast_schema_version: FORECAST_SCHEMA_VERSION,
+        }
+    }
+}
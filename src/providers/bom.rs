@@ -1,22 +1,32 @@
 use anyhow::Error;
+use async_trait::async_trait;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use crate::{
-    apis::bom::models::{BomError, DailyForecastResponse, HourlyForecastResponse},
+    apis::bom::models::{
+        BomError, DailyForecastResponse, HourlyForecastResponse, LocationResponse,
+        ObservationsResponse, WarningsResponse,
+    },
+    clock::Clock,
+    configs::settings::Providers,
     constants::{
-        DAILY_CACHE_SUFFIX, DAILY_FORECAST_ENDPOINT, HOURLY_CACHE_SUFFIX, HOURLY_FORECAST_ENDPOINT,
+        bom_location_endpoint, bom_observations_endpoint, bom_warnings_endpoint,
+        daily_forecast_endpoint, hourly_forecast_endpoint, DAILY_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX,
+        LOCATION_CACHE_SUFFIX, OBSERVATIONS_CACHE_SUFFIX, WARNINGS_CACHE_SUFFIX,
     },
-    domain::models::{DailyForecast, HourlyForecast},
+    domain::models::{DailyForecast, HourlyForecast, RainGaugeReading},
     errors::DashboardError,
+    logger,
     providers::{
         fetcher::{FetchOutcome, Fetcher},
         FetchResult, WeatherProvider,
     },
+    CONFIG,
 };
 
 /// BOM-specific error checker
 fn check_bom_error(body: &str) -> Result<(), DashboardError> {
-    use crate::logger;
     logger::debug("Checking for API errors");
     // Try to parse as error response; if it's not an error format, that's fine (return Ok)
     let api_error = match serde_json::from_str::<BomError>(body) {
@@ -53,47 +63,130 @@ fn check_bom_error(body: &str) -> Result<(), DashboardError> {
 
 pub struct BomProvider {
     fetcher: Fetcher,
+    /// Rain gauge reading of the last observation fetched with the hourly forecast
+    rain_gauge_reading: Mutex<Option<RainGaugeReading>>,
+    /// Marine wind warnings among the warnings of the last fetch
+    marine_wind_warnings: Mutex<Vec<String>>,
 }
 
 impl BomProvider {
     pub fn new(cache_path: PathBuf) -> Self {
         Self {
-            fetcher: Fetcher::new(cache_path),
+            fetcher: Fetcher::new(cache_path, Providers::Bom),
+            rain_gauge_reading: Mutex::new(None),
+            marine_wind_warnings: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fetches the nearest station's latest observation when it is enabled. The forecast is
+    /// still usable without it, so failures are logged and the forecast is kept.
+    async fn fetch_observations(&self) -> Option<ObservationsResponse> {
+        if !CONFIG.api.bom_observations {
+            return None;
+        }
+        match self
+            .fetcher
+            .fetch_data::<ObservationsResponse>(
+                bom_observations_endpoint(),
+                &self.generate_cache_filename(OBSERVATIONS_CACHE_SUFFIX),
+                Some(check_bom_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Observations unavailable: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Fetches the time zone of the location for the station's 9am gauge reset, only with the
+    /// observations. Without it the rain gauge reading is left out.
+    async fn fetch_time_zone(&self) -> Option<chrono_tz::Tz> {
+        if !CONFIG.api.bom_observations {
+            return None;
+        }
+        match self
+            .fetcher
+            .fetch_data::<LocationResponse>(
+                bom_location_endpoint(),
+                &self.generate_cache_filename(LOCATION_CACHE_SUFFIX),
+                Some(check_bom_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => {
+                let time_zone = data.time_zone();
+                if time_zone.is_none() {
+                    logger::warning(format!("Unknown location time zone {}", data.data.timezone));
+                }
+                time_zone
+            }
+            Err(e) => {
+                logger::warning(format!("Location time zone unavailable: {e}"));
+                None
+            }
         }
     }
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl WeatherProvider for BomProvider {
-    fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error> {
-        match self.fetcher.fetch_data::<HourlyForecastResponse>(
-            HOURLY_FORECAST_ENDPOINT.clone(),
-            &self.generate_cache_filename(HOURLY_CACHE_SUFFIX),
-            Some(check_bom_error),
-        )? {
+    async fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error> {
+        // The forecast, the observations and the time zone are independent, request them together
+        let cache_filename = self.generate_cache_filename(HOURLY_CACHE_SUFFIX);
+        let (forecast, observations, time_zone) = tokio::join!(
+            self.fetcher.fetch_data::<HourlyForecastResponse>(
+                hourly_forecast_endpoint(),
+                &cache_filename,
+                Some(check_bom_error),
+            ),
+            self.fetch_observations(),
+            self.fetch_time_zone(),
+        );
+
+        let mut result = match forecast? {
             FetchOutcome::Fresh(data) => {
                 // Convert BOM models to domain models
                 let domain_data: Vec<HourlyForecast> =
                     data.data.into_iter().map(|h| h.into()).collect();
-                crate::logger::debug(format!(
+                logger::debug(format!(
                     "Converted {} BOM hourly entries to domain model",
                     domain_data.len()
                 ));
-                Ok(FetchResult::fresh(domain_data))
+                FetchResult::fresh(domain_data)
             }
             FetchOutcome::Stale { data, error } => {
                 let domain_data: Vec<HourlyForecast> =
                     data.data.into_iter().map(|h| h.into()).collect();
-                Ok(FetchResult::stale(domain_data, error))
+                FetchResult::stale(domain_data, error)
             }
+        };
+
+        if let Some(observations) = observations {
+            observations.merge_into(&mut result.data);
+            *self
+                .rain_gauge_reading
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) =
+                time_zone.and_then(|time_zone| observations.rain_gauge_reading(time_zone));
         }
+
+        Ok(result)
     }
 
-    fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error> {
-        match self.fetcher.fetch_data::<DailyForecastResponse>(
-            DAILY_FORECAST_ENDPOINT.clone(),
-            &self.generate_cache_filename(DAILY_CACHE_SUFFIX),
-            Some(check_bom_error),
-        )? {
+    async fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error> {
+        match self
+            .fetcher
+            .fetch_data::<DailyForecastResponse>(
+                daily_forecast_endpoint(),
+                &self.generate_cache_filename(DAILY_CACHE_SUFFIX),
+                Some(check_bom_error),
+            )
+            .await?
+        {
             FetchOutcome::Fresh(data) => {
                 // Convert BOM models to domain models
                 let domain_data: Vec<DailyForecast> =
@@ -119,4 +212,46 @@ impl WeatherProvider for BomProvider {
     fn provider_filename_prefix(&self) -> &str {
         "bom_"
     }
+
+    async fn fetch_warnings(&self, clock: &dyn Clock) -> Vec<DashboardError> {
+        if !CONFIG.api.bom_warnings {
+            return Vec::new();
+        }
+        match self
+            .fetcher
+            .fetch_data::<WarningsResponse>(
+                bom_warnings_endpoint(),
+                &self.generate_cache_filename(WARNINGS_CACHE_SUFFIX),
+                Some(check_bom_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => {
+                let now = clock.now_utc();
+                *self
+                    .marine_wind_warnings
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()) = data.marine_wind_warnings(now);
+                data.active_warnings(now)
+            }
+            Err(e) => {
+                logger::warning(format!("Warnings unavailable: {e}"));
+                Vec::new()
+            }
+        }
+    }
+
+    fn rain_gauge_reading(&self) -> Option<RainGaugeReading> {
+        *self
+            .rain_gauge_reading
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn marine_wind_warnings(&self) -> Vec<String> {
+        self.marine_wind_warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
 }
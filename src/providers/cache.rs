@@ -0,0 +1,87 @@
+//! Versioned cache file layout
+//!
+//! Cached API responses are stored in an envelope:
+//!
+//! ```json
+//! { "schema_version": 1, "data": { ...raw API response... } }
+//! ```
+//!
+//! Files written before versioning hold the raw response and are treated as version 0.
+//! When a cached response model changes shape, bump [`CACHE_SCHEMA_VERSION`] and add a step
+//! to [`MIGRATIONS`] that rewrites the previous layout.
+
+use anyhow::{anyhow, Error};
+use serde_json::{json, Value};
+
+/// Current cache layout version
+pub const CACHE_SCHEMA_VERSION: u64 = 1;
+
+/// Migration steps, `MIGRATIONS[n]` upgrades a version `n` file to version `n + 1`
+const MIGRATIONS: [fn(Value) -> Value; CACHE_SCHEMA_VERSION as usize] = [wrap_raw_response];
+
+/// Version 0 -> 1: wrap the raw API response in the versioned envelope
+fn wrap_raw_response(raw: Value) -> Value {
+    json!({ "schema_version": 1, "data": raw })
+}
+
+/// Wraps a freshly fetched API response for writing to the cache
+pub fn to_envelope(data: Value) -> Value {
+    json!({ "schema_version": CACHE_SCHEMA_VERSION, "data": data })
+}
+
+/// Returns the schema version of a cache file, 0 for the unversioned layout
+pub fn schema_version(cached: &Value) -> u64 {
+    cached
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Upgrades a cache file of any known version and returns the API response inside it.
+///
+/// Fails for files written by a newer version of the application.
+pub fn migrate(mut cached: Value) -> Result<Value, Error> {
+    let version = schema_version(&cached);
+    if version > CACHE_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "cache schema version {version} is newer than the supported version {CACHE_SCHEMA_VERSION}"
+        ));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        cached = migration(cached);
+    }
+
+    match cached {
+        Value::Object(mut envelope) => envelope
+            .remove("data")
+            .ok_or_else(|| anyhow!("cache file has no data")),
+        _ => Err(anyhow!("cache file is not a JSON object")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_cache_is_migrated() {
+        let raw = json!({ "hourly": { "time": [] } });
+        assert_eq!(schema_version(&raw), 0);
+        assert_eq!(migrate(raw.clone()).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_current_envelope_round_trips() {
+        let raw = json!({ "data": [1, 2, 3] });
+        let envelope = to_envelope(raw.clone());
+        assert_eq!(schema_version(&envelope), CACHE_SCHEMA_VERSION);
+        assert_eq!(migrate(envelope).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_newer_cache_is_rejected() {
+        let envelope = json!({ "schema_version": CACHE_SCHEMA_VERSION + 1, "data": {} });
+        assert!(migrate(envelope).is_err());
+    }
+}
@@ -1,11 +1,22 @@
 use crate::{
-    configs::settings::Providers,
+    configs::{profiles::active_profile, settings::Providers},
     providers::{bom::BomProvider, open_meteo::OpenMeteoProvider, WeatherProvider},
     CONFIG,
 };
+use std::path::PathBuf;
 
-pub fn create_provider() -> anyhow::Result<Box<dyn WeatherProvider>> {
+/// Cache directory of the active profile, the base cache directory without one
+pub fn cache_path() -> PathBuf {
     let cache_path = CONFIG.misc.weather_data_cache_path.clone();
+    // Profiles usually have their own location, keep their cached forecasts apart
+    match active_profile() {
+        Some(profile) => cache_path.join("profiles").join(&profile.name),
+        None => cache_path,
+    }
+}
+
+pub fn create_provider() -> anyhow::Result<Box<dyn WeatherProvider>> {
+    let cache_path = cache_path();
 
     match CONFIG.api.provider {
         Providers::Bom => Ok(Box::new(BomProvider::new(cache_path))),
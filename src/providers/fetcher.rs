@@ -1,9 +1,23 @@
 use anyhow::Error;
+use chrono::Local;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::{fs, path::PathBuf};
+use tokio::sync::OnceCell;
 use url::Url;
 
-use crate::{errors::DashboardError, logger, CONFIG};
+use crate::{
+    configs::settings::Providers,
+    errors::DashboardError,
+    http::http_client,
+    logger,
+    providers::{
+        cache,
+        quota::{self, Quota},
+    },
+    CONFIG,
+};
 
 /// Type alias for API-specific error checking function
 pub type ErrorChecker = fn(&str) -> Result<(), DashboardError>;
@@ -16,14 +30,57 @@ pub enum FetchOutcome<T> {
     Stale { data: T, error: DashboardError },
 }
 
+/// Why a request returned no response body
+#[derive(Debug, Clone)]
+enum RequestFailure {
+    /// Use the cached data, the error is shown on the dashboard
+    Fallback(DashboardError),
+    /// The response body couldn't be read
+    Body(String),
+}
+
+type SharedResponse = Arc<OnceCell<Result<String, RequestFailure>>>;
+
+/// Responses by URL while fetches are shared, `None` otherwise
+static SHARED_RESPONSES: Mutex<Option<HashMap<Url, SharedResponse>>> = Mutex::new(None);
+
+/// Shares responses between identical requests until the returned guard is dropped.
+///
+/// Used by the batch mode: locations with the same coordinates and provider make one request
+/// instead of one each. Every fetcher still writes its own cache file.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn share_responses() -> SharedResponses {
+    *SHARED_RESPONSES.lock().unwrap_or_else(|e| e.into_inner()) = Some(HashMap::new());
+    SharedResponses
+}
+
+/// Stops sharing responses when dropped, see [`share_responses`]
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct SharedResponses;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for SharedResponses {
+    fn drop(&mut self) {
+        *SHARED_RESPONSES.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
 /// Shared fetcher for API data with caching fallback
 pub struct Fetcher {
     cache_path: PathBuf,
+    /// Provider whose daily request count the fetches are counted against
+    provider: Providers,
+    /// See [`http_client`]
+    client: reqwest::Client,
 }
 
 impl Fetcher {
-    pub fn new(cache_path: PathBuf) -> Self {
-        Self { cache_path }
+    pub fn new(cache_path: PathBuf, provider: Providers) -> Self {
+        Self {
+            cache_path,
+            provider,
+            client: http_client(),
+        }
     }
 
     /// Load cached data from file
@@ -38,8 +95,37 @@ impl Fetcher {
                 e
             )
         })?;
-        let data = serde_json::from_str(&cached).map_err(Error::msg)?;
-        Ok(data)
+        let data = serde_json::from_str(&cached)
+            .map_err(Error::msg)
+            .and_then(|cached| {
+                if cache::schema_version(&cached) < cache::CACHE_SCHEMA_VERSION {
+                    logger::detail("Migrating cached data to the current schema");
+                }
+                cache::migrate(cached)
+            })
+            .and_then(|data| serde_json::from_value(data).map_err(Error::msg));
+
+        data.map_err(|e| Self::quarantine_invalid_cache(file_path, e))
+    }
+
+    /// Moves an unreadable cache file aside so it can be inspected, and explains what happened.
+    /// The next successful fetch writes a fresh cache file.
+    fn quarantine_invalid_cache(file_path: &PathBuf, error: Error) -> Error {
+        let invalid_path = file_path.with_extension("json.invalid");
+        match fs::rename(file_path, &invalid_path) {
+            Ok(()) => logger::warning(format!(
+                "Cached data is invalid, moved to {}",
+                invalid_path.display()
+            )),
+            Err(e) => logger::warning(format!("Failed to move invalid cached data aside: {e}")),
+        }
+        anyhow::anyhow!(
+            "Cached weather data at {:?} could not be read ({}). \
+             It was probably written by a different version of the application. \
+             A fresh copy is cached after the next successful API call.",
+            file_path,
+            error
+        )
     }
 
     /// Fallback to cached data when API fails
@@ -55,13 +141,76 @@ impl Fetcher {
         })
     }
 
+    /// Requests `endpoint`, or waits for the response of the same request when responses are
+    /// shared (see [`share_responses`])
+    async fn request_body(&self, endpoint: Url) -> Result<String, RequestFailure> {
+        let shared = SHARED_RESPONSES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+            .map(|responses| responses.entry(endpoint.clone()).or_default().clone());
+
+        match shared {
+            Some(response) => response
+                .get_or_init(|| self.send_request(endpoint))
+                .await
+                .clone(),
+            None => self.send_request(endpoint).await,
+        }
+    }
+
+    async fn send_request(&self, endpoint: Url) -> Result<String, RequestFailure> {
+        // Counted in the base cache directory, so profiles share one budget per provider
+        let budget = CONFIG.api.daily_request_budget;
+        match quota::acquire(
+            &CONFIG.misc.weather_data_cache_path,
+            self.provider,
+            budget,
+            Local::now().date_naive(),
+        ) {
+            Quota::Available { count } => logger::detail(match budget {
+                Some(budget) => format!("{} requests today: {count}/{budget}", self.provider),
+                None => format!("{} requests today: {count}", self.provider),
+            }),
+            Quota::Exceeded { budget } => {
+                logger::warning(format!(
+                    "Daily request budget of {budget} for {} used up, using cached data",
+                    self.provider
+                ));
+                return Err(RequestFailure::Fallback(
+                    DashboardError::RequestBudgetExceeded {
+                        details: format!(
+                            "{budget} requests to {} today, requests resume tomorrow",
+                            self.provider
+                        ),
+                    },
+                ));
+            }
+        }
+
+        let response = match self.client.get(endpoint).send().await {
+            Ok(res) => res,
+            Err(e) => {
+                logger::warning(format!("API request failed: {}", e));
+                return Err(RequestFailure::Fallback(DashboardError::NoInternet {
+                    details: e.to_string(),
+                }));
+            }
+        };
+
+        response
+            .text()
+            .await
+            .map_err(|e| RequestFailure::Body(e.to_string()))
+    }
+
     /// Fetch data from API with caching fallback
     ///
     /// # Arguments
     /// * `endpoint` - API endpoint URL
     /// * `cache_filename` - Name of cache file (e.g., "hourly_forecast.json")
     /// * `error_checker` - Optional function to check response for API-specific errors
-    pub fn fetch_data<T>(
+    pub async fn fetch_data<T>(
         &self,
         endpoint: Url,
         cache_filename: &str,
@@ -77,21 +226,13 @@ impl Fetcher {
         }
 
         if !CONFIG.debugging.disable_weather_api_requests {
-            let client = reqwest::blocking::Client::new();
-            let response = match client.get(endpoint).send() {
-                Ok(res) => res,
-                Err(e) => {
-                    logger::warning(format!("API request failed: {}", e));
-                    return self.fallback(
-                        &file_path,
-                        DashboardError::NoInternet {
-                            details: e.to_string(),
-                        },
-                    );
+            let body = match self.request_body(endpoint).await {
+                Ok(body) => body,
+                Err(RequestFailure::Fallback(dashboard_error)) => {
+                    return self.fallback(&file_path, dashboard_error)
                 }
+                Err(RequestFailure::Body(e)) => return Err(Error::msg(e)),
             };
-
-            let body = response.text().map_err(Error::msg)?;
             logger::debug(format!("Received API response: {} bytes", body.len()));
 
             // Check for API-specific errors if checker provided
@@ -103,12 +244,94 @@ impl Fetcher {
                 }
             }
 
-            fs::write(&file_path, &body)?;
+            // Only cache responses the current models understand, so a changed API response
+            // doesn't replace the last good cache
+            let data = match serde_json::from_str(&body) {
+                Ok(data) => data,
+                Err(e) => {
+                    logger::warning(format!("Unexpected API response format: {e}"));
+                    return self.fallback(
+                        &file_path,
+                        DashboardError::ApiError {
+                            details: format!("Unexpected response format: {e}"),
+                        },
+                    );
+                }
+            };
+
+            let envelope = cache::to_envelope(serde_json::from_str(&body)?);
+            fs::write(&file_path, serde_json::to_string(&envelope)?)?;
             logger::debug(format!("Cached response to: {}", file_path.display()));
-            let data = serde_json::from_str(&body).map_err(Error::msg)?;
             Ok(FetchOutcome::Fresh(data))
         } else {
             Ok(FetchOutcome::Fresh(self.load_cached(&file_path)?))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_cached_reads_unversioned_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("forecast.json");
+        fs::write(&file_path, "[1, 2, 3]").unwrap();
+
+        let fetcher = Fetcher::new(dir.path().to_path_buf(), Providers::OpenMeteo);
+        let data: Vec<u32> = fetcher.load_cached(&file_path).unwrap();
+        assert_eq!(data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_load_cached_moves_invalid_file_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("forecast.json");
+        fs::write(
+            &file_path,
+            r#"{"schema_version": 1, "data": {"unexpected": true}}"#,
+        )
+        .unwrap();
+
+        let fetcher = Fetcher::new(dir.path().to_path_buf(), Providers::OpenMeteo);
+        let result: Result<Vec<u32>, Error> = fetcher.load_cached(&file_path);
+        assert!(result.is_err());
+        assert!(!file_path.exists());
+        assert!(dir.path().join("forecast.json.invalid").exists());
+    }
+
+    #[tokio::test]
+    async fn test_shared_responses_make_one_request_per_url() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/shared.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[4, 5]"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let endpoint: Url = format!("{}/shared.json", server.uri()).parse().unwrap();
+        let first_dir = tempfile::tempdir().unwrap();
+        let second_dir = tempfile::tempdir().unwrap();
+        let first = Fetcher::new(first_dir.path().to_path_buf(), Providers::OpenMeteo);
+        let second = Fetcher::new(second_dir.path().to_path_buf(), Providers::OpenMeteo);
+
+        let _shared = share_responses();
+        let (first, second) = tokio::join!(
+            first.fetch_data::<Vec<u32>>(endpoint.clone(), "shared.json", None),
+            second.fetch_data::<Vec<u32>>(endpoint, "shared.json", None)
+        );
+
+        for outcome in [first.unwrap(), second.unwrap()] {
+            let FetchOutcome::Fresh(data) = outcome else {
+                panic!("expected fresh data");
+            };
+            assert_eq!(data, vec![4, 5]);
+        }
+        // Each location still has its own cache
+        assert!(first_dir.path().join("shared.json").exists());
+        assert!(second_dir.path().join("shared.json").exists());
+    }
+}
@@ -1,11 +1,15 @@
 use anyhow::Error;
+use async_trait::async_trait;
 
 pub mod bom;
+pub mod cache;
 pub mod factory;
 pub mod fetcher;
 pub mod open_meteo;
+pub mod quota;
 
-use crate::domain::models::{DailyForecast, HourlyForecast};
+use crate::clock::Clock;
+use crate::domain::models::{DailyForecast, HourlyForecast, RainGaugeReading};
 use crate::errors::DashboardError;
 
 /// Result of a weather data fetch operation
@@ -42,12 +46,36 @@ impl<T> FetchResult<T> {
     }
 }
 
-pub trait WeatherProvider {
-    fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error>;
-    fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error>;
+/// A weather data source. Fetches are async so the hourly and daily forecasts
+/// (and any extra data a provider needs) can be requested concurrently. The browser's fetch
+/// futures of WebAssembly builds aren't `Send`.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait WeatherProvider: Send + Sync {
+    async fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error>;
+    async fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error>;
     fn provider_name(&self) -> &str;
     fn provider_filename_prefix(&self) -> &str;
 
+    /// Weather warnings in force for the location, shown with the diagnostics. Warnings are a
+    /// nice-to-have, so failures are logged and no warnings are returned. Warnings expired at the
+    /// `clock`'s time are left out.
+    async fn fetch_warnings(&self, _clock: &dyn Clock) -> Vec<DashboardError> {
+        Vec::new()
+    }
+
+    /// Rain gauge reading of a station near the location, made with the last hourly fetch.
+    /// Providers without observations have none.
+    fn rain_gauge_reading(&self) -> Option<RainGaugeReading> {
+        None
+    }
+
+    /// Titles of the marine wind warnings among the warnings of the last fetch, for the sea wind
+    /// warning of the marine layout. Providers without warnings have none.
+    fn marine_wind_warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Helper method to generate cache filename from provider prefix and suffix
     ///
     /// # Arguments
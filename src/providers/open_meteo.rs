@@ -1,23 +1,33 @@
 use anyhow::Error;
+use async_trait::async_trait;
 use std::path::PathBuf;
 
 use crate::{
-    apis::open_meteo::models::{OpenMeteoDailyResponse, OpenMeteoError, OpenMeteoHourlyResponse},
+    apis::open_meteo::models::{
+        OpenMeteoAirQualityResponse, OpenMeteoDailyResponse, OpenMeteoEnsembleResponse,
+        OpenMeteoError, OpenMeteoGardenResponse, OpenMeteoHourlyResponse, OpenMeteoMarineResponse,
+        OpenMeteoSnowResponse,
+    },
+    configs::settings::{MaxTableMetric, Providers},
     constants::{
-        DAILY_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX, OPEN_METEO_DAILY_ENDPOINT,
-        OPEN_METEO_HOURLY_ENDPOINT,
+        open_meteo_air_quality_endpoint, open_meteo_daily_endpoint, open_meteo_ensemble_endpoint,
+        open_meteo_garden_endpoint, open_meteo_hourly_endpoint, open_meteo_marine_endpoint,
+        open_meteo_snow_endpoint, AIR_QUALITY_CACHE_SUFFIX, DAILY_CACHE_SUFFIX,
+        ENSEMBLE_CACHE_SUFFIX, GARDEN_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX, MARINE_CACHE_SUFFIX,
+        SNOW_CACHE_SUFFIX,
     },
     domain::models::{DailyForecast, HourlyForecast},
     errors::DashboardError,
+    logger,
     providers::{
         fetcher::{FetchOutcome, Fetcher},
         FetchResult, WeatherProvider,
     },
+    CONFIG,
 };
 
 /// Open-Meteo-specific error checker
 fn check_open_meteo_error(body: &str) -> Result<(), DashboardError> {
-    use crate::logger;
     logger::debug("Checking for API errors");
     // Try to parse as error response; if it's not an error format, that's fine (return Ok)
     let api_error = match serde_json::from_str::<OpenMeteoError>(body) {
@@ -42,31 +52,168 @@ pub struct OpenMeteoProvider {
 impl OpenMeteoProvider {
     pub fn new(cache_path: PathBuf) -> Self {
         Self {
-            fetcher: Fetcher::new(cache_path),
+            fetcher: Fetcher::new(cache_path, Providers::OpenMeteo),
+        }
+    }
+
+    /// Air quality is only fetched when something on the dashboard uses it
+    fn needs_air_quality() -> bool {
+        CONFIG
+            .render_options
+            .max_table_rows
+            .as_ref()
+            .contains(&MaxTableMetric::Aqi)
+            || CONFIG.render_options.smoke_pm2_5_threshold.is_some()
+    }
+
+    /// Fetches AQI and PM2.5 when the dashboard uses them. Air quality is a nice-to-have,
+    /// so failures are logged and the forecasts are left without AQI.
+    async fn fetch_air_quality(&self) -> Option<OpenMeteoAirQualityResponse> {
+        if !Self::needs_air_quality() {
+            return None;
+        }
+        match self
+            .fetcher
+            .fetch_data::<OpenMeteoAirQualityResponse>(
+                open_meteo_air_quality_endpoint(),
+                &self.generate_cache_filename(AIR_QUALITY_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Air quality data unavailable: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Fetches the snow depth and snowfall for the snow report, whichever provider renders the
+    /// forecast. Failures are logged and the report is left out.
+    pub async fn fetch_snow(&self) -> Option<OpenMeteoSnowResponse> {
+        match self
+            .fetcher
+            .fetch_data::<OpenMeteoSnowResponse>(
+                open_meteo_snow_endpoint(),
+                &self.generate_cache_filename(SNOW_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Snow data unavailable: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Fetches the waves and swell for the marine layout from the Open-Meteo Marine API,
+    /// whichever provider renders the forecast. Failures are logged and the panel is left out.
+    pub async fn fetch_marine(&self) -> Option<OpenMeteoMarineResponse> {
+        match self
+            .fetcher
+            .fetch_data::<OpenMeteoMarineResponse>(
+                open_meteo_marine_endpoint(),
+                &self.generate_cache_filename(MARINE_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Marine data unavailable: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Fetches the soil moisture and evapotranspiration for the garden indicator, whichever
+    /// provider renders the forecast. Failures are logged and the indicator is left out.
+    pub async fn fetch_garden(&self) -> Option<OpenMeteoGardenResponse> {
+        match self
+            .fetcher
+            .fetch_data::<OpenMeteoGardenResponse>(
+                open_meteo_garden_endpoint(),
+                &self.generate_cache_filename(GARDEN_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Garden data unavailable: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Fetches the ensemble members when the temperature spread band is enabled. Like air
+    /// quality, the band is optional, so failures are logged and the band is left out.
+    async fn fetch_ensemble(&self) -> Option<OpenMeteoEnsembleResponse> {
+        if !CONFIG.render_options.show_temperature_spread {
+            return None;
+        }
+        match self
+            .fetcher
+            .fetch_data::<OpenMeteoEnsembleResponse>(
+                open_meteo_ensemble_endpoint(),
+                &self.generate_cache_filename(ENSEMBLE_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await
+        {
+            Ok(FetchOutcome::Fresh(data)) | Ok(FetchOutcome::Stale { data, .. }) => Some(data),
+            Err(e) => {
+                logger::warning(format!("Ensemble data unavailable: {e}"));
+                None
+            }
         }
     }
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl WeatherProvider for OpenMeteoProvider {
-    fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error> {
-        let result = match self.fetcher.fetch_data::<OpenMeteoHourlyResponse>(
-            OPEN_METEO_HOURLY_ENDPOINT.clone(),
-            &self.generate_cache_filename(HOURLY_CACHE_SUFFIX),
-            Some(check_open_meteo_error),
-        )? {
+    async fn fetch_hourly_forecast(&self) -> Result<FetchResult<Vec<HourlyForecast>>, Error> {
+        // The forecast, air quality and ensemble APIs are independent, request them together
+        let cache_filename = self.generate_cache_filename(HOURLY_CACHE_SUFFIX);
+        let (forecast, air_quality, ensemble) = tokio::join!(
+            self.fetcher.fetch_data::<OpenMeteoHourlyResponse>(
+                open_meteo_hourly_endpoint(),
+                &cache_filename,
+                Some(check_open_meteo_error),
+            ),
+            self.fetch_air_quality(),
+            self.fetch_ensemble(),
+        );
+
+        let mut result: FetchResult<Vec<HourlyForecast>> = match forecast? {
             FetchOutcome::Fresh(data) => FetchResult::fresh(data.into()),
             FetchOutcome::Stale { data, error } => FetchResult::stale(data.into(), error),
         };
 
+        if let Some(air_quality) = air_quality {
+            air_quality.merge_into(&mut result.data);
+        }
+        if let Some(ensemble) = ensemble {
+            ensemble.merge_into(&mut result.data, CONFIG.render_options.temp_unit);
+        }
+
         Ok(result)
     }
 
-    fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error> {
-        let result = match self.fetcher.fetch_data::<OpenMeteoDailyResponse>(
-            OPEN_METEO_DAILY_ENDPOINT.clone(),
-            &self.generate_cache_filename(DAILY_CACHE_SUFFIX),
-            Some(check_open_meteo_error),
-        )? {
+    async fn fetch_daily_forecast(&self) -> Result<FetchResult<Vec<DailyForecast>>, Error> {
+        let result = match self
+            .fetcher
+            .fetch_data::<OpenMeteoDailyResponse>(
+                open_meteo_daily_endpoint(),
+                &self.generate_cache_filename(DAILY_CACHE_SUFFIX),
+                Some(check_open_meteo_error),
+            )
+            .await?
+        {
             FetchOutcome::Fresh(data) => FetchResult::fresh(data.into()),
             FetchOutcome::Stale { data, error } => FetchResult::stale(data.into(), error),
         };
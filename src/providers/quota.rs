@@ -0,0 +1,123 @@
+//! Daily request counts per weather provider
+//!
+//! Counts are stored in `request_counts.json` in the cache directory and start over each local day.
+//! With `api.daily_request_budget` set, a provider that used up its budget for the day is served
+//! from the cache, so a misconfigured cron job can't exhaust a free tier.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use crate::configs::settings::Providers;
+use crate::logger;
+
+const REQUEST_COUNTS_FILE: &str = "request_counts.json";
+
+/// Serializes updates of the counts file, the forecasts are fetched concurrently
+static COUNTS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RequestCounts {
+    date: Option<NaiveDate>,
+    counts: BTreeMap<String, u32>,
+}
+
+/// Outcome of asking for one more request to a provider
+#[derive(Debug, PartialEq, Eq)]
+pub enum Quota {
+    /// The request was counted, `count` includes it
+    Available { count: u32 },
+    /// The daily budget is used up, the request should not be made
+    Exceeded { budget: u32 },
+}
+
+/// Counts a request to `provider` for `today`, unless its daily `budget` is used up
+pub fn acquire(
+    cache_dir: &Path,
+    provider: Providers,
+    budget: Option<u32>,
+    today: NaiveDate,
+) -> Quota {
+    let _guard = COUNTS_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let path = cache_dir.join(REQUEST_COUNTS_FILE);
+
+    let mut counts: RequestCounts = fs::read_to_string(&path)
+        .ok()
+        .and_then(|counts| serde_json::from_str(&counts).ok())
+        .unwrap_or_default();
+    if counts.date != Some(today) {
+        counts = RequestCounts {
+            date: Some(today),
+            counts: BTreeMap::new(),
+        };
+    }
+
+    let count = counts.counts.entry(provider.to_string()).or_default();
+    if let Some(budget) = budget {
+        if *count >= budget {
+            return Quota::Exceeded { budget };
+        }
+    }
+    *count += 1;
+    let count = *count;
+
+    let written = fs::create_dir_all(cache_dir).and_then(|()| {
+        fs::write(
+            &path,
+            serde_json::to_string(&counts).expect("request counts always serialize"),
+        )
+    });
+    if let Err(e) = written {
+        logger::warning(format!("Failed to save request counts: {e}"));
+    }
+
+    Quota::Available { count }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 10, day).unwrap()
+    }
+
+    #[test]
+    fn test_requests_are_counted_per_provider() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            acquire(dir.path(), Providers::OpenMeteo, None, day(9)),
+            Quota::Available { count: 1 }
+        );
+        assert_eq!(
+            acquire(dir.path(), Providers::OpenMeteo, None, day(9)),
+            Quota::Available { count: 2 }
+        );
+        assert_eq!(
+            acquire(dir.path(), Providers::Bom, None, day(9)),
+            Quota::Available { count: 1 }
+        );
+    }
+
+    #[test]
+    fn test_budget_is_enforced_until_the_next_day() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            acquire(dir.path(), Providers::Bom, Some(1), day(9)),
+            Quota::Available { count: 1 }
+        );
+        assert_eq!(
+            acquire(dir.path(), Providers::Bom, Some(1), day(9)),
+            Quota::Exceeded { budget: 1 }
+        );
+        assert_eq!(
+            acquire(dir.path(), Providers::Bom, Some(1), day(10)),
+            Quota::Available { count: 1 }
+        );
+    }
+}
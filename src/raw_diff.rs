@@ -0,0 +1,397 @@
+//! Partial-region updates of the raw output
+//!
+//! When the previous frame sent to a display is known, only the rectangles that changed since
+//! then need to be transferred. Panels and drivers that support partial refresh can redraw just
+//! those regions, the others still save on transfer size by patching their copy of the frame.
+//!
+//! A diff is little-endian binary:
+//!
+//! | Bytes | Field                                                             |
+//! |-------|-------------------------------------------------------------------|
+//! | 4     | Magic `RDIF`                                                      |
+//! | 1     | Format version, currently 1                                       |
+//! | 1     | Flags, bit 0 set when the diff holds the whole frame, bit 1 set   |
+//! |       | for IT8951 greyscale data instead of ACeP palette indices         |
+//! | 2     | Frame width in pixels                                             |
+//! | 2     | Frame height in pixels                                            |
+//! | 2     | Number of rectangles                                              |
+//!
+//! followed by each rectangle: its x, y, width and height in pixels (2 bytes each) and its rows
+//! of raw data, packed like the raw output of the panel with 2 pixels per byte. Rectangles start
+//! at an even x and have an even width, except at the right edge of an odd-width frame, so their
+//! bytes are copied straight from the rows of the raw frame.
+
+use anyhow::{Error, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use crate::display_options::DisplayDriver;
+use crate::logger;
+
+const MAGIC: &[u8; 4] = b"RDIF";
+const FORMAT_VERSION: u8 = 1;
+const FLAG_FULL_FRAME: u8 = 1;
+const FLAG_IT8951: u8 = 2;
+const HEADER_SIZE: usize = 12;
+const RECT_HEADER_SIZE: usize = 8;
+
+/// Unchanged rows between two changed ones that are sent anyway to keep them in one rectangle,
+/// a rectangle header costs as much as a few bytes of row data
+const MERGE_GAP_ROWS: usize = 4;
+
+const LAST_FRAME_FILE: &str = "last_frame.raw";
+
+/// Serializes updates of the last frame file, displays may poll concurrently
+static FRAME_LOCK: Mutex<()> = Mutex::new(());
+
+/// A frame of the raw output with its size and the panel family it's packed for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawFrame {
+    pub width: u16,
+    pub height: u16,
+    pub driver: DisplayDriver,
+    pub data: Vec<u8>,
+}
+
+impl RawFrame {
+    /// Checks that `data` is a raw frame of `width` x `height` pixels for `driver`'s panel
+    pub fn new(width: u32, height: u32, driver: DisplayDriver, data: Vec<u8>) -> Result<Self> {
+        let (Ok(frame_width), Ok(frame_height)) = (u16::try_from(width), u16::try_from(height))
+        else {
+            return Err(Error::msg(format!(
+                "A {width}x{height} frame is too large for the raw diff format"
+            )));
+        };
+        if data.len() as u64 != driver.raw_size(width, height) {
+            return Err(Error::msg(format!(
+                "{} bytes are not a raw {width}x{height} {driver} frame",
+                data.len()
+            )));
+        }
+        Ok(Self {
+            width: frame_width,
+            height: frame_height,
+            driver,
+            data,
+        })
+    }
+
+    /// Converts a PNG image to a raw frame for `driver`'s panel
+    pub fn from_png_bytes(png_data: &[u8], driver: DisplayDriver) -> Result<Self> {
+        let rgb_img = image::load_from_memory(png_data)
+            .map_err(|e| Error::msg(format!("Failed to load PNG from memory: {e}")))?
+            .to_rgb8();
+        let (width, height) = rgb_img.dimensions();
+        Self::new(width, height, driver, driver.to_raw(&rgb_img))
+    }
+
+    fn row_size(&self) -> usize {
+        self.driver.raw_row_size(u32::from(self.width))
+    }
+
+    /// Bytes of a row that hold pixels, IT8951 rows may end in padding
+    fn pixel_bytes(&self) -> usize {
+        usize::from(self.width).div_ceil(2)
+    }
+
+    /// FNV-1a checksum of the size, panel family and data, identifies the frame a display shows
+    pub fn checksum(&self) -> u32 {
+        let mut hash: u32 = 0x811c_9dc5;
+        for byte in self
+            .width
+            .to_le_bytes()
+            .iter()
+            .chain(&self.height.to_le_bytes())
+            .chain(&[driver_flag(self.driver)])
+            .chain(&self.data)
+        {
+            hash ^= u32::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        hash
+    }
+
+    /// The checksum as sent in the `X-Frame-Checksum` header
+    pub fn checksum_hex(&self) -> String {
+        format!("{:08x}", self.checksum())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5 + self.data.len());
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.push(driver_flag(self.driver));
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let [w0, w1, h0, h1, flags, data @ ..] = bytes else {
+            return Err(Error::msg("Frame is too short"));
+        };
+        Self::new(
+            u32::from(u16::from_le_bytes([*w0, *w1])),
+            u32::from(u16::from_le_bytes([*h0, *h1])),
+            flag_driver(*flags),
+            data.to_vec(),
+        )
+    }
+}
+
+fn driver_flag(driver: DisplayDriver) -> u8 {
+    match driver {
+        DisplayDriver::Acep => 0,
+        DisplayDriver::It8951 => FLAG_IT8951,
+    }
+}
+
+fn flag_driver(flags: u8) -> DisplayDriver {
+    if flags & FLAG_IT8951 != 0 {
+        DisplayDriver::It8951
+    } else {
+        DisplayDriver::Acep
+    }
+}
+
+/// A changed rectangle and its raw data, row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub data: Vec<u8>,
+}
+
+impl DiffRect {
+    fn row_size(&self) -> usize {
+        usize::from(self.width).div_ceil(2)
+    }
+}
+
+/// The rectangles that changed between two frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDiff {
+    pub width: u16,
+    pub height: u16,
+    pub driver: DisplayDriver,
+    /// The diff holds the whole frame, the display's copy is not needed
+    pub full_frame: bool,
+    pub rects: Vec<DiffRect>,
+}
+
+/// Rectangle covering bytes `first..=last` of rows `top..=bottom` of `frame`
+fn rect_of(frame: &RawFrame, top: usize, bottom: usize, first: usize, last: usize) -> DiffRect {
+    let row_size = frame.row_size();
+    let x = first * 2;
+    let width = ((last + 1) * 2).min(usize::from(frame.width)) - x;
+    let data = (top..=bottom)
+        .flat_map(|row| &frame.data[row * row_size + first..=row * row_size + last])
+        .copied()
+        .collect();
+    DiffRect {
+        x: x as u16,
+        y: top as u16,
+        width: width as u16,
+        height: (bottom - top + 1) as u16,
+        data,
+    }
+}
+
+/// Diffs `current` against the `previous` frame the display shows. Runs of changed rows become
+/// rectangles spanning the changed columns of the run. Without a previous frame of the same
+/// size the diff holds the whole frame.
+pub fn diff_frames(previous: Option<&RawFrame>, current: &RawFrame) -> RawDiff {
+    let full_frame = |current: &RawFrame| RawDiff {
+        width: current.width,
+        height: current.height,
+        driver: current.driver,
+        full_frame: true,
+        rects: if current.data.is_empty() {
+            Vec::new()
+        } else {
+            vec![rect_of(
+                current,
+                0,
+                usize::from(current.height) - 1,
+                0,
+                current.pixel_bytes() - 1,
+            )]
+        },
+    };
+    let Some(previous) = previous.filter(|previous| {
+        (previous.width, previous.height, previous.driver)
+            == (current.width, current.height, current.driver)
+    }) else {
+        return full_frame(current);
+    };
+
+    let row_size = current.row_size();
+    let pixel_bytes = current.pixel_bytes();
+    let changed_columns = |row: usize| {
+        let range = row * row_size..row * row_size + pixel_bytes;
+        let pairs = || {
+            previous.data[range.clone()]
+                .iter()
+                .zip(&current.data[range.clone()])
+        };
+        let first = pairs().position(|(old, new)| old != new)?;
+        let last = pixel_bytes - 1 - pairs().rev().position(|(old, new)| old != new)?;
+        Some((first, last))
+    };
+
+    // (top, bottom, first column, last column) of each run of changed rows
+    let mut runs: Vec<(usize, usize, usize, usize)> = Vec::new();
+    for row in 0..usize::from(current.height) {
+        let Some((first, last)) = changed_columns(row) else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some(run) if row - run.1 <= MERGE_GAP_ROWS + 1 => {
+                *run = (run.0, row, run.2.min(first), run.3.max(last));
+            }
+            _ => runs.push((row, row, first, last)),
+        }
+    }
+
+    RawDiff {
+        width: current.width,
+        height: current.height,
+        driver: current.driver,
+        full_frame: false,
+        rects: runs
+            .into_iter()
+            .map(|(top, bottom, first, last)| rect_of(current, top, bottom, first, last))
+            .collect(),
+    }
+}
+
+impl RawDiff {
+    /// Encodes the diff in the format described in the module docs
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = HEADER_SIZE
+            + self
+                .rects
+                .iter()
+                .map(|rect| RECT_HEADER_SIZE + rect.data.len())
+                .sum::<usize>();
+        let mut bytes = Vec::with_capacity(size);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        let full_frame = if self.full_frame { FLAG_FULL_FRAME } else { 0 };
+        bytes.push(full_frame | driver_flag(self.driver));
+        for value in [self.width, self.height, self.rects.len() as u16] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for rect in &self.rects {
+            for value in [rect.x, rect.y, rect.width, rect.height] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes.extend_from_slice(&rect.data);
+        }
+        bytes
+    }
+
+    /// Decodes a diff, checking that every rectangle lies within the frame
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let read_u16 = |offset: usize| -> Result<u16> {
+            bytes
+                .get(offset..offset + 2)
+                .map(|value| u16::from_le_bytes([value[0], value[1]]))
+                .ok_or_else(|| Error::msg("Raw diff is truncated"))
+        };
+        if bytes.get(..4) != Some(MAGIC.as_slice()) {
+            return Err(Error::msg("Not a raw diff"));
+        }
+        if bytes.get(4) != Some(&FORMAT_VERSION) {
+            return Err(Error::msg("Unsupported raw diff version"));
+        }
+        let flags = bytes.get(5).copied().unwrap_or_default();
+        let full_frame = flags & FLAG_FULL_FRAME != 0;
+        let (width, height, count) = (read_u16(6)?, read_u16(8)?, read_u16(10)?);
+
+        let mut rects = Vec::with_capacity(usize::from(count));
+        let mut offset = HEADER_SIZE;
+        for _ in 0..count {
+            let (x, y) = (read_u16(offset)?, read_u16(offset + 2)?);
+            let (rect_width, rect_height) = (read_u16(offset + 4)?, read_u16(offset + 6)?);
+            if x % 2 != 0
+                || u32::from(x) + u32::from(rect_width) > u32::from(width)
+                || u32::from(y) + u32::from(rect_height) > u32::from(height)
+            {
+                return Err(Error::msg(format!(
+                    "Rectangle {rect_width}x{rect_height} at {x},{y} is outside the {width}x{height} frame"
+                )));
+            }
+            offset += RECT_HEADER_SIZE;
+            let size = usize::from(rect_width).div_ceil(2) * usize::from(rect_height);
+            let data = bytes
+                .get(offset..offset + size)
+                .ok_or_else(|| Error::msg("Raw diff is truncated"))?;
+            offset += size;
+            rects.push(DiffRect {
+                x,
+                y,
+                width: rect_width,
+                height: rect_height,
+                data: data.to_vec(),
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            driver: flag_driver(flags),
+            full_frame,
+            rects,
+        })
+    }
+
+    /// Patches `frame`, the frame the diff was made against, into the current frame
+    pub fn apply(&self, frame: &mut RawFrame) -> Result<()> {
+        if (frame.width, frame.height, frame.driver) != (self.width, self.height, self.driver) {
+            return Err(Error::msg(format!(
+                "The diff is for a {}x{} {} frame, not {}x{} {}",
+                self.width, self.height, self.driver, frame.width, frame.height, frame.driver
+            )));
+        }
+        let row_size = frame.row_size();
+        for rect in &self.rects {
+            let rect_row_size = rect.row_size();
+            if rect.x % 2 != 0
+                || u32::from(rect.x) + u32::from(rect.width) > u32::from(self.width)
+                || u32::from(rect.y) + u32::from(rect.height) > u32::from(self.height)
+                || rect.data.len() != rect_row_size * usize::from(rect.height)
+            {
+                return Err(Error::msg(format!(
+                    "Rectangle {}x{} at {},{} doesn't fit the frame",
+                    rect.width, rect.height, rect.x, rect.y
+                )));
+            }
+            let first = usize::from(rect.x) / 2;
+            for (index, row) in rect.data.chunks(rect_row_size).enumerate() {
+                let start = (usize::from(rect.y) + index) * row_size + first;
+                frame.data[start..start + rect_row_size].copy_from_slice(row);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stores `frame` as the last frame sent to the display and returns the one stored before it.
+///
+/// The frame is kept in `last_frame.raw` in the cache directory, so diffs survive server restarts.
+pub fn swap_last_frame(cache_dir: &Path, frame: &RawFrame) -> Option<RawFrame> {
+    let _guard = FRAME_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let path = cache_dir.join(LAST_FRAME_FILE);
+
+    let previous = fs::read(&path)
+        .ok()
+        .and_then(|bytes| RawFrame::from_bytes(&bytes).ok());
+    let written = fs::create_dir_all(cache_dir).and_then(|()| fs::write(&path, frame.to_bytes()));
+    if let Err(e) = written {
+        logger::warning(format!("Failed to save the last frame: {e}"));
+    }
+    previous
+}
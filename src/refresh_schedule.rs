@@ -0,0 +1,251 @@
+//! Recommended refresh schedule for battery displays
+//!
+//! A display that wakes on a fixed timer spends as much battery on a calm night as on a front
+//! coming through. The schedule splits the next 24 forecast hours into slots with a recommended
+//! refresh interval: short around fast changes, a pressure swing of a front or rain starting or
+//! stopping, long at night and the active hours interval otherwise. Outside the active hours and
+//! during the quiet hours the display sleeps until they end, as with `X-Next-Delay`. Displays
+//! fetch it from `/api/devices/<id>/config`.
+
+use chrono::{DateTime, Duration, Local, Timelike, Utc};
+use serde::Serialize;
+
+use crate::configs::settings::{DashboardSettings, QuietHours, RefreshHint};
+use crate::domain::models::HourlyForecast;
+
+/// Forecast hours the schedule covers
+pub const SCHEDULE_HOURS: usize = 24;
+
+/// Hours over which a pressure change counts towards a front, as in the 3-hour pressure tendency
+const PRESSURE_TENDENCY_HOURS: usize = 3;
+
+/// Why a slot has its interval
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshReason {
+    /// Nobody looks at the display in the dark
+    Night,
+    /// Pressure changes by `pressure_change_hpa` or more within 3 hours around the hour
+    Front,
+    /// Rain starts, stops or changes by `rain_change_mm` or more from one hour to the next
+    RainChange,
+    /// Nothing changes fast
+    Steady,
+    /// The display runs low on battery, so it refreshes at most every `low_battery_interval_seconds`
+    LowBattery,
+    /// The quiet hours, the display sleeps until they end
+    QuietHours,
+    /// Outside the active hours, the display sleeps until they start
+    Inactive,
+}
+
+impl RefreshReason {
+    /// Slots the display sleeps through, their interval is the length of the slot
+    fn sleeps(self) -> bool {
+        matches!(self, Self::QuietHours | Self::Inactive)
+    }
+}
+
+/// Local hours of the day the display refreshes in, as for `X-Next-Delay`
+#[derive(Debug, Clone, Copy)]
+pub struct AwakeHours {
+    /// First hour of the active hours
+    pub active_start: u8,
+    /// First hour after the active hours
+    pub active_end: u8,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl AwakeHours {
+    /// The active hours of the web server and the quiet hours of `settings`
+    pub fn new(settings: &DashboardSettings) -> Self {
+        Self {
+            active_start: settings.web_server.active_hours_start,
+            active_end: settings.web_server.active_hours_end,
+            quiet_hours: settings.schedule.quiet_hours,
+        }
+    }
+
+    fn sleep_reason(&self, time: DateTime<Utc>) -> Option<RefreshReason> {
+        let time = time.with_timezone(&Local);
+        if self
+            .quiet_hours
+            .is_some_and(|quiet_hours| quiet_hours.contains(time))
+        {
+            return Some(RefreshReason::QuietHours);
+        }
+        let hour = time.hour();
+        let active = u32::from(self.active_start)..u32::from(self.active_end);
+        (!active.contains(&hour)).then_some(RefreshReason::Inactive)
+    }
+}
+
+/// Consecutive forecast hours with the same recommended interval
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RefreshSlot {
+    pub from: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub interval_seconds: u32,
+    pub reason: RefreshReason,
+}
+
+/// Recommended refreshes of a display over the next forecast hours
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RefreshSchedule {
+    /// Seconds until the next refresh: the interval of the current slot, shortened to wake up at
+    /// the start of a later slot with a shorter interval
+    pub next_delay_seconds: u32,
+    pub slots: Vec<RefreshSlot>,
+}
+
+impl RefreshSchedule {
+    /// The schedule of a display low on battery: slots with a shorter interval get
+    /// `low_battery_interval`, and the next refresh is at least that far away. The display
+    /// already sleeps through the quiet and inactive hours.
+    pub fn low_battery(self, low_battery_interval: u32) -> Self {
+        let mut slots: Vec<RefreshSlot> = Vec::new();
+        for slot in self.slots {
+            let slot = if !slot.reason.sleeps() && slot.interval_seconds < low_battery_interval {
+                RefreshSlot {
+                    interval_seconds: low_battery_interval,
+                    reason: RefreshReason::LowBattery,
+                    ..slot
+                }
+            } else {
+                slot
+            };
+            match slots.last_mut() {
+                Some(last) if last.reason == slot.reason && last.until == slot.from => {
+                    last.until = slot.until
+                }
+                _ => slots.push(slot),
+            }
+        }
+
+        Self {
+            next_delay_seconds: self.next_delay_seconds.max(low_battery_interval),
+            slots,
+        }
+    }
+}
+
+/// Settings a display fetches from `/api/devices/<id>/config`
+#[cfg_attr(feature = "web", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceConfig {
+    /// Battery level (percent) below which the display gets the low power layout, 0 when disabled
+    pub low_battery_threshold: u8,
+    pub refresh_schedule: RefreshSchedule,
+}
+
+/// Schedule for the forecast hours from `now`. Hours the forecast doesn't have get no slot, and
+/// without any hours the next delay is `steady_interval`.
+pub fn refresh_schedule(
+    hourly: &[HourlyForecast],
+    hint: &RefreshHint,
+    steady_interval: u32,
+    awake_hours: &AwakeHours,
+    now: DateTime<Utc>,
+) -> RefreshSchedule {
+    let mut slots: Vec<RefreshSlot> = Vec::new();
+    let upcoming = hourly
+        .iter()
+        .enumerate()
+        .filter(|(_, hour)| hour.time + Duration::hours(1) > now)
+        .take(SCHEDULE_HOURS);
+
+    for (index, hour) in upcoming {
+        let reason = awake_hours
+            .sleep_reason(hour.time)
+            .unwrap_or_else(|| hour_reason(hourly, index, hint));
+        let interval_seconds = match reason {
+            RefreshReason::Night => hint.night_interval_seconds,
+            RefreshReason::Front | RefreshReason::RainChange => hint.fast_interval_seconds,
+            // Only set afterwards by `RefreshSchedule::low_battery`
+            RefreshReason::Steady | RefreshReason::LowBattery => steady_interval,
+            // The length of the slot, set below
+            RefreshReason::QuietHours | RefreshReason::Inactive => 0,
+        };
+        let until = hour.time + Duration::hours(1);
+
+        match slots.last_mut() {
+            Some(slot) if slot.reason == reason && slot.until == hour.time => slot.until = until,
+            _ => slots.push(RefreshSlot {
+                from: hour.time,
+                until,
+                interval_seconds,
+                reason,
+            }),
+        }
+    }
+    for slot in slots.iter_mut().filter(|slot| slot.reason.sleeps()) {
+        slot.interval_seconds = u32::try_from((slot.until - slot.from).num_seconds()).unwrap_or(0);
+    }
+
+    RefreshSchedule {
+        next_delay_seconds: next_delay(&slots, steady_interval, now),
+        slots,
+    }
+}
+
+fn hour_reason(hourly: &[HourlyForecast], index: usize, hint: &RefreshHint) -> RefreshReason {
+    let hour = &hourly[index];
+    if hour.is_night {
+        return RefreshReason::Night;
+    }
+
+    // Any 3-hour window over the hour with the pressure change of a front
+    let front = (index.saturating_sub(PRESSURE_TENDENCY_HOURS)..=index).any(|window_start| {
+        let pressure = |offset: usize| {
+            hourly
+                .get(window_start + offset)
+                .and_then(|hour| hour.pressure)
+        };
+        pressure(0)
+            .zip(pressure(PRESSURE_TENDENCY_HOURS))
+            .is_some_and(|(first, last)| (last - first).abs() >= hint.pressure_change_hpa)
+    });
+    if front {
+        return RefreshReason::Front;
+    }
+
+    let rain = hour.precipitation.calculate_median();
+    let rain_change = |other: Option<&HourlyForecast>| {
+        other.map_or(0.0, |other| {
+            (other.precipitation.calculate_median() - rain).abs()
+        })
+    };
+    let previous = index
+        .checked_sub(1)
+        .and_then(|previous| hourly.get(previous));
+    let next = hourly.get(index + 1);
+    if rain_change(previous).max(rain_change(next)) >= hint.rain_change_mm {
+        return RefreshReason::RainChange;
+    }
+
+    RefreshReason::Steady
+}
+
+fn next_delay(slots: &[RefreshSlot], steady_interval: u32, now: DateTime<Utc>) -> u32 {
+    let Some((current, later)) = slots.split_first() else {
+        return steady_interval;
+    };
+    if current.reason.sleeps() {
+        return u32::try_from((current.until - now).num_seconds()).unwrap_or(0);
+    }
+    let mut delay = current.interval_seconds;
+    for slot in later {
+        let until_start = u32::try_from((slot.from - now).num_seconds()).unwrap_or(0);
+        if until_start >= delay {
+            break;
+        }
+        if slot.interval_seconds < delay {
+            delay = until_start;
+            break;
+        }
+    }
+    delay
+}
@@ -0,0 +1,203 @@
+//! The dashboard render without files or network
+//!
+//! Turning forecasts into the dashboard SVG only needs the template, the forecasts and a clock:
+//! the theme colours, the daily tiles, the hourly graph, the diagnostics, the clock face and the
+//! panels of the context. Everything here works on strings and values, so it can run where there
+//! is no filesystem, e.g. a template playground rendering pasted provider responses. What needs
+//! the cache directory or the network, like the rain log, the forecast changes, the quote footer
+//! or the image panel, is added by [`weather_dashboard`](crate::weather_dashboard) around it.
+//!
+//! The context, the theme, the clock face and the sanity checks use the settings passed in, and
+//! nothing is logged, the caller decides what to report. The icon paths, the time filter of the
+//! template and the number format of the filters still read [`CONFIG`](crate::CONFIG). The crate
+//! builds for `wasm32-unknown-unknown` with the cron runs and the self-update left out, CI checks
+//! it.
+
+use anyhow::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use crate::clock::Clock;
+use crate::configs::settings::{DashboardSettings, Providers};
+use crate::constants::{DAILY_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX};
+use crate::dashboard::clock_face::clock_face;
+use crate::dashboard::context::{Context, ContextBuilder};
+use crate::dashboard::panel::{composite_panels, Panel};
+use crate::dashboard::template_filters::register_filters;
+use crate::dashboard::theme::{themed_colours, ThemeWeather};
+use crate::domain::daylight::{apply_sun_times, fill_missing_sun_times};
+use crate::domain::models::{DailyForecast, HourlyForecast};
+use crate::domain::sanity::{anomaly_warning, drop_daily_anomalies, drop_hourly_anomalies};
+use crate::errors::{DashboardError, WeatherEpdError};
+use crate::fixtures::{normalize, NormalizedForecast};
+use crate::utils;
+
+/// Forecasts and diagnostics a dashboard is rendered from
+#[derive(Debug, Clone, Default)]
+pub struct ForecastInput {
+    pub daily: Vec<DailyForecast>,
+    pub hourly: Vec<HourlyForecast>,
+    pub warnings: Vec<DashboardError>,
+}
+
+/// A recorded response normalized to the other kind of forecast than it was passed as
+#[derive(Debug, thiserror::Error)]
+#[error("expected {expected} forecasts, the response has {found} forecasts")]
+pub struct UnexpectedForecastKind {
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+
+impl ForecastInput {
+    /// Forecasts of recorded `provider` responses, e.g. the fixtures in `tests/fixtures/`,
+    /// checked like the forecasts of a live run
+    pub fn from_responses(
+        settings: &DashboardSettings,
+        provider: Providers,
+        daily_response: &str,
+        hourly_response: &str,
+    ) -> Result<Self, WeatherEpdError> {
+        let daily = match normalize(provider, DAILY_CACHE_SUFFIX, daily_response)
+            .map_err(WeatherEpdError::provider)?
+        {
+            NormalizedForecast::Daily(daily) => daily,
+            other => return Err(unexpected_kind("daily", &other)),
+        };
+        let hourly = match normalize(provider, HOURLY_CACHE_SUFFIX, hourly_response)
+            .map_err(WeatherEpdError::provider)?
+        {
+            NormalizedForecast::Hourly(hourly) => hourly,
+            other => return Err(unexpected_kind("hourly", &other)),
+        };
+        let (input, _) = Self {
+            daily,
+            hourly,
+            warnings: Vec::new(),
+        }
+        .checked(settings);
+        Ok(input)
+    }
+
+    /// The forecasts without implausible values, with the sun times filled in for the location
+    /// of `settings` and applied to the hours. The dropped values are returned for the caller to
+    /// log, the diagnostic naming them is added to the warnings.
+    pub fn checked(mut self, settings: &DashboardSettings) -> (Self, Vec<String>) {
+        let anomalies: Vec<String> = drop_daily_anomalies(&mut self.daily)
+            .into_iter()
+            .chain(drop_hourly_anomalies(&mut self.hourly))
+            .collect();
+        self.warnings.extend(anomaly_warning(&anomalies));
+        fill_missing_sun_times(
+            &mut self.daily,
+            settings.api.latitude.into_inner(),
+            settings.api.longitude.into_inner(),
+        );
+        apply_sun_times(&mut self.hourly, &self.daily);
+        (self, anomalies)
+    }
+}
+
+fn unexpected_kind(expected: &'static str, found: &NormalizedForecast) -> WeatherEpdError {
+    WeatherEpdError::provider(UnexpectedForecastKind {
+        expected,
+        found: found.kind(),
+    })
+}
+
+/// Adds `input` to `context_builder`: the themed colours of `settings`, the daily tiles, the
+/// hourly graph and the diagnostics
+pub fn add_forecast(
+    context_builder: &mut ContextBuilder,
+    input: ForecastInput,
+    settings: &DashboardSettings,
+    clock: &dyn Clock,
+) {
+    if !settings.theme_rules.is_empty() {
+        let weather = ThemeWeather::at(
+            &input.daily,
+            &input.hourly,
+            clock.now_utc(),
+            clock.now_local().date_naive(),
+        );
+        context_builder.with_colours(themed_colours(
+            &settings.colours,
+            &settings.theme_rules,
+            &weather,
+        ));
+    }
+    context_builder.with_daily_forecast_data(input.daily, clock);
+    context_builder.with_hourly_forecast_data(input.hourly, clock);
+    for warning in input.warnings {
+        context_builder.with_warning(warning);
+    }
+}
+
+/// Diagnostics of a dashboard rendered from `input`: the warnings and what building the context
+/// finds, e.g. missing days. The JSON endpoints report these, so they match the rendered frame.
+pub fn forecast_diagnostics(
+    input: ForecastInput,
+    settings: &DashboardSettings,
+    clock: &dyn Clock,
+) -> Vec<DashboardError> {
+    let mut context_builder = ContextBuilder::with_settings(settings);
+    add_forecast(&mut context_builder, input, settings, clock);
+    context_builder.diagnostics().to_vec()
+}
+
+/// The clock face of `settings` and the panels of the context, in the order they're composited
+pub fn dashboard_panels(
+    context_builder: &ContextBuilder,
+    settings: &DashboardSettings,
+    clock: &dyn Clock,
+) -> Vec<Panel> {
+    clock_face(
+        &settings.clock_face,
+        clock.now_local().time(),
+        context_builder.colours(),
+    )
+    .into_iter()
+    .chain(context_builder.panels().iter().cloned())
+    .collect()
+}
+
+/// Renders `template` with `context` and composites `panels` over it, with the numbers rounded
+/// when `settings` asks for deterministic output
+pub fn render_template(
+    template: &str,
+    context: &Context,
+    panels: &[Panel],
+    settings: &DashboardSettings,
+) -> Result<String, Error> {
+    let mut tt = TinyTemplate::new();
+    let tt_name = "dashboard";
+
+    tt.add_template(tt_name, template)?;
+    tt.set_default_formatter(&format_unescaped);
+    register_filters(&mut tt);
+
+    let rendered = composite_panels(tt.render(tt_name, context)?, panels);
+    if settings.debugging.deterministic_output {
+        return Ok(utils::normalize_svg_numbers(&rendered));
+    }
+    Ok(rendered)
+}
+
+/// Renders `template` from recorded `provider` responses with `settings` at the time of `clock`
+pub fn render_from_responses(
+    template: &str,
+    settings: &DashboardSettings,
+    provider: Providers,
+    daily_response: &str,
+    hourly_response: &str,
+    clock: &dyn Clock,
+) -> Result<String, WeatherEpdError> {
+    let input = ForecastInput::from_responses(settings, provider, daily_response, hourly_response)?;
+    let mut context_builder = ContextBuilder::with_settings(settings);
+    add_forecast(&mut context_builder, input, settings, clock);
+    render_template(
+        template,
+        &context_builder.context,
+        &dashboard_panels(&context_builder, settings, clock),
+        settings,
+    )
+    .map_err(WeatherEpdError::render)
+}
@@ -0,0 +1,226 @@
+//! Single-instance lock and stop requests of the cron runs
+//!
+//! Cron starts a run every few minutes, and a run stuck on a slow provider would still be writing
+//! the cache and the outputs when the next one started. A run takes an exclusive lock on
+//! `run.lock` in the cache directory first, and a second run gives up while it's held. The lock
+//! is released by the OS when the process ends, so a crashed run never leaves a stale lock.
+//!
+//! A stop request, e.g. the binary's SIGINT or SIGTERM handler when systemd stops the unit,
+//! cancels a phase that waits on the network. The outputs are written to staged files and renamed
+//! at the end, so they're left as they were. The self-update isn't cancelled halfway through
+//! replacing the binary, it's finished first. The library never installs signal handlers or ends
+//! the process itself, that's up to the binary.
+//!
+//! The state of the last run is kept in `run_status.txt` next to the lock, in the format of the
+//! update status: `running: <phase>`, `success`, `failed: <error>` or `aborted: <phase>`.
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use once_cell::sync::Lazy;
+use tokio::runtime::Runtime;
+use tokio::sync::watch;
+
+use crate::logger;
+
+pub const RUN_LOCK_FILE_NAME: &str = "run.lock";
+pub const RUN_STATUS_FILE_NAME: &str = "run_status.txt";
+
+/// Stop requests of the process, the guards started by [`RunGuard::start`] follow them
+static STOP_REQUESTS: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+
+/// Asks the runs of this process to stop after the current step, e.g. from a signal handler. A
+/// run started afterwards stops before its first phase.
+pub fn request_stop() {
+    STOP_REQUESTS.send_replace(true);
+}
+
+/// State of the last run, see the module docs for the file format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunStatus {
+    Running(String),
+    Success,
+    Failed(String),
+    Aborted(String),
+}
+
+impl fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunStatus::Running(phase) => write!(f, "running: {phase}"),
+            RunStatus::Success => write!(f, "success"),
+            RunStatus::Failed(error) => write!(f, "failed: {error}"),
+            RunStatus::Aborted(phase) => write!(f, "aborted: {phase}"),
+        }
+    }
+}
+
+impl RunStatus {
+    pub fn parse(status: &str) -> Option<Self> {
+        let status = status.trim();
+        if status == "success" {
+            return Some(RunStatus::Success);
+        }
+        let (state, detail) = status.split_once(": ")?;
+        let detail = detail.to_string();
+        match state {
+            "running" => Some(RunStatus::Running(detail)),
+            "failed" => Some(RunStatus::Failed(detail)),
+            "aborted" => Some(RunStatus::Aborted(detail)),
+            _ => None,
+        }
+    }
+}
+
+/// Writes `status` to the run status file in `dir`, a failure is only logged
+pub fn write_run_status(dir: &Path, status: &RunStatus) {
+    if let Err(e) = fs::write(dir.join(RUN_STATUS_FILE_NAME), status.to_string()) {
+        logger::error(format!("Failed to write run status: {e}"));
+    }
+}
+
+/// Reads the run status file in `dir`, `None` before the first run
+pub fn read_run_status(dir: &Path) -> Option<RunStatus> {
+    RunStatus::parse(&fs::read_to_string(dir.join(RUN_STATUS_FILE_NAME)).ok()?)
+}
+
+/// Exclusive lock of the run lock file, held until dropped
+#[derive(Debug)]
+pub struct RunLock {
+    _file: File,
+}
+
+impl RunLock {
+    /// Locks the run lock file in `dir`, fails when another run holds it
+    pub fn acquire(dir: &Path) -> Result<Self, Error> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(RUN_LOCK_FILE_NAME);
+        // Not truncated before it's locked, the holder's PID would be lost
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = fs::read_to_string(&path).unwrap_or_default();
+                anyhow::bail!(
+                    "Another run (PID {}) holds {}, skipping this one",
+                    holder.trim(),
+                    path.display()
+                );
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()))
+            }
+        }
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// A phase cancelled by a stop request
+#[derive(Debug, thiserror::Error)]
+#[error("Interrupted while {phase}")]
+pub struct Interrupted {
+    pub phase: String,
+}
+
+/// Runs the phases of a cron run under the run lock, stopping on request and keeping the run
+/// status up to date
+pub struct RunGuard {
+    _lock: RunLock,
+    dir: PathBuf,
+    runtime: Runtime,
+    stop: watch::Receiver<bool>,
+}
+
+impl RunGuard {
+    /// Takes the run lock in `dir`, the run stops on [`request_stop`]
+    pub fn start(dir: &Path) -> Result<Self, Error> {
+        Self::with_stop_requests(dir, STOP_REQUESTS.subscribe())
+    }
+
+    /// Takes the run lock in `dir`, the run stops when `stop` turns true, e.g. for a host with
+    /// its own shutdown
+    pub fn with_stop_requests(dir: &Path, stop: watch::Receiver<bool>) -> Result<Self, Error> {
+        let lock = RunLock::acquire(dir)?;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            _lock: lock,
+            dir: dir.to_path_buf(),
+            runtime,
+            stop,
+        })
+    }
+
+    /// Drives `future` to completion, or cancels it when a stop is requested
+    pub fn run<T, E>(
+        &self,
+        phase: &str,
+        future: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, Error>
+    where
+        Error: From<E>,
+    {
+        self.begin(phase)?;
+        let mut stop = self.stop.clone();
+        self.runtime.block_on(async {
+            tokio::select! {
+                result = future => Ok(result?),
+                _ = stop.wait_for(|stop| *stop) => Err(interrupted(phase)),
+            }
+        })
+    }
+
+    /// Runs `phase` to completion even when a stop is requested, the run stops after it
+    pub fn finish_phase<T>(
+        &self,
+        phase: &str,
+        run: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        self.begin(phase)?;
+        let result = run()?;
+        if *self.stop.borrow() {
+            return Err(interrupted(phase));
+        }
+        Ok(result)
+    }
+
+    /// Records the outcome of the run and releases the lock
+    pub fn finish<T>(self, result: &Result<T, Error>) {
+        let status = match result {
+            Ok(_) => RunStatus::Success,
+            Err(e) => match e.downcast_ref::<Interrupted>() {
+                Some(interrupted) => RunStatus::Aborted(interrupted.phase.clone()),
+                None => RunStatus::Failed(e.to_string()),
+            },
+        };
+        write_run_status(&self.dir, &status);
+    }
+
+    /// Records `phase` as running, unless a stop was requested since the last phase
+    fn begin(&self, phase: &str) -> Result<(), Error> {
+        if *self.stop.borrow() {
+            return Err(interrupted(phase));
+        }
+        write_run_status(&self.dir, &RunStatus::Running(phase.to_string()));
+        Ok(())
+    }
+}
+
+fn interrupted(phase: &str) -> Error {
+    Interrupted {
+        phase: phase.to_string(),
+    }
+    .into()
+}
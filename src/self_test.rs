@@ -0,0 +1,164 @@
+//! Self-test screen for setting up a display
+//!
+//! The screen is rendered without any weather data: it shows the version, a summary of the
+//! configuration, whether the weather provider can be reached, a row of sample icons and the
+//! time it was rendered. It goes through the same SVG, PNG and RAW outputs as the dashboard, so
+//! pushing it to the panel checks the whole chain before the first forecast is fetched.
+
+use std::fmt::{self, Display, Write};
+use std::time::Duration;
+
+use url::Url;
+
+use crate::clock::Clock;
+use crate::configs::settings::Providers;
+use crate::constants::{daily_forecast_endpoint, open_meteo_hourly_endpoint};
+use crate::dashboard::panel::{frame_size, scaled_frame};
+use crate::http::http_client;
+use crate::platform;
+use crate::utils::escape_xml;
+use crate::CONFIG;
+
+/// How long the network check waits for the provider before reporting it unreachable
+const NETWORK_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Icons drawn on the screen, one per kind of weather the dashboard shows
+const SAMPLE_ICONS: [&str; 6] = [
+    "clear-day.svg",
+    "partly-cloudy-day.svg",
+    "rain.svg",
+    "thunderstorms.svg",
+    "snow.svg",
+    "clear-night.svg",
+];
+
+/// Whether the configured weather provider answered the network check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkStatus {
+    /// The host answered with this HTTP status
+    Reachable {
+        host: String,
+        status: u16,
+    },
+    Unreachable {
+        host: String,
+        error: String,
+    },
+}
+
+impl Display for NetworkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reachable { host, status } => write!(f, "{host} reachable (HTTP {status})"),
+            Self::Unreachable { host, error } => write!(f, "{host} unreachable: {error}"),
+        }
+    }
+}
+
+/// Host of the configured weather provider, honouring the base URL overrides
+fn provider_url() -> Url {
+    let mut url = match CONFIG.api.provider {
+        Providers::Bom => daily_forecast_endpoint(),
+        Providers::OpenMeteo => open_meteo_hourly_endpoint(),
+    };
+    url.set_path("/");
+    url.set_query(None);
+    url
+}
+
+/// Sends a HEAD request to the provider's host. Any HTTP answer counts as reachable, no forecast
+/// is requested so the check doesn't use up the daily request budget.
+pub async fn check_network() -> NetworkStatus {
+    let url = provider_url();
+    let host = url.host_str().unwrap_or(url.as_str()).to_string();
+    let response = http_client()
+        .head(url)
+        .timeout(NETWORK_CHECK_TIMEOUT)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) => NetworkStatus::Reachable {
+            host,
+            status: response.status().as_u16(),
+        },
+        Err(error) => NetworkStatus::Unreachable {
+            host,
+            error: error.to_string(),
+        },
+    }
+}
+
+/// Renders the self-test screen the size of the dashboard (see [`frame_size`]), stamped with the
+/// current time of `clock`
+pub fn self_test_svg(network: &NetworkStatus, clock: &dyn Clock) -> String {
+    let now = clock.now_local();
+    let timestamp = format!(
+        "{} {}",
+        now.format(&CONFIG.render_options.date_format),
+        now.format(&CONFIG.render_options.time_format)
+    );
+    let summary = [
+        ("Version", env!("CARGO_PKG_VERSION").to_string()),
+        ("Provider", CONFIG.api.provider.to_string()),
+        (
+            "Location",
+            format!(
+                "lat: {}, lon: {}",
+                CONFIG.api.latitude.into_inner(),
+                CONFIG.api.longitude.into_inner()
+            ),
+        ),
+        ("Template", CONFIG.misc.template_path.display().to_string()),
+        (
+            "Output",
+            format!(
+                "{} driver, PNG scale {}",
+                CONFIG.misc.display_driver, CONFIG.misc.png_scale_factor
+            ),
+        ),
+        (
+            "Units",
+            format!(
+                "{}, {}",
+                CONFIG.render_options.temp_unit, CONFIG.render_options.wind_speed_unit
+            ),
+        ),
+    ];
+    let (network_colour, network_label) = match network {
+        NetworkStatus::Reachable { .. } => ("green", "Network OK"),
+        NetworkStatus::Unreachable { .. } => ("red", "Network down"),
+    };
+
+    let mut svg = String::from(
+        r#"    <text x="40" y="60" font-size="32" font-weight="bold" fill="black">Self-test</text>
+"#,
+    );
+    for (index, (key, value)) in summary.iter().enumerate() {
+        let y = 110 + index * 30;
+        let _ = writeln!(
+            svg,
+            r#"    <text x="40" y="{y}" font-size="20" fill="black">{key}</text><text x="180" y="{y}" font-size="20" fill="black">{}</text>"#,
+            escape_xml(value)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"    <text x="40" y="310" font-size="20" font-weight="bold" fill="{network_colour}">{network_label}</text><text x="200" y="310" font-size="16" fill="black">{}</text>"#,
+        escape_xml(&network.to_string())
+    );
+    for (index, name) in SAMPLE_ICONS.iter().enumerate() {
+        let icon = CONFIG.misc.svg_icons_directory.join(name);
+        let _ = writeln!(
+            svg,
+            r#"    <image x="{}" y="330" width="80" height="80" href="{}" />"#,
+            40 + index * 120,
+            escape_xml(&platform::href(&icon))
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"    <text x="40" y="450" font-size="16" fill="black">Rendered at {timestamp} without weather data</text>"#
+    );
+    scaled_frame(frame_size(&CONFIG.misc), "white", &svg)
+}
@@ -0,0 +1,92 @@
+//! Opt-in anonymous telemetry
+//!
+//! With `[telemetry] enabled = true` a small JSON ping is posted after each run: the app version,
+//! the CPU architecture, the weather provider, the display driver and resolution, and how long
+//! the render took. It helps deciding which providers and displays are worth supporting. The
+//! location, the profile names and the forecast are never sent, and there is no install id.
+//! `--show-telemetry` logs the ping of a run instead of sending it.
+
+use std::time::Duration;
+
+use anyhow::Error;
+use serde::Serialize;
+use url::Url;
+
+use crate::configs::settings::{DashboardSettings, Providers, Telemetry};
+use crate::display_options::DisplayDriver;
+use crate::http::http_client;
+use crate::logger;
+
+/// How long a ping may take before it is given up
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Everything a ping sends
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TelemetryPing {
+    pub version: String,
+    /// CPU architecture, e.g. `arm` or `aarch64`
+    pub arch: String,
+    pub provider: Providers,
+    pub display_driver: DisplayDriver,
+    /// Configured display size in pixels, e.g. `800x480`
+    pub display_resolution: Option<String>,
+    pub render_duration_ms: u64,
+}
+
+impl TelemetryPing {
+    /// The ping of a run with `settings` that took `render_duration`
+    pub fn new(settings: &DashboardSettings, render_duration: Duration) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            provider: settings.api.provider,
+            display_driver: settings.misc.display_driver,
+            display_resolution: settings
+                .misc
+                .display_resolution
+                .map(|(width, height)| format!("{width}x{height}")),
+            render_duration_ms: render_duration.as_millis() as u64,
+        }
+    }
+
+    /// The exact body posted
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("telemetry ping always serializes")
+    }
+}
+
+async fn post(url: &Url, ping: &TelemetryPing) -> Result<(), Error> {
+    http_client()
+        .post(url.clone())
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(ping.to_json())
+        .timeout(PING_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Sends `ping` when telemetry is enabled, or logs it in a dry run. A failed ping is only
+/// logged, it never fails the run.
+pub async fn send_ping(settings: &Telemetry, ping: &TelemetryPing) {
+    if settings.dry_run {
+        let destination = match (&settings.url, settings.enabled) {
+            (Some(url), true) => url.to_string(),
+            _ => "nowhere, telemetry is disabled".to_string(),
+        };
+        logger::info(format!(
+            "Telemetry ping, not sent, it would go to {destination}:\n{}",
+            ping.to_json()
+        ));
+        return;
+    }
+    let Some(url) = settings.url.as_ref().filter(|_| settings.enabled) else {
+        return;
+    };
+
+    match post(url, ping).await {
+        Ok(()) => logger::debug("Telemetry ping sent"),
+        Err(error) => logger::warning(format!("Failed to send the telemetry ping: {error}")),
+    }
+}
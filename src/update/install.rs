@@ -1,3 +1,6 @@
+//! Downloading and installing a newer release, native builds only
+
+use super::{get_base_dir_path, write_update_status};
 use crate::logger;
 use crate::CONFIG;
 use anyhow::{Context, Error, Result};
@@ -5,16 +8,13 @@ use chrono::{DateTime, Duration, Utc};
 use semver::Version;
 use serde::Deserialize;
 use std::env;
-use std::io::{ErrorKind, Seek, SeekFrom};
-use std::os::unix::fs::PermissionsExt;
-use std::path::PathBuf;
+use std::io::{Seek, SeekFrom};
 use std::{fs, path::Path};
 use tempfile::NamedTempFile;
 use url::Url;
 use zip::ZipArchive;
 
 const LAST_CHECKED_FILE_NAME: &str = "last_checked";
-const UPDATE_STATUS_FILE_NAME: &str = "update_status.txt";
 const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 
 #[cfg(target_arch = "arm")]
@@ -185,13 +185,22 @@ fn backup_existing_binary(bin_path: &Path, backup_link: &Path) -> Result<(), any
 /// # Errors
 ///
 /// Returns an error if the file permissions cannot be set.
+#[cfg(unix)]
 fn set_executable_permissions(bin_path: &Path) -> Result<(), anyhow::Error> {
+    use std::os::unix::fs::PermissionsExt;
+
     let mut perms = fs::metadata(bin_path)?.permissions();
     perms.set_mode(0o755);
     fs::set_permissions(bin_path, perms)?;
     Ok(())
 }
 
+/// Files are executable by their extension off Unix, there is nothing to set.
+#[cfg(not(unix))]
+fn set_executable_permissions(_bin_path: &Path) -> Result<(), anyhow::Error> {
+    Ok(())
+}
+
 /// Swaps in new files from temporary directory to base directory.
 ///
 /// # Arguments
@@ -263,18 +272,6 @@ fn download_and_extract_release(
     Ok(())
 }
 
-/// Gets the base directory path of the current executable.
-fn get_base_dir_path() -> Result<PathBuf> {
-    let exe_path = std::env::current_exe()?;
-    let base_dir = exe_path.parent().ok_or_else(|| {
-        std::io::Error::new(
-            ErrorKind::NotFound,
-            "Could not determine executable directory",
-        )
-    })?;
-    Ok(base_dir.to_path_buf())
-}
-
 /// Checks for updates and updates the application if a newer version is available.
 ///
 /// # Errors
@@ -347,47 +344,3 @@ pub fn update_app() -> Result<(), anyhow::Error> {
 
     update_result
 }
-
-/// Writes the update status to a file for later retrieval
-///
-/// This allows the dashboard to display update errors without blocking on the update process.
-/// The status file contains either "success" or "failed: <error message>".
-///
-/// # Arguments
-/// * `base_dir` - The directory where the status file will be written
-/// * `result` - The result of the update operation
-pub fn write_update_status(base_dir: &Path, result: &Result<(), Error>) {
-    let status_path = base_dir.join(UPDATE_STATUS_FILE_NAME);
-    let status = match result {
-        Ok(_) => "success".to_string(),
-        Err(e) => format!("failed: {e}"),
-    };
-
-    if let Err(e) = fs::write(&status_path, status) {
-        logger::error(format!("Failed to write update status: {}", e));
-    }
-}
-
-/// Reads the last update status from the status file in the given directory
-///
-/// Returns Some(error_message) if the last update failed, None otherwise.
-///
-/// # Arguments
-/// * `base_dir` - The directory where the status file is located
-pub fn read_update_status_from_dir(base_dir: &Path) -> Option<String> {
-    let status_path = base_dir.join(UPDATE_STATUS_FILE_NAME);
-    let status = fs::read_to_string(status_path).ok()?;
-
-    status
-        .strip_prefix("failed: ")
-        .map(|error_msg| error_msg.to_string())
-}
-
-/// Reads the last update status from the status file
-///
-/// Returns Some(error_message) if the last update failed, None otherwise.
-/// This is used by the dashboard to display update failures.
-pub fn read_last_update_status() -> Option<String> {
-    let base_dir = get_base_dir_path().ok()?;
-    read_update_status_from_dir(&base_dir)
-}
@@ -0,0 +1,75 @@
+//! The self-update and the status of the last one
+//!
+//! The status is read by every render to show a failed update on the dashboard, the update itself
+//! downloads the release with a blocking client and unpacks a zip, so it's left out of WebAssembly
+//! builds of the render core.
+
+use crate::logger;
+use anyhow::{Error, Result};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::{fs, path::Path};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod install;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use install::update_app;
+
+const UPDATE_STATUS_FILE_NAME: &str = "update_status.txt";
+
+/// Gets the base directory path of the current executable.
+fn get_base_dir_path() -> Result<PathBuf> {
+    let exe_path = std::env::current_exe()?;
+    let base_dir = exe_path.parent().ok_or_else(|| {
+        std::io::Error::new(
+            ErrorKind::NotFound,
+            "Could not determine executable directory",
+        )
+    })?;
+    Ok(base_dir.to_path_buf())
+}
+
+/// Writes the update status to a file for later retrieval
+///
+/// This allows the dashboard to display update errors without blocking on the update process.
+/// The status file contains either "success" or "failed: <error message>".
+///
+/// # Arguments
+/// * `base_dir` - The directory where the status file will be written
+/// * `result` - The result of the update operation
+pub fn write_update_status(base_dir: &Path, result: &Result<(), Error>) {
+    let status_path = base_dir.join(UPDATE_STATUS_FILE_NAME);
+    let status = match result {
+        Ok(_) => "success".to_string(),
+        Err(e) => format!("failed: {e}"),
+    };
+
+    if let Err(e) = fs::write(&status_path, status) {
+        logger::error(format!("Failed to write update status: {}", e));
+    }
+}
+
+/// Reads the last update status from the status file in the given directory
+///
+/// Returns Some(error_message) if the last update failed, None otherwise.
+///
+/// # Arguments
+/// * `base_dir` - The directory where the status file is located
+pub fn read_update_status_from_dir(base_dir: &Path) -> Option<String> {
+    let status_path = base_dir.join(UPDATE_STATUS_FILE_NAME);
+    let status = fs::read_to_string(status_path).ok()?;
+
+    status
+        .strip_prefix("failed: ")
+        .map(|error_msg| error_msg.to_string())
+}
+
+/// Reads the last update status from the status file
+///
+/// Returns Some(error_message) if the last update failed, None otherwise.
+/// This is used by the dashboard to display update failures.
+pub fn read_last_update_status() -> Option<String> {
+    let base_dir = get_base_dir_path().ok()?;
+    read_update_status_from_dir(&base_dir)
+}
@@ -1,17 +1,58 @@
-use crate::errors::GeohashError;
+use crate::display_options::{DisplayDriver, DisplayOptions};
+use crate::errors::{DashboardError, GeohashError};
+use crate::icon_cache::CachedIcons;
 use crate::logger;
+use crate::memory::{pooled_pixmap, return_pixmap};
 use anyhow::Error;
 use anyhow::Result;
 use chrono::Local;
 use chrono::TimeZone;
 use chrono::{DateTime, NaiveDateTime};
+use once_cell::sync::Lazy;
 use resvg::tiny_skia;
 use resvg::usvg;
 use serde::Deserialize;
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 use usvg::fontdb;
 
+/// Font database shared by every conversion, built on first use, with the shipped font files
+/// that had to be replaced with their embedded copy.
+///
+/// Loading the system fonts into the font database takes far longer than the rendering
+/// itself, so the long running web server must not redo it for every request.
+static FONT_DB: Lazy<(Arc<fontdb::Database>, Vec<&'static str>)> = Lazy::new(|| {
+    let mut font_db = fontdb::Database::new();
+    let embedded = load_fonts(&mut font_db);
+    (Arc::new(font_db), embedded)
+});
+
+/// SVG parse options, without anti-aliasing shapes and text get crisp edges.
+///
+/// Anti-aliased edges are blends of two colours that the 7-colour quantization dithers into
+/// speckles, edges in pure palette colours look much cleaner on the display. Elements with their
+/// own `shape-rendering` or `text-rendering` attribute keep it.
+fn svg_options(anti_aliasing: bool) -> usvg::Options<'static> {
+    let families = crate::CONFIG.fonts.fallback_families.clone();
+    let mut options = usvg::Options {
+        fontdb: FONT_DB.0.clone(),
+        font_resolver: usvg::FontResolver {
+            select_font: usvg::FontResolver::default_font_selector(),
+            select_fallback: Box::new(move |c, used_fonts, font_db| {
+                fallback_font(c, used_fonts, font_db, &families)
+            }),
+        },
+        ..Default::default()
+    };
+    if !anti_aliasing {
+        options.shape_rendering = usvg::ShapeRendering::CrispEdges;
+        options.text_rendering = usvg::TextRendering::OptimizeSpeed;
+    }
+    options
+}
+
 /// Converts an SVG file to a PNG file.
 ///
 /// # Arguments
@@ -19,6 +60,7 @@ use usvg::fontdb;
 /// * `input_path` - Path to the input SVG file.
 /// * `output_path` - Path to save the output PNG file.
 /// * `scale_factor` - The scale factor to apply to the SVG.
+/// * `anti_aliasing` - Whether edges of shapes and text are anti-aliased.
 ///
 /// # Returns
 ///
@@ -27,12 +69,13 @@ pub fn convert_svg_to_png(
     input_path: &PathBuf,
     output_path: &PathBuf,
     scale_factor: f32,
+    anti_aliasing: bool,
 ) -> Result<(), Error> {
     // Read the SVG file
     let svg_data = fs::read_to_string(input_path)
         .map_err(|e| Error::msg(format!("Failed to read SVG file: {e}")))?;
 
-    let png_bytes = convert_svg_to_png_bytes(&svg_data, scale_factor)?;
+    let png_bytes = convert_svg_to_png_bytes(&svg_data, scale_factor, anti_aliasing)?;
 
     // Save the PNG file
     fs::write(output_path, &png_bytes)
@@ -47,29 +90,33 @@ pub fn convert_svg_to_png(
 ///
 /// * `svg_data` - SVG content as string
 /// * `scale_factor` - The scale factor to apply to the SVG
+/// * `anti_aliasing` - Whether edges of shapes and text are anti-aliased
 ///
 /// # Returns
 ///
 /// * `Result<Vec<u8>, Error>` - PNG image data as bytes
-pub fn convert_svg_to_png_bytes(svg_data: &str, scale_factor: f32) -> Result<Vec<u8>, Error> {
-    let mut font_db = fontdb::Database::new();
-    load_fonts(&mut font_db);
-
-    // Parse the SVG
-    let opts = usvg::Options {
-        fontdb: font_db.into(),
-        ..Default::default()
+pub fn convert_svg_to_png_bytes(
+    svg_data: &str,
+    scale_factor: f32,
+    anti_aliasing: bool,
+) -> Result<Vec<u8>, Error> {
+    let options = svg_options(anti_aliasing);
+    let (svg_data, options) = if crate::CONFIG.render_options.icon_cache {
+        CachedIcons::of(svg_data, scale_factor, anti_aliasing, &options).options(options)
+    } else {
+        (svg_data.to_string(), options)
     };
 
-    let tree = usvg::Tree::from_str(svg_data, &opts)
+    // Parse the SVG
+    let tree = usvg::Tree::from_str(&svg_data, &options)
         .map_err(|e| Error::msg(format!("Failed to parse SVG: {e}")))?;
 
     // Create a higher resolution canvas
     let pixmap_size = tree.size().to_int_size();
     let width = (pixmap_size.width() as f32 * scale_factor) as u32;
     let height = (pixmap_size.height() as f32 * scale_factor) as u32;
-    let mut pixmap = tiny_skia::Pixmap::new(width, height)
-        .ok_or_else(|| Error::msg("Failed to create pixmap"))?;
+    let mut pixmap =
+        pooled_pixmap(width, height).ok_or_else(|| Error::msg("Failed to create pixmap"))?;
 
     // Create a transform that scales the SVG
     let transform = tiny_skia::Transform::from_scale(scale_factor, scale_factor);
@@ -78,14 +125,16 @@ pub fn convert_svg_to_png_bytes(svg_data: &str, scale_factor: f32) -> Result<Vec
     resvg::render(&tree, transform, &mut pixmap.as_mut());
 
     // Encode PNG to bytes
-    pixmap
+    let png = pixmap
         .encode_png()
-        .map_err(|e| Error::msg(format!("Failed to encode PNG: {e}")))
+        .map_err(|e| Error::msg(format!("Failed to encode PNG: {e}")));
+    return_pixmap(pixmap);
+    png
 }
 
 /// 7-color e-ink display palette (RGB values)
 /// Colors: Black, White, Green, Blue, Red, Yellow, Orange, Purple
-const PALETTE_7COLOR: [[u8; 3]; 8] = [
+pub(crate) const PALETTE_7COLOR: [[u8; 3]; 8] = [
     [0, 0, 0],       // Black
     [255, 255, 255], // White
     [67, 138, 28],   // Green
@@ -96,20 +145,24 @@ const PALETTE_7COLOR: [[u8; 3]; 8] = [
     [194, 164, 244], // Purple
 ];
 
-/// Finds the closest palette color index for a given RGB color using Euclidean distance.
-///
-/// # Arguments
-///
-/// * `color` - RGB color as [r, g, b] array
-///
-/// # Returns
-///
-/// * `u8` - Index of the closest palette color (0-7)
-fn depalette(color: [u8; 3]) -> u8 {
+/// Pure colours the inks aim for, the palette at a `saturation` of 1
+pub(crate) const SATURATED_PALETTE: [[u8; 3]; 8] = [
+    [0, 0, 0],       // Black
+    [255, 255, 255], // White
+    [0, 255, 0],     // Green
+    [0, 0, 255],     // Blue
+    [255, 0, 0],     // Red
+    [255, 255, 0],   // Yellow
+    [255, 140, 0],   // Orange
+    [194, 164, 244], // Purple
+];
+
+/// Finds the closest color of `palette` for a given RGB color using Euclidean distance.
+pub(crate) fn closest_palette_index(palette: &[[u8; 3]; 8], color: [u8; 3]) -> u8 {
     let mut min_diff = i32::MAX;
     let mut best_index = 0u8;
 
-    for (index, palette_color) in PALETTE_7COLOR.iter().enumerate() {
+    for (index, palette_color) in palette.iter().enumerate() {
         let diff_r = color[0] as i32 - palette_color[0] as i32;
         let diff_g = color[1] as i32 - palette_color[1] as i32;
         let diff_b = color[2] as i32 - palette_color[2] as i32;
@@ -133,8 +186,11 @@ fn depalette(color: [u8; 3]) -> u8 {
 /// # Returns
 ///
 /// * `Vec<u8>` - Raw 4-bit color data
-fn rgb_to_raw_7color(rgb_img: &image::RgbImage) -> Vec<u8> {
+pub(crate) fn rgb_to_raw_7color(rgb_img: &image::RgbImage) -> Vec<u8> {
     let (width, height) = rgb_img.dimensions();
+    // Inks as calibrated in the `[palette]` config
+    let palette = crate::CONFIG.palette.colours();
+    let depalette = |color: [u8; 3]| closest_palette_index(&palette, color);
 
     // Calculate output buffer size (2 pixels per byte due to 4-bit packing)
     let total_pixels = (width * height) as usize;
@@ -178,6 +234,33 @@ fn rgb_to_raw_7color(rgb_img: &image::RgbImage) -> Vec<u8> {
     output_buffer
 }
 
+/// Helper function to convert RGB image to the raw 4 bits per pixel greyscale format of IT8951
+/// panels.
+///
+/// Each pixel's luminance is mapped to one of 16 grey levels, 0 is black and 15 white. The
+/// first pixel of each pair goes in the low nibble, and rows are padded with white to a whole
+/// 16-bit word, as the controller loads images in words.
+pub(crate) fn rgb_to_raw_it8951(rgb_img: &image::RgbImage) -> Vec<u8> {
+    let (width, height) = rgb_img.dimensions();
+    let row_size = width.div_ceil(4) as usize * 2;
+    let mut output_buffer = Vec::with_capacity(row_size * height as usize);
+
+    for y in 0..height {
+        let levels = (0..row_size as u32 * 2).map(|x| {
+            if x >= width {
+                return 0x0f;
+            }
+            let [red, green, blue] = rgb_img.get_pixel(x, y).0.map(f32::from);
+            let luminance = 0.299 * red + 0.587 * green + 0.114 * blue;
+            (luminance / 17.0).round() as u8
+        });
+        let levels: Vec<u8> = levels.collect();
+        output_buffer.extend(levels.chunks(2).map(|pair| pair[0] | (pair[1] << 4)));
+    }
+
+    output_buffer
+}
+
 /// Converts a PNG image to raw 7-color format with 4-bit nibble packing.
 ///
 /// Each pixel is mapped to the closest color in the 7-color palette,
@@ -192,21 +275,100 @@ fn rgb_to_raw_7color(rgb_img: &image::RgbImage) -> Vec<u8> {
 ///
 /// * `Result<(), Error>` - Ok(()) if successful, or an error message
 pub fn convert_png_to_raw_7color(input_path: &PathBuf, output_path: &PathBuf) -> Result<(), Error> {
+    convert_png_to_raw(input_path, output_path, DisplayDriver::Acep)
+}
+
+/// Converts a PNG image to the raw format of the `driver`'s panel, see [`DisplayDriver::to_raw`]
+pub fn convert_png_to_raw(
+    input_path: &PathBuf,
+    output_path: &PathBuf,
+    driver: DisplayDriver,
+) -> Result<(), Error> {
+    convert_png_to_raw_with(input_path, output_path, driver, |image| image)
+}
+
+/// Converts a PNG image like [`convert_png_to_raw`], with `prepare` applied to the image before
+/// it's mapped onto the panel's colours, e.g. the [`ghosting`](crate::ghosting) compensation
+pub fn convert_png_to_raw_with(
+    input_path: &PathBuf,
+    output_path: &PathBuf,
+    driver: DisplayDriver,
+    prepare: impl FnOnce(image::RgbImage) -> image::RgbImage,
+) -> Result<(), Error> {
     // Load the PNG image
     let img =
         image::open(input_path).map_err(|e| Error::msg(format!("Failed to open PNG file: {e}")))?;
 
     // Convert to RGB8 format
-    let rgb_img = img.to_rgb8();
-    let output_buffer = rgb_to_raw_7color(&rgb_img);
+    let rgb_img = prepare(img.to_rgb8());
+    let output_buffer = driver.to_raw(&rgb_img);
 
     // Write the packed data to the output file
     fs::write(output_path, &output_buffer)
         .map_err(|e| Error::msg(format!("Failed to write raw file: {e}")))?;
 
+    verify_written_size(output_path, output_buffer.len() as u64)
+}
+
+/// Size in bytes of a raw 7-color image of `width` x `height` pixels.
+///
+/// Rows of an odd width end in a padding nibble.
+pub fn raw_7color_size(width: u32, height: u32) -> u64 {
+    u64::from(width.div_ceil(2)) * u64::from(height)
+}
+
+/// Size in bytes of a raw IT8951 greyscale image of `width` x `height` pixels.
+///
+/// Rows are padded to a whole 16-bit word, 4 pixels.
+pub fn raw_it8951_size(width: u32, height: u32) -> u64 {
+    u64::from(width.div_ceil(4) * 2) * u64::from(height)
+}
+
+/// Checks that the file at `path` holds `expected` bytes.
+///
+/// A write cut short by a full disk can still report success, the display would then show a
+/// scrambled image from the truncated file.
+pub fn verify_written_size(path: &Path, expected: u64) -> Result<(), Error> {
+    let written = fs::metadata(path)
+        .map_err(|e| Error::msg(format!("Failed to check {}: {e}", path.display())))?
+        .len();
+    if written != expected {
+        return Err(Error::msg(format!(
+            "{} is {written} bytes instead of {expected}, the disk may be full",
+            path.display()
+        )));
+    }
     Ok(())
 }
 
+/// Free space in bytes available to the application on the file system holding `path`.
+///
+/// `None` when it can't be determined.
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)] // The statvfs field types differ between platforms
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL terminated and `stats` is only read after statvfs filled it
+    let stats = unsafe {
+        if libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+    Some(u64::from(stats.f_bavail) * u64::from(stats.f_frsize))
+}
+
+/// Free space in bytes available to the application on the file system holding `path`.
+///
+/// `None` when it can't be determined.
+#[cfg(not(unix))]
+pub fn available_disk_space(_path: &Path) -> Option<u64> {
+    None
+}
+
 /// Converts PNG bytes to raw 7-color format with 4-bit nibble packing.
 ///
 /// Each pixel is mapped to the closest color in the 7-color palette,
@@ -229,29 +391,203 @@ pub fn convert_png_bytes_to_raw_7color(png_data: &[u8]) -> Result<Vec<u8>, Error
     Ok(rgb_to_raw_7color(&rgb_img))
 }
 
+/// Applies per-display options (rotation, palette, dithering) to PNG image data.
+///
+/// # Arguments
+///
+/// * `png_data` - PNG image data as bytes
+/// * `options` - Options requested by the display
+///
+/// # Returns
+///
+/// * `Result<Vec<u8>, Error>` - PNG image data as bytes, unchanged when there is nothing to apply
+pub fn apply_display_options_to_png_bytes(
+    png_data: Vec<u8>,
+    options: &DisplayOptions,
+) -> Result<Vec<u8>, Error> {
+    if options.is_identity() {
+        return Ok(png_data);
+    }
+
+    let img = image::load_from_memory(&png_data)
+        .map_err(|e| Error::msg(format!("Failed to load PNG from memory: {e}")))?;
+    let rgb_img = options.apply(img.to_rgb8());
+
+    let mut png_bytes = Vec::new();
+    rgb_img
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| Error::msg(format!("Failed to encode PNG: {e}")))?;
+    Ok(png_bytes)
+}
+
+/// Converts PNG image data to an uncompressed 24-bit BMP, for displays without a PNG decoder.
+///
+/// # Arguments
+///
+/// * `png_data` - PNG image data as bytes
+///
+/// # Returns
+///
+/// * `Result<Vec<u8>, Error>` - BMP image data as bytes
+pub fn convert_png_to_bmp_bytes(png_data: &[u8]) -> Result<Vec<u8>, Error> {
+    let img = image::load_from_memory(png_data)
+        .map_err(|e| Error::msg(format!("Failed to load PNG from memory: {e}")))?;
+
+    let mut bmp_bytes = Vec::new();
+    img.to_rgb8()
+        .write_to(
+            &mut std::io::Cursor::new(&mut bmp_bytes),
+            image::ImageFormat::Bmp,
+        )
+        .map_err(|e| Error::msg(format!("Failed to encode BMP: {e}")))?;
+    Ok(bmp_bytes)
+}
+
+/// Font files shipped in `static/fonts`, paired with the embedded copy used when the file is missing.
+///
+/// The embedded copies are subsets with the Latin scripts, the punctuation and the symbols,
+/// written by `scripts/subset-fonts.py`.
+const FONT_FILES: [(&str, &[u8]); 3] = [
+    (
+        "static/fonts/Roboto-VariableFont_wdth,wght.ttf",
+        include_bytes!("../static/fonts/embedded/Roboto-VariableFont_wdth,wght.ttf"),
+    ),
+    (
+        "static/fonts/Roboto-Italic-VariableFont_wdth,wght.ttf",
+        include_bytes!("../static/fonts/embedded/Roboto-Italic-VariableFont_wdth,wght.ttf"),
+    ),
+    (
+        "static/fonts/Roboto-Regular-Dashed.ttf",
+        include_bytes!("../static/fonts/embedded/Roboto-Regular-Dashed.ttf"),
+    ),
+];
+
+/// Returns the shipped font files the font database had to replace with their embedded copy,
+/// loading the fonts if no conversion has yet.
+///
+/// Rendering still works with the embedded fonts, but a fresh install missing `static/fonts`
+/// should be reported on the dashboard.
+pub fn missing_font_files() -> &'static [&'static str] {
+    &FONT_DB.1
+}
+
+/// The diagnostic for the shipped font files replaced with their embedded copy, if any were
+pub fn fonts_missing_warning() -> Option<DashboardError> {
+    let missing = missing_font_files();
+    (!missing.is_empty()).then(|| DashboardError::FontsMissing {
+        details: missing.join(", "),
+    })
+}
+
 /// Loads fonts into the provided font database.
 ///
+/// Font files that can't be loaded are replaced with their embedded copy, and returned.
+/// With `deterministic_output` the system fonts are left out, so the PNG doesn't depend on the
+/// fonts installed on the machine.
+///
 /// # Arguments
 ///
 /// * `font_db` - A mutable reference to a `fontdb::Database` to load fonts into.
-fn load_fonts(font_db: &mut fontdb::Database) {
-    font_db.load_system_fonts();
+///
+/// # Returns
+///
+/// * `Vec<&'static str>` - The shipped font files replaced with their embedded copy.
+fn load_fonts(font_db: &mut fontdb::Database) -> Vec<&'static str> {
+    if !crate::CONFIG.debugging.deterministic_output {
+        font_db.load_system_fonts();
+        if let Some(dir) = crate::platform::user_fonts_dir() {
+            font_db.load_fonts_dir(dir);
+        }
+    }
 
     // print current path
     let current_path = std::env::current_dir().unwrap();
 
-    let font_files = [
-        "static/fonts/Roboto-VariableFont_wdth,wght.ttf",
-        "static/fonts/Roboto-Italic-VariableFont_wdth,wght.ttf",
-        "static/fonts/Roboto-Regular-Dashed.ttf",
-    ];
+    let mut embedded_files = Vec::new();
+    for (file, embedded) in FONT_FILES {
+        if let Err(e) = font_db.load_font_file(current_path.join(file)) {
+            logger::warning(format!(
+                "Failed to load font file {file}: {e}, using the embedded copy"
+            ));
+            font_db.load_font_data(embedded.to_vec());
+            embedded_files.push(file);
+        }
+    }
 
-    for file in &font_files {
-        match font_db.load_font_file(current_path.join(file)) {
-            Ok(_) => {}
-            Err(e) => logger::warning(format!("Failed to load font file: {e}")),
+    for file in &crate::CONFIG.fonts.font_files {
+        if let Err(e) = font_db.load_font_file(file) {
+            logger::warning(format!("Failed to load font file {}: {e}", file.display()));
         }
     }
+
+    embedded_files
+}
+
+/// Whether the font `id` in `font_db` has a glyph for `c`
+fn has_glyph(font_db: &fontdb::Database, id: fontdb::ID, c: char) -> bool {
+    font_db
+        .with_face_data(id, |data, index| {
+            ttf_parser::Face::parse(data, index).is_ok_and(|face| face.glyph_index(c).is_some())
+        })
+        .unwrap_or(false)
+}
+
+/// Characters no font had a glyph for, each is only logged once
+static MISSING_GLYPHS: Lazy<Mutex<HashSet<char>>> = Lazy::new(Default::default);
+
+/// Picks the font for a character the fonts in `used_fonts` don't have.
+///
+/// The `families` are tried in order, in the weight and style of the text, then any other font
+/// with the character as usvg picks it. A character no font has is logged once and drawn as a
+/// missing glyph box.
+///
+/// # Arguments
+///
+/// * `c` - The character to draw.
+/// * `used_fonts` - Fonts already tried for the text, the first is the text's own font.
+/// * `font_db` - The font database to search.
+/// * `families` - Fallback families in order of preference.
+///
+/// # Returns
+///
+/// * `Option<fontdb::ID>` - The font to draw the character with, `None` when no font has it.
+pub fn fallback_font(
+    c: char,
+    used_fonts: &[fontdb::ID],
+    font_db: &mut Arc<fontdb::Database>,
+    families: &[String],
+) -> Option<fontdb::ID> {
+    let base_face = used_fonts.first().and_then(|id| font_db.face(*id));
+    let preferred = families.iter().find_map(|family| {
+        let id = font_db.query(&fontdb::Query {
+            families: &[fontdb::Family::Name(family)],
+            weight: base_face.map_or(fontdb::Weight::NORMAL, |face| face.weight),
+            stretch: base_face.map_or(fontdb::Stretch::Normal, |face| face.stretch),
+            style: base_face.map_or(fontdb::Style::Normal, |face| face.style),
+        })?;
+        (!used_fonts.contains(&id) && has_glyph(font_db, id, c)).then_some(id)
+    });
+    let font = match (preferred, used_fonts.is_empty()) {
+        (Some(id), _) => Some(id),
+        (None, true) => None,
+        (None, false) => usvg::FontResolver::default_fallback_selector()(c, used_fonts, font_db),
+    };
+
+    if font.is_none()
+        && MISSING_GLYPHS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(c)
+    {
+        logger::warning(format!(
+            "No font has a glyph for '{c}' (U+{:04X}), add one to fallback_families or font_files in [fonts]",
+            c as u32
+        ));
+    }
+    font
 }
 
 /// Calculates the total value between two dates from a dataset.
@@ -289,6 +625,55 @@ where
         .sum()
 }
 
+/// Fraction digits numbers in the SVG are rounded to by [`normalize_svg_numbers`]
+pub const NORMALIZED_FRACTION_DIGITS: usize = 3;
+
+static LONG_DECIMAL: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"-?\d+\.\d{4,}").expect("valid decimal pattern"));
+
+/// Rounds every number with more than [`NORMALIZED_FRACTION_DIGITS`] fraction digits.
+///
+/// Graph coordinates are computed in floating point, the last digits can differ between
+/// platforms. Rounded, the rendered SVG is byte-identical, e.g. for golden tests.
+pub fn normalize_svg_numbers(svg: &str) -> String {
+    LONG_DECIMAL
+        .replace_all(svg, |captures: &regex::Captures| {
+            let value: f64 = captures[0].parse().unwrap_or_default();
+            let rounded = format!("{value:.NORMALIZED_FRACTION_DIGITS$}");
+            let rounded = rounded.trim_end_matches('0').trim_end_matches('.');
+            match rounded {
+                "-0" => "0".to_string(),
+                rounded => rounded.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Escapes text for use in SVG text content and attribute values
+pub fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// Formats the minutes until rain for display, e.g. "Rain in ~40 min".
+///
+/// Hourly data can't place the start more precisely than that, so minutes are rounded to
+/// the nearest 10 and anything from 90 minutes up is shown in hours.
+pub fn format_rain_countdown(minutes: i64) -> String {
+    match minutes {
+        m if m < 5 => "Rain now".to_string(),
+        m if m < 90 => format!("Rain in ~{} min", ((m + 5) / 10 * 10).max(10)),
+        m => format!("Rain in ~{} h", (m + 30) / 60),
+    }
+}
+
 /// Finds the maximum value between two dates from a dataset.
 ///
 /// # Arguments
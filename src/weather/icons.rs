@@ -1,8 +1,87 @@
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
+use anyhow::{bail, Context, Error};
+use serde::Deserialize;
 use strum_macros::Display;
 
-use crate::CONFIG;
+use crate::{logger, platform, CONFIG};
+
+#[derive(Debug, Deserialize)]
+struct IconOverridesFile {
+    icons: HashMap<String, PathBuf>,
+}
+
+/// Loads an icon override file.
+///
+/// ```toml
+/// [icons]
+/// haze = "my-icons/smoke.svg"
+/// overcast-day-rain = "/home/pi/icons/rain.svg"
+/// ```
+///
+/// Keys are the names of the icons in `icons_directory`, with or without `.svg`. Relative paths
+/// are resolved against the directory of the override file. Entries whose icon file doesn't
+/// exist or isn't an SVG are skipped with a warning.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read, isn't valid TOML with an `[icons]` table or
+/// names an icon that isn't in `icons_directory`.
+pub fn load_icon_overrides(
+    path: &Path,
+    icons_directory: &Path,
+) -> Result<HashMap<String, PathBuf>, Error> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read icon overrides {}", path.display()))?;
+    let file: IconOverridesFile = config::Config::builder()
+        .add_source(config::File::from_str(&content, config::FileFormat::Toml))
+        .build()
+        .and_then(|config| config.try_deserialize())
+        .with_context(|| format!("Invalid icon overrides {}", path.display()))?;
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let mut overrides = HashMap::new();
+    for (name, target) in file.icons {
+        let name = name.trim().trim_end_matches(".svg").to_string();
+        if !icons_directory.join(format!("{name}.svg")).is_file() {
+            bail!(
+                "Unknown icon '{name}' in icon overrides {}, the names are the files in {} without .svg",
+                path.display(),
+                icons_directory.display()
+            );
+        }
+        let target = base_dir.join(target);
+        if target
+            .extension()
+            .is_none_or(|extension| extension != "svg")
+        {
+            logger::warning(format!(
+                "Icon override for '{name}' is not an SVG file: {}",
+                target.display()
+            ));
+            continue;
+        }
+        if !target.is_file() {
+            logger::warning(format!(
+                "Icon override for '{name}' not found: {}",
+                target.display()
+            ));
+            continue;
+        }
+        if let Some(on_disk) = platform::case_mismatch(&target) {
+            logger::warning(platform::case_warning(
+                &format!("Icon override for '{name}'"),
+                &target,
+                &on_disk,
+            ));
+        }
+        overrides.insert(name, target);
+    }
+    Ok(overrides)
+}
 
 #[derive(Debug, Display, Copy, Clone)]
 pub enum RainChanceName {
@@ -26,6 +105,17 @@ pub enum RainAmountName {
     Rain,
 }
 
+/// Suffix used in smoke, haze and dust icon names
+#[derive(Debug, Display, Copy, Clone)]
+pub enum ObscurationName {
+    #[strum(to_string = "smoke")]
+    Smoke,
+    #[strum(to_string = "haze")]
+    Haze,
+    #[strum(to_string = "dust")]
+    Dust,
+}
+
 #[derive(Debug, Display, Copy, Clone)]
 pub enum DayNight {
     #[strum(to_string = "-day")]
@@ -62,6 +152,18 @@ pub enum SunPositionIconName {
     Sunset,
 }
 
+#[derive(Debug, Display)]
+pub enum LightningIconName {
+    #[strum(to_string = "lightning-bolt.svg")]
+    LightningBolt,
+}
+
+#[derive(Debug, Display)]
+pub enum SpecialDateIconName {
+    #[strum(to_string = "star.svg")]
+    Star,
+}
+
 #[derive(Debug, Display)]
 pub enum NotAvailableIcon {
     #[strum(to_string = "not-available.svg")]
@@ -80,6 +182,22 @@ pub enum RainAmountIcon {
     RainAmount,
 }
 
+#[derive(Debug, Display)]
+pub enum PressureIconName {
+    #[strum(to_string = "pressure-low.svg")]
+    Low,
+    #[strum(to_string = "barometer.svg")]
+    Normal,
+    #[strum(to_string = "pressure-high.svg")]
+    High,
+}
+
+#[derive(Debug, Display)]
+pub enum AirQualityIcon {
+    #[strum(to_string = "smoke-particles.svg")]
+    AirQuality,
+}
+
 #[derive(Debug, Display)]
 pub enum UVIndexIcon {
     #[strum(to_string = "uv-index-none.svg")]
@@ -115,13 +233,17 @@ pub trait Icon {
     /// Returns the path of the icon as a `String`.
     /// The path is constructed using the `svg_icons_directory` from the configuration
     /// and the icon name obtained from `get_icon_name`.
+    /// A user override from `misc.icon_overrides_path` takes precedence over the bundled icon.
     fn get_icon_path(&self) -> String {
-        CONFIG
+        let icon_name = self.get_icon_name();
+        if let Some(path) = CONFIG
             .misc
-            .svg_icons_directory
-            .join(Path::new(&self.get_icon_name()))
-            .to_string_lossy()
-            .to_string()
+            .icon_overrides
+            .get(icon_name.trim_end_matches(".svg"))
+        {
+            return platform::href(path);
+        }
+        platform::href(&CONFIG.misc.svg_icons_directory.join(Path::new(&icon_name)))
     }
 }
 
@@ -136,3 +258,15 @@ impl Icon for HumidityIconName {
         self.to_string()
     }
 }
+
+impl Icon for LightningIconName {
+    fn get_icon_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Icon for SpecialDateIconName {
+    fn get_icon_name(&self) -> String {
+        self.to_string()
+    }
+}
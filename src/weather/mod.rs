@@ -1,2 +1,3 @@
 pub mod icons;
+pub mod solar;
 pub mod utils;
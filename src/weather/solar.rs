@@ -0,0 +1,65 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use std::f64::consts::PI;
+
+/// Fractional year in radians for `date`, evaluated at noon
+fn fractional_year(date: NaiveDate) -> f64 {
+    let days_in_year = if date.leap_year() { 366.0 } else { 365.0 };
+    2.0 * PI / days_in_year * (date.ordinal0() as f64)
+}
+
+/// Computes solar noon (the sun's highest point) in UTC for a date and longitude.
+///
+/// Uses the NOAA equation of time approximation, which is accurate to within a minute,
+/// good enough for a display that shows hours and minutes.
+pub fn solar_noon(date: NaiveDate, longitude: f64) -> Option<DateTime<Utc>> {
+    let gamma = fractional_year(date);
+
+    let equation_of_time_minutes = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+
+    let noon_minutes_utc = 720.0 - 4.0 * longitude - equation_of_time_minutes;
+    let midnight_utc = date.and_hms_opt(0, 0, 0)?.and_utc();
+    Some(midnight_utc + Duration::seconds((noon_minutes_utc * 60.0).round() as i64))
+}
+
+/// Computes sunrise and sunset in UTC for a date and location, for providers that don't send
+/// them.
+///
+/// Uses the NOAA solar declination approximation with the usual 0.833° for refraction and the
+/// sun's radius, within a few minutes of published times outside the polar circles.
+/// `None` on days the sun doesn't rise or doesn't set.
+pub fn sun_times(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let gamma = fractional_year(date);
+
+    let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+
+    let latitude = latitude.to_radians();
+    let cos_hour_angle = 90.833_f64.to_radians().cos() / (latitude.cos() * declination.cos())
+        - latitude.tan() * declination.tan();
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+
+    // The sun moves 4 minutes per degree of hour angle
+    let half_day =
+        Duration::seconds((cos_hour_angle.acos().to_degrees() * 4.0 * 60.0).round() as i64);
+    let noon = solar_noon(date, longitude)?;
+    Some((noon - half_day, noon + half_day))
+}
+
+/// Formats a day length as hours and minutes, e.g. "14h 32m".
+pub fn format_day_length(day_length: Duration) -> String {
+    let total_minutes = day_length.num_minutes().max(0);
+    format!("{}h {:02}m", total_minutes / 60, total_minutes % 60)
+}
@@ -1,4 +1,4 @@
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use strum_macros::Display;
 
 // Determine the moon phase icon based on the moon age
@@ -22,11 +22,11 @@ pub enum MoonPhaseIconName {
     WaningCrescent,
 }
 
-pub fn get_moon_phase_icon_name() -> MoonPhaseIconName {
-    let now = chrono::Local::now();
-    let year = now.year();
-    let month = now.month();
-    let day = now.day();
+/// Moon phase icon for `date`
+pub fn get_moon_phase_icon_name(date: NaiveDate) -> MoonPhaseIconName {
+    let year = date.year();
+    let month = date.month();
+    let day = date.day();
 
     // Calculate the approximate age of the moon in days since the last new moon
     let mut moon_age_days = ((year as f32 - 2000.0) * 365.25 + month as f32 * 30.6 + day as f32
@@ -1,21 +1,73 @@
+use crate::apis::open_meteo::models::{OpenMeteoGardenResponse, OpenMeteoMarineResponse};
 use crate::clock::{Clock, SystemClock};
-use crate::dashboard::context::{Context, ContextBuilder};
-use crate::errors::{DashboardError, Description};
+use crate::configs::profiles::{active_profile, with_profile};
+use crate::configs::settings::{Layout, QuietHours, QuietMode};
+use crate::dashboard::comparison::{other_label, render_comparison_svg, LocationSummary};
+use crate::dashboard::context::ContextBuilder;
+use crate::dashboard::forecast_changes::forecast_changes;
+use crate::dashboard::forecast_json::{DashboardDiagnostics, DashboardStatus, ForecastJson};
+use crate::dashboard::garden::{fetch_garden, garden_panel, GardenConditions};
+use crate::dashboard::image_panel::fetch_image_panel;
+use crate::dashboard::indoor::{fetch_indoor, indoor_panel, IndoorComparison, IndoorReading};
+use crate::dashboard::marine::{fetch_marine, marine_svg, MarineConditions};
+use crate::dashboard::panel::{frame_size, registered_panels, scaled_frame, Panel};
+use crate::dashboard::quote_footer::quote_footer;
+use crate::dashboard::rain_today::update_rain_log;
+use crate::dashboard::snow_report::{fetch_snow_report, snow_report_panel, SnowData};
+use crate::dashboard::temperature_records::temperature_records_badge;
+use crate::dashboard::text_fallback::render_text_dashboard;
+use crate::dashboard::theme::{themed_colours, ThemeWeather};
+use crate::display_options::DisplayDriver;
+use crate::domain::models::{DailyForecast, HourlyForecast, RainGaugeReading};
+use crate::errors::{DashboardError, Description, WeatherEpdError};
+use crate::ghosting::compensate_against_previous;
 use crate::logger;
-use crate::providers::factory::create_provider;
+use crate::memory::MemoryUsage;
+use crate::metrics_export::{push_metrics, RenderMetrics};
+use crate::output_names;
+use crate::platform;
+use crate::providers::factory::{cache_path, create_provider};
+use crate::refresh_schedule::{refresh_schedule, AwakeHours, RefreshSchedule};
+use crate::render_core::{
+    add_forecast, dashboard_panels, forecast_diagnostics, render_template, ForecastInput,
+};
+use crate::self_test::{self_test_svg, NetworkStatus};
+use crate::telemetry::{send_ping, TelemetryPing};
 use crate::update::read_last_update_status;
+use crate::weather::icons::Icon;
 use crate::{utils, CONFIG};
 use anyhow::Error;
+use chrono::{DateTime, Local};
+use std::any::Any;
 use std::fs;
 use std::io::Write;
-use std::path::Path;
-use tinytemplate::{format_unescaped, TinyTemplate};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 pub use utils::*;
 
-fn update_forecast_context(
-    context_builder: &mut ContextBuilder,
-    clock: &dyn Clock,
-) -> Result<(), Error> {
+/// Forecast data fetched from the provider, ready to be turned into a dashboard context
+struct ForecastData {
+    daily: Vec<DailyForecast>,
+    hourly: Vec<HourlyForecast>,
+    warnings: Vec<DashboardError>,
+    /// Image panel as a data URI, only fetched for rendered dashboards
+    image_panel: Option<String>,
+    /// Snow report data, only fetched for rendered dashboards
+    snow_report: Option<SnowData>,
+    /// Waves and swell of the marine layout, only fetched for rendered dashboards
+    marine: Option<OpenMeteoMarineResponse>,
+    /// Soil moisture and evapotranspiration for the garden indicator, only fetched for rendered dashboards
+    garden: Option<OpenMeteoGardenResponse>,
+    /// Indoor sensor reading, only fetched for rendered dashboards
+    indoor: Option<IndoorReading>,
+    /// Rain gauge reading of a nearby station, BOM observations only
+    rain_gauge_reading: Option<RainGaugeReading>,
+    /// Titles of the marine wind warnings in force, BOM warnings only
+    marine_wind_warnings: Vec<String>,
+}
+
+async fn fetch_forecast_data(clock: &dyn Clock) -> Result<ForecastData, Error> {
     let provider = create_provider()?;
     let mut warnings: Vec<DashboardError> = Vec::new();
 
@@ -28,8 +80,18 @@ fn update_forecast_context(
 
     logger::subsection(format!("Using provider: {}", provider.provider_name()));
 
-    logger::subsection("Fetching daily forecast");
-    let daily_result = provider.fetch_daily_forecast()?;
+    logger::subsection("Fetching daily and hourly forecasts");
+    let (daily_result, hourly_result, weather_warnings) = tokio::join!(
+        provider.fetch_daily_forecast(),
+        provider.fetch_hourly_forecast(),
+        provider.fetch_warnings(clock)
+    );
+    for warning in &weather_warnings {
+        logger::warning(warning.long_description());
+    }
+    warnings.extend(weather_warnings);
+
+    let daily_result = daily_result?;
     if let Some(warning) = daily_result.warning {
         logger::warning(format!(
             "Using cached data due to: {}",
@@ -39,10 +101,8 @@ fn update_forecast_context(
     } else {
         logger::success("Daily forecast retrieved");
     }
-    context_builder.with_daily_forecast_data(daily_result.data, clock);
 
-    logger::subsection("Fetching hourly forecast");
-    let hourly_result = provider.fetch_hourly_forecast()?;
+    let hourly_result = hourly_result?;
     if let Some(warning) = hourly_result.warning {
         logger::warning(format!(
             "Using cached data due to: {}",
@@ -52,72 +112,648 @@ fn update_forecast_context(
     } else {
         logger::success("Hourly forecast retrieved");
     }
-    context_builder.with_hourly_forecast_data(hourly_result.data, clock);
 
-    // Add all accumulated warnings to the context
-    for warning in warnings {
-        context_builder.with_warning(warning);
+    let (
+        ForecastInput {
+            daily,
+            hourly,
+            warnings,
+        },
+        anomalies,
+    ) = ForecastInput {
+        daily: daily_result.data,
+        hourly: hourly_result.data,
+        warnings,
+    }
+    .checked(&CONFIG);
+    for anomaly in &anomalies {
+        logger::warning(format!("Dropped implausible forecast {anomaly}"));
     }
 
-    Ok(())
+    Ok(ForecastData {
+        daily,
+        hourly,
+        warnings,
+        image_panel: None,
+        snow_report: None,
+        marine: None,
+        garden: None,
+        indoor: None,
+        rain_gauge_reading: provider.rain_gauge_reading(),
+        marine_wind_warnings: provider.marine_wind_warnings(),
+    })
+}
+
+/// Whether the comparison replaces the template, only for the base configuration: a profile is
+/// rendered on its own, e.g. by the batch mode
+fn comparison_enabled() -> bool {
+    CONFIG.comparison.enabled && active_profile().is_none()
+}
+
+/// Fetches the configured location and the comparison profile's in one go and renders them side
+/// by side, returns the SVG with the diagnostics of both, see [`comparison`]
+///
+/// [`comparison`]: crate::dashboard::comparison
+async fn generate_comparison_svg(
+    clock: &dyn Clock,
+) -> Result<(String, Vec<DashboardError>), WeatherEpdError> {
+    let settings = &CONFIG.comparison;
+    let profile = CONFIG.profile(&settings.profile).ok_or_else(|| {
+        WeatherEpdError::config(format!(
+            "Comparison profile \"{}\" isn't defined",
+            settings.profile
+        ))
+    })?;
+
+    logger::subsection(format!(
+        "Fetching forecasts of {} and {}",
+        settings.home_label,
+        other_label(settings)
+    ));
+    let (home, other) = tokio::join!(
+        fetch_forecast_data(clock),
+        with_profile(profile, fetch_forecast_data(clock))
+    );
+    let home = home.map_err(WeatherEpdError::provider)?;
+    let other = other.map_err(WeatherEpdError::provider)?;
+
+    let now = clock.now_utc();
+    let today = clock.now_local().date_naive();
+    let summary = |label: &str, data: &ForecastData| {
+        LocationSummary::at(label, &data.hourly, &data.daily, now, today, settings.days)
+    };
+    let warnings: Vec<DashboardError> = home
+        .warnings
+        .iter()
+        .chain(&other.warnings)
+        .cloned()
+        .chain(utils::fonts_missing_warning())
+        .collect();
+    // The theme follows the weather at home, where the display is
+    let colours = themed_colours(
+        &CONFIG.colours,
+        &CONFIG.theme_rules,
+        &ThemeWeather::at(&home.daily, &home.hourly, now, today),
+    );
+    let svg = render_comparison_svg(
+        &summary(&settings.home_label, &home),
+        &summary(other_label(settings), &other),
+        &warnings,
+        frame_size(&CONFIG.misc),
+        &colours,
+        clock,
+    );
+    Ok((svg, warnings))
+}
+
+/// Forecast data together with the image panel, the snow report, the garden and marine data and
+/// the indoor reading, everything a rendered dashboard shows
+async fn fetch_dashboard_data(clock: &dyn Clock) -> Result<ForecastData, Error> {
+    let (forecast_data, image_panel, snow_report, garden, marine, indoor) = tokio::join!(
+        fetch_forecast_data(clock),
+        fetch_image_panel(
+            &CONFIG.image_panel,
+            &CONFIG.misc.weather_data_cache_path,
+            CONFIG.debugging.disable_weather_api_requests,
+        ),
+        fetch_snow_report(
+            &CONFIG.snow_report,
+            CONFIG.debugging.disable_weather_api_requests,
+        ),
+        fetch_garden(&CONFIG.garden),
+        fetch_marine(),
+        fetch_indoor(
+            &CONFIG.indoor,
+            CONFIG.debugging.disable_weather_api_requests,
+        )
+    );
+
+    Ok(ForecastData {
+        image_panel,
+        snow_report,
+        marine,
+        garden,
+        indoor,
+        ..forecast_data?
+    })
+}
+
+/// The `warnings` of the forecast and the diagnostic for fonts replaced with their embedded copy,
+/// which loads the fonts the dashboard is drawn with
+fn with_font_warning(warnings: Vec<DashboardError>) -> Vec<DashboardError> {
+    warnings
+        .into_iter()
+        .chain(utils::fonts_missing_warning())
+        .collect()
+}
+
+/// Context of a rendered dashboard. Building it records the forecast outlooks, the rain log and
+/// the temperature history, so only the render paths call it, see
+/// [`generate_dashboard_diagnostics`] for the forecast alone.
+fn build_forecast_context(data: ForecastData, clock: &dyn Clock) -> ContextBuilder<'static> {
+    let mut context_builder = ContextBuilder::new();
+    let today = clock.now_local().date_naive();
+    let daily = data.daily.clone();
+    context_builder.with_forecast_changes(
+        &forecast_changes(
+            &CONFIG.forecast_changes,
+            &data.daily,
+            today,
+            clock.now_utc(),
+        ),
+        today,
+    );
+    context_builder.with_rain_log(update_rain_log(
+        &data.hourly,
+        data.rain_gauge_reading,
+        clock.now_utc(),
+    ));
+    let indoor = data
+        .indoor
+        .map(|reading| IndoorComparison::at(reading, &data.hourly, clock.now_utc()));
+    add_forecast(
+        &mut context_builder,
+        ForecastInput {
+            daily: data.daily,
+            hourly: data.hourly,
+            warnings: with_font_warning(data.warnings),
+        },
+        &CONFIG,
+        clock,
+    );
+    // The panels take the colours of the matching theme rule, added with the forecast
+    let colours = context_builder.colours().clone();
+    context_builder.with_panel(temperature_records_badge(
+        &CONFIG.temperature_records,
+        &daily,
+        today,
+        &colours,
+    ));
+    context_builder.with_image_panel(data.image_panel);
+    context_builder.with_panel(data.snow_report.and_then(|snow| {
+        snow_report_panel(
+            &CONFIG.snow_report,
+            &snow.conditions_at(clock.now_utc()),
+            &colours,
+        )
+    }));
+    context_builder.with_marine(data.marine.map(|marine| {
+        marine_svg(
+            &MarineConditions::at(&marine, &data.marine_wind_warnings, clock.now_utc()),
+            CONFIG.render_options.wave_height_unit,
+            &colours,
+        )
+    }));
+    context_builder.with_panel(data.garden.map(|garden| {
+        garden_panel(
+            &CONFIG.garden,
+            &GardenConditions::at(&garden, clock.now_utc()),
+            &colours,
+        )
+    }));
+    context_builder.with_panel(
+        indoor.and_then(|comparison| indoor_panel(&CONFIG.indoor, &comparison, &colours)),
+    );
+
+    context_builder
 }
 
 fn render_dashboard_template(
-    context: &Context,
+    context_builder: &ContextBuilder,
     dashboard_svg: String,
     output_svg_name: &Path,
-) -> Result<(), Error> {
-    let rendered = render_dashboard_template_to_string(context, dashboard_svg)?;
-    let mut output = fs::File::create(output_svg_name)?;
-    output.write_all(rendered.as_bytes())?;
+    clock: &dyn Clock,
+) -> Result<(), WeatherEpdError> {
+    let rendered = render_dashboard_template_to_string(context_builder, dashboard_svg, clock)
+        .map_err(WeatherEpdError::render)?;
+    let mut output = fs::File::create(output_svg_name).map_err(WeatherEpdError::output)?;
+    output
+        .write_all(rendered.as_bytes())
+        .map_err(WeatherEpdError::output)?;
     Ok(())
 }
 
-/// Renders dashboard template to SVG string in memory.
+/// Reads the SVG template, a missing template is a configuration problem
+fn read_template(input_template_name: &Path) -> Result<String, WeatherEpdError> {
+    fs::read_to_string(input_template_name).map_err(|e| {
+        logger::error(format!("Failed to read template file: {e}"));
+        if let Ok(current_dir) = std::env::current_dir() {
+            logger::detail(format!("Current directory: {}", current_dir.display()));
+        }
+        logger::detail(format!("Template path: {}", input_template_name.display()));
+        WeatherEpdError::config(e)
+    })
+}
+
+/// Renders dashboard template to SVG string in memory, with the quote footer, the clock face,
+/// the context's panels and the registered panels on top.
 ///
 /// # Arguments
 ///
-/// * `context` - The dashboard context
+/// * `context_builder` - The dashboard context and its panels
 /// * `dashboard_svg` - The SVG template string
+/// * `clock` - The clock the registered panels are rendered with
 ///
 /// # Returns
 ///
 /// * `Result<String, Error>` - Rendered SVG as string
 fn render_dashboard_template_to_string(
-    context: &Context,
+    context_builder: &ContextBuilder,
     dashboard_svg: String,
+    clock: &dyn Clock,
 ) -> Result<String, Error> {
-    let mut tt = TinyTemplate::new();
-    let tt_name = "dashboard";
-
-    if let Err(e) = tt.add_template(tt_name, &dashboard_svg) {
-        logger::error(format!("Failed to add template: {e}"));
-        return Err(e.into());
+    let panels: Vec<Panel> = quote_footer(
+        &CONFIG.footer,
+        clock.now_local().date_naive(),
+        context_builder.colours(),
+    )
+    .into_iter()
+    .chain(dashboard_panels(context_builder, &CONFIG, clock))
+    .chain(registered_panels(clock))
+    .collect();
+    if let Some(warning) = graph_viewport_warning(
+        &dashboard_svg,
+        CONFIG.render_options.graph_width.into_inner(),
+        CONFIG.render_options.graph_height.into_inner(),
+    ) {
+        logger::warning(warning);
     }
-    tt.set_default_formatter(&format_unescaped);
+    if CONFIG.misc.layout == Layout::Marine && !dashboard_svg.contains("{marine_svg}") {
+        logger::warning(
+            "The marine layout needs a template with a {marine_svg} region, e.g. dashboard-template-marine.svg",
+        );
+    }
+    render_template(&dashboard_svg, &context_builder.context, &panels, &CONFIG)
+        .inspect_err(|e| logger::error(format!("Failed to render template: {e}")))
+}
 
-    // Attempt to render the template
-    match tt.render(tt_name, &context) {
-        Ok(rendered) => Ok(rendered),
-        Err(e) => {
-            logger::error(format!("Failed to render template: {e}"));
-            Err(e.into())
-        }
+/// Checks the configured graph size against the viewport of the template's graph `<svg>` element.
+///
+/// The graph is scaled to fit its viewport, so a size far off from the viewport shrinks or
+/// blows up the axis labels and line widths. Templates with a hard-coded `viewBox` ignore
+/// the configured size entirely.
+///
+/// # Returns
+///
+/// * `Option<String>` - A warning message, or `None` if the size fits the template.
+pub fn graph_viewport_warning(template_svg: &str, width: f32, height: f32) -> Option<String> {
+    let Some(placeholder) = template_svg.find("{graph_viewbox}") else {
+        let is_default_size = width == 600.0 && height == 300.0;
+        return (!is_default_size).then(|| {
+            "Template has no {graph_viewbox} placeholder, graph_width/graph_height only take effect with a template that uses it".to_string()
+        });
+    };
+
+    let tag_start = template_svg[..placeholder].rfind("<svg")?;
+    let tag_end = placeholder + template_svg[placeholder..].find('>')?;
+    let tag = &template_svg[tag_start..tag_end];
+    let attribute = |name: &str| -> Option<f32> {
+        let value_start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+        let value_len = tag[value_start..].find('"')?;
+        tag[value_start..value_start + value_len].parse().ok()
+    };
+    let (viewport_width, viewport_height) = (attribute("width")?, attribute("height")?);
+
+    let viewbox_width = width
+        + crate::dashboard::context::GRAPH_VIEWBOX_LEFT_MARGIN
+        + crate::dashboard::context::GRAPH_VIEWBOX_RIGHT_MARGIN;
+    let scale = (viewport_width / viewbox_width).min(viewport_height / height);
+    if (0.5..=2.0).contains(&scale) {
+        None
+    } else {
+        Some(format!(
+            "Graph size {width}x{height} is drawn at {:.0}% in the template's {viewport_width}x{viewport_height} viewport, adjust graph_width/graph_height",
+            scale * 100.0
+        ))
     }
 }
 
 /// Generate weather dashboard using the system clock (production)
-pub fn generate_weather_dashboard() -> Result<(), Error> {
+pub async fn generate_weather_dashboard() -> Result<(), WeatherEpdError> {
     let clock = SystemClock;
     let input_template_name = &CONFIG.misc.template_path;
     let output_svg_name = &CONFIG.misc.generated_svg_name;
-    generate_weather_dashboard_injection(&clock, input_template_name, output_svg_name)
+    generate_weather_dashboard_or_unavailable(&clock, input_template_name, output_svg_name).await
+}
+
+/// Like [`generate_weather_dashboard_injection`], but when generation fails a
+/// "dashboard unavailable" frame is written to the configured outputs before the error is returned.
+///
+/// Without it the display keeps showing the last successful forecast with no hint that it is stale.
+pub async fn generate_weather_dashboard_or_unavailable(
+    clock: &dyn Clock,
+    input_template_name: &Path,
+    output_svg_name: &Path,
+) -> Result<(), WeatherEpdError> {
+    generate_or_unavailable(
+        clock,
+        input_template_name,
+        &OutputPaths::with_svg(output_svg_name),
+    )
+    .await
+}
+
+/// Like [`generate_weather_dashboard_or_unavailable`] with the configured template, but the
+/// outputs are written to `output_dir` under their configured file names.
+///
+/// Used by the batch mode, each location gets its own directory.
+pub async fn generate_weather_dashboard_in_dir(
+    clock: &dyn Clock,
+    output_dir: &Path,
+) -> Result<(), WeatherEpdError> {
+    generate_or_unavailable(
+        clock,
+        &CONFIG.misc.template_path,
+        &OutputPaths::in_dir(output_dir),
+    )
+    .await
+}
+
+/// Files a dashboard is written to
+struct OutputPaths {
+    svg: PathBuf,
+    png: PathBuf,
+    raw: PathBuf,
+}
+
+impl OutputPaths {
+    /// The configured PNG and RAW outputs with the SVG at `svg`
+    fn with_svg(svg: &Path) -> Self {
+        Self {
+            svg: svg.to_path_buf(),
+            png: CONFIG.misc.generated_png_name.clone(),
+            raw: CONFIG.misc.generated_raw_name.clone(),
+        }
+    }
+
+    /// The configured output file names in `dir`
+    fn in_dir(dir: &Path) -> Self {
+        let in_dir = |path: &Path| dir.join(path.file_name().unwrap_or(path.as_os_str()));
+        Self {
+            svg: in_dir(&CONFIG.misc.generated_svg_name),
+            png: in_dir(&CONFIG.misc.generated_png_name),
+            raw: in_dir(&CONFIG.misc.generated_raw_name),
+        }
+    }
+
+    /// The outputs of a render at `time`, see [`output_names`]
+    fn at(&self, time: DateTime<Local>) -> Self {
+        Self {
+            svg: output_names::expand(&self.svg, time),
+            png: output_names::expand(&self.png, time),
+            raw: output_names::expand(&self.raw, time),
+        }
+    }
+
+    /// Removes the archived outputs past `misc.output_retention_days`
+    fn remove_expired(&self) {
+        let retention_days = CONFIG.misc.output_retention_days;
+        if retention_days == 0 {
+            return;
+        }
+        let retention = std::time::Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60);
+        let removed: usize = self
+            .files()
+            .into_iter()
+            .map(|(_, path)| output_names::remove_expired(path, retention, SystemTime::now()))
+            .sum();
+        if removed > 0 {
+            logger::info(format!(
+                "Removed {removed} outputs older than {retention_days} days"
+            ));
+        }
+    }
+
+    /// Temporary files next to the outputs, see [`staged_path`]
+    fn staged(&self) -> Self {
+        Self {
+            svg: staged_path(&self.svg),
+            png: staged_path(&self.png),
+            raw: staged_path(&self.raw),
+        }
+    }
+
+    fn files(&self) -> [(&'static str, &PathBuf); 3] {
+        [("SVG", &self.svg), ("PNG", &self.png), ("RAW", &self.raw)]
+    }
+}
+
+/// Temporary file an output is written to before it's renamed into place, in the same directory
+/// so the rename is atomic. The extension is kept, the PNG decoder goes by it.
+pub fn staged_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}.tmp.{}", extension.to_string_lossy()),
+        None => format!("{stem}.tmp"),
+    };
+    path.with_file_name(name)
+}
+
+/// Writes the outputs through `write` to their staged files and renames them into place only
+/// once every one of them is written. A run failing halfway leaves the previous outputs as they
+/// were, so a display polling the RAW file never gets a half-written one.
+fn write_outputs(
+    outputs: &OutputPaths,
+    write: impl FnOnce(&OutputPaths) -> Result<(), WeatherEpdError>,
+) -> Result<(), WeatherEpdError> {
+    let staged = outputs.staged();
+    let result = write(&staged).and_then(|()| swap_in(&staged, outputs));
+    if result.is_err() {
+        for (_, path) in staged.files() {
+            let _ = fs::remove_file(path);
+        }
+    }
+    result
+}
+
+/// Renames the written staged files over the outputs, disabled outputs have none
+fn swap_in(staged: &OutputPaths, outputs: &OutputPaths) -> Result<(), WeatherEpdError> {
+    let current_dir = std::env::current_dir().map_err(WeatherEpdError::output)?;
+    for ((kind, from), (_, to)) in staged.files().into_iter().zip(outputs.files()) {
+        if !from.exists() {
+            continue;
+        }
+        fs::rename(from, to).map_err(WeatherEpdError::output)?;
+        logger::success(format!("{kind} saved: {}", current_dir.join(to).display()));
+    }
+    Ok(())
+}
+
+async fn generate_or_unavailable(
+    clock: &dyn Clock,
+    input_template_name: &Path,
+    outputs: &OutputPaths,
+) -> Result<(), WeatherEpdError> {
+    let result =
+        write_dashboard_or_unavailable(clock, input_template_name, &outputs.at(clock.now_local()))
+            .await;
+    outputs.remove_expired();
+    result
+}
+
+async fn write_dashboard_or_unavailable(
+    clock: &dyn Clock,
+    input_template_name: &Path,
+    outputs: &OutputPaths,
+) -> Result<(), WeatherEpdError> {
+    if let Some(quiet_hours) = CONFIG.schedule.quiet_hours_at(clock.now_local()) {
+        return write_quiet_hours_dashboard(&quiet_hours, outputs);
+    }
+
+    let Err(failure) = generate_dashboard_files(clock, input_template_name, outputs).await else {
+        return Ok(());
+    };
+
+    logger::error(format!("Dashboard generation failed: {}", failure.error));
+    if !failure.text_dashboard_written {
+        logger::subsection("Writing dashboard unavailable image");
+        if let Err(write_error) = write_unavailable_files(clock, &failure.error, outputs) {
+            logger::error(format!(
+                "Failed to write dashboard unavailable image: {write_error}"
+            ));
+        }
+    }
+
+    Err(failure.error)
+}
+
+/// A failed dashboard generation, and whether the text-only dashboard was written in its place
+struct GenerationFailure {
+    error: WeatherEpdError,
+    text_dashboard_written: bool,
+}
+
+impl From<WeatherEpdError> for GenerationFailure {
+    fn from(error: WeatherEpdError) -> Self {
+        Self {
+            error,
+            text_dashboard_written: false,
+        }
+    }
+}
+
+/// Whether `error` comes from the template: it can't be read or doesn't render
+fn is_template_error(error: &WeatherEpdError, input_template_name: &Path) -> bool {
+    match error {
+        WeatherEpdError::Render(_) => true,
+        WeatherEpdError::Config(_) => !input_template_name.is_file(),
+        _ => false,
+    }
+}
+
+/// Writes the text-only dashboard in place of the one the template `error` stopped
+fn write_text_dashboard_instead(
+    context_builder: &ContextBuilder,
+    error: WeatherEpdError,
+    outputs: &OutputPaths,
+) -> GenerationFailure {
+    logger::error(format!("Dashboard template failed: {error}"));
+    logger::subsection("Writing text-only dashboard");
+    let text_dashboard_written = write_text_dashboard(context_builder, &error, outputs)
+        .inspect_err(|fallback_error| {
+            logger::error(format!(
+                "Failed to write text-only dashboard: {fallback_error}"
+            ))
+        })
+        .is_ok();
+    GenerationFailure {
+        error,
+        text_dashboard_written,
+    }
+}
+
+/// Writes the text-only dashboard of the already fetched forecast to the SVG, PNG and RAW outputs,
+/// see [`render_text_dashboard`]
+fn write_text_dashboard(
+    context_builder: &ContextBuilder,
+    error: &WeatherEpdError,
+    outputs: &OutputPaths,
+) -> Result<(), WeatherEpdError> {
+    let svg = render_text_dashboard(
+        &context_builder.context,
+        &error.to_string(),
+        frame_size(&CONFIG.misc),
+    );
+
+    prepare_output_dir(outputs)?;
+    write_outputs(outputs, |staged| {
+        fs::write(&staged.svg, svg).map_err(WeatherEpdError::output)?;
+        write_png_and_raw(staged)
+    })
+}
+
+/// Writes the night layout to the SVG, PNG and RAW outputs, or leaves them untouched in skip mode
+fn write_quiet_hours_dashboard(
+    quiet_hours: &QuietHours,
+    outputs: &OutputPaths,
+) -> Result<(), WeatherEpdError> {
+    if quiet_hours.mode == QuietMode::Skip {
+        logger::info(format!(
+            "Quiet hours until {}, skipping the refresh",
+            quiet_hours.end
+        ));
+        return Ok(());
+    }
+
+    logger::subsection("Writing night layout for the quiet hours");
+    prepare_output_dir(outputs)?;
+    write_outputs(outputs, |staged| {
+        fs::write(&staged.svg, render_night_svg(quiet_hours)).map_err(WeatherEpdError::output)?;
+        write_png_and_raw(staged)
+    })
+}
+
+/// Writes a diagnostic frame describing `error` to the SVG, PNG and RAW outputs
+pub fn write_unavailable_dashboard(
+    clock: &dyn Clock,
+    error: &WeatherEpdError,
+    output_svg_name: &Path,
+) -> Result<(), WeatherEpdError> {
+    write_unavailable_files(
+        clock,
+        error,
+        &OutputPaths::with_svg(output_svg_name).at(clock.now_local()),
+    )
+}
+
+/// Writes the self-test screen to the configured SVG, PNG and RAW outputs, see [`self_test`]
+///
+/// [`self_test`]: crate::self_test
+pub fn write_self_test_dashboard(
+    clock: &dyn Clock,
+    network: &NetworkStatus,
+) -> Result<(), WeatherEpdError> {
+    let outputs = OutputPaths::with_svg(&CONFIG.misc.generated_svg_name).at(clock.now_local());
+
+    prepare_output_dir(&outputs)?;
+    write_outputs(&outputs, |staged| {
+        fs::write(&staged.svg, self_test_svg(network, clock)).map_err(WeatherEpdError::output)?;
+        write_png_and_raw(staged)
+    })
+}
+
+fn write_unavailable_files(
+    clock: &dyn Clock,
+    error: &WeatherEpdError,
+    outputs: &OutputPaths,
+) -> Result<(), WeatherEpdError> {
+    let svg = render_diagnostic_svg("Dashboard unavailable", &error.to_string(), clock);
+
+    prepare_output_dir(outputs)?;
+    write_outputs(outputs, |staged| {
+        fs::write(&staged.svg, svg).map_err(WeatherEpdError::output)?;
+        write_png_and_raw(staged)
+    })
 }
 
 /// Generate weather dashboard with a custom clock and custom paths  (for testing)
 ///
 /// This function allows dependency injection of a Clock implementation and custom paths,
-/// enabling deterministic testing with FixedClock.
+/// enabling deterministic testing with FixedClock. When only the template fails, the text-only
+/// dashboard is written to the outputs and the template error is still returned.
 ///
 /// # Arguments
 ///
@@ -133,74 +769,187 @@ pub fn generate_weather_dashboard() -> Result<(), Error> {
 /// let input_template_name = std::path::Path::new("templates/weather_dashboard.svg");
 /// let output_svg_name = std::path::Path::new("output/weather_dashboard.svg");
 /// let clock = FixedClock::from_rfc3339("2025-10-09T22:00:00Z").unwrap();
-/// generate_weather_dashboard_injection(&clock, input_template_name, output_svg_name)?;
+/// generate_weather_dashboard_injection(&clock, input_template_name, output_svg_name).await?;
 /// ```
-pub fn generate_weather_dashboard_injection(
+pub async fn generate_weather_dashboard_injection(
     clock: &dyn Clock,
     input_template_name: &Path,
     output_svg_name: &Path,
-) -> Result<(), Error> {
-    let current_dir = std::env::current_dir()?;
-    let mut context_builder = ContextBuilder::new();
+) -> Result<(), WeatherEpdError> {
+    generate_dashboard_files(
+        clock,
+        input_template_name,
+        &OutputPaths::with_svg(output_svg_name),
+    )
+    .await
+    .map_err(|failure| failure.error)
+}
 
-    let template_svg = match fs::read_to_string(input_template_name) {
-        Ok(svg) => svg,
-        Err(e) => {
-            logger::error(format!("Failed to read template file: {e}"));
-            logger::detail(format!("Current directory: {}", current_dir.display()));
-            logger::detail(format!("Template path: {}", &input_template_name.display()));
-            return Err(e.into());
+/// Renders the dashboard to the outputs. When only the template fails, the text-only dashboard of
+/// the fetched forecast is written instead and the template error is still returned.
+async fn generate_dashboard_files(
+    clock: &dyn Clock,
+    input_template_name: &Path,
+    outputs: &OutputPaths,
+) -> Result<(), GenerationFailure> {
+    let started = std::time::Instant::now();
+
+    if comparison_enabled() {
+        let (svg, _) = generate_comparison_svg(clock).await?;
+        logger::subsection("Rendering comparison to SVG");
+        prepare_output_dir(outputs)?;
+        return Ok(write_outputs(outputs, |staged| {
+            fs::write(&staged.svg, svg).map_err(WeatherEpdError::output)?;
+            write_png_and_raw(staged)
+        })?);
+    }
+
+    let template_svg = match read_template(input_template_name) {
+        Ok(template_svg) => template_svg,
+        Err(error) if is_template_error(&error, input_template_name) => {
+            // The forecast is still fetched once, for the text-only dashboard
+            return Err(match fetch_dashboard_data(clock).await {
+                Ok(forecast_data) => write_text_dashboard_instead(
+                    &build_forecast_context(forecast_data, clock),
+                    error,
+                    outputs,
+                ),
+                Err(fetch_error) => {
+                    logger::error(format!("Failed to fetch the forecast: {fetch_error}"));
+                    error.into()
+                }
+            });
         }
+        Err(error) => return Err(error.into()),
     };
 
-    update_forecast_context(&mut context_builder, clock)?;
+    let forecast_data = fetch_dashboard_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+    let context_builder = build_forecast_context(forecast_data, clock);
 
     logger::subsection("Rendering dashboard to SVG");
-    // Ensure the parent directory for the output SVG exists
-    if let Some(parent) = output_svg_name.parent() {
-        std::fs::create_dir_all(parent)?;
+    prepare_output_dir(outputs)?;
+    let rendered = write_outputs(outputs, |staged| {
+        render_dashboard_template(&context_builder, template_svg, &staged.svg, clock)?;
+        write_png_and_raw(staged)
+    });
+    match rendered {
+        Err(error) if is_template_error(&error, input_template_name) => {
+            return Err(write_text_dashboard_instead(
+                &context_builder,
+                error,
+                outputs,
+            ))
+        }
+        rendered => rendered?,
     }
 
-    render_dashboard_template(&context_builder.context, template_svg, output_svg_name)?;
-    logger::success(format!(
-        "SVG saved: {}",
-        current_dir.join(output_svg_name).display()
-    ));
+    let metrics = RenderMetrics::from_context(&context_builder.context, started.elapsed())
+        .with_memory_usage(MemoryUsage::current());
+    push_metrics(&CONFIG.metrics_export, &metrics).await;
+    send_ping(
+        &CONFIG.telemetry,
+        &TelemetryPing::new(&CONFIG, metrics.render_duration),
+    )
+    .await;
+    Ok(())
+}
 
+/// Creates the directories of the outputs and checks the SVG's has room for them
+fn prepare_output_dir(outputs: &OutputPaths) -> Result<(), WeatherEpdError> {
+    for (_, path) in outputs.files() {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(WeatherEpdError::output)?;
+        }
+    }
+    let dir = match outputs.svg.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    check_free_disk_space(dir, CONFIG.misc.min_free_disk_space_mb)
+}
+
+/// Fails when the disk holding `dir` has less than `min_free_mb` MB free.
+///
+/// A full SD card truncates the RAW file, which the display shows as a scrambled image, so the
+/// outputs aren't written at all. The check is skipped when the free space can't be determined.
+pub fn check_free_disk_space(dir: &Path, min_free_mb: u64) -> Result<(), WeatherEpdError> {
+    if min_free_mb == 0 {
+        return Ok(());
+    }
+    let Some(available) = utils::available_disk_space(dir) else {
+        logger::debug(format!("Free disk space of {} unknown", dir.display()));
+        return Ok(());
+    };
+
+    let available_mb = available / (1024 * 1024);
+    if available_mb < min_free_mb {
+        return Err(WeatherEpdError::output(format!(
+            "only {available_mb} MB free on the disk holding {}, at least {min_free_mb} MB are \
+             needed to write the dashboard. Free up space, e.g. old logs, or lower \
+             misc.min_free_disk_space_mb",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Fails when the RAW file at `raw_path` doesn't have the size of a `(width, height)` display
+pub fn check_raw_size(
+    raw_path: &Path,
+    (width, height): (u32, u32),
+    driver: DisplayDriver,
+) -> Result<(), WeatherEpdError> {
+    let expected = driver.raw_size(width, height);
+    let size = fs::metadata(raw_path)
+        .map_err(WeatherEpdError::output)?
+        .len();
+    if size != expected {
+        return Err(WeatherEpdError::output(format!(
+            "{} is {size} bytes, a {width}x{height} display expects {expected} bytes. Check that \
+             the template size times misc.png_scale_factor matches misc.display_resolution",
+            raw_path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Converts the SVG output to the PNG and RAW outputs, unless disabled
+fn write_png_and_raw(outputs: &OutputPaths) -> Result<(), WeatherEpdError> {
     if !CONFIG.debugging.disable_png_output {
         logger::subsection("Converting SVG to PNG");
         // Ensure the parent directory for the generated PNG exists
-        if let Some(png_parent) = CONFIG.misc.generated_png_name.parent() {
-            std::fs::create_dir_all(png_parent)?;
+        if let Some(png_parent) = outputs.png.parent() {
+            std::fs::create_dir_all(png_parent).map_err(WeatherEpdError::output)?;
         }
 
         convert_svg_to_png(
-            &output_svg_name.to_path_buf(),
-            &CONFIG.misc.generated_png_name,
+            &outputs.svg,
+            &outputs.png,
             CONFIG.misc.png_scale_factor,
-        )?;
-
-        logger::success(format!(
-            "PNG saved: {}",
-            current_dir.join(&CONFIG.misc.generated_png_name).display()
-        ));
+            CONFIG.render_options.anti_aliasing,
+        )
+        .map_err(WeatherEpdError::convert)?;
 
         if !CONFIG.debugging.disable_raw_7color_output {
             logger::subsection("Converting PNG to RAW 4bit-color image data");
             // Ensure the parent directory for the generated RAW exists
-            if let Some(raw_parent) = CONFIG.misc.generated_raw_name.parent() {
-                std::fs::create_dir_all(raw_parent)?;
+            if let Some(raw_parent) = outputs.raw.parent() {
+                std::fs::create_dir_all(raw_parent).map_err(WeatherEpdError::output)?;
             }
 
-            convert_png_to_raw_7color(
-                &CONFIG.misc.generated_png_name,
-                &CONFIG.misc.generated_raw_name,
-            )?;
-
-            logger::success(format!(
-                "RAW saved: {}",
-                current_dir.join(&CONFIG.misc.generated_raw_name).display()
-            ));
+            let driver = CONFIG.misc.display_driver;
+            convert_png_to_raw_with(&outputs.png, &outputs.raw, driver, |image| {
+                compensate_against_previous(image, &cache_path(), CONFIG.misc.ghosting_compensation)
+            })
+            .map_err(WeatherEpdError::convert)?;
+            if let Some(resolution) = CONFIG.misc.display_resolution {
+                check_raw_size(&outputs.raw, resolution, driver)?;
+            }
         }
     }
     Ok(())
@@ -217,22 +966,211 @@ pub fn generate_weather_dashboard_injection(
 ///
 /// # Returns
 ///
-/// * `Result<String, Error>` - Rendered SVG as string
-pub fn generate_dashboard_svg_string(
+/// * `Result<String, WeatherEpdError>` - Rendered SVG as string
+pub async fn generate_dashboard_svg_string(
     clock: &dyn Clock,
     input_template_name: &Path,
-) -> Result<String, Error> {
-    let mut context_builder = ContextBuilder::new();
+) -> Result<String, WeatherEpdError> {
+    if comparison_enabled() {
+        return Ok(generate_comparison_svg(clock).await?.0);
+    }
+
+    let template_svg = read_template(input_template_name)?;
+
+    let forecast_data = fetch_dashboard_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+    let context_builder = build_forecast_context(forecast_data, clock);
 
-    let template_svg = match fs::read_to_string(input_template_name) {
-        Ok(svg) => svg,
-        Err(e) => {
-            logger::error(format!("Failed to read template file: {e}"));
-            return Err(e.into());
+    render_dashboard_template_to_string(&context_builder, template_svg, clock)
+        .map_err(WeatherEpdError::render)
+}
+
+/// Fetches the forecast and returns the data the dashboard would render (for the web server's JSON API)
+pub async fn generate_forecast_json(clock: &dyn Clock) -> Result<ForecastJson, WeatherEpdError> {
+    let forecast_data = fetch_forecast_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+    let diagnostics = forecast_diagnostics(
+        ForecastInput {
+            daily: forecast_data.daily.clone(),
+            hourly: forecast_data.hourly.clone(),
+            warnings: with_font_warning(forecast_data.warnings),
+        },
+        &CONFIG,
+        clock,
+    );
+
+    Ok(ForecastJson::new(
+        forecast_data.daily,
+        forecast_data.hourly,
+        &diagnostics,
+        clock,
+    ))
+}
+
+/// Fetches the forecast and returns the recommended refreshes of a display over the next hours
+pub async fn generate_refresh_schedule(
+    clock: &dyn Clock,
+) -> Result<RefreshSchedule, WeatherEpdError> {
+    let forecast_data = fetch_forecast_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+
+    Ok(refresh_schedule(
+        &forecast_data.hourly,
+        &CONFIG.web_server.refresh_hint,
+        CONFIG.web_server.active_hours_interval_seconds,
+        &AwakeHours::new(&CONFIG),
+        clock.now_utc(),
+    ))
+}
+
+/// Fetches the forecast and returns the diagnostics the dashboard would show, without rendering
+/// it. Only the forecast is added to the context, so the outlooks, the rain log and the history
+/// the rendered dashboards keep are left as they are.
+pub async fn generate_dashboard_diagnostics(
+    clock: &dyn Clock,
+) -> Result<DashboardDiagnostics, WeatherEpdError> {
+    let forecast_data = fetch_forecast_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+
+    Ok(DashboardDiagnostics::new(&forecast_diagnostics(
+        ForecastInput {
+            daily: forecast_data.daily,
+            hourly: forecast_data.hourly,
+            warnings: with_font_warning(forecast_data.warnings),
+        },
+        &CONFIG,
+        clock,
+    )))
+}
+
+/// Dashboard SVG together with the diagnostics shown on it
+pub struct RenderedDashboard {
+    pub svg: String,
+    pub diagnostics: DashboardDiagnostics,
+}
+
+/// Like [`generate_dashboard_svg_string`], but a panic while building the context or rendering
+/// the template is caught and turned into a diagnostic frame (see [`render_diagnostic_svg`]).
+///
+/// Meant for the long running web server: one bad forecast must not take the process down,
+/// and the display shows what went wrong instead of a stale or blank image. Only the context
+/// and the render are covered: a panic while fetching the forecast or rendering the comparison
+/// ends the request without a frame, the server keeps serving the next ones.
+pub async fn generate_dashboard_svg_string_catch_panic(
+    clock: &dyn Clock,
+    input_template_name: &Path,
+) -> Result<RenderedDashboard, WeatherEpdError> {
+    if comparison_enabled() {
+        let (svg, warnings) = generate_comparison_svg(clock).await?;
+        return Ok(RenderedDashboard {
+            svg,
+            diagnostics: DashboardDiagnostics::new(&warnings),
+        });
+    }
+
+    // A missing template still gets the text-only dashboard
+    let template_svg = read_template(input_template_name);
+
+    let forecast_data = fetch_dashboard_data(clock)
+        .await
+        .map_err(WeatherEpdError::provider)?;
+
+    let rendered = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        let context_builder = build_forecast_context(forecast_data, clock);
+        let diagnostics = DashboardDiagnostics::new(context_builder.diagnostics());
+        let svg = template_svg.and_then(|template_svg| {
+            render_dashboard_template_to_string(&context_builder, template_svg, clock)
+                .map_err(WeatherEpdError::render)
+        });
+        match svg {
+            Ok(svg) => RenderedDashboard { svg, diagnostics },
+            Err(error) => {
+                logger::error(format!("Serving the text-only dashboard: {error}"));
+                RenderedDashboard {
+                    svg: render_text_dashboard(
+                        &context_builder.context,
+                        &error.to_string(),
+                        frame_size(&CONFIG.misc),
+                    ),
+                    diagnostics: DashboardDiagnostics {
+                        status: DashboardStatus::Degraded,
+                        ..diagnostics
+                    },
+                }
+            }
         }
-    };
+    }));
 
-    update_forecast_context(&mut context_builder, clock)?;
+    match rendered {
+        Ok(rendered) => Ok(rendered),
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            logger::error(format!("Dashboard rendering panicked: {message}"));
+            Ok(RenderedDashboard {
+                svg: render_diagnostic_svg("Dashboard rendering failed", &message, clock),
+                diagnostics: DashboardDiagnostics::failed(),
+            })
+        }
+    }
+}
+
+/// Extracts the message from a panic payload
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Renders the static night layout shown during the quiet hours, the size of the dashboard (see
+/// [`frame_size`]).
+///
+/// It has no clock or forecast on it, so refreshes during the quiet hours show the same image.
+pub fn render_night_svg(quiet_hours: &QuietHours) -> String {
+    let icon = platform::href(&CONFIG.misc.svg_icons_directory.join("clear-night.svg"));
+    let end = quiet_hours.end;
+
+    let content = format!(
+        r#"    <image x="336" y="120" width="128" height="128" href="{icon}" />
+    <text x="400" y="300" font-size="32" text-anchor="middle" fill="black">Quiet hours</text>
+    <text x="400" y="340" font-size="20" text-anchor="middle" fill="black">The forecast returns at {end}</text>
+"#
+    );
+    scaled_frame(frame_size(&CONFIG.misc), "white", &content)
+}
+
+/// Longest error message shown on the diagnostic frame, longer ones run off the display
+const DIAGNOSTIC_MESSAGE_MAX_CHARS: usize = 70;
+
+/// Renders a plain frame the size of the dashboard (see [`frame_size`]) that reports a failure on
+/// the display, stamped with the current time
+pub fn render_diagnostic_svg(title: &str, message: &str, clock: &dyn Clock) -> String {
+    let truncated: String = message.chars().take(DIAGNOSTIC_MESSAGE_MAX_CHARS).collect();
+    let escaped = utils::escape_xml(&truncated);
+    let icon = DashboardError::ApiError {
+        details: message.to_string(),
+    }
+    .get_icon_path();
+    let now = clock.now_local();
+    let timestamp = format!(
+        "{} {}",
+        now.format(&CONFIG.render_options.date_format),
+        now.format(&CONFIG.render_options.time_format)
+    );
 
-    render_dashboard_template_to_string(&context_builder.context, template_svg)
+    let content = format!(
+        r#"    <image x="40" y="40" width="64" height="64" href="{icon}" />
+    <text x="124" y="84" font-size="32" font-weight="bold" fill="red">{title}</text>
+    <text x="40" y="160" font-size="20" fill="black">{escaped}</text>
+    <text x="40" y="440" font-size="16" fill="black">Failed at {timestamp}. The next refresh will try again.</text>
+"#
+    );
+    scaled_frame(frame_size(&CONFIG.misc), "white", &content)
 }
@@ -1,33 +1,433 @@
-use crate::clock::SystemClock;
+use crate::buttons::{next_layout, selected_layout};
+use crate::clock::{Clock, SystemClock};
+use crate::configs::profiles::with_profile;
+use crate::configs::settings::{Button, ButtonAction, DashboardFormat, QuietMode};
+use crate::dashboard::forecast_json::{DashboardDiagnostics, ForecastJson};
+use crate::dashboard::panel::{composite_panels, svg_size, Panel};
+use crate::device_health::{
+    device_summaries, is_valid_device_id, record_report, status_page_html, BootReport,
+    DeviceSummary, MAX_DEVICE_ID_LEN,
+};
+use crate::display_options::{DisplayDriver, DisplayOptions, Dither, Palette, Rotation};
+use crate::errors::WeatherEpdError;
+use crate::full_clear::count_refresh;
+use crate::ghosting::compensate_against_previous;
 use crate::logger;
-use crate::utils::{convert_png_bytes_to_raw_7color, convert_svg_to_png_bytes};
-use crate::weather_dashboard::generate_dashboard_svg_string;
+use crate::memory::{self, MemoryUsage};
+use crate::protocol::{ProtocolVersion, VersionInfo, PROTOCOL_VERSION_HEADER};
+use crate::providers::factory::cache_path;
+use crate::raw_diff::{diff_frames, swap_last_frame, RawFrame};
+use crate::refresh_schedule::DeviceConfig;
+use crate::self_test::{check_network, self_test_svg};
+use crate::utils::{
+    apply_display_options_to_png_bytes, convert_png_to_bmp_bytes, convert_svg_to_png_bytes,
+};
+use crate::weather_dashboard::{
+    generate_dashboard_diagnostics, generate_dashboard_svg_string_catch_panic,
+    generate_forecast_json, generate_refresh_schedule, render_night_svg, RenderedDashboard,
+};
 use crate::CONFIG;
 use axum::{
-    extract::Path,
-    http::{header, HeaderMap, StatusCode},
-    response::{IntoResponse, Response},
-    routing::get,
-    Router,
+    extract::{Path, Query, Request},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
 };
 use chrono::{Local, Timelike};
+#[cfg(unix)]
+use hyper_util::rt::TokioIo;
+#[cfg(unix)]
+use hyper_util::service::TowerToHyperService;
+use serde::Deserialize;
+use std::future::Future;
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(unix)]
+use std::os::unix::io::{FromRawFd, OwnedFd, RawFd};
 use std::path::PathBuf;
 use std::time::Duration;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use tower_http::set_header::SetResponseHeader;
+use utoipa::{IntoParams, OpenApi};
+
+/// Where the web server accepts connections when systemd didn't pass it a socket
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListenAddress {
+    /// All interfaces on this TCP port
+    Port(u16),
+    /// A Unix domain socket, e.g. for a reverse proxy running on the same machine. Only on Unix
+    /// systems, elsewhere the server fails to start.
+    UnixSocket(PathBuf),
+}
+
+pub async fn run_server(address: ListenAddress) -> Result<(), anyhow::Error> {
+    crate::init_config()?;
+    let app = router();
+
+    #[cfg(unix)]
+    if let Some(listener) = systemd_listener()? {
+        return serve_inherited(listener, app).await;
+    }
 
-pub async fn run_server(port: u16) -> Result<(), anyhow::Error> {
-    let app = Router::new()
+    match address {
+        ListenAddress::Port(port) => {
+            let addr = format!("0.0.0.0:{}", port);
+            logger::info(format!("Starting web server on {addr}"));
+
+            let listener = tokio::net::TcpListener::bind(&addr).await?;
+            axum::serve(listener, app).await?;
+        }
+        #[cfg(unix)]
+        ListenAddress::UnixSocket(path) => {
+            remove_stale_socket(&path)?;
+            logger::info(format!("Starting web server on {}", path.display()));
+
+            let listener = tokio::net::UnixListener::bind(&path)?;
+            serve_unix(listener, app).await;
+        }
+        #[cfg(not(unix))]
+        ListenAddress::UnixSocket(path) => {
+            return Err(anyhow::anyhow!(
+                "Can't listen on {}, Unix sockets need a Unix system",
+                path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Serves `app` on the socket systemd passed
+#[cfg(unix)]
+async fn serve_inherited(listener: InheritedListener, app: Router) -> Result<(), anyhow::Error> {
+    match listener {
+        InheritedListener::Tcp(listener) => {
+            logger::info(format!(
+                "Starting web server on {} passed by systemd",
+                listener.local_addr()?
+            ));
+            axum::serve(tokio::net::TcpListener::from_std(listener)?, app).await?;
+        }
+        InheritedListener::Unix(listener) => {
+            logger::info("Starting web server on a Unix socket passed by systemd");
+            serve_unix(tokio::net::UnixListener::from_std(listener)?, app).await;
+        }
+    }
+    Ok(())
+}
+
+/// Builds the router with every endpoint and layer enabled in the configuration
+pub fn router() -> Router {
+    let mut app = Router::new()
+        .route("/dashboard", get(serve_dashboard))
         .route("/dashboard.svg", get(serve_svg))
         .route("/dashboard.png", get(serve_png))
+        .route("/dashboard.bmp", get(serve_bmp))
         .route("/dashboard.raw", get(serve_raw))
-        .route("/static/*path", get(serve_static));
+        .route("/dashboard.diff", get(serve_diff))
+        .route("/version", get(serve_version))
+        .route("/api/memory.json", get(serve_memory_json))
+        .route("/display.png", get(serve_display_png))
+        .route("/api/buttons/:button", post(press_button))
+        .route("/api/devices/:device/health", post(report_device_health))
+        .route("/api/devices/:device/config", get(serve_device_config))
+        .route("/api/devices.json", get(serve_devices_json))
+        .route("/api/forecast.json", get(serve_forecast_json))
+        .route("/api/diagnostics.json", get(serve_diagnostics_json))
+        .route("/openapi.json", get(serve_openapi))
+        .nest_service("/static", static_files_service())
+        .route("/p/:profile/dashboard", get(serve_profile_dashboard))
+        .route("/p/:profile/dashboard.svg", get(serve_profile_svg))
+        .route("/p/:profile/dashboard.png", get(serve_profile_png))
+        .route("/p/:profile/dashboard.raw", get(serve_profile_raw))
+        .route("/p/:profile/dashboard.diff", get(serve_profile_diff))
+        // The SVG links icons relative to the page, so profile pages need /static as well
+        .nest_service("/p/:profile/static", static_files_service())
+        // gzip/brotli for clients that ask for it, PNG is skipped as it's already compressed
+        .layer(CompressionLayer::new());
 
-    let addr = format!("0.0.0.0:{}", port);
-    println!("Starting web server on {}", addr);
+    if CONFIG.web_server.swagger_ui {
+        app = app.route("/swagger-ui", get(serve_swagger_ui));
+    }
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    if CONFIG.web_server.status_page {
+        app = app.route("/status", get(serve_status_page));
+    }
 
-    Ok(())
+    if let Some(cors) = cors_layer(
+        &CONFIG.web_server.cors_allowed_origins,
+        &CONFIG.web_server.cors_allowed_methods,
+    ) {
+        app = app.layer(cors);
+    }
+
+    app
+}
+
+/// First descriptor systemd passes to a socket activated service (`SD_LISTEN_FDS_START`)
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Pause after a failed `accept`, e.g. when the process is out of file descriptors
+#[cfg(unix)]
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+#[cfg(unix)]
+enum InheritedListener {
+    Tcp(std::net::TcpListener),
+    Unix(std::os::unix::net::UnixListener),
+}
+
+/// Takes the listening socket passed by systemd socket activation, if the process was started that way
+#[cfg(unix)]
+fn systemd_listener() -> std::io::Result<Option<InheritedListener>> {
+    let env_number = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+    };
+
+    // LISTEN_PID guards against using variables inherited from a parent that was activated
+    if env_number("LISTEN_PID") != Some(std::process::id()) {
+        return Ok(None);
+    }
+    let count = env_number("LISTEN_FDS").unwrap_or(0);
+    if count == 0 {
+        return Ok(None);
+    }
+    if count > 1 {
+        logger::warning(format!(
+            "systemd passed {count} sockets, only the first one is used"
+        ));
+    }
+
+    // SAFETY: systemd hands the descriptor over to this process and nothing else takes ownership of it
+    let fd = unsafe { OwnedFd::from_raw_fd(SD_LISTEN_FDS_START) };
+
+    // Only TCP sockets have an inet address, anything else is expected to be a Unix socket
+    let tcp = std::net::TcpListener::from(fd);
+    let listener = if tcp.local_addr().is_ok() {
+        tcp.set_nonblocking(true)?;
+        InheritedListener::Tcp(tcp)
+    } else {
+        let unix = std::os::unix::net::UnixListener::from(OwnedFd::from(tcp));
+        unix.set_nonblocking(true)?;
+        InheritedListener::Unix(unix)
+    };
+    Ok(Some(listener))
+}
+
+/// Removes a socket left behind by a previous run, binding fails while it exists
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> std::io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Serves `app` over HTTP/1 on a Unix socket, `axum::serve` only accepts TCP listeners
+#[cfg(unix)]
+pub async fn serve_unix(listener: tokio::net::UnixListener, app: Router) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                logger::warning(format!("Failed to accept a Unix socket connection: {e}"));
+                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                continue;
+            }
+        };
+
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                logger::warning(format!("Unix socket connection failed: {e}"));
+            }
+        });
+    }
+}
+
+/// OpenAPI description of the dashboard endpoints, served at `/openapi.json`
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Pi Inky Weather Display"),
+    paths(
+        serve_dashboard,
+        serve_svg,
+        serve_png,
+        serve_bmp,
+        serve_raw,
+        serve_diff,
+        serve_version,
+        serve_memory_json,
+        serve_display_png,
+        press_button,
+        report_device_health,
+        serve_device_config,
+        serve_devices_json,
+        serve_forecast_json,
+        serve_diagnostics_json,
+        serve_profile_dashboard,
+        serve_profile_svg,
+        serve_profile_png,
+        serve_profile_raw,
+        serve_profile_diff
+    )
+)]
+pub struct ApiDoc;
+
+async fn serve_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI page for `/openapi.json`, the UI assets are loaded from a CDN
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <title>Pi Inky Weather Display API</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script>
+        window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    </script>
+</body>
+</html>
+"##;
+
+async fn serve_swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
+
+/// Builds the CORS layer from the configured origins and methods, `None` when no origin is allowed.
+///
+/// `"*"` allows any origin. Entries that aren't valid origins or methods are logged and skipped.
+pub fn cors_layer(origins: &[String], methods: &[String]) -> Option<CorsLayer> {
+    if origins.is_empty() {
+        return None;
+    }
+
+    let allow_origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins.iter().filter_map(|origin| {
+            HeaderValue::from_str(origin)
+                .inspect_err(|_| logger::warning(format!("Ignoring invalid CORS origin: {origin}")))
+                .ok()
+        }))
+    };
+
+    let allow_methods: Vec<Method> = methods
+        .iter()
+        .filter_map(|method| {
+            Method::from_bytes(method.to_uppercase().as_bytes())
+                .inspect_err(|_| logger::warning(format!("Ignoring invalid CORS method: {method}")))
+                .ok()
+        })
+        .collect();
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            // Lets browser clients schedule their next refresh or retry, and check the data quality
+            .expose_headers([
+                HeaderName::from_static("x-next-delay"),
+                header::RETRY_AFTER,
+                HeaderName::from_static("x-dashboard-status"),
+                HeaderName::from_static("x-dashboard-diagnostics"),
+                HeaderName::from_static("x-full-clear"),
+                HeaderName::from_static("x-frame-checksum"),
+            ]),
+    )
+}
+
+/// Icons and fonts only change with a new release, clients may keep them for a day
+const STATIC_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Serves `static/` with path traversal protection, MIME detection, range and
+/// conditional (ETag and Last-Modified) requests, plus a Cache-Control header
+fn static_files_service() -> Router {
+    Router::new()
+        .fallback_service(SetResponseHeader::if_not_present(
+            ServeDir::new("static"),
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(STATIC_CACHE_CONTROL),
+        ))
+        .layer(middleware::from_fn(static_etag))
+}
+
+/// Weak ETag of a static file from its modification time and size, like nginx's
+fn static_file_etag(headers: &HeaderMap) -> Option<HeaderValue> {
+    let modified = headers.get(header::LAST_MODIFIED)?.to_str().ok()?;
+    let modified = chrono::DateTime::parse_from_rfc2822(modified).ok()?;
+    let length: u64 = headers
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    format!("W/\"{:x}-{length:x}\"", modified.timestamp())
+        .parse()
+        .ok()
+}
+
+/// Whether an `If-None-Match` header names `etag`, compared weakly as RFC 9110 asks
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+/// Adds the ETag to the static files and answers `304 Not Modified` when the client has it
+async fn static_etag(mut request: Request, next: Next) -> Response {
+    let if_none_match = request
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    if if_none_match.is_some() {
+        // If-None-Match takes precedence, ServeDir would answer If-Modified-Since on its own
+        request.headers_mut().remove(header::IF_MODIFIED_SINCE);
+    }
+
+    let mut response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    let Some(etag) = static_file_etag(response.headers()) else {
+        return response;
+    };
+
+    if if_none_match.is_some_and(|tags| etag_matches(&tags, etag.to_str().unwrap_or_default())) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        for name in [header::CACHE_CONTROL, header::LAST_MODIFIED] {
+            if let Some(value) = response.headers().get(&name) {
+                not_modified.headers_mut().insert(name, value.clone());
+            }
+        }
+        not_modified.headers_mut().insert(header::ETAG, etag);
+        return not_modified;
+    }
+    response.headers_mut().insert(header::ETAG, etag);
+    response
 }
 
 /// Calculate the X-Next-Delay header value in seconds based on current time and configuration
@@ -37,6 +437,11 @@ fn calculate_next_delay() -> u32 {
     let active_interval = CONFIG.web_server.active_hours_interval_seconds;
 
     let now = Local::now();
+
+    // The night layout doesn't change and skipped refreshes show nothing new until the quiet hours end
+    if let Some(quiet_hours) = CONFIG.schedule.quiet_hours_at(now) {
+        return quiet_hours.seconds_until_end(now);
+    }
     let current_hour = now.hour() as u8;
 
     // Check if we're in active hours (9:00-21:00)
@@ -67,8 +472,8 @@ fn calculate_next_delay() -> u32 {
     }
 }
 
-/// Create headers with X-Next-Delay for dashboard responses
-fn create_dashboard_headers(content_type: &str) -> HeaderMap {
+/// Create headers with X-Next-Delay, X-Dashboard-Status and X-Dashboard-Diagnostics for dashboard responses
+fn create_dashboard_headers(content_type: &str, diagnostics: &DashboardDiagnostics) -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
 
@@ -79,101 +484,1123 @@ fn create_dashboard_headers(content_type: &str) -> HeaderMap {
     ));
     headers.insert("X-Next-Delay", next_delay.to_string().parse().unwrap());
 
+    // Lets a polling display skip refreshing the panel with degraded data, details are at /api/diagnostics.json
+    headers.insert(
+        "X-Dashboard-Status",
+        diagnostics.status.to_string().parse().unwrap(),
+    );
+    let summaries: Vec<&str> = diagnostics
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.summary)
+        .collect();
+    headers.insert(
+        "X-Dashboard-Diagnostics",
+        serde_json::to_string(&summaries).unwrap().parse().unwrap(),
+    );
+
     headers
 }
 
+/// In the quiet hours' skip mode the display keeps its image: 204 No Content, with X-Next-Delay until they end
+fn quiet_hours_skip_response() -> Option<Response> {
+    let quiet_hours = CONFIG.schedule.quiet_hours_at(Local::now())?;
+    if quiet_hours.mode != QuietMode::Skip {
+        return None;
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "X-Next-Delay",
+        calculate_next_delay().to_string().parse().unwrap(),
+    );
+    Some((StatusCode::NO_CONTENT, headers).into_response())
+}
+
+/// Spaces out the refreshes of a display running low on battery
+fn insert_low_battery_delay(headers: &mut HeaderMap) {
+    let next_delay = calculate_next_delay().max(CONFIG.web_server.low_battery_interval_seconds);
+    headers.insert("X-Next-Delay", next_delay.to_string().parse().unwrap());
+}
+
+/// Counts the response as a display refresh and tells the display whether to clear the panel first
+fn insert_full_clear_header(headers: &mut HeaderMap) -> bool {
+    let full_clear = count_refresh(&cache_path(), CONFIG.web_server.full_clear_interval);
+    headers.insert("X-Full-Clear", full_clear.to_string().parse().unwrap());
+    full_clear
+}
+
+/// Remembers `frame` as the one the display shows and returns the one it showed before
+fn record_frame(headers: &mut HeaderMap, frame: &RawFrame) -> Option<RawFrame> {
+    headers.insert("X-Frame-Checksum", frame.checksum_hex().parse().unwrap());
+    swap_last_frame(&cache_path(), frame)
+}
+
+/// Media types `/dashboard` serves
+const DASHBOARD_MEDIA_TYPES: [(&str, DashboardFormat); 6] = [
+    ("image/svg+xml", DashboardFormat::Svg),
+    ("image/png", DashboardFormat::Png),
+    ("image/bmp", DashboardFormat::Bmp),
+    ("image/x-bmp", DashboardFormat::Bmp),
+    ("image/x-ms-bmp", DashboardFormat::Bmp),
+    ("application/octet-stream", DashboardFormat::Raw),
+];
+
+/// Formats a wildcard falls back to when the default is excluded, in order
+const DASHBOARD_FALLBACK_FORMATS: [DashboardFormat; 4] = [
+    DashboardFormat::Png,
+    DashboardFormat::Svg,
+    DashboardFormat::Bmp,
+    DashboardFormat::Raw,
+];
+
+/// One media range of an Accept header, e.g. `image/png; q=0.9; scale=2`
+struct MediaRange {
+    media_type: String,
+    quality: f32,
+    /// The parameters other than `q`
+    params: Vec<(String, String)>,
+}
+
+/// The media ranges of an Accept header, ranges with an invalid quality are left out
+fn parse_accept(accept: &str) -> Vec<MediaRange> {
+    accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let media_type = parts.next()?.trim().to_ascii_lowercase();
+            if media_type.is_empty() {
+                return None;
+            }
+            let mut quality = 1.0;
+            let mut params = Vec::new();
+            for param in parts {
+                let (name, value) = param.split_once('=')?;
+                let name = name.trim().to_ascii_lowercase();
+                let value = value.trim().trim_matches('"').to_string();
+                if name == "q" {
+                    quality = value.parse().ok().filter(|q| (0.0..=1.0).contains(q))?;
+                } else {
+                    params.push((name, value));
+                }
+            }
+            Some(MediaRange {
+                media_type,
+                quality,
+                params,
+            })
+        })
+        .collect()
+}
+
+/// Picks the format of `/dashboard` from the `accept` header, `default` for a client that accepts
+/// any format. Returns `None` when no format is acceptable, and the render parameters of the
+/// picked media range, e.g. `image/png; scale=2; palette=bw`, or a message for a 400 response
+/// when they're invalid.
+pub fn negotiate_dashboard_format(
+    accept: Option<&str>,
+    default: DashboardFormat,
+) -> Result<Option<(DashboardFormat, RenderQuery)>, String> {
+    let mut ranges = parse_accept(accept.unwrap_or_default());
+    if ranges.is_empty() {
+        return Ok(Some((default, RenderQuery::default())));
+    }
+    let excluded: Vec<DashboardFormat> = ranges
+        .iter()
+        .filter(|range| range.quality == 0.0)
+        .filter_map(|range| specific_format(&range.media_type))
+        .collect();
+    let acceptable = |format: &DashboardFormat| !excluded.contains(format);
+    // Most preferred first, a named type before a wildcard of the same quality
+    ranges.sort_by(|a, b| {
+        b.quality
+            .total_cmp(&a.quality)
+            .then(a.media_type.contains('*').cmp(&b.media_type.contains('*')))
+    });
+
+    for range in ranges.iter().filter(|range| range.quality > 0.0) {
+        let format = match range.media_type.as_str() {
+            "*/*" => Some(default)
+                .filter(acceptable)
+                .or_else(|| DASHBOARD_FALLBACK_FORMATS.into_iter().find(acceptable)),
+            "image/*" => Some(default)
+                .filter(|format| format.is_image() && acceptable(format))
+                .or_else(|| {
+                    DASHBOARD_FALLBACK_FORMATS
+                        .into_iter()
+                        .find(|format| format.is_image() && acceptable(format))
+                }),
+            media_type => specific_format(media_type).filter(acceptable),
+        };
+        if let Some(format) = format {
+            return Ok(Some((format, accept_render_query(&range.params)?)));
+        }
+    }
+    Ok(None)
+}
+
+fn specific_format(media_type: &str) -> Option<DashboardFormat> {
+    DASHBOARD_MEDIA_TYPES
+        .iter()
+        .find(|(name, _)| *name == media_type)
+        .map(|&(_, format)| format)
+}
+
+/// Render parameters given as media type parameters, unknown ones such as `charset` are ignored
+fn accept_render_query(params: &[(String, String)]) -> Result<RenderQuery, String> {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    serde_urlencoded::from_str(&query).map_err(|e| format!("Invalid Accept parameter: {e}"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard",
+    params(
+        RenderQuery,
+        ("Accept" = Option<String>, Header, description = "image/svg+xml, image/png, image/bmp or application/octet-stream for raw data, with q values. Render parameters may follow the media type, e.g. `image/png; scale=2; palette=bw`, the query string wins over them. A wildcard gets `default_dashboard_format`"),
+        ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware for raw data, 1 when missing, see `protocol`")
+    ),
+    responses(
+        (status = 200, description = "Dashboard in the format picked from the Accept header, see the Content-Type header"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 406, description = "The Accept header allows none of the dashboard formats"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_dashboard(request_headers: HeaderMap, Query(query): Query<RenderQuery>) -> Response {
+    let accept = request_headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok());
+    let mut response =
+        match negotiate_dashboard_format(accept, CONFIG.web_server.default_dashboard_format) {
+            Ok(Some((format, accept_query))) => {
+                let query = query.or(accept_query);
+                match format {
+                    DashboardFormat::Svg => serve_svg().await,
+                    DashboardFormat::Png => serve_png(Query(query)).await,
+                    DashboardFormat::Bmp => serve_bmp(Query(query)).await,
+                    DashboardFormat::Raw => serve_raw(request_headers, Query(query)).await,
+                }
+            }
+            Ok(None) => (
+                StatusCode::NOT_ACCEPTABLE,
+                "The dashboard is served as image/svg+xml, image/png, image/bmp or application/octet-stream",
+            )
+                .into_response(),
+            Err(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+        };
+    response
+        .headers_mut()
+        .insert(header::VARY, HeaderValue::from_static("Accept"));
+    response
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard.svg",
+    responses(
+        (status = 200, description = "Dashboard as an SVG image", content_type = "image/svg+xml"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
 async fn serve_svg() -> Response {
-    match generate_svg_data() {
-        Ok(svg_data) => (
+    if let Some(response) = quiet_hours_skip_response() {
+        return response;
+    }
+    match with_render_timeout(generate_svg_data(None)).await {
+        Ok(Ok(rendered)) => (
             StatusCode::OK,
-            create_dashboard_headers("image/svg+xml"),
-            svg_data,
+            create_dashboard_headers("image/svg+xml", &rendered.diagnostics),
+            rendered.svg,
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to generate SVG: {}", e),
+        Ok(Err(e)) => error_response("SVG", e),
+        Err(_) => timeout_response("SVG"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard.png",
+    params(RenderQuery),
+    responses(
+        (status = 200, description = "Dashboard as a PNG image", content_type = "image/png"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_png(Query(query): Query<RenderQuery>) -> Response {
+    let low_battery = query.low_battery();
+    let (scale_factor, display_options) = match query.into_options() {
+        Ok(options) => options,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    if let Some(response) = quiet_hours_skip_response() {
+        return response;
+    }
+    match with_render_timeout(generate_png_data(
+        scale_factor,
+        &display_options,
+        low_battery,
+    ))
+    .await
+    {
+        Ok(Ok((png_data, diagnostics))) => {
+            let mut headers = create_dashboard_headers("image/png", &diagnostics);
+            insert_full_clear_header(&mut headers);
+            if low_battery.is_some() {
+                insert_low_battery_delay(&mut headers);
+            }
+            (StatusCode::OK, headers, png_data).into_response()
+        }
+        Ok(Err(e)) => error_response("PNG", e),
+        Err(_) => timeout_response("PNG"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard.bmp",
+    params(RenderQuery),
+    responses(
+        (status = 200, description = "Dashboard as an uncompressed 24-bit BMP image", content_type = "image/bmp"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_bmp(Query(query): Query<RenderQuery>) -> Response {
+    let low_battery = query.low_battery();
+    let (scale_factor, display_options) = match query.into_options() {
+        Ok(options) => options,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    if let Some(response) = quiet_hours_skip_response() {
+        return response;
+    }
+    match with_render_timeout(generate_bmp_data(
+        scale_factor,
+        &display_options,
+        low_battery,
+    ))
+    .await
+    {
+        Ok(Ok((bmp_data, diagnostics))) => {
+            let mut headers = create_dashboard_headers("image/bmp", &diagnostics);
+            insert_full_clear_header(&mut headers);
+            if low_battery.is_some() {
+                insert_low_battery_delay(&mut headers);
+            }
+            (StatusCode::OK, headers, bmp_data).into_response()
+        }
+        Ok(Err(e)) => error_response("BMP", e),
+        Err(_) => timeout_response("BMP"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard.raw",
+    params(RenderQuery, ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware, 1 when missing, see `protocol`")),
+    responses(
+        (status = 200, description = "Dashboard as raw 4-bit packed display data, 7-colour palette indices or IT8951 grey levels", content_type = "application/octet-stream"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_raw(request_headers: HeaderMap, Query(query): Query<RenderQuery>) -> Response {
+    let protocol = match raw_protocol(&request_headers, &query) {
+        Ok(protocol) => protocol,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    let low_battery = query.low_battery();
+    let (scale_factor, display_options) = match query.into_options() {
+        Ok(options) => options,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    let display_options = display_options_for(protocol, display_options);
+    if let Some(response) = quiet_hours_skip_response() {
+        return response;
+    }
+    match with_render_timeout(generate_raw_data(
+        scale_factor,
+        &display_options,
+        low_battery,
+    ))
+    .await
+    {
+        Ok(Ok((frame, diagnostics))) => {
+            let mut headers = create_dashboard_headers("application/octet-stream", &diagnostics);
+            insert_full_clear_header(&mut headers);
+            if low_battery.is_some() {
+                insert_low_battery_delay(&mut headers);
+            }
+            insert_protocol_header(&mut headers, protocol);
+            record_frame(&mut headers, &frame);
+            (StatusCode::OK, headers, frame.data).into_response()
+        }
+        Ok(Err(e)) => error_response("RAW", e),
+        Err(_) => timeout_response("RAW"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/dashboard.diff",
+    params(RenderQuery, DiffQuery, ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware, 1 when missing, see `protocol`")),
+    responses(
+        (status = 200, description = "Rectangles of the raw display data that changed since the previous frame, see `raw_diff`", content_type = "application/octet-stream"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 426, description = "The display firmware is too old for raw diffs, see X-Protocol-Version"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_diff(
+    request_headers: HeaderMap,
+    Query(query): Query<RenderQuery>,
+    Query(diff_query): Query<DiffQuery>,
+) -> Response {
+    let protocol = match raw_protocol(&request_headers, &query) {
+        Ok(protocol) => protocol,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    if !protocol.supports_diff() {
+        return (
+            StatusCode::UPGRADE_REQUIRED,
+            format!(
+                "Raw diffs need protocol version 2, the display speaks {}",
+                protocol.0
+            ),
         )
-            .into_response(),
+            .into_response();
+    }
+    let low_battery = query.low_battery();
+    let (scale_factor, display_options) = match query.into_options() {
+        Ok(options) => options,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    if let Some(response) = quiet_hours_skip_response() {
+        return response;
+    }
+    match with_render_timeout(generate_raw_data(
+        scale_factor,
+        &display_options,
+        low_battery,
+    ))
+    .await
+    {
+        Ok(Ok((frame, diagnostics))) => {
+            let mut headers = create_dashboard_headers("application/octet-stream", &diagnostics);
+            let full_clear = insert_full_clear_header(&mut headers);
+            insert_protocol_header(&mut headers, protocol);
+            if low_battery.is_some() {
+                insert_low_battery_delay(&mut headers);
+            }
+            // A cleared panel, or one that doesn't say it shows the last frame served, needs the
+            // whole frame: displays sharing the server overwrite each other's last frame
+            let previous = record_frame(&mut headers, &frame).filter(|previous| {
+                !full_clear
+                    && diff_query
+                        .since
+                        .as_ref()
+                        .is_some_and(|since| since.eq_ignore_ascii_case(&previous.checksum_hex()))
+            });
+            let diff = diff_frames(previous.as_ref(), &frame);
+            (StatusCode::OK, headers, diff.to_bytes()).into_response()
+        }
+        Ok(Err(e)) => error_response("diff", e),
+        Err(_) => timeout_response("diff"),
     }
 }
 
-async fn serve_png() -> Response {
-    match generate_png_data() {
-        Ok(png_data) => (
-            StatusCode::OK,
-            create_dashboard_headers("image/png"),
-            png_data,
+#[utoipa::path(
+    get,
+    path = "/version",
+    responses(
+        (status = 200, description = "Release of the server and the protocol versions it serves to display firmware", body = VersionInfo, content_type = "application/json"),
+    )
+)]
+async fn serve_version() -> Json<VersionInfo> {
+    Json(VersionInfo::current())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/memory.json",
+    responses(
+        (status = 200, description = "Resident memory of the server, to watch for growth over months of renders", body = MemoryUsage, content_type = "application/json"),
+        (status = 501, description = "Memory use isn't available on this system, it's read from /proc"),
+    )
+)]
+async fn serve_memory_json() -> Response {
+    match MemoryUsage::current() {
+        Some(usage) => (StatusCode::OK, Json(usage)).into_response(),
+        None => (
+            StatusCode::NOT_IMPLEMENTED,
+            "Memory use isn't available on this system",
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to generate PNG: {}", e),
+    }
+}
+
+/// Protocol version a raw request is served with, or a message for a 400 response. Asking for
+/// IT8951 grey levels needs firmware that knows them.
+fn raw_protocol(
+    request_headers: &HeaderMap,
+    query: &RenderQuery,
+) -> Result<ProtocolVersion, String> {
+    let requested = request_headers
+        .get(PROTOCOL_VERSION_HEADER)
+        .map(|version| version.to_str().unwrap_or_default());
+    let protocol = ProtocolVersion::negotiate(requested)?;
+    if query.driver == Some(DisplayDriver::It8951) && !protocol.supports_greyscale() {
+        return Err(format!(
+            "driver=it8951 needs protocol version 2, the display speaks {}",
+            protocol.0
+        ));
+    }
+    Ok(protocol)
+}
+
+/// Firmware without greyscale support gets ACeP frames, even when the config picks IT8951 panels
+fn display_options_for(
+    protocol: ProtocolVersion,
+    display_options: DisplayOptions,
+) -> DisplayOptions {
+    if protocol.supports_greyscale() || display_options.driver == DisplayDriver::Acep {
+        return display_options;
+    }
+    logger::warning(format!(
+        "Display speaks protocol version {}, serving an ACeP frame instead of IT8951 grey levels",
+        protocol.0
+    ));
+    DisplayOptions {
+        driver: DisplayDriver::Acep,
+        ..display_options
+    }
+}
+
+fn insert_protocol_header(headers: &mut HeaderMap, protocol: ProtocolVersion) {
+    headers.insert(PROTOCOL_VERSION_HEADER, protocol.0.into());
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/forecast.json",
+    responses(
+        (status = 200, description = "Forecast data the dashboard renders", body = ForecastJson, content_type = "application/json"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_forecast_json() -> Response {
+    match with_render_timeout(generate_forecast_json(&SystemClock)).await {
+        Ok(Ok(forecast)) => (StatusCode::OK, Json(forecast)).into_response(),
+        Ok(Err(e)) => error_response("JSON", e),
+        Err(_) => timeout_response("JSON"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/diagnostics.json",
+    responses(
+        (status = 200, description = "Status and diagnostics of the dashboard, e.g. stale cached data or missing days", body = DashboardDiagnostics, content_type = "application/json"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_diagnostics_json() -> Response {
+    match with_render_timeout(generate_dashboard_diagnostics(&SystemClock)).await {
+        Ok(Ok(diagnostics)) => (StatusCode::OK, Json(diagnostics)).into_response(),
+        Ok(Err(e)) => error_response("diagnostics", e),
+        Err(_) => timeout_response("diagnostics"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/p/{profile}/dashboard",
+    params(
+        ("profile" = String, Path, description = "Profile name from the `[profiles]` config"),
+        RenderQuery,
+        ("Accept" = Option<String>, Header, description = "Format and render parameters as for `/dashboard`"),
+        ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware for raw data, 1 when missing, see `protocol`")
+    ),
+    responses(
+        (status = 200, description = "Dashboard of the profile in the format picked from the Accept header, see the Content-Type header"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 404, description = "Unknown profile"),
+        (status = 406, description = "The Accept header allows none of the dashboard formats"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_profile_dashboard(
+    Path(profile): Path<String>,
+    request_headers: HeaderMap,
+    query: Query<RenderQuery>,
+) -> Response {
+    in_profile(&profile, serve_dashboard(request_headers, query)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/p/{profile}/dashboard.svg",
+    params(("profile" = String, Path, description = "Profile name from the `[profiles]` config")),
+    responses(
+        (status = 200, description = "Dashboard of the profile as an SVG image", content_type = "image/svg+xml"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 404, description = "Unknown profile"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_profile_svg(Path(profile): Path<String>) -> Response {
+    in_profile(&profile, serve_svg()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/p/{profile}/dashboard.png",
+    params(
+        ("profile" = String, Path, description = "Profile name from the `[profiles]` config"),
+        RenderQuery
+    ),
+    responses(
+        (status = 200, description = "Dashboard of the profile as a PNG image", content_type = "image/png"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters"),
+        (status = 404, description = "Unknown profile"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_profile_png(Path(profile): Path<String>, query: Query<RenderQuery>) -> Response {
+    in_profile(&profile, serve_png(query)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/p/{profile}/dashboard.raw",
+    params(
+        ("profile" = String, Path, description = "Profile name from the `[profiles]` config"),
+        RenderQuery,
+        ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware, 1 when missing, see `protocol`")
+    ),
+    responses(
+        (status = 200, description = "Dashboard of the profile as raw 4-bit packed display data, 7-colour palette indices or IT8951 grey levels", content_type = "application/octet-stream"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 404, description = "Unknown profile"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_profile_raw(
+    Path(profile): Path<String>,
+    request_headers: HeaderMap,
+    query: Query<RenderQuery>,
+) -> Response {
+    in_profile(&profile, serve_raw(request_headers, query)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/p/{profile}/dashboard.diff",
+    params(
+        ("profile" = String, Path, description = "Profile name from the `[profiles]` config"),
+        RenderQuery,
+        DiffQuery,
+        ("X-Protocol-Version" = Option<u16>, Header, description = "Protocol version of the display firmware, 1 when missing, see `protocol`")
+    ),
+    responses(
+        (status = 200, description = "Rectangles of the profile's raw display data that changed since the previous frame", content_type = "application/octet-stream"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters or protocol version"),
+        (status = 426, description = "The display firmware is too old for raw diffs, see X-Protocol-Version"),
+        (status = 404, description = "Unknown profile"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_profile_diff(
+    Path(profile): Path<String>,
+    request_headers: HeaderMap,
+    query: Query<RenderQuery>,
+    diff_query: Query<DiffQuery>,
+) -> Response {
+    in_profile(&profile, serve_diff(request_headers, query, diff_query)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/display.png",
+    params(RenderQuery),
+    responses(
+        (status = 200, description = "Dashboard of the layout selected with the display buttons as a PNG image", content_type = "image/png"),
+        (status = 204, description = "Quiet hours in skip mode, the display keeps its image until X-Next-Delay"),
+        (status = 400, description = "Invalid render parameters"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_display_png(query: Query<RenderQuery>) -> Response {
+    match selected_layout() {
+        Some(profile) => in_profile(profile, serve_png(query)).await,
+        None => serve_png(query).await,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/buttons/{button}",
+    params(
+        ("button" = String, Path, description = "Pressed button: a, b, c or d"),
+        RenderQuery
+    ),
+    responses(
+        (status = 200, description = "Image to show for the button's action, the layout on the display or the self-test screen", content_type = "image/png"),
+        (status = 204, description = "The button does nothing, or quiet hours in skip mode"),
+        (status = 400, description = "Unknown button or invalid render parameters"),
+        (status = 500, description = "Dashboard generation failed"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn press_button(Path(button): Path<Button>, query: Query<RenderQuery>) -> Response {
+    let action = CONFIG.web_server.buttons.action(button);
+    logger::info(format!("Button {button} pressed: {action}"));
+    match action {
+        ButtonAction::None => StatusCode::NO_CONTENT.into_response(),
+        ButtonAction::Refresh => serve_display_png(query).await,
+        ButtonAction::NextLayout => {
+            next_layout();
+            serve_display_png(query).await
+        }
+        ButtonAction::Diagnostics => serve_self_test_png(query.0).await,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/devices/{device}/health",
+    params(("device" = String, Path, description = "Id of the display, up to 32 letters, digits, `-` or `_`")),
+    request_body = BootReport,
+    responses(
+        (status = 204, description = "Report stored"),
+        (status = 400, description = "Invalid display id"),
+        (status = 422, description = "The body isn't a boot report"),
+        (status = 500, description = "The report couldn't be stored"),
+    )
+)]
+async fn report_device_health(
+    Path(device): Path<String>,
+    Json(report): Json<BootReport>,
+) -> Response {
+    if !is_valid_device_id(&device) {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Display ids are 1 to {MAX_DEVICE_ID_LEN} letters, digits, '-' or '_'"),
         )
-            .into_response(),
+            .into_response();
+    }
+    if report.is_watchdog_reset() {
+        logger::warning(format!("Display {device} was reset by its watchdog"));
+    }
+    match record_report(&cache_path(), &device, report, chrono::Utc::now()) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            logger::error(format!(
+                "Failed to store the report of display {device}: {e}"
+            ));
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to store the report: {e}"),
+            )
+                .into_response()
+        }
     }
 }
 
-async fn serve_raw() -> Response {
-    match generate_raw_data() {
-        Ok(raw_data) => (
+#[utoipa::path(
+    get,
+    path = "/api/devices/{device}/config",
+    params(
+        ("device" = String, Path, description = "Id of the display, up to 32 letters, digits, `-` or `_`"),
+        DeviceConfigQuery,
+    ),
+    responses(
+        (status = 200, description = "Settings of the display with the recommended refresh schedule", body = DeviceConfig, content_type = "application/json"),
+        (status = 400, description = "Invalid display id or battery level"),
+        (status = 502, description = "Weather provider failed and no cached data is available"),
+        (status = 503, description = "Generation timed out, see the Retry-After header"),
+    )
+)]
+async fn serve_device_config(
+    Path(device): Path<String>,
+    Query(query): Query<DeviceConfigQuery>,
+) -> Response {
+    if !is_valid_device_id(&device) {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Display ids are 1 to {MAX_DEVICE_ID_LEN} letters, digits, '-' or '_'"),
+        )
+            .into_response();
+    }
+    if query.battery.is_some_and(|level| level > 100) {
+        return (
+            StatusCode::BAD_REQUEST,
+            "battery must be a percentage from 0 to 100",
+        )
+            .into_response();
+    }
+    match with_render_timeout(generate_refresh_schedule(&SystemClock)).await {
+        Ok(Ok(refresh_schedule)) => (
             StatusCode::OK,
-            create_dashboard_headers("application/octet-stream"),
-            raw_data,
+            Json(DeviceConfig {
+                low_battery_threshold: CONFIG.web_server.low_battery_threshold,
+                refresh_schedule: match query.low_battery() {
+                    Some(_) => {
+                        refresh_schedule.low_battery(CONFIG.web_server.low_battery_interval_seconds)
+                    }
+                    None => refresh_schedule,
+                },
+            }),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to generate RAW: {}", e),
+        Ok(Err(e)) => error_response("device config", e),
+        Err(_) => timeout_response("device config"),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/devices.json",
+    responses(
+        (status = 200, description = "Health of each display that reported its boots, by id", body = [DeviceSummary], content_type = "application/json"),
+    )
+)]
+async fn serve_devices_json() -> Json<Vec<DeviceSummary>> {
+    Json(device_summaries(&cache_path()))
+}
+
+async fn serve_status_page() -> Html<String> {
+    Html(status_page_html(
+        &device_summaries(&cache_path()),
+        chrono::Utc::now(),
+    ))
+}
+
+/// The self-test screen as a PNG, for the diagnostics button
+async fn serve_self_test_png(query: RenderQuery) -> Response {
+    let (scale_factor, display_options) = match query.into_options() {
+        Ok(options) => options,
+        Err(message) => return (StatusCode::BAD_REQUEST, message).into_response(),
+    };
+    let network = check_network().await;
+    let svg = self_test_svg(&network, &SystemClock);
+    let png_data =
+        convert_svg_to_png_bytes(&svg, scale_factor, CONFIG.render_options.anti_aliasing)
+            .and_then(|png_bytes| apply_display_options_to_png_bytes(png_bytes, &display_options))
+            .map_err(WeatherEpdError::convert);
+    match png_data {
+        Ok(png_data) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "image/png")],
+            png_data,
         )
             .into_response(),
+        Err(e) => error_response("self-test PNG", e),
     }
 }
 
-fn generate_svg_data() -> Result<String, anyhow::Error> {
-    let clock = SystemClock;
-    let input_template_name = &CONFIG.misc.template_path;
-    generate_dashboard_svg_string(&clock, input_template_name)
+/// Handles a request with the named profile's settings, unknown profiles are a 404
+async fn in_profile(name: &str, handler: impl Future<Output = Response>) -> Response {
+    match CONFIG.profile(name) {
+        Some(profile) => with_profile(profile, handler).await,
+        None => (StatusCode::NOT_FOUND, format!("Unknown profile: {name}")).into_response(),
+    }
 }
 
-fn generate_png_data() -> Result<Vec<u8>, anyhow::Error> {
-    let svg_data = generate_svg_data()?;
-    let png_bytes = convert_svg_to_png_bytes(&svg_data, CONFIG.misc.png_scale_factor)?;
-    Ok(png_bytes)
+/// Battery level a display reports when fetching its config
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeviceConfigQuery {
+    /// Battery level of the display in percent, a low level spaces out the refresh schedule
+    pub battery: Option<u8>,
 }
 
-fn generate_raw_data() -> Result<Vec<u8>, anyhow::Error> {
-    let png_data = generate_png_data()?;
-    let raw_bytes = convert_png_bytes_to_raw_7color(&png_data)?;
-    Ok(raw_bytes)
+impl DeviceConfigQuery {
+    /// The reported battery level, if it is below `low_battery_threshold`
+    pub fn low_battery(&self) -> Option<u8> {
+        self.battery
+            .filter(|&level| level < CONFIG.web_server.low_battery_threshold)
+    }
 }
 
-async fn serve_static(Path(path): Path<String>) -> Response {
-    let file_path = PathBuf::from("static").join(&path);
+/// Part of the low power layout's height the battery banner takes, 50px of the bundled 480px one
+const LOW_BATTERY_BANNER_SHARE: f32 = 50.0 / 480.0;
 
-    match tokio::fs::read(&file_path).await {
-        Ok(contents) => {
-            let content_type = if path.ends_with(".svg") {
-                "image/svg+xml"
-            } else if path.ends_with(".png") {
-                "image/png"
-            } else if path.ends_with(".jpg") || path.ends_with(".jpeg") {
-                "image/jpeg"
-            } else if path.ends_with(".css") {
-                "text/css"
-            } else if path.ends_with(".js") {
-                "application/javascript"
-            } else {
-                "application/octet-stream"
-            };
+/// Largest `scale` accepted per request, bigger images take too long to render on a Pi
+const MAX_REQUEST_SCALE_FACTOR: f32 = 4.0;
 
-            (
-                StatusCode::OK,
-                [(header::CONTENT_TYPE, content_type)],
-                contents,
-            )
-                .into_response()
+/// Per-request overrides for the PNG, BMP and raw endpoints, so different displays can share one server
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RenderQuery {
+    /// PNG scale factor, defaults to `png_scale_factor` from the config
+    pub scale: Option<f32>,
+    /// Clockwise rotation in degrees: 0, 90, 180 or 270
+    #[param(value_type = Option<u16>)]
+    pub rotate: Option<Rotation>,
+    /// Restrict the image to the colours the panel can show
+    pub palette: Option<Palette>,
+    /// How colours are mapped onto the palette
+    pub dither: Option<Dither>,
+    /// Battery level of the display in percent, a low level gets the low power layout
+    pub battery: Option<u8>,
+    /// Panel family, defaults to `display_driver` from the config
+    pub driver: Option<DisplayDriver>,
+}
+
+impl RenderQuery {
+    /// The reported battery level, if it is below `low_battery_threshold`
+    pub fn low_battery(&self) -> Option<u8> {
+        self.battery
+            .filter(|&level| level < CONFIG.web_server.low_battery_threshold)
+    }
+
+    /// Fills the parameters not given here from `other`
+    pub fn or(self, other: RenderQuery) -> RenderQuery {
+        RenderQuery {
+            scale: self.scale.or(other.scale),
+            rotate: self.rotate.or(other.rotate),
+            palette: self.palette.or(other.palette),
+            dither: self.dither.or(other.dither),
+            battery: self.battery.or(other.battery),
+            driver: self.driver.or(other.driver),
+        }
+    }
+
+    /// Returns the scale factor and display options, or a message for a 400 response
+    pub fn into_options(self) -> Result<(f32, DisplayOptions), String> {
+        if self.battery.is_some_and(|level| level > 100) {
+            return Err("battery must be a percentage from 0 to 100".to_string());
+        }
+        let scale_factor = self.scale.unwrap_or(CONFIG.misc.png_scale_factor);
+        if !(scale_factor > 0.0 && scale_factor <= MAX_REQUEST_SCALE_FACTOR) {
+            return Err(format!(
+                "scale must be greater than 0 and at most {MAX_REQUEST_SCALE_FACTOR}"
+            ));
         }
-        Err(_) => (StatusCode::NOT_FOUND, format!("File not found: {}", path)).into_response(),
+
+        let driver = self.driver.unwrap_or(CONFIG.misc.display_driver);
+        if driver == DisplayDriver::It8951 && self.palette.is_some() {
+            return Err(
+                "palette doesn't apply to it8951 panels, they show grey levels".to_string(),
+            );
+        }
+
+        Ok((
+            scale_factor,
+            DisplayOptions {
+                rotation: self.rotate.unwrap_or_default(),
+                palette: self.palette,
+                dither: self.dither.unwrap_or_default(),
+                driver,
+            },
+        ))
+    }
+}
+
+/// Frame the display shows, for the diff endpoint
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffQuery {
+    /// `X-Frame-Checksum` of the frame the display shows, the whole frame is sent when it's
+    /// missing or not the last one served
+    pub since: Option<String>,
+}
+
+/// Maps a dashboard failure to an HTTP status, upstream weather API problems are a bad gateway
+fn error_status(error: &WeatherEpdError) -> StatusCode {
+    match error {
+        WeatherEpdError::Provider(_) => StatusCode::BAD_GATEWAY,
+        WeatherEpdError::Config(_)
+        | WeatherEpdError::Render(_)
+        | WeatherEpdError::Convert(_)
+        | WeatherEpdError::Output(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error_response(format: &str, error: WeatherEpdError) -> Response {
+    logger::error(format!("Failed to generate {format}: {error}"));
+    (
+        error_status(&error),
+        format!("Failed to generate {format}: {error}"),
+    )
+        .into_response()
+}
+
+/// Seconds a client should wait before retrying after a render timeout
+const RENDER_TIMEOUT_RETRY_AFTER_SECONDS: u32 = 60;
+
+/// Aborts dashboard generation after the configured render timeout,
+/// so a hung provider call doesn't hold on to the request forever.
+/// The memory freed by the render is handed back to the system afterwards, see [`memory`].
+async fn with_render_timeout<T>(
+    generation: impl Future<Output = T>,
+) -> Result<T, tokio::time::error::Elapsed> {
+    let render_timeout = Duration::from_secs(CONFIG.web_server.render_timeout_seconds);
+    let result = tokio::time::timeout(render_timeout, generation).await;
+    memory::after_render(CONFIG.web_server.memory_limit_mb);
+    result
+}
+
+fn timeout_response(format: &str) -> Response {
+    logger::error(format!(
+        "Timed out generating {format} after {}s",
+        CONFIG.web_server.render_timeout_seconds
+    ));
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(
+            header::RETRY_AFTER,
+            RENDER_TIMEOUT_RETRY_AFTER_SECONDS.to_string(),
+        )],
+        format!("Timed out generating {format}, try again later"),
+    )
+        .into_response()
+}
+
+/// Renders the dashboard, or the low power layout with a warning banner for a `low_battery` level
+async fn generate_svg_data(low_battery: Option<u8>) -> Result<RenderedDashboard, WeatherEpdError> {
+    let clock = SystemClock;
+    if let Some(quiet_hours) = CONFIG.schedule.quiet_hours_at(clock.now_local()) {
+        return Ok(RenderedDashboard {
+            svg: render_night_svg(&quiet_hours),
+            diagnostics: DashboardDiagnostics::new(&[]),
+        });
     }
+
+    let Some(level) = low_battery else {
+        let input_template_name = &CONFIG.misc.template_path;
+        return generate_dashboard_svg_string_catch_panic(&clock, input_template_name).await;
+    };
+    let input_template_name = &CONFIG.web_server.low_battery_template_path;
+    let rendered = generate_dashboard_svg_string_catch_panic(&clock, input_template_name).await?;
+    let Some((width, height)) = svg_size(&rendered.svg) else {
+        logger::warning("The low battery template has no size, leaving out the battery banner");
+        return Ok(rendered);
+    };
+    Ok(RenderedDashboard {
+        svg: composite_panels(rendered.svg, &[low_battery_banner(level, width, height)]),
+        diagnostics: rendered.diagnostics,
+    })
+}
+
+/// Banner across the top of a `width` x `height` low power layout
+pub fn low_battery_banner(level: u8, width: f32, height: f32) -> Panel {
+    let banner_height = (height * LOW_BATTERY_BANNER_SHARE).round();
+    Panel::svg(
+        0.0,
+        0.0,
+        width,
+        banner_height,
+        format!(
+            r#"<rect width="{width}" height="{banner_height}" fill="red" /><text x="{}" y="{}" font-size="{}" fill="white" text-anchor="middle" dominant-baseline="middle">Battery low ({level}%), please charge the display</text>"#,
+            width / 2.0,
+            banner_height / 2.0,
+            (banner_height * 0.48).round()
+        ),
+    )
+}
+
+async fn generate_png_data(
+    scale_factor: f32,
+    display_options: &DisplayOptions,
+    low_battery: Option<u8>,
+) -> Result<(Vec<u8>, DashboardDiagnostics), WeatherEpdError> {
+    let rendered = generate_svg_data(low_battery).await?;
+    let png_bytes = convert_svg_to_png_bytes(
+        &rendered.svg,
+        scale_factor,
+        CONFIG.render_options.anti_aliasing,
+    )
+    .map_err(WeatherEpdError::convert)?;
+    let png_bytes = apply_display_options_to_png_bytes(png_bytes, display_options)
+        .map_err(WeatherEpdError::convert)?;
+    Ok((png_bytes, rendered.diagnostics))
+}
+
+async fn generate_bmp_data(
+    scale_factor: f32,
+    display_options: &DisplayOptions,
+    low_battery: Option<u8>,
+) -> Result<(Vec<u8>, DashboardDiagnostics), WeatherEpdError> {
+    let (png_data, diagnostics) =
+        generate_png_data(scale_factor, display_options, low_battery).await?;
+    let bmp_data = convert_png_to_bmp_bytes(&png_data).map_err(WeatherEpdError::convert)?;
+    Ok((bmp_data, diagnostics))
+}
+
+async fn generate_raw_data(
+    scale_factor: f32,
+    display_options: &DisplayOptions,
+    low_battery: Option<u8>,
+) -> Result<(RawFrame, DashboardDiagnostics), WeatherEpdError> {
+    let rendered = generate_svg_data(low_battery).await?;
+    let png_bytes = convert_svg_to_png_bytes(
+        &rendered.svg,
+        scale_factor,
+        CONFIG.render_options.anti_aliasing,
+    )
+    .map_err(WeatherEpdError::convert)?;
+    let image = image::load_from_memory(&png_bytes)
+        .map_err(WeatherEpdError::convert)?
+        .to_rgb8();
+    // Compensated before the display options map it onto the panel's colours
+    let image = display_options.apply(compensate_against_previous(
+        image,
+        &cache_path(),
+        CONFIG.misc.ghosting_compensation,
+    ));
+    let (width, height) = image.dimensions();
+    let frame = RawFrame::new(
+        width,
+        height,
+        display_options.driver,
+        display_options.driver.to_raw(&image),
+    )
+    .map_err(WeatherEpdError::convert)?;
+    Ok((frame, rendered.diagnostics))
 }
@@ -0,0 +1,35 @@
+use pi_inky_weather_epd::utils::convert_svg_to_png_bytes;
+use std::collections::HashSet;
+
+const SVG: &str = r#"<svg width="100" height="60" xmlns="http://www.w3.org/2000/svg">
+    <rect width="100" height="60" fill="white"/>
+    <circle cx="30" cy="30" r="20" fill="red"/>
+    <path d="M55 55 L95 5" stroke="black" stroke-width="2.5"/>
+    <text x="55" y="50" font-size="16" fill="blue">Hi</text>
+</svg>"#;
+
+fn colours(png: &[u8]) -> HashSet<[u8; 3]> {
+    image::load_from_memory(png)
+        .unwrap()
+        .to_rgb8()
+        .pixels()
+        .map(|pixel| pixel.0)
+        .collect()
+}
+
+#[test]
+fn test_anti_aliased_edges_blend_colours() {
+    let png = convert_svg_to_png_bytes(SVG, 1.0, true).unwrap();
+
+    assert!(colours(&png).len() > 4);
+}
+
+#[test]
+fn test_crisp_edges_only_use_the_drawn_colours() {
+    let png = convert_svg_to_png_bytes(SVG, 1.0, false).unwrap();
+
+    let expected: HashSet<[u8; 3]> = [[255, 255, 255], [255, 0, 0], [0, 0, 0], [0, 0, 255]]
+        .into_iter()
+        .collect();
+    assert!(colours(&png).is_subset(&expected));
+}
@@ -0,0 +1,171 @@
+mod helpers;
+
+use chrono::{DateTime, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::apis::bom::models::{
+    LocationResponse, ObservationsResponse, WarningsResponse,
+};
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Temperature};
+use pi_inky_weather_epd::errors::{DashboardError, DiagnosticPriority};
+
+fn observations() -> ObservationsResponse {
+    serde_json::from_str(include_str!("fixtures/bom_observations.json")).unwrap()
+}
+
+fn warnings() -> WarningsResponse {
+    serde_json::from_str(include_str!("fixtures/bom_warnings.json")).unwrap()
+}
+
+fn hour(time: DateTime<Utc>, temperature: Temperature) -> HourlyForecast {
+    HourlyForecast {
+        temperature,
+        apparent_temperature: temperature,
+        relative_humidity: 50,
+        ..forecast::hour(time)
+    }
+}
+
+fn forecast(temperature: Temperature) -> Vec<HourlyForecast> {
+    (9..12)
+        .map(|h| {
+            hour(
+                Utc.with_ymd_and_hms(2025, 10, 25, h, 0, 0).unwrap(),
+                temperature,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_observation_replaces_the_hour_it_was_made_in() {
+    let mut hourly = forecast(Temperature::celsius(14.0));
+
+    observations().merge_into(&mut hourly);
+
+    // Observed at 10:30 UTC
+    let observed = &hourly[1];
+    assert_eq!(observed.temperature, Temperature::celsius(17.6));
+    assert_eq!(observed.apparent_temperature, Temperature::celsius(15.2));
+    assert_eq!(
+        (observed.wind.speed_kmh, observed.wind.gust_speed_kmh),
+        (19, 31)
+    );
+    assert_eq!(observed.relative_humidity, 71);
+    assert_eq!(hourly[0].temperature, Temperature::celsius(14.0));
+    assert_eq!(hourly[2].temperature, Temperature::celsius(14.0));
+}
+
+#[test]
+fn test_observation_keeps_the_forecast_unit() {
+    let mut hourly = forecast(Temperature::fahrenheit(57.0));
+
+    observations().merge_into(&mut hourly);
+
+    assert_eq!(
+        hourly[1].temperature,
+        Temperature::celsius(17.6).to_fahrenheit()
+    );
+}
+
+#[test]
+fn test_observation_outside_the_forecast_is_ignored() {
+    let mut hourly = vec![hour(
+        Utc.with_ymd_and_hms(2025, 10, 25, 11, 0, 0).unwrap(),
+        Temperature::celsius(14.0),
+    )];
+
+    observations().merge_into(&mut hourly);
+
+    assert_eq!(hourly[0].temperature, Temperature::celsius(14.0));
+    assert_eq!(hourly[0].relative_humidity, 50);
+}
+
+#[test]
+fn test_cancelled_and_expired_warnings_are_left_out() {
+    let now = Utc.with_ymd_and_hms(2025, 10, 25, 10, 45, 0).unwrap();
+
+    let active = warnings().active_warnings(now);
+
+    assert_eq!(active.len(), 1);
+    assert!(matches!(
+        &active[0],
+        DashboardError::WeatherWarning { details } if details == "Frost Warning for Central district"
+    ));
+}
+
+#[test]
+fn test_marine_wind_warnings_in_force() {
+    let now = Utc.with_ymd_and_hms(2025, 10, 25, 10, 45, 0).unwrap();
+    let mut response = warnings();
+    assert!(response.marine_wind_warnings(now).is_empty(), "cancelled");
+
+    response.data[2].phase = Some("update".to_string());
+
+    // The frost warning is in force too, but isn't a marine wind warning
+    assert_eq!(
+        response.marine_wind_warnings(now),
+        vec!["Strong Wind Warning for Port Phillip".to_string()]
+    );
+}
+
+#[test]
+fn test_warning_is_shown_over_stale_data() {
+    let now = Utc.with_ymd_and_hms(2025, 10, 25, 10, 45, 0).unwrap();
+    let mut builder = ContextBuilder::new();
+    builder.with_warning(DashboardError::NoInternet {
+        details: "timeout".to_string(),
+    });
+    for warning in warnings().active_warnings(now) {
+        assert_eq!(warning.priority(), DiagnosticPriority::High);
+        builder.with_warning(warning);
+    }
+
+    assert_eq!(
+        builder.context.diagnostic_message,
+        "Weather warning in force"
+    );
+    assert!(builder
+        .context
+        .diagnostic_icons_svg
+        .contains("flag-storm-warning.svg"));
+}
+
+#[test]
+fn test_rain_gauge_reading_counts_from_9am() {
+    let reading = observations()
+        .rain_gauge_reading(chrono_tz::Australia::Melbourne)
+        .unwrap();
+
+    assert_eq!(reading.rain_mm, 0.4);
+    assert_eq!(
+        reading.time,
+        "2025-10-25T10:30:00Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    // Emptied at the last 9am in Melbourne (UTC+11) before the reading, 9:30pm there
+    assert_eq!(
+        reading.since,
+        "2025-10-24T22:00:00Z".parse::<DateTime<Utc>>().unwrap()
+    );
+}
+
+#[test]
+fn test_rain_gauge_reading_uses_the_station_time_zone() {
+    // 6:30pm in Perth (UTC+8), whatever zone the device runs in
+    let reading = observations()
+        .rain_gauge_reading(chrono_tz::Australia::Perth)
+        .unwrap();
+
+    assert_eq!(
+        reading.since,
+        "2025-10-25T01:00:00Z".parse::<DateTime<Utc>>().unwrap()
+    );
+}
+
+#[test]
+fn test_location_time_zone() {
+    let location: LocationResponse =
+        serde_json::from_str(include_str!("fixtures/bom_location.json")).unwrap();
+
+    assert_eq!(location.time_zone(), Some(chrono_tz::Australia::Melbourne));
+}
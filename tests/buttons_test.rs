@@ -0,0 +1,69 @@
+#![cfg(all(feature = "web", unix))]
+
+use pi_inky_weather_epd::buttons::{layouts, next_layout, selected_layout};
+use pi_inky_weather_epd::configs::settings::{Button, ButtonAction};
+use pi_inky_weather_epd::web_server::{router, serve_unix};
+use pi_inky_weather_epd::CONFIG;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+async fn request(name: &str, request: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "pi-inky-weather-buttons-{name}-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let listener = UnixListener::bind(&path).unwrap();
+    tokio::spawn(serve_unix(listener, router()));
+
+    let mut stream = UnixStream::connect(&path).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    response
+}
+
+#[test]
+fn test_default_button_actions() {
+    let buttons = &CONFIG.web_server.buttons;
+    assert_eq!(buttons.action(Button::A), ButtonAction::Refresh);
+    assert_eq!(buttons.action(Button::B), ButtonAction::NextLayout);
+    assert_eq!(buttons.action(Button::C), ButtonAction::Diagnostics);
+    assert_eq!(buttons.action(Button::D), ButtonAction::None);
+}
+
+#[test]
+fn test_layouts_cycle_back_to_the_base_configuration() {
+    let layouts = layouts();
+    assert_eq!(layouts[0], None);
+
+    let start = selected_layout();
+    let cycled: Vec<Option<&str>> = (0..layouts.len()).map(|_| next_layout()).collect();
+
+    assert_eq!(cycled.last().copied(), Some(start));
+    for layout in &layouts {
+        assert!(cycled.contains(layout));
+    }
+}
+
+#[tokio::test]
+async fn test_button_without_action_is_no_content() {
+    let response = request(
+        "none",
+        "POST /api/buttons/d HTTP/1.1\r\nHost: localhost\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 204"), "{response}");
+}
+
+#[tokio::test]
+async fn test_unknown_button_is_a_bad_request() {
+    let response = request(
+        "unknown",
+        "POST /api/buttons/e HTTP/1.1\r\nHost: localhost\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
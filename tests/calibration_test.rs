@@ -0,0 +1,50 @@
+use pi_inky_weather_epd::calibration::{calibration_card_svg, write_calibration_card};
+use pi_inky_weather_epd::configs::settings::{InkPalette, Saturation};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+
+#[test]
+fn test_card_shows_every_ink_gradient_and_text_size() {
+    let svg = calibration_card_svg(&CONFIG.palette);
+
+    for label in ["black", "white", "green", "blue", "red", "yellow", "orange"] {
+        assert!(svg.contains(&format!(">{label}</text>")), "missing {label}");
+    }
+    assert!(!svg.contains(">purple<"));
+    assert!(svg.contains(r##"fill="#438a1c""##), "green swatch");
+    assert!(svg.contains(">67, 138, 28</text>"));
+    for gradient in ["grey", "green", "blue", "red", "yellow", "orange"] {
+        assert!(svg.contains(&format!(r##"fill="url(#gradient-{gradient})""##)));
+    }
+    for size in [8, 24] {
+        assert!(svg.contains(&format!(">{size}px Weather 23°C</text>")));
+    }
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_saturation_blends_towards_the_pure_inks() {
+    let palette = InkPalette {
+        red: [191, 0, 0],
+        saturation: Saturation::try_new(0.5).unwrap(),
+        ..CONFIG.palette.clone()
+    };
+
+    assert_eq!(palette.colours()[4], [223, 0, 0]);
+    assert_eq!(palette.colours()[1], [255, 255, 255]);
+    assert!(serde_json::from_value::<Saturation>(json!(1.5)).is_err());
+}
+
+#[test]
+fn test_card_is_written_in_every_format() {
+    let dir = tempfile::tempdir().unwrap();
+
+    write_calibration_card(dir.path()).unwrap();
+
+    for extension in ["svg", "png", "raw"] {
+        assert!(dir
+            .path()
+            .join(format!("calibration.{extension}"))
+            .is_file());
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{TimeZone, Utc};
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::chart::{
+    downsample, GraphDataPath, HourlyForecastGraph, HourlyForecastGraphBuilder,
+};
+
+#[test]
+fn test_builder_defaults_match_dashboard_graph() {
+    let graph = HourlyForecastGraph::builder().build();
+    assert_eq!(graph.width, 600.0);
+    assert_eq!(graph.height, 300.0);
+    assert_eq!(graph.text_colour, "black");
+    assert_eq!(graph.curves.len(), 3);
+}
+
+#[test]
+fn test_builder_sets_size_colour_and_series() {
+    let temps: Vec<f32> = (0..24).map(|hour| hour as f32).collect();
+    let graph = HourlyForecastGraph::builder()
+        .size(800.0, 400.0)
+        .text_colour("white")
+        .x_axis_always_at_min(true)
+        .ticks(4, 3, 2)
+        .actual_temperature(&temps)
+        .rain_chance(&[50.0, 60.0])
+        .uv_index(&[1, 2, 3])
+        .lightning_risk(&[false, true])
+        .build();
+
+    assert_eq!((graph.width, graph.height), (800.0, 400.0));
+    assert_eq!(graph.text_colour, "white");
+    assert!(graph.x_axis_always_at_min);
+    assert_eq!(
+        (graph.x_ticks, graph.y_left_ticks, graph.y_right_ticks),
+        (4, 3, 2)
+    );
+    assert_eq!(graph.curves[0].get_points().len(), 24);
+    assert!(graph.curves[1].get_points().is_empty());
+    assert_eq!(graph.curves[2].get_points()[1].y, 60.0);
+    assert_eq!(&graph.uv_data[..4], &[1, 2, 3, 0]);
+    assert!(graph.lightning_risk[1]);
+}
+
+#[test]
+fn test_builder_graph_scales_to_configured_width() {
+    let temps: Vec<f32> = (0..24).map(|hour| 10.0 + hour as f32).collect();
+    let mut graph = HourlyForecastGraph::builder()
+        .size(230.0, 100.0)
+        .actual_temperature(&temps)
+        .feels_like(&temps)
+        .rain_chance(&[0.0; 24])
+        .build();
+
+    let paths = graph.draw_graph().unwrap();
+    let rain = paths
+        .iter()
+        .find_map(|path| match path {
+            GraphDataPath::Rain(data) => Some(data.clone()),
+            _ => None,
+        })
+        .unwrap();
+    // The last hour lands on the right edge of the chart
+    assert!(rain.contains("L 220 0L 230 0"), "{rain}");
+}
+
+#[test]
+fn test_text_halo_outlines_every_label() {
+    let temps: Vec<f32> = (0..24).map(|hour| 10.0 + hour as f32).collect();
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let labels = |builder: HourlyForecastGraphBuilder| {
+        let mut graph = builder
+            .actual_temperature(&temps)
+            .feels_like(&temps)
+            .rain_chance(&[0.0; 24])
+            .build();
+        graph.draw_graph().unwrap();
+        let axis = graph.create_axis_with_labels(14.0, &clock);
+        format!(
+            "{}{}{}",
+            axis.x_labels, axis.y_left_labels, axis.y_right_labels
+        )
+    };
+
+    let plain = labels(HourlyForecastGraph::builder());
+    let outlined = labels(HourlyForecastGraph::builder().text_halo("white", 3.0));
+
+    assert!(!plain.contains("paint-order"));
+    assert_eq!(
+        outlined.matches("<text").count(),
+        outlined
+            .matches(
+                r#" stroke="white" stroke-width="3" stroke-linejoin="round" paint-order="stroke""#
+            )
+            .count()
+    );
+    assert!(outlined.matches("<text").count() > 10);
+}
+
+#[test]
+fn test_downsample_combines_consecutive_hours() {
+    let hours: Vec<f32> = (0..48).map(|hour| hour as f32).collect();
+    let means = downsample(&hours, 2, |pair| {
+        pair.iter().sum::<f32>() / pair.len() as f32
+    });
+    assert_eq!(means.len(), 24);
+    assert_eq!(&means[..3], &[0.5, 2.5, 4.5]);
+
+    let unchanged = downsample(&hours[..24], 1, |hour| hour[0]);
+    assert_eq!(unchanged, hours[..24]);
+}
+
+#[test]
+fn test_two_hour_points_label_hours_and_mark_every_day() {
+    let temps: Vec<f32> = (0..24).map(|point| 10.0 + point as f32).collect();
+    // Friday 2pm in Melbourne
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let mut graph = HourlyForecastGraph::builder()
+        .hours_per_point(2)
+        .actual_temperature(&temps)
+        .feels_like(&temps)
+        .rain_chance(&[0.0; 24])
+        .build();
+    graph.draw_graph().unwrap();
+    let labels = graph.create_axis_with_labels(14.0, &clock).x_labels;
+
+    // Ticks every 4 points are 8 hours apart
+    for hour in ["2pm", "10pm", "6am"] {
+        assert!(labels.contains(&format!(">{hour}</text>")), "{labels}");
+    }
+    // Saturday starts 10 hours in and Sunday 34 hours in, at points 5 and 17
+    assert!(labels.contains(r#"<line x1="125" "#), "{labels}");
+    assert!(labels.contains(">Saturday</text>"), "{labels}");
+    assert!(labels.contains(r#"<line x1="425" "#), "{labels}");
+    assert!(labels.contains(">Sunday</text>"), "{labels}");
+}
+
+#[test]
+fn test_rain_band_amounts_are_labelled_on_the_right_axis() {
+    let temps: Vec<f32> = (0..24).map(|hour| 10.0 + hour as f32).collect();
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let right_labels = |band: &[Option<(f32, f32)>]| {
+        let mut graph = HourlyForecastGraph::builder()
+            .actual_temperature(&temps)
+            .rain_chance(&[50.0; 24])
+            .rain_band(band, 10.0)
+            .build();
+        graph.draw_graph().unwrap();
+        graph.create_axis_with_labels(14.0, &clock).y_right_labels
+    };
+
+    let with_band = right_labels(&[Some((1.0, 4.0)); 24]);
+    assert!(with_band.contains(">100%<"));
+    assert!(with_band.contains(">10mm<"), "{with_band}");
+    assert!(with_band.contains(">2mm<"));
+    assert!(!right_labels(&[None; 24]).contains("mm<"));
+}
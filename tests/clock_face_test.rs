@@ -0,0 +1,46 @@
+use chrono::NaiveTime;
+use pi_inky_weather_epd::configs::settings::ClockFace;
+use pi_inky_weather_epd::dashboard::clock_face::{clock_face, hand_angles};
+use pi_inky_weather_epd::CONFIG;
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+fn settings(enabled: bool) -> ClockFace {
+    ClockFace {
+        enabled,
+        x: 680.0,
+        y: 10.0,
+        size: 100.0,
+    }
+}
+
+#[test]
+fn test_hand_angles() {
+    assert_eq!(hand_angles(time(0, 0)), (0.0, 0.0));
+    assert_eq!(hand_angles(time(3, 0)), (90.0, 0.0));
+    assert_eq!(hand_angles(time(15, 30)), (105.0, 180.0));
+    assert_eq!(hand_angles(time(21, 45)), (292.5, 270.0));
+}
+
+#[test]
+fn test_clock_face_is_valid_svg() {
+    let panel = clock_face(&settings(true), time(9, 0), &CONFIG.colours).unwrap();
+    let svg = format!(
+        r#"<svg width="800" height="480" xmlns="http://www.w3.org/2000/svg">{}</svg>"#,
+        panel.to_svg()
+    );
+
+    assert_eq!(
+        (panel.x, panel.y, panel.width, panel.height),
+        (680.0, 10.0, 100.0, 100.0)
+    );
+    assert!(svg.contains(r#"transform="rotate(270)""#));
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_disabled_clock_face_is_not_drawn() {
+    assert!(clock_face(&settings(false), time(9, 0), &CONFIG.colours).is_none());
+}
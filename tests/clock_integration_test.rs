@@ -2,8 +2,10 @@
 ///
 /// These tests show how to use the Clock trait abstraction to write
 /// deterministic tests for time-dependent functionality.
-use chrono::{Datelike, TimeZone, Timelike, Utc};
-use pi_inky_weather_epd::clock::{Clock, FixedClock};
+use chrono::{Datelike, Duration, TimeZone, Timelike, Utc};
+use pi_inky_weather_epd::clock::{
+    parse_accelerate_factor, parse_time_offset, AcceleratedClock, Clock, FixedClock, OffsetClock,
+};
 
 #[test]
 fn test_fixed_clock_allows_time_controlled_testing() {
@@ -104,3 +106,67 @@ fn test_time_dependent_function_example() {
     let clock2 = FixedClock::new(after_hours);
     assert!(!is_business_hours(&clock2));
 }
+
+#[test]
+fn test_offset_clock_shifts_the_inner_clock() {
+    let morning = Utc.with_ymd_and_hms(2025, 3, 15, 8, 0, 0).unwrap();
+    let tonight = OffsetClock::new(FixedClock::new(morning), Duration::hours(13));
+    let yesterday = OffsetClock::new(FixedClock::new(morning), Duration::days(-1));
+
+    assert_eq!(tonight.now_utc().hour(), 21);
+    assert_eq!(tonight.now_local().with_timezone(&Utc), tonight.now_utc());
+    assert_eq!(yesterday.now_utc().day(), 14);
+}
+
+#[test]
+fn test_accelerated_clock_runs_faster_than_real_time() {
+    let start = Utc.with_ymd_and_hms(2025, 3, 15, 0, 0, 0).unwrap();
+    let clock = AcceleratedClock::new(start, 3600.0);
+
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    // 20 ms of real time are at least 72 s at 3600x
+    assert!(clock.now_utc() >= start + Duration::seconds(72));
+    assert!(clock.now_utc() < start + Duration::hours(1));
+}
+
+#[test]
+fn test_accelerated_clock_stops_at_the_last_date() {
+    let start = Utc.with_ymd_and_hms(2025, 3, 15, 0, 0, 0).unwrap();
+    let clock = AcceleratedClock::new(start, 1e300);
+
+    std::thread::sleep(std::time::Duration::from_millis(1));
+
+    assert_eq!(clock.now_utc(), chrono::DateTime::<Utc>::MAX_UTC);
+}
+
+#[test]
+fn test_time_offsets_parse_with_units_and_sign() {
+    assert_eq!(parse_time_offset("-3h"), Ok(Duration::hours(-3)));
+    assert_eq!(parse_time_offset("+90m"), Ok(Duration::minutes(90)));
+    assert_eq!(
+        parse_time_offset("1d12h30s"),
+        Ok(Duration::hours(36) + Duration::seconds(30))
+    );
+    assert!(parse_time_offset("3").is_err());
+    assert!(parse_time_offset("-").is_err());
+    assert!(parse_time_offset("2w").is_err());
+}
+
+#[test]
+fn test_out_of_range_time_offsets_are_refused() {
+    assert_eq!(parse_time_offset("-3650d"), Ok(Duration::days(-3650)));
+    assert!(parse_time_offset("3651d").is_err());
+    assert!(parse_time_offset("9223372036854775807d").is_err());
+    assert!(parse_time_offset("9223372036854775807s").is_err());
+    assert!(parse_time_offset("99999999999999999999h").is_err());
+}
+
+#[test]
+fn test_accelerate_factor_is_a_finite_number_above_zero() {
+    assert_eq!(parse_accelerate_factor("120"), Ok(120.0));
+    assert_eq!(parse_accelerate_factor("0.5"), Ok(0.5));
+    for factor in ["0", "-60", "NaN", "inf", "fast", ""] {
+        assert!(parse_accelerate_factor(factor).is_err(), "{factor}");
+    }
+}
@@ -0,0 +1,140 @@
+use pi_inky_weather_epd::configs::contrast::{ink, ink_clashes, snapped, ClosestInk, InkClash};
+use pi_inky_weather_epd::configs::settings::{Colour, Colours, InkPalette, Saturation};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::{json, Value};
+
+fn palette() -> [[u8; 3]; 8] {
+    CONFIG.palette.colours()
+}
+
+fn colours(overrides: Value) -> Colours {
+    let mut colours = json!({
+        "background_colour": "white",
+        "text_colour": "black",
+        "x_axis_colour": "black",
+        "y_left_axis_colour": "red",
+        "y_right_axis_colour": "blue",
+        "actual_temp_colour": "red",
+        "feels_like_colour": "green",
+        "rain_colour": "blue",
+        "weekend_background_colour": "yellow",
+        "weekend_text_colour": "black",
+        "humidity_colour": "black",
+        "humidity_dry_colour": "orange",
+        "humidity_humid_colour": "green",
+        "cold_temp_colour": "blue",
+        "hot_temp_colour": "red"
+    });
+    for (key, value) in overrides.as_object().unwrap() {
+        colours[key] = value.clone();
+    }
+    serde_json::from_value(colours).unwrap()
+}
+
+#[test]
+fn test_colours_map_to_the_closest_ink() {
+    assert_eq!(ink("darkred", &palette()), Some("red"));
+    assert_eq!(ink("#fffff0", &palette()), Some("white"));
+    assert_eq!(ink("rgb(20, 20, 30)", &palette()), Some("black"));
+    assert_eq!(ink("hsl(120, 100%, 25%)", &palette()), Some("green"));
+    assert_eq!(ink("transparent", &palette()), None);
+    assert_eq!(ink("currentColor", &palette()), None);
+}
+
+#[test]
+fn test_default_colours_do_not_clash() {
+    assert!(ink_clashes(&colours(json!({})), &palette()).is_empty());
+}
+
+#[test]
+fn test_text_on_a_background_of_the_same_ink_clashes() {
+    let clashes = ink_clashes(
+        &colours(json!({
+            "background_colour": "maroon",
+            "text_colour": "darkred",
+            "x_axis_colour": "white",
+            "y_left_axis_colour": "white",
+            "actual_temp_colour": "orange",
+            "hot_temp_colour": "orange"
+        })),
+        &palette(),
+    );
+
+    assert_eq!(
+        clashes,
+        vec![InkClash {
+            first: "text_colour",
+            second: "background_colour",
+            ink: "red",
+        }]
+    );
+    assert_eq!(
+        clashes[0].to_string(),
+        "text_colour and background_colour both print as red"
+    );
+}
+
+#[test]
+fn test_curves_must_print_differently() {
+    let clashes = ink_clashes(
+        &colours(json!({ "feels_like_colour": "darkgreen", "rain_colour": "forestgreen" })),
+        &palette(),
+    );
+
+    assert_eq!(clashes.len(), 1);
+    assert_eq!(
+        (clashes[0].first, clashes[0].second),
+        ("feels_like_colour", "rain_colour")
+    );
+}
+
+#[test]
+fn test_axes_may_share_a_curve_colour() {
+    let clashes = ink_clashes(
+        &colours(json!({ "y_left_axis_colour": "darkred" })),
+        &palette(),
+    );
+
+    assert!(clashes.is_empty());
+}
+
+#[test]
+fn test_closest_ink_from_hex() {
+    let red = ClosestInk::from_hex("#8b0000", &palette()).unwrap();
+
+    assert_eq!(red.name(), "red");
+    assert_eq!(red.rgb(), [191, 0, 0]);
+    assert_eq!(red.hex(), "#bf0000");
+    assert_eq!(
+        ClosestInk::from_hex("#fff", &palette()).map(ClosestInk::name),
+        Some("white")
+    );
+    assert_eq!(ClosestInk::from_hex("darkred", &palette()), None);
+    assert_eq!(ClosestInk::from_hex("#12345", &palette()), None);
+}
+
+#[test]
+fn test_snapping_replaces_colours_by_their_ink() {
+    let snap = |colour: &str| snapped(&Colour::try_new(colour).unwrap(), &palette()).to_string();
+
+    assert_eq!(snap("darkred"), "#bf0000");
+    assert_eq!(snap("rgb(250, 250, 240)"), "#ffffff");
+    assert_eq!(snap("transparent"), "transparent");
+}
+
+#[test]
+fn test_snapping_takes_the_calibrated_palette() {
+    let calibrated = InkPalette {
+        red: [160, 20, 20],
+        saturation: Saturation::try_new(0.0).unwrap(),
+        ..CONFIG.palette.clone()
+    }
+    .colours();
+    let darkred = Colour::try_new("darkred").unwrap();
+
+    assert_eq!(snapped(&darkred, &calibrated).to_string(), "#a01414");
+    assert_eq!(
+        ClosestInk::from_hex("#8b0000", &calibrated).map(ClosestInk::rgb),
+        Some([160, 20, 20])
+    );
+}
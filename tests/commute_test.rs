@@ -0,0 +1,164 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::configs::settings::CommuteWindow;
+use pi_inky_weather_epd::dashboard::chart::{HourlyForecastGraph, TimeWindow};
+use pi_inky_weather_epd::dashboard::commute::{commute_summaries, time_windows, CommuteSummary};
+use pi_inky_weather_epd::dashboard::number_format::NumberFormat;
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Precipitation, Temperature};
+use serde_json::json;
+
+fn local(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2025, 6, day, hour, minute, 0)
+        .single()
+        .unwrap()
+}
+
+fn window(start: &str, end: &str) -> CommuteWindow {
+    serde_json::from_value(json!({ "start": start, "end": end })).unwrap()
+}
+
+fn hour(time: DateTime<Local>, temperature: f32, chance: u16, amount: u16) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(temperature),
+        apparent_temperature: Temperature::celsius(temperature),
+        precipitation: Precipitation::new(Some(chance), Some(0), Some(amount)),
+        ..forecast::hour(time.with_timezone(&Utc))
+    }
+}
+
+/// 48 hours from 00:00 on the 10th, warming by a degree an hour, rain at 17:00 and 18:00
+fn forecast() -> Vec<HourlyForecast> {
+    (0..48)
+        .map(|h| {
+            let time = local(10, 0, 0) + Duration::hours(h);
+            let rainy = time.format("%H").to_string() == "17";
+            hour(
+                time,
+                h as f32,
+                if rainy { 60 } else { 10 },
+                if rainy { 2 } else { 0 },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_summary_covers_the_hours_overlapping_the_window() {
+    let summary = CommuteSummary::of(&forecast(), local(10, 16, 30), local(10, 18, 0));
+
+    assert_eq!(summary.temperature, Some((16.0, 17.0)));
+    assert_eq!(summary.rain_chance, Some(60));
+    assert_eq!(summary.rain_amount, 2);
+    assert_eq!(
+        summary.callout(&NumberFormat::default()),
+        "16–17° · 60% · 2mm"
+    );
+}
+
+#[test]
+fn test_callout_leaves_out_a_dry_window_amount() {
+    let summary = CommuteSummary::of(&forecast(), local(10, 8, 0), local(10, 9, 0));
+
+    assert_eq!(summary.callout(&NumberFormat::default()), "8° · 10%");
+}
+
+#[test]
+fn test_summary_without_hours_has_an_empty_callout() {
+    let summary = CommuteSummary::of(&[], local(10, 8, 0), local(10, 9, 0));
+
+    assert_eq!(summary.temperature, None);
+    assert_eq!(summary.callout(&NumberFormat::default()), "");
+}
+
+#[test]
+fn test_every_occurrence_in_the_graph_is_summarised_in_order() {
+    let windows = [window("17:00", "18:00"), window("08:00", "09:00")];
+
+    let summaries = commute_summaries(&windows, &forecast(), local(10, 12, 0), local(11, 12, 0));
+
+    let starts: Vec<DateTime<Local>> = summaries.iter().map(|summary| summary.start).collect();
+    assert_eq!(starts, vec![local(10, 17, 0), local(11, 8, 0)]);
+}
+
+#[test]
+fn test_window_under_way_starts_at_the_graph_start() {
+    let summaries = commute_summaries(
+        &[window("08:00", "09:30")],
+        &forecast(),
+        local(10, 9, 0),
+        local(10, 21, 0),
+    );
+
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].start, local(10, 9, 0));
+    assert_eq!(summaries[0].end, local(10, 9, 30));
+}
+
+#[test]
+fn test_time_windows_count_hours_from_the_graph_start() {
+    let summaries = commute_summaries(
+        &[window("17:30", "18:00")],
+        &forecast(),
+        local(10, 12, 0),
+        local(11, 12, 0),
+    );
+
+    let windows = time_windows(&summaries, local(10, 12, 0), &NumberFormat::default());
+
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0].from_hours, 5.5);
+    assert_eq!(windows[0].to_hours, 6.0);
+}
+
+fn graph(hours_per_point: u16) -> HourlyForecastGraph {
+    let temperatures = [10.0; 24];
+    let mut graph = HourlyForecastGraph::builder()
+        .size(460.0, 230.0)
+        .text_colour("black")
+        .hours_per_point(hours_per_point)
+        .actual_temperature(&temperatures)
+        .feels_like(&temperatures)
+        .rain_chance(&[0.0; 24])
+        .build();
+    graph.draw_graph().unwrap();
+    graph
+}
+
+#[test]
+fn test_time_windows_are_drawn_as_bands_with_callouts() {
+    let (bands, callouts) = graph(1).draw_time_windows(&[TimeWindow {
+        from_hours: 2.0,
+        to_hours: 3.0,
+        callout: "12° · 40%".to_string(),
+    }]);
+
+    assert_eq!(bands, "M 40.00 0 H 60.00 V 230.00 H 40.00 Z");
+    assert!(callouts.contains(r#"x="50.00""#));
+    assert!(callouts.contains(">12° · 40%</text>"));
+}
+
+#[test]
+fn test_time_windows_follow_the_hours_per_point() {
+    let (bands, _) = graph(2).draw_time_windows(&[TimeWindow {
+        from_hours: 4.0,
+        to_hours: 6.0,
+        callout: String::new(),
+    }]);
+
+    assert_eq!(bands, "M 40.00 0 H 60.00 V 230.00 H 40.00 Z");
+}
+
+#[test]
+fn test_time_windows_outside_the_graph_are_not_drawn() {
+    let (bands, callouts) = graph(1).draw_time_windows(&[TimeWindow {
+        from_hours: 30.0,
+        to_hours: 31.0,
+        callout: "late".to_string(),
+    }]);
+
+    assert!(bands.is_empty());
+    assert!(callouts.is_empty());
+}
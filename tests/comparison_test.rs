@@ -0,0 +1,141 @@
+mod helpers;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::configs::settings::Comparison;
+use pi_inky_weather_epd::dashboard::comparison::{
+    other_label, render_comparison_svg, LocationSummary,
+};
+use pi_inky_weather_epd::dashboard::panel::{svg_size, DEFAULT_FRAME_SIZE};
+use pi_inky_weather_epd::domain::models::{
+    DailyForecast, HourlyForecast, Precipitation, Temperature,
+};
+use pi_inky_weather_epd::errors::DashboardError;
+use pi_inky_weather_epd::utils::convert_svg_to_png_bytes;
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, 25, hour, 0, 0).unwrap()
+}
+
+fn hour(time: DateTime<Utc>, temperature: f32) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(temperature),
+        apparent_temperature: Temperature::celsius(temperature - 2.0),
+        ..forecast::hour(time)
+    }
+}
+
+fn day(day: u32, temp_max: f32) -> DailyForecast {
+    DailyForecast {
+        temp_max: Some(Temperature::celsius(temp_max)),
+        temp_min: Some(Temperature::celsius(9.0)),
+        precipitation: Some(Precipitation::new(Some(10), Some(0), Some(0))),
+        cloud_cover: Some(20),
+        ..forecast::day(NaiveDate::from_ymd_opt(2025, 10, day).unwrap())
+    }
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 10, 25).unwrap()
+}
+
+fn location(label: &str, temperature: f32) -> LocationSummary {
+    let hourly: Vec<HourlyForecast> = (0..6)
+        .map(|h| hour(at(h), temperature + h as f32))
+        .collect();
+    let daily: Vec<DailyForecast> = (24..32).map(|d| day(d, temperature + 5.0)).collect();
+    LocationSummary::at(
+        label,
+        &hourly,
+        &daily,
+        at(3) + chrono::Duration::minutes(20),
+        today(),
+        5,
+    )
+}
+
+#[test]
+fn test_summary_has_the_current_hour_and_days_from_today() {
+    let summary = location("Home", 15.0);
+
+    assert_eq!(summary.current.unwrap().time, at(3));
+    let dates: Vec<u32> = summary
+        .days
+        .iter()
+        .map(|day| chrono::Datelike::day(&day.date.unwrap()))
+        .collect();
+    assert_eq!(dates, vec![25, 26, 27, 28, 29]);
+}
+
+#[test]
+fn test_summary_before_the_first_hour_uses_the_first_hour() {
+    let hourly = vec![hour(at(5), 15.0), hour(at(6), 16.0)];
+
+    let summary = LocationSummary::at("Home", &hourly, &[], at(2), today(), 5);
+
+    assert_eq!(summary.current.unwrap().time, at(5));
+    assert!(summary.days.is_empty());
+}
+
+#[test]
+fn test_other_label_defaults_to_the_profile_name() {
+    let mut settings: Comparison = serde_json::from_value(json!({
+        "enabled": true,
+        "profile": "parents",
+        "home_label": "Home",
+        "other_label": "",
+        "days": 5
+    }))
+    .unwrap();
+    assert_eq!(other_label(&settings), "parents");
+
+    settings.other_label = "Mum & Dad".to_string();
+    assert_eq!(other_label(&settings), "Mum & Dad");
+}
+
+#[test]
+fn test_comparison_shows_both_locations() {
+    let clock = FixedClock::from_rfc3339("2025-10-25T03:20:00Z").unwrap();
+    let warnings = [DashboardError::NoInternet {
+        details: "offline".to_string(),
+    }];
+
+    let svg = render_comparison_svg(
+        &location("Home", 15.0),
+        &location("Mum & Dad", 25.0),
+        &warnings,
+        DEFAULT_FRAME_SIZE,
+        &CONFIG.colours,
+        &clock,
+    );
+
+    assert!(svg.contains(">Home</text>"));
+    assert!(svg.contains(">Mum &amp; Dad</text>"));
+    // Current temperatures of both, 3am is 3 degrees up from the first hour
+    assert!(svg.contains(">18°</text>"));
+    assert!(svg.contains(">28°</text>"));
+    assert!(svg.contains("Feels 16° · 60%"));
+    assert_eq!(svg.matches("20° / 9°").count(), 5);
+    assert!(svg.contains("API unreachable -&gt; Stale Data"));
+    assert!(convert_svg_to_png_bytes(&svg, 1.0, true).is_ok());
+}
+
+#[test]
+fn test_comparison_fills_the_frame_of_the_display() {
+    let clock = FixedClock::from_rfc3339("2025-10-25T03:20:00Z").unwrap();
+
+    let svg = render_comparison_svg(
+        &location("Home", 15.0),
+        &location("Mum & Dad", 25.0),
+        &[],
+        (600.0, 448.0),
+        &CONFIG.colours,
+        &clock,
+    );
+
+    assert_eq!(svg_size(&svg), Some((600.0, 448.0)));
+    assert!(svg.contains(r#"<line x1="300" y1="20" x2="300" y2="413""#));
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::fs;
+
+use pi_inky_weather_epd::apis::open_meteo::models::OpenMeteoHourlyResponse;
+use pi_inky_weather_epd::domain::conditions::{Condition, BOM_ICON_DESCRIPTORS, WMO_WEATHER_CODES};
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Obscuration};
+use strum::IntoEnumIterator;
+
+/// Every weather code listed in the Open-Meteo forecast API documentation
+const OPEN_METEO_DOCUMENTED_CODES: [u8; 28] = [
+    0, 1, 2, 3, 45, 48, 51, 53, 55, 56, 57, 61, 63, 65, 66, 67, 71, 73, 75, 77, 80, 81, 82, 85, 86,
+    95, 96, 99,
+];
+
+/// `icon_descriptor` values found in the recorded BOM responses
+fn bom_sample_descriptors() -> HashSet<String> {
+    let mut descriptors = HashSet::new();
+    for entry in fs::read_dir("src/apis/bom/samples").unwrap() {
+        let sample: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(entry.unwrap().path()).unwrap()).unwrap();
+        for forecast in sample["data"].as_array().into_iter().flatten() {
+            if let Some(descriptor) = forecast["icon_descriptor"].as_str() {
+                descriptors.insert(descriptor.to_string());
+            }
+        }
+    }
+    descriptors
+}
+
+#[test]
+fn test_bom_sample_descriptors_are_mapped() {
+    let descriptors = bom_sample_descriptors();
+    assert!(!descriptors.is_empty());
+
+    for descriptor in descriptors {
+        assert!(
+            Condition::from_bom_descriptor(&descriptor).is_some(),
+            "unmapped BOM descriptor {descriptor}"
+        );
+    }
+}
+
+#[test]
+fn test_documented_wmo_codes_are_mapped() {
+    for code in OPEN_METEO_DOCUMENTED_CODES {
+        assert!(
+            Condition::from_wmo_code(code).is_some(),
+            "unmapped WMO code {code}"
+        );
+    }
+    assert_eq!(WMO_WEATHER_CODES.len(), OPEN_METEO_DOCUMENTED_CODES.len());
+    assert_eq!(Condition::from_wmo_code(4), None);
+    assert_eq!(Condition::from_wmo_code(100), None);
+}
+
+#[test]
+fn test_tables_have_no_duplicate_codes() {
+    let bom: HashSet<&str> = BOM_ICON_DESCRIPTORS.iter().map(|(code, _)| *code).collect();
+    assert_eq!(bom.len(), BOM_ICON_DESCRIPTORS.len());
+
+    let wmo: HashSet<u8> = WMO_WEATHER_CODES.iter().map(|(code, _)| *code).collect();
+    assert_eq!(wmo.len(), WMO_WEATHER_CODES.len());
+}
+
+#[test]
+fn test_every_condition_is_reachable() {
+    let mapped: HashSet<Condition> = BOM_ICON_DESCRIPTORS
+        .iter()
+        .map(|(_, condition)| *condition)
+        .chain(WMO_WEATHER_CODES.iter().map(|(_, condition)| *condition))
+        .collect();
+
+    for condition in Condition::iter() {
+        assert!(mapped.contains(&condition), "{condition} is never mapped");
+    }
+}
+
+#[test]
+fn test_only_obscuring_conditions_have_an_obscuration() {
+    for condition in Condition::iter() {
+        let expected = match condition {
+            Condition::Haze => Some(Obscuration::Haze),
+            Condition::Smoke => Some(Obscuration::Smoke),
+            Condition::Dust => Some(Obscuration::Dust),
+            _ => None,
+        };
+        assert_eq!(condition.obscuration(), expected, "{condition}");
+    }
+}
+
+#[test]
+fn test_bom_spelling_variants_map_to_the_same_condition() {
+    for (a, b) in [
+        ("hazy", "haze"),
+        ("smoky", "smoke"),
+        ("dusty", "dust"),
+        ("windy", "wind"),
+    ] {
+        assert_eq!(
+            Condition::from_bom_descriptor(a),
+            Condition::from_bom_descriptor(b)
+        );
+    }
+}
+
+#[test]
+fn test_open_meteo_hours_take_the_reported_condition() {
+    let mut json: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string("tests/fixtures/open_meteo_hourly_forecast.json").unwrap(),
+    )
+    .unwrap();
+    let hours = json["hourly"]["time"].as_array().unwrap().len();
+    let mut codes = vec![serde_json::Value::Null; hours];
+    codes[0] = 95.into();
+    codes[1] = 0.into();
+    json["hourly"]["weather_code"] = codes.into();
+
+    let response: OpenMeteoHourlyResponse = serde_json::from_value(json).unwrap();
+    let hourly: Vec<HourlyForecast> = response.into();
+
+    assert_eq!(hourly[0].reported_condition, Some(Condition::Storm));
+    // A thunderstorm looks like rain on the icon, the description says what it is
+    assert_eq!(hourly[0].condition(), Condition::Storm);
+    // The icon's own cloud level wins over a reported clear sky
+    assert_eq!(hourly[1].reported_condition, Some(Condition::Clear));
+    assert_eq!(
+        hourly[1].condition(),
+        HourlyForecast {
+            reported_condition: None,
+            ..hourly[1].clone()
+        }
+        .condition()
+    );
+    assert_eq!(hourly[2].reported_condition, None);
+}
@@ -0,0 +1,160 @@
+#![cfg(all(feature = "web", unix))]
+
+use pi_inky_weather_epd::configs::settings::DashboardFormat;
+use pi_inky_weather_epd::display_options::{Palette, Rotation};
+use pi_inky_weather_epd::utils::convert_png_to_bmp_bytes;
+use pi_inky_weather_epd::web_server::{negotiate_dashboard_format, router, serve_unix};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+fn format_for(accept: &str) -> Option<DashboardFormat> {
+    negotiate_dashboard_format(Some(accept), DashboardFormat::Png)
+        .unwrap()
+        .map(|(format, _)| format)
+}
+
+#[test]
+fn test_named_media_types_pick_their_format() {
+    assert_eq!(format_for("image/svg+xml"), Some(DashboardFormat::Svg));
+    assert_eq!(format_for("image/png"), Some(DashboardFormat::Png));
+    assert_eq!(format_for("image/bmp"), Some(DashboardFormat::Bmp));
+    assert_eq!(format_for("image/x-ms-bmp"), Some(DashboardFormat::Bmp));
+    assert_eq!(
+        format_for("application/octet-stream"),
+        Some(DashboardFormat::Raw)
+    );
+}
+
+#[test]
+fn test_missing_or_wildcard_accept_gets_the_default() {
+    for accept in [None, Some(""), Some("*/*")] {
+        let (format, _) = negotiate_dashboard_format(accept, DashboardFormat::Bmp)
+            .unwrap()
+            .unwrap();
+        assert_eq!(format, DashboardFormat::Bmp, "{accept:?}");
+    }
+}
+
+#[test]
+fn test_image_wildcard_never_picks_raw_data() {
+    let (format, _) = negotiate_dashboard_format(Some("image/*"), DashboardFormat::Raw)
+        .unwrap()
+        .unwrap();
+    assert_eq!(format, DashboardFormat::Png);
+}
+
+#[test]
+fn test_highest_quality_wins() {
+    assert_eq!(
+        format_for("image/png;q=0.5, image/svg+xml;q=0.9, image/bmp;q=0.7"),
+        Some(DashboardFormat::Svg)
+    );
+}
+
+#[test]
+fn test_named_type_wins_over_a_wildcard_of_the_same_quality() {
+    assert_eq!(format_for("*/*, image/svg+xml"), Some(DashboardFormat::Svg));
+    // A browser loading an image
+    assert_eq!(
+        format_for("image/avif,image/webp,image/svg+xml,image/*,*/*;q=0.8"),
+        Some(DashboardFormat::Svg)
+    );
+}
+
+#[test]
+fn test_excluded_default_falls_back_to_another_format() {
+    assert_eq!(format_for("*/*, image/png;q=0"), Some(DashboardFormat::Svg));
+}
+
+#[test]
+fn test_unsupported_types_are_not_acceptable() {
+    assert_eq!(format_for("text/html, application/json"), None);
+    assert_eq!(format_for("image/png;q=0"), None);
+}
+
+#[test]
+fn test_invalid_quality_ignores_the_range() {
+    assert_eq!(
+        format_for("image/svg+xml;q=high, image/bmp;q=0.5"),
+        Some(DashboardFormat::Bmp)
+    );
+}
+
+#[test]
+fn test_media_type_parameters_are_render_parameters() {
+    let (format, query) = negotiate_dashboard_format(
+        Some("image/png; scale=2; palette=bw; rotate=90; charset=utf-8"),
+        DashboardFormat::Svg,
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(format, DashboardFormat::Png);
+    assert_eq!(query.scale, Some(2.0));
+    assert_eq!(query.palette, Some(Palette::BlackWhite));
+    assert_eq!(query.rotate, Some(Rotation::Clockwise90));
+}
+
+#[test]
+fn test_invalid_media_type_parameters_are_refused() {
+    assert!(
+        negotiate_dashboard_format(Some("image/png; scale=big"), DashboardFormat::Png).is_err()
+    );
+}
+
+#[test]
+fn test_png_converts_to_bmp() {
+    let mut png = Vec::new();
+    image::RgbImage::from_pixel(4, 2, image::Rgb([255, 0, 0]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+
+    let bmp = convert_png_to_bmp_bytes(&png).unwrap();
+
+    assert!(bmp.starts_with(b"BM"));
+    let decoded = image::load_from_memory(&bmp).unwrap().to_rgb8();
+    assert_eq!(decoded.dimensions(), (4, 2));
+    assert_eq!(decoded.get_pixel(3, 1), &image::Rgb([255, 0, 0]));
+}
+
+async fn get(name: &str, path: &str, headers: &str) -> String {
+    let socket = std::env::temp_dir().join(format!(
+        "pi-inky-weather-negotiation-{name}-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket);
+
+    let listener = UnixListener::bind(&socket).unwrap();
+    tokio::spawn(serve_unix(listener, router()));
+
+    let mut stream = UnixStream::connect(&socket).await.unwrap();
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n{headers}Connection: close\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    std::fs::remove_file(&socket).unwrap();
+    response
+}
+
+#[tokio::test]
+async fn test_unacceptable_request_is_refused_and_varies_by_accept() {
+    let response = get("refused", "/dashboard", "Accept: text/html\r\n").await;
+
+    assert!(response.starts_with("HTTP/1.1 406"), "{response}");
+    assert!(
+        response.to_ascii_lowercase().contains("vary: accept"),
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_accept_parameter_is_a_bad_request() {
+    let response = get("invalid", "/dashboard", "Accept: image/png; scale=9\r\n").await;
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "web")]
+
+use pi_inky_weather_epd::web_server::cors_layer;
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn test_cors_disabled_without_origins() {
+    assert!(cors_layer(&[], &strings(&["GET"])).is_none());
+}
+
+#[test]
+fn test_cors_allows_configured_origins_and_methods() {
+    let layer = cors_layer(
+        &strings(&["http://kiosk.local:3000", "not an origin\n"]),
+        &strings(&["get", "HEAD", "BAD METHOD"]),
+    )
+    .unwrap();
+    let description = format!("{layer:?}");
+
+    assert!(description.contains("http://kiosk.local:3000"));
+    assert!(description.contains("GET,HEAD"));
+    assert!(!description.contains("BAD"));
+}
@@ -0,0 +1,126 @@
+mod helpers;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::domain::daylight::{
+    apply_sun_times, fill_missing_sun_times, is_night_hour,
+};
+use pi_inky_weather_epd::domain::models::{Astronomical, DailyForecast, HourlyForecast};
+use pi_inky_weather_epd::weather::icons::Icon;
+use pi_inky_weather_epd::weather::solar::sun_times as computed_sun_times;
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 10, 25).unwrap()
+}
+
+fn local(hour: u32, minute: u32) -> NaiveDateTime {
+    date().and_hms_opt(hour, minute, 0).unwrap()
+}
+
+/// Start of the local `hour` on the test day
+fn at(hour: u32) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&local(hour, 0))
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+fn sun_times() -> Astronomical {
+    Astronomical {
+        sunrise_time: Some(local(6, 21)),
+        sunset_time: Some(local(19, 47)),
+    }
+}
+
+fn hour(time: DateTime<Utc>, is_night: bool) -> HourlyForecast {
+    HourlyForecast {
+        is_night,
+        ..forecast::hour(time)
+    }
+}
+
+fn day(astronomical: Option<Astronomical>) -> DailyForecast {
+    DailyForecast {
+        astronomical,
+        ..forecast::day(date())
+    }
+}
+
+#[test]
+fn test_hours_are_night_outside_the_sun_times() {
+    assert_eq!(is_night_hour(at(5), &sun_times()), Some(true));
+    // The middle of the hour, 6:30, is after sunrise
+    assert_eq!(is_night_hour(at(6), &sun_times()), Some(false));
+    assert_eq!(is_night_hour(at(12), &sun_times()), Some(false));
+    // 19:30 is still before sunset, 20:30 is after
+    assert_eq!(is_night_hour(at(19), &sun_times()), Some(false));
+    assert_eq!(is_night_hour(at(21), &sun_times()), Some(true));
+    assert_eq!(is_night_hour(at(12), &Astronomical::default()), None);
+}
+
+#[test]
+fn test_each_hour_gets_the_flag_of_its_own_sun_times() {
+    // Open-Meteo flags every hour with the day/night of the request time
+    let mut hourly = vec![hour(at(12), true), hour(at(21), false)];
+
+    apply_sun_times(&mut hourly, &[day(Some(sun_times()))]);
+
+    assert!(!hourly[0].is_night);
+    assert!(hourly[1].is_night);
+    assert!(
+        hourly[1].get_icon_name().contains("night") || hourly[1].get_icon_name().contains("moon")
+    );
+    assert!(hourly[0].get_icon_name().contains("day"));
+}
+
+#[test]
+fn test_hours_without_sun_times_keep_the_provider_flag() {
+    let mut hourly = vec![hour(at(12), true)];
+
+    apply_sun_times(&mut hourly, &[day(None)]);
+    assert!(hourly[0].is_night);
+
+    apply_sun_times(&mut hourly, &[]);
+    assert!(hourly[0].is_night);
+}
+
+#[test]
+fn test_missing_sun_times_are_computed_for_the_location() {
+    let (sunrise, sunset) = computed_sun_times(date(), -37.8136, 144.9631).unwrap();
+    let mut daily = vec![
+        day(None),
+        day(Some(Astronomical {
+            sunrise_time: Some(local(6, 21)),
+            sunset_time: None,
+        })),
+    ];
+
+    fill_missing_sun_times(&mut daily, -37.8136, 144.9631);
+
+    let computed = daily[0].astronomical.unwrap();
+    assert_eq!(
+        computed.sunrise_time,
+        Some(sunrise.with_timezone(&Local).naive_local())
+    );
+    assert_eq!(
+        computed.sunset_time,
+        Some(sunset.with_timezone(&Local).naive_local())
+    );
+    // The provider's sunrise is kept, only the sunset is filled in
+    let partial = daily[1].astronomical.unwrap();
+    assert_eq!(partial.sunrise_time, Some(local(6, 21)));
+    assert!(partial.sunset_time.is_some());
+}
+
+#[test]
+fn test_polar_days_stay_without_sun_times() {
+    let mut daily = vec![DailyForecast {
+        date: NaiveDate::from_ymd_opt(2025, 6, 21),
+        ..day(None)
+    }];
+
+    // Tromsø has midnight sun at the June solstice
+    fill_missing_sun_times(&mut daily, 69.6492, 18.9553);
+
+    assert!(daily[0].astronomical.is_none());
+}
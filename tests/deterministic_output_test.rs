@@ -0,0 +1,72 @@
+//! Deterministic mode: the same forecast and clock render byte-identical SVG and PNG outputs
+
+mod helpers;
+
+use chrono::NaiveDate;
+use helpers::wiremock_setup;
+use pi_inky_weather_epd::utils::{convert_svg_to_png_bytes, normalize_svg_numbers};
+use pi_inky_weather_epd::weather::utils::get_moon_phase_icon_name;
+use pi_inky_weather_epd::{clock::FixedClock, generate_weather_dashboard_injection, CONFIG};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_long_decimals_are_rounded() {
+    assert_eq!(
+        normalize_svg_numbers(
+            r#"<path d="M 0.123456 -12.99996 L 3.5 4.0001 L 2.50000004 -0.0000001"/>"#
+        ),
+        r#"<path d="M 0.123 -13 L 3.5 4 L 2.5 0"/>"#
+    );
+    // Short decimals and other text are left alone
+    assert_eq!(
+        normalize_svg_numbers("x=\"12.375\" fill=\"#123456\" v1.2.3"),
+        "x=\"12.375\" fill=\"#123456\" v1.2.3"
+    );
+}
+
+#[test]
+fn test_moon_phase_follows_the_date_not_the_wall_clock() {
+    let date = NaiveDate::from_ymd_opt(2025, 10, 7).unwrap();
+
+    assert_eq!(get_moon_phase_icon_name(date).to_string(), "moon-full.svg");
+}
+
+#[tokio::test]
+async fn test_rendering_twice_gives_identical_outputs() {
+    // Must be set before the configuration is loaded
+    std::env::set_var("APP_DEBUGGING__DETERMINISTIC_OUTPUT", "true");
+    if !helpers::test_utils::is_provider(
+        pi_inky_weather_epd::configs::settings::Providers::OpenMeteo,
+    ) {
+        return;
+    }
+    assert!(CONFIG.debugging.deterministic_output);
+
+    let clock = FixedClock::from_rfc3339("2025-10-25T01:00:00Z").unwrap();
+
+    let mut renders = Vec::new();
+    for name in ["deterministic_a.svg", "deterministic_b.svg"] {
+        // Each mock answers one request
+        let mock_server = wiremock_setup::setup_open_meteo_mock(
+            "tests/fixtures/open_meteo_hourly_forecast.json",
+            "tests/fixtures/open_meteo_daily_forecast.json",
+        )
+        .await;
+        std::env::set_var("OPEN_METEO_BASE_URL", mock_server.uri());
+
+        let output = Path::new("tests/output").join(name);
+        generate_weather_dashboard_injection(&clock, &CONFIG.misc.template_path, &output)
+            .await
+            .expect("dashboard renders");
+        renders.push(fs::read_to_string(&output).unwrap());
+    }
+    std::env::remove_var("OPEN_METEO_BASE_URL");
+
+    assert_eq!(renders[0], renders[1]);
+    assert_eq!(normalize_svg_numbers(&renders[0]), renders[0]);
+    assert_eq!(
+        convert_svg_to_png_bytes(&renders[0], 1.0, true).unwrap(),
+        convert_svg_to_png_bytes(&renders[1], 1.0, true).unwrap()
+    );
+}
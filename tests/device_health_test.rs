@@ -0,0 +1,197 @@
+#![cfg(all(feature = "web", unix))]
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pi_inky_weather_epd::device_health::{
+    device_summaries, is_valid_device_id, record_report, status_page_html, BootReport, FleetHealth,
+    MAX_DEVICES, MAX_REPORTS_PER_DEVICE,
+};
+use pi_inky_weather_epd::web_server::{router, serve_unix};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, 25, 6, 0, 0).unwrap() + Duration::minutes(minutes)
+}
+
+fn report(reset_reason: &str, wifi_join_ms: u32, free_heap_bytes: u32) -> BootReport {
+    BootReport {
+        reset_reason: Some(reset_reason.to_string()),
+        wifi_join_ms: Some(wifi_join_ms),
+        download_ms: Some(1500),
+        busy_wait_iterations: Some(4200),
+        free_heap_bytes: Some(free_heap_bytes),
+    }
+}
+
+#[test]
+fn test_device_ids() {
+    assert!(is_valid_device_id("kitchen-pico_2"));
+    assert!(!is_valid_device_id(""));
+    assert!(!is_valid_device_id("../etc"));
+    assert!(!is_valid_device_id(&"a".repeat(33)));
+}
+
+#[test]
+fn test_summary_over_the_kept_reports() {
+    let mut health = FleetHealth::default();
+    health.record("kitchen", report("PWRON_RESET", 2000, 90_000), at(0));
+    health.record("kitchen", report("WDT_RESET", 4000, 60_000), at(60));
+    health.record("hall", report("PWRON_RESET", 1000, 100_000), at(30));
+
+    let summaries = health.summaries();
+
+    assert_eq!(summaries.len(), 2);
+    let kitchen = &summaries[1];
+    assert_eq!(kitchen.device, "kitchen");
+    assert_eq!(kitchen.last_seen, at(60));
+    assert_eq!(kitchen.reports, 2);
+    assert_eq!(kitchen.watchdog_resets, 1);
+    assert_eq!(kitchen.mean_wifi_join_ms, Some(3000));
+    assert_eq!(kitchen.min_free_heap_bytes, Some(60_000));
+    assert_eq!(
+        kitchen.last_report.reset_reason.as_deref(),
+        Some("WDT_RESET")
+    );
+}
+
+#[test]
+fn test_only_the_latest_reports_are_kept() {
+    let mut health = FleetHealth::default();
+    for minutes in 0..30 {
+        health.record("kitchen", BootReport::default(), at(minutes));
+    }
+
+    let reports = &health.devices["kitchen"];
+    assert_eq!(reports.len(), MAX_REPORTS_PER_DEVICE);
+    assert_eq!(reports[0].received_at, at(6));
+}
+
+#[test]
+fn test_the_display_seen_longest_ago_is_dropped() {
+    let mut health = FleetHealth::default();
+    for device in 0..MAX_DEVICES {
+        health.record(
+            &format!("display-{device}"),
+            BootReport::default(),
+            at(100 + device as i64),
+        );
+    }
+    health.record("display-0", BootReport::default(), at(500));
+
+    health.record("hall", BootReport::default(), at(600));
+
+    assert_eq!(health.devices.len(), MAX_DEVICES);
+    assert!(health.devices.contains_key("hall"));
+    assert!(health.devices.contains_key("display-0"));
+    assert!(!health.devices.contains_key("display-1"));
+}
+
+#[test]
+fn test_partial_reports_are_accepted() {
+    let report: BootReport = serde_json::from_str(r#"{"free_heap_bytes": 1024}"#).unwrap();
+
+    assert_eq!(report.free_heap_bytes, Some(1024));
+    assert_eq!(report.reset_reason, None);
+    assert!(!report.is_watchdog_reset());
+}
+
+#[test]
+fn test_reports_survive_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    record_report(
+        dir.path(),
+        "kitchen",
+        report("PWRON_RESET", 2000, 90_000),
+        at(0),
+    )
+    .unwrap();
+    record_report(
+        dir.path(),
+        "kitchen",
+        report("PWRON_RESET", 2400, 80_000),
+        at(60),
+    )
+    .unwrap();
+
+    let summaries = device_summaries(dir.path());
+
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].reports, 2);
+    assert_eq!(summaries[0].mean_wifi_join_ms, Some(2200));
+}
+
+#[test]
+fn test_unreadable_health_file_starts_over() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("device_health.json"), "not json").unwrap();
+
+    assert!(device_summaries(dir.path()).is_empty());
+}
+
+#[test]
+fn test_status_page_lists_the_displays() {
+    let mut health = FleetHealth::default();
+    health.record("kitchen", report("WDT_RESET", 2000, 90_000), at(0));
+
+    let page = status_page_html(&health.summaries(), at(125));
+
+    assert!(page.contains("<td>kitchen</td>"));
+    assert!(page.contains("<td>2 h ago</td>"));
+    assert!(page.contains("<td>1 / 1</td>"));
+    assert!(page.contains("<td>87 KiB</td>"));
+    assert!(page.contains(r#"<tr class="warn">"#));
+}
+
+#[test]
+fn test_status_page_without_displays() {
+    let page = status_page_html(&[], at(0));
+
+    assert!(page.contains("No display has reported yet."));
+    assert!(!page.contains("<table>"));
+}
+
+async fn post(name: &str, path: &str, body: &str) -> String {
+    let socket = std::env::temp_dir().join(format!(
+        "pi-inky-weather-health-{name}-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket);
+
+    let listener = UnixListener::bind(&socket).unwrap();
+    tokio::spawn(serve_unix(listener, router()));
+
+    let mut stream = UnixStream::connect(&socket).await.unwrap();
+    stream
+        .write_all(
+            format!(
+                "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    std::fs::remove_file(&socket).unwrap();
+    response
+}
+
+#[tokio::test]
+async fn test_invalid_device_id_is_refused() {
+    let response = post("invalid", "/api/devices/bad.id/health", "{}").await;
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
+
+#[tokio::test]
+async fn test_malformed_report_is_refused() {
+    let response = post(
+        "malformed",
+        "/api/devices/kitchen/health",
+        r#"{"free_heap_bytes": "lots"}"#,
+    )
+    .await;
+
+    assert!(response.starts_with("HTTP/1.1 422"), "{response}");
+}
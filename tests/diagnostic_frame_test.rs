@@ -0,0 +1,71 @@
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::panel::svg_size;
+use pi_inky_weather_epd::weather_dashboard::{render_diagnostic_svg, write_unavailable_dashboard};
+use pi_inky_weather_epd::WeatherEpdError;
+
+fn clock() -> FixedClock {
+    FixedClock::from_rfc3339("2025-10-09T22:00:00Z").unwrap()
+}
+
+#[test]
+fn test_diagnostic_frame_is_valid_svg() {
+    let svg = render_diagnostic_svg(
+        "Dashboard rendering failed",
+        "attempt to subtract with overflow",
+        &clock(),
+    );
+
+    assert!(svg.contains("Dashboard rendering failed"));
+    assert!(svg.contains("attempt to subtract with overflow"));
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_diagnostic_frame_escapes_message() {
+    let svg = render_diagnostic_svg(
+        "Dashboard rendering failed",
+        "index out of bounds: <len> & \"idx\"",
+        &clock(),
+    );
+
+    assert!(svg.contains("index out of bounds: &lt;len&gt; &amp; &quot;idx&quot;"));
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_diagnostic_frame_truncates_long_messages() {
+    let svg = render_diagnostic_svg("Dashboard unavailable", &"x".repeat(500), &clock());
+
+    assert!(!svg.contains(&"x".repeat(71)));
+}
+
+#[test]
+fn test_diagnostic_frame_shows_failure_time() {
+    let svg = render_diagnostic_svg("Dashboard unavailable", "offline", &clock());
+
+    // 22:00 UTC is 09:00 the next morning in Melbourne
+    assert!(svg.contains("Failed at"));
+    assert!(svg.contains("09:00"));
+}
+
+#[test]
+fn test_unavailable_dashboard_is_written_to_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let output_svg_name = dir.path().join("dashboard.svg");
+    let error = WeatherEpdError::provider(anyhow::anyhow!("no cached forecast available"));
+
+    write_unavailable_dashboard(&clock(), &error, &output_svg_name).unwrap();
+
+    let svg = std::fs::read_to_string(&output_svg_name).unwrap();
+    assert!(svg.contains("Dashboard unavailable"));
+    assert!(svg.contains("no cached forecast available"));
+}
+
+#[test]
+fn test_diagnostic_frame_has_the_size_of_the_template() {
+    let svg = render_diagnostic_svg("Dashboard unavailable", "offline", &clock());
+    let template =
+        std::fs::read_to_string(&pi_inky_weather_epd::CONFIG.misc.template_path).unwrap();
+
+    assert_eq!(svg_size(&svg), svg_size(&template));
+}
@@ -0,0 +1,55 @@
+use pi_inky_weather_epd::configs::settings::DiagnosticsLayout;
+use pi_inky_weather_epd::dashboard::context::cascading_icons_svg;
+use pi_inky_weather_epd::errors::DashboardError;
+
+fn layout() -> DiagnosticsLayout {
+    DiagnosticsLayout {
+        x: 700.0,
+        y: 400.0,
+        icon_size: 40.0,
+        x_offset: 10.0,
+        y_offset: -4.5,
+    }
+}
+
+#[test]
+fn test_icons_are_placed_from_the_layout() {
+    let svg = cascading_icons_svg(
+        &[DashboardError::ApiError {
+            details: "timeout".to_string(),
+        }],
+        &layout(),
+    );
+    assert!(
+        svg.starts_with(r#"<image x="700" y="400" width="40" height="40" href=""#),
+        "{svg}"
+    );
+    assert!(svg.contains("code-red.svg"));
+}
+
+#[test]
+fn test_lower_priority_icons_are_offset_behind() {
+    let svg = cascading_icons_svg(
+        &[
+            DashboardError::IncompleteData {
+                details: "Only 5 days available".to_string(),
+            },
+            DashboardError::ApiError {
+                details: "timeout".to_string(),
+            },
+        ],
+        &layout(),
+    );
+    let icons: Vec<&str> = svg.lines().map(str::trim).collect();
+    assert_eq!(icons.len(), 2);
+    // Drawn back to front, the highest priority icon last
+    assert!(icons[0].starts_with(r#"<image x="710" y="395.5""#), "{svg}");
+    assert!(icons[0].contains("code-yellow.svg"));
+    assert!(icons[1].starts_with(r#"<image x="700" y="400""#), "{svg}");
+    assert!(icons[1].contains("code-red.svg"));
+}
+
+#[test]
+fn test_no_diagnostics_draw_nothing() {
+    assert_eq!(cascading_icons_svg(&[], &layout()), "");
+}
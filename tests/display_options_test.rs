@@ -0,0 +1,102 @@
+use image::{Rgb, RgbImage};
+use pi_inky_weather_epd::display_options::{
+    gray_levels, DisplayDriver, DisplayOptions, Dither, Palette, Rotation,
+};
+
+#[derive(serde::Deserialize)]
+struct Query {
+    rotate: Rotation,
+    palette: Palette,
+    dither: Dither,
+}
+
+#[test]
+fn test_query_values_deserialize() {
+    let query: Query =
+        serde_json::from_str(r#"{"rotate": 90, "palette": "bwr", "dither": "floyd-steinberg"}"#)
+            .unwrap();
+
+    assert_eq!(query.rotate, Rotation::Clockwise90);
+    assert_eq!(query.palette, Palette::BlackWhiteRed);
+    assert_eq!(query.dither, Dither::FloydSteinberg);
+    assert!(serde_json::from_str::<Rotation>("45").is_err());
+}
+
+#[test]
+fn test_default_options_leave_image_unchanged() {
+    let image = RgbImage::from_pixel(4, 2, Rgb([120, 130, 140]));
+    let options = DisplayOptions::default();
+
+    assert!(options.is_identity());
+    assert_eq!(options.apply(image.clone()), image);
+}
+
+#[test]
+fn test_rotation_swaps_dimensions() {
+    let image = RgbImage::new(800, 480);
+    let options = DisplayOptions {
+        rotation: Rotation::Clockwise270,
+        ..Default::default()
+    };
+
+    assert_eq!(options.apply(image).dimensions(), (480, 800));
+}
+
+#[test]
+fn test_palette_maps_to_panel_colours() {
+    let image = RgbImage::from_pixel(2, 2, Rgb([30, 140, 40]));
+    let options = DisplayOptions {
+        palette: Some(Palette::BlackWhiteRed),
+        ..Default::default()
+    };
+
+    let colours = Palette::BlackWhiteRed.colours();
+    for pixel in options.apply(image).pixels() {
+        assert!(colours.contains(&pixel.0));
+    }
+}
+
+#[test]
+fn test_dithering_mixes_palette_colours_for_grey() {
+    let image = RgbImage::from_pixel(16, 16, Rgb([128, 128, 128]));
+    let options = DisplayOptions {
+        palette: Some(Palette::BlackWhite),
+        dither: Dither::FloydSteinberg,
+        ..Default::default()
+    };
+
+    let dithered = options.apply(image);
+    let white = dithered.pixels().filter(|p| p.0 == [255, 255, 255]).count();
+    let black = dithered.pixels().filter(|p| p.0 == [0, 0, 0]).count();
+
+    assert_eq!(white + black, 256);
+    assert!((100..=156).contains(&white));
+}
+
+#[test]
+fn test_it8951_gets_grey_levels() {
+    let image = RgbImage::from_pixel(2, 2, Rgb([200, 30, 30]));
+    let options = DisplayOptions {
+        driver: DisplayDriver::It8951,
+        ..Default::default()
+    };
+
+    let levels = gray_levels();
+    assert_eq!(levels.len(), 16);
+    for pixel in options.apply(image).pixels() {
+        assert!(levels.contains(&pixel.0));
+    }
+}
+
+#[test]
+fn test_it8951_raw_packs_the_first_pixel_low_and_pads_rows_with_white() {
+    let mut image = RgbImage::from_pixel(5, 1, Rgb([255, 255, 255]));
+    image.put_pixel(0, 0, Rgb([0, 0, 0]));
+    image.put_pixel(3, 0, Rgb([119, 119, 119]));
+
+    let raw = DisplayDriver::It8951.to_raw(&image);
+
+    assert_eq!(raw, [0xf0, 0x7f, 0xff, 0xff]);
+    assert_eq!(DisplayDriver::It8951.raw_size(1872, 1404), 1_314_144);
+    assert_eq!(DisplayDriver::It8951.raw_size(5, 1), 4);
+}
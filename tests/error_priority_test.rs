@@ -7,7 +7,8 @@ use pi_inky_weather_epd::clock::FixedClock;
 use pi_inky_weather_epd::dashboard::chart::ElementVisibility;
 use pi_inky_weather_epd::dashboard::context::ContextBuilder;
 use pi_inky_weather_epd::domain::models::DailyForecast;
-use pi_inky_weather_epd::errors::DashboardError;
+use pi_inky_weather_epd::errors::{DashboardError, DiagnosticKind, DiagnosticPriority};
+use std::collections::HashMap;
 
 #[test]
 fn test_single_validation_error_displays() {
@@ -282,3 +283,42 @@ fn test_single_error_shows_one_icon() {
 
     assert!(context.diagnostic_icons_svg.contains("code-yellow.svg"));
 }
+
+#[test]
+fn test_configured_priorities_override_the_defaults() {
+    let stale = DashboardError::NoInternet {
+        details: "timeout".to_string(),
+    };
+    let incomplete = DashboardError::IncompleteData {
+        details: "Only 6 days available".to_string(),
+    };
+    let priorities = HashMap::from([(DiagnosticKind::IncompleteData, DiagnosticPriority::High)]);
+
+    assert_eq!(
+        incomplete.priority_in(&priorities),
+        DiagnosticPriority::High
+    );
+    // Kinds that aren't listed keep their default
+    assert_eq!(stale.priority_in(&priorities), DiagnosticPriority::Medium);
+    assert_eq!(incomplete.priority(), incomplete.default_priority());
+}
+
+#[test]
+fn test_priorities_are_configured_in_lowercase() {
+    let priorities: HashMap<DiagnosticKind, DiagnosticPriority> =
+        serde_json::from_str(r#"{"incomplete_data": "high", "weather_warning": "low"}"#).unwrap();
+
+    assert_eq!(
+        priorities[&DiagnosticKind::IncompleteData],
+        DiagnosticPriority::High
+    );
+    assert_eq!(
+        priorities[&DiagnosticKind::WeatherWarning],
+        DiagnosticPriority::Low
+    );
+    // Still served capitalized
+    assert_eq!(
+        serde_json::to_value(DiagnosticPriority::High).unwrap(),
+        "High"
+    );
+}
@@ -0,0 +1,30 @@
+use pi_inky_weather_epd::{
+    clock::FixedClock, generate_weather_dashboard_injection, WeatherEpdError,
+};
+use std::error::Error;
+use std::path::Path;
+
+#[tokio::test]
+async fn test_missing_template_is_config_error() {
+    let clock = FixedClock::from_rfc3339("2025-10-25T01:00:00Z").unwrap();
+
+    let result = generate_weather_dashboard_injection(
+        &clock,
+        Path::new("tests/output/does-not-exist.svg"),
+        Path::new("tests/output/error_taxonomy.svg"),
+    )
+    .await;
+
+    assert!(matches!(result, Err(WeatherEpdError::Config(_))));
+}
+
+#[test]
+fn test_error_keeps_source_chain() {
+    let error = WeatherEpdError::provider(anyhow::anyhow!("connection refused"));
+
+    assert_eq!(
+        error.to_string(),
+        "weather provider error: connection refused"
+    );
+    assert_eq!(error.source().unwrap().to_string(), "connection refused");
+}
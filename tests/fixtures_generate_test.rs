@@ -0,0 +1,83 @@
+use pi_inky_weather_epd::configs::settings::Providers;
+use pi_inky_weather_epd::constants::{DAILY_CACHE_SUFFIX, HOURLY_CACHE_SUFFIX};
+use pi_inky_weather_epd::fixtures::{normalize, sanitize, write_fixture, NormalizedForecast};
+use serde_json::{json, Value};
+use std::fs;
+
+#[test]
+fn test_sanitize_rounds_coordinates_and_zeroes_volatile_values() {
+    let mut response = json!({
+        "latitude": -37.8136,
+        "longitude": 144.9631,
+        "generationtime_ms": 0.1991,
+        "nested": [{ "latitude": 51.4779 }],
+        "temperature": 21.37,
+    });
+    sanitize(&mut response);
+    assert_eq!(
+        response,
+        json!({
+            "latitude": -37.8,
+            "longitude": 145.0,
+            "generationtime_ms": 0,
+            "nested": [{ "latitude": 51.5 }],
+            "temperature": 21.37,
+        })
+    );
+}
+
+#[test]
+fn test_fixtures_are_written_with_their_domain_forecasts() {
+    let dir = tempfile::tempdir().unwrap();
+    let response = fs::read_to_string("tests/fixtures/open_meteo_hourly_forecast.json").unwrap();
+
+    let paths = write_fixture(
+        dir.path(),
+        Providers::OpenMeteo,
+        HOURLY_CACHE_SUFFIX,
+        &response,
+    )
+    .unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("open_meteo_hourly_forecast.json"),
+            dir.path().join("open_meteo_hourly_forecast.expected.json"),
+        ]
+    );
+
+    let fixture: Value = serde_json::from_str(&fs::read_to_string(&paths[0]).unwrap()).unwrap();
+    assert_eq!(fixture["latitude"], json!(-37.8));
+    assert_eq!(fixture["generationtime_ms"], json!(0));
+
+    let expected: Value = serde_json::from_str(&fs::read_to_string(&paths[1]).unwrap()).unwrap();
+    let NormalizedForecast::Hourly(hourly) =
+        normalize(Providers::OpenMeteo, HOURLY_CACHE_SUFFIX, &response).unwrap()
+    else {
+        panic!("expected hourly forecasts");
+    };
+    assert_eq!(expected.as_array().unwrap().len(), hourly.len());
+    assert!(expected[0]["temperature"].is_object());
+}
+
+#[test]
+fn test_bom_daily_fixture_normalizes_to_days() {
+    let response = fs::read_to_string("tests/fixtures/bom_daily_forecast.json").unwrap();
+    let normalized = normalize(Providers::Bom, DAILY_CACHE_SUFFIX, &response).unwrap();
+    assert!(matches!(normalized, NormalizedForecast::Daily(days) if !days.is_empty()));
+}
+
+#[test]
+fn test_payloads_not_matching_the_models_have_no_expected_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let changed = r#"{"data": [{"renamed": true}]}"#;
+    let error =
+        write_fixture(dir.path(), Providers::Bom, HOURLY_CACHE_SUFFIX, changed).unwrap_err();
+    assert!(error.to_string().contains("doesn't match the models"));
+    // The payload is kept to update the models with
+    assert!(dir.path().join("bom_hourly_forecast.json").exists());
+    assert!(!dir
+        .path()
+        .join("bom_hourly_forecast.expected.json")
+        .exists());
+}
@@ -0,0 +1,163 @@
+use pi_inky_weather_epd::errors::{DashboardError, DiagnosticPriority};
+use pi_inky_weather_epd::utils::{fonts_missing_warning, missing_font_files};
+use pi_inky_weather_epd::weather::icons::Icon;
+
+#[test]
+fn test_shipped_fonts_are_present() {
+    assert!(missing_font_files().is_empty());
+}
+
+#[test]
+fn test_shipped_fonts_need_no_diagnostic() {
+    assert!(fonts_missing_warning().is_none());
+}
+
+const EMBEDDED_FONTS: [&str; 3] = [
+    "static/fonts/embedded/Roboto-VariableFont_wdth,wght.ttf",
+    "static/fonts/embedded/Roboto-Italic-VariableFont_wdth,wght.ttf",
+    "static/fonts/embedded/Roboto-Regular-Dashed.ttf",
+];
+
+/// Whether the font has an outline for `c`, the subsets keep the glyph ids of dropped glyphs
+fn has_outline(face: &ttf_parser::Face, c: char) -> bool {
+    face.glyph_index(c)
+        .and_then(|glyph| face.glyph_bounding_box(glyph))
+        .is_some()
+}
+
+#[test]
+fn test_embedded_fonts_keep_the_latin_characters_and_symbols() {
+    for file in EMBEDDED_FONTS {
+        let data = std::fs::read(file).unwrap();
+        let face = ttf_parser::Face::parse(&data, 0).unwrap();
+
+        for c in ['A', 'z', '7', '°', 'é', 'ł', '–', '…', '∞', '−', 'ﬁ'] {
+            assert!(has_outline(&face, c), "{file} lost {c}");
+        }
+        assert!(!has_outline(&face, 'Ж'), "{file} kept Cyrillic");
+        assert!(!has_outline(&face, 'λ'), "{file} kept Greek");
+    }
+}
+
+#[test]
+fn test_embedded_fonts_have_an_italic_face() {
+    let data = std::fs::read(EMBEDDED_FONTS[1]).unwrap();
+    let face = ttf_parser::Face::parse(&data, 0).unwrap();
+
+    assert!(face.is_italic());
+    assert!(face.is_variable());
+}
+
+#[test]
+fn test_fonts_missing_diagnostic_is_low_priority() {
+    let warning = DashboardError::FontsMissing {
+        details: "static/fonts/Roboto-Regular-Dashed.ttf".to_string(),
+    };
+
+    assert_eq!(warning.priority(), DiagnosticPriority::Low);
+    assert_eq!(warning.get_icon_name(), "code-yellow.svg");
+}
+
+#[test]
+fn test_repeated_conversions_render_identically() {
+    use pi_inky_weather_epd::utils::convert_svg_to_png_bytes;
+
+    let svg = r#"<svg width="200" height="50" xmlns="http://www.w3.org/2000/svg">
+    <text x="10" y="35" font-family="Roboto" font-size="24">Feels like 21</text>
+</svg>"#;
+
+    let first = convert_svg_to_png_bytes(svg, 1.0, true).unwrap();
+    let second = convert_svg_to_png_bytes(svg, 1.0, true).unwrap();
+    assert_eq!(first, second);
+}
+
+const DEJAVU_SANS: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
+const DEJAVU_SERIF: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSerif.ttf";
+
+/// Roboto and the DejaVu fonts, `None` where DejaVu isn't installed
+fn fallback_database() -> Option<(std::sync::Arc<usvg::fontdb::Database>, usvg::fontdb::ID)> {
+    if !std::path::Path::new(DEJAVU_SANS).is_file() || !std::path::Path::new(DEJAVU_SERIF).is_file()
+    {
+        return None;
+    }
+    let mut font_db = usvg::fontdb::Database::new();
+    let roboto = font_db
+        .load_font_source(usvg::fontdb::Source::File(
+            "static/fonts/Roboto-VariableFont_wdth,wght.ttf".into(),
+        ))
+        .first()
+        .copied()
+        .unwrap();
+    font_db.load_font_file(DEJAVU_SANS).unwrap();
+    font_db.load_font_file(DEJAVU_SERIF).unwrap();
+    Some((std::sync::Arc::new(font_db), roboto))
+}
+
+fn family_of(font_db: &usvg::fontdb::Database, id: usvg::fontdb::ID) -> String {
+    font_db.face(id).unwrap().families[0].0.clone()
+}
+
+#[test]
+fn test_fallback_families_are_tried_in_order() {
+    use pi_inky_weather_epd::utils::fallback_font;
+
+    let Some((mut font_db, roboto)) = fallback_database() else {
+        return;
+    };
+    let families = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let serif_first = fallback_font(
+        '∞',
+        &[roboto],
+        &mut font_db,
+        &families(&["DejaVu Serif", "DejaVu Sans"]),
+    )
+    .unwrap();
+    let sans_first = fallback_font(
+        '∞',
+        &[roboto],
+        &mut font_db,
+        &families(&["Not Installed", "DejaVu Sans", "DejaVu Serif"]),
+    )
+    .unwrap();
+
+    assert_eq!(family_of(&font_db, serif_first), "DejaVu Serif");
+    assert_eq!(family_of(&font_db, sans_first), "DejaVu Sans");
+}
+
+#[test]
+fn test_fallback_without_families_finds_any_font_with_the_glyph() {
+    use pi_inky_weather_epd::utils::fallback_font;
+
+    let Some((mut font_db, roboto)) = fallback_database() else {
+        return;
+    };
+
+    let font = fallback_font('☃', &[roboto], &mut font_db, &[]).unwrap();
+
+    assert!(family_of(&font_db, font).starts_with("DejaVu"));
+}
+
+#[test]
+fn test_character_no_font_has_gets_no_fallback() {
+    use pi_inky_weather_epd::utils::fallback_font;
+
+    let Some((mut font_db, roboto)) = fallback_database() else {
+        return;
+    };
+
+    assert_eq!(
+        fallback_font(
+            '\u{10FFFD}',
+            &[roboto],
+            &mut font_db,
+            &["DejaVu Sans".to_string()]
+        ),
+        None
+    );
+}
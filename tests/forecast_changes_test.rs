@@ -0,0 +1,107 @@
+mod helpers;
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::dashboard::forecast_changes::{DayOutlook, ForecastOutlooks};
+use pi_inky_weather_epd::domain::conditions::Condition;
+use pi_inky_weather_epd::domain::models::{DailyForecast, Precipitation, Temperature};
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+}
+
+/// A forecast day `offset` days from today, raining `rain_mm` with a high of `temp_max` °C
+fn day(offset: i64, rain_mm: u16, temp_max: f32) -> DailyForecast {
+    DailyForecast {
+        temp_max: Some(Temperature::celsius(temp_max)),
+        temp_min: Some(Temperature::celsius(temp_max - 10.0)),
+        precipitation: Some(Precipitation::new(Some(10), Some(rain_mm), Some(rain_mm))),
+        ..forecast::day(today() + Duration::days(offset))
+    }
+}
+
+fn outlook(condition: Condition, temp_max: f32) -> DayOutlook {
+    DayOutlook {
+        condition,
+        temp_max: Some(temp_max),
+    }
+}
+
+#[test]
+fn test_daily_condition_follows_the_icon() {
+    assert_eq!(day(1, 0, 25.0).condition(), Condition::Clear);
+    assert_eq!(day(1, 30, 25.0).condition(), Condition::Rain);
+}
+
+#[test]
+fn test_rain_appearing_or_a_big_temperature_move_is_a_revision() {
+    let sunny = outlook(Condition::Clear, 25.0);
+
+    assert!(outlook(Condition::Rain, 25.0).revises(&sunny, 4.0));
+    assert!(sunny.revises(&outlook(Condition::Drizzle, 25.0), 4.0));
+    assert!(outlook(Condition::Clear, 20.5).revises(&sunny, 4.0));
+    // More or fewer clouds, or a small temperature move, isn't worth pointing out
+    assert!(!outlook(Condition::Cloudy, 27.0).revises(&sunny, 4.0));
+    assert!(!outlook(Condition::HeavyRain, 25.0).revises(&outlook(Condition::Drizzle, 25.0), 4.0));
+}
+
+#[test]
+fn test_revised_days_keep_their_marker_for_a_while() {
+    let first_run = Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap();
+    let mut outlooks = ForecastOutlooks::default();
+
+    outlooks.update(&[day(1, 0, 25.0), day(2, 0, 25.0)], today(), first_run, 4.0);
+    assert!(outlooks.revised_days(first_run, 6).is_empty());
+
+    let second_run = first_run + Duration::hours(1);
+    outlooks.update(
+        &[day(1, 30, 25.0), day(2, 0, 26.0)],
+        today(),
+        second_run,
+        4.0,
+    );
+    let tomorrow = today() + Duration::days(1);
+    assert_eq!(outlooks.revised_days(second_run, 6), vec![tomorrow]);
+
+    // An unchanged run keeps the marker until it expires
+    let third_run = second_run + Duration::hours(1);
+    outlooks.update(
+        &[day(1, 30, 25.0), day(2, 0, 26.0)],
+        today(),
+        third_run,
+        4.0,
+    );
+    assert_eq!(outlooks.revised_days(third_run, 6), vec![tomorrow]);
+    assert!(outlooks
+        .revised_days(second_run + Duration::hours(6), 6)
+        .is_empty());
+}
+
+#[test]
+fn test_past_days_are_dropped() {
+    let now = Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap();
+    let mut outlooks = ForecastOutlooks::default();
+    outlooks.update(&[day(-1, 0, 25.0), day(0, 0, 25.0)], today(), now, 4.0);
+
+    assert_eq!(
+        outlooks.days.keys().copied().collect::<Vec<_>>(),
+        vec![today()]
+    );
+}
+
+#[test]
+fn test_outlooks_survive_a_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("forecast_outlooks.json");
+    let now = Utc.with_ymd_and_hms(2025, 1, 20, 0, 0, 0).unwrap();
+    let mut outlooks = ForecastOutlooks::default();
+    outlooks.update(&[day(1, 30, 25.0)], today(), now, 4.0);
+
+    outlooks.save(&path).unwrap();
+
+    assert_eq!(ForecastOutlooks::load(&path), outlooks);
+    assert_eq!(
+        ForecastOutlooks::load(&dir.path().join("missing.json")),
+        ForecastOutlooks::default()
+    );
+}
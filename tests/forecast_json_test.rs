@@ -0,0 +1,184 @@
+mod helpers;
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::forecast_json::{
+    DashboardDiagnostics, DashboardStatus, ForecastJson, FORECAST_SCHEMA_VERSION,
+};
+use pi_inky_weather_epd::domain::conditions::Condition;
+use pi_inky_weather_epd::domain::models::{
+    AmountPercentiles, Astronomical, DailyForecast, HourlyForecast, Obscuration, Precipitation,
+    Temperature,
+};
+use pi_inky_weather_epd::errors::{DashboardError, DiagnosticPriority};
+
+fn hour(offset: i64) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(14.0),
+        apparent_temperature: Temperature::celsius(13.0),
+        precipitation: Precipitation::new(Some(0), None, Some(0)),
+        ..forecast::hour(
+            Utc.with_ymd_and_hms(2025, 10, 9, 20, 0, 0).unwrap() + Duration::hours(offset),
+        )
+    }
+}
+
+fn day(date: NaiveDate) -> DailyForecast {
+    DailyForecast {
+        precipitation: None,
+        cloud_cover: None,
+        ..forecast::day(date)
+    }
+}
+
+fn clock() -> FixedClock {
+    // 09:30 on 10 Oct in Melbourne
+    FixedClock::from_rfc3339("2025-10-09T22:30:00Z").unwrap()
+}
+
+#[test]
+fn test_forecast_json_keeps_dashboard_windows() {
+    let hourly = (0..48).map(hour).collect();
+    let daily = (0..14)
+        .map(
+            |offset| day(NaiveDate::from_ymd_opt(2025, 10, 8).unwrap() + chrono::Days::new(offset)),
+        )
+        .collect();
+
+    let forecast = ForecastJson::new(daily, hourly, &[], &clock());
+
+    let current = forecast.current.unwrap();
+    assert_eq!(
+        current.time,
+        Utc.with_ymd_and_hms(2025, 10, 9, 22, 0, 0).unwrap()
+    );
+    assert_eq!(forecast.hourly.len(), 24);
+    assert_eq!(forecast.hourly[0].time, current.time);
+
+    assert_eq!(forecast.daily.len(), 7);
+    assert_eq!(
+        forecast.daily[0].date,
+        NaiveDate::from_ymd_opt(2025, 10, 10)
+    );
+}
+
+#[test]
+fn test_forecast_json_serializes_diagnostics_by_priority() {
+    let warnings = [
+        DashboardError::UpdateFailed {
+            details: "network".to_string(),
+        },
+        DashboardError::NoInternet {
+            details: "timeout".to_string(),
+        },
+    ];
+
+    let forecast = ForecastJson::new(vec![], vec![hour(2)], &warnings, &clock());
+
+    assert_eq!(forecast.diagnostics[0].priority, DiagnosticPriority::Medium);
+    assert_eq!(forecast.diagnostics[1].priority, DiagnosticPriority::Low);
+
+    let json = serde_json::to_value(&forecast).unwrap();
+    assert_eq!(json["current"]["temperature"]["unit"], "C");
+    assert_eq!(json["diagnostics"][0]["priority"], "Medium");
+    assert_eq!(
+        json["diagnostics"][0]["summary"],
+        "API unreachable -> Stale Data"
+    );
+}
+
+#[test]
+fn test_dashboard_status_is_degraded_with_diagnostics() {
+    assert_eq!(DashboardDiagnostics::new(&[]).status, DashboardStatus::Ok);
+
+    let warnings = [
+        DashboardError::IncompleteData {
+            details: "day 7 missing".to_string(),
+        },
+        DashboardError::ApiError {
+            details: "HTTP 500".to_string(),
+        },
+    ];
+    let diagnostics = DashboardDiagnostics::new(&warnings);
+
+    assert_eq!(diagnostics.status, DashboardStatus::Degraded);
+    assert_eq!(
+        diagnostics.diagnostics[0].priority,
+        DiagnosticPriority::High
+    );
+
+    let json = serde_json::to_value(&diagnostics).unwrap();
+    assert_eq!(json["status"], "degraded");
+    assert_eq!(DashboardStatus::Failed.to_string(), "failed");
+}
+
+#[test]
+fn test_weather_warnings_keep_the_status_ok() {
+    let warning = DashboardError::WeatherWarning {
+        details: "Frost Warning for Central district".to_string(),
+    };
+
+    let diagnostics = DashboardDiagnostics::new(std::slice::from_ref(&warning));
+    assert_eq!(diagnostics.status, DashboardStatus::Ok);
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+
+    let diagnostics = DashboardDiagnostics::new(&[
+        warning,
+        DashboardError::IncompleteData {
+            details: "day 7 missing".to_string(),
+        },
+    ]);
+    assert_eq!(diagnostics.status, DashboardStatus::Degraded);
+}
+
+/// Every field of the schema filled in, so none can change unnoticed
+fn full_forecast() -> ForecastJson {
+    let hour = HourlyForecast {
+        temperature: Temperature::celsius(14.5),
+        precipitation: Precipitation::new(Some(40), Some(1), Some(5)).with_percentiles(
+            AmountPercentiles {
+                p25: Some(1),
+                p50: Some(2),
+                p75: Some(4),
+            },
+        ),
+        uv_index: 3,
+        cloud_cover: Some(75),
+        cape: Some(120.5),
+        pressure: Some(1013.25),
+        aqi: Some(42),
+        pm2_5: Some(12.5),
+        obscuration: Some(Obscuration::Smoke),
+        temperature_spread: Some((Temperature::celsius(12.0), Temperature::celsius(16.5))),
+        reported_condition: Some(Condition::Storm),
+        ..hour(2)
+    };
+    let date = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+    let day = DailyForecast {
+        precipitation: Some(Precipitation::new(Some(40), Some(1), Some(5))),
+        astronomical: Some(Astronomical {
+            sunrise_time: date.and_hms_opt(6, 30, 0),
+            sunset_time: date.and_hms_opt(19, 30, 0),
+        }),
+        cloud_cover: Some(50),
+        ..day(date)
+    };
+    let warnings = [DashboardError::NoInternet {
+        details: "timeout".to_string(),
+    }];
+
+    ForecastJson::new(vec![day], vec![hour], &warnings, &clock())
+}
+
+#[test]
+fn test_forecast_json_matches_schema_v1() {
+    let expected: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/forecast_schema_v1.json")).unwrap();
+
+    let json = serde_json::to_value(full_forecast()).unwrap();
+
+    // A change here breaks companion apps: bump FORECAST_SCHEMA_VERSION and add a fixture for it
+    assert_eq!(FORECAST_SCHEMA_VERSION, 1);
+    assert_eq!(json, expected);
+}
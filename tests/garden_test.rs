@@ -0,0 +1,103 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pi_inky_weather_epd::apis::open_meteo::models::{GardenHourly, OpenMeteoGardenResponse};
+use pi_inky_weather_epd::configs::settings::Garden;
+use pi_inky_weather_epd::dashboard::garden::{garden_panel, GardenConditions};
+use pi_inky_weather_epd::dashboard::panel::PanelContent;
+use pi_inky_weather_epd::CONFIG;
+
+fn settings() -> Garden {
+    Garden {
+        enabled: true,
+        x: 620.0,
+        y: 90.0,
+        width: 170.0,
+        height: 50.0,
+        soil_moisture_threshold: 20.0,
+        water_deficit_threshold: 10.0,
+    }
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 10, 12, 0, 0).unwrap()
+}
+
+/// 4 days of hours up to 12 hours after `now` with the same values every hour
+fn garden_data(soil_moisture: f32, et0: f32, rain: f32) -> OpenMeteoGardenResponse {
+    let start = now() - Duration::days(4);
+    let time: Vec<_> = (0..=(4 * 24 + 12))
+        .map(|hour| start + Duration::hours(hour))
+        .collect();
+    let hours = time.len();
+    OpenMeteoGardenResponse {
+        hourly: GardenHourly {
+            soil_moisture_3_to_9cm: vec![Some(soil_moisture); hours],
+            et0_fao_evapotranspiration: vec![Some(et0); hours],
+            precipitation: vec![Some(rain); hours],
+            time,
+        },
+    }
+}
+
+#[test]
+fn test_conditions_balance_the_last_3_days() {
+    let conditions = GardenConditions::at(&garden_data(0.25, 0.25, 0.125), now());
+
+    assert_eq!(conditions.soil_moisture, Some(25.0));
+    // 72 hours of 0.25 mm ET0 and 0.125 mm rain
+    assert_eq!(conditions.water_deficit, Some(9.0));
+    assert!(!conditions.needs_water(&settings()));
+}
+
+#[test]
+fn test_dry_soil_or_water_deficit_needs_water() {
+    let dry_soil = GardenConditions::at(&garden_data(0.125, 0.0, 0.0), now());
+    let hot_week = GardenConditions::at(&garden_data(0.25, 0.25, 0.0), now());
+
+    assert!(dry_soil.needs_water(&settings()));
+    assert!(hot_week.needs_water(&settings()));
+}
+
+#[test]
+fn test_panel_shows_the_watering_status() {
+    let dry = garden_panel(
+        &settings(),
+        &GardenConditions::at(&garden_data(0.12, 0.25, 0.0), now()),
+        &CONFIG.colours,
+    );
+    let PanelContent::Svg(fragment) = &dry.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(">Watering needed<"));
+    assert!(fragment.contains(">Soil 12% · deficit 18 mm<"));
+
+    let wet = garden_panel(
+        &settings(),
+        &GardenConditions {
+            soil_moisture: None,
+            water_deficit: Some(-4.0),
+        },
+        &CONFIG.colours,
+    );
+    let PanelContent::Svg(fragment) = &wet.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(">No watering<"));
+    assert!(fragment.contains(">Deficit -4 mm<"));
+    assert!(fragment.contains(r#"fill="none""#));
+}
+
+#[test]
+fn test_panel_without_data_is_not_available() {
+    // The hours of the data all come after `now`
+    let conditions = GardenConditions::at(&garden_data(0.12, 0.25, 0.0), now() - Duration::days(5));
+    assert_eq!(conditions, GardenConditions::default());
+    assert!(!conditions.needs_water(&settings()));
+
+    let panel = garden_panel(&settings(), &conditions, &CONFIG.colours);
+    let PanelContent::Svg(fragment) = &panel.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(">NA<"));
+    assert!(fragment.contains(">No garden data<"));
+    assert!(!fragment.contains("watering"));
+}
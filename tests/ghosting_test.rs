@@ -0,0 +1,98 @@
+use image::{Rgb, RgbImage};
+use pi_inky_weather_epd::ghosting::{
+    compensate, compensate_against_previous, frame_changed, PREVIOUS_FRAME_FILE,
+    REFERENCE_FRAME_FILE,
+};
+
+fn frame(colour: [u8; 3]) -> RgbImage {
+    RgbImage::from_pixel(4, 2, Rgb(colour))
+}
+
+#[test]
+fn test_changed_pixels_are_pushed_past_their_new_colour() {
+    let previous = frame([100, 100, 100]);
+    let mut image = frame([100, 100, 100]);
+    image.put_pixel(0, 0, Rgb([200, 50, 100]));
+    image.put_pixel(1, 0, Rgb([240, 10, 100]));
+    // Below the threshold, e.g. anti-aliasing
+    image.put_pixel(2, 0, Rgb([120, 100, 100]));
+
+    compensate(&mut image, &previous, 0.5);
+
+    assert_eq!(image.get_pixel(0, 0), &Rgb([250, 25, 100]));
+    // Clamped to the colour range
+    assert_eq!(image.get_pixel(1, 0), &Rgb([255, 0, 100]));
+    assert_eq!(image.get_pixel(2, 0), &Rgb([120, 100, 100]));
+    assert_eq!(image.get_pixel(3, 1), &Rgb([100, 100, 100]));
+}
+
+#[test]
+fn test_frames_of_another_size_are_not_compared() {
+    let previous = RgbImage::from_pixel(2, 2, Rgb([0, 0, 0]));
+    let mut image = frame([200, 200, 200]);
+
+    compensate(&mut image, &previous, 1.0);
+
+    assert_eq!(image, frame([200, 200, 200]));
+}
+
+#[test]
+fn test_previous_frame_is_kept_uncompensated() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // The first frame has nothing to compare with
+    let first = compensate_against_previous(frame([0, 0, 0]), dir.path(), 0.5);
+    assert_eq!(first, frame([0, 0, 0]));
+
+    let second = compensate_against_previous(frame([100, 100, 100]), dir.path(), 0.5);
+    assert_eq!(second, frame([150, 150, 150]));
+
+    let kept = image::open(dir.path().join(PREVIOUS_FRAME_FILE))
+        .unwrap()
+        .to_rgb8();
+    assert_eq!(kept, frame([100, 100, 100]));
+}
+
+#[test]
+fn test_no_compensation_keeps_no_frame() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let image = compensate_against_previous(frame([100, 100, 100]), dir.path(), 0.0);
+
+    assert_eq!(image, frame([100, 100, 100]));
+    assert!(!dir.path().join(PREVIOUS_FRAME_FILE).exists());
+}
+
+#[test]
+fn test_same_content_comes_out_compensated_the_same() {
+    let dir = tempfile::tempdir().unwrap();
+    compensate_against_previous(frame([0, 0, 0]), dir.path(), 0.5);
+
+    let first = compensate_against_previous(frame([100, 100, 100]), dir.path(), 0.5);
+    let again = compensate_against_previous(frame([100, 100, 100]), dir.path(), 0.5);
+
+    assert_eq!(first, frame([150, 150, 150]));
+    assert_eq!(again, first);
+    let reference = image::open(dir.path().join(REFERENCE_FRAME_FILE))
+        .unwrap()
+        .to_rgb8();
+    assert_eq!(reference, frame([0, 0, 0]));
+
+    // New content is compensated against the frame before it
+    let next = compensate_against_previous(frame([60, 60, 60]), dir.path(), 0.5);
+    assert_eq!(next, frame([40, 40, 40]));
+}
+
+#[test]
+fn test_changes_below_the_threshold_keep_the_frame() {
+    let mut image = frame([100, 100, 100]);
+    image.put_pixel(0, 0, Rgb([120, 100, 100]));
+
+    assert!(!frame_changed(&image, &frame([100, 100, 100])));
+    image.put_pixel(1, 0, Rgb([200, 100, 100]));
+    assert!(frame_changed(&image, &frame([100, 100, 100])));
+    assert!(frame_changed(
+        &frame([100, 100, 100]),
+        &RgbImage::from_pixel(2, 2, Rgb([100, 100, 100]))
+    ));
+}
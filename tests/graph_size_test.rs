@@ -0,0 +1,48 @@
+use pi_inky_weather_epd::configs::settings::GraphDimension;
+use pi_inky_weather_epd::dashboard::context::graph_viewbox;
+use pi_inky_weather_epd::weather_dashboard::graph_viewport_warning;
+
+const TEMPLATE: &str = r#"<svg width="800" height="480">
+    <svg x="220" y="160" width="560" height="350" viewBox="{graph_viewbox}" preserveAspectRatio="xMidYMid meet">
+    </svg>
+</svg>"#;
+
+#[test]
+fn test_graph_viewbox_includes_label_margins() {
+    assert_eq!(graph_viewbox(600.0, 300.0), "-50 0 690 300");
+    assert_eq!(graph_viewbox(1200.0, 600.0), "-50 0 1290 600");
+}
+
+#[test]
+fn test_default_graph_size_fits_default_template() {
+    assert_eq!(graph_viewport_warning(TEMPLATE, 600.0, 300.0), None);
+}
+
+#[test]
+fn test_graph_size_far_from_viewport_warns() {
+    let too_large = graph_viewport_warning(TEMPLATE, 2400.0, 1200.0).unwrap();
+    assert!(too_large.contains("2400x1200"), "{too_large}");
+
+    let scaled_template = TEMPLATE
+        .replace(r#"width="560""#, r#"width="2240""#)
+        .replace(r#"height="350""#, r#"height="1400""#);
+    assert!(graph_viewport_warning(&scaled_template, 600.0, 300.0).is_some());
+    assert_eq!(
+        graph_viewport_warning(&scaled_template, 2400.0, 1200.0),
+        None
+    );
+}
+
+#[test]
+fn test_hard_coded_viewbox_only_warns_for_custom_size() {
+    let legacy = TEMPLATE.replace("{graph_viewbox}", "-50 0 690 300");
+    assert_eq!(graph_viewport_warning(&legacy, 600.0, 300.0), None);
+    assert!(graph_viewport_warning(&legacy, 1200.0, 600.0).is_some());
+}
+
+#[test]
+fn test_graph_dimension_bounds() {
+    assert!(GraphDimension::try_new(600.0).is_ok());
+    assert!(GraphDimension::try_new(50.0).is_err());
+    assert!(GraphDimension::try_new(10_000.0).is_err());
+}
@@ -57,3 +57,59 @@ pub mod outputs {
         Path::new("tests/output").join(format!("snapshot_bom_{}.svg", name))
     }
 }
+
+/// Forecast records with mild, dry defaults, so a test only spells out the fields it checks:
+///
+/// ```ignore
+/// let hour = HourlyForecast {
+///     temperature: Temperature::celsius(30.0),
+///     ..forecast::hour(time)
+/// };
+/// ```
+#[allow(dead_code)] // Used by the dashboard unit test files
+pub mod forecast {
+    use chrono::{DateTime, NaiveDate, Utc};
+    use pi_inky_weather_epd::domain::models::{
+        DailyForecast, HourlyForecast, Precipitation, Temperature, Wind,
+    };
+
+    /// No chance of precipitation and none expected
+    pub fn dry() -> Precipitation {
+        Precipitation::new(Some(0), Some(0), Some(0))
+    }
+
+    /// A clear, dry, 18°C daytime hour starting at `time`
+    pub fn hour(time: DateTime<Utc>) -> HourlyForecast {
+        HourlyForecast {
+            time,
+            temperature: Temperature::celsius(18.0),
+            apparent_temperature: Temperature::celsius(17.0),
+            wind: Wind::new(10, 20),
+            precipitation: dry(),
+            uv_index: 0,
+            relative_humidity: 60,
+            is_night: false,
+            cloud_cover: Some(0),
+            cape: None,
+            pressure: None,
+            aqi: None,
+            pm2_5: None,
+            obscuration: None,
+            temperature_spread: None,
+            reported_condition: None,
+        }
+    }
+
+    /// A clear, dry day from 10°C to 20°C without sunrise and sunset times
+    pub fn day(date: NaiveDate) -> DailyForecast {
+        DailyForecast {
+            date: Some(date),
+            temp_max: Some(Temperature::celsius(20.0)),
+            temp_min: Some(Temperature::celsius(10.0)),
+            precipitation: Some(dry()),
+            astronomical: None,
+            cloud_cover: Some(0),
+            obscuration: None,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::dashboard::summary::hourly_summary;
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Precipitation, Wind};
+
+fn hour(time: DateTime<Utc>, chance: u16, wind_kmh: u16) -> HourlyForecast {
+    HourlyForecast {
+        wind: Wind::new(wind_kmh, wind_kmh + 10),
+        // Light amounts, so wet hours are showers rather than rain
+        precipitation: Precipitation::new(Some(chance), Some(0), Some(chance / 80)),
+        cloud_cover: Some(40),
+        ..forecast::hour(time)
+    }
+}
+
+/// 24 hours starting at 9:00 local time, with the given chance and wind for each hour
+fn day_from_9am(chance: impl Fn(usize) -> u16, wind: impl Fn(usize) -> u16) -> Vec<HourlyForecast> {
+    let start = nine_am().with_timezone(&Utc);
+    (0..24)
+        .map(|i| hour(start + Duration::hours(i as i64), chance(i), wind(i)))
+        .collect()
+}
+
+fn nine_am() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 10, 1, 9, 0, 0).unwrap()
+}
+
+#[test]
+fn test_summary_showers_easing_and_wind_later() {
+    // Wet until 14:00, windy from 23:00
+    let hourly = day_from_9am(
+        |i| if i < 5 { 80 } else { 10 },
+        |i| if i >= 14 { 50 } else { 10 },
+    );
+
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, false),
+        "Showers easing this afternoon, windy overnight"
+    );
+}
+
+#[test]
+fn test_summary_showers_developing_tomorrow() {
+    let hourly = day_from_9am(|i| if i >= 21 { 90 } else { 0 }, |_| 10);
+
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, false),
+        "Showers developing tomorrow morning"
+    );
+}
+
+#[test]
+fn test_summary_dry_day_uses_the_sky_condition() {
+    let hourly = day_from_9am(|_| 0, |_| 45);
+
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, false),
+        "Partly cloudy, windy"
+    );
+}
+
+#[test]
+fn test_summary_gusts_count_when_configured() {
+    let hourly = day_from_9am(|_| 0, |_| 35);
+
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, false),
+        "Partly cloudy"
+    );
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, true),
+        "Partly cloudy, windy"
+    );
+}
+
+#[test]
+fn test_summary_heavy_amounts_are_rain() {
+    let mut hourly = day_from_9am(|_| 90, |_| 10);
+    hourly[0].precipitation = Precipitation::new(Some(90), Some(2), Some(4));
+
+    assert_eq!(
+        hourly_summary(&hourly, nine_am(), 50, false),
+        "Rain continuing"
+    );
+}
+
+#[test]
+fn test_summary_is_empty_without_data() {
+    assert_eq!(hourly_summary(&[], nine_am(), 50, false), "");
+}
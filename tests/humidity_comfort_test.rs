@@ -0,0 +1,53 @@
+use pi_inky_weather_epd::configs::settings::{HumidityComfortRange, PaletteColour};
+use pi_inky_weather_epd::dashboard::chart::HourlyForecastGraph;
+
+#[test]
+fn test_draw_humidity_curve_on_the_right_axis() {
+    let graph = HourlyForecastGraph::builder()
+        .size(230.0, 100.0)
+        .humidity(&[40.0, 65.0, 120.0])
+        .build();
+
+    // 100% is the top of the graph, values above it are clamped
+    assert_eq!(
+        graph.draw_humidity_curve(),
+        "M 0.0000 40.0000 L 10.0000 65.0000 L 20.0000 100.0000"
+    );
+}
+
+#[test]
+fn test_draw_humidity_comfort_bands() {
+    let graph = HourlyForecastGraph::builder()
+        .size(230.0, 200.0)
+        .humidity(&[50.0; 24])
+        .humidity_comfort_range(30.0, 60.0)
+        .build();
+    let (dry, humid) = graph.draw_humidity_comfort_bands();
+
+    assert_eq!(dry, "M 0 0.0000 H 230.0000 V 60.0000 H 0 Z");
+    assert_eq!(humid, "M 0 120.0000 H 230.0000 V 200.0000 H 0 Z");
+}
+
+#[test]
+fn test_no_humidity_curve_or_bands_without_humidity_data() {
+    let graph = HourlyForecastGraph::builder()
+        .humidity_comfort_range(30.0, 60.0)
+        .build();
+
+    assert_eq!(graph.draw_humidity_curve(), "");
+    assert_eq!(
+        graph.draw_humidity_comfort_bands(),
+        (String::new(), String::new())
+    );
+}
+
+#[test]
+fn test_comfort_range_and_band_colours_are_validated() {
+    assert!(HumidityComfortRange::try_new((30, 60)).is_ok());
+    assert!(HumidityComfortRange::try_new((60, 30)).is_err());
+    assert!(HumidityComfortRange::try_new((30, 120)).is_err());
+
+    assert!(PaletteColour::try_new(" Orange ").is_ok());
+    assert!(PaletteColour::try_new("pink").is_err());
+    assert!(PaletteColour::try_new("#ff8800").is_err());
+}
@@ -0,0 +1,97 @@
+use pi_inky_weather_epd::icon_cache::{is_cached, CachedIcons, IconKey};
+use pi_inky_weather_epd::utils::convert_svg_to_png_bytes;
+use resvg::usvg;
+use std::path::{Path, PathBuf};
+
+/// A red square icon in `dir`
+fn icon(dir: &Path) -> PathBuf {
+    let path = dir.join("square.svg");
+    std::fs::write(
+        &path,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"><rect width="10" height="10" fill="red"/></svg>"#,
+    )
+    .unwrap();
+    path
+}
+
+fn dashboard(body: &str) -> String {
+    format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="40">{body}</svg>"#)
+}
+
+fn key(path: &Path, size: u32) -> IconKey {
+    IconKey {
+        path: path.to_path_buf(),
+        width: size,
+        height: size,
+        anti_aliasing: true,
+    }
+}
+
+#[test]
+fn test_icons_are_cached_per_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let icon = icon(dir.path());
+    let svg = dashboard(&format!(
+        r#"<image x="10" y="10" width="20" height="20" href="{0}"/><svg x="0" y="0"><image width="5" height="5" href="{0}"/></svg>"#,
+        icon.display()
+    ));
+
+    let cached = CachedIcons::of(&svg, 2.0, true, &usvg::Options::default());
+
+    assert_eq!(cached.icons.len(), 2);
+    assert!(is_cached(&key(&icon, 40)));
+    assert!(is_cached(&key(&icon, 10)));
+    assert!(!cached.svg.contains(&icon.display().to_string()));
+    assert!(cached
+        .svg
+        .contains(r#"<image image-rendering="optimizeSpeed" x="10""#));
+}
+
+#[test]
+fn test_scaled_icons_are_left_to_usvg() {
+    let dir = tempfile::tempdir().unwrap();
+    let icon = icon(dir.path());
+    let svg = dashboard(&format!(
+        r#"<svg viewBox="0 0 10 10" width="40" height="40"><image width="5" height="5" href="{0}"/></svg><g transform="scale(2)"><image width="5" height="5" href="{0}"/></g><image width="5" height="5" transform="rotate(45)" href="{0}"/>"#,
+        icon.display()
+    ));
+
+    let cached = CachedIcons::of(&svg, 1.0, true, &usvg::Options::default());
+
+    assert!(cached.icons.is_empty());
+    assert_eq!(cached.svg, svg);
+}
+
+#[test]
+fn test_missing_and_raster_icons_are_left_to_usvg() {
+    let svg = dashboard(
+        r#"<image width="5" height="5" href="static/missing.svg"/><image width="5" height="5" href="static/photo.png"/><image width="5" height="5" href="data:image/svg+xml;base64,AAAA"/>"#,
+    );
+
+    let cached = CachedIcons::of(&svg, 1.0, true, &usvg::Options::default());
+
+    assert!(cached.icons.is_empty());
+    assert_eq!(cached.svg, svg);
+}
+
+#[test]
+fn test_cached_icons_render_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let icon = icon(dir.path());
+    let svg = dashboard(&format!(
+        r#"<rect width="40" height="40" fill="white"/><image x="10" y="10" width="20" height="20" href="{}"/>"#,
+        icon.display()
+    ));
+
+    for _ in 0..2 {
+        let png = convert_svg_to_png_bytes(&svg, 2.0, true).unwrap();
+
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (80, 80));
+        assert_eq!(image.get_pixel(19, 19), &image::Rgb([255, 255, 255]));
+        assert_eq!(image.get_pixel(20, 20), &image::Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(59, 59), &image::Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(60, 60), &image::Rgb([255, 255, 255]));
+    }
+    assert!(is_cached(&key(&icon, 40)));
+}
@@ -30,6 +30,13 @@ fn test_cloud_cover_overrides_low_precipitation_chance() {
         relative_humidity: 70,
         is_night: false,
         cloud_cover: Some(80), // High cloud cover - should override
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     assert_eq!(forecast.get_icon_name(), "extreme-day.svg");
@@ -48,6 +55,13 @@ fn test_cloud_cover_boundary_25_percent() {
         relative_humidity: 50,
         is_night: false,
         cloud_cover: Some(25), // Boundary - still Clear
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let forecast_26 = HourlyForecast {
@@ -72,6 +86,13 @@ fn test_cloud_cover_boundary_50_and_51_percent() {
         relative_humidity: 65,
         is_night: false,
         cloud_cover: Some(50),
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let forecast_51 = HourlyForecast {
@@ -96,6 +117,13 @@ fn test_cloud_cover_boundary_75_and_76_percent() {
         relative_humidity: 80,
         is_night: false,
         cloud_cover: Some(75),
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let forecast_76 = HourlyForecast {
@@ -124,6 +152,13 @@ fn test_null_cloud_cover_falls_back_to_precipitation() {
         relative_humidity: 60,
         is_night: false,
         cloud_cover: None, // Fallback to precipitation
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     assert_eq!(forecast.get_icon_name(), "partly-cloudy-day.svg");
@@ -150,6 +185,13 @@ fn test_precipitation_override_drizzle_requires_partly_cloudy() {
         relative_humidity: 65,
         is_night: false,
         cloud_cover: Some(15), // Clear range, but drizzle present
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     // Should be bumped to partly-cloudy due to drizzle
@@ -173,6 +215,13 @@ fn test_precipitation_override_rain_requires_overcast() {
         relative_humidity: 85,
         is_night: false,
         cloud_cover: Some(20), // Clear range, but heavy rain present
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     // Should be bumped to overcast due to heavy rain
@@ -196,6 +245,13 @@ fn test_precipitation_override_partly_cloudy_rain_becomes_overcast() {
         relative_humidity: 90,
         is_night: false,
         cloud_cover: Some(40), // PartlyCloudy range, but heavy rain present
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     // Should be bumped to overcast due to heavy rain
@@ -217,6 +273,7 @@ fn test_fallback_with_precipitation_override() {
         )),
         astronomical: None,
         cloud_cover: None, // Fallback to precipitation
+        obscuration: None,
     };
 
     assert_eq!(forecast.get_icon_name(), "clear-day.svg");
@@ -244,6 +301,13 @@ fn test_partly_cloudy_with_drizzle_is_valid() {
         relative_humidity: 70,
         is_night: false,
         cloud_cover: None,
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -270,6 +334,13 @@ fn test_overcast_with_rain_is_valid() {
         relative_humidity: 85,
         is_night: false,
         cloud_cover: None,
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -296,6 +367,13 @@ fn test_extreme_with_drizzle_is_valid() {
         relative_humidity: 90,
         is_night: true,
         cloud_cover: None,
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -319,6 +397,7 @@ fn test_zero_chance_zero_amount_produces_clear() {
         )),
         astronomical: None,
         cloud_cover: None,
+        obscuration: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -344,6 +423,13 @@ fn test_boundary_case_25_percent_is_still_clear() {
         relative_humidity: 55,
         is_night: false,
         cloud_cover: Some(22), // Explicitly set low cloud cover to test clear sky logic
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -370,6 +456,13 @@ fn test_boundary_case_26_percent_allows_precipitation_suffix() {
         relative_humidity: 55,
         is_night: false,
         cloud_cover: None,
+        cape: None,
+        pressure: None,
+        aqi: None,
+        pm2_5: None,
+        obscuration: None,
+        temperature_spread: None,
+        reported_condition: None,
     };
 
     let icon_name = forecast.get_icon_name();
@@ -0,0 +1,80 @@
+use pi_inky_weather_epd::weather::icons::load_icon_overrides;
+use std::fs;
+use std::path::Path;
+
+const ICONS: &str = "static/fill-svg-static";
+
+#[test]
+fn test_icon_overrides_resolve_relative_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("custom")).unwrap();
+    fs::write(dir.path().join("custom/smoke.svg"), "<svg/>").unwrap();
+    let overrides_path = dir.path().join("icons.toml");
+    fs::write(
+        &overrides_path,
+        r#"
+        [icons]
+        haze = "custom/smoke.svg"
+        "overcast-day-rain.svg" = "custom/smoke.svg"
+        "#,
+    )
+    .unwrap();
+
+    let overrides = load_icon_overrides(&overrides_path, Path::new(ICONS)).unwrap();
+
+    assert_eq!(overrides["haze"], dir.path().join("custom/smoke.svg"));
+    assert_eq!(
+        overrides["overcast-day-rain"],
+        dir.path().join("custom/smoke.svg")
+    );
+}
+
+#[test]
+fn test_icon_overrides_skip_missing_and_non_svg_files() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("rain.png"), "png").unwrap();
+    let overrides_path = dir.path().join("icons.toml");
+    fs::write(
+        &overrides_path,
+        r#"
+        [icons]
+        rain = "rain.png"
+        snow = "does-not-exist.svg"
+        "#,
+    )
+    .unwrap();
+
+    assert!(load_icon_overrides(&overrides_path, Path::new(ICONS))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_icon_overrides_invalid_file_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let overrides_path = dir.path().join("icons.toml");
+    fs::write(&overrides_path, "icons = 42").unwrap();
+
+    assert!(load_icon_overrides(&overrides_path, Path::new(ICONS)).is_err());
+    assert!(load_icon_overrides(&dir.path().join("missing.toml"), Path::new(ICONS)).is_err());
+}
+
+#[test]
+fn test_icon_overrides_unknown_icon_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("smoke.svg"), "<svg/>").unwrap();
+    let overrides_path = dir.path().join("icons.toml");
+    fs::write(
+        &overrides_path,
+        r#"
+        [icons]
+        haze = "smoke.svg"
+        hazy = "smoke.svg"
+        "#,
+    )
+    .unwrap();
+
+    let error = load_icon_overrides(&overrides_path, Path::new(ICONS)).unwrap_err();
+
+    assert!(error.to_string().contains("Unknown icon 'hazy'"), "{error}");
+}
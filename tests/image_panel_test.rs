@@ -0,0 +1,85 @@
+use image::{ImageFormat, Rgb, RgbImage};
+use pi_inky_weather_epd::configs::settings::ImagePanel;
+use pi_inky_weather_epd::dashboard::image_panel::{fetch_image_panel, prepare_image};
+use pi_inky_weather_epd::display_options::{Dither, Palette};
+use std::io::Cursor;
+use wiremock::matchers::path;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn settings(url: Option<String>) -> ImagePanel {
+    ImagePanel {
+        url: url.map(|url| url.parse().unwrap()),
+        width: 40,
+        height: 30,
+        palette: Palette::BlackWhite,
+        dither: Dither::FloydSteinberg,
+    }
+}
+
+/// An 80x40 grey gradient, as a webcam JPEG would decode to
+fn gradient_png() -> Vec<u8> {
+    let image = RgbImage::from_fn(80, 40, |x, _| {
+        let level = (x * 255 / 79) as u8;
+        Rgb([level, level, level])
+    });
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+    png
+}
+
+#[test]
+fn test_prepared_image_fills_the_panel_with_palette_colours() {
+    let png = prepare_image(&gradient_png(), &settings(None)).unwrap();
+    let image = image::load_from_memory(&png).unwrap().to_rgb8();
+
+    assert_eq!(image.dimensions(), (40, 30));
+    assert!(image
+        .pixels()
+        .all(|pixel| pixel.0 == [0, 0, 0] || pixel.0 == [255, 255, 255]));
+}
+
+#[tokio::test]
+async fn test_failed_fetch_falls_back_to_the_cached_image() {
+    let server = MockServer::start().await;
+    let cache_dir = tempfile::tempdir().unwrap();
+    let panel = settings(Some(format!("{}/webcam.png", server.uri())));
+
+    Mock::given(path("/webcam.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(gradient_png()))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(path("/webcam.png"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let fresh = fetch_image_panel(&panel, cache_dir.path(), false)
+        .await
+        .unwrap();
+    assert!(fresh.starts_with("data:image/png;base64,"));
+
+    let fallback = fetch_image_panel(&panel, cache_dir.path(), false).await;
+    assert_eq!(fallback, Some(fresh));
+}
+
+#[tokio::test]
+async fn test_panel_is_hidden_without_url_or_cache() {
+    let cache_dir = tempfile::tempdir().unwrap();
+
+    assert_eq!(
+        fetch_image_panel(&settings(None), cache_dir.path(), false).await,
+        None
+    );
+    assert_eq!(
+        fetch_image_panel(
+            &settings(Some("http://127.0.0.1:1/webcam.png".to_string())),
+            cache_dir.path(),
+            true,
+        )
+        .await,
+        None
+    );
+}
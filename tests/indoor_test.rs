@@ -0,0 +1,216 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::configs::settings::{ComfortRules, Indoor};
+use pi_inky_weather_epd::dashboard::indoor::{
+    fetch_indoor, indoor_lines, indoor_panel, parse_indoor_reading, IndoorComparison,
+    IndoorReading, WindowHint,
+};
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Temperature};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+
+fn rules() -> ComfortRules {
+    serde_json::from_value(json!({
+        "min_temperature": 18.0,
+        "max_temperature": 24.0,
+        "max_humidity": 60.0,
+        "temperature_delta": 2.0,
+        "humidity_delta": 10.0
+    }))
+    .unwrap()
+}
+
+fn settings(path: Option<&std::path::Path>) -> Indoor {
+    serde_json::from_value(json!({
+        "enabled": true,
+        "x": 620.0,
+        "y": 145.0,
+        "width": 170.0,
+        "height": 60.0,
+        "path": path,
+        "temperature_pointer": "/attributes/current_temperature",
+        "humidity_pointer": "/attributes/current_humidity",
+        "comfort": {
+            "min_temperature": 18.0,
+            "max_temperature": 24.0,
+            "max_humidity": 60.0,
+            "temperature_delta": 2.0,
+            "humidity_delta": 10.0
+        }
+    }))
+    .unwrap()
+}
+
+fn comparison(inside: (f32, f32), outside: (f32, f32)) -> IndoorComparison {
+    IndoorComparison {
+        indoor: IndoorReading {
+            temperature: Some(inside.0),
+            humidity: Some(inside.1),
+        },
+        outdoor: IndoorReading {
+            temperature: Some(outside.0),
+            humidity: Some(outside.1),
+        },
+    }
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 10, 3, 30, 0).unwrap()
+}
+
+fn hour(time: DateTime<Utc>, temperature: f32, humidity: i16) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(temperature),
+        apparent_temperature: Temperature::celsius(temperature),
+        relative_humidity: humidity,
+        ..forecast::hour(time)
+    }
+}
+
+#[test]
+fn test_home_assistant_states_are_read_from_strings_and_numbers() {
+    let state = json!({
+        "state": "heat",
+        "attributes": { "current_temperature": "21.5", "current_humidity": 48 }
+    });
+    assert_eq!(
+        parse_indoor_reading(
+            &state,
+            "/attributes/current_temperature",
+            "/attributes/current_humidity"
+        ),
+        Some(IndoorReading {
+            temperature: Some(21.5),
+            humidity: Some(48.0),
+        })
+    );
+    assert_eq!(
+        parse_indoor_reading(&json!({ "temperature": 20 }), "/temperature", "/humidity"),
+        Some(IndoorReading {
+            temperature: Some(20.0),
+            humidity: None,
+        })
+    );
+    assert_eq!(
+        parse_indoor_reading(&json!({ "state": "unavailable" }), "/state", "/humidity"),
+        None
+    );
+}
+
+#[test]
+fn test_outdoors_is_the_current_hour_of_the_forecast() {
+    let hourly = vec![
+        hour(now() - Duration::hours(1), 12.0, 90),
+        hour(now() - Duration::minutes(30), 15.0, 70),
+        hour(now() + Duration::minutes(30), 30.0, 20),
+    ];
+    let indoor = IndoorReading {
+        temperature: Some(21.0),
+        humidity: Some(50.0),
+    };
+    let comparison = IndoorComparison::at(indoor, &hourly, now());
+    assert_eq!(comparison.outdoor.temperature, Some(15.0));
+    assert_eq!(comparison.temperature_delta(), Some(6.0));
+    assert_eq!(comparison.humidity_delta(), Some(-20.0));
+}
+
+#[test]
+fn test_windows_open_when_outdoors_helps() {
+    let rules = rules();
+    // Too warm inside, cooler outside
+    assert_eq!(
+        comparison((27.0, 50.0), (20.0, 50.0)).window_hint(&rules),
+        Some(WindowHint::Open)
+    );
+    // Too cold inside, warmer outside
+    assert_eq!(
+        comparison((16.0, 50.0), (22.0, 50.0)).window_hint(&rules),
+        Some(WindowHint::Open)
+    );
+    // Too warm inside, even warmer outside
+    assert_eq!(
+        comparison((27.0, 50.0), (33.0, 30.0)).window_hint(&rules),
+        Some(WindowHint::KeepClosed)
+    );
+    // Too humid inside, drier and mild outside
+    assert_eq!(
+        comparison((21.0, 70.0), (19.0, 55.0)).window_hint(&rules),
+        Some(WindowHint::AirOut)
+    );
+}
+
+#[test]
+fn test_no_hint_when_comfortable_or_outdoors_wouldnt_help() {
+    let rules = rules();
+    assert_eq!(
+        comparison((21.0, 50.0), (10.0, 90.0)).window_hint(&rules),
+        None
+    );
+    // Only slightly cooler outside
+    assert_eq!(
+        comparison((25.0, 50.0), (24.0, 50.0)).window_hint(&rules),
+        None
+    );
+    // Humid inside but cold outside
+    assert_eq!(
+        comparison((21.0, 70.0), (5.0, 40.0)).window_hint(&rules),
+        None
+    );
+}
+
+#[test]
+fn test_lines_show_reading_deltas_and_hint() {
+    assert_eq!(
+        indoor_lines(&comparison((27.0, 50.0), (20.5, 62.0)), &rules()),
+        vec![
+            "Indoor 27.0° · 50%",
+            "vs outside +6.5° · -12%",
+            "Open the windows",
+        ]
+    );
+
+    let temperature_only = IndoorComparison {
+        indoor: IndoorReading {
+            temperature: Some(21.0),
+            humidity: None,
+        },
+        outdoor: IndoorReading::default(),
+    };
+    assert_eq!(
+        indoor_lines(&temperature_only, &rules()),
+        vec!["Indoor 21.0°"]
+    );
+    assert!(indoor_panel(
+        &settings(None),
+        &IndoorComparison::default(),
+        &CONFIG.colours
+    )
+    .is_none());
+}
+
+#[tokio::test]
+async fn test_reading_comes_from_the_sensor_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("indoor.json");
+    std::fs::write(
+        &path,
+        r#"{"attributes": {"current_temperature": 22.4, "current_humidity": "55"}}"#,
+    )
+    .unwrap();
+
+    // The file is read even when requests are disabled
+    let reading = fetch_indoor(&settings(Some(&path)), true).await;
+    assert_eq!(
+        reading,
+        Some(IndoorReading {
+            temperature: Some(22.4),
+            humidity: Some(55.0),
+        })
+    );
+
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(fetch_indoor(&settings(Some(&path)), false).await, None);
+    assert_eq!(fetch_indoor(&settings(None), false).await, None);
+}
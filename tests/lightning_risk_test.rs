@@ -0,0 +1,48 @@
+mod helpers;
+
+use chrono::Utc;
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::dashboard::chart::HourlyForecastGraph;
+use pi_inky_weather_epd::domain::models::HourlyForecast;
+
+fn hourly_with_cape(cape: Option<f32>) -> HourlyForecast {
+    HourlyForecast {
+        cape,
+        ..forecast::hour(Utc::now())
+    }
+}
+
+#[test]
+fn test_lightning_risk_threshold_is_inclusive() {
+    assert!(hourly_with_cape(Some(1000.0)).has_lightning_risk(1000.0));
+    assert!(hourly_with_cape(Some(2500.0)).has_lightning_risk(1000.0));
+    assert!(!hourly_with_cape(Some(999.9)).has_lightning_risk(1000.0));
+}
+
+#[test]
+fn test_lightning_risk_without_cape_data() {
+    // Providers without CAPE (e.g. BOM) must never flag an hour
+    assert!(!hourly_with_cape(None).has_lightning_risk(0.0));
+}
+
+#[test]
+fn test_lightning_icons_only_drawn_for_flagged_hours() {
+    let mut graph = HourlyForecastGraph::default();
+    graph.lightning_risk[0] = true;
+    graph.lightning_risk[23] = true;
+
+    let svg = graph.draw_lightning_risk_icons("lightning-bolt.svg");
+
+    assert_eq!(svg.matches("<image").count(), 2);
+    // Hour 0 is centered on x=0, hour 23 on the right edge of the 600px graph
+    assert!(svg.contains(r#"x="-12.00""#));
+    assert!(svg.contains(r#"x="588.00""#));
+}
+
+#[test]
+fn test_no_lightning_icons_without_risk() {
+    let graph = HourlyForecastGraph::default();
+    assert!(graph
+        .draw_lightning_risk_icons("lightning-bolt.svg")
+        .is_empty());
+}
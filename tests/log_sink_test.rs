@@ -0,0 +1,23 @@
+use pi_inky_weather_epd::{init_config, reset_log_sink, set_log_sink, LogLevel};
+use std::sync::{Arc, Mutex};
+
+/// The configuration printed when it's loaded goes to the installed sink, without colours
+#[test]
+fn test_log_messages_go_to_the_installed_sink() {
+    let messages: Arc<Mutex<Vec<(LogLevel, String)>>> = Arc::default();
+    let received = Arc::clone(&messages);
+    set_log_sink(move |level: LogLevel, message: &str| {
+        received.lock().unwrap().push((level, message.to_string()));
+    });
+
+    init_config().expect("the test configuration loads");
+    reset_log_sink();
+
+    let messages = messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|(level, message)| *level == LogLevel::Info && message == "File Paths"));
+    assert!(messages
+        .iter()
+        .all(|(_, message)| !message.contains('\x1b')));
+}
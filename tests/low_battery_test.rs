@@ -0,0 +1,44 @@
+#![cfg(feature = "web")]
+
+use pi_inky_weather_epd::web_server::{low_battery_banner, DeviceConfigQuery, RenderQuery};
+use pi_inky_weather_epd::CONFIG;
+
+fn battery(level: u8) -> RenderQuery {
+    RenderQuery {
+        battery: Some(level),
+        ..RenderQuery::default()
+    }
+}
+
+#[test]
+fn test_battery_below_the_threshold_is_low() {
+    let threshold = CONFIG.web_server.low_battery_threshold;
+
+    assert_eq!(battery(threshold - 1).low_battery(), Some(threshold - 1));
+    assert_eq!(battery(threshold).low_battery(), None);
+    assert_eq!(RenderQuery::default().low_battery(), None);
+    assert_eq!(
+        DeviceConfigQuery {
+            battery: Some(threshold - 1)
+        }
+        .low_battery(),
+        Some(threshold - 1)
+    );
+}
+
+#[test]
+fn test_battery_level_must_be_a_percentage() {
+    assert!(battery(100).into_options().is_ok());
+    assert!(battery(101).into_options().is_err());
+}
+
+#[test]
+fn test_banner_spans_the_template() {
+    let banner = low_battery_banner(15, 600.0, 448.0);
+
+    assert_eq!((banner.x, banner.y, banner.width), (0.0, 0.0, 600.0));
+    assert_eq!(banner.height, 47.0);
+
+    let banner = low_battery_banner(15, 800.0, 480.0);
+    assert_eq!((banner.width, banner.height), (800.0, 50.0));
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pi_inky_weather_epd::apis::open_meteo::models::{MarineHourly, OpenMeteoMarineResponse};
+use pi_inky_weather_epd::configs::settings::WaveHeightUnit;
+use pi_inky_weather_epd::dashboard::marine::{
+    compass_point, marine_svg, MarineConditions, SeaWindWarning,
+};
+use pi_inky_weather_epd::CONFIG;
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 10, 12, 30, 0).unwrap()
+}
+
+/// 48 hours from midnight, the swell grows by 0.1 m an hour
+fn marine_data() -> OpenMeteoMarineResponse {
+    let start = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+    let time: Vec<_> = (0..48).map(|hour| start + Duration::hours(hour)).collect();
+    OpenMeteoMarineResponse {
+        hourly: MarineHourly {
+            wave_height: (0..48).map(|hour| Some(1.0 + hour as f32 * 0.1)).collect(),
+            swell_wave_height: (0..48).map(|hour| Some(0.5 + hour as f32 * 0.1)).collect(),
+            swell_wave_period: vec![Some(12.0); 48],
+            swell_wave_direction: vec![Some(225.0); 48],
+            time,
+        },
+    }
+}
+
+fn swell_and_gale() -> MarineConditions {
+    MarineConditions {
+        swell_height: Some(1.8),
+        swell_period: Some(12.0),
+        swell_direction: Some(225.0),
+        wave_height: Some(2.1),
+        wind_warning: Some(SeaWindWarning::Gale),
+    }
+}
+
+#[test]
+fn test_conditions_of_the_current_hour() {
+    let conditions = MarineConditions::at(&marine_data(), &[], now());
+
+    assert_eq!(conditions.swell_height, Some(0.5 + 12.0 * 0.1));
+    assert_eq!(conditions.swell_period, Some(12.0));
+    assert_eq!(conditions.swell_direction, Some(225.0));
+    assert_eq!(conditions.wave_height, Some(1.0 + 12.0 * 0.1));
+    assert_eq!(conditions.wind_warning, None);
+}
+
+#[test]
+fn test_most_severe_marine_wind_warning_is_shown() {
+    let warnings = [
+        "Strong Wind Warning for Port Phillip".to_string(),
+        "Gale Warning for Central Coast".to_string(),
+    ];
+
+    let conditions = MarineConditions::at(&marine_data(), &warnings, now());
+
+    assert_eq!(conditions.wind_warning, Some(SeaWindWarning::Gale));
+}
+
+#[test]
+fn test_sea_wind_warning_levels_from_titles() {
+    assert_eq!(
+        SeaWindWarning::from_title("Strong Wind Warning for Port Phillip"),
+        Some(SeaWindWarning::StrongWind)
+    );
+    assert_eq!(
+        SeaWindWarning::from_title("Gale Warning for Western Port"),
+        Some(SeaWindWarning::Gale)
+    );
+    assert_eq!(
+        SeaWindWarning::from_title("Storm Force Wind Warning for Bass Strait"),
+        Some(SeaWindWarning::Storm)
+    );
+    assert_eq!(
+        SeaWindWarning::from_title("Hurricane Force Wind Warning for Gippsland Coast"),
+        Some(SeaWindWarning::Hurricane)
+    );
+    assert_eq!(SeaWindWarning::from_title("Frost Warning"), None);
+}
+
+#[test]
+fn test_compass_points() {
+    assert_eq!(compass_point(0.0), "N");
+    assert_eq!(compass_point(225.0), "SW");
+    assert_eq!(compass_point(200.0), "SSW");
+    assert_eq!(compass_point(355.0), "N");
+    assert_eq!(compass_point(-90.0), "W");
+}
+
+#[test]
+fn test_svg_shows_swell_waves_and_warning() {
+    let svg = marine_svg(&swell_and_gale(), WaveHeightUnit::Metres, &CONFIG.colours);
+
+    assert!(svg.contains(">Swell 1.8m 12s SW<"), "{svg}");
+    assert!(svg.contains(">Waves 2.1m<"));
+    assert!(svg.contains(&format!(
+        r#"fill="{}">Gale warning<"#,
+        CONFIG.colours.actual_temp_colour
+    )));
+
+    let calm = marine_svg(
+        &MarineConditions::default(),
+        WaveHeightUnit::Metres,
+        &CONFIG.colours,
+    );
+    assert!(calm.contains(">Swell n/a<"));
+    assert!(!calm.contains("warning"));
+}
+
+#[test]
+fn test_svg_fills_the_template_region() {
+    let svg = marine_svg(&swell_and_gale(), WaveHeightUnit::Metres, &CONFIG.colours);
+
+    assert!(svg.starts_with(r#"<svg viewBox="0 0 170 75" width="100%" height="100%""#));
+}
+
+#[test]
+fn test_heights_in_feet() {
+    let svg = marine_svg(&swell_and_gale(), WaveHeightUnit::Feet, &CONFIG.colours);
+
+    assert!(svg.contains(">Swell 5.9ft 12s SW<"), "{svg}");
+    assert!(svg.contains(">Waves 6.9ft<"));
+}
@@ -0,0 +1,84 @@
+mod helpers;
+
+use chrono::{Duration, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::apis::open_meteo::models::{
+    AirQualityHourly, OpenMeteoAirQualityResponse,
+};
+use pi_inky_weather_epd::configs::settings::{MaxTableMetric, MaxTableRows};
+use pi_inky_weather_epd::domain::models::HourlyForecast;
+
+#[test]
+fn test_max_table_rows_accepts_one_to_three_metrics() {
+    assert!(MaxTableRows::try_new(vec![MaxTableMetric::Pressure]).is_ok());
+    assert!(MaxTableRows::try_new(vec![
+        MaxTableMetric::Aqi,
+        MaxTableMetric::RainRate,
+        MaxTableMetric::Uv,
+    ])
+    .is_ok());
+}
+
+#[test]
+fn test_max_table_rows_rejects_empty_or_too_many() {
+    assert!(MaxTableRows::try_new(vec![]).is_err());
+    assert!(MaxTableRows::try_new(vec![
+        MaxTableMetric::Uv,
+        MaxTableMetric::Wind,
+        MaxTableMetric::Humidity,
+        MaxTableMetric::Pressure,
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_air_quality_is_merged_by_time() {
+    let start = Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap();
+    let mut hourly: Vec<HourlyForecast> = (0..3)
+        .map(|i| HourlyForecast {
+            pressure: Some(1013.0),
+            ..forecast::hour(start + Duration::hours(i))
+        })
+        .collect();
+
+    let air_quality = OpenMeteoAirQualityResponse {
+        hourly: AirQualityHourly {
+            time: vec![start + Duration::hours(1), start + Duration::hours(2)],
+            us_aqi: vec![Some(42.4), None],
+            pm2_5: vec![Some(61.0), None],
+        },
+    };
+    air_quality.merge_into(&mut hourly);
+
+    assert_eq!(hourly[0].aqi, None);
+    assert_eq!(hourly[1].aqi, Some(42));
+    assert_eq!(hourly[2].aqi, None);
+    assert_eq!(hourly[1].pm2_5, Some(61.0));
+}
+
+#[test]
+fn test_tomorrow_max_markers_deserialize_per_metric() {
+    use pi_inky_weather_epd::configs::settings::TomorrowMaxMarker;
+    use std::collections::HashMap;
+
+    let markers: HashMap<MaxTableMetric, TomorrowMaxMarker> = config::Config::builder()
+        .add_source(config::File::from_str(
+            r#"
+            [markers]
+            uv = "suffix"
+            rain_rate = "superscript"
+            "#,
+            config::FileFormat::Toml,
+        ))
+        .build()
+        .unwrap()
+        .get("markers")
+        .unwrap();
+
+    assert_eq!(markers[&MaxTableMetric::Uv], TomorrowMaxMarker::Suffix);
+    assert_eq!(
+        markers[&MaxTableMetric::RainRate],
+        TomorrowMaxMarker::Superscript
+    );
+    assert!(!markers.contains_key(&MaxTableMetric::Wind));
+}
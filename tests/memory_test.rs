@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use pi_inky_weather_epd::memory::{pooled_pixmap, return_pixmap, MemoryUsage};
+use pi_inky_weather_epd::metrics_export::{prometheus_text, RenderMetrics};
+use resvg::tiny_skia;
+
+const STATUS: &str = "Name:\tpi-inky-weather\nVmPeak:\t  412000 kB\nVmHWM:\t   98304 kB\nVmRSS:\t   65536 kB\nThreads:\t5\n";
+
+#[test]
+fn test_memory_usage_is_read_from_proc_status() {
+    let usage = MemoryUsage::from_proc_status(STATUS).unwrap();
+
+    assert_eq!(usage.rss_bytes, 65536 * 1024);
+    assert_eq!(usage.peak_rss_bytes, 98304 * 1024);
+    assert_eq!(usage.rss_mb(), 64);
+}
+
+#[test]
+fn test_memory_usage_needs_both_lines() {
+    assert_eq!(MemoryUsage::from_proc_status("VmRSS:\t 1024 kB\n"), None);
+    assert_eq!(MemoryUsage::from_proc_status(""), None);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_current_memory_usage_is_available_on_linux() {
+    let usage = MemoryUsage::current().unwrap();
+
+    assert!(usage.rss_bytes > 0);
+    assert!(usage.peak_rss_bytes >= usage.rss_bytes);
+}
+
+#[test]
+fn test_resident_memory_is_exported() {
+    let metrics = RenderMetrics {
+        current_temperature: None,
+        max_uv_index: None,
+        rain_total: None,
+        render_duration: Duration::from_secs(1),
+        rss_bytes: None,
+    }
+    .with_memory_usage(MemoryUsage::from_proc_status(STATUS));
+
+    assert!(prometheus_text(&metrics).contains("weather_epd_rss_bytes 67108864\n"));
+}
+
+#[test]
+fn test_pixmaps_are_reused_cleared() {
+    let mut pixmap = pooled_pixmap(4, 2).unwrap();
+    pixmap.fill(tiny_skia::Color::BLACK);
+    let buffer = pixmap.data().as_ptr();
+    return_pixmap(pixmap);
+
+    let reused = pooled_pixmap(4, 2).unwrap();
+    assert_eq!(reused.data().as_ptr(), buffer);
+    assert!(reused.data().iter().all(|&byte| byte == 0));
+
+    // A different size gets its own pixmap
+    assert_eq!(pooled_pixmap(2, 2).unwrap().width(), 2);
+    assert!(pooled_pixmap(0, 2).is_none());
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use pi_inky_weather_epd::configs::settings::{MetricsBackend, MetricsExport};
+use pi_inky_weather_epd::dashboard::context::Context;
+use pi_inky_weather_epd::metrics_export::{
+    influx_line, prometheus_text, push_metrics, pushgateway_url, RenderMetrics,
+};
+use url::Url;
+use wiremock::matchers::{body_string, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn metrics() -> RenderMetrics {
+    RenderMetrics {
+        current_temperature: Some(23.0),
+        max_uv_index: Some(9.0),
+        rain_total: None,
+        render_duration: Duration::from_millis(1500),
+        rss_bytes: None,
+    }
+}
+
+fn settings(backend: MetricsBackend, url: String) -> MetricsExport {
+    MetricsExport {
+        url: Some(Url::parse(&url).unwrap()),
+        backend,
+        job: "weather".to_string(),
+        influxdb_token: Some("secret".to_string()),
+    }
+}
+
+#[test]
+fn test_metrics_are_read_from_the_rendered_values() {
+    let context = Context {
+        current_hour_actual_temp: "23".to_string(),
+        max_uv_index: "9".to_string(),
+        total_rain_today: "NA".to_string(),
+        ..Context::default()
+    };
+
+    assert_eq!(
+        RenderMetrics::from_context(&context, Duration::from_millis(1500)),
+        metrics()
+    );
+}
+
+#[test]
+fn test_prometheus_text_has_a_gauge_per_value() {
+    assert_eq!(
+        prometheus_text(&metrics()),
+        "# TYPE weather_epd_current_temperature gauge\n\
+         weather_epd_current_temperature 23\n\
+         # TYPE weather_epd_max_uv_index gauge\n\
+         weather_epd_max_uv_index 9\n\
+         # TYPE weather_epd_render_duration_seconds gauge\n\
+         weather_epd_render_duration_seconds 1.5\n"
+    );
+}
+
+#[test]
+fn test_influx_line_escapes_the_tags() {
+    assert_eq!(
+        influx_line(&metrics(), "weather", "living room"),
+        "weather,instance=living\\ room current_temperature=23,max_uv_index=9,render_duration_seconds=1.5\n"
+    );
+}
+
+#[test]
+fn test_pushgateway_url_names_the_job_and_instance() {
+    let base = Url::parse("http://pushgateway.local:9091/").unwrap();
+
+    assert_eq!(
+        pushgateway_url(&base, "weather", "kitchen")
+            .unwrap()
+            .as_str(),
+        "http://pushgateway.local:9091/metrics/job/weather/instance/kitchen"
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_are_put_to_the_pushgateway() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/metrics/job/weather/instance/default"))
+        .and(body_string(prometheus_text(&metrics())))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    push_metrics(
+        &settings(MetricsBackend::Pushgateway, server.uri()),
+        &metrics(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_metrics_are_posted_to_influxdb_with_the_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v2/write"))
+        .and(header("authorization", "Token secret"))
+        .and(body_string(influx_line(&metrics(), "weather", "default")))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    push_metrics(
+        &settings(
+            MetricsBackend::Influxdb,
+            format!("{}/api/v2/write?org=home&bucket=weather", server.uri()),
+        ),
+        &metrics(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_failed_push_does_not_fail_the_run() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    // Only logged, nothing to return
+    push_metrics(
+        &settings(MetricsBackend::Pushgateway, server.uri()),
+        &metrics(),
+    )
+    .await;
+}
@@ -0,0 +1,86 @@
+use chrono::{TimeZone, Utc};
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::configs::settings::Locale;
+use pi_inky_weather_epd::dashboard::chart::HourlyForecastGraph;
+use pi_inky_weather_epd::dashboard::number_format::NumberFormat;
+
+#[test]
+fn test_english_keeps_the_decimal_point_and_the_unit_attached() {
+    let format = NumberFormat::for_locale("en-AU");
+    assert_eq!(format.fixed(23.44, 1), "23.4");
+    assert_eq!(format.with_unit(&format.fixed(23.44, 1), "°C"), "23.4°C");
+    assert_eq!(format, NumberFormat::default());
+}
+
+#[test]
+fn test_decimal_comma_languages_set_the_unit_apart() {
+    for locale in ["de", "de-AT", "fr_FR", "SV"] {
+        let format = NumberFormat::for_locale(locale);
+        assert_eq!(
+            format.with_unit(&format.fixed(23.44, 1), "°C"),
+            "23,4 °C",
+            "{locale}"
+        );
+    }
+    let format = NumberFormat::for_locale("de");
+    assert_eq!(format.number(2.5_f32), "2,5");
+    assert_eq!(format.number(3_f32), "3");
+    assert_eq!(format.with_unit("18 / 35", "km/h"), "18 / 35 km/h");
+    assert_eq!(format.with_unit("40", ""), "40");
+}
+
+#[test]
+fn test_unknown_languages_format_like_english() {
+    assert_eq!(NumberFormat::for_locale("ja-JP"), NumberFormat::default());
+    assert_eq!(NumberFormat::for_locale(""), NumberFormat::default());
+}
+
+#[test]
+fn test_formatted_numbers_read_back() {
+    let format = NumberFormat::for_locale("fr");
+    assert_eq!(format.parse("23,4"), Some(23.4));
+    assert_eq!(format.parse("NA"), None);
+    assert_eq!(NumberFormat::default().parse(" 1.5 "), Some(1.5));
+}
+
+#[test]
+fn test_locale_setting_must_be_a_language_with_an_optional_region() {
+    for locale in ["en", "en-AU", "de_DE", "fil"] {
+        assert!(Locale::try_new(locale.to_string()).is_ok(), "{locale}");
+    }
+    for locale in ["", "e", "en-", "en AU", "23"] {
+        assert!(Locale::try_new(locale.to_string()).is_err(), "{locale}");
+    }
+}
+
+#[test]
+fn test_axis_labels_use_the_number_format() {
+    let temps: Vec<f32> = (0..24).map(|hour| 10.0 + hour as f32 * 0.5).collect();
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let mut graph = HourlyForecastGraph::builder()
+        .number_format(NumberFormat::for_locale("de"))
+        .actual_temperature(&temps)
+        .feels_like(&temps)
+        .rain_chance(&[0.0; 24])
+        .build();
+    graph.draw_graph().unwrap();
+    let axis = graph.create_axis_with_labels(14.0, &clock);
+
+    let texts = |labels: &str| -> Vec<String> {
+        labels
+            .split("</text>")
+            .filter_map(|label| label.rsplit_once('>'))
+            .map(|(_, text)| text.to_string())
+            .collect()
+    };
+    let temperatures = texts(&axis.y_left_labels);
+    assert!(temperatures.len() > 2);
+    assert!(
+        temperatures
+            .iter()
+            .all(|label| label.ends_with(" °") && !label.contains('.')),
+        "{temperatures:?}"
+    );
+    assert!(temperatures.iter().any(|label| label.contains(',')));
+    assert!(texts(&axis.y_right_labels).contains(&"100 %".to_string()));
+}
@@ -0,0 +1,119 @@
+mod helpers;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::domain::models::{
+    DailyForecast, HourlyForecast, Obscuration, Precipitation, Temperature,
+};
+use pi_inky_weather_epd::weather::icons::Icon;
+
+fn hour(cloud_cover: u16, is_night: bool, obscuration: Option<Obscuration>) -> HourlyForecast {
+    HourlyForecast {
+        is_night,
+        cloud_cover: Some(cloud_cover),
+        obscuration,
+        ..forecast::hour(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap())
+    }
+}
+
+#[test]
+fn test_obscuration_from_bom_descriptor() {
+    assert_eq!(
+        Obscuration::from_bom_descriptor("hazy"),
+        Some(Obscuration::Haze)
+    );
+    assert_eq!(
+        Obscuration::from_bom_descriptor("dusty"),
+        Some(Obscuration::Dust)
+    );
+    assert_eq!(
+        Obscuration::from_bom_descriptor("smoke"),
+        Some(Obscuration::Smoke)
+    );
+    assert_eq!(Obscuration::from_bom_descriptor("sunny"), None);
+}
+
+#[test]
+fn test_hourly_obscuration_icons() {
+    assert_eq!(
+        hour(0, false, Some(Obscuration::Smoke)).get_icon_name(),
+        "smoke.svg"
+    );
+    assert_eq!(
+        hour(40, true, Some(Obscuration::Smoke)).get_icon_name(),
+        "partly-cloudy-night-smoke.svg"
+    );
+    assert_eq!(
+        hour(0, true, Some(Obscuration::Haze)).get_icon_name(),
+        "haze-night.svg"
+    );
+    assert_eq!(
+        hour(90, false, Some(Obscuration::Haze)).get_icon_name(),
+        "extreme-day-haze.svg"
+    );
+    assert_eq!(
+        hour(60, false, Some(Obscuration::Dust)).get_icon_name(),
+        "dust-day.svg"
+    );
+}
+
+#[test]
+fn test_rain_takes_precedence_over_obscuration() {
+    let mut rainy = hour(90, false, Some(Obscuration::Smoke));
+    rainy.precipitation = Precipitation::new(Some(90), Some(2), Some(4));
+
+    assert!(!rainy.get_icon_name().contains("smoke"));
+}
+
+#[test]
+fn test_daily_obscuration_icon() {
+    let day = DailyForecast {
+        temp_max: Some(Temperature::celsius(38.0)),
+        temp_min: Some(Temperature::celsius(22.0)),
+        precipitation: Some(Precipitation::new(Some(5), Some(0), Some(0))),
+        cloud_cover: None,
+        obscuration: Some(Obscuration::Dust),
+        ..forecast::day(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap())
+    };
+
+    assert_eq!(day.get_icon_name(), "dust-day.svg");
+}
+
+#[test]
+fn test_pm2_5_override_only_above_threshold() {
+    let mut clear = hour(0, false, None);
+    clear.pm2_5 = Some(80.0);
+
+    assert_eq!(clear.effective_obscuration(None), None);
+    assert_eq!(
+        clear.effective_obscuration(Some(55.5)),
+        Some(Obscuration::Smoke)
+    );
+    assert_eq!(clear.effective_obscuration(Some(100.0)), None);
+
+    // A provider reported condition is kept
+    clear.obscuration = Some(Obscuration::Dust);
+    assert_eq!(
+        clear.effective_obscuration(Some(55.5)),
+        Some(Obscuration::Dust)
+    );
+}
+
+#[test]
+fn test_hourly_condition_follows_the_icon() {
+    use pi_inky_weather_epd::domain::conditions::Condition;
+
+    assert_eq!(hour(10, false, None).condition(), Condition::Clear);
+    assert_eq!(hour(40, false, None).condition(), Condition::PartlyCloudy);
+    assert_eq!(hour(90, true, None).condition(), Condition::Cloudy);
+    assert_eq!(
+        hour(10, false, Some(Obscuration::Haze)).condition(),
+        Condition::Haze
+    );
+
+    let mut rainy = hour(90, false, Some(Obscuration::Smoke));
+    rainy.precipitation = Precipitation::new(Some(90), Some(2), Some(4));
+    assert_eq!(rainy.condition(), Condition::Rain);
+    assert_eq!(Condition::PartlyCloudy.description(), "Partly cloudy");
+    assert_eq!(Condition::PartlyCloudy.to_string(), "partly_cloudy");
+}
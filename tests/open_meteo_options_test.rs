@@ -0,0 +1,60 @@
+use pi_inky_weather_epd::configs::settings::{CellSelection, OpenMeteoModel, PastDays};
+use pi_inky_weather_epd::constants::{open_meteo_daily_endpoint, open_meteo_hourly_endpoint};
+use serde_json::json;
+
+fn query(url: &url::Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.to_string())
+}
+
+#[test]
+fn test_forecast_queries_use_the_configured_model_and_cell() {
+    for url in [open_meteo_hourly_endpoint(), open_meteo_daily_endpoint()] {
+        assert_eq!(query(&url, "models").as_deref(), Some("best_match"));
+        assert_eq!(query(&url, "cell_selection").as_deref(), Some("land"));
+    }
+}
+
+#[test]
+fn test_daily_forecast_always_has_yesterday() {
+    assert_eq!(
+        query(&open_meteo_hourly_endpoint(), "past_days").as_deref(),
+        Some("0")
+    );
+    assert_eq!(
+        query(&open_meteo_daily_endpoint(), "past_days").as_deref(),
+        Some("1")
+    );
+}
+
+#[test]
+fn test_model_must_be_a_single_name() {
+    assert_eq!(
+        OpenMeteoModel::try_new(" ICON_Seamless ".to_string())
+            .unwrap()
+            .to_string(),
+        "icon_seamless"
+    );
+    assert!(OpenMeteoModel::try_new("icon_seamless,gfs_seamless".to_string()).is_err());
+    assert!(OpenMeteoModel::try_new(String::new()).is_err());
+}
+
+#[test]
+fn test_past_days_are_limited_to_what_the_api_serves() {
+    assert!(PastDays::try_new(92).is_ok());
+    assert!(PastDays::try_new(93).is_err());
+}
+
+#[test]
+fn test_cell_selection_options() {
+    assert_eq!(
+        serde_json::from_value::<Vec<CellSelection>>(json!(["land", "sea", "nearest"])).unwrap(),
+        vec![
+            CellSelection::Land,
+            CellSelection::Sea,
+            CellSelection::Nearest
+        ]
+    );
+    assert!(serde_json::from_value::<CellSelection>(json!("ocean")).is_err());
+}
@@ -224,3 +224,19 @@ fn test_open_meteo_coordinates() {
         "Longitude should be between -180 and 180"
     );
 }
+
+/// Test Open-Meteo snake_case fields without an explicit rename are read
+#[test]
+fn test_open_meteo_pressure_is_read() {
+    let mut json: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string("tests/fixtures/open_meteo_hourly_forecast.json").unwrap(),
+    )
+    .unwrap();
+    let hours = json["hourly"]["time"].as_array().unwrap().len();
+    json["hourly"]["pressure_msl"] = vec![1013.5; hours].into();
+
+    let response: OpenMeteoHourlyResponse = serde_json::from_value(json).unwrap();
+
+    assert_eq!(response.hourly.pressure_msl.len(), hours);
+    assert_eq!(response.hourly.pressure_msl[0], Some(1013.5));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "web")]
+
+use pi_inky_weather_epd::web_server::ApiDoc;
+use utoipa::OpenApi;
+
+#[test]
+fn test_openapi_describes_all_dashboard_endpoints() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+    for path in [
+        "/dashboard",
+        "/dashboard.svg",
+        "/dashboard.png",
+        "/dashboard.bmp",
+        "/dashboard.raw",
+        "/dashboard.diff",
+        "/display.png",
+        "/version",
+        "/api/memory.json",
+        "/api/forecast.json",
+        "/api/diagnostics.json",
+        "/api/devices.json",
+    ] {
+        assert!(spec["paths"][path]["get"].is_object(), "missing {path}");
+    }
+    assert!(spec["paths"]["/api/buttons/{button}"]["post"].is_object());
+    assert!(spec["paths"]["/api/devices/{device}/health"]["post"].is_object());
+}
+
+#[test]
+fn test_openapi_includes_forecast_schema() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let schemas = &spec["components"]["schemas"];
+
+    for schema in [
+        "ForecastJson",
+        "HourlyForecast",
+        "DailyForecast",
+        "Diagnostic",
+    ] {
+        assert!(schemas[schema].is_object(), "missing {schema}");
+    }
+}
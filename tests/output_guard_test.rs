@@ -0,0 +1,101 @@
+use pi_inky_weather_epd::display_options::DisplayDriver;
+use pi_inky_weather_epd::utils::{
+    available_disk_space, convert_png_to_raw_7color, raw_7color_size, verify_written_size,
+};
+use pi_inky_weather_epd::weather_dashboard::{
+    check_free_disk_space, check_raw_size, staged_path, write_unavailable_dashboard,
+};
+use pi_inky_weather_epd::{FixedClock, WeatherEpdError};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_raw_size_packs_two_pixels_per_byte() {
+    assert_eq!(raw_7color_size(800, 480), 192_000);
+    // Odd rows end in a padding nibble
+    assert_eq!(raw_7color_size(5, 2), 6);
+}
+
+#[test]
+fn test_raw_output_must_match_the_display() {
+    let dir = tempfile::tempdir().unwrap();
+    let png = dir.path().join("dashboard.png");
+    let raw = dir.path().join("dashboard.raw");
+    image::RgbImage::new(10, 4).save(&png).unwrap();
+
+    convert_png_to_raw_7color(&png, &raw).unwrap();
+
+    assert!(check_raw_size(&raw, (10, 4), DisplayDriver::Acep).is_ok());
+    let error = check_raw_size(&raw, (800, 480), DisplayDriver::Acep)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("is 20 bytes, a 800x480 display expects 192000 bytes"));
+}
+
+#[test]
+fn test_truncated_file_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let raw = dir.path().join("dashboard.raw");
+    fs::write(&raw, [0u8; 100]).unwrap();
+
+    assert!(verify_written_size(&raw, 100).is_ok());
+    let error = verify_written_size(&raw, 192_000).unwrap_err().to_string();
+    assert!(error.contains("is 100 bytes instead of 192000, the disk may be full"));
+}
+
+#[test]
+fn test_free_disk_space_below_the_minimum_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    if available_disk_space(dir.path()).is_none() {
+        return;
+    }
+
+    assert!(check_free_disk_space(dir.path(), 1).is_ok());
+    assert!(check_free_disk_space(dir.path(), 0).is_ok());
+    let error = check_free_disk_space(dir.path(), u64::MAX)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("MB free on the disk holding"));
+}
+
+#[test]
+fn test_staged_outputs_keep_their_extension() {
+    assert_eq!(
+        staged_path(Path::new("out/dashboard.png")),
+        Path::new("out/dashboard.tmp.png")
+    );
+    assert_eq!(
+        staged_path(Path::new("dashboard")),
+        Path::new("dashboard.tmp")
+    );
+}
+
+#[test]
+fn test_outputs_are_renamed_into_place_once_written() {
+    let dir = tempfile::tempdir().unwrap();
+    let svg = dir.path().join("dashboard.svg");
+    fs::write(&svg, "previous").unwrap();
+    let clock = FixedClock::from_rfc3339("2025-01-10T03:00:00Z").unwrap();
+    let error = WeatherEpdError::output("disk on fire");
+
+    write_unavailable_dashboard(&clock, &error, &svg).unwrap();
+
+    assert!(fs::read_to_string(&svg).unwrap().contains("disk on fire"));
+    assert!(!staged_path(&svg).exists());
+}
+
+#[test]
+fn test_failed_swap_removes_the_staged_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    // A directory in the way of the SVG output makes the rename fail
+    let svg = dir.path().join("dashboard.svg");
+    fs::create_dir(&svg).unwrap();
+    fs::write(svg.join("keep"), "").unwrap();
+    let clock = FixedClock::from_rfc3339("2025-01-10T03:00:00Z").unwrap();
+    let error = WeatherEpdError::output("disk on fire");
+
+    assert!(write_unavailable_dashboard(&clock, &error, &svg).is_err());
+
+    assert!(svg.join("keep").exists());
+    assert!(!staged_path(&svg).exists());
+}
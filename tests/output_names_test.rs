@@ -0,0 +1,80 @@
+use chrono::{Local, TimeZone};
+use pi_inky_weather_epd::output_names::{check_output_name, expand, remove_expired};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[test]
+fn test_placeholders_are_filled_in() {
+    let time = Local.with_ymd_and_hms(2025, 6, 9, 7, 5, 0).unwrap();
+
+    assert_eq!(
+        expand(Path::new("out/dashboard-{date}-{hour}.png"), time),
+        Path::new("out/dashboard-2025-06-09-07.png")
+    );
+    assert_eq!(
+        expand(Path::new("dashboard-{time}.raw"), time),
+        Path::new("dashboard-0705.raw")
+    );
+    assert_eq!(
+        expand(Path::new("dashboard-{hour}{minute}.svg"), time),
+        Path::new("dashboard-0705.svg")
+    );
+    assert_eq!(
+        expand(Path::new("dashboard.png"), time),
+        Path::new("dashboard.png")
+    );
+}
+
+#[test]
+fn test_output_names_are_checked() {
+    assert!(check_output_name(Path::new("dashboard.png")).is_ok());
+    assert!(check_output_name(Path::new("out/dashboard-{date}-{hour}.png")).is_ok());
+    assert!(check_output_name(Path::new("out/{date}/dashboard.png"))
+        .unwrap_err()
+        .contains("only allowed in the file name"));
+    assert!(check_output_name(Path::new("dashboard-{day}.png"))
+        .unwrap_err()
+        .contains("unknown placeholder"));
+}
+
+fn write_aged(path: &Path, age: Duration) {
+    let file = std::fs::File::create(path).unwrap();
+    file.set_modified(SystemTime::now() - age).unwrap();
+}
+
+#[test]
+fn test_only_expired_matching_outputs_are_removed() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = dir.path().join("dashboard-2025-06-01-07.png");
+    let recent = dir.path().join("dashboard-2025-06-09-07.png");
+    let other = dir.path().join("dashboard-notes.png");
+    let latest = dir.path().join("dashboard.png");
+    write_aged(&old, 8 * DAY);
+    write_aged(&recent, DAY);
+    write_aged(&other, 8 * DAY);
+    write_aged(&latest, 8 * DAY);
+
+    let removed = remove_expired(
+        &dir.path().join("dashboard-{date}-{hour}.png"),
+        7 * DAY,
+        SystemTime::now(),
+    );
+
+    assert_eq!(removed, 1);
+    assert!(!old.exists());
+    assert!(recent.exists());
+    assert!(other.exists());
+    assert!(latest.exists());
+}
+
+#[test]
+fn test_names_without_placeholders_are_kept() {
+    let dir = tempfile::tempdir().unwrap();
+    let latest = dir.path().join("dashboard.png");
+    write_aged(&latest, 30 * DAY);
+
+    assert_eq!(remove_expired(&latest, 7 * DAY, SystemTime::now()), 0);
+    assert!(latest.exists());
+}
@@ -0,0 +1,99 @@
+use chrono::Timelike;
+use pi_inky_weather_epd::clock::{Clock, FixedClock};
+use pi_inky_weather_epd::dashboard::panel::{
+    clear_panels, composite_panels, register_panel, registered_panels, svg_size, Panel,
+};
+
+const DASHBOARD: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="800" height="480"><rect width="800" height="480" fill="white"/></svg>"#;
+
+fn clock() -> FixedClock {
+    FixedClock::from_rfc3339("2025-10-09T22:00:00Z").unwrap()
+}
+
+fn png() -> Vec<u8> {
+    let mut png = Vec::new();
+    image::RgbImage::from_pixel(4, 2, image::Rgb([255, 0, 0]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    png
+}
+
+#[test]
+fn test_panels_are_drawn_on_top_of_the_dashboard() {
+    let svg = composite_panels(
+        DASHBOARD.to_string(),
+        &[
+            Panel::svg(10.0, 20.0, 100.0, 40.0, r#"<text y="30">Hello</text>"#),
+            Panel::png(600.0, 0.0, 200.0, 100.0, png()),
+        ],
+    );
+
+    let rect = svg.find("<rect").unwrap();
+    let text = svg.find("Hello").unwrap();
+    let image = svg.find("data:image/png;base64,").unwrap();
+    assert!(rect < text && text < image);
+    assert!(svg.contains(r#"<svg x="10" y="20" width="100" height="40" overflow="hidden">"#));
+    assert!(svg.contains(r#"<image x="600" y="0" width="200" height="100""#));
+    assert!(svg.ends_with("</svg>"));
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    assert_eq!(tree.size().width(), 800.0);
+}
+
+#[test]
+fn test_svg_without_panels_is_unchanged() {
+    assert_eq!(composite_panels(DASHBOARD.to_string(), &[]), DASHBOARD);
+    assert_eq!(
+        composite_panels(
+            "not an svg".to_string(),
+            &[Panel::svg(0.0, 0.0, 1.0, 1.0, "")]
+        ),
+        "not an svg"
+    );
+}
+
+#[test]
+fn test_registered_panels_render_with_the_clock() {
+    clear_panels();
+    register_panel(Panel::svg(0.0, 0.0, 50.0, 50.0, "<circle r=\"5\"/>"));
+    register_panel(|clock: &dyn Clock| {
+        let hour = clock.now_local().hour();
+        Some(Panel::svg(
+            0.0,
+            50.0,
+            50.0,
+            20.0,
+            format!("<text>{hour}</text>"),
+        ))
+    });
+    register_panel(|_: &dyn Clock| None);
+
+    let panels = registered_panels(&clock());
+    clear_panels();
+
+    assert_eq!(panels.len(), 2);
+    assert_eq!(
+        panels[0],
+        Panel::svg(0.0, 0.0, 50.0, 50.0, "<circle r=\"5\"/>")
+    );
+    // 22:00 UTC is 09:00 in Melbourne
+    assert_eq!(
+        panels[1],
+        Panel::svg(0.0, 50.0, 50.0, 20.0, "<text>9</text>")
+    );
+    assert!(registered_panels(&clock()).is_empty());
+}
+
+#[test]
+fn test_svg_size_from_the_root_element() {
+    assert_eq!(svg_size(DASHBOARD), Some((800.0, 480.0)));
+    assert_eq!(
+        svg_size(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 600 448"/>"#),
+        Some((600.0, 448.0))
+    );
+    assert_eq!(
+        svg_size(r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#),
+        None
+    );
+    assert_eq!(svg_size("not an svg"), None);
+}
@@ -0,0 +1,46 @@
+use pi_inky_weather_epd::platform::{case_mismatch, href, user_config_files};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_case_mismatch_none_when_spelled_exactly() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("Icons")).unwrap();
+    fs::write(dir.path().join("Icons/rain.svg"), "<svg/>").unwrap();
+
+    assert_eq!(case_mismatch(&dir.path().join("Icons/rain.svg")), None);
+}
+
+#[test]
+fn test_case_mismatch_reports_spelling_on_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("Icons")).unwrap();
+    fs::write(dir.path().join("Icons/Rain.svg"), "<svg/>").unwrap();
+
+    assert_eq!(
+        case_mismatch(&dir.path().join("icons/rain.svg")),
+        Some(dir.path().join("Icons/Rain.svg"))
+    );
+}
+
+#[test]
+fn test_case_mismatch_none_when_missing() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert_eq!(case_mismatch(&dir.path().join("missing.svg")), None);
+}
+
+#[test]
+fn test_href_uses_forward_slashes() {
+    let path = Path::new("static").join("fill-svg-static").join("rain.svg");
+
+    assert_eq!(href(&path), "static/fill-svg-static/rain.svg");
+}
+
+#[test]
+fn test_user_config_files_start_with_dot_config() {
+    let files = user_config_files();
+
+    let first = files.first().expect("a home directory");
+    assert!(first.ends_with(Path::new(".config").join("pi-inky-weather-epd")));
+}
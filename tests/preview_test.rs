@@ -0,0 +1,30 @@
+#![cfg(feature = "web")]
+
+use pi_inky_weather_epd::preview::fingerprint;
+use std::fs;
+
+#[test]
+fn test_fingerprint_changes_when_a_watched_file_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("template.svg");
+    let icons = dir.path().join("icons");
+    fs::create_dir(&icons).unwrap();
+    fs::write(&template, "<svg/>").unwrap();
+    fs::write(icons.join("rain.svg"), "<svg/>").unwrap();
+    let paths = [template.clone(), icons.clone(), dir.path().join("missing")];
+
+    let initial = fingerprint(&paths);
+    assert_eq!(initial.len(), 2);
+    assert_eq!(fingerprint(&paths), initial);
+
+    fs::write(&template, "<svg width=\"800\"/>").unwrap();
+    let saved = fingerprint(&paths);
+    assert_ne!(saved, initial);
+
+    fs::write(icons.join("snow.svg"), "<svg/>").unwrap();
+    let added = fingerprint(&paths);
+    assert_eq!(added.len(), 3);
+
+    fs::remove_file(icons.join("rain.svg")).unwrap();
+    assert_ne!(fingerprint(&paths), added);
+}
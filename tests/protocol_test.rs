@@ -0,0 +1,94 @@
+#![cfg(all(feature = "web", unix))]
+
+use pi_inky_weather_epd::dashboard::forecast_json::FORECAST_SCHEMA_VERSION;
+use pi_inky_weather_epd::protocol::{
+    ProtocolVersion, VersionInfo, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION,
+};
+use pi_inky_weather_epd::web_server::{router, serve_unix};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+async fn get(name: &str, path: &str, headers: &str) -> String {
+    let socket = std::env::temp_dir().join(format!(
+        "pi-inky-weather-protocol-{name}-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket);
+
+    let listener = UnixListener::bind(&socket).unwrap();
+    tokio::spawn(serve_unix(listener, router()));
+
+    let mut stream = UnixStream::connect(&socket).await.unwrap();
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n{headers}Connection: close\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    std::fs::remove_file(&socket).unwrap();
+    response
+}
+
+#[test]
+fn test_firmware_without_the_header_speaks_the_first_version() {
+    assert_eq!(
+        ProtocolVersion::negotiate(None),
+        Ok(ProtocolVersion(MIN_PROTOCOL_VERSION))
+    );
+    assert!(!ProtocolVersion(1).supports_diff());
+    assert!(!ProtocolVersion(1).supports_greyscale());
+}
+
+#[test]
+fn test_newer_firmware_falls_back_to_the_server_version() {
+    assert_eq!(
+        ProtocolVersion::negotiate(Some(" 2 ")),
+        Ok(ProtocolVersion(2))
+    );
+    assert_eq!(
+        ProtocolVersion::negotiate(Some("99")),
+        Ok(ProtocolVersion(PROTOCOL_VERSION))
+    );
+    assert!(ProtocolVersion(PROTOCOL_VERSION).supports_diff());
+}
+
+#[test]
+fn test_invalid_versions_are_refused() {
+    for requested in ["0", "two", ""] {
+        assert!(
+            ProtocolVersion::negotiate(Some(requested)).is_err(),
+            "{requested}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_version_endpoint() {
+    let response = get("version", "/version", "").await;
+    let body = response.split("\r\n\r\n").nth(1).unwrap();
+    let info: serde_json::Value = serde_json::from_str(body).unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert_eq!(info["version"], VersionInfo::current().version);
+    assert_eq!(info["protocol_version"], PROTOCOL_VERSION);
+    assert_eq!(info["min_protocol_version"], MIN_PROTOCOL_VERSION);
+    assert_eq!(info["forecast_schema_version"], FORECAST_SCHEMA_VERSION);
+}
+
+#[tokio::test]
+async fn test_old_firmware_gets_no_diff() {
+    let response = get("diff", "/dashboard.diff", "").await;
+    assert!(response.starts_with("HTTP/1.1 426"), "{response}");
+}
+
+#[tokio::test]
+async fn test_old_firmware_gets_no_grey_levels() {
+    let response = get("grey", "/dashboard.raw?driver=it8951", "").await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+
+    let response = get("invalid", "/dashboard.raw", "X-Protocol-Version: 0\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+}
@@ -0,0 +1,83 @@
+use chrono::{Local, TimeZone};
+use pi_inky_weather_epd::configs::settings::{QuietHours, QuietMode, Schedule};
+use pi_inky_weather_epd::dashboard::panel::svg_size;
+use pi_inky_weather_epd::weather_dashboard::render_night_svg;
+use pi_inky_weather_epd::CONFIG;
+
+fn quiet_hours(json: &str) -> QuietHours {
+    serde_json::from_str(json).unwrap()
+}
+
+fn at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+    Local
+        .with_ymd_and_hms(2025, 10, 9, hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_quiet_hours_spanning_midnight() {
+    let quiet_hours = quiet_hours(r#"{"start": 22, "end": 7}"#);
+
+    assert_eq!(quiet_hours.mode, QuietMode::Night);
+    assert!(!quiet_hours.contains(at(21, 59)));
+    assert!(quiet_hours.contains(at(22, 0)));
+    assert!(quiet_hours.contains(at(3, 0)));
+    assert!(quiet_hours.contains(at(6, 59)));
+    assert!(!quiet_hours.contains(at(7, 0)));
+}
+
+#[test]
+fn test_quiet_hours_within_a_day() {
+    let quiet_hours = quiet_hours(r#"{"start": 1, "end": 6, "mode": "skip"}"#);
+
+    assert_eq!(quiet_hours.mode, QuietMode::Skip);
+    assert!(!quiet_hours.contains(at(0, 30)));
+    assert!(quiet_hours.contains(at(1, 0)));
+    assert!(!quiet_hours.contains(at(6, 0)));
+    assert!(!quiet_hours.contains(at(23, 0)));
+}
+
+#[test]
+fn test_seconds_until_the_quiet_hours_end() {
+    let quiet_hours = quiet_hours(r#"{"start": 22, "end": 7}"#);
+
+    assert_eq!(quiet_hours.seconds_until_end(at(6, 30)), 30 * 60);
+    assert_eq!(quiet_hours.seconds_until_end(at(22, 0)), 9 * 3600);
+    assert_eq!(
+        quiet_hours.seconds_until_end(at(23, 45)),
+        7 * 3600 + 15 * 60
+    );
+}
+
+#[test]
+fn test_invalid_hours_are_rejected() {
+    assert!(serde_json::from_str::<QuietHours>(r#"{"start": 24, "end": 7}"#).is_err());
+    assert!(
+        serde_json::from_str::<QuietHours>(r#"{"start": 22, "end": 7, "mode": "dim"}"#).is_err()
+    );
+}
+
+#[test]
+fn test_schedule_without_quiet_hours_never_applies() {
+    let schedule: Schedule = serde_json::from_str("{}").unwrap();
+
+    assert!(schedule.quiet_hours_at(at(3, 0)).is_none());
+}
+
+#[test]
+fn test_night_layout_is_static() {
+    let quiet_hours = quiet_hours(r#"{"start": 22, "end": 7}"#);
+    let svg = render_night_svg(&quiet_hours);
+
+    assert!(svg.contains("The forecast returns at 07:00"));
+    assert_eq!(svg, render_night_svg(&quiet_hours));
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_night_layout_has_the_size_of_the_template() {
+    let svg = render_night_svg(&quiet_hours(r#"{"start": 22, "end": 7}"#));
+    let template = std::fs::read_to_string(&CONFIG.misc.template_path).unwrap();
+
+    assert_eq!(svg_size(&svg), svg_size(&template));
+}
@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use pi_inky_weather_epd::configs::settings::Footer;
+use pi_inky_weather_epd::dashboard::quote_footer::{parse_quotes, quote_footer, quote_of_the_day};
+use pi_inky_weather_epd::CONFIG;
+use std::io::Write;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 10, day).unwrap()
+}
+
+fn footer(quotes: &str) -> (Footer, tempfile::NamedTempFile) {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(quotes.as_bytes()).unwrap();
+    let footer = Footer {
+        quotes_path: Some(file.path().to_path_buf()),
+        x: 0.0,
+        y: 455.0,
+        width: 800.0,
+        height: 25.0,
+        font_size: 16.0,
+    };
+    (footer, file)
+}
+
+#[test]
+fn test_blank_lines_and_comments_are_skipped() {
+    let quotes = parse_quotes("# Weather lore\n\nRed sky at night\n  \n  Rain before seven  \n");
+
+    assert_eq!(quotes, ["Red sky at night", "Rain before seven"]);
+}
+
+#[test]
+fn test_quote_is_the_same_all_day_and_changes_the_next() {
+    let quotes = ["first", "second", "third"];
+
+    let today = quote_of_the_day(&quotes, date(9)).unwrap();
+    let tomorrow = quote_of_the_day(&quotes, date(10)).unwrap();
+
+    assert_eq!(quote_of_the_day(&quotes, date(9)), Some(today));
+    assert_ne!(today, tomorrow);
+    assert_eq!(quote_of_the_day(&quotes, date(12)), Some(today));
+    assert_eq!(quote_of_the_day(&[], date(9)), None);
+}
+
+#[test]
+fn test_footer_is_escaped_and_fits_the_width() {
+    let (settings, _file) = footer("Fish & chips <3\n");
+    let panel = quote_footer(&settings, date(9), &CONFIG.colours).unwrap();
+    let svg = panel.to_svg();
+
+    assert!(svg.contains("Fish &amp; chips &lt;3"));
+    assert!(usvg::Tree::from_str(
+        &format!(r#"<svg xmlns="http://www.w3.org/2000/svg">{svg}</svg>"#),
+        &usvg::Options::default()
+    )
+    .is_ok());
+
+    let (settings, _file) = footer(&"word ".repeat(50));
+    let svg = quote_footer(&settings, date(9), &CONFIG.colours)
+        .unwrap()
+        .to_svg();
+    // 800 wide at 16px fits about 100 characters
+    assert!(svg.contains("word…"));
+    assert!(!svg.contains(&"word ".repeat(21)));
+}
+
+#[test]
+fn test_no_footer_without_quotes() {
+    let (mut settings, _file) = footer("# only a comment\n");
+    assert!(quote_footer(&settings, date(9), &CONFIG.colours).is_none());
+
+    settings.quotes_path = None;
+    assert!(quote_footer(&settings, date(9), &CONFIG.colours).is_none());
+}
@@ -0,0 +1,85 @@
+use pi_inky_weather_epd::dashboard::chart::HourlyForecastGraph;
+use pi_inky_weather_epd::domain::models::{AmountPercentiles, Precipitation};
+
+#[test]
+fn test_amount_band_prefers_percentiles() {
+    let precipitation =
+        Precipitation::new(Some(80), Some(1), Some(12)).with_percentiles(AmountPercentiles {
+            p25: Some(2),
+            p50: Some(4),
+            p75: Some(7),
+        });
+
+    assert_eq!(precipitation.amount_band(), Some((2.0, 7.0)));
+}
+
+#[test]
+fn test_amount_band_falls_back_to_min_max() {
+    let precipitation =
+        Precipitation::new(Some(60), Some(1), Some(4)).with_percentiles(AmountPercentiles {
+            p25: None,
+            p50: Some(1),
+            p75: Some(2),
+        });
+
+    assert_eq!(precipitation.amount_band(), Some((1.0, 2.0)));
+    assert_eq!(
+        Precipitation::new(Some(60), Some(1), Some(4)).amount_band(),
+        Some((1.0, 4.0))
+    );
+}
+
+#[test]
+fn test_amount_band_needs_a_range() {
+    // Open-Meteo only gives a single amount
+    assert_eq!(
+        Precipitation::new(Some(60), None, Some(3)).amount_band(),
+        None
+    );
+}
+
+#[test]
+fn test_bom_exceedance_amounts_map_to_percentiles() {
+    let rain: pi_inky_weather_epd::apis::bom::models::Rain = serde_json::from_str(
+        r#"{
+            "amount": { "min": 0, "max": 5, "units": "mm" },
+            "chance": 70,
+            "precipitation_amount_25_percent_chance": 4,
+            "precipitation_amount_50_percent_chance": 2,
+            "precipitation_amount_75_percent_chance": 1
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        rain.amount_percentiles(),
+        AmountPercentiles {
+            p25: Some(1),
+            p50: Some(2),
+            p75: Some(4),
+        }
+    );
+}
+
+#[test]
+fn test_draw_rain_band() {
+    let mut band = vec![None; 24];
+    band[1] = Some((1.0, 5.0));
+    band[2] = Some((2.0, 20.0));
+
+    let graph = HourlyForecastGraph::builder()
+        .size(230.0, 100.0)
+        .rain_band(&band, 10.0)
+        .build();
+    let path = graph.draw_rain_band();
+
+    assert!(path.starts_with("M 0.0000 0.0000 L 10.0000 50.0000 L 20.0000 100.0000"));
+    assert!(path.contains("L 20.0000 20.0000 L 10.0000 10.0000 L 0.0000 0.0000"));
+    assert!(path.ends_with(" Z"));
+}
+
+#[test]
+fn test_draw_rain_band_empty_without_ranges() {
+    let graph = HourlyForecastGraph::builder().build();
+    assert_eq!(graph.draw_rain_band(), "");
+}
@@ -0,0 +1,57 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::domain::models::{minutes_until_rain, HourlyForecast, Precipitation};
+use pi_inky_weather_epd::utils::format_rain_countdown;
+
+fn hour(time: DateTime<Utc>, chance: u16) -> HourlyForecast {
+    HourlyForecast {
+        precipitation: Precipitation::new(Some(chance), Some(0), Some(2)),
+        ..forecast::hour(time)
+    }
+}
+
+fn forecast_from(start: DateTime<Utc>, chances: &[u16]) -> Vec<HourlyForecast> {
+    chances
+        .iter()
+        .enumerate()
+        .map(|(i, chance)| hour(start + Duration::hours(i as i64), *chance))
+        .collect()
+}
+
+#[test]
+fn test_rain_countdown_to_next_rainy_hour() {
+    let start = Utc.with_ymd_and_hms(2025, 10, 1, 3, 0, 0).unwrap();
+    let hourly = forecast_from(start, &[10, 20, 70, 90]);
+    let now = start + Duration::minutes(80);
+
+    assert_eq!(minutes_until_rain(&hourly, now, 50, 180), Some(40));
+}
+
+#[test]
+fn test_rain_countdown_when_already_raining() {
+    let start = Utc.with_ymd_and_hms(2025, 10, 1, 3, 0, 0).unwrap();
+    let hourly = forecast_from(start, &[80, 90]);
+    let now = start + Duration::minutes(25);
+
+    assert_eq!(minutes_until_rain(&hourly, now, 50, 180), Some(0));
+}
+
+#[test]
+fn test_rain_countdown_ignores_rain_beyond_horizon() {
+    let start = Utc.with_ymd_and_hms(2025, 10, 1, 3, 0, 0).unwrap();
+    let hourly = forecast_from(start, &[0, 0, 0, 0, 95]);
+
+    assert_eq!(minutes_until_rain(&hourly, start, 50, 180), None);
+    assert_eq!(minutes_until_rain(&hourly, start, 50, 240), Some(240));
+}
+
+#[test]
+fn test_format_rain_countdown() {
+    assert_eq!(format_rain_countdown(0), "Rain now");
+    assert_eq!(format_rain_countdown(7), "Rain in ~10 min");
+    assert_eq!(format_rain_countdown(38), "Rain in ~40 min");
+    assert_eq!(format_rain_countdown(95), "Rain in ~2 h");
+    assert_eq!(format_rain_countdown(150), "Rain in ~3 h");
+}
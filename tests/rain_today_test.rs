@@ -0,0 +1,161 @@
+mod helpers;
+
+use chrono::{Duration, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::clock::{Clock, FixedClock};
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+use pi_inky_weather_epd::dashboard::rain_today::{RainLog, RAIN_LOG_RETENTION_HOURS};
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Precipitation, RainGaugeReading};
+
+/// Hours from `start` raining `rain_mm` each
+fn hours(start: chrono::DateTime<Utc>, count: i64, rain_mm: u16) -> Vec<HourlyForecast> {
+    (0..count)
+        .map(|i| HourlyForecast {
+            precipitation: Precipitation::new(Some(80), Some(rain_mm), Some(rain_mm)),
+            cloud_cover: Some(100),
+            ..forecast::hour(start + Duration::hours(i))
+        })
+        .collect()
+}
+
+#[test]
+fn test_rain_log_keeps_the_hours_started_by_now() {
+    let start = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+    let mut log = RainLog::default();
+
+    log.record(&hours(start, 6, 2), start + Duration::minutes(150));
+
+    assert_eq!(log.hours.len(), 3);
+    assert_eq!(
+        log.total_between(start, start + Duration::hours(2)),
+        Some(4.0)
+    );
+    assert_eq!(
+        log.total_between(start + Duration::hours(5), start + Duration::hours(6)),
+        None
+    );
+
+    log.record(&[], start + Duration::hours(RAIN_LOG_RETENTION_HOURS + 1));
+    assert_eq!(log.hours.len(), 2);
+}
+
+#[test]
+fn test_rain_today_splits_fallen_and_expected() {
+    // 2pm in Melbourne, local midnight was at 13:00 UTC the day before
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let midnight = Utc.with_ymd_and_hms(2025, 1, 9, 13, 0, 0).unwrap();
+    // The forecast starts at 7am, 1am is only in the rain log
+    let mut log = RainLog::default();
+    log.record(
+        &hours(midnight + Duration::hours(1), 1, 3),
+        midnight + Duration::hours(1),
+    );
+
+    let mut builder = ContextBuilder::new();
+    builder
+        .with_rain_log(log)
+        .with_hourly_forecast_data(hours(midnight + Duration::hours(7), 40, 1), &clock);
+
+    // 3 mm at 1am and 1 mm an hour from 7am to 1pm
+    assert_eq!(builder.context.rain_fallen_today, "10.0");
+    // 1 mm an hour from 2pm to midnight
+    assert_eq!(builder.context.rain_expected_today, "10.0");
+    assert_eq!(builder.context.rain_expected_today_with_unit, "10.0mm");
+}
+
+#[test]
+fn test_rain_fallen_is_unknown_without_past_hours() {
+    let now = Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap();
+    let mut builder = ContextBuilder::new();
+    builder.with_hourly_forecast_data(hours(now, 30, 0), &FixedClock::new(now));
+
+    assert_eq!(builder.context.rain_fallen_today, "NA");
+    assert_eq!(builder.context.rain_expected_today, "0.0");
+}
+
+/// Gauge reading at `time` of a gauge emptied at `since`
+fn reading(
+    time: chrono::DateTime<Utc>,
+    since: chrono::DateTime<Utc>,
+    rain_mm: f32,
+) -> RainGaugeReading {
+    RainGaugeReading {
+        time,
+        since,
+        rain_mm,
+    }
+}
+
+#[test]
+fn test_observed_rain_adds_up_across_the_gauge_emptying() {
+    let emptied = Utc.with_ymd_and_hms(2025, 1, 9, 22, 0, 0).unwrap();
+    let emptied_again = emptied + Duration::hours(24);
+    let start = Utc.with_ymd_and_hms(2025, 1, 10, 13, 0, 0).unwrap();
+    let mut log = RainLog::default();
+    log.record_gauge(reading(start - Duration::minutes(30), emptied, 2.0));
+    log.record_gauge(reading(start + Duration::minutes(30), emptied, 3.0));
+    log.record_gauge(reading(emptied_again - Duration::minutes(30), emptied, 7.5));
+    // The gauge was emptied at 9am local time, 22:00 UTC
+    log.record_gauge(reading(
+        emptied_again + Duration::minutes(30),
+        emptied_again,
+        0.5,
+    ));
+
+    // 1 mm around midnight, 4.5 mm until 9am and 0.5 mm after
+    assert_eq!(
+        log.observed_between(start, start + Duration::hours(12)),
+        Some(6.0)
+    );
+    // Without a reading before, the first one has nothing to count from
+    assert_eq!(
+        log.observed_between(start - Duration::hours(2), start),
+        None
+    );
+}
+
+#[test]
+fn test_rain_since_midnight_prefers_the_gauge() {
+    // 2pm in Melbourne, local midnight was at 13:00 UTC the day before
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let midnight = Utc.with_ymd_and_hms(2025, 1, 9, 13, 0, 0).unwrap();
+    let emptied = Utc.with_ymd_and_hms(2025, 1, 9, 22, 0, 0).unwrap();
+    let mut log = RainLog::default();
+    log.record_gauge(reading(
+        midnight - Duration::minutes(30),
+        emptied - Duration::hours(24),
+        1.0,
+    ));
+    log.record_gauge(reading(
+        midnight + Duration::minutes(30),
+        emptied - Duration::hours(24),
+        1.2,
+    ));
+    log.record_gauge(reading(
+        clock.now_utc() - Duration::minutes(30),
+        emptied,
+        6.8,
+    ));
+
+    let mut builder = ContextBuilder::new();
+    builder
+        .with_rain_log(log)
+        .with_hourly_forecast_data(hours(midnight + Duration::hours(7), 40, 1), &clock);
+
+    assert_eq!(builder.context.rain_since_midnight, "7.0");
+    assert_eq!(builder.context.rain_since_midnight_source, "observed");
+    // The forecast of the hours past is still shown in the table row
+    assert_eq!(builder.context.rain_fallen_today, "7.0");
+}
+
+#[test]
+fn test_rain_since_midnight_falls_back_to_the_forecast() {
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap());
+    let midnight = Utc.with_ymd_and_hms(2025, 1, 9, 13, 0, 0).unwrap();
+
+    let mut builder = ContextBuilder::new();
+    builder.with_hourly_forecast_data(hours(midnight + Duration::hours(7), 40, 2), &clock);
+
+    assert_eq!(builder.context.rain_since_midnight, "14.0");
+    assert_eq!(builder.context.rain_since_midnight_source, "forecast");
+}
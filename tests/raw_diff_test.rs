@@ -0,0 +1,156 @@
+use pi_inky_weather_epd::display_options::DisplayDriver;
+use pi_inky_weather_epd::raw_diff::{diff_frames, swap_last_frame, DiffRect, RawDiff, RawFrame};
+
+/// White frame of `width` x `height` pixels
+fn frame(width: u32, height: u32) -> RawFrame {
+    let size = width.div_ceil(2) * height;
+    RawFrame::new(
+        width,
+        height,
+        DisplayDriver::Acep,
+        vec![0x11; size as usize],
+    )
+    .unwrap()
+}
+
+/// Sets the pixel at `x`, `y` to ink `colour`
+fn paint(frame: &mut RawFrame, x: usize, y: usize, colour: u8) {
+    let index = y * usize::from(frame.width).div_ceil(2) + x / 2;
+    frame.data[index] = if x.is_multiple_of(2) {
+        (frame.data[index] & 0x0f) | (colour << 4)
+    } else {
+        (frame.data[index] & 0xf0) | colour
+    };
+}
+
+#[test]
+fn test_identical_frames_have_no_rectangles() {
+    let diff = diff_frames(Some(&frame(8, 4)), &frame(8, 4));
+
+    assert!(!diff.full_frame);
+    assert!(diff.rects.is_empty());
+}
+
+#[test]
+fn test_changed_pixels_become_an_even_aligned_rectangle() {
+    let previous = frame(16, 20);
+    let mut current = previous.clone();
+    paint(&mut current, 5, 3, 4);
+    paint(&mut current, 8, 4, 4);
+
+    let diff = diff_frames(Some(&previous), &current);
+
+    assert_eq!(diff.rects.len(), 1);
+    let rect = &diff.rects[0];
+    assert_eq!((rect.x, rect.y, rect.width, rect.height), (4, 3, 6, 2));
+    assert_eq!(rect.data, [0x14, 0x11, 0x11, 0x11, 0x11, 0x41]);
+}
+
+#[test]
+fn test_distant_changes_are_separate_rectangles() {
+    let previous = frame(16, 20);
+    let mut current = previous.clone();
+    paint(&mut current, 0, 0, 4);
+    paint(&mut current, 0, 2, 4);
+    paint(&mut current, 15, 19, 3);
+
+    let diff = diff_frames(Some(&previous), &current);
+
+    let rects: Vec<(u16, u16, u16, u16)> = diff
+        .rects
+        .iter()
+        .map(|rect| (rect.x, rect.y, rect.width, rect.height))
+        .collect();
+    // The unchanged row 1 is merged into the first rectangle
+    assert_eq!(rects, [(0, 0, 2, 3), (14, 19, 2, 1)]);
+}
+
+#[test]
+fn test_unknown_or_resized_previous_frame_sends_the_whole_frame() {
+    let current = frame(7, 3);
+
+    for previous in [None, Some(frame(8, 3))] {
+        let diff = diff_frames(previous.as_ref(), &current);
+
+        assert!(diff.full_frame);
+        assert_eq!(diff.rects.len(), 1);
+        let rect = &diff.rects[0];
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (0, 0, 7, 3));
+        assert_eq!(rect.data, current.data);
+    }
+}
+
+#[test]
+fn test_diff_round_trips_and_patches_the_previous_frame() {
+    let previous = frame(9, 10);
+    let mut current = previous.clone();
+    paint(&mut current, 8, 0, 2);
+    paint(&mut current, 3, 9, 5);
+
+    let bytes = diff_frames(Some(&previous), &current).to_bytes();
+    let diff = RawDiff::from_bytes(&bytes).unwrap();
+    let mut patched = previous.clone();
+    diff.apply(&mut patched).unwrap();
+
+    assert_eq!(&bytes[..4], b"RDIF");
+    assert_eq!(patched, current);
+}
+
+#[test]
+fn test_invalid_diffs_are_rejected() {
+    let diff = RawDiff {
+        width: 8,
+        height: 4,
+        driver: DisplayDriver::Acep,
+        full_frame: false,
+        rects: vec![DiffRect {
+            x: 6,
+            y: 0,
+            width: 4,
+            height: 1,
+            data: vec![0x11, 0x11],
+        }],
+    };
+    let bytes = diff.to_bytes();
+
+    assert!(RawDiff::from_bytes(&bytes).is_err());
+    assert!(RawDiff::from_bytes(&bytes[..11]).is_err());
+    assert!(RawDiff::from_bytes(b"PNG").is_err());
+    assert!(diff.apply(&mut frame(8, 4)).is_err());
+    assert!(RawFrame::new(8, 4, DisplayDriver::Acep, vec![0; 3]).is_err());
+}
+
+#[test]
+fn test_last_frame_survives_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = frame(8, 4);
+    let mut second = first.clone();
+    paint(&mut second, 1, 1, 0);
+
+    assert_eq!(swap_last_frame(dir.path(), &first), None);
+    assert_eq!(swap_last_frame(dir.path(), &second), Some(first.clone()));
+    assert_ne!(first.checksum(), second.checksum());
+    assert_eq!(second.checksum_hex().len(), 8);
+}
+
+#[test]
+fn test_it8951_rows_skip_the_padding() {
+    // 5 pixels wide, rows are padded to 4 bytes
+    let previous = RawFrame::new(5, 3, DisplayDriver::It8951, vec![0xff; 12]).unwrap();
+    let mut current = previous.clone();
+    current.data[4 + 2] = 0xf0;
+
+    let diff = diff_frames(Some(&previous), &current);
+    let full = diff_frames(None, &current);
+    let mut patched = previous.clone();
+    RawDiff::from_bytes(&diff.to_bytes())
+        .unwrap()
+        .apply(&mut patched)
+        .unwrap();
+
+    let rect = &diff.rects[0];
+    assert_eq!((rect.x, rect.y, rect.width, rect.height), (4, 1, 1, 1));
+    assert_eq!(full.rects[0].data.len(), 9);
+    assert_eq!(patched, current);
+    assert!(diff.apply(&mut frame(5, 3)).is_err());
+}
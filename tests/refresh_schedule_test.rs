@@ -0,0 +1,214 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::configs::settings::{HourOfDay, QuietHours, QuietMode, RefreshHint};
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Precipitation};
+use pi_inky_weather_epd::refresh_schedule::{refresh_schedule, AwakeHours, RefreshReason};
+
+const STEADY: u32 = 3600;
+
+/// Awake around the clock, so only the forecast decides the intervals
+const ALWAYS_AWAKE: AwakeHours = AwakeHours {
+    active_start: 0,
+    active_end: 24,
+    quiet_hours: None,
+};
+
+fn hint() -> RefreshHint {
+    RefreshHint {
+        fast_interval_seconds: 900,
+        night_interval_seconds: 10800,
+        pressure_change_hpa: 3.0,
+        rain_change_mm: 1.0,
+    }
+}
+
+/// Hours from `start` with the given pressure and rain, the hours from `night_from` on are night
+fn hours(
+    start: DateTime<Utc>,
+    pressure: &[f32],
+    rain_mm: &[u16],
+    night_from: usize,
+) -> Vec<HourlyForecast> {
+    pressure
+        .iter()
+        .zip(rain_mm)
+        .enumerate()
+        .map(|(i, (&pressure, &rain_mm))| HourlyForecast {
+            precipitation: Precipitation::new(Some(50), Some(rain_mm), Some(rain_mm)),
+            is_night: i >= night_from,
+            pressure: Some(pressure),
+            ..forecast::hour(start + Duration::hours(i as i64))
+        })
+        .collect()
+}
+
+#[test]
+fn test_steady_day_and_night() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    let hourly = hours(start, &[1015.0; 8], &[0; 8], 5);
+
+    let schedule = refresh_schedule(
+        &hourly,
+        &hint(),
+        STEADY,
+        &ALWAYS_AWAKE,
+        start + Duration::minutes(20),
+    );
+
+    assert_eq!(schedule.slots.len(), 2);
+    assert_eq!(schedule.slots[0].reason, RefreshReason::Steady);
+    assert_eq!(schedule.slots[0].from, start);
+    assert_eq!(schedule.slots[0].until, start + Duration::hours(5));
+    assert_eq!(schedule.slots[0].interval_seconds, STEADY);
+    assert_eq!(schedule.slots[1].reason, RefreshReason::Night);
+    assert_eq!(schedule.slots[1].interval_seconds, 10800);
+    assert_eq!(schedule.next_delay_seconds, STEADY);
+}
+
+#[test]
+fn test_front_and_rain_shorten_the_interval() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    // Pressure drops 4 hPa between 13:00 and 16:00, rain starts at 19:00
+    let pressure = [
+        1015.0, 1015.0, 1015.0, 1015.0, 1013.0, 1011.5, 1011.0, 1011.0, 1011.0, 1011.0, 1011.0,
+        1011.0,
+    ];
+    let rain = [0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 3, 3];
+    let hourly = hours(start, &pressure, &rain, 12);
+
+    let schedule = refresh_schedule(&hourly, &hint(), STEADY, &ALWAYS_AWAKE, start);
+    let reasons: Vec<RefreshReason> = schedule.slots.iter().map(|slot| slot.reason).collect();
+
+    assert_eq!(
+        reasons,
+        vec![
+            RefreshReason::Steady,
+            RefreshReason::Front,
+            RefreshReason::Steady,
+            RefreshReason::RainChange,
+            RefreshReason::Steady,
+        ]
+    );
+    // The 3.5 hPa drop from 12:00 to 15:00 starts the front, the 4 hPa one from 13:00 ends it
+    assert_eq!(schedule.slots[1].from, start + Duration::hours(2));
+    assert_eq!(schedule.slots[1].until, start + Duration::hours(7));
+    assert_eq!(schedule.slots[1].interval_seconds, 900);
+    // The hour before the rain and the first rainy hour
+    assert_eq!(schedule.slots[3].from, start + Duration::hours(8));
+    assert_eq!(schedule.slots[3].until, start + Duration::hours(10));
+}
+
+#[test]
+fn test_next_delay_wakes_up_for_a_faster_slot() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    let rain = [0, 0, 5, 5];
+    let hourly = hours(start, &[1015.0; 4], &rain, 4);
+
+    // Rain starts at 12:00, the hour before it is already fast
+    let schedule = refresh_schedule(
+        &hourly,
+        &hint(),
+        STEADY,
+        &ALWAYS_AWAKE,
+        start + Duration::minutes(30),
+    );
+    assert_eq!(schedule.slots[1].from, start + Duration::hours(1));
+    assert_eq!(schedule.next_delay_seconds, 30 * 60);
+}
+
+#[test]
+fn test_low_battery_spaces_out_the_refreshes() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    let rain = [0, 0, 5, 5, 5, 5];
+    let hourly = hours(start, &[1015.0; 6], &rain, 4);
+
+    let schedule =
+        refresh_schedule(&hourly, &hint(), STEADY, &ALWAYS_AWAKE, start).low_battery(7200);
+    let reasons: Vec<RefreshReason> = schedule.slots.iter().map(|slot| slot.reason).collect();
+
+    // The steady and rain slots merge into one, the longer night keeps its own
+    assert_eq!(
+        reasons,
+        vec![RefreshReason::LowBattery, RefreshReason::Night]
+    );
+    assert_eq!(schedule.slots[0].until, start + Duration::hours(4));
+    assert_eq!(schedule.slots[0].interval_seconds, 7200);
+    assert_eq!(schedule.slots[1].interval_seconds, 10800);
+    assert_eq!(schedule.next_delay_seconds, 7200);
+
+    // Intervals already longer are kept
+    let schedule =
+        refresh_schedule(&hourly, &hint(), STEADY, &ALWAYS_AWAKE, start).low_battery(1800);
+    assert_eq!(schedule.slots[0].reason, RefreshReason::Steady);
+    assert_eq!(schedule.slots[1].reason, RefreshReason::LowBattery);
+}
+
+#[test]
+fn test_past_hours_and_empty_forecasts() {
+    let start = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+    let hourly = hours(start, &[1015.0; 30], &[0; 30], 30);
+
+    let schedule = refresh_schedule(
+        &hourly,
+        &hint(),
+        STEADY,
+        &ALWAYS_AWAKE,
+        start + Duration::hours(2),
+    );
+    assert_eq!(schedule.slots[0].from, start + Duration::hours(2));
+    // The hours after the next 24 are left out
+    assert_eq!(
+        schedule.slots.last().unwrap().until,
+        start + Duration::hours(26)
+    );
+
+    let schedule = refresh_schedule(&[], &hint(), STEADY, &ALWAYS_AWAKE, start);
+    assert!(schedule.slots.is_empty());
+    assert_eq!(schedule.next_delay_seconds, STEADY);
+}
+
+#[test]
+fn test_display_sleeps_outside_the_active_and_through_the_quiet_hours() {
+    let start = Local
+        .with_ymd_and_hms(2025, 6, 1, 18, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc);
+    let hourly = hours(start, &[1015.0; 8], &[0; 8], 8);
+    let awake_hours = AwakeHours {
+        active_start: 9,
+        active_end: 21,
+        quiet_hours: Some(QuietHours {
+            start: HourOfDay::try_new(23).unwrap(),
+            end: HourOfDay::try_new(6).unwrap(),
+            mode: QuietMode::Night,
+        }),
+    };
+
+    let schedule = refresh_schedule(&hourly, &hint(), STEADY, &awake_hours, start);
+    let slots: Vec<(RefreshReason, u32)> = schedule
+        .slots
+        .iter()
+        .map(|slot| (slot.reason, slot.interval_seconds))
+        .collect();
+
+    assert_eq!(
+        slots,
+        vec![
+            (RefreshReason::Steady, STEADY),
+            (RefreshReason::Inactive, 2 * 3600),
+            (RefreshReason::QuietHours, 3 * 3600),
+        ]
+    );
+    assert_eq!(schedule.slots[2].from, start + Duration::hours(5));
+
+    // Outside the active hours the display sleeps until the quiet hours start
+    let now = start + Duration::minutes(3 * 60 + 30);
+    let schedule = refresh_schedule(&hourly, &hint(), STEADY, &awake_hours, now);
+    assert_eq!(schedule.next_delay_seconds, 90 * 60);
+    assert_eq!(
+        schedule.low_battery(7200).slots[0].reason,
+        RefreshReason::Inactive
+    );
+}
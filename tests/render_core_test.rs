@@ -0,0 +1,113 @@
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::configs::settings::Providers;
+use pi_inky_weather_epd::errors::DashboardError;
+use pi_inky_weather_epd::render_core::{
+    forecast_diagnostics, render_from_responses, ForecastInput,
+};
+use pi_inky_weather_epd::{WeatherEpdError, CONFIG};
+use std::fs;
+
+const TEMPLATE: &str = "dashboard-template-min.svg";
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(format!("tests/fixtures/{name}")).unwrap()
+}
+
+fn render_open_meteo() -> String {
+    // 12:00 in Melbourne, the hours of the fixture start at midnight
+    let clock = FixedClock::from_rfc3339("2025-10-25T01:00:00Z").unwrap();
+    render_from_responses(
+        &fs::read_to_string(TEMPLATE).unwrap(),
+        &CONFIG,
+        Providers::OpenMeteo,
+        &fixture("open_meteo_daily_forecast.json"),
+        &fixture("open_meteo_hourly_forecast.json"),
+        &clock,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_dashboard_renders_from_recorded_responses() {
+    let svg = render_open_meteo();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(">Sun</text>"), "tomorrow's tile is named");
+}
+
+#[test]
+fn test_render_keeps_no_state_between_runs() {
+    assert_eq!(render_open_meteo(), render_open_meteo());
+}
+
+#[test]
+fn test_bom_responses_render_too() {
+    // 21:00 in Melbourne, just before the first hour of the fixture
+    let clock = FixedClock::from_rfc3339("2025-10-25T10:00:00Z").unwrap();
+
+    let svg = render_from_responses(
+        &fs::read_to_string(TEMPLATE).unwrap(),
+        &CONFIG,
+        Providers::Bom,
+        &fixture("bom_daily_forecast.json"),
+        &fixture("bom_hourly_forecast.json"),
+        &clock,
+    )
+    .unwrap();
+
+    assert!(svg.starts_with("<svg"));
+}
+
+#[test]
+fn test_responses_of_another_provider_are_refused() {
+    let error = ForecastInput::from_responses(
+        &CONFIG,
+        Providers::Bom,
+        &fixture("open_meteo_daily_forecast.json"),
+        &fixture("open_meteo_hourly_forecast.json"),
+    )
+    .unwrap_err();
+
+    assert!(matches!(error, WeatherEpdError::Provider(_)), "{error}");
+}
+
+#[test]
+fn test_invalid_template_is_a_render_error() {
+    let clock = FixedClock::from_rfc3339("2025-10-25T01:00:00Z").unwrap();
+
+    let error = render_from_responses(
+        "<svg>{unknown_field}</svg>",
+        &CONFIG,
+        Providers::OpenMeteo,
+        &fixture("open_meteo_daily_forecast.json"),
+        &fixture("open_meteo_hourly_forecast.json"),
+        &clock,
+    )
+    .unwrap_err();
+
+    assert!(
+        error.to_string().to_lowercase().contains("render"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_diagnostics_include_what_building_the_context_finds() {
+    let clock = FixedClock::from_rfc3339("2025-10-25T01:00:00Z").unwrap();
+    let mut input = ForecastInput::from_responses(
+        &CONFIG,
+        Providers::OpenMeteo,
+        &fixture("open_meteo_daily_forecast.json"),
+        &fixture("open_meteo_hourly_forecast.json"),
+    )
+    .unwrap();
+    assert!(forecast_diagnostics(input.clone(), &CONFIG, &clock).is_empty());
+
+    input.daily.truncate(2);
+    let diagnostics = forecast_diagnostics(input, &CONFIG, &clock);
+
+    assert!(
+        matches!(diagnostics[..], [DashboardError::IncompleteData { .. }]),
+        "{diagnostics:?}"
+    );
+}
@@ -0,0 +1,79 @@
+use pi_inky_weather_epd::run_guard::{
+    read_run_status, Interrupted, RunGuard, RunLock, RunStatus, RUN_LOCK_FILE_NAME,
+};
+use serial_test::serial;
+
+#[test]
+fn test_second_run_is_refused_while_the_lock_is_held() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let lock = RunLock::acquire(dir.path()).unwrap();
+    let pid = std::fs::read_to_string(dir.path().join(RUN_LOCK_FILE_NAME)).unwrap();
+    assert_eq!(pid, std::process::id().to_string());
+
+    let error = RunLock::acquire(dir.path()).unwrap_err();
+    assert!(error.to_string().contains("skipping this one"), "{error}");
+
+    drop(lock);
+    assert!(RunLock::acquire(dir.path()).is_ok());
+}
+
+#[test]
+fn test_run_status_round_trips() {
+    for status in [
+        RunStatus::Running("generating".to_string()),
+        RunStatus::Success,
+        RunStatus::Failed("API error: timeout".to_string()),
+        RunStatus::Aborted("updating".to_string()),
+    ] {
+        assert_eq!(RunStatus::parse(&status.to_string()), Some(status));
+    }
+    assert_eq!(RunStatus::parse("unknown"), None);
+}
+
+#[test]
+#[serial]
+fn test_run_status_follows_the_phases() {
+    let dir = tempfile::tempdir().unwrap();
+    let guard = RunGuard::start(dir.path()).unwrap();
+
+    let status = guard
+        .run("generating", async {
+            Ok::<_, anyhow::Error>(read_run_status(dir.path()))
+        })
+        .unwrap();
+    assert_eq!(status, Some(RunStatus::Running("generating".to_string())));
+
+    let result = guard.finish_phase("updating", || Err::<(), _>(anyhow::anyhow!("no release")));
+    guard.finish(&result);
+    assert_eq!(
+        read_run_status(dir.path()),
+        Some(RunStatus::Failed("no release".to_string()))
+    );
+}
+
+#[test]
+fn test_stop_request_aborts_the_current_phase() {
+    let dir = tempfile::tempdir().unwrap();
+    let (stop, stop_requests) = tokio::sync::watch::channel(false);
+    let guard = RunGuard::with_stop_requests(dir.path(), stop_requests).unwrap();
+
+    let result = guard.run("generating", async {
+        stop.send_replace(true);
+        std::future::pending::<Result<(), anyhow::Error>>().await
+    });
+
+    let error = result.as_ref().unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<Interrupted>().unwrap().phase,
+        "generating"
+    );
+    let next_phase = guard.finish_phase("updating", || Ok(()));
+    assert!(next_phase.is_err(), "no phase runs after a stop request");
+
+    guard.finish(&result);
+    assert_eq!(
+        read_run_status(dir.path()),
+        Some(RunStatus::Aborted("generating".to_string()))
+    );
+}
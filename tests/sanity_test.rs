@@ -0,0 +1,148 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+use pi_inky_weather_epd::domain::models::{DailyForecast, HourlyForecast, Temperature};
+use pi_inky_weather_epd::domain::sanity::{
+    anomaly_warning, drop_daily_anomalies, drop_hourly_anomalies,
+};
+use pi_inky_weather_epd::errors::DashboardError;
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 10, 25, hour, 0, 0).unwrap()
+}
+
+fn hour(time: DateTime<Utc>, temperature: Temperature, humidity: i16) -> HourlyForecast {
+    HourlyForecast {
+        temperature,
+        apparent_temperature: temperature,
+        relative_humidity: humidity,
+        ..forecast::hour(time)
+    }
+}
+
+fn day(day: u32, temp_max: f32) -> DailyForecast {
+    DailyForecast {
+        temp_max: Some(Temperature::celsius(temp_max)),
+        ..forecast::day(NaiveDate::from_ymd_opt(2025, 10, day).unwrap())
+    }
+}
+
+#[test]
+fn test_plausible_hours_are_kept() {
+    let mut hourly: Vec<HourlyForecast> = (0..24)
+        .map(|h| hour(at(h), Temperature::celsius(15.0), 50))
+        .collect();
+
+    assert!(drop_hourly_anomalies(&mut hourly).is_empty());
+    assert_eq!(hourly.len(), 24);
+}
+
+#[test]
+fn test_implausible_hours_are_dropped() {
+    let mut hourly = vec![
+        hour(at(0), Temperature::celsius(15.0), 50),
+        hour(at(1), Temperature::celsius(-999.0), 50),
+        hour(at(2), Temperature::celsius(16.0), -1),
+        hour(at(3), Temperature::celsius(16.0), 50),
+        hour(at(3), Temperature::celsius(16.0), 50),
+        hour(at(2), Temperature::celsius(16.0), 50),
+        hour(at(4), Temperature::celsius(17.0), 101),
+        hour(at(5), Temperature::celsius(17.0), 50),
+    ];
+
+    let anomalies = drop_hourly_anomalies(&mut hourly);
+
+    let kept: Vec<DateTime<Utc>> = hourly.iter().map(|hour| hour.time).collect();
+    assert_eq!(kept, vec![at(0), at(3), at(5)]);
+    assert_eq!(
+        anomalies,
+        vec![
+            "hour 2025-10-25 01:00 UTC: temperature -999.0 °C outside -60..60 °C",
+            "hour 2025-10-25 02:00 UTC: humidity -1 % outside 0..100 %",
+            "hour 2025-10-25 03:00 UTC: duplicate hour",
+            "hour 2025-10-25 02:00 UTC: out of order after 2025-10-25 03:00 UTC",
+            "hour 2025-10-25 04:00 UTC: humidity 101 % outside 0..100 %",
+        ]
+    );
+}
+
+#[test]
+fn test_temperature_range_applies_in_celsius() {
+    // 130 °F is 54 °C, 150 °F is 66 °C
+    let mut hourly = vec![
+        hour(at(0), Temperature::fahrenheit(130.0), 20),
+        hour(at(1), Temperature::fahrenheit(150.0), 20),
+    ];
+
+    let anomalies = drop_hourly_anomalies(&mut hourly);
+
+    assert_eq!(hourly.len(), 1);
+    assert_eq!(anomalies.len(), 1);
+}
+
+#[test]
+fn test_implausible_daily_values_are_cleared_and_repeated_days_dropped() {
+    let mut daily = vec![day(25, 20.0), day(26, 99.0), day(26, 21.0), day(25, 22.0)];
+
+    let anomalies = drop_daily_anomalies(&mut daily);
+
+    assert_eq!(daily.len(), 2);
+    assert_eq!(daily[1].temp_max, None);
+    assert_eq!(daily[1].temp_min, Some(Temperature::celsius(10.0)));
+    assert_eq!(
+        anomalies,
+        vec![
+            "day 2025-10-26: max temperature 99.0 °C outside -60..60 °C",
+            "day 2025-10-26: duplicate day",
+            "day 2025-10-25: out of order after 2025-10-26",
+        ]
+    );
+}
+
+#[test]
+fn test_dropped_points_raise_incomplete_data() {
+    assert!(anomaly_warning(&[]).is_none());
+
+    let warning = anomaly_warning(&["hour 2025-10-25 01:00 UTC: duplicate hour".to_string()]);
+    match warning {
+        Some(DashboardError::IncompleteData { details }) => {
+            assert!(details.starts_with("Dropped 1 implausible forecast value(s)"));
+            assert!(details.ends_with("duplicate hour"));
+        }
+        other => panic!("expected IncompleteData, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_dropped_hour_does_not_shift_the_graph() {
+    let now = Local
+        .with_ymd_and_hms(2025, 10, 25, 9, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc);
+    let clock = FixedClock::new(now);
+    let ramp: Vec<HourlyForecast> = (0..24)
+        .map(|h| {
+            hour(
+                now + Duration::hours(h),
+                Temperature::celsius(10.0 + h as f32),
+                50,
+            )
+        })
+        .collect();
+    let mut gapped = ramp.clone();
+    gapped[5].temperature = Temperature::celsius(90.0);
+    assert_eq!(drop_hourly_anomalies(&mut gapped).len(), 1);
+
+    let curve_end = |hourly: Vec<HourlyForecast>| {
+        let mut builder = ContextBuilder::new();
+        builder.with_hourly_forecast_data(hourly, &clock);
+        let curve = builder.context.actual_temp_curve_data.clone();
+        let coordinates: Vec<&str> = curve.split_whitespace().rev().take(2).collect();
+        coordinates.join(" ")
+    };
+
+    assert_eq!(curve_end(gapped), curve_end(ramp));
+}
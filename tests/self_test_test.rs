@@ -0,0 +1,102 @@
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::panel::svg_size;
+use pi_inky_weather_epd::self_test::{check_network, self_test_svg, NetworkStatus};
+use pi_inky_weather_epd::CONFIG;
+use serial_test::serial;
+use wiremock::MockServer;
+
+fn clock() -> FixedClock {
+    FixedClock::from_rfc3339("2025-10-09T22:00:00Z").unwrap()
+}
+
+fn reachable() -> NetworkStatus {
+    NetworkStatus::Reachable {
+        host: "api.open-meteo.com".to_string(),
+        status: 200,
+    }
+}
+
+fn point_providers_at(base_url: &str) {
+    std::env::set_var("OPEN_METEO_BASE_URL", base_url);
+    std::env::set_var("BOM_BASE_URL", format!("{base_url}/v1/locations"));
+}
+
+#[test]
+fn test_screen_shows_the_version_and_configuration() {
+    let svg = self_test_svg(&reachable(), &clock());
+
+    assert!(svg.contains(env!("CARGO_PKG_VERSION")));
+    assert!(svg.contains(&CONFIG.api.provider.to_string()));
+    assert!(svg.contains(&format!("{} driver", CONFIG.misc.display_driver)));
+    assert!(svg.contains("Rendered at "));
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+}
+
+#[test]
+fn test_screen_has_the_size_of_the_template() {
+    let svg = self_test_svg(&reachable(), &clock());
+    let template = std::fs::read_to_string(&CONFIG.misc.template_path).unwrap();
+
+    assert_eq!(svg_size(&svg), svg_size(&template));
+}
+
+#[test]
+fn test_screen_shows_the_network_status() {
+    let online = self_test_svg(&reachable(), &clock());
+    let offline = self_test_svg(
+        &NetworkStatus::Unreachable {
+            host: "api.open-meteo.com".to_string(),
+            error: "dns error <timeout>".to_string(),
+        },
+        &clock(),
+    );
+
+    assert!(online.contains(">Network OK<"));
+    assert!(online.contains("api.open-meteo.com reachable (HTTP 200)"));
+    assert!(offline.contains(">Network down<"));
+    assert!(
+        offline.contains("dns error &lt;timeout&gt;"),
+        "errors are escaped"
+    );
+}
+
+#[test]
+fn test_screen_draws_sample_icons_from_the_icon_directory() {
+    let svg = self_test_svg(&reachable(), &clock());
+    let icons = CONFIG
+        .misc
+        .svg_icons_directory
+        .to_string_lossy()
+        .to_string();
+
+    assert!(svg.matches(&format!(r#"href="{icons}"#)).count() >= 4);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_any_http_answer_counts_as_reachable() {
+    let server = MockServer::start().await;
+    point_providers_at(&server.uri());
+
+    let status = check_network().await;
+
+    // Nothing is mounted, the server answers 404 and no forecast is requested
+    assert!(
+        matches!(status, NetworkStatus::Reachable { status: 404, .. }),
+        "{status}"
+    );
+    assert!(server.received_requests().await.unwrap()[0].url.path() == "/");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_refused_connection_is_unreachable() {
+    point_providers_at("http://127.0.0.1:1");
+
+    let status = check_network().await;
+
+    assert!(
+        matches!(status, NetworkStatus::Unreachable { ref host, .. } if host == "127.0.0.1"),
+        "{status}"
+    );
+}
@@ -113,13 +113,13 @@ async fn snapshot_open_meteo_dashboard() {
 
     let output_svg_name = Path::new("tests/output/snapshot_open_meteo_dashboard.svg");
 
-    // Run sync dashboard generation in blocking task
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -130,9 +130,7 @@ async fn snapshot_open_meteo_dashboard() {
         assert!(!svg.is_empty(), "Generated SVG should not be empty");
         assert!(svg.contains("<svg"), "Generated file should be valid SVG");
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     // Cleanup
     std::env::remove_var("OPEN_METEO_BASE_URL");
@@ -173,12 +171,13 @@ async fn snapshot_open_meteo_midnight_boundary() {
         FixedClock::from_rfc3339("2025-10-26T00:00:00Z").expect("Failed to create fixed clock");
     let output_svg_name = Path::new("tests/output/snapshot_open_meteo_midnight_boundary.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -187,9 +186,7 @@ async fn snapshot_open_meteo_midnight_boundary() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("OPEN_METEO_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -226,12 +223,13 @@ async fn snapshot_open_meteo_end_of_day() {
         FixedClock::from_rfc3339("2025-10-25T13:00:00Z").expect("Failed to create fixed clock");
     let output_svg_name = Path::new("tests/output/snapshot_open_meteo_end_of_day.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -240,9 +238,7 @@ async fn snapshot_open_meteo_end_of_day() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("OPEN_METEO_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -279,12 +275,13 @@ async fn snapshot_open_meteo_early_morning() {
         FixedClock::from_rfc3339("2025-10-25T16:00:00Z").expect("Failed to create fixed clock");
     let output_svg_name = Path::new("tests/output/snapshot_open_meteo_early_morning.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -293,9 +290,7 @@ async fn snapshot_open_meteo_early_morning() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("OPEN_METEO_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -363,13 +358,13 @@ async fn snapshot_bom_dashboard() {
 
     let output_svg_name = Path::new("tests/output/snapshot_bom_dashboard.svg");
 
-    // Run sync dashboard generation in blocking task
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -380,9 +375,7 @@ async fn snapshot_bom_dashboard() {
         assert!(!svg.is_empty(), "Generated SVG should not be empty");
         assert!(svg.contains("<svg"), "Generated file should be valid SVG");
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     // Cleanup
     std::env::remove_var("BOM_BASE_URL");
@@ -424,12 +417,13 @@ async fn snapshot_bom_midnight_boundary() {
 
     let output_svg_name = Path::new("tests/output/snapshot_bom_midnight_boundary.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -438,9 +432,7 @@ async fn snapshot_bom_midnight_boundary() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("BOM_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -479,12 +471,13 @@ async fn snapshot_bom_local_midnight() {
 
     let output_svg_name = Path::new("tests/output/snapshot_bom_local_midnight.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -493,9 +486,7 @@ async fn snapshot_bom_local_midnight() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("BOM_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -537,12 +528,13 @@ async fn snapshot_bom_early_morning() {
 
     let output_svg_name = Path::new("tests/output/snapshot_bom_early_morning.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         let result = generate_weather_dashboard_injection(
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -551,9 +543,7 @@ async fn snapshot_bom_early_morning() {
         let svg = fs::read_to_string(output_svg_name).expect("Failed to read generated SVG file");
         assert!(!svg.is_empty() && svg.contains("<svg"));
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("BOM_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -591,7 +581,7 @@ async fn snapshot_open_meteo_ny_6pm_before_gmt_boundary() {
     let output_svg_name =
         Path::new("tests/output/snapshot_open_meteo_ny_6pm_before_gmt_boundary.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         // Set timezone
         let original_tz = std::env::var("TZ").ok();
         unsafe {
@@ -602,7 +592,8 @@ async fn snapshot_open_meteo_ny_6pm_before_gmt_boundary() {
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -619,9 +610,7 @@ async fn snapshot_open_meteo_ny_6pm_before_gmt_boundary() {
             }
         }
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("OPEN_METEO_BASE_URL");
     insta::assert_snapshot!(svg_content);
@@ -669,7 +658,7 @@ async fn snapshot_open_meteo_ny_7pm_after_gmt_boundary() {
     let output_svg_name =
         Path::new("tests/output/snapshot_open_meteo_ny_7pm_after_gmt_boundary.svg");
 
-    let svg_content = tokio::task::spawn_blocking(move || {
+    let svg_content = {
         // Set timezone
         let original_tz = std::env::var("TZ").ok();
         unsafe {
@@ -680,7 +669,8 @@ async fn snapshot_open_meteo_ny_7pm_after_gmt_boundary() {
             &clock,
             &CONFIG.misc.template_path,
             output_svg_name,
-        );
+        )
+        .await;
         assert!(
             result.is_ok(),
             "Dashboard generation failed: {:?}",
@@ -697,9 +687,7 @@ async fn snapshot_open_meteo_ny_7pm_after_gmt_boundary() {
             }
         }
         svg
-    })
-    .await
-    .expect("Task panicked");
+    };
 
     std::env::remove_var("OPEN_METEO_BASE_URL");
     insta::assert_snapshot!(svg_content);
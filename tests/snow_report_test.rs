@@ -0,0 +1,151 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pi_inky_weather_epd::apis::open_meteo::models::{OpenMeteoSnowResponse, SnowHourly};
+use pi_inky_weather_epd::configs::settings::SnowReport;
+use pi_inky_weather_epd::dashboard::panel::PanelContent;
+use pi_inky_weather_epd::dashboard::snow_report::{
+    fetch_lift_status, parse_lift_status, snow_report_lines, snow_report_panel, LiftStatus,
+    SnowConditions, SnowData,
+};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+use wiremock::matchers::path;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn settings(lift_status_url: Option<String>) -> SnowReport {
+    SnowReport {
+        enabled: true,
+        x: 620.0,
+        y: 10.0,
+        width: 170.0,
+        height: 75.0,
+        lift_status_url: lift_status_url.map(|url| url.parse().unwrap()),
+        lifts_open_pointer: "/lifts/open".to_string(),
+        lifts_total_pointer: "/lifts/total".to_string(),
+    }
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 7, 10, 12, 0, 0).unwrap()
+}
+
+/// 4 days of hours up to 12 hours after `now`, 1 cm of snow an hour and a depth of 1.25 m
+fn snow_data() -> SnowData {
+    let start = now() - Duration::days(4);
+    let time: Vec<_> = (0..=(4 * 24 + 12))
+        .map(|hour| start + Duration::hours(hour))
+        .collect();
+    let hours = time.len();
+    SnowData {
+        snow: Some(OpenMeteoSnowResponse {
+            hourly: SnowHourly {
+                snow_depth: (0..hours).map(|_| Some(1.25)).collect(),
+                snowfall: (0..hours).map(|_| Some(1.0)).collect(),
+                time,
+            },
+        }),
+        lifts: Some(LiftStatus { open: 5, total: 12 }),
+    }
+}
+
+#[test]
+fn test_conditions_sum_the_snowfall_up_to_now() {
+    let conditions = snow_data().conditions_at(now());
+
+    assert_eq!(conditions.depth_cm, Some(125.0));
+    assert_eq!(conditions.snowfall_24h_cm, Some(24.0));
+    assert_eq!(conditions.snowfall_3d_cm, Some(72.0));
+    assert_eq!(conditions.lifts, Some(LiftStatus { open: 5, total: 12 }));
+}
+
+#[test]
+fn test_conditions_use_the_latest_reported_depth() {
+    let mut data = snow_data();
+    let hourly = &mut data.snow.as_mut().unwrap().hourly;
+    let now_index = hourly.time.iter().position(|time| *time == now()).unwrap();
+    hourly.snow_depth[now_index - 1] = Some(0.75);
+    hourly.snow_depth[now_index] = None;
+
+    assert_eq!(data.conditions_at(now()).depth_cm, Some(75.0));
+}
+
+#[test]
+fn test_lift_status_from_numbers_strings_and_arrays() {
+    let feed = json!({
+        "lifts": { "open": 5, "total": "12" },
+        "resort": { "lifts": [{ "name": "Summit" }, { "name": "Valley" }] }
+    });
+
+    assert_eq!(
+        parse_lift_status(&feed, "/lifts/open", "/lifts/total"),
+        Some(LiftStatus { open: 5, total: 12 })
+    );
+    assert_eq!(
+        parse_lift_status(&feed, "/lifts/open", "/resort/lifts"),
+        Some(LiftStatus { open: 5, total: 2 })
+    );
+    assert_eq!(parse_lift_status(&feed, "/lifts/open", "/missing"), None);
+}
+
+#[test]
+fn test_report_leaves_out_lines_without_data() {
+    let conditions = SnowConditions {
+        lifts: Some(LiftStatus { open: 0, total: 8 }),
+        ..SnowConditions::default()
+    };
+
+    assert_eq!(snow_report_lines(&conditions), vec!["Lifts 0/8 open"]);
+    assert!(
+        snow_report_panel(&settings(None), &SnowConditions::default(), &CONFIG.colours).is_none()
+    );
+}
+
+#[test]
+fn test_panel_shows_the_snow_report() {
+    let panel = snow_report_panel(
+        &settings(None),
+        &snow_data().conditions_at(now()),
+        &CONFIG.colours,
+    )
+    .unwrap();
+
+    assert_eq!(
+        (panel.x, panel.y, panel.width, panel.height),
+        (620.0, 10.0, 170.0, 75.0)
+    );
+    let PanelContent::Svg(fragment) = &panel.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(">Snow 125 cm<"));
+    assert!(fragment.contains(">24h +24 cm · 3d +72 cm<"));
+    assert!(fragment.contains(">Lifts 5/12 open<"));
+}
+
+#[tokio::test]
+async fn test_lift_status_is_fetched_from_the_feed() {
+    let server = MockServer::start().await;
+    Mock::given(path("/lifts.json"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "lifts": { "open": 3, "total": 9 } })),
+        )
+        .mount(&server)
+        .await;
+    let report = settings(Some(format!("{}/lifts.json", server.uri())));
+
+    assert_eq!(
+        fetch_lift_status(&report, false).await,
+        Some(LiftStatus { open: 3, total: 9 })
+    );
+    assert_eq!(fetch_lift_status(&report, true).await, None);
+}
+
+#[tokio::test]
+async fn test_unreachable_lift_feed_hides_the_lift_line() {
+    let server = MockServer::start().await;
+    Mock::given(path("/lifts.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let report = settings(Some(format!("{}/lifts.json", server.uri())));
+
+    assert_eq!(fetch_lift_status(&report, false).await, None);
+}
@@ -0,0 +1,58 @@
+mod helpers;
+
+use chrono::{Duration, Local, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::clock::FixedClock;
+use pi_inky_weather_epd::dashboard::chart::sparkline_path;
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Temperature};
+
+fn hour(time: chrono::DateTime<Utc>, temperature: f32) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(temperature),
+        apparent_temperature: Temperature::celsius(temperature),
+        ..forecast::hour(time)
+    }
+}
+
+#[test]
+fn test_sparkline_spans_the_day_from_low_to_high() {
+    let path = sparkline_path(&[(0.0, 10.0), (12.0, 20.0), (23.0, 15.0)]).unwrap();
+
+    assert_eq!(path, "M 0.00 20.00 L 52.17 0.00 L 100.00 10.00");
+}
+
+#[test]
+fn test_flat_sparkline_runs_through_the_middle() {
+    let path = sparkline_path(&[(6.0, 12.0), (7.0, 12.0)]).unwrap();
+
+    assert_eq!(path, "M 26.09 10.00 L 30.43 10.00");
+}
+
+#[test]
+fn test_single_hour_has_no_sparkline() {
+    assert!(sparkline_path(&[(6.0, 12.0)]).is_none());
+    assert!(sparkline_path(&[]).is_none());
+}
+
+#[test]
+fn test_sparklines_only_for_days_with_hourly_data() {
+    let now = Local.with_ymd_and_hms(2025, 10, 9, 9, 0, 0).unwrap();
+    let clock = FixedClock::new(now.with_timezone(&Utc));
+    // Hourly data until the end of tomorrow
+    let start = Local
+        .with_ymd_and_hms(2025, 10, 9, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc);
+    let hourly: Vec<HourlyForecast> = (0..48)
+        .map(|i| hour(start + Duration::hours(i), 10.0 + (i % 24) as f32))
+        .collect();
+
+    let mut builder = ContextBuilder::new();
+    builder.with_hourly_forecast_data(hourly, &clock);
+
+    assert_eq!(builder.context.day2_sparkline_visibility, "visible");
+    assert!(builder.context.day2_sparkline.starts_with("M 0.00 20.00"));
+    assert_eq!(builder.context.day3_sparkline_visibility, "hidden");
+    assert!(builder.context.day3_sparkline.is_empty());
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+use pi_inky_weather_epd::calendar::{easter_sunday, DateRule};
+use pi_inky_weather_epd::configs::validation::is_valid_special_date_rule;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_easter_sunday_known_years() {
+    assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+    assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+    assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+    assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+}
+
+#[test]
+fn test_yearly_rule_matches_every_year() {
+    let rule = DateRule::parse("12-25").unwrap();
+    assert!(rule.matches(date(2025, 12, 25)));
+    assert!(rule.matches(date(2031, 12, 25)));
+    assert!(!rule.matches(date(2025, 12, 24)));
+}
+
+#[test]
+fn test_once_rule_matches_single_date() {
+    let rule = DateRule::parse("2026-03-14").unwrap();
+    assert!(rule.matches(date(2026, 3, 14)));
+    assert!(!rule.matches(date(2027, 3, 14)));
+}
+
+#[test]
+fn test_easter_offsets() {
+    let good_friday = DateRule::parse("easter-2").unwrap();
+    let easter_monday = DateRule::parse("Easter+1").unwrap();
+    assert!(good_friday.matches(date(2025, 4, 18)));
+    assert!(easter_monday.matches(date(2025, 4, 21)));
+    assert!(DateRule::parse("easter")
+        .unwrap()
+        .matches(date(2025, 4, 20)));
+}
+
+#[test]
+fn test_special_date_rule_validation() {
+    assert!(is_valid_special_date_rule("02-29").is_ok());
+    assert!(is_valid_special_date_rule(" easter+39 ").is_ok());
+    assert!(is_valid_special_date_rule("13-01").is_err());
+    assert!(is_valid_special_date_rule("christmas").is_err());
+    assert!(is_valid_special_date_rule("easter+x").is_err());
+}
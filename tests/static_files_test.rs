@@ -0,0 +1,85 @@
+#![cfg(all(feature = "web", unix))]
+
+use pi_inky_weather_epd::web_server::{etag_matches, router, serve_unix};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+const ICON: &str = "/static/experimental/clear-day-red.svg";
+
+async fn get(name: &str, path: &str, headers: &str) -> String {
+    let socket = std::env::temp_dir().join(format!(
+        "pi-inky-weather-static-{name}-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket);
+
+    let listener = UnixListener::bind(&socket).unwrap();
+    tokio::spawn(serve_unix(listener, router()));
+
+    let mut stream = UnixStream::connect(&socket).await.unwrap();
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n{headers}Connection: close\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    std::fs::remove_file(&socket).unwrap();
+    response
+}
+
+/// Value of the `name` header of a response
+fn header<'a>(response: &'a str, name: &str) -> Option<&'a str> {
+    response.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+#[test]
+fn test_if_none_match_compares_weakly() {
+    assert!(etag_matches(r#"W/"1-2""#, r#"W/"1-2""#));
+    assert!(etag_matches(r#""1-2""#, r#"W/"1-2""#));
+    assert!(etag_matches(r#""0-0", W/"1-2""#, r#"W/"1-2""#));
+    assert!(etag_matches("*", r#"W/"1-2""#));
+    assert!(!etag_matches(r#"W/"1-3""#, r#"W/"1-2""#));
+}
+
+#[tokio::test]
+async fn test_static_file_has_an_etag_and_cache_headers() {
+    let response = get("etag", ICON, "").await;
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    let etag = header(&response, "etag").expect("no ETag");
+    assert!(etag.starts_with(r#"W/""#), "{etag}");
+    assert!(header(&response, "last-modified").is_some());
+    assert_eq!(
+        header(&response, "cache-control"),
+        Some("public, max-age=86400")
+    );
+}
+
+#[tokio::test]
+async fn test_matching_etag_is_not_modified() {
+    let response = get("first", ICON, "").await;
+    let etag = header(&response, "etag").expect("no ETag");
+
+    let cached = get("cached", ICON, &format!("If-None-Match: {etag}\r\n")).await;
+    assert!(cached.starts_with("HTTP/1.1 304"), "{cached}");
+    assert_eq!(header(&cached, "etag"), Some(etag));
+    assert!(!cached.contains("<svg"));
+
+    // A stale ETag gets the file even when it wasn't modified since the given date
+    let stale = get(
+        "stale",
+        ICON,
+        &format!(
+            "If-None-Match: W/\"0-0\"\r\nIf-Modified-Since: {}\r\n",
+            header(&response, "last-modified").unwrap()
+        ),
+    )
+    .await;
+    assert!(stale.starts_with("HTTP/1.1 200"), "{stale}");
+}
@@ -0,0 +1,61 @@
+use chrono::{Duration, NaiveDate, NaiveTime, Timelike};
+use pi_inky_weather_epd::weather::solar::{format_day_length, solar_noon, sun_times};
+
+fn minutes_of_day(time: NaiveTime) -> i64 {
+    (time.hour() * 60 + time.minute()) as i64
+}
+
+#[test]
+fn test_solar_noon_at_greenwich_follows_equation_of_time() {
+    // Mid-February the sun is ~14 minutes "slow", early November ~16 minutes "fast"
+    let february = solar_noon(NaiveDate::from_ymd_opt(2025, 2, 11).unwrap(), 0.0).unwrap();
+    let november = solar_noon(NaiveDate::from_ymd_opt(2025, 11, 3).unwrap(), 0.0).unwrap();
+
+    assert!((minutes_of_day(february.time()) - (12 * 60 + 14)).abs() <= 1);
+    assert!((minutes_of_day(november.time()) - (11 * 60 + 44)).abs() <= 1);
+}
+
+#[test]
+fn test_solar_noon_shifts_with_longitude() {
+    // Melbourne (144.96E) reaches solar noon around 02:22 UTC at the winter solstice
+    let melbourne = solar_noon(NaiveDate::from_ymd_opt(2025, 6, 21).unwrap(), 144.9631).unwrap();
+    assert!((minutes_of_day(melbourne.time()) - (2 * 60 + 22)).abs() <= 1);
+}
+
+#[test]
+fn test_sun_times_match_published_times() {
+    // London at the March equinox: sunrise 06:03 UTC, sunset 18:13 UTC
+    let (sunrise, sunset) = sun_times(
+        NaiveDate::from_ymd_opt(2025, 3, 20).unwrap(),
+        51.5074,
+        -0.1278,
+    )
+    .unwrap();
+    assert!((minutes_of_day(sunrise.time()) - (6 * 60 + 3)).abs() <= 3);
+    assert!((minutes_of_day(sunset.time()) - (18 * 60 + 13)).abs() <= 3);
+
+    // Melbourne at the winter solstice: sunrise 07:35 AEST (21:35 UTC the day before), sunset
+    // 17:08 AEST (07:08 UTC)
+    let (sunrise, sunset) = sun_times(
+        NaiveDate::from_ymd_opt(2025, 6, 21).unwrap(),
+        -37.8136,
+        144.9631,
+    )
+    .unwrap();
+    assert!((minutes_of_day(sunrise.time()) - (21 * 60 + 35)).abs() <= 2);
+    assert!((minutes_of_day(sunset.time()) - (7 * 60 + 8)).abs() <= 2);
+}
+
+#[test]
+fn test_sun_times_none_in_polar_day_and_night() {
+    let solstice = NaiveDate::from_ymd_opt(2025, 6, 21).unwrap();
+    assert!(sun_times(solstice, 69.6492, 18.9553).is_none());
+    assert!(sun_times(solstice, -77.8463, 166.6682).is_none());
+}
+
+#[test]
+fn test_format_day_length() {
+    assert_eq!(format_day_length(Duration::minutes(14 * 60 + 5)), "14h 05m");
+    assert_eq!(format_day_length(Duration::minutes(9 * 60 + 32)), "9h 32m");
+    assert_eq!(format_day_length(Duration::minutes(-5)), "0h 00m");
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use pi_inky_weather_epd::configs::settings::{Providers, Telemetry};
+use pi_inky_weather_epd::display_options::DisplayDriver;
+use pi_inky_weather_epd::telemetry::{send_ping, TelemetryPing};
+use pi_inky_weather_epd::CONFIG;
+use url::Url;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn ping() -> TelemetryPing {
+    TelemetryPing {
+        version: "1.2.3".to_string(),
+        arch: "aarch64".to_string(),
+        provider: Providers::OpenMeteo,
+        display_driver: DisplayDriver::Acep,
+        display_resolution: Some("800x480".to_string()),
+        render_duration_ms: 1500,
+    }
+}
+
+fn settings(enabled: bool, dry_run: bool, url: String) -> Telemetry {
+    Telemetry {
+        enabled,
+        url: Some(Url::parse(&url).unwrap()),
+        dry_run,
+    }
+}
+
+#[test]
+fn test_telemetry_is_off_by_default() {
+    assert!(!CONFIG.telemetry.enabled);
+    assert!(!CONFIG.telemetry.dry_run);
+}
+
+#[test]
+fn test_ping_sends_nothing_about_the_location() {
+    let ping = TelemetryPing::new(&CONFIG, Duration::from_millis(1500));
+
+    let json: serde_json::Value = serde_json::from_str(&ping.to_json()).unwrap();
+    let mut keys: Vec<&str> = json
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        [
+            "arch",
+            "display_driver",
+            "display_resolution",
+            "provider",
+            "render_duration_ms",
+            "version"
+        ]
+    );
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["render_duration_ms"], 1500);
+}
+
+#[test]
+fn test_ping_names_provider_and_display() {
+    let json = ping().to_json();
+
+    assert!(json.contains(r#""provider": "open_meteo""#), "{json}");
+    assert!(json.contains(r#""display_driver": "acep""#), "{json}");
+    assert!(
+        json.contains(r#""display_resolution": "800x480""#),
+        "{json}"
+    );
+}
+
+#[tokio::test]
+async fn test_enabled_telemetry_posts_the_ping() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/ping"))
+        .and(body_json(serde_json::to_value(ping()).unwrap()))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    send_ping(
+        &settings(true, false, format!("{}/ping", server.uri())),
+        &ping(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_disabled_telemetry_sends_nothing() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    send_ping(&settings(false, false, server.uri()), &ping()).await;
+}
+
+#[tokio::test]
+async fn test_dry_run_only_logs_the_ping() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    send_ping(&settings(true, true, server.uri()), &ping()).await;
+}
+
+#[tokio::test]
+async fn test_failed_ping_does_not_fail_the_run() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    // Only logged, nothing to return
+    send_ping(&settings(true, false, server.uri()), &ping()).await;
+}
@@ -0,0 +1,46 @@
+mod helpers;
+
+use chrono::{Days, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::{
+    clock::{Clock, FixedClock},
+    dashboard::context::ContextBuilder,
+    domain::models::{DailyForecast, Temperature},
+};
+
+fn day(offset: u64, clock: &FixedClock, temp_max: Option<Temperature>) -> DailyForecast {
+    let date = clock.now_local().date_naive() + Days::new(offset);
+    DailyForecast {
+        temp_max,
+        temp_min: Some(Temperature::celsius(5.0)),
+        precipitation: None,
+        cloud_cover: None,
+        ..forecast::day(date)
+    }
+}
+
+#[test]
+fn test_daily_tiles_are_coloured_by_max_temperature() {
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 1, 7, 12, 0, 0).unwrap());
+    let forecast = vec![
+        day(0, &clock, Some(Temperature::celsius(22.0))),
+        day(1, &clock, Some(Temperature::celsius(10.0))),
+        day(2, &clock, Some(Temperature::celsius(20.0))),
+        day(3, &clock, Some(Temperature::celsius(35.0))),
+        day(4, &clock, Some(Temperature::fahrenheit(95.0))),
+        day(5, &clock, None),
+    ];
+
+    let mut builder = ContextBuilder::new();
+    builder.with_daily_forecast_data(forecast, &clock);
+    let context = &builder.context;
+
+    // Default bands: cold below 15°C in blue, hot above 30°C in red
+    assert_eq!(context.day2_temp_colour, "blue");
+    assert_eq!(context.day3_temp_colour, context.day3_text_colour);
+    assert_eq!(context.day4_temp_colour, "red");
+    assert_eq!(context.day5_temp_colour, "red");
+    // No max temperature, or no forecast at all for the last day
+    assert_eq!(context.day6_temp_colour, context.day6_text_colour);
+    assert_eq!(context.day7_temp_colour, context.day7_text_colour);
+}
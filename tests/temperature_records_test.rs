@@ -0,0 +1,171 @@
+mod helpers;
+
+use chrono::{Duration, NaiveDate};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::configs::settings::{ColourOverrides, TemperatureRecords};
+use pi_inky_weather_epd::dashboard::panel::PanelContent;
+use pi_inky_weather_epd::dashboard::temperature_records::{
+    temperature_record, temperature_record_panel, today_history, TemperatureRecord,
+};
+use pi_inky_weather_epd::domain::models::{DailyForecast, Temperature};
+use pi_inky_weather_epd::history::{DayHistory, History, HISTORY_RETENTION_DAYS};
+use pi_inky_weather_epd::CONFIG;
+
+fn settings() -> TemperatureRecords {
+    TemperatureRecords {
+        enabled: true,
+        x: 620.0,
+        y: 145.0,
+        width: 170.0,
+        height: 24.0,
+        min_history_days: 10,
+    }
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, 20).unwrap()
+}
+
+fn day(temp_max: f32, temp_min: f32) -> DayHistory {
+    DayHistory {
+        temp_max: Some(temp_max),
+        temp_min: Some(temp_min),
+    }
+}
+
+/// 20 days before today with highs of 21-40 °C and lows of 1-20 °C
+fn history() -> History {
+    let mut history = History::default();
+    for offset in 1..=20 {
+        history.record(
+            today() - Duration::days(offset),
+            day(20.0 + offset as f32, offset as f32),
+        );
+    }
+    history
+}
+
+#[test]
+fn test_hottest_forecast_is_a_record() {
+    let record = temperature_record(&history(), today(), &day(41.0, 15.0), 10);
+
+    assert_eq!(record, Some(TemperatureRecord::RecordHeat));
+}
+
+#[test]
+fn test_top_decile_without_beating_the_record() {
+    // Only 40 °C and 39 °C are at least as hot, 2 of 20 days
+    let heat = temperature_record(&history(), today(), &day(38.5, 15.0), 10);
+    // 1 °C and 2 °C are at least as cold
+    let cold = temperature_record(&history(), today(), &day(30.0, 2.0), 10);
+
+    assert_eq!(heat, Some(TemperatureRecord::TopDecileHeat));
+    assert_eq!(cold, Some(TemperatureRecord::TopDecileCold));
+}
+
+#[test]
+fn test_equalling_the_record_is_not_a_record() {
+    let record = temperature_record(&history(), today(), &day(40.0, 15.0), 10);
+
+    assert_eq!(record, Some(TemperatureRecord::TopDecileHeat));
+}
+
+#[test]
+fn test_heat_is_shown_before_cold() {
+    let record = temperature_record(&history(), today(), &day(41.0, 0.0), 10);
+
+    assert_eq!(record, Some(TemperatureRecord::RecordHeat));
+}
+
+#[test]
+fn test_ordinary_day_or_short_history_has_no_badge() {
+    assert_eq!(
+        temperature_record(&history(), today(), &day(30.0, 10.0), 10),
+        None
+    );
+    assert_eq!(
+        temperature_record(&history(), today(), &day(41.0, 0.0), 21),
+        None
+    );
+}
+
+#[test]
+fn test_today_and_later_days_are_left_out_of_the_comparison() {
+    let mut history = history();
+    history.record(today(), day(41.0, 15.0));
+    history.record(today() + Duration::days(1), day(45.0, 15.0));
+
+    let record = temperature_record(&history, today(), &day(41.0, 15.0), 10);
+
+    assert_eq!(record, Some(TemperatureRecord::RecordHeat));
+}
+
+#[test]
+fn test_history_drops_days_past_the_retention() {
+    let mut history = History::default();
+    history.record(
+        today() - Duration::days(HISTORY_RETENTION_DAYS + 1),
+        day(30.0, 10.0),
+    );
+    history.record(today(), day(30.0, 10.0));
+
+    assert_eq!(history.days.len(), 1);
+}
+
+#[test]
+fn test_history_round_trips_through_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("profiles/home/history.json");
+
+    assert_eq!(History::load(&path), History::default());
+    history().save(&path).unwrap();
+    assert_eq!(History::load(&path), history());
+
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(History::load(&path), History::default());
+}
+
+#[test]
+fn test_today_is_stored_in_celsius() {
+    let daily = vec![DailyForecast {
+        temp_max: Some(Temperature::fahrenheit(104.0)),
+        temp_min: Some(Temperature::fahrenheit(50.0)),
+        ..forecast::day(today())
+    }];
+
+    assert_eq!(today_history(&daily, today()), Some(day(40.0, 10.0)));
+    assert_eq!(today_history(&daily, today() + Duration::days(1)), None);
+}
+
+#[test]
+fn test_badge_shows_the_record() {
+    let panel =
+        temperature_record_panel(&settings(), TemperatureRecord::RecordHeat, &CONFIG.colours);
+
+    assert_eq!(
+        (panel.x, panel.y, panel.width, panel.height),
+        (620.0, 145.0, 170.0, 24.0)
+    );
+    let PanelContent::Svg(fragment) = &panel.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(">Record heat<"));
+}
+
+#[test]
+fn test_badge_takes_the_themed_colours() {
+    let overrides: ColourOverrides = serde_json::from_value(serde_json::json!({
+        "actual_temp_colour": "orange",
+        "background_colour": "yellow"
+    }))
+    .unwrap();
+    let colours = CONFIG.colours.with_overrides(&overrides);
+
+    let panel = temperature_record_panel(&settings(), TemperatureRecord::RecordHeat, &colours);
+
+    let PanelContent::Svg(fragment) = &panel.content else {
+        panic!("expected an SVG panel");
+    };
+    assert!(fragment.contains(r#"fill="orange""#), "{fragment}");
+    assert!(fragment.contains(r#"fill="yellow">Record heat<"#));
+}
@@ -0,0 +1,88 @@
+mod helpers;
+
+use chrono::{Duration, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::apis::open_meteo::models::OpenMeteoEnsembleResponse;
+use pi_inky_weather_epd::configs::settings::TemperatureUnit;
+use pi_inky_weather_epd::dashboard::chart::HourlyForecastGraph;
+use pi_inky_weather_epd::domain::models::{HourlyForecast, Temperature};
+
+const ENSEMBLE_JSON: &str = r#"{
+    "latitude": -37.8,
+    "longitude": 144.9,
+    "hourly": {
+        "time": ["2025-10-01T00:00", "2025-10-01T01:00", "2025-10-01T02:00"],
+        "temperature_2m": [14.0, 15.0, null],
+        "temperature_2m_member01": [12.5, 16.0, null],
+        "temperature_2m_member02": [15.5, 14.0, null]
+    }
+}"#;
+
+fn hour(offset: i64) -> HourlyForecast {
+    HourlyForecast {
+        temperature: Temperature::celsius(14.0),
+        apparent_temperature: Temperature::celsius(13.0),
+        ..forecast::hour(
+            Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap() + Duration::hours(offset),
+        )
+    }
+}
+
+#[test]
+fn test_ensemble_spread_over_all_members() {
+    let ensemble: OpenMeteoEnsembleResponse = serde_json::from_str(ENSEMBLE_JSON).unwrap();
+
+    assert_eq!(ensemble.spread_at(0), Some((12.5, 15.5)));
+    assert_eq!(ensemble.spread_at(1), Some((14.0, 16.0)));
+    assert_eq!(ensemble.spread_at(2), None);
+}
+
+#[test]
+fn test_ensemble_merge_converts_unit() {
+    let ensemble: OpenMeteoEnsembleResponse = serde_json::from_str(ENSEMBLE_JSON).unwrap();
+    let mut hourly: Vec<HourlyForecast> = (0..4).map(hour).collect();
+
+    ensemble.merge_into(&mut hourly, TemperatureUnit::F);
+
+    let (lower, upper) = hourly[1].temperature_spread.unwrap();
+    assert_eq!(lower, Temperature::fahrenheit(57.2));
+    assert_eq!(upper, Temperature::fahrenheit(60.8));
+    assert!(hourly[2].temperature_spread.is_none());
+    assert!(hourly[3].temperature_spread.is_none());
+}
+
+#[test]
+fn test_temperature_band_extends_bounds_and_splits_gaps() {
+    let temperatures = [10.0; 24];
+    let mut band = vec![Some((8.0, 12.0)); 24];
+    band[10] = None;
+
+    let mut graph = HourlyForecastGraph::builder()
+        .size(230.0, 100.0)
+        .actual_temperature(&temperatures)
+        .feels_like(&temperatures)
+        .rain_chance(&[0.0; 24])
+        .temperature_band(&band)
+        .build();
+    graph.draw_graph().unwrap();
+
+    assert_eq!(graph.min_y, 8.0);
+    assert_eq!(graph.max_y, 12.0);
+
+    let path = graph.draw_temperature_band();
+    assert_eq!(path.matches('M').count(), 2);
+    assert!(path.starts_with("M 0.0000 100.0000"));
+    assert!(path.contains("L 90.0000 0.0000"));
+}
+
+#[test]
+fn test_temperature_band_empty_without_ensemble() {
+    let mut graph = HourlyForecastGraph::builder()
+        .actual_temperature(&[10.0; 24])
+        .feels_like(&[10.0; 24])
+        .rain_chance(&[0.0; 24])
+        .build();
+    graph.draw_graph().unwrap();
+
+    assert_eq!(graph.draw_temperature_band(), "");
+}
@@ -0,0 +1,64 @@
+use pi_inky_weather_epd::dashboard::template_filters::{localize_time, register_filters};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+fn render(template: &str, context: serde_json::Value) -> String {
+    let mut tt = TinyTemplate::new();
+    tt.add_template("test", template).unwrap();
+    tt.set_default_formatter(&format_unescaped);
+    register_filters(&mut tt);
+    tt.render("test", &context).unwrap()
+}
+
+#[test]
+fn test_round_formats_numbers_and_numeric_strings() {
+    let context = json!({ "temp": "23.46", "rain": 2.04, "missing": "NA" });
+    assert_eq!(render("{temp | round}", context.clone()), "23");
+    assert_eq!(render("{temp | round1}", context.clone()), "23.5");
+    assert_eq!(render("{rain | round1}", context.clone()), "2.0");
+    assert_eq!(render("{missing | round}", context), "NA");
+}
+
+#[test]
+fn test_case_formatters() {
+    let context = json!({ "day": "Tuesday" });
+    assert_eq!(
+        render("{day | upper} {day | lower}", context),
+        "TUESDAY tuesday"
+    );
+}
+
+#[test]
+fn test_localize_time_uses_the_time_format() {
+    assert_eq!(localize_time("06:42", "%-I:%M %p"), "6:42 AM");
+    assert_eq!(localize_time("18:05:30", "%H.%M"), "18.05");
+    assert_eq!(localize_time("NA", "%H:%M"), "NA");
+    assert_eq!(
+        render("{sunset | localize_time}", json!({ "sunset": "19:58" })),
+        localize_time("19:58", &CONFIG.render_options.time_format)
+    );
+}
+
+#[test]
+fn test_localize_time_keeps_the_time_when_the_format_needs_a_date() {
+    assert_eq!(localize_time("06:42", "%d/%m %H:%M"), "06:42");
+    assert_eq!(localize_time("06:42", "%Q"), "06:42");
+}
+
+#[test]
+fn test_icon_resolves_names_to_paths() {
+    let path = render("{name | icon}", json!({ "name": "clear-day" }));
+    assert_eq!(
+        path,
+        CONFIG
+            .misc
+            .svg_icons_directory
+            .join("clear-day.svg")
+            .to_string_lossy()
+    );
+    assert_eq!(
+        render("{name | icon}", json!({ "name": "clear-day.svg" })),
+        path
+    );
+}
@@ -16,3 +16,16 @@ fn base_template_svg_ok() {
         "The base template file is not a valid SVG"
     );
 }
+
+/// Validates the low power layout served to displays with a low battery
+#[test]
+fn low_battery_template_svg_ok() {
+    let svg_content = fs::read_to_string(&CONFIG.web_server.low_battery_template_path)
+        .expect("Failed to read the low battery template SVG file");
+    let svg_tree = usvg::Tree::from_str(&svg_content, &usvg::Options::default());
+
+    assert!(
+        svg_tree.is_ok(),
+        "The low battery template file is not a valid SVG"
+    );
+}
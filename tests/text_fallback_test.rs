@@ -0,0 +1,71 @@
+mod helpers;
+
+use chrono::{Days, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::{
+    clock::{Clock, FixedClock},
+    dashboard::{
+        context::ContextBuilder, panel::DEFAULT_FRAME_SIZE, text_fallback::render_text_dashboard,
+    },
+    domain::models::{DailyForecast, Temperature},
+};
+
+fn context_builder() -> ContextBuilder<'static> {
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 1, 7, 12, 0, 0).unwrap());
+    let today = clock.now_local().date_naive();
+    let daily = (0..7)
+        .map(|offset| DailyForecast {
+            temp_max: Some(Temperature::celsius(20.0 + offset as f32)),
+            ..forecast::day(today + Days::new(offset))
+        })
+        .collect();
+
+    let mut builder = ContextBuilder::new();
+    builder.with_daily_forecast_data(daily, &clock);
+    builder
+}
+
+#[test]
+fn test_text_dashboard_is_valid_svg_without_images() {
+    let builder = context_builder();
+    let svg = render_text_dashboard(
+        &builder.context,
+        "configuration error: No such file",
+        DEFAULT_FRAME_SIZE,
+    );
+
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+    assert!(!svg.contains("<image"));
+    assert!(svg.contains(&format!(">{}<", builder.context.day2_name)));
+    assert!(svg.contains(">21°<"));
+    assert!(svg.contains(">26°<"));
+    assert!(svg.contains("Text-only dashboard: configuration error: No such file"));
+}
+
+#[test]
+fn test_text_dashboard_escapes_and_shows_the_diagnostic() {
+    let mut builder = context_builder();
+    builder.with_warning(pi_inky_weather_epd::errors::DashboardError::NoInternet {
+        details: "timeout".to_string(),
+    });
+    let svg = render_text_dashboard(
+        &builder.context,
+        "failed to render: unexpected <token> & more",
+        DEFAULT_FRAME_SIZE,
+    );
+
+    assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+    assert!(svg.contains("unexpected &lt;token&gt; &amp; more"));
+    assert_eq!(builder.context.diagnostic_visibility, "visible");
+    assert!(svg.contains(r#"fill="red""#));
+}
+
+#[test]
+fn test_text_dashboard_fills_the_frame_of_the_display() {
+    let builder = context_builder();
+    let svg = render_text_dashboard(&builder.context, "template missing", (600.0, 448.0));
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    assert_eq!((tree.size().width(), tree.size().height()), (600.0, 448.0));
+    assert!(svg.contains(r#"<g transform="translate(0 44) scale(0.75)">"#));
+}
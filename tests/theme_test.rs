@@ -0,0 +1,156 @@
+mod helpers;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use helpers::test_utils::forecast;
+use pi_inky_weather_epd::configs::settings::ThemeRule;
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+use pi_inky_weather_epd::dashboard::theme::{rule_matches, themed_colours, ThemeWeather};
+use pi_inky_weather_epd::domain::conditions::Condition;
+use pi_inky_weather_epd::domain::models::{
+    DailyForecast, HourlyForecast, Precipitation, Temperature,
+};
+use pi_inky_weather_epd::CONFIG;
+use serde_json::json;
+
+fn rules() -> Vec<ThemeRule> {
+    serde_json::from_value(json!([
+        {
+            "conditions": ["rain", "drizzle"],
+            "colours": { "x_axis_colour": "blue", "actual_temp_colour": "blue" }
+        },
+        {
+            "min_temperature": 35.0,
+            "colours": { "actual_temp_colour": "red", "background_colour": "yellow" }
+        },
+        {
+            "max_temperature": 5.0,
+            "colours": { "humidity_dry_colour": "white" }
+        }
+    ]))
+    .unwrap()
+}
+
+fn weather(condition: Condition, temp_max: f32) -> ThemeWeather {
+    ThemeWeather {
+        condition: Some(condition),
+        temp_max: Some(temp_max),
+    }
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 10, 3, 30, 0).unwrap()
+}
+
+fn hour(time: DateTime<Utc>, rain: u16) -> HourlyForecast {
+    HourlyForecast {
+        precipitation: Precipitation::new(Some(90), Some(rain), Some(rain)),
+        cloud_cover: Some(100),
+        ..forecast::hour(time)
+    }
+}
+
+#[test]
+fn test_rules_match_conditions_and_temperature_ranges() {
+    let rules = rules();
+
+    assert!(rule_matches(&rules[0], &weather(Condition::Rain, 20.0)));
+    assert!(!rule_matches(&rules[0], &weather(Condition::Clear, 20.0)));
+    assert!(rule_matches(&rules[1], &weather(Condition::Clear, 35.0)));
+    assert!(!rule_matches(&rules[1], &weather(Condition::Clear, 34.9)));
+    assert!(rule_matches(&rules[2], &weather(Condition::Cloudy, 4.0)));
+    assert!(!rule_matches(&rules[2], &weather(Condition::Cloudy, 5.0)));
+    assert!(!rule_matches(&rules[1], &ThemeWeather::default()));
+}
+
+#[test]
+fn test_first_matching_rule_wins() {
+    // Rain on a hot day, the rain rule is listed first
+    let colours = themed_colours(&CONFIG.colours, &rules(), &weather(Condition::Rain, 38.0));
+
+    assert_eq!(colours.actual_temp_colour.to_string(), "blue");
+    assert_eq!(colours.x_axis_colour.to_string(), "blue");
+    assert_eq!(
+        colours.background_colour, CONFIG.colours.background_colour,
+        "colours the rule leaves out are kept"
+    );
+}
+
+#[test]
+fn test_no_matching_rule_keeps_the_configured_colours() {
+    let colours = themed_colours(&CONFIG.colours, &rules(), &weather(Condition::Clear, 20.0));
+
+    assert_eq!(
+        colours.actual_temp_colour,
+        CONFIG.colours.actual_temp_colour
+    );
+    assert_eq!(
+        colours.humidity_dry_colour,
+        CONFIG.colours.humidity_dry_colour
+    );
+}
+
+#[test]
+fn test_rule_overrides_the_humidity_and_daily_tile_colours() {
+    let rules: Vec<ThemeRule> = serde_json::from_value(json!([{
+        "colours": {
+            "humidity_colour": "green",
+            "humidity_dry_colour": "yellow",
+            "humidity_humid_colour": "blue",
+            "cold_temp_colour": "green",
+            "hot_temp_colour": "orange"
+        }
+    }]))
+    .unwrap();
+
+    let colours = themed_colours(&CONFIG.colours, &rules, &weather(Condition::Clear, 20.0));
+
+    assert_eq!(colours.humidity_colour.to_string(), "green");
+    assert_eq!(colours.humidity_dry_colour.to_string(), "yellow");
+    assert_eq!(colours.humidity_humid_colour.to_string(), "blue");
+    assert_eq!(colours.cold_temp_colour.to_string(), "green");
+    assert_eq!(colours.hot_temp_colour.to_string(), "orange");
+}
+
+#[test]
+fn test_unknown_condition_or_colour_is_rejected() {
+    assert!(serde_json::from_value::<ThemeRule>(json!({
+        "conditions": ["raining cats"],
+        "colours": {}
+    }))
+    .is_err());
+    assert!(serde_json::from_value::<ThemeRule>(json!({
+        "colours": { "accent_colour": "red" }
+    }))
+    .is_err());
+}
+
+#[test]
+fn test_weather_uses_the_current_hour_and_today_high() {
+    let today = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+    let hourly = vec![
+        hour(Utc.with_ymd_and_hms(2025, 1, 10, 2, 0, 0).unwrap(), 0),
+        hour(Utc.with_ymd_and_hms(2025, 1, 10, 3, 0, 0).unwrap(), 5),
+        hour(Utc.with_ymd_and_hms(2025, 1, 10, 4, 0, 0).unwrap(), 0),
+    ];
+    let daily = vec![DailyForecast {
+        temp_max: Some(Temperature::celsius(36.0)),
+        temp_min: Some(Temperature::celsius(21.0)),
+        ..forecast::day(today)
+    }];
+
+    assert_eq!(
+        ThemeWeather::at(&daily, &hourly, now(), today),
+        weather(Condition::Rain, 36.0)
+    );
+}
+
+#[test]
+fn test_themed_colours_reach_the_daily_tiles() {
+    let colours = themed_colours(&CONFIG.colours, &rules(), &weather(Condition::Clear, 40.0));
+    let mut builder = ContextBuilder::new();
+    builder.with_colours(colours);
+
+    assert_eq!(builder.context.background_colour, "yellow");
+    assert_eq!(builder.context.day2_background_colour, "yellow");
+    assert_eq!(builder.context.actual_temp_colour, "red");
+}
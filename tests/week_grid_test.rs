@@ -0,0 +1,89 @@
+use chrono::{Datelike, NaiveDate, TimeZone, Utc, Weekday};
+use pi_inky_weather_epd::calendar::{week_column, week_days, week_number_from_jan1, week_start};
+use pi_inky_weather_epd::clock::{Clock, FixedClock};
+use pi_inky_weather_epd::configs::settings::WeekNumbering;
+use pi_inky_weather_epd::dashboard::context::ContextBuilder;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_week_column_follows_the_first_weekday() {
+    // 2026-01-07 is a Wednesday
+    let wednesday = date(2026, 1, 7);
+    assert_eq!(week_column(wednesday, Weekday::Mon), 2);
+    assert_eq!(week_column(wednesday, Weekday::Sun), 3);
+    assert_eq!(week_column(wednesday, Weekday::Sat), 4);
+    assert_eq!(week_column(wednesday, Weekday::Wed), 0);
+
+    assert_eq!(week_start(wednesday, Weekday::Mon), date(2026, 1, 5));
+    assert_eq!(week_start(wednesday, Weekday::Sun), date(2026, 1, 4));
+}
+
+#[test]
+fn test_week_days_start_on_the_first_weekday() {
+    assert_eq!(
+        week_days(Weekday::Sun),
+        [
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat
+        ]
+    );
+}
+
+#[test]
+fn test_iso_week_numbers() {
+    // 2026-01-01 is a Thursday, so its week is week 1
+    assert_eq!(
+        WeekNumbering::Iso.week_number(date(2026, 1, 1), Weekday::Sun),
+        1
+    );
+    // 2027-01-01 is a Friday, still in week 53 of 2026
+    assert_eq!(
+        WeekNumbering::Iso.week_number(date(2027, 1, 1), Weekday::Mon),
+        53
+    );
+}
+
+#[test]
+fn test_week_numbers_from_jan1() {
+    // 2026-01-01 is a Thursday, the Sunday weeks turn on 4 January
+    assert_eq!(week_number_from_jan1(date(2026, 1, 1), Weekday::Sun), 1);
+    assert_eq!(week_number_from_jan1(date(2026, 1, 3), Weekday::Sun), 1);
+    assert_eq!(week_number_from_jan1(date(2026, 1, 4), Weekday::Sun), 2);
+    assert_eq!(week_number_from_jan1(date(2026, 12, 31), Weekday::Sun), 53);
+    assert_eq!(
+        WeekNumbering::Jan1.week_number(date(2026, 1, 5), Weekday::Mon),
+        2
+    );
+}
+
+#[test]
+fn test_context_places_the_days_in_week_rows() {
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2026, 1, 7, 12, 0, 0).unwrap());
+    let today = clock.now_local().date_naive();
+
+    let mut builder = ContextBuilder::new();
+    builder.with_daily_forecast_data(vec![], &clock);
+    let context = &builder.context;
+
+    // Default configuration, weeks start on Monday with ISO numbers
+    assert_eq!(context.weekday_header1, "Mon");
+    assert_eq!(context.weekday_header7, "Sun");
+    assert_eq!(context.week_number, "2");
+    assert_eq!(context.next_week_number, "3");
+
+    let column = today.weekday().num_days_from_monday();
+    assert_eq!(context.today_week_column, column.to_string());
+    assert_eq!(context.day2_week_column, ((column + 1) % 7).to_string());
+    assert_eq!(context.day7_week_column, ((column + 6) % 7).to_string());
+    // Six days on from a Wednesday or Thursday are in the next week
+    assert_eq!(context.day2_week_row, "0");
+    assert_eq!(context.day7_week_row, "1");
+}
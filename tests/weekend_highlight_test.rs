@@ -0,0 +1,94 @@
+use chrono::{TimeZone, Utc};
+use pi_inky_weather_epd::{
+    clock::FixedClock, configs::settings::DashboardSettings, dashboard::context::ContextBuilder,
+};
+
+/// The default and test configs with `highlight_weekend` and `weekend_days` replaced
+fn settings(highlight_weekend: bool, weekend_days: &[&str]) -> DashboardSettings {
+    config::Config::builder()
+        .add_source(config::File::with_name("config/default"))
+        .add_source(config::File::with_name("config/test"))
+        .set_override("render_options.highlight_weekend", highlight_weekend)
+        .unwrap()
+        .set_override("render_options.weekend_days", weekend_days.to_vec())
+        .unwrap()
+        .build()
+        .unwrap()
+        .try_deserialize()
+        .unwrap()
+}
+
+/// Background and text colours of the daily tiles from tomorrow, Thursday, to Tuesday
+fn tile_colours(settings: &DashboardSettings) -> Vec<(String, String)> {
+    // Wednesday in every time zone from UTC-10 to UTC+13
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap());
+    let mut builder = ContextBuilder::with_settings(settings);
+    builder.with_daily_forecast_data(Vec::new(), &clock);
+
+    let context = &builder.context;
+    vec![
+        (
+            context.day2_background_colour.clone(),
+            context.day2_text_colour.clone(),
+        ),
+        (
+            context.day3_background_colour.clone(),
+            context.day3_text_colour.clone(),
+        ),
+        (
+            context.day4_background_colour.clone(),
+            context.day4_text_colour.clone(),
+        ),
+        (
+            context.day5_background_colour.clone(),
+            context.day5_text_colour.clone(),
+        ),
+        (
+            context.day6_background_colour.clone(),
+            context.day6_text_colour.clone(),
+        ),
+        (
+            context.day7_background_colour.clone(),
+            context.day7_text_colour.clone(),
+        ),
+    ]
+}
+
+#[test]
+fn test_custom_weekend_days_are_highlighted() {
+    let settings = settings(true, &["Fri", "Sat"]);
+    let colours = &settings.colours;
+    let weekday = (
+        colours.background_colour.to_string(),
+        colours.text_colour.to_string(),
+    );
+    let weekend = (
+        colours.weekend_background_colour.to_string(),
+        colours.weekend_text_colour.to_string(),
+    );
+    assert_ne!(weekday, weekend);
+
+    assert_eq!(
+        tile_colours(&settings),
+        vec![
+            weekday.clone(), // Thu
+            weekend.clone(), // Fri
+            weekend,         // Sat
+            weekday.clone(), // Sun
+            weekday.clone(), // Mon
+            weekday,         // Tue
+        ]
+    );
+}
+
+#[test]
+fn test_weekend_is_not_highlighted_when_disabled() {
+    let settings = settings(false, &["Sat", "Sun"]);
+    let colours = &settings.colours;
+    let weekday = (
+        colours.background_colour.to_string(),
+        colours.text_colour.to_string(),
+    );
+
+    assert_eq!(tile_colours(&settings), vec![weekday; 6]);
+}
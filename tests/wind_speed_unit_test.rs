@@ -1,5 +1,7 @@
-use pi_inky_weather_epd::configs::settings::WindSpeedUnit;
+use pi_inky_weather_epd::configs::settings::{WindDisplay, WindSpeedUnit};
+use pi_inky_weather_epd::domain::icons::WindSpeed;
 use pi_inky_weather_epd::domain::models::Wind;
+use pi_inky_weather_epd::weather::icons::Icon;
 
 #[test]
 fn test_wind_speed_kmh_no_conversion() {
@@ -89,3 +91,53 @@ fn test_conversion_factors_accuracy() {
         );
     }
 }
+
+#[test]
+fn test_wind_display_shows_sustained_gust_or_both() {
+    let wind = Wind::new(18, 35);
+
+    assert_eq!(
+        wind.format_speed(WindDisplay::Sustained, WindSpeedUnit::KmH),
+        "18"
+    );
+    assert_eq!(
+        wind.format_speed(WindDisplay::Gust, WindSpeedUnit::KmH),
+        "35"
+    );
+    assert_eq!(
+        wind.format_speed(WindDisplay::Both, WindSpeedUnit::KmH),
+        "18 / 35"
+    );
+    // 18 km/h ≈ 11 mph, 35 km/h ≈ 22 mph
+    assert_eq!(
+        wind.format_speed(WindDisplay::Both, WindSpeedUnit::Mph),
+        "11 / 22"
+    );
+}
+
+#[test]
+fn test_gust_gets_its_own_icon() {
+    let wind = Wind::new(18, 45);
+
+    assert_eq!(WindSpeed(wind.speed_kmh).get_icon_name(), "wind.svg");
+    assert_eq!(
+        WindSpeed(wind.gust_speed_kmh).get_icon_name(),
+        "umbrella-wind-alt.svg"
+    );
+}
+
+#[test]
+fn test_use_gust_instead_of_wind_still_shows_the_gust() {
+    assert_eq!(
+        WindDisplay::Both.with_use_gust_instead_of_wind(Some(true)),
+        WindDisplay::Gust
+    );
+    assert_eq!(
+        WindDisplay::Both.with_use_gust_instead_of_wind(Some(false)),
+        WindDisplay::Both
+    );
+    assert_eq!(
+        WindDisplay::Sustained.with_use_gust_instead_of_wind(None),
+        WindDisplay::Sustained
+    );
+}